@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
 use crate::media::MediaAttachment;
+use crate::rich::RichMessage;
 
 // ── Channel approval types ──────────────────────────────────────
 
@@ -62,6 +63,10 @@ pub struct SendMessage {
     /// File attachments to send with the message.
     /// Channels that don't support attachments ignore this field.
     pub attachments: Vec<MediaAttachment>,
+    /// Interactive content (buttons, menus, forms) rendered natively by
+    /// channels that support it. `content` always carries the plain-text
+    /// fallback, so channels that don't support rich messages ignore this field.
+    pub rich: Option<RichMessage>,
 }
 
 impl SendMessage {
@@ -74,6 +79,7 @@ impl SendMessage {
             thread_ts: None,
             cancellation_token: None,
             attachments: vec![],
+            rich: None,
         }
     }
 
@@ -90,6 +96,7 @@ impl SendMessage {
             thread_ts: None,
             cancellation_token: None,
             attachments: vec![],
+            rich: None,
         }
     }
 
@@ -110,8 +117,39 @@ impl SendMessage {
         self.attachments = attachments;
         self
     }
+
+    /// Create an interactive message. `content` is set to the rich message's
+    /// numbered plain-text fallback.
+    pub fn rich(rich: RichMessage, recipient: impl Into<String>) -> Self {
+        let mut message = Self::new(rich.to_plain_text(), recipient);
+        message.rich = Some(rich);
+        message
+    }
+}
+
+/// Error returned from [`Channel::send`] when the platform rejected the
+/// request with a rate limit.
+///
+/// Channels wrap this in `anyhow::Error` so callers that only log keep
+/// working; delivery layers such as the outbound outbox downcast to it to
+/// honour the platform's `Retry-After` hint instead of their own backoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelRateLimited {
+    /// Delay requested by the platform, when it sent one.
+    pub retry_after: Option<std::time::Duration>,
+}
+
+impl std::fmt::Display for ChannelRateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.retry_after {
+            Some(delay) => write!(f, "rate limited (retry after {}s)", delay.as_secs()),
+            None => f.write_str("rate limited"),
+        }
+    }
 }
 
+impl std::error::Error for ChannelRateLimited {}
+
 /// Core channel trait — implement for any messaging platform
 #[async_trait]
 pub trait Channel: Send + Sync {
@@ -149,6 +187,11 @@ pub trait Channel: Send + Sync {
         false
     }
 
+    /// Whether `send` uploads local files named by `[DOCUMENT:<path>]` markers.
+    fn supports_document_upload(&self) -> bool {
+        false
+    }
+
     /// Minimum delay (ms) between sending each paragraph in multi-message mode.
     fn multi_message_delay_ms(&self) -> u64 {
         800
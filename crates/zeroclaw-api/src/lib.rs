@@ -21,6 +21,7 @@ pub mod memory_traits;
 pub mod observability_traits;
 pub mod peripherals_traits;
 pub mod provider;
+pub mod rich;
 pub mod runtime_traits;
 pub mod schema;
 pub mod tool;
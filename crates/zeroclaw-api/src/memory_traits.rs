@@ -27,10 +27,13 @@ pub struct ProceduralMessage {
 
 /// A single memory entry
 #[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct MemoryEntry {
     pub id: String,
     pub key: String,
     pub content: String,
+    /// `core`, `daily`, `conversation`, or a custom category name.
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub category: MemoryCategory,
     pub timestamp: String,
     pub session_id: Option<String>,
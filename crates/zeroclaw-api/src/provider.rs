@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 /// A single message in a conversation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
@@ -0,0 +1,925 @@
+//! Channel-agnostic rich message model.
+//!
+//! A [`RichMessage`] describes interactive content — headers, text sections,
+//! buttons, select menus and simple forms — without committing to any one
+//! platform's UI toolkit. Channels that support interactive UI (Slack Block
+//! Kit, Telegram inline keyboards, Discord components) render it natively;
+//! every other channel receives [`RichMessage::to_plain_text`], which lists
+//! the options as numbered text so the user can answer by replying `2` or
+//! the option label.
+//!
+//! Button clicks, menu selections and form submissions come back as
+//! [`RichInteraction`] events. Callers that want to wait for one (e.g. the
+//! `ask_user` and `poll` tools) call [`subscribe`] before sending; channels
+//! feed platform callbacks through [`dispatch`], which either hands the event
+//! to the subscriber or returns it so the channel can forward it to the agent
+//! as an ordinary inbound message.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Prefix marking platform callback identifiers that belong to a rich message.
+///
+/// Encoded identifiers look like `rich:<message_id>:<action>`, where the
+/// action is a choice index, `select` or `submit`. The whole string stays well
+/// under Telegram's 64-byte `callback_data` limit.
+pub const RICH_ACTION_PREFIX: &str = "rich:";
+
+/// Maximum number of sent rich messages remembered for callback resolution.
+const TRACKED_MESSAGES_MAX: usize = 256;
+
+// ── Message model ───────────────────────────────────────────────
+
+/// Interactive message composed of ordered blocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RichMessage {
+    /// Short unique identifier embedded in platform callback data.
+    pub id: String,
+    pub blocks: Vec<RichBlock>,
+}
+
+/// One visual block of a [`RichMessage`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RichBlock {
+    /// Bold title line.
+    Header { text: String },
+    /// Paragraph of Markdown text.
+    Section { text: String },
+    /// Horizontal separator.
+    Divider,
+    /// Row of buttons; each button is one selectable choice.
+    Buttons { buttons: Vec<RichButton> },
+    /// Dropdown menu; each option is one selectable choice.
+    Select {
+        placeholder: String,
+        options: Vec<RichOption>,
+    },
+    /// Input form submitted as a whole.
+    Form(RichForm),
+}
+
+/// Visual emphasis for a button. Channels without styling ignore it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ButtonStyle {
+    #[default]
+    Default,
+    Primary,
+    Danger,
+}
+
+/// Clickable button.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RichButton {
+    /// Caller-defined identifier reported back in [`RichInteraction::action_id`].
+    pub action_id: String,
+    pub label: String,
+    /// Value reported back on click. Defaults to the label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub style: ButtonStyle,
+}
+
+/// Option of a select menu.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RichOption {
+    pub label: String,
+    pub value: String,
+}
+
+/// Simple form with validated fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RichForm {
+    /// Caller-defined identifier reported back in [`RichInteraction::action_id`].
+    pub action_id: String,
+    pub fields: Vec<FormField>,
+    pub submit_label: String,
+}
+
+/// Input field of a [`RichForm`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    /// Key of the submitted value in [`RichInteraction::fields`].
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub kind: FormFieldKind,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+/// Accepted input for a [`FormField`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FormFieldKind {
+    #[default]
+    Text,
+    Number,
+    Email,
+    /// One of a fixed set of values (matched case-insensitively).
+    Choice {
+        options: Vec<String>,
+    },
+}
+
+/// A selectable choice flattened from the buttons and select menus of a
+/// message, in display order. The index is what the numbered text fallback
+/// and platform callback data refer to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RichChoice {
+    pub index: usize,
+    pub action_id: String,
+    pub label: String,
+    pub value: String,
+}
+
+/// Validation failure for a single form field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Outcome of matching a free-text reply against a rich message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplyResolution {
+    /// The reply picked a button or menu option.
+    Choice(RichChoice),
+    /// The reply filled in the message's form.
+    Form {
+        action_id: String,
+        fields: BTreeMap<String, String>,
+    },
+    /// The reply targeted the form but failed validation.
+    Invalid(Vec<FieldError>),
+    /// The reply did not match any choice and the message has no form.
+    Unmatched,
+}
+
+/// Structured inbound event produced by a button click, menu selection or
+/// form submission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RichInteraction {
+    /// [`RichMessage::id`] of the message interacted with.
+    pub message_id: String,
+    /// `action_id` of the clicked button, select menu option or form.
+    pub action_id: String,
+    /// Selected value (button/option value, or empty for forms).
+    pub value: String,
+    /// Submitted form values keyed by field id. Empty for choices.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    pub sender: String,
+    pub channel: String,
+}
+
+impl RichInteraction {
+    /// Render the interaction as the text a user would have typed, for
+    /// forwarding to the agent as an ordinary inbound message.
+    pub fn as_reply_text(&self) -> String {
+        if self.fields.is_empty() {
+            return self.value.clone();
+        }
+        self.fields
+            .iter()
+            .map(|(k, v)| format!("{k}: {v}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn next_message_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{millis:x}-{seq:x}")
+}
+
+impl Default for RichMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RichMessage {
+    /// Create an empty message with a fresh id.
+    pub fn new() -> Self {
+        Self {
+            id: next_message_id(),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn header(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(RichBlock::Header { text: text.into() });
+        self
+    }
+
+    pub fn section(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(RichBlock::Section { text: text.into() });
+        self
+    }
+
+    pub fn divider(mut self) -> Self {
+        self.blocks.push(RichBlock::Divider);
+        self
+    }
+
+    pub fn buttons(mut self, buttons: Vec<RichButton>) -> Self {
+        self.blocks.push(RichBlock::Buttons { buttons });
+        self
+    }
+
+    pub fn select(mut self, placeholder: impl Into<String>, options: Vec<RichOption>) -> Self {
+        self.blocks.push(RichBlock::Select {
+            placeholder: placeholder.into(),
+            options,
+        });
+        self
+    }
+
+    pub fn form(mut self, form: RichForm) -> Self {
+        self.blocks.push(RichBlock::Form(form));
+        self
+    }
+
+    /// All buttons and select options in display order.
+    pub fn choices(&self) -> Vec<RichChoice> {
+        let mut choices = Vec::new();
+        for block in &self.blocks {
+            match block {
+                RichBlock::Buttons { buttons } => {
+                    for button in buttons {
+                        choices.push(RichChoice {
+                            index: choices.len(),
+                            action_id: button.action_id.clone(),
+                            label: button.label.clone(),
+                            value: button.value.clone().unwrap_or_else(|| button.label.clone()),
+                        });
+                    }
+                }
+                RichBlock::Select { options, .. } => {
+                    for option in options {
+                        choices.push(RichChoice {
+                            index: choices.len(),
+                            action_id: option.value.clone(),
+                            label: option.label.clone(),
+                            value: option.value.clone(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        choices
+    }
+
+    /// The message's form, if any. Only the first form is interactive.
+    pub fn form_block(&self) -> Option<&RichForm> {
+        self.blocks.iter().find_map(|block| match block {
+            RichBlock::Form(form) => Some(form),
+            _ => None,
+        })
+    }
+
+    /// Index of the first choice contributed by block `block_index`.
+    pub fn first_choice_index(&self, block_index: usize) -> usize {
+        self.blocks
+            .iter()
+            .take(block_index)
+            .map(|block| match block {
+                RichBlock::Buttons { buttons } => buttons.len(),
+                RichBlock::Select { options, .. } => options.len(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Plain-text rendering for channels without interactive UI.
+    ///
+    /// Choices are numbered across the whole message; forms list their fields
+    /// with reply instructions.
+    pub fn to_plain_text(&self) -> String {
+        self.render_text(true)
+    }
+
+    /// Text of the non-interactive blocks, for channels that render buttons
+    /// and menus natively but show forms as text (e.g. Telegram, Discord).
+    pub fn body_text(&self) -> String {
+        self.render_text(false)
+    }
+
+    fn render_text(&self, include_choices: bool) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut next_index = 1;
+        let mut has_choices = false;
+        for block in &self.blocks {
+            match block {
+                RichBlock::Header { text } => lines.push(format!("**{text}**")),
+                RichBlock::Section { text } => lines.push(text.clone()),
+                RichBlock::Divider => lines.push("---".to_string()),
+                RichBlock::Buttons { .. } | RichBlock::Select { .. } if !include_choices => {}
+                RichBlock::Buttons { buttons } => {
+                    has_choices |= !buttons.is_empty();
+                    for button in buttons {
+                        lines.push(format!("{next_index}. {}", button.label));
+                        next_index += 1;
+                    }
+                }
+                RichBlock::Select {
+                    placeholder,
+                    options,
+                } => {
+                    has_choices |= !options.is_empty();
+                    if !placeholder.is_empty() {
+                        lines.push(format!("_{placeholder}_"));
+                    }
+                    for option in options {
+                        lines.push(format!("{next_index}. {}", option.label));
+                        next_index += 1;
+                    }
+                }
+                RichBlock::Form(form) => {
+                    for field in &form.fields {
+                        let required = if field.required { " (required)" } else { "" };
+                        let hint = match &field.kind {
+                            FormFieldKind::Text => String::new(),
+                            FormFieldKind::Number => " — number".to_string(),
+                            FormFieldKind::Email => " — email".to_string(),
+                            FormFieldKind::Choice { options } => {
+                                format!(" — one of: {}", options.join(", "))
+                            }
+                        };
+                        lines.push(format!("• {}{required}{hint}", field.label));
+                    }
+                    if form.fields.len() == 1 {
+                        lines.push("_Reply with your answer._".to_string());
+                    } else {
+                        lines.push("_Reply with one `field: value` line per field._".to_string());
+                    }
+                }
+            }
+        }
+        if has_choices {
+            lines.push("_Reply with a number or the option text._".to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// Match a free-text reply against the message's choices, then its form.
+    pub fn resolve_reply(&self, reply: &str) -> ReplyResolution {
+        let reply = reply.trim();
+        let choices = self.choices();
+        if let Ok(number) = reply.trim_end_matches('.').parse::<usize>()
+            && let Some(choice) = number.checked_sub(1).and_then(|i| choices.get(i))
+        {
+            return ReplyResolution::Choice(choice.clone());
+        }
+        if let Some(choice) = choices
+            .iter()
+            .find(|c| c.label.eq_ignore_ascii_case(reply) || c.value.eq_ignore_ascii_case(reply))
+        {
+            return ReplyResolution::Choice(choice.clone());
+        }
+        match self.form_block() {
+            Some(form) => match form.validate(&form.parse_text_reply(reply)) {
+                Ok(fields) => ReplyResolution::Form {
+                    action_id: form.action_id.clone(),
+                    fields,
+                },
+                Err(errors) => ReplyResolution::Invalid(errors),
+            },
+            None => ReplyResolution::Unmatched,
+        }
+    }
+}
+
+impl RichButton {
+    pub fn new(action_id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            action_id: action_id.into(),
+            label: label.into(),
+            value: None,
+            style: ButtonStyle::Default,
+        }
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn primary(mut self) -> Self {
+        self.style = ButtonStyle::Primary;
+        self
+    }
+
+    pub fn danger(mut self) -> Self {
+        self.style = ButtonStyle::Danger;
+        self
+    }
+}
+
+impl RichOption {
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl FormField {
+    pub fn new(id: impl Into<String>, label: impl Into<String>, kind: FormFieldKind) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            kind,
+            required: false,
+            max_length: None,
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    /// Validate a single trimmed value. Empty values are only checked for
+    /// `required`.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() {
+            return if self.required {
+                Err("is required".to_string())
+            } else {
+                Ok(())
+            };
+        }
+        if let Some(max) = self.max_length
+            && value.chars().count() > max
+        {
+            return Err(format!("must be at most {max} characters"));
+        }
+        match &self.kind {
+            FormFieldKind::Text => Ok(()),
+            FormFieldKind::Number => value
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(|_| ())
+                .ok_or_else(|| "must be a number".to_string()),
+            FormFieldKind::Email => {
+                let valid = value.split_once('@').is_some_and(|(local, domain)| {
+                    !local.is_empty()
+                        && domain.contains('.')
+                        && !domain.starts_with('.')
+                        && !domain.ends_with('.')
+                        && !value.contains(char::is_whitespace)
+                });
+                if valid {
+                    Ok(())
+                } else {
+                    Err("must be an email address".to_string())
+                }
+            }
+            FormFieldKind::Choice { options } => {
+                if options.iter().any(|o| o.eq_ignore_ascii_case(value)) {
+                    Ok(())
+                } else {
+                    Err(format!("must be one of: {}", options.join(", ")))
+                }
+            }
+        }
+    }
+}
+
+impl RichForm {
+    pub fn new(
+        action_id: impl Into<String>,
+        fields: Vec<FormField>,
+        submit_label: impl Into<String>,
+    ) -> Self {
+        Self {
+            action_id: action_id.into(),
+            fields,
+            submit_label: submit_label.into(),
+        }
+    }
+
+    /// Validate submitted values, returning the trimmed values of known
+    /// fields or every field error found.
+    pub fn validate(
+        &self,
+        values: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, Vec<FieldError>> {
+        let mut accepted = BTreeMap::new();
+        let mut errors = Vec::new();
+        for field in &self.fields {
+            let value = values.get(&field.id).map(|v| v.trim()).unwrap_or_default();
+            match field.validate(value) {
+                Ok(()) if !value.is_empty() => {
+                    accepted.insert(field.id.clone(), value.to_string());
+                }
+                Ok(()) => {}
+                Err(message) => errors.push(FieldError {
+                    field: field.id.clone(),
+                    message,
+                }),
+            }
+        }
+        if errors.is_empty() {
+            Ok(accepted)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse a text reply into field values.
+    ///
+    /// Lines of the form `field: value` are matched against field ids and
+    /// labels (case-insensitive). A single-field form accepts the whole reply.
+    pub fn parse_text_reply(&self, reply: &str) -> BTreeMap<String, String> {
+        let mut values = BTreeMap::new();
+        for line in reply.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().trim_start_matches(['-', '•', '*']).trim();
+            if let Some(field) = self
+                .fields
+                .iter()
+                .find(|f| f.id.eq_ignore_ascii_case(key) || f.label.eq_ignore_ascii_case(key))
+            {
+                values.insert(field.id.clone(), value.trim().to_string());
+            }
+        }
+        if values.is_empty()
+            && let [field] = self.fields.as_slice()
+        {
+            values.insert(field.id.clone(), reply.trim().to_string());
+        }
+        values
+    }
+}
+
+// ── Platform callback encoding ──────────────────────────────────
+
+/// Action referenced by a platform callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionRef {
+    /// Flattened choice index (see [`RichMessage::choices`]).
+    Choice(usize),
+    /// Form submission with the submitted raw values.
+    Submit(BTreeMap<String, String>),
+}
+
+/// Encode platform callback data for choice `index` of message `message_id`.
+pub fn encode_choice(message_id: &str, index: usize) -> String {
+    format!("{RICH_ACTION_PREFIX}{message_id}:{index}")
+}
+
+/// Encode the callback identifier of a select menu of message `message_id`.
+///
+/// Platforms report the chosen option separately; option values are the
+/// flattened choice index as a string.
+pub fn encode_select(message_id: &str) -> String {
+    format!("{RICH_ACTION_PREFIX}{message_id}:select")
+}
+
+/// Encode the callback identifier of the form submit button.
+pub fn encode_submit(message_id: &str) -> String {
+    format!("{RICH_ACTION_PREFIX}{message_id}:submit")
+}
+
+/// Decode platform callback data produced by the `encode_*` helpers.
+///
+/// Returns the message id and the action suffix (`"3"`, `"select"`,
+/// `"submit"`), or `None` for callbacks that are not rich actions.
+pub fn decode_action(data: &str) -> Option<(&str, &str)> {
+    let rest = data.strip_prefix(RICH_ACTION_PREFIX)?;
+    let (message_id, action) = rest.rsplit_once(':')?;
+    if message_id.is_empty() || action.is_empty() {
+        return None;
+    }
+    Some((message_id, action))
+}
+
+// ── Interaction registry ────────────────────────────────────────
+
+#[derive(Default)]
+struct Registry {
+    messages: VecDeque<RichMessage>,
+    subscribers: HashMap<String, tokio::sync::mpsc::UnboundedSender<RichInteraction>>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Registry::default()))
+}
+
+fn remember(registry: &mut Registry, message: &RichMessage) {
+    if registry.messages.iter().any(|m| m.id == message.id) {
+        return;
+    }
+    if registry.messages.len() >= TRACKED_MESSAGES_MAX {
+        registry.messages.pop_front();
+    }
+    registry.messages.push_back(message.clone());
+}
+
+/// Remember a natively rendered message so later callbacks can be resolved.
+///
+/// Channels call this when they render a [`RichMessage`] with interactive
+/// controls. Only the most recent messages are kept.
+pub fn track(message: &RichMessage) {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    remember(&mut registry, message);
+}
+
+/// Look up a tracked message by id.
+pub fn tracked(message_id: &str) -> Option<RichMessage> {
+    let registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    registry
+        .messages
+        .iter()
+        .find(|m| m.id == message_id)
+        .cloned()
+}
+
+/// Receives interactions for one rich message until dropped.
+pub struct InteractionSubscription {
+    message_id: String,
+    rx: tokio::sync::mpsc::UnboundedReceiver<RichInteraction>,
+}
+
+impl InteractionSubscription {
+    /// Wait for the next interaction. Returns `None` if the subscription was
+    /// replaced by another subscriber for the same message.
+    pub async fn recv(&mut self) -> Option<RichInteraction> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for InteractionSubscription {
+    fn drop(&mut self) {
+        // Closing our receiver marks our sender closed, which tells it apart
+        // from a newer subscription registered for the same message.
+        self.rx.close();
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        if registry
+            .subscribers
+            .get(&self.message_id)
+            .is_some_and(|tx| tx.is_closed())
+        {
+            registry.subscribers.remove(&self.message_id);
+        }
+    }
+}
+
+/// Subscribe to interactions with `message`. Call before sending so that an
+/// immediate click is not missed.
+pub fn subscribe(message: &RichMessage) -> InteractionSubscription {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    remember(&mut registry, message);
+    registry.subscribers.insert(message.id.clone(), tx);
+    InteractionSubscription {
+        message_id: message.id.clone(),
+        rx,
+    }
+}
+
+/// Result of routing a platform callback.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchOutcome {
+    /// A subscriber received the interaction.
+    Delivered,
+    /// Nobody is waiting; the channel should forward the interaction to the
+    /// agent as an ordinary message (see [`RichInteraction::as_reply_text`]).
+    Unclaimed(RichInteraction),
+    /// The form submission failed validation; the channel should tell the user.
+    Invalid(Vec<FieldError>),
+}
+
+/// Route a platform callback for message `message_id` to its subscriber.
+///
+/// Returns `None` when the message is unknown (e.g. sent before a restart)
+/// or the action does not exist.
+pub fn dispatch(
+    message_id: &str,
+    action: ActionRef,
+    sender: &str,
+    channel: &str,
+) -> Option<DispatchOutcome> {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let message = registry.messages.iter().find(|m| m.id == message_id)?;
+
+    let interaction = match action {
+        ActionRef::Choice(index) => {
+            let choice = message.choices().into_iter().nth(index)?;
+            RichInteraction {
+                message_id: message_id.to_string(),
+                action_id: choice.action_id,
+                value: choice.value,
+                fields: BTreeMap::new(),
+                sender: sender.to_string(),
+                channel: channel.to_string(),
+            }
+        }
+        ActionRef::Submit(values) => {
+            let form = message.form_block()?;
+            let fields = match form.validate(&values) {
+                Ok(fields) => fields,
+                Err(errors) => return Some(DispatchOutcome::Invalid(errors)),
+            };
+            RichInteraction {
+                message_id: message_id.to_string(),
+                action_id: form.action_id.clone(),
+                value: String::new(),
+                fields,
+                sender: sender.to_string(),
+                channel: channel.to_string(),
+            }
+        }
+    };
+
+    if let Some(tx) = registry.subscribers.get(message_id) {
+        match tx.send(interaction) {
+            Ok(()) => return Some(DispatchOutcome::Delivered),
+            Err(tokio::sync::mpsc::error::SendError(interaction)) => {
+                registry.subscribers.remove(message_id);
+                return Some(DispatchOutcome::Unclaimed(interaction));
+            }
+        }
+    }
+    Some(DispatchOutcome::Unclaimed(interaction))
+}
+
+/// Route a decoded callback action string (`"3"` or `"submit"`).
+///
+/// `selected` carries the option value reported by a select menu, and
+/// `form_values` the submitted values for `"submit"`.
+pub fn dispatch_encoded(
+    message_id: &str,
+    action: &str,
+    selected: Option<&str>,
+    form_values: BTreeMap<String, String>,
+    sender: &str,
+    channel: &str,
+) -> Option<DispatchOutcome> {
+    let action = match action {
+        "submit" => ActionRef::Submit(form_values),
+        "select" => ActionRef::Choice(selected?.parse().ok()?),
+        index => ActionRef::Choice(index.parse().ok()?),
+    };
+    dispatch(message_id, action, sender, channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RichMessage {
+        RichMessage::new()
+            .header("Deploy?")
+            .section("Staging is green.")
+            .buttons(vec![
+                RichButton::new("deploy", "Deploy").primary(),
+                RichButton::new("cancel", "Cancel").danger(),
+            ])
+            .select(
+                "Or pick a region",
+                vec![RichOption::new("EU", "eu"), RichOption::new("US", "us")],
+            )
+    }
+
+    #[test]
+    fn plain_text_numbers_choices_across_blocks() {
+        let text = sample().to_plain_text();
+        assert!(text.contains("**Deploy?**"));
+        assert!(text.contains("1. Deploy"));
+        assert!(text.contains("2. Cancel"));
+        assert!(text.contains("3. EU"));
+        assert!(text.contains("4. US"));
+        assert!(text.contains("Reply with a number"));
+    }
+
+    #[test]
+    fn resolve_reply_accepts_numbers_and_labels() {
+        let message = sample();
+        match message.resolve_reply("2") {
+            ReplyResolution::Choice(choice) => assert_eq!(choice.action_id, "cancel"),
+            other => panic!("unexpected {other:?}"),
+        }
+        match message.resolve_reply(" us ") {
+            ReplyResolution::Choice(choice) => assert_eq!(choice.value, "us"),
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(message.resolve_reply("9"), ReplyResolution::Unmatched);
+        assert_eq!(message.first_choice_index(3), 2);
+    }
+
+    #[test]
+    fn form_validation_reports_every_bad_field() {
+        let form = RichForm::new(
+            "signup",
+            vec![
+                FormField::new("email", "Email", FormFieldKind::Email).required(),
+                FormField::new("age", "Age", FormFieldKind::Number),
+                FormField::new(
+                    "tier",
+                    "Tier",
+                    FormFieldKind::Choice {
+                        options: vec!["free".into(), "pro".into()],
+                    },
+                ),
+            ],
+            "Sign up",
+        );
+        let message = RichMessage::new().form(form.clone());
+
+        match message.resolve_reply("Email: a@b.io\nage: 42\ntier: PRO") {
+            ReplyResolution::Form { action_id, fields } => {
+                assert_eq!(action_id, "signup");
+                assert_eq!(fields["email"], "a@b.io");
+                assert_eq!(fields["tier"], "PRO");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let errors = form
+            .validate(&form.parse_text_reply("age: old\ntier: gold"))
+            .unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["email", "age", "tier"]);
+    }
+
+    #[test]
+    fn single_field_form_accepts_whole_reply() {
+        let form = RichForm::new(
+            "note",
+            vec![FormField::new("text", "Note", FormFieldKind::Text).max_length(5)],
+            "Save",
+        );
+        assert_eq!(form.parse_text_reply("hello")["text"], "hello");
+        assert!(form.validate(&form.parse_text_reply("too long")).is_err());
+    }
+
+    #[test]
+    fn encoded_actions_round_trip_and_fit_telegram_limit() {
+        let message = sample();
+        let data = encode_choice(&message.id, 3);
+        assert!(data.len() <= 64);
+        assert_eq!(decode_action(&data), Some((message.id.as_str(), "3")));
+        assert_eq!(decode_action("approval:x:approve"), None);
+    }
+
+    #[tokio::test]
+    async fn dispatch_delivers_to_subscriber_then_falls_back_to_unclaimed() {
+        let message = sample();
+        let mut subscription = subscribe(&message);
+
+        let outcome = dispatch_encoded(
+            &message.id,
+            "select",
+            Some("3"),
+            BTreeMap::new(),
+            "u1",
+            "slack",
+        );
+        assert_eq!(outcome, Some(DispatchOutcome::Delivered));
+        let interaction = subscription.recv().await.unwrap();
+        assert_eq!(interaction.value, "us");
+        assert_eq!(interaction.sender, "u1");
+
+        drop(subscription);
+        match dispatch(&message.id, ActionRef::Choice(0), "u2", "slack") {
+            Some(DispatchOutcome::Unclaimed(interaction)) => {
+                assert_eq!(interaction.as_reply_text(), "Deploy");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(dispatch("unknown", ActionRef::Choice(0), "u", "slack").is_none());
+    }
+}
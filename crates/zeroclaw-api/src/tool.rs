@@ -11,9 +11,11 @@ pub struct ToolResult {
 
 /// Description of a tool for the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ToolSpec {
     pub name: String,
     pub description: String,
+    /// JSON Schema of the tool's arguments.
     pub parameters: serde_json::Value,
 }
 
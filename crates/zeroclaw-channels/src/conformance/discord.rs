@@ -0,0 +1,371 @@
+//! Discord REST API and gateway mock.
+//!
+//! `GET /gateway/bot` points the channel at a local gateway that sends HELLO,
+//! waits for IDENTIFY and then dispatches `MESSAGE_CREATE` and
+//! `INTERACTION_CREATE` events. `disconnect` closes the gateway socket, which
+//! ends `listen()` so the supervisor must start it again. Discord threads are
+//! channels of their own, so a reply in a thread is a post to the thread's
+//! channel id.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use axum::Json;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use serde_json::{Value, json};
+use zeroclaw_api::rich;
+
+use super::{
+    Capability, MockPlatform, MockServer, RecordedMessage, Recorder, SocketHub, ThreadTarget,
+    pump_socket,
+};
+
+const CHANNEL_ID: &str = "900100";
+const USER_ID: &str = "900200";
+/// A public thread started under [`CHANNEL_ID`].
+const THREAD_ID: &str = "900300";
+
+struct DiscordState {
+    recorder: Recorder,
+    hub: SocketHub,
+    gateway_url: OnceLock<String>,
+}
+
+impl DiscordState {
+    fn dispatch(&self, event: &str, data: Value) {
+        let frame = json!({
+            "op": 0,
+            "s": self.recorder.next_seq(),
+            "t": event,
+            "d": data,
+        });
+        self.hub.deliver(frame.to_string());
+    }
+}
+
+/// Local Discord REST API and gateway.
+pub struct DiscordMock {
+    server: MockServer,
+    state: Arc<DiscordState>,
+}
+
+impl DiscordMock {
+    /// Capabilities [`crate::discord::DiscordChannel`] claims with partial
+    /// streaming enabled.
+    pub const CLAIMS: &'static [Capability] = &[
+        Capability::SendText,
+        Capability::ChunkedSend,
+        Capability::ThreadedReply,
+        Capability::DraftUpdates,
+        Capability::RateLimitSignal,
+        Capability::SendRejected,
+        Capability::Listen,
+        Capability::Reactions,
+        Capability::Approvals,
+        Capability::RichMessages,
+        Capability::Reconnect,
+    ];
+
+    pub async fn start() -> anyhow::Result<Self> {
+        let state = Arc::new(DiscordState {
+            recorder: Recorder::default(),
+            hub: SocketHub::default(),
+            gateway_url: OnceLock::new(),
+        });
+        let api = axum::Router::new()
+            .route("/gateway/bot", get(gateway_bot))
+            .route(
+                "/users/@me",
+                get(|| async { Json(json!({ "id": "1", "bot": true })) }),
+            )
+            .route("/channels/{channel}/messages", post(create_message))
+            .route(
+                "/channels/{channel}/messages/{message}",
+                axum::routing::patch(edit_message).delete(delete_message),
+            )
+            .route(
+                "/channels/{channel}/messages/{message}/reactions/{emoji}/@me",
+                put(add_reaction).delete(remove_reaction),
+            )
+            .route("/channels/{channel}/typing", post(no_content))
+            .route("/interactions/{id}/{token}/callback", post(no_content));
+        let router = axum::Router::new()
+            .route("/", get(gateway))
+            .nest("/api/v10", api)
+            .with_state(Arc::clone(&state));
+        let server = MockServer::start(router).await?;
+        let _ = state.gateway_url.set(server.ws_url());
+        Ok(Self { server, state })
+    }
+
+    /// Value for `DiscordChannel::with_api_base`.
+    pub fn api_base(&self) -> String {
+        format!("{}/api/v10", self.server.http_url())
+    }
+}
+
+async fn gateway_bot(State(state): State<Arc<DiscordState>>) -> Json<Value> {
+    Json(json!({ "url": state.gateway_url.get(), "shards": 1 }))
+}
+
+async fn no_content() -> StatusCode {
+    StatusCode::NO_CONTENT
+}
+
+async fn gateway(State(state): State<Arc<DiscordState>>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| handshake(state, socket))
+}
+
+/// HELLO, then wait for IDENTIFY before attaching the socket to the hub so
+/// no dispatch can overtake the handshake.
+async fn handshake(state: Arc<DiscordState>, mut socket: WebSocket) {
+    let hello = json!({ "op": 10, "d": { "heartbeat_interval": 45_000 } });
+    if socket
+        .send(Message::Text(hello.to_string().into()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+    loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => {
+                let frame: Value = serde_json::from_str(text.as_str()).unwrap_or_default();
+                if frame["op"] == 2 {
+                    break;
+                }
+            }
+            Some(Ok(_)) => {}
+            Some(Err(_)) | None => return,
+        }
+    }
+    let frames = state.hub.attach();
+    state.dispatch(
+        "READY",
+        json!({ "v": 10, "user": { "id": "1", "bot": true } }),
+    );
+    pump_socket(socket, frames).await;
+}
+
+async fn create_message(
+    State(state): State<Arc<DiscordState>>,
+    Path(channel): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    if let Some(retry_after) = state.recorder.take_rate_limit() {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(json!({ "message": "You are being rate limited.", "retry_after": retry_after })),
+        )
+            .into_response();
+    }
+    if state.recorder.rejecting_sends() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "Missing Access", "code": 50001 })),
+        )
+            .into_response();
+    }
+
+    let id = state.recorder.next_seq().to_string();
+    state.recorder.record(RecordedMessage {
+        id: id.clone(),
+        recipient: channel.clone(),
+        thread: (channel == THREAD_ID).then(|| THREAD_ID.to_string()),
+        text: body["content"].as_str().unwrap_or_default().to_string(),
+        payload: body,
+        deleted: false,
+    });
+    Json(json!({ "id": id, "channel_id": channel })).into_response()
+}
+
+async fn edit_message(
+    State(state): State<Arc<DiscordState>>,
+    Path((channel, message)): Path<(String, String)>,
+    Json(body): Json<Value>,
+) -> Response {
+    let text = body["content"].as_str().unwrap_or_default();
+    if state.recorder.edit(&message, text) {
+        Json(json!({ "id": message, "channel_id": channel })).into_response()
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Unknown Message", "code": 10008 })),
+        )
+            .into_response()
+    }
+}
+
+async fn delete_message(
+    State(state): State<Arc<DiscordState>>,
+    Path((_channel, message)): Path<(String, String)>,
+) -> StatusCode {
+    state.recorder.delete(&message);
+    StatusCode::NO_CONTENT
+}
+
+async fn add_reaction(
+    State(state): State<Arc<DiscordState>>,
+    Path((_channel, message, emoji)): Path<(String, String, String)>,
+) -> StatusCode {
+    state.recorder.react(&message, &emoji);
+    StatusCode::NO_CONTENT
+}
+
+async fn remove_reaction(
+    State(state): State<Arc<DiscordState>>,
+    Path((_channel, message, emoji)): Path<(String, String, String)>,
+) -> StatusCode {
+    state.recorder.unreact(&message, &emoji);
+    StatusCode::NO_CONTENT
+}
+
+#[async_trait]
+impl MockPlatform for DiscordMock {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    fn recipient(&self) -> String {
+        CHANNEL_ID.to_string()
+    }
+
+    fn recorder(&self) -> &Recorder {
+        &self.state.recorder
+    }
+
+    fn max_message_len(&self) -> Option<usize> {
+        Some(2000)
+    }
+
+    fn thread_target(&self) -> Option<ThreadTarget> {
+        Some(ThreadTarget {
+            recipient: THREAD_ID.to_string(),
+            thread_ts: None,
+            expected_thread: THREAD_ID.to_string(),
+        })
+    }
+
+    fn is_listening(&self) -> bool {
+        self.state.hub.is_connected()
+    }
+
+    async fn push_inbound(&self, text: &str) {
+        let id = self.state.recorder.next_seq().to_string();
+        self.state.dispatch(
+            "MESSAGE_CREATE",
+            json!({
+                "id": id,
+                "channel_id": CHANNEL_ID,
+                "author": { "id": USER_ID, "username": "conformance", "bot": false },
+                "content": text,
+                "attachments": [],
+            }),
+        );
+    }
+
+    async fn disconnect(&self) {
+        self.state.hub.disconnect();
+    }
+
+    async fn answer_approval(&self, approve: bool) -> anyhow::Result<()> {
+        // Discord approvals are answered by replying "<token> yes|no".
+        let prompt = self
+            .state
+            .recorder
+            .messages()
+            .into_iter()
+            .rev()
+            .find(|m| !m.deleted && m.text.starts_with("APPROVAL REQUIRED ["))
+            .ok_or_else(|| anyhow::anyhow!("no approval prompt sent"))?;
+        let token = prompt
+            .text
+            .split_once('[')
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .map(|(token, _)| token.to_string())
+            .ok_or_else(|| anyhow::anyhow!("approval prompt has no token"))?;
+        let answer = if approve { "yes" } else { "no" };
+        self.push_inbound(&format!("{token} {answer}")).await;
+        Ok(())
+    }
+
+    async fn click_rich(&self, message_id: &str, index: usize) -> anyhow::Result<()> {
+        let custom_id = rich::encode_choice(message_id, index);
+        let (message, custom_id) = self
+            .state
+            .recorder
+            .find_payload_string(|s| s == custom_id)
+            .ok_or_else(|| anyhow::anyhow!("no component with custom_id {custom_id}"))?;
+        let seq = self.state.recorder.next_seq();
+        self.state.dispatch(
+            "INTERACTION_CREATE",
+            json!({
+                "type": 3,
+                "id": format!("interaction-{seq}"),
+                "token": format!("token-{seq}"),
+                "channel_id": message.recipient,
+                "message": { "id": message.id },
+                "user": { "id": USER_ID },
+                "data": { "custom_id": custom_id, "component_type": 2 },
+            }),
+        );
+        Ok(())
+    }
+
+    fn has_reaction(&self, message_id: &str, emoji: &str) -> bool {
+        let raw_id = message_id.strip_prefix("discord_").unwrap_or(message_id);
+        self.state.recorder.has_reaction(raw_id, emoji)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::{Outcome, run_capability, run_suite};
+    use crate::discord::DiscordChannel;
+    use zeroclaw_config::schema::StreamMode;
+
+    fn channel(mock: &DiscordMock) -> DiscordChannel {
+        DiscordChannel::new("conformance".into(), None, vec!["*".into()], false, false)
+            .with_api_base(mock.api_base())
+            .with_streaming(StreamMode::Partial, 0, 0)
+    }
+
+    #[tokio::test]
+    async fn discord_channel_passes_conformance_suite() {
+        let mock = DiscordMock::start().await.unwrap();
+        run_suite(Arc::new(channel(&mock)), &mock, DiscordMock::CLAIMS)
+            .await
+            .assert_conformant();
+    }
+
+    async fn scenario(capability: Capability) -> Outcome {
+        let mock = DiscordMock::start().await.unwrap();
+        run_capability(Arc::new(channel(&mock)), &mock, capability).await
+    }
+
+    #[tokio::test]
+    async fn sends_text() {
+        assert_eq!(scenario(Capability::SendText).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn receives_inbound_messages() {
+        assert_eq!(scenario(Capability::Listen).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn replies_in_threads() {
+        assert_eq!(scenario(Capability::ThreadedReply).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn surfaces_refused_sends_as_errors() {
+        assert_eq!(scenario(Capability::SendRejected).await, Outcome::Passed);
+    }
+}
@@ -0,0 +1,503 @@
+//! Matrix client-server API mock.
+//!
+//! Serves the endpoints `matrix-sdk` touches for an access-token session in
+//! one unencrypted room: long-polled `/sync`, room sends and redactions,
+//! single-event lookups for replies and edits, and stub answers for the
+//! end-to-end key endpoints the SDK calls after every sync. The bot only
+//! knows the room once the first sync has delivered it, so every scenario
+//! runs with `listen()` connected. `disconnect` fails the next sync with a
+//! 502, which the channel must ride out.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+
+use super::{Capability, MockPlatform, MockServer, RecordedMessage, Recorder, ThreadTarget};
+
+const ROOM_ID: &str = "!conformance:localhost";
+const USER_ID: &str = "@conformance:localhost";
+/// Account the channel logs in as.
+pub const BOT_USER_ID: &str = "@bot:localhost";
+/// Device the channel's access token is bound to.
+pub const BOT_DEVICE_ID: &str = "CONFORMANCE";
+/// A message from the test user that threaded replies hang off.
+const THREAD_ROOT_ID: &str = "$thread-root";
+/// Longest a `/sync` call is held open, regardless of the requested timeout.
+const MAX_SYNC_HOLD: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct MatrixState {
+    recorder: Recorder,
+    /// Inbound timeline, in delivery order; sync tokens index into it.
+    timeline: Mutex<Vec<Value>>,
+    /// Every event the room holds, by id, for `/event/{id}` lookups.
+    events: Mutex<HashMap<String, Value>>,
+    /// Reaction event id to the `(target, key)` it annotates.
+    reactions: Mutex<HashMap<String, (String, String)>>,
+    wake: Notify,
+    fail_next_sync: AtomicBool,
+    syncing: AtomicBool,
+}
+
+impl MatrixState {
+    fn new_event_id(&self) -> String {
+        format!("$conformance{}", self.recorder.next_seq())
+    }
+
+    fn store(&self, event: &Value) {
+        if let Some(id) = event["event_id"].as_str() {
+            self.events.lock().insert(id.to_string(), event.clone());
+        }
+    }
+
+    fn push_timeline(&self, event: Value) {
+        self.store(&event);
+        self.timeline.lock().push(event);
+        self.wake.notify_waiters();
+    }
+}
+
+/// Local Matrix homeserver with a single room shared by the bot and a test user.
+pub struct MatrixMock {
+    server: MockServer,
+    state: Arc<MatrixState>,
+}
+
+impl MatrixMock {
+    /// Capabilities [`crate::matrix::MatrixChannel`] claims with partial
+    /// streaming, threaded replies and acknowledgement reactions enabled.
+    pub const CLAIMS: &'static [Capability] = &[
+        Capability::SendText,
+        Capability::ThreadedReply,
+        Capability::DraftUpdates,
+        Capability::SendRejected,
+        Capability::Listen,
+        Capability::Reactions,
+        Capability::Approvals,
+        Capability::Reconnect,
+    ];
+
+    pub async fn start() -> anyhow::Result<Self> {
+        let state = Arc::new(MatrixState::default());
+        state.store(&room_event(
+            THREAD_ROOT_ID,
+            USER_ID,
+            "m.room.message",
+            json!({ "msgtype": "m.text", "body": "conformance: thread root" }),
+        ));
+
+        let api = axum::Router::new()
+            .route("/account/whoami", get(whoami))
+            .route("/profile/{user}", get(profile))
+            .route("/profile/{user}/displayname", get(profile))
+            .route("/sync", get(sync))
+            .route("/rooms/{room}/send/{event_type}/{txn}", put(send_event))
+            .route("/rooms/{room}/redact/{event}/{txn}", put(redact_event))
+            .route("/rooms/{room}/event/{event}", get(get_event))
+            .route("/rooms/{room}/state/{*rest}", get(missing_state))
+            .route("/rooms/{room}/members", get(members))
+            .route("/rooms/{room}/typing/{user}", put(empty))
+            .route("/rooms/{room}/receipt/{kind}/{event}", post(empty))
+            .route("/keys/upload", post(keys_upload))
+            .route("/keys/query", post(keys_query))
+            .route("/keys/claim", post(keys_claim))
+            .route("/sendToDevice/{event_type}/{txn}", put(empty))
+            .route("/user/{user}/filter", post(create_filter));
+        let router = axum::Router::new()
+            .route("/_matrix/client/versions", get(versions))
+            .nest("/_matrix/client/v3", api)
+            .fallback(unrecognized)
+            .with_state(Arc::clone(&state));
+        let server = MockServer::start(router).await?;
+        Ok(Self { server, state })
+    }
+
+    /// Value for `MatrixConfig::homeserver`.
+    pub fn homeserver(&self) -> String {
+        self.server.http_url()
+    }
+}
+
+fn room_event(event_id: &str, sender: &str, kind: &str, content: Value) -> Value {
+    json!({
+        "event_id": event_id,
+        "room_id": ROOM_ID,
+        "sender": sender,
+        "type": kind,
+        "origin_server_ts": chrono::Utc::now().timestamp_millis(),
+        "content": content,
+    })
+}
+
+fn member_event(user: &str) -> Value {
+    json!({
+        "event_id": format!("$member-{}", user.trim_start_matches('@').replace(':', "-")),
+        "sender": user,
+        "type": "m.room.member",
+        "state_key": user,
+        "origin_server_ts": 0,
+        "content": { "membership": "join" },
+    })
+}
+
+fn room_state() -> Vec<Value> {
+    vec![
+        json!({
+            "event_id": "$create",
+            "sender": USER_ID,
+            "type": "m.room.create",
+            "state_key": "",
+            "origin_server_ts": 0,
+            "content": { "creator": USER_ID, "room_version": "10" },
+        }),
+        member_event(USER_ID),
+        member_event(BOT_USER_ID),
+    ]
+}
+
+fn matrix_error(status: StatusCode, errcode: &str, error: &str) -> Response {
+    (status, Json(json!({ "errcode": errcode, "error": error }))).into_response()
+}
+
+async fn versions() -> Json<Value> {
+    Json(json!({ "versions": ["v1.1", "v1.5", "v1.11"], "unstable_features": {} }))
+}
+
+async fn whoami() -> Json<Value> {
+    Json(json!({ "user_id": BOT_USER_ID, "device_id": BOT_DEVICE_ID }))
+}
+
+async fn profile() -> Json<Value> {
+    Json(json!({ "displayname": "conformance bot" }))
+}
+
+async fn empty() -> Json<Value> {
+    Json(json!({}))
+}
+
+async fn unrecognized() -> Response {
+    matrix_error(
+        StatusCode::NOT_FOUND,
+        "M_UNRECOGNIZED",
+        "Unrecognized request",
+    )
+}
+
+async fn missing_state() -> Response {
+    matrix_error(StatusCode::NOT_FOUND, "M_NOT_FOUND", "Event not found")
+}
+
+async fn members() -> Json<Value> {
+    Json(json!({ "chunk": [member_event(USER_ID), member_event(BOT_USER_ID)] }))
+}
+
+async fn create_filter() -> Json<Value> {
+    Json(json!({ "filter_id": "conformance" }))
+}
+
+async fn keys_upload() -> Json<Value> {
+    Json(json!({ "one_time_key_counts": { "signed_curve25519": 50 } }))
+}
+
+async fn keys_query() -> Json<Value> {
+    Json(json!({
+        "device_keys": {},
+        "master_keys": {},
+        "self_signing_keys": {},
+        "user_signing_keys": {},
+        "failures": {},
+    }))
+}
+
+async fn keys_claim() -> Json<Value> {
+    Json(json!({ "one_time_keys": {}, "failures": {} }))
+}
+
+async fn sync(
+    State(state): State<Arc<MatrixState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let since = params
+        .get("since")
+        .and_then(|token| token.strip_prefix('s'))
+        .and_then(|n| n.parse::<usize>().ok());
+    let timeout = params
+        .get("timeout")
+        .and_then(|ms| ms.parse::<u64>().ok())
+        .map_or(Duration::ZERO, Duration::from_millis)
+        .min(MAX_SYNC_HOLD);
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if state.fail_next_sync.swap(false, Ordering::SeqCst) {
+            state.syncing.store(false, Ordering::SeqCst);
+            return (StatusCode::BAD_GATEWAY, "Bad Gateway").into_response();
+        }
+        // A sync that carries a token proves the initial sync was processed.
+        if since.is_some() {
+            state.syncing.store(true, Ordering::SeqCst);
+        }
+        let wake = state.wake.notified();
+        let (events, next) = {
+            let timeline = state.timeline.lock();
+            let from = since.unwrap_or(0).min(timeline.len());
+            (timeline[from..].to_vec(), timeline.len())
+        };
+        if since.is_none() || !events.is_empty() || tokio::time::Instant::now() >= deadline {
+            let mut room = json!({
+                "timeline": { "events": events, "limited": false, "prev_batch": "p0" },
+            });
+            if since.is_none() {
+                room["state"] = json!({ "events": room_state() });
+            }
+            let rooms = if since.is_none() || !events.is_empty() {
+                json!({ "join": { ROOM_ID: room } })
+            } else {
+                json!({})
+            };
+            return Json(json!({ "next_batch": format!("s{next}"), "rooms": rooms }))
+                .into_response();
+        }
+        let _ = tokio::time::timeout_at(deadline, wake).await;
+    }
+}
+
+async fn send_event(
+    State(state): State<Arc<MatrixState>>,
+    Path((room, event_type, _txn)): Path<(String, String, String)>,
+    Json(content): Json<Value>,
+) -> Response {
+    if let Some(retry_after) = state.recorder.take_rate_limit() {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "errcode": "M_LIMIT_EXCEEDED",
+                "error": "Too many requests",
+                "retry_after_ms": retry_after * 1000,
+            })),
+        )
+            .into_response();
+    }
+    if state.recorder.rejecting_sends() {
+        return matrix_error(
+            StatusCode::FORBIDDEN,
+            "M_FORBIDDEN",
+            "You do not have permission to send in this room",
+        );
+    }
+
+    let event_id = state.new_event_id();
+    let relation = &content["m.relates_to"];
+    match (event_type.as_str(), relation["rel_type"].as_str()) {
+        ("m.reaction", _) => {
+            let target = relation["event_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let key = relation["key"].as_str().unwrap_or_default().to_string();
+            state.recorder.react(&target, &key);
+            state
+                .reactions
+                .lock()
+                .insert(event_id.clone(), (target, key));
+        }
+        ("m.room.message", Some("m.replace")) => {
+            let target = relation["event_id"].as_str().unwrap_or_default();
+            let text = content["m.new_content"]["body"]
+                .as_str()
+                .unwrap_or_default();
+            if !state.recorder.edit(target, text) {
+                return matrix_error(StatusCode::BAD_REQUEST, "M_UNKNOWN", "Unknown edit target");
+            }
+        }
+        _ => state.recorder.record(RecordedMessage {
+            id: event_id.clone(),
+            recipient: room.clone(),
+            thread: (relation["rel_type"] == "m.thread").then(|| {
+                relation["event_id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            }),
+            text: content["body"].as_str().unwrap_or_default().to_string(),
+            payload: content.clone(),
+            deleted: false,
+        }),
+    }
+    state.store(&room_event(&event_id, BOT_USER_ID, &event_type, content));
+    Json(json!({ "event_id": event_id })).into_response()
+}
+
+async fn redact_event(
+    State(state): State<Arc<MatrixState>>,
+    Path((_room, event, _txn)): Path<(String, String, String)>,
+) -> Json<Value> {
+    if let Some((target, key)) = state.reactions.lock().remove(&event) {
+        state.recorder.unreact(&target, &key);
+    } else {
+        state.recorder.delete(&event);
+    }
+    Json(json!({ "event_id": state.new_event_id() }))
+}
+
+async fn get_event(
+    State(state): State<Arc<MatrixState>>,
+    Path((_room, event)): Path<(String, String)>,
+) -> Response {
+    match state.events.lock().get(&event) {
+        Some(found) => Json(found.clone()).into_response(),
+        None => matrix_error(StatusCode::NOT_FOUND, "M_NOT_FOUND", "Event not found"),
+    }
+}
+
+#[async_trait]
+impl MockPlatform for MatrixMock {
+    fn name(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn recipient(&self) -> String {
+        ROOM_ID.to_string()
+    }
+
+    fn recorder(&self) -> &Recorder {
+        &self.state.recorder
+    }
+
+    fn thread_target(&self) -> Option<ThreadTarget> {
+        Some(ThreadTarget {
+            recipient: ROOM_ID.to_string(),
+            thread_ts: Some(THREAD_ROOT_ID.into()),
+            expected_thread: THREAD_ROOT_ID.into(),
+        })
+    }
+
+    fn is_listening(&self) -> bool {
+        self.state.syncing.load(Ordering::SeqCst)
+    }
+
+    fn requires_listener(&self) -> bool {
+        true
+    }
+
+    async fn push_inbound(&self, text: &str) {
+        let event_id = self.state.new_event_id();
+        self.state.push_timeline(room_event(
+            &event_id,
+            USER_ID,
+            "m.room.message",
+            json!({ "msgtype": "m.text", "body": text }),
+        ));
+    }
+
+    async fn disconnect(&self) {
+        self.state.fail_next_sync.store(true, Ordering::SeqCst);
+        self.state.syncing.store(false, Ordering::SeqCst);
+        self.state.wake.notify_waiters();
+    }
+
+    async fn answer_approval(&self, approve: bool) -> anyhow::Result<()> {
+        // Matrix approvals are answered by replying "<token> approve|deny".
+        let prompt = self
+            .state
+            .recorder
+            .messages()
+            .into_iter()
+            .rev()
+            .find(|m| !m.deleted && m.text.starts_with("APPROVAL REQUIRED ["))
+            .ok_or_else(|| anyhow::anyhow!("no approval prompt sent"))?;
+        let token = prompt
+            .text
+            .split_once('[')
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .map(|(token, _)| token.to_string())
+            .ok_or_else(|| anyhow::anyhow!("approval prompt has no token"))?;
+        let answer = if approve { "approve" } else { "deny" };
+        self.push_inbound(&format!("{token} {answer}")).await;
+        Ok(())
+    }
+
+    async fn click_rich(&self, _message_id: &str, _index: usize) -> anyhow::Result<()> {
+        anyhow::bail!("matrix renders rich messages as text")
+    }
+
+    fn has_reaction(&self, message_id: &str, emoji: &str) -> bool {
+        self.state.recorder.has_reaction(message_id, emoji)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::{Outcome, run_capability, run_suite};
+    use crate::matrix::MatrixChannel;
+    use tempfile::TempDir;
+    use zeroclaw_config::schema::{MatrixConfig, StreamMode};
+
+    fn channel(mock: &MatrixMock, state_dir: &TempDir) -> MatrixChannel {
+        let config = MatrixConfig {
+            enabled: true,
+            homeserver: mock.homeserver(),
+            access_token: "conformance".into(),
+            user_id: Some(BOT_USER_ID.into()),
+            device_id: Some(BOT_DEVICE_ID.into()),
+            allowed_users: vec!["*".into()],
+            stream_mode: StreamMode::Partial,
+            approval_timeout_secs: 30,
+            reply_in_thread: true,
+            ack_reactions: true,
+            ..MatrixConfig::default()
+        };
+        MatrixChannel::new(config, state_dir.path().to_path_buf()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn matrix_channel_passes_conformance_suite() {
+        let mock = MatrixMock::start().await.unwrap();
+        let state_dir = TempDir::new().unwrap();
+        run_suite(
+            Arc::new(channel(&mock, &state_dir)),
+            &mock,
+            MatrixMock::CLAIMS,
+        )
+        .await
+        .assert_conformant();
+    }
+
+    async fn scenario(capability: Capability) -> Outcome {
+        let mock = MatrixMock::start().await.unwrap();
+        let state_dir = TempDir::new().unwrap();
+        run_capability(Arc::new(channel(&mock, &state_dir)), &mock, capability).await
+    }
+
+    #[tokio::test]
+    async fn sends_text() {
+        assert_eq!(scenario(Capability::SendText).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn receives_inbound_messages() {
+        assert_eq!(scenario(Capability::Listen).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn replies_in_threads() {
+        assert_eq!(scenario(Capability::ThreadedReply).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn surfaces_refused_sends_as_errors() {
+        assert_eq!(scenario(Capability::SendRejected).await, Outcome::Passed);
+    }
+}
@@ -0,0 +1,994 @@
+//! Channel conformance kit.
+//!
+//! Drives a [`Channel`] implementation through a standard scenario suite
+//! against a local mock of its platform API. Every mock binds an HTTP (and,
+//! where the platform uses one, WebSocket) server on `127.0.0.1:0`, records
+//! what the channel sends, and can inject inbound traffic, rate limits and
+//! disconnects on demand.
+//!
+//! A channel declares the [`Capability`] set it claims. [`run_suite`] runs the
+//! scenario for every claimed capability end to end, and checks that
+//! unclaimed capabilities are reported as unsupported wherever the
+//! [`Channel`] trait exposes a flag for them.
+//!
+//! ```ignore
+//! let mock = TelegramMock::start().await?;
+//! let channel = TelegramChannel::new("token".into(), vec!["*".into()], false)
+//!     .with_api_base(mock.api_base());
+//! run_suite(Arc::new(channel), &mock, TelegramMock::CLAIMS).await.assert_conformant();
+//! ```
+//!
+//! Mocks exist for the Telegram Bot API (long polling), the Slack Web API with
+//! Socket Mode, the Discord REST API with its gateway, and the Matrix
+//! client-server API (sync, room sends, relations and the key endpoints
+//! `matrix-sdk` calls). New platforms plug in by implementing
+//! [`MockPlatform`]. [`run_capability`] runs one scenario on its own, for
+//! tests that pin a single behaviour.
+//!
+//! The module is compiled for tests and behind the `conformance` feature so
+//! that out-of-tree channels can run the same suite.
+
+#[cfg(feature = "channel-discord")]
+pub mod discord;
+#[cfg(feature = "channel-matrix")]
+pub mod matrix;
+#[cfg(feature = "channel-slack")]
+pub mod slack;
+#[cfg(feature = "channel-telegram")]
+pub mod telegram;
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, bail, ensure};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use zeroclaw_api::channel::{
+    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, ChannelRateLimited,
+    SendMessage,
+};
+use zeroclaw_api::rich::{self, RichButton, RichMessage};
+
+/// Upper bound for a single scenario, including reconnect backoff.
+const SCENARIO_TIMEOUT: Duration = Duration::from_secs(30);
+/// Delay before the suite's supervisor restarts a returned `listen()`.
+const LISTEN_RESTART_DELAY: Duration = Duration::from_millis(200);
+/// Poll interval while waiting for mock-side state changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+// ── Capabilities and report ─────────────────────────────────────
+
+/// A behaviour a channel can claim to support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    /// Plain text delivery to a recipient.
+    SendText,
+    /// Messages over the platform limit are split into in-limit chunks.
+    ChunkedSend,
+    /// Replies carrying a thread reference land in that thread.
+    ThreadedReply,
+    /// `send_draft` / `update_draft` / `finalize_draft` edit one message in place.
+    DraftUpdates,
+    /// A platform 429 surfaces as [`ChannelRateLimited`] with the requested delay.
+    RateLimitSignal,
+    /// A platform that refuses a send makes `send` fail instead of reporting delivery.
+    SendRejected,
+    /// Inbound messages are delivered through `listen()`.
+    Listen,
+    /// `add_reaction` / `remove_reaction` on an inbound message.
+    Reactions,
+    /// `request_approval` resolves from the platform's approval gesture.
+    Approvals,
+    /// Rich messages render native controls whose clicks reach subscribers.
+    RichMessages,
+    /// Inbound delivery resumes after the platform drops the connection.
+    Reconnect,
+}
+
+impl Capability {
+    /// Every capability in the order the suite exercises them. `Reconnect`
+    /// runs last because it tears down the live connection.
+    pub const ALL: [Capability; 11] = [
+        Capability::SendText,
+        Capability::ChunkedSend,
+        Capability::ThreadedReply,
+        Capability::DraftUpdates,
+        Capability::RateLimitSignal,
+        Capability::SendRejected,
+        Capability::Listen,
+        Capability::Reactions,
+        Capability::Approvals,
+        Capability::RichMessages,
+        Capability::Reconnect,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Capability::SendText => "send_text",
+            Capability::ChunkedSend => "chunked_send",
+            Capability::ThreadedReply => "threaded_reply",
+            Capability::DraftUpdates => "draft_updates",
+            Capability::RateLimitSignal => "rate_limit_signal",
+            Capability::SendRejected => "send_rejected",
+            Capability::Listen => "listen",
+            Capability::Reactions => "reactions",
+            Capability::Approvals => "approvals",
+            Capability::RichMessages => "rich_messages",
+            Capability::Reconnect => "reconnect",
+        }
+    }
+
+    /// Whether the scenario needs a running `listen()` loop.
+    fn needs_listener(self) -> bool {
+        matches!(
+            self,
+            Capability::Listen
+                | Capability::Reactions
+                | Capability::Approvals
+                | Capability::RichMessages
+                | Capability::Reconnect
+        )
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of one scenario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    /// Not claimed and nothing to verify.
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub capability: Capability,
+    pub claimed: bool,
+    pub outcome: Outcome,
+}
+
+/// Outcome of [`run_suite`] for every capability.
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub platform: String,
+    pub results: Vec<ScenarioResult>,
+}
+
+impl ConformanceReport {
+    pub fn outcome(&self, capability: Capability) -> Option<&Outcome> {
+        self.results
+            .iter()
+            .find(|r| r.capability == capability)
+            .map(|r| &r.outcome)
+    }
+
+    pub fn failures(&self) -> Vec<&ScenarioResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, Outcome::Failed(_)))
+            .collect()
+    }
+
+    /// Panic with a per-capability summary if any scenario failed.
+    pub fn assert_conformant(&self) {
+        let failures = self.failures();
+        if failures.is_empty() {
+            return;
+        }
+        let summary = failures
+            .iter()
+            .map(|r| match &r.outcome {
+                Outcome::Failed(reason) => format!("  {}: {reason}", r.capability),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        panic!(
+            "{} channel failed {} conformance scenario(s):\n{summary}",
+            self.platform,
+            failures.len()
+        );
+    }
+}
+
+// ── Mock platform contract ──────────────────────────────────────
+
+/// Where a threaded reply should go and how the platform records it.
+#[derive(Debug, Clone)]
+pub struct ThreadTarget {
+    pub recipient: String,
+    pub thread_ts: Option<String>,
+    /// Thread identifier the mock is expected to record for the reply.
+    pub expected_thread: String,
+}
+
+/// A local emulation of a messaging platform that the suite can drive.
+#[async_trait]
+pub trait MockPlatform: Send + Sync {
+    /// Channel name the platform's messages must carry (`ChannelMessage::channel`).
+    fn name(&self) -> &'static str;
+
+    /// Conversation the suite sends to and receives from.
+    fn recipient(&self) -> String;
+
+    /// Everything the channel sent, edited, deleted or reacted with.
+    fn recorder(&self) -> &Recorder;
+
+    /// Maximum characters per message, when the platform enforces one.
+    fn max_message_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Target for the threaded-reply scenario, when the platform has threads.
+    fn thread_target(&self) -> Option<ThreadTarget> {
+        None
+    }
+
+    /// Whether a channel is currently connected and receiving inbound traffic.
+    fn is_listening(&self) -> bool;
+
+    /// Whether the channel only learns its conversations from the inbound
+    /// connection, so even send scenarios need `listen()` running first.
+    fn requires_listener(&self) -> bool {
+        false
+    }
+
+    /// Deliver a text message from the test user to [`Self::recipient`].
+    async fn push_inbound(&self, text: &str);
+
+    /// Drop the channel's connection. Polling platforms fail the next poll.
+    async fn disconnect(&self);
+
+    /// Answer the most recent approval prompt the way a user would.
+    async fn answer_approval(&self, approve: bool) -> anyhow::Result<()>;
+
+    /// Click choice `index` of the rendered rich message `message_id`.
+    async fn click_rich(&self, message_id: &str, index: usize) -> anyhow::Result<()>;
+
+    /// Whether `emoji` is currently on the message the channel reported as `message_id`.
+    fn has_reaction(&self, message_id: &str, emoji: &str) -> bool;
+}
+
+// ── Recorder ────────────────────────────────────────────────────
+
+/// A message as the mock platform stores it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedMessage {
+    pub id: String,
+    pub recipient: String,
+    pub thread: Option<String>,
+    pub text: String,
+    /// Request body the channel sent, for platform-specific assertions.
+    pub payload: serde_json::Value,
+    pub deleted: bool,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    next_id: u64,
+    messages: Vec<RecordedMessage>,
+    reactions: BTreeSet<(String, String)>,
+    rate_limit: Option<u64>,
+    reject_sends: bool,
+}
+
+/// Shared store of platform-side effects.
+#[derive(Default)]
+pub struct Recorder {
+    state: Mutex<RecorderState>,
+}
+
+impl Recorder {
+    /// Allocate a platform-unique sequence number for message or event ids.
+    pub fn next_seq(&self) -> u64 {
+        let mut state = self.state.lock();
+        state.next_id += 1;
+        state.next_id
+    }
+
+    pub fn record(&self, message: RecordedMessage) {
+        self.state.lock().messages.push(message);
+    }
+
+    /// Replace the text of message `id`. Returns `false` for unknown ids.
+    pub fn edit(&self, id: &str, text: &str) -> bool {
+        let mut state = self.state.lock();
+        match state.messages.iter_mut().find(|m| m.id == id && !m.deleted) {
+            Some(message) => {
+                message.text = text.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut state = self.state.lock();
+        match state.messages.iter_mut().find(|m| m.id == id && !m.deleted) {
+            Some(message) => {
+                message.deleted = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn messages(&self) -> Vec<RecordedMessage> {
+        self.state.lock().messages.clone()
+    }
+
+    pub fn react(&self, message_id: &str, emoji: &str) {
+        self.state
+            .lock()
+            .reactions
+            .insert((message_id.to_string(), emoji.to_string()));
+    }
+
+    pub fn unreact(&self, message_id: &str, emoji: &str) {
+        self.state
+            .lock()
+            .reactions
+            .remove(&(message_id.to_string(), emoji.to_string()));
+    }
+
+    pub fn has_reaction(&self, message_id: &str, emoji: &str) -> bool {
+        self.state
+            .lock()
+            .reactions
+            .contains(&(message_id.to_string(), emoji.to_string()))
+    }
+
+    /// Answer the next send with HTTP 429 and `Retry-After: retry_after_secs`.
+    pub fn rate_limit_next_send(&self, retry_after_secs: u64) {
+        self.state.lock().rate_limit = Some(retry_after_secs);
+    }
+
+    pub fn take_rate_limit(&self) -> Option<u64> {
+        self.state.lock().rate_limit.take()
+    }
+
+    /// Refuse every send with the platform's permission error until cleared.
+    pub fn reject_sends(&self, reject: bool) {
+        self.state.lock().reject_sends = reject;
+    }
+
+    pub fn rejecting_sends(&self) -> bool {
+        self.state.lock().reject_sends
+    }
+
+    /// Most recent live message whose payload contains a string matching `pred`,
+    /// together with that string.
+    pub fn find_payload_string(
+        &self,
+        pred: impl Fn(&str) -> bool,
+    ) -> Option<(RecordedMessage, String)> {
+        let state = self.state.lock();
+        state
+            .messages
+            .iter()
+            .rev()
+            .filter(|m| !m.deleted)
+            .find_map(|m| {
+                find_string(&m.payload, &pred).map(|found| (m.clone(), found.to_string()))
+            })
+    }
+}
+
+fn find_string<'a>(value: &'a serde_json::Value, pred: &impl Fn(&str) -> bool) -> Option<&'a str> {
+    match value {
+        serde_json::Value::String(s) if pred(s) => Some(s),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_string(v, pred)),
+        serde_json::Value::Object(map) => map.values().find_map(|v| find_string(v, pred)),
+        _ => None,
+    }
+}
+
+// ── Server plumbing shared by the mocks ─────────────────────────
+
+/// A running mock HTTP server; aborted on drop.
+pub struct MockServer {
+    addr: SocketAddr,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start(router: axum::Router) -> anyhow::Result<Self> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("bind mock platform server")?;
+        let addr = listener.local_addr()?;
+        let task = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                tracing::warn!("conformance mock server stopped: {e}");
+            }
+        });
+        Ok(Self { addr, task })
+    }
+
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub(crate) enum HubFrame {
+    Text(String),
+    Close,
+}
+
+#[derive(Default)]
+struct HubState {
+    sockets: Vec<mpsc::UnboundedSender<HubFrame>>,
+    pending: VecDeque<String>,
+}
+
+/// Fan-out of server-initiated WebSocket frames. Frames pushed while no
+/// socket is attached are queued and flushed to the next one, the way a
+/// platform replays events missed during a reconnect.
+#[derive(Default)]
+pub(crate) struct SocketHub {
+    state: Mutex<HubState>,
+}
+
+impl SocketHub {
+    pub(crate) fn attach(&self) -> mpsc::UnboundedReceiver<HubFrame> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut state = self.state.lock();
+        while let Some(text) = state.pending.pop_front() {
+            let _ = tx.send(HubFrame::Text(text));
+        }
+        state.sockets.push(tx);
+        rx
+    }
+
+    pub(crate) fn deliver(&self, text: String) {
+        let mut state = self.state.lock();
+        state.sockets.retain(|tx| !tx.is_closed());
+        let delivered = state
+            .sockets
+            .iter()
+            .filter(|tx| tx.send(HubFrame::Text(text.clone())).is_ok())
+            .count();
+        if delivered == 0 {
+            state.pending.push_back(text);
+        }
+    }
+
+    pub(crate) fn disconnect(&self) {
+        let mut state = self.state.lock();
+        for tx in state.sockets.drain(..) {
+            let _ = tx.send(HubFrame::Close);
+        }
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        self.state.lock().sockets.iter().any(|tx| !tx.is_closed())
+    }
+}
+
+/// Forward hub frames to `socket` until either side closes. Client frames
+/// (acks, heartbeats) are read and dropped.
+pub(crate) async fn pump_socket(
+    mut socket: axum::extract::ws::WebSocket,
+    mut frames: mpsc::UnboundedReceiver<HubFrame>,
+) {
+    use axum::extract::ws::Message;
+
+    loop {
+        tokio::select! {
+            frame = frames.recv() => match frame {
+                Some(HubFrame::Text(text)) => {
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(HubFrame::Close) | None => {
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+// ── Suite ───────────────────────────────────────────────────────
+
+/// Supervises `listen()` the way the orchestrator does: restart on return.
+struct Listener {
+    rx: mpsc::Receiver<ChannelMessage>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Listener {
+    fn spawn(channel: Arc<dyn Channel>) -> Self {
+        let (tx, rx) = mpsc::channel(64);
+        let task = tokio::spawn(async move {
+            loop {
+                if let Err(e) = channel.listen(tx.clone()).await {
+                    tracing::debug!("conformance: listen() returned error: {e:#}");
+                }
+                if tx.is_closed() {
+                    break;
+                }
+                tokio::time::sleep(LISTEN_RESTART_DELAY).await;
+            }
+        });
+        Self { rx, task }
+    }
+
+    async fn expect(&mut self, content: &str) -> anyhow::Result<ChannelMessage> {
+        loop {
+            match self.rx.recv().await {
+                Some(message) if message.content.contains(content) => return Ok(message),
+                Some(_) => {}
+                None => bail!("listen() stopped before delivering {content:?}"),
+            }
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn wait_until(what: &str, mut cond: impl FnMut() -> bool) -> anyhow::Result<()> {
+    let deadline = tokio::time::Instant::now() + SCENARIO_TIMEOUT;
+    while !cond() {
+        if tokio::time::Instant::now() >= deadline {
+            bail!("timed out waiting for {what}");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Run every scenario against `channel`, which must be configured to talk to
+/// `platform`. Claimed capabilities must pass; unclaimed ones are checked for
+/// consistency with the channel's own capability flags.
+pub async fn run_suite(
+    channel: Arc<dyn Channel>,
+    platform: &dyn MockPlatform,
+    claims: &[Capability],
+) -> ConformanceReport {
+    let mut report = ConformanceReport {
+        platform: platform.name().to_string(),
+        results: Vec::new(),
+    };
+    let mut listener = (platform.requires_listener() || claims.iter().any(|c| c.needs_listener()))
+        .then(|| Listener::spawn(Arc::clone(&channel)));
+
+    for capability in Capability::ALL {
+        let claimed = claims.contains(&capability);
+        let outcome = if claimed {
+            run_timed(capability, channel.as_ref(), platform, listener.as_mut()).await
+        } else {
+            check_unclaimed(capability, channel.as_ref())
+        };
+        report.results.push(ScenarioResult {
+            capability,
+            claimed,
+            outcome,
+        });
+    }
+    report
+}
+
+/// Run the scenario for a single `capability`, starting `listen()` first when
+/// the scenario or the platform needs it.
+pub async fn run_capability(
+    channel: Arc<dyn Channel>,
+    platform: &dyn MockPlatform,
+    capability: Capability,
+) -> Outcome {
+    let mut listener = (platform.requires_listener() || capability.needs_listener())
+        .then(|| Listener::spawn(Arc::clone(&channel)));
+    run_timed(capability, channel.as_ref(), platform, listener.as_mut()).await
+}
+
+async fn run_timed(
+    capability: Capability,
+    channel: &dyn Channel,
+    platform: &dyn MockPlatform,
+    listener: Option<&mut Listener>,
+) -> Outcome {
+    let scenario = run_scenario(capability, channel, platform, listener);
+    match tokio::time::timeout(SCENARIO_TIMEOUT, scenario).await {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(e)) => Outcome::Failed(format!("{e:#}")),
+        Err(_) => Outcome::Failed(format!("timed out after {}s", SCENARIO_TIMEOUT.as_secs())),
+    }
+}
+
+fn check_unclaimed(capability: Capability, channel: &dyn Channel) -> Outcome {
+    match capability {
+        Capability::DraftUpdates if channel.supports_draft_updates() => Outcome::Failed(
+            "supports_draft_updates() is true but draft_updates is not claimed".into(),
+        ),
+        _ => Outcome::Skipped,
+    }
+}
+
+async fn run_scenario(
+    capability: Capability,
+    channel: &dyn Channel,
+    platform: &dyn MockPlatform,
+    listener: Option<&mut Listener>,
+) -> anyhow::Result<()> {
+    let listener = if capability.needs_listener() || platform.requires_listener() {
+        let listener = listener.context("listener not running")?;
+        wait_until("the channel to connect", || platform.is_listening()).await?;
+        Some(listener)
+    } else {
+        None
+    };
+
+    match capability {
+        Capability::SendText => send_text(channel, platform).await,
+        Capability::ChunkedSend => chunked_send(channel, platform).await,
+        Capability::ThreadedReply => threaded_reply(channel, platform).await,
+        Capability::DraftUpdates => draft_updates(channel, platform).await,
+        Capability::RateLimitSignal => rate_limit_signal(channel, platform).await,
+        Capability::SendRejected => send_rejected(channel, platform).await,
+        Capability::Listen => listen(platform, listener.context("listener")?).await,
+        Capability::Reactions => reactions(channel, platform, listener.context("listener")?).await,
+        Capability::Approvals => approvals(channel, platform).await,
+        Capability::RichMessages => rich_messages(channel, platform).await,
+        Capability::Reconnect => reconnect(platform, listener.context("listener")?).await,
+    }
+}
+
+fn new_messages(platform: &dyn MockPlatform, before: usize) -> Vec<RecordedMessage> {
+    platform
+        .recorder()
+        .messages()
+        .into_iter()
+        .skip(before)
+        .collect()
+}
+
+async fn send_text(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let text = "conformance: plain text";
+    let before = platform.recorder().messages().len();
+    channel
+        .send(&SendMessage::new(text, platform.recipient()))
+        .await
+        .context("send")?;
+
+    let sent = new_messages(platform, before);
+    ensure!(
+        sent.len() == 1,
+        "expected 1 platform message, got {}",
+        sent.len()
+    );
+    ensure!(
+        sent[0].recipient == platform.recipient(),
+        "sent to {:?}, expected {:?}",
+        sent[0].recipient,
+        platform.recipient()
+    );
+    ensure!(
+        sent[0].text.contains(text),
+        "platform received {:?}",
+        sent[0].text
+    );
+    ensure!(
+        sent[0].thread.is_none(),
+        "unthreaded send landed in a thread"
+    );
+    Ok(())
+}
+
+async fn chunked_send(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let limit = platform
+        .max_message_len()
+        .context("platform declares no message length limit")?;
+    let mut words = Vec::new();
+    let mut len = 0;
+    while len <= limit * 2 {
+        let word = format!("w{}", words.len());
+        len += word.len() + 1;
+        words.push(word);
+    }
+    let before = platform.recorder().messages().len();
+    channel
+        .send(&SendMessage::new(words.join(" "), platform.recipient()))
+        .await
+        .context("send")?;
+
+    let sent = new_messages(platform, before);
+    ensure!(
+        sent.len() >= 2,
+        "expected several chunks, got {}",
+        sent.len()
+    );
+    for chunk in &sent {
+        let chars = chunk.text.chars().count();
+        ensure!(
+            chars <= limit,
+            "chunk of {chars} chars exceeds the {limit} limit"
+        );
+    }
+    let received: Vec<&str> = sent
+        .iter()
+        .flat_map(|m| m.text.split_whitespace())
+        .filter(|token| {
+            token.len() > 1
+                && token.starts_with('w')
+                && token[1..].bytes().all(|b| b.is_ascii_digit())
+        })
+        .collect();
+    ensure!(
+        received == words,
+        "chunks do not reassemble to the original text ({} of {} words)",
+        received.len(),
+        words.len()
+    );
+    Ok(())
+}
+
+async fn threaded_reply(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let target = platform
+        .thread_target()
+        .context("platform declares no thread target")?;
+    let before = platform.recorder().messages().len();
+    channel
+        .send(
+            &SendMessage::new("conformance: threaded", &target.recipient)
+                .in_thread(target.thread_ts),
+        )
+        .await
+        .context("send")?;
+
+    let sent = new_messages(platform, before);
+    ensure!(
+        sent.len() == 1,
+        "expected 1 platform message, got {}",
+        sent.len()
+    );
+    ensure!(
+        sent[0].thread.as_deref() == Some(target.expected_thread.as_str()),
+        "reply landed in thread {:?}, expected {:?}",
+        sent[0].thread,
+        target.expected_thread
+    );
+    Ok(())
+}
+
+async fn draft_updates(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    ensure!(
+        channel.supports_draft_updates(),
+        "supports_draft_updates() is false"
+    );
+    let recipient = platform.recipient();
+    let before = platform.recorder().messages().len();
+    let draft_id = channel
+        .send_draft(&SendMessage::new("...", &recipient))
+        .await
+        .context("send_draft")?
+        .context("send_draft returned no draft id")?;
+    channel
+        .update_draft(&recipient, &draft_id, "conformance: draft partial")
+        .await
+        .context("update_draft")?;
+    channel
+        .finalize_draft(&recipient, &draft_id, "conformance: draft final")
+        .await
+        .context("finalize_draft")?;
+
+    let live: Vec<RecordedMessage> = new_messages(platform, before)
+        .into_iter()
+        .filter(|m| !m.deleted)
+        .collect();
+    ensure!(
+        live.len() == 1,
+        "expected the draft to leave exactly 1 visible message, got {}",
+        live.len()
+    );
+    ensure!(
+        live[0].text.contains("conformance: draft final"),
+        "final draft text is {:?}",
+        live[0].text
+    );
+    Ok(())
+}
+
+async fn rate_limit_signal(
+    channel: &dyn Channel,
+    platform: &dyn MockPlatform,
+) -> anyhow::Result<()> {
+    platform.recorder().rate_limit_next_send(7);
+    let err = match channel
+        .send(&SendMessage::new(
+            "conformance: throttled",
+            platform.recipient(),
+        ))
+        .await
+    {
+        Ok(()) => bail!("send succeeded although the platform answered 429"),
+        Err(e) => e,
+    };
+    let limited = err
+        .downcast_ref::<ChannelRateLimited>()
+        .with_context(|| format!("429 surfaced as an untyped error: {err:#}"))?;
+    ensure!(
+        limited.retry_after == Some(Duration::from_secs(7)),
+        "retry_after is {:?}, expected 7s",
+        limited.retry_after
+    );
+
+    channel
+        .send(&SendMessage::new(
+            "conformance: after throttle",
+            platform.recipient(),
+        ))
+        .await
+        .context("send after the rate limit cleared")?;
+    Ok(())
+}
+
+async fn send_rejected(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let before = platform.recorder().messages().len();
+    platform.recorder().reject_sends(true);
+    let result = channel
+        .send(&SendMessage::new(
+            "conformance: refused",
+            platform.recipient(),
+        ))
+        .await;
+    platform.recorder().reject_sends(false);
+    ensure!(
+        result.is_err(),
+        "send reported success although the platform refused it"
+    );
+    ensure!(
+        new_messages(platform, before).is_empty(),
+        "a refused send still recorded a message"
+    );
+
+    channel
+        .send(&SendMessage::new(
+            "conformance: after refusal",
+            platform.recipient(),
+        ))
+        .await
+        .context("send after the platform accepted sends again")?;
+    Ok(())
+}
+
+async fn listen(platform: &dyn MockPlatform, listener: &mut Listener) -> anyhow::Result<()> {
+    let text = "conformance: inbound hello";
+    platform.push_inbound(text).await;
+    let message = listener.expect(text).await?;
+    ensure!(
+        message.channel == platform.name(),
+        "message channel is {:?}",
+        message.channel
+    );
+    ensure!(
+        message.reply_target == platform.recipient(),
+        "reply_target is {:?}, expected {:?}",
+        message.reply_target,
+        platform.recipient()
+    );
+    ensure!(!message.sender.is_empty(), "message has no sender");
+    Ok(())
+}
+
+async fn reactions(
+    channel: &dyn Channel,
+    platform: &dyn MockPlatform,
+    listener: &mut Listener,
+) -> anyhow::Result<()> {
+    let text = "conformance: react to me";
+    platform.push_inbound(text).await;
+    let message = listener.expect(text).await?;
+
+    let emoji = "\u{1F44D}";
+    channel
+        .add_reaction(&message.reply_target, &message.id, emoji)
+        .await
+        .context("add_reaction")?;
+    ensure!(
+        platform.has_reaction(&message.id, emoji),
+        "reaction missing on the platform"
+    );
+    channel
+        .remove_reaction(&message.reply_target, &message.id, emoji)
+        .await
+        .context("remove_reaction")?;
+    ensure!(
+        !platform.has_reaction(&message.id, emoji),
+        "reaction still present after remove_reaction"
+    );
+    Ok(())
+}
+
+async fn approvals(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let request = ChannelApprovalRequest {
+        tool_name: "shell".into(),
+        arguments_summary: "conformance: ls".into(),
+    };
+    let recipient = platform.recipient();
+    let pending = channel.request_approval(&recipient, &request);
+    let answer = async {
+        let deadline = tokio::time::Instant::now() + SCENARIO_TIMEOUT;
+        loop {
+            match platform.answer_approval(true).await {
+                Ok(()) => return Ok(()),
+                Err(e) if tokio::time::Instant::now() >= deadline => return Err(e),
+                Err(_) => tokio::time::sleep(POLL_INTERVAL).await,
+            }
+        }
+    };
+    let (response, answered) = tokio::join!(pending, answer);
+    answered.context("no approval prompt reached the platform")?;
+    let response = response.context("request_approval")?;
+    ensure!(
+        response == Some(ChannelApprovalResponse::Approve),
+        "request_approval returned {response:?}"
+    );
+    Ok(())
+}
+
+async fn rich_messages(channel: &dyn Channel, platform: &dyn MockPlatform) -> anyhow::Result<()> {
+    let message = RichMessage::new()
+        .section("conformance: deploy?")
+        .buttons(vec![
+            RichButton::new("yes", "Ship it"),
+            RichButton::new("no", "Hold"),
+        ]);
+    let mut subscription = rich::subscribe(&message);
+    channel
+        .send(&SendMessage::rich(message.clone(), platform.recipient()))
+        .await
+        .context("send rich message")?;
+    platform
+        .click_rich(&message.id, 1)
+        .await
+        .context("click rendered button")?;
+    let interaction = subscription
+        .recv()
+        .await
+        .context("subscription closed without an interaction")?;
+    ensure!(
+        interaction.action_id == "no" && interaction.value == "Hold",
+        "click resolved to {:?} / {:?}",
+        interaction.action_id,
+        interaction.value
+    );
+    Ok(())
+}
+
+async fn reconnect(platform: &dyn MockPlatform, listener: &mut Listener) -> anyhow::Result<()> {
+    platform.disconnect().await;
+    let text = "conformance: after reconnect";
+    platform.push_inbound(text).await;
+    listener.expect(text).await?;
+    ensure!(platform.is_listening(), "channel did not reconnect");
+    Ok(())
+}
@@ -0,0 +1,367 @@
+//! Telegram Bot API mock.
+//!
+//! Serves `/bot<token>/<method>` with long-polled `getUpdates`. Inbound
+//! messages and button taps are queued as updates; `disconnect` fails the
+//! next poll with a 502, which the channel must ride out.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+use zeroclaw_api::rich;
+
+use super::{Capability, MockPlatform, MockServer, RecordedMessage, Recorder, ThreadTarget};
+
+const CHAT_ID: i64 = 1000;
+const USER_ID: i64 = 4242;
+const USERNAME: &str = "conformance_user";
+/// Longest a `getUpdates` call is held open, regardless of the requested timeout.
+const MAX_POLL_HOLD: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct TelegramState {
+    recorder: Recorder,
+    updates: Mutex<VecDeque<Value>>,
+    wake: Notify,
+    fail_next_poll: AtomicBool,
+    polling: AtomicBool,
+}
+
+impl TelegramState {
+    fn queue_update(&self, mut update: Value) {
+        update["update_id"] = json!(self.recorder.next_seq());
+        self.updates.lock().push_back(update);
+        self.wake.notify_waiters();
+    }
+}
+
+/// Local Telegram Bot API.
+pub struct TelegramMock {
+    server: MockServer,
+    state: Arc<TelegramState>,
+}
+
+impl TelegramMock {
+    /// Capabilities [`crate::telegram::TelegramChannel`] claims when
+    /// configured with partial streaming.
+    pub const CLAIMS: &'static [Capability] = &[
+        Capability::SendText,
+        Capability::ChunkedSend,
+        Capability::ThreadedReply,
+        Capability::DraftUpdates,
+        Capability::SendRejected,
+        Capability::Listen,
+        Capability::Approvals,
+        Capability::RichMessages,
+        Capability::Reconnect,
+    ];
+
+    pub async fn start() -> anyhow::Result<Self> {
+        let state = Arc::new(TelegramState::default());
+        let router = axum::Router::new()
+            .route("/{bot}/{method}", post(handle_method).get(handle_method))
+            .with_state(Arc::clone(&state));
+        let server = MockServer::start(router).await?;
+        Ok(Self { server, state })
+    }
+
+    /// Value for `TelegramChannel::with_api_base`.
+    pub fn api_base(&self) -> String {
+        self.server.http_url()
+    }
+}
+
+async fn handle_method(
+    State(state): State<Arc<TelegramState>>,
+    Path((_bot, method)): Path<(String, String)>,
+    body: Option<Json<Value>>,
+) -> Response {
+    let body = body.map(|Json(v)| v).unwrap_or_default();
+    match method.as_str() {
+        "getUpdates" => get_updates(&state, &body).await,
+        "sendMessage" => send_message(&state, &body),
+        "editMessageText" => {
+            let id = id_string(&body["message_id"]);
+            let text = body["text"].as_str().unwrap_or_default();
+            if state.recorder.edit(&id, text) {
+                ok(json!(true))
+            } else {
+                error(400, "Bad Request: message to edit not found")
+            }
+        }
+        "deleteMessage" => {
+            state.recorder.delete(&id_string(&body["message_id"]));
+            ok(json!(true))
+        }
+        "getMe" => ok(json!({ "id": 1, "is_bot": true, "username": "conformance_bot" })),
+        _ => ok(json!(true)),
+    }
+}
+
+async fn get_updates(state: &TelegramState, body: &Value) -> Response {
+    let offset = body["offset"].as_i64().unwrap_or(0);
+    let timeout = Duration::from_secs(body["timeout"].as_u64().unwrap_or(0)).min(MAX_POLL_HOLD);
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if state.fail_next_poll.swap(false, Ordering::SeqCst) {
+            state.polling.store(false, Ordering::SeqCst);
+            return (StatusCode::BAD_GATEWAY, "Bad Gateway").into_response();
+        }
+        let wake = state.wake.notified();
+        let ready: Vec<Value> = {
+            let mut updates = state.updates.lock();
+            updates.retain(|u| u["update_id"].as_i64().unwrap_or(0) >= offset);
+            updates.iter().cloned().collect()
+        };
+        if !timeout.is_zero() {
+            state.polling.store(true, Ordering::SeqCst);
+        }
+        if !ready.is_empty() || tokio::time::Instant::now() >= deadline {
+            return ok(json!(ready));
+        }
+        let _ = tokio::time::timeout_at(deadline, wake).await;
+    }
+}
+
+fn send_message(state: &TelegramState, body: &Value) -> Response {
+    if let Some(retry_after) = state.recorder.take_rate_limit() {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "ok": false,
+                "error_code": 429,
+                "description": format!("Too Many Requests: retry after {retry_after}"),
+                "parameters": { "retry_after": retry_after },
+            })),
+        )
+            .into_response();
+    }
+    if state.recorder.rejecting_sends() {
+        return error(403, "Forbidden: bot was blocked by the user");
+    }
+
+    let message_id = state.recorder.next_seq();
+    let chat_id = id_string(&body["chat_id"]);
+    let text = body["text"].as_str().unwrap_or_default().to_string();
+    state.recorder.record(RecordedMessage {
+        id: message_id.to_string(),
+        recipient: chat_id.clone(),
+        thread: body.get("message_thread_id").map(id_string),
+        text: text.clone(),
+        payload: body.clone(),
+        deleted: false,
+    });
+    ok(json!({
+        "message_id": message_id,
+        "chat": { "id": chat_id.parse::<i64>().unwrap_or(CHAT_ID) },
+        "text": text,
+    }))
+}
+
+fn ok(result: Value) -> Response {
+    Json(json!({ "ok": true, "result": result })).into_response()
+}
+
+fn error(code: u16, description: &str) -> Response {
+    (
+        StatusCode::from_u16(code).unwrap_or(StatusCode::BAD_REQUEST),
+        Json(json!({ "ok": false, "error_code": code, "description": description })),
+    )
+        .into_response()
+}
+
+/// Telegram ids arrive as numbers or numeric strings depending on the call.
+fn id_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn sender() -> Value {
+    json!({ "id": USER_ID, "is_bot": false, "username": USERNAME, "first_name": "Conformance" })
+}
+
+#[async_trait]
+impl MockPlatform for TelegramMock {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn recipient(&self) -> String {
+        CHAT_ID.to_string()
+    }
+
+    fn recorder(&self) -> &Recorder {
+        &self.state.recorder
+    }
+
+    fn max_message_len(&self) -> Option<usize> {
+        Some(4096)
+    }
+
+    fn thread_target(&self) -> Option<ThreadTarget> {
+        Some(ThreadTarget {
+            recipient: format!("{CHAT_ID}:77"),
+            thread_ts: None,
+            expected_thread: "77".into(),
+        })
+    }
+
+    fn is_listening(&self) -> bool {
+        self.state.polling.load(Ordering::SeqCst)
+    }
+
+    async fn push_inbound(&self, text: &str) {
+        let message_id = self.state.recorder.next_seq();
+        self.state.queue_update(json!({
+            "message": {
+                "message_id": message_id,
+                "date": chrono::Utc::now().timestamp(),
+                "chat": { "id": CHAT_ID, "type": "private" },
+                "from": sender(),
+                "text": text,
+            }
+        }));
+    }
+
+    async fn disconnect(&self) {
+        self.state.fail_next_poll.store(true, Ordering::SeqCst);
+        self.state.polling.store(false, Ordering::SeqCst);
+        self.state.wake.notify_waiters();
+    }
+
+    async fn answer_approval(&self, approve: bool) -> anyhow::Result<()> {
+        let suffix = if approve { ":approve" } else { ":deny" };
+        let (message, data) = self
+            .state
+            .recorder
+            .find_payload_string(|s| s.starts_with("approval:") && s.ends_with(suffix))
+            .ok_or_else(|| anyhow::anyhow!("no approval keyboard sent"))?;
+        self.tap(&message, &data);
+        Ok(())
+    }
+
+    async fn click_rich(&self, message_id: &str, index: usize) -> anyhow::Result<()> {
+        let data = rich::encode_choice(message_id, index);
+        let (message, data) = self
+            .state
+            .recorder
+            .find_payload_string(|s| s == data)
+            .ok_or_else(|| anyhow::anyhow!("no inline button with callback_data {data}"))?;
+        self.tap(&message, &data);
+        Ok(())
+    }
+
+    fn has_reaction(&self, _message_id: &str, _emoji: &str) -> bool {
+        false
+    }
+}
+
+impl TelegramMock {
+    /// Queue a `callback_query` update for a tap on `message`'s inline button.
+    fn tap(&self, message: &RecordedMessage, data: &str) {
+        let mut chat_message = json!({
+            "message_id": message.id.parse::<i64>().unwrap_or_default(),
+            "chat": { "id": CHAT_ID, "type": "private" },
+        });
+        if let Some(thread) = message
+            .thread
+            .as_deref()
+            .and_then(|t| t.parse::<i64>().ok())
+        {
+            chat_message["message_thread_id"] = json!(thread);
+        }
+        self.state.queue_update(json!({
+            "callback_query": {
+                "id": format!("cb{}", self.state.recorder.next_seq()),
+                "from": sender(),
+                "message": chat_message,
+                "data": data,
+            }
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::{Outcome, run_capability, run_suite};
+    use crate::telegram::TelegramChannel;
+    use zeroclaw_config::schema::StreamMode;
+
+    fn channel(mock: &TelegramMock) -> TelegramChannel {
+        TelegramChannel::new("CONFORMANCE".into(), vec!["*".into()], false)
+            .with_api_base(mock.api_base())
+            .with_streaming(StreamMode::Partial, 0)
+    }
+
+    #[tokio::test]
+    async fn telegram_channel_passes_conformance_suite() {
+        let mock = TelegramMock::start().await.unwrap();
+        run_suite(Arc::new(channel(&mock)), &mock, TelegramMock::CLAIMS)
+            .await
+            .assert_conformant();
+    }
+
+    async fn scenario(capability: Capability) -> Outcome {
+        let mock = TelegramMock::start().await.unwrap();
+        run_capability(Arc::new(channel(&mock)), &mock, capability).await
+    }
+
+    #[tokio::test]
+    async fn sends_text() {
+        assert_eq!(scenario(Capability::SendText).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn receives_inbound_messages() {
+        assert_eq!(scenario(Capability::Listen).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn replies_in_threads() {
+        assert_eq!(scenario(Capability::ThreadedReply).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn surfaces_refused_sends_as_errors() {
+        assert_eq!(scenario(Capability::SendRejected).await, Outcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn suite_fails_capabilities_the_channel_does_not_honour() {
+        let mock = TelegramMock::start().await.unwrap();
+        let report = run_suite(
+            Arc::new(channel(&mock)),
+            &mock,
+            &[Capability::SendText, Capability::RateLimitSignal],
+        )
+        .await;
+
+        assert_eq!(report.outcome(Capability::SendText), Some(&Outcome::Passed));
+        // Telegram retries a failed send without parse_mode and reports no
+        // typed rate-limit error, so the claim cannot hold.
+        assert!(matches!(
+            report.outcome(Capability::RateLimitSignal),
+            Some(Outcome::Failed(_))
+        ));
+        // Streaming is on but draft updates were not claimed.
+        assert!(matches!(
+            report.outcome(Capability::DraftUpdates),
+            Some(Outcome::Failed(_))
+        ));
+        assert_eq!(report.outcome(Capability::Listen), Some(&Outcome::Skipped));
+    }
+}
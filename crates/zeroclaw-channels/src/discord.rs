@@ -12,8 +12,10 @@ use tokio::sync::{Mutex as AsyncMutex, oneshot};
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 use zeroclaw_api::channel::{
-    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, SendMessage,
+    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, ChannelRateLimited,
+    SendMessage,
 };
+use zeroclaw_api::rich::{self, ButtonStyle, DispatchOutcome, RichBlock, RichMessage};
 
 /// Discord channel — connects via Gateway WebSocket for real-time messages
 pub struct DiscordChannel {
@@ -49,6 +51,9 @@ pub struct DiscordChannel {
     /// Seconds to wait for an operator reply to a `request_approval` prompt
     /// before treating the silence as a deny. Default 300.
     approval_timeout_secs: u64,
+    /// Base URL for the Discord REST API. Defaults to `https://discord.com/api/v10`.
+    /// Override for testing against a mock server.
+    api_base: String,
 }
 
 impl DiscordChannel {
@@ -79,9 +84,17 @@ impl DiscordChannel {
             stall_timeout_secs: 0,
             pending_approvals: Arc::new(AsyncMutex::new(HashMap::new())),
             approval_timeout_secs: 300,
+            api_base: DISCORD_API_BASE.to_string(),
         }
     }
 
+    /// Override the Discord REST API base URL.
+    /// Useful for testing against a mock server.
+    pub fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
     /// Set a per-channel proxy URL that overrides the global proxy config.
     pub fn with_proxy_url(mut self, proxy_url: Option<String>) -> Self {
         self.proxy_url = proxy_url;
@@ -150,6 +163,79 @@ impl DiscordChannel {
     /// Check if a Discord user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
+    /// Route a rich-message component interaction and acknowledge it.
+    ///
+    /// Unclaimed interactions are forwarded to the agent as ordinary
+    /// messages. Returns `false` once the inbound channel is closed.
+    async fn handle_rich_interaction(
+        &self,
+        interaction: RichComponentInteraction,
+        tx: &tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> bool {
+        if !self.is_user_allowed(&interaction.user_id) {
+            tracing::warn!(
+                "Discord: ignoring interaction from unauthorized user: {}",
+                interaction.user_id
+            );
+            return true;
+        }
+
+        let outcome = rich::dispatch_encoded(
+            &interaction.message_id,
+            &interaction.action,
+            interaction.selected.as_deref(),
+            Default::default(),
+            &interaction.user_id,
+            "discord",
+        );
+        let error_text = match &outcome {
+            None => Some("This prompt has expired.".to_string()),
+            Some(DispatchOutcome::Invalid(errors)) => Some(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            Some(_) => None,
+        };
+        if let Err(e) = respond_to_discord_interaction(
+            &self.http_client(),
+            &self.api_base,
+            &interaction.interaction_id,
+            &interaction.interaction_token,
+            error_text.as_deref(),
+        )
+        .await
+        {
+            tracing::warn!("Discord: {e}");
+        }
+
+        if let Some(DispatchOutcome::Unclaimed(rich_interaction)) = outcome {
+            let reply_target = if interaction.channel_id.is_empty() {
+                interaction.user_id.clone()
+            } else {
+                interaction.channel_id.clone()
+            };
+            let msg = ChannelMessage {
+                id: format!("discord_interaction_{}", interaction.interaction_id),
+                sender: interaction.user_id,
+                reply_target,
+                content: rich_interaction.as_reply_text(),
+                channel: "discord".to_string(),
+                timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                thread_ts: None,
+                interruption_scope_id: None,
+                attachments: vec![],
+            };
+            return tx.send(msg).await.is_ok();
+        }
+        true
+    }
+
     fn is_user_allowed(&self, user_id: &str) -> bool {
         self.allowed_users.iter().any(|u| u == "*" || u == user_id)
     }
@@ -463,11 +549,12 @@ fn with_inline_attachment_urls(
 
 async fn send_discord_message_json(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     recipient: &str,
     content: &str,
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
+    let url = format!("{api_base}/channels/{recipient}/messages");
     let body = json!({ "content": content });
 
     let resp = client
@@ -477,6 +564,13 @@ async fn send_discord_message_json(
         .send()
         .await?;
 
+    if resp.status().as_u16() == 429 {
+        return Err(ChannelRateLimited {
+            retry_after: discord_retry_after(resp.headers()),
+        }
+        .into());
+    }
+
     if !resp.status().is_success() {
         let status = resp.status();
         let err = resp
@@ -489,14 +583,215 @@ async fn send_discord_message_json(
     Ok(())
 }
 
+/// Discord allows at most 5 action rows per message and 5 buttons per row.
+const DISCORD_MAX_ACTION_ROWS: usize = 5;
+const DISCORD_MAX_BUTTONS_PER_ROW: usize = 5;
+const DISCORD_MAX_SELECT_OPTIONS: usize = 25;
+
+/// Build message components (action rows) for a rich message.
+///
+/// Buttons map to component type 2 and select menus to type 3; both carry
+/// `rich:` custom IDs so `INTERACTION_CREATE` events can be routed through
+/// [`rich::dispatch_encoded`]. Forms have no message-component equivalent and
+/// are rendered as text by the caller.
+fn rich_message_components(message: &RichMessage) -> Vec<serde_json::Value> {
+    let mut rows = Vec::new();
+    for (block_index, block) in message.blocks.iter().enumerate() {
+        let first_choice = message.first_choice_index(block_index);
+        match block {
+            RichBlock::Buttons { buttons } => {
+                let buttons: Vec<serde_json::Value> = buttons
+                    .iter()
+                    .enumerate()
+                    .map(|(i, button)| {
+                        let style = match button.style {
+                            ButtonStyle::Primary => 1,
+                            ButtonStyle::Default => 2,
+                            ButtonStyle::Danger => 4,
+                        };
+                        json!({
+                            "type": 2,
+                            "style": style,
+                            "label": button.label.chars().take(80).collect::<String>(),
+                            "custom_id": rich::encode_choice(&message.id, first_choice + i),
+                        })
+                    })
+                    .collect();
+                for chunk in buttons.chunks(DISCORD_MAX_BUTTONS_PER_ROW) {
+                    rows.push(json!({ "type": 1, "components": chunk }));
+                }
+            }
+            RichBlock::Select {
+                placeholder,
+                options,
+            } => {
+                let options: Vec<serde_json::Value> = options
+                    .iter()
+                    .take(DISCORD_MAX_SELECT_OPTIONS)
+                    .enumerate()
+                    .map(|(i, option)| {
+                        json!({
+                            "label": option.label.chars().take(100).collect::<String>(),
+                            "value": (first_choice + i).to_string(),
+                        })
+                    })
+                    .collect();
+                let mut select = json!({
+                    "type": 3,
+                    "custom_id": rich::encode_select(&message.id),
+                    "options": options,
+                });
+                if !placeholder.is_empty() {
+                    select["placeholder"] = json!(placeholder);
+                }
+                rows.push(json!({ "type": 1, "components": [select] }));
+            }
+            _ => {}
+        }
+    }
+    rows.truncate(DISCORD_MAX_ACTION_ROWS);
+    rows
+}
+
+/// Send a rich message with native components.
+async fn send_discord_rich_message(
+    client: &reqwest::Client,
+    api_base: &str,
+    bot_token: &str,
+    recipient: &str,
+    message: &RichMessage,
+) -> anyhow::Result<()> {
+    let url = format!("{api_base}/channels/{recipient}/messages");
+    let content: String = message
+        .body_text()
+        .chars()
+        .take(DISCORD_MAX_MESSAGE_LENGTH)
+        .collect();
+    let body = json!({
+        "content": content,
+        "components": rich_message_components(message),
+    });
+
+    let resp = client
+        .post(&url)
+        .header("Authorization", format!("Bot {bot_token}"))
+        .json(&body)
+        .send()
+        .await?;
+
+    if resp.status().as_u16() == 429 {
+        return Err(ChannelRateLimited {
+            retry_after: discord_retry_after(resp.headers()),
+        }
+        .into());
+    }
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        anyhow::bail!("Discord send rich message failed ({status}): {err}");
+    }
+
+    Ok(())
+}
+
+/// Fields of a message-component `INTERACTION_CREATE` event on a rich message.
+struct RichComponentInteraction {
+    interaction_id: String,
+    interaction_token: String,
+    message_id: String,
+    action: String,
+    selected: Option<String>,
+    user_id: String,
+    channel_id: String,
+}
+
+/// Parse the `d` payload of an `INTERACTION_CREATE` gateway event.
+///
+/// Returns `None` for non-component interactions (type 3) and components
+/// whose custom ID is not a rich action.
+fn parse_rich_component_interaction(d: &serde_json::Value) -> Option<RichComponentInteraction> {
+    if d.get("type").and_then(serde_json::Value::as_u64) != Some(3) {
+        return None;
+    }
+    let data = d.get("data")?;
+    let custom_id = data.get("custom_id").and_then(|v| v.as_str())?;
+    let (message_id, action) = rich::decode_action(custom_id)?;
+    let user_id = d
+        .get("member")
+        .and_then(|m| m.get("user"))
+        .or_else(|| d.get("user"))
+        .and_then(|u| u.get("id"))
+        .and_then(|v| v.as_str())?;
+    Some(RichComponentInteraction {
+        interaction_id: d.get("id").and_then(|v| v.as_str())?.to_string(),
+        interaction_token: d.get("token").and_then(|v| v.as_str())?.to_string(),
+        message_id: message_id.to_string(),
+        action: action.to_string(),
+        selected: data
+            .get("values")
+            .and_then(|v| v.as_array())
+            .and_then(|v| v.first())
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        user_id: user_id.to_string(),
+        channel_id: d
+            .get("channel_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Acknowledge a component interaction. Without an acknowledgement within
+/// three seconds Discord shows "This interaction failed" to the user.
+async fn respond_to_discord_interaction(
+    client: &reqwest::Client,
+    api_base: &str,
+    interaction_id: &str,
+    interaction_token: &str,
+    error_text: Option<&str>,
+) -> anyhow::Result<()> {
+    let url = format!("{api_base}/interactions/{interaction_id}/{interaction_token}/callback");
+    // 6 = DEFERRED_UPDATE_MESSAGE; 4 = CHANNEL_MESSAGE_WITH_SOURCE (ephemeral via flag 64).
+    let body = match error_text {
+        Some(text) => json!({ "type": 4, "data": { "content": text, "flags": 64 } }),
+        None => json!({ "type": 6 }),
+    };
+    let resp = client.post(&url).json(&body).send().await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err = resp.text().await.unwrap_or_default();
+        anyhow::bail!("Discord interaction callback failed ({status}): {err}");
+    }
+    Ok(())
+}
+
+/// Parse Discord's `Retry-After` header (seconds, possibly fractional).
+fn discord_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
 async fn send_discord_message_with_files(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     recipient: &str,
     content: &str,
     files: &[PathBuf],
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
+    let url = format!("{api_base}/channels/{recipient}/messages");
 
     let mut form = Form::new().text("payload_json", json!({ "content": content }).to_string());
 
@@ -540,11 +835,12 @@ async fn send_discord_message_with_files(
 /// Send a message and return the Discord message ID from the response.
 async fn send_discord_message_json_with_id(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     recipient: &str,
     content: &str,
 ) -> anyhow::Result<String> {
-    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
+    let url = format!("{api_base}/channels/{recipient}/messages");
     let body = json!({ "content": content });
 
     let resp = client
@@ -577,12 +873,13 @@ async fn send_discord_message_json_with_id(
 /// level and returns `Ok(())` since skipping a mid-stream edit is harmless.
 async fn edit_discord_message(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     channel_id: &str,
     message_id: &str,
     content: &str,
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{channel_id}/messages/{message_id}");
+    let url = format!("{api_base}/channels/{channel_id}/messages/{message_id}");
     let body = json!({ "content": content });
 
     let resp = client
@@ -615,11 +912,12 @@ async fn edit_discord_message(
 /// level and returns `Ok(())` since a stale message is cosmetic only.
 async fn delete_discord_message(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     channel_id: &str,
     message_id: &str,
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{channel_id}/messages/{message_id}");
+    let url = format!("{api_base}/channels/{channel_id}/messages/{message_id}");
 
     let resp = client
         .delete(&url)
@@ -650,6 +948,9 @@ const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstu
 ///
 /// Discord rejects longer payloads with `50035 Invalid Form Body`.
 const DISCORD_MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Default Discord REST API base URL.
+const DISCORD_API_BASE: &str = "https://discord.com/api/v10";
 const DISCORD_ACK_REACTIONS: &[&str] = &["⚡️", "🦀", "🙌", "💪", "👌", "👀", "👣"];
 
 /// Split a message into chunks that respect Discord's 2000-character limit.
@@ -792,12 +1093,10 @@ fn encode_emoji_for_discord(emoji: &str) -> String {
     encoded
 }
 
-fn discord_reaction_url(channel_id: &str, message_id: &str, emoji: &str) -> String {
+fn discord_reaction_url(api_base: &str, channel_id: &str, message_id: &str, emoji: &str) -> String {
     let raw_id = message_id.strip_prefix("discord_").unwrap_or(message_id);
     let encoded_emoji = encode_emoji_for_discord(emoji);
-    format!(
-        "https://discord.com/api/v10/channels/{channel_id}/messages/{raw_id}/reactions/{encoded_emoji}/@me"
-    )
+    format!("{api_base}/channels/{channel_id}/messages/{raw_id}/reactions/{encoded_emoji}/@me")
 }
 
 fn mention_tags(bot_user_id: &str) -> [String; 2] {
@@ -882,6 +1181,20 @@ impl Channel for DiscordChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        if let Some(rich_message) = &message.rich
+            && !rich_message_components(rich_message).is_empty()
+        {
+            rich::track(rich_message);
+            return send_discord_rich_message(
+                &self.http_client(),
+                &self.api_base,
+                &self.bot_token,
+                &message.recipient,
+                rich_message,
+            )
+            .await;
+        }
+
         let raw_content = crate::util::strip_tool_call_tags(&message.content);
         let (cleaned_content, parsed_attachments) = parse_attachment_markers(&raw_content);
         let (mut local_files, remote_urls, unresolved_markers) =
@@ -916,6 +1229,7 @@ impl Channel for DiscordChannel {
                 if i == 0 && !local_files.is_empty() {
                     send_discord_message_with_files(
                         &client,
+                        &self.api_base,
                         &self.bot_token,
                         &message.recipient,
                         chunk,
@@ -923,8 +1237,14 @@ impl Channel for DiscordChannel {
                     )
                     .await?;
                 } else {
-                    send_discord_message_json(&client, &self.bot_token, &message.recipient, chunk)
-                        .await?;
+                    send_discord_message_json(
+                        &client,
+                        &self.api_base,
+                        &self.bot_token,
+                        &message.recipient,
+                        chunk,
+                    )
+                    .await?;
                 }
 
                 if i < chunks.len() - 1 {
@@ -959,6 +1279,7 @@ impl Channel for DiscordChannel {
             if i == 0 && !local_files.is_empty() {
                 send_discord_message_with_files(
                     &client,
+                    &self.api_base,
                     &self.bot_token,
                     &message.recipient,
                     chunk,
@@ -966,8 +1287,14 @@ impl Channel for DiscordChannel {
                 )
                 .await?;
             } else {
-                send_discord_message_json(&client, &self.bot_token, &message.recipient, chunk)
-                    .await?;
+                send_discord_message_json(
+                    &client,
+                    &self.api_base,
+                    &self.bot_token,
+                    &message.recipient,
+                    chunk,
+                )
+                .await?;
             }
 
             if i < chunks.len() - 1 {
@@ -985,7 +1312,7 @@ impl Channel for DiscordChannel {
         // Get Gateway URL
         let gw_resp: serde_json::Value = self
             .http_client()
-            .get("https://discord.com/api/v10/gateway/bot")
+            .get(format!("{}/gateway/bot", self.api_base))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await?
@@ -1150,8 +1477,19 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
+
+                    // Button clicks and menu selections on rich messages.
+                    if event_type == "INTERACTION_CREATE" {
+                        if let Some(interaction) = event.get("d").and_then(parse_rich_component_interaction)
+                            && !self.handle_rich_interaction(interaction, &tx).await
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Otherwise only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     if event_type != "MESSAGE_CREATE" {
                         continue;
                     }
@@ -1261,7 +1599,8 @@ impl Channel for DiscordChannel {
                             self.allowed_users.clone(),
                             self.listen_to_bots,
                             self.mention_only,
-                        );
+                        )
+                        .with_api_base(self.api_base.clone());
                         let reaction_channel_id = channel_id.clone();
                         let reaction_message_id = message_id.to_string();
                         let reaction_emoji = random_discord_ack_reaction().to_string();
@@ -1322,7 +1661,7 @@ impl Channel for DiscordChannel {
 
     async fn health_check(&self) -> bool {
         self.http_client()
-            .get("https://discord.com/api/v10/users/@me")
+            .get(format!("{}/users/@me", self.api_base))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await
@@ -1335,10 +1674,11 @@ impl Channel for DiscordChannel {
 
         let client = self.http_client();
         let token = self.bot_token.clone();
+        let api_base = self.api_base.clone();
         let channel_id = recipient.to_string();
 
         let handle = tokio::spawn(async move {
-            let url = format!("https://discord.com/api/v10/channels/{channel_id}/typing");
+            let url = format!("{api_base}/channels/{channel_id}/typing");
             loop {
                 let _ = client
                     .post(&url)
@@ -1371,6 +1711,10 @@ impl Channel for DiscordChannel {
         self.stream_mode == zeroclaw_config::schema::StreamMode::MultiMessage
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     fn multi_message_delay_ms(&self) -> u64 {
         self.multi_message_delay_ms
     }
@@ -1389,6 +1733,7 @@ impl Channel for DiscordChannel {
                 let client = self.http_client();
                 let msg_id = send_discord_message_json_with_id(
                     &client,
+                    &self.api_base,
                     &self.bot_token,
                     &message.recipient,
                     &initial_text,
@@ -1453,6 +1798,7 @@ impl Channel for DiscordChannel {
                 let client = self.http_client();
                 match edit_discord_message(
                     &client,
+                    &self.api_base,
                     &self.bot_token,
                     recipient,
                     message_id,
@@ -1597,7 +1943,14 @@ impl Channel for DiscordChannel {
 
         // Path 1: file attachments — delete draft and POST fresh message with files.
         if !local_files.is_empty() {
-            let _ = delete_discord_message(&client, &self.bot_token, recipient, message_id).await;
+            let _ = delete_discord_message(
+                &client,
+                &self.api_base,
+                &self.bot_token,
+                recipient,
+                message_id,
+            )
+            .await;
 
             if local_files.len() > 10 {
                 local_files.truncate(10);
@@ -1607,6 +1960,7 @@ impl Channel for DiscordChannel {
                 if i == 0 {
                     send_discord_message_with_files(
                         &client,
+                        &self.api_base,
                         &self.bot_token,
                         recipient,
                         chunk,
@@ -1614,7 +1968,14 @@ impl Channel for DiscordChannel {
                     )
                     .await?;
                 } else {
-                    send_discord_message_json(&client, &self.bot_token, recipient, chunk).await?;
+                    send_discord_message_json(
+                        &client,
+                        &self.api_base,
+                        &self.bot_token,
+                        recipient,
+                        chunk,
+                    )
+                    .await?;
                 }
                 if i < chunks.len() - 1 {
                     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
@@ -1625,11 +1986,25 @@ impl Channel for DiscordChannel {
 
         // Path 2: text exceeds limit — delete draft and POST as chunked messages.
         if content.chars().count() > DISCORD_MAX_MESSAGE_LENGTH {
-            let _ = delete_discord_message(&client, &self.bot_token, recipient, message_id).await;
+            let _ = delete_discord_message(
+                &client,
+                &self.api_base,
+                &self.bot_token,
+                recipient,
+                message_id,
+            )
+            .await;
 
             let chunks = split_message_for_discord(&content);
             for (i, chunk) in chunks.iter().enumerate() {
-                send_discord_message_json(&client, &self.bot_token, recipient, chunk).await?;
+                send_discord_message_json(
+                    &client,
+                    &self.api_base,
+                    &self.bot_token,
+                    recipient,
+                    chunk,
+                )
+                .await?;
                 if i < chunks.len() - 1 {
                     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                 }
@@ -1638,12 +2013,33 @@ impl Channel for DiscordChannel {
         }
 
         // Path 3: simple case — edit in-place; fall back to delete + POST on failure.
-        if let Err(e) =
-            edit_discord_message(&client, &self.bot_token, recipient, message_id, &content).await
+        if let Err(e) = edit_discord_message(
+            &client,
+            &self.api_base,
+            &self.bot_token,
+            recipient,
+            message_id,
+            &content,
+        )
+        .await
         {
             tracing::warn!("Discord finalize_draft edit failed: {e}; falling back to delete+send");
-            let _ = delete_discord_message(&client, &self.bot_token, recipient, message_id).await;
-            send_discord_message_json(&client, &self.bot_token, recipient, &content).await?;
+            let _ = delete_discord_message(
+                &client,
+                &self.api_base,
+                &self.bot_token,
+                recipient,
+                message_id,
+            )
+            .await;
+            send_discord_message_json(
+                &client,
+                &self.api_base,
+                &self.bot_token,
+                recipient,
+                &content,
+            )
+            .await?;
         }
 
         Ok(())
@@ -1660,8 +2056,14 @@ impl Channel for DiscordChannel {
         self.last_draft_edit.lock().remove(recipient);
 
         let client = self.http_client();
-        if let Err(e) =
-            delete_discord_message(&client, &self.bot_token, recipient, message_id).await
+        if let Err(e) = delete_discord_message(
+            &client,
+            &self.api_base,
+            &self.bot_token,
+            recipient,
+            message_id,
+        )
+        .await
         {
             tracing::debug!("Discord cancel_draft delete failed: {e}");
         }
@@ -1675,7 +2077,7 @@ impl Channel for DiscordChannel {
         message_id: &str,
         emoji: &str,
     ) -> anyhow::Result<()> {
-        let url = discord_reaction_url(channel_id, message_id, emoji);
+        let url = discord_reaction_url(&self.api_base, channel_id, message_id, emoji);
 
         let resp = self
             .http_client()
@@ -1703,7 +2105,7 @@ impl Channel for DiscordChannel {
         message_id: &str,
         emoji: &str,
     ) -> anyhow::Result<()> {
-        let url = discord_reaction_url(channel_id, message_id, emoji);
+        let url = discord_reaction_url(&self.api_base, channel_id, message_id, emoji);
 
         let resp = self
             .http_client()
@@ -1770,6 +2172,19 @@ mod tests {
         assert_eq!(ch.name(), "discord");
     }
 
+    #[test]
+    fn discord_retry_after_parses_fractional_seconds() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "1.5".parse().unwrap());
+        assert_eq!(
+            discord_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+
+        headers.insert(reqwest::header::RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(discord_retry_after(&headers), None);
+    }
+
     #[test]
     fn base64_decode_bot_id() {
         // "MTIzNDU2" decodes to "123456"
@@ -2165,7 +2580,7 @@ mod tests {
 
     #[test]
     fn discord_reaction_url_encodes_emoji_and_strips_prefix() {
-        let url = discord_reaction_url("123", "discord_456", "👀");
+        let url = discord_reaction_url(DISCORD_API_BASE, "123", "discord_456", "👀");
         assert_eq!(
             url,
             "https://discord.com/api/v10/channels/123/messages/456/reactions/%F0%9F%91%80/@me"
@@ -2610,4 +3025,41 @@ mod tests {
         sender.send(ChannelApprovalResponse::Deny).unwrap();
         assert_eq!(rx.await.unwrap(), ChannelApprovalResponse::Deny);
     }
+
+    #[test]
+    fn rich_components_chunk_buttons_into_rows() {
+        use zeroclaw_api::rich::RichButton;
+
+        let buttons = (0..7)
+            .map(|i| RichButton::new(format!("b{i}"), format!("Button {i}")))
+            .collect();
+        let message = RichMessage::new().section("Pick").buttons(buttons);
+        let rows = rich_message_components(&message);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["components"].as_array().unwrap().len(), 5);
+        assert_eq!(
+            rows[1]["components"][1]["custom_id"],
+            rich::encode_choice(&message.id, 6)
+        );
+    }
+
+    #[test]
+    fn parse_rich_component_interaction_reads_select_values() {
+        let d = json!({
+            "type": 3,
+            "id": "i1",
+            "token": "tok",
+            "channel_id": "c1",
+            "member": { "user": { "id": "u1" } },
+            "data": { "custom_id": "rich:m1:select", "values": ["2"] }
+        });
+        let parsed = parse_rich_component_interaction(&d).unwrap();
+        assert_eq!(parsed.message_id, "m1");
+        assert_eq!(parsed.action, "select");
+        assert_eq!(parsed.selected.as_deref(), Some("2"));
+        assert_eq!(parsed.user_id, "u1");
+
+        let slash_command = json!({ "type": 2, "id": "i2", "token": "t", "data": {} });
+        assert!(parse_rich_component_interaction(&slash_command).is_none());
+    }
 }
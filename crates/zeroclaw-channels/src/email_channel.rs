@@ -15,13 +15,16 @@ use async_imap::types::Fetch;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
 use lettre::message::header::ContentType;
-use lettre::message::{Attachment, MultiPart, SinglePart};
+use lettre::message::{Attachment, MultiPart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
 use rustls::{ClientConfig, RootCertStore};
 use rustls_pki_types::DnsName;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpStream;
@@ -42,6 +45,244 @@ type ImapSession = Session<TlsStream<TcpStream>>;
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    threads: Arc<Mutex<EmailThreads>>,
+}
+
+/// Maximum number of ids kept in an outbound `References` header. The thread
+/// root is always kept; the rest are the most recent ancestors (RFC 5322 §3.6.4).
+const MAX_REFERENCES: usize = 20;
+
+/// Threads whose reply headers are remembered; the least recently active
+/// are forgotten first.
+const MAX_THREADS: usize = 1_000;
+/// `Message-ID`s remembered for thread lookup.
+const MAX_THREAD_IDS: usize = 10_000;
+
+/// Thread bookkeeping learned from inbound mail and our own replies.
+///
+/// Every inbound message is mapped to the `Message-ID` of its thread root,
+/// which becomes the message's `thread_ts` so an entire email thread shares
+/// one agent session. Mail delivered to a routed address is keyed by root
+/// and address (see [`thread_key`]), so each route keeps its own session.
+///
+/// Both maps are LRU-bounded. With a store path set they are written to disk
+/// after every change and reloaded on start, so replies after a restart still
+/// land in the right thread.
+struct EmailThreads {
+    /// Message-ID → thread root Message-ID, for every message seen or sent.
+    roots: lru::LruCache<String, String>,
+    /// Thread key → headers needed to reply into that thread.
+    replies: lru::LruCache<String, ReplyContext>,
+    store: Option<PathBuf>,
+}
+
+impl Default for EmailThreads {
+    fn default() -> Self {
+        Self {
+            roots: lru::LruCache::new(NonZeroUsize::new(MAX_THREAD_IDS).expect("non-zero")),
+            replies: lru::LruCache::new(NonZeroUsize::new(MAX_THREADS).expect("non-zero")),
+            store: None,
+        }
+    }
+}
+
+/// On-disk form of [`EmailThreads`], least recently used first.
+#[derive(Default, Serialize, Deserialize)]
+struct ThreadSnapshot {
+    roots: Vec<(String, String)>,
+    replies: Vec<(String, ReplyContext)>,
+}
+
+/// `thread_ts` for a thread root, scoped to the routed address replies are
+/// sent from. Message-IDs cannot contain spaces, so the root is recoverable.
+fn thread_key(root: &str, reply_from: Option<&str>) -> String {
+    match reply_from {
+        Some(from) => format!("{root} {from}"),
+        None => root.to_string(),
+    }
+}
+
+/// Thread root of a [`thread_key`].
+fn thread_root(key: &str) -> &str {
+    key.split_once(' ').map_or(key, |(root, _)| root)
+}
+
+/// Headers for the next reply in a thread.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ReplyContext {
+    /// Subject of the latest inbound message.
+    subject: String,
+    /// `References` chain, oldest first, ending with the `In-Reply-To` target.
+    /// Starts with the thread root and holds at most [`MAX_REFERENCES`] ids.
+    references: Vec<String>,
+    /// `From` address for replies; `None` uses the configured from address.
+    from: Option<String>,
+}
+
+impl ReplyContext {
+    fn in_reply_to(&self) -> Option<&str> {
+        self.references.last().map(String::as_str)
+    }
+
+    /// Append `id`, dropping the oldest ancestors after the root once the
+    /// chain is full.
+    fn push_reference(&mut self, id: String) {
+        self.references.push(id);
+        while self.references.len() > MAX_REFERENCES {
+            self.references.remove(1);
+        }
+    }
+}
+
+impl EmailThreads {
+    /// Load threads saved at `path`; a missing or unreadable file starts empty.
+    fn load(path: PathBuf) -> Self {
+        let mut threads = Self::default();
+        match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<ThreadSnapshot>(&bytes) {
+                Ok(snapshot) => {
+                    for (id, root) in snapshot.roots {
+                        threads.roots.put(id, root);
+                    }
+                    for (key, reply) in snapshot.replies {
+                        threads.replies.put(key, reply);
+                    }
+                }
+                Err(e) => warn!(
+                    "Ignoring corrupt email thread store {}: {e}",
+                    path.display()
+                ),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read email thread store {}: {e}", path.display()),
+        }
+        threads.store = Some(path);
+        threads
+    }
+
+    /// Write the threads to the store path, if any.
+    fn save(&self) {
+        let Some(path) = self.store.as_ref() else {
+            return;
+        };
+        let snapshot = ThreadSnapshot {
+            roots: self
+                .roots
+                .iter()
+                .rev()
+                .map(|(id, root)| (id.clone(), root.clone()))
+                .collect(),
+            replies: self
+                .replies
+                .iter()
+                .rev()
+                .map(|(key, reply)| (key.clone(), reply.clone()))
+                .collect(),
+        };
+        let result = serde_json::to_vec(&snapshot)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let tmp = path.with_extension("json.tmp");
+                std::fs::write(&tmp, json)?;
+                std::fs::rename(&tmp, path)?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!("Failed to save email thread store {}: {e}", path.display());
+        }
+    }
+
+    /// Resolve the thread root for a message from its `In-Reply-To` and
+    /// `References` headers.
+    ///
+    /// An ancestor we have already seen wins, which keeps threads together
+    /// when a client truncates `References`. Otherwise the oldest reference
+    /// is the root, then the parent, and a message with neither starts a new
+    /// thread of its own.
+    fn resolve_root(
+        &self,
+        msg_id: &str,
+        in_reply_to: Option<&str>,
+        references: &[String],
+    ) -> String {
+        in_reply_to
+            .into_iter()
+            .chain(references.iter().rev().map(String::as_str))
+            .find_map(|id| self.roots.peek(id))
+            .cloned()
+            .or_else(|| references.first().cloned())
+            .or_else(|| in_reply_to.map(str::to_string))
+            .unwrap_or_else(|| msg_id.to_string())
+    }
+
+    /// Record an inbound message and return its thread key.
+    fn record_inbound(&mut self, email: &ParsedEmail) -> String {
+        let root = self.resolve_root(
+            &email.msg_id,
+            email.in_reply_to.as_deref(),
+            &email.references,
+        );
+        // The chain always starts at the root, even when the client
+        // truncated it away.
+        let mut reply = ReplyContext {
+            subject: email.subject.clone(),
+            references: vec![root.clone()],
+            from: email.reply_from.clone(),
+        };
+        for id in email
+            .references
+            .iter()
+            .chain(&email.in_reply_to)
+            .chain(std::iter::once(&email.msg_id))
+        {
+            if !reply.references.contains(id) {
+                reply.push_reference(id.clone());
+            }
+        }
+
+        let key = thread_key(&root, email.reply_from.as_deref());
+        self.roots.put(email.msg_id.clone(), root);
+        self.replies.put(key.clone(), reply);
+        self.save();
+        key
+    }
+
+    /// Record a reply we sent so follow-ups to it stay in the same thread.
+    fn record_outbound(&mut self, key: &str, msg_id: &str) {
+        self.roots
+            .put(msg_id.to_string(), thread_root(key).to_string());
+        if let Some(reply) = self.replies.get_mut(key) {
+            reply.push_reference(msg_id.to_string());
+        }
+        self.save();
+    }
+}
+
+/// Check `email` against an allowlist of addresses, `@domain`/`domain`
+/// suffixes and `*`. An empty allowlist denies everyone.
+pub(crate) fn sender_allowed(allowed_senders: &[String], email: &str) -> bool {
+    if allowed_senders.is_empty() {
+        return false; // Empty = deny all
+    }
+    if allowed_senders.iter().any(|a| a == "*") {
+        return true; // Wildcard = allow all
+    }
+    let email_lower = email.to_lowercase();
+    allowed_senders.iter().any(|allowed| {
+        if allowed.starts_with('@') {
+            // Domain match with @ prefix: "@example.com"
+            email_lower.ends_with(&allowed.to_lowercase())
+        } else if allowed.contains('@') {
+            // Full email address match
+            allowed.eq_ignore_ascii_case(email)
+        } else {
+            // Domain match without @ prefix: "example.com"
+            email_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
+        }
+    })
 }
 
 impl EmailChannel {
@@ -49,30 +290,24 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            threads: Arc::new(Mutex::new(EmailThreads::default())),
         }
     }
 
+    /// Persist thread state at `path` (JSON) and resume from it, so replies
+    /// sent after a restart still thread correctly.
+    pub fn with_thread_store(mut self, path: PathBuf) -> Self {
+        self.set_thread_store(path);
+        self
+    }
+
+    pub(crate) fn set_thread_store(&mut self, path: PathBuf) {
+        self.threads = Arc::new(Mutex::new(EmailThreads::load(path)));
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
-        if self.config.allowed_senders.is_empty() {
-            return false; // Empty = deny all
-        }
-        if self.config.allowed_senders.iter().any(|a| a == "*") {
-            return true; // Wildcard = allow all
-        }
-        let email_lower = email.to_lowercase();
-        self.config.allowed_senders.iter().any(|allowed| {
-            if allowed.starts_with('@') {
-                // Domain match with @ prefix: "@example.com"
-                email_lower.ends_with(&allowed.to_lowercase())
-            } else if allowed.contains('@') {
-                // Full email address match
-                allowed.eq_ignore_ascii_case(email)
-            } else {
-                // Domain match without @ prefix: "example.com"
-                email_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
-            }
-        })
+        sender_allowed(&self.config.allowed_senders, email)
     }
 
     /// Strip HTML tags from content (basic)
@@ -128,6 +363,70 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Extract `In-Reply-To` and `References` message ids from a parsed email.
+    fn extract_thread_headers(parsed: &mail_parser::Message) -> (Option<String>, Vec<String>) {
+        let in_reply_to = parsed
+            .in_reply_to()
+            .as_text_list()
+            .and_then(|ids| ids.last())
+            .map(|id| id.to_string());
+        let references = parsed
+            .references()
+            .as_text_list()
+            .map(|ids| ids.iter().map(|id| id.to_string()).collect())
+            .unwrap_or_default();
+        (in_reply_to, references)
+    }
+
+    /// Render markdown to an HTML body part.
+    ///
+    /// Raw HTML in the source is escaped rather than passed through, so model
+    /// output cannot inject markup into the message.
+    fn markdown_to_html(markdown: &str) -> String {
+        use pulldown_cmark::{Event, Options, Parser, html};
+
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+        let parser = Parser::new_ext(markdown, options).map(|event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            other => other,
+        });
+
+        let mut body = String::with_capacity(markdown.len() * 3 / 2);
+        html::push_html(&mut body, parser);
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"></head><body>\n{}</body></html>\n",
+            body
+        )
+    }
+
+    /// Prefix a subject with `Re: ` unless it already carries one.
+    fn reply_subject(subject: &str) -> String {
+        let is_reply = subject
+            .get(..3)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"));
+        if is_reply {
+            subject.to_string()
+        } else {
+            format!("Re: {}", subject)
+        }
+    }
+
+    /// Generate a `Message-ID` (without angle brackets) in the domain of
+    /// the configured from address.
+    fn generate_message_id(&self) -> String {
+        let domain = self
+            .config
+            .from_address
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim_end_matches('>').trim())
+            .filter(|domain| !domain.is_empty())
+            .unwrap_or("zeroclaw.local");
+        format!("{}@{}", Uuid::new_v4(), domain)
+    }
+
     /// Extract binary attachments from a parsed email as MediaAttachment entries.
     fn extract_attachments(
         &self,
@@ -244,53 +543,8 @@ impl EmailChannel {
 
             for msg in messages {
                 let uid = msg.uid.unwrap_or(0);
-                if let Some(body) = msg.body()
-                    && let Some(parsed) = MessageParser::default().parse(body)
-                {
-                    let sender = Self::extract_sender(&parsed);
-                    let subject = parsed.subject().unwrap_or("(no subject)").to_string();
-                    let body_text = Self::extract_text(&parsed);
-                    let content = format!("Subject: {}\n\n{}", subject, body_text);
-                    let msg_id = parsed
-                        .message_id()
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
-
-                    #[allow(clippy::cast_sign_loss)]
-                    let ts = parsed
-                        .date()
-                        .map(|d| {
-                            let naive = chrono::NaiveDate::from_ymd_opt(
-                                d.year as i32,
-                                u32::from(d.month),
-                                u32::from(d.day),
-                            )
-                            .and_then(|date| {
-                                date.and_hms_opt(
-                                    u32::from(d.hour),
-                                    u32::from(d.minute),
-                                    u32::from(d.second),
-                                )
-                            });
-                            naive.map_or(0, |n| n.and_utc().timestamp() as u64)
-                        })
-                        .unwrap_or_else(|| {
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .map(|d| d.as_secs())
-                                .unwrap_or(0)
-                        });
-
-                    let attachments = self.extract_attachments(&parsed);
-
-                    results.push(ParsedEmail {
-                        _uid: uid,
-                        msg_id,
-                        sender,
-                        content,
-                        timestamp: ts,
-                        attachments,
-                    });
+                if let Some(email) = msg.body().and_then(|body| self.parse_email(body, uid)) {
+                    results.push(email);
                 }
             }
 
@@ -305,6 +559,63 @@ impl EmailChannel {
         Ok(results)
     }
 
+    /// Parse a raw RFC 822 message into a [`ParsedEmail`].
+    pub(crate) fn parse_email(&self, raw: &[u8], uid: u32) -> Option<ParsedEmail> {
+        let parsed = MessageParser::default().parse(raw)?;
+        let sender = Self::extract_sender(&parsed);
+        let subject = parsed.subject().unwrap_or("(no subject)").to_string();
+        let body_text = Self::extract_text(&parsed);
+        let content = format!("Subject: {}\n\n{}", subject, body_text);
+        let msg_id = parsed
+            .message_id()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+        let (in_reply_to, references) = Self::extract_thread_headers(&parsed);
+
+        #[allow(clippy::cast_sign_loss)]
+        let ts = parsed
+            .date()
+            .map(|d| {
+                let naive = chrono::NaiveDate::from_ymd_opt(
+                    d.year as i32,
+                    u32::from(d.month),
+                    u32::from(d.day),
+                )
+                .and_then(|date| {
+                    date.and_hms_opt(u32::from(d.hour), u32::from(d.minute), u32::from(d.second))
+                });
+                naive.map_or(0, |n| n.and_utc().timestamp() as u64)
+            })
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+            });
+
+        let attachments = self.extract_attachments(&parsed);
+
+        Some(ParsedEmail {
+            _uid: uid,
+            msg_id,
+            sender,
+            subject,
+            content,
+            timestamp: ts,
+            in_reply_to,
+            references,
+            reply_from: None,
+            attachments,
+        })
+    }
+
+    /// Record an inbound message's place in its thread and return the
+    /// thread key (the root `Message-ID`, scoped to `reply_from` when set),
+    /// used as the message's `thread_ts`.
+    pub(crate) async fn record_thread(&self, email: &ParsedEmail) -> String {
+        self.threads.lock().await.record_inbound(email)
+    }
+
     /// Run the IDLE loop, returning when a new message arrives or timeout
     /// Note: IDLE consumes the session and returns it via done()
     async fn wait_for_changes(
@@ -487,6 +798,8 @@ impl EmailChannel {
                 continue;
             }
 
+            let thread_root = self.record_thread(&email).await;
+
             let msg = ChannelMessage {
                 id: email.msg_id,
                 reply_target: email.sender.clone(),
@@ -494,7 +807,7 @@ impl EmailChannel {
                 content: email.content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
-                thread_ts: None,
+                thread_ts: Some(thread_root),
                 interruption_scope_id: None,
                 attachments: email.attachments,
             };
@@ -508,6 +821,91 @@ impl EmailChannel {
         Ok(())
     }
 
+    /// Build an outbound email: markdown rendered to `multipart/alternative`
+    /// text and HTML, threading headers when replying into a known thread,
+    /// and `message.attachments` as `multipart/mixed` parts.
+    fn build_email(
+        &self,
+        message: &SendMessage,
+        reply: Option<&ReplyContext>,
+        message_id: &str,
+    ) -> Result<Message> {
+        // Use explicit subject if provided, otherwise fall back to legacy parsing,
+        // the thread's subject, or the default
+        let (subject, body) = if let Some(ref subj) = message.subject {
+            (subj.clone(), message.content.as_str())
+        } else if message.content.starts_with("Subject: ")
+            && let Some(pos) = message.content.find('\n')
+        {
+            (
+                message.content[9..pos].to_string(),
+                message.content[pos + 1..].trim(),
+            )
+        } else if let Some(reply) = reply {
+            (
+                Self::reply_subject(&reply.subject),
+                message.content.as_str(),
+            )
+        } else {
+            (
+                self.config.default_subject.clone(),
+                message.content.as_str(),
+            )
+        };
+
+        let from = reply
+            .and_then(|reply| reply.from.as_deref())
+            .unwrap_or(&self.config.from_address);
+        let mut builder = Message::builder()
+            .from(from.parse()?)
+            .to(message.recipient.parse()?)
+            .subject(subject)
+            .message_id(Some(format!("<{}>", message_id)));
+
+        if let Some(reply) = reply
+            && let Some(parent) = reply.in_reply_to()
+        {
+            let references = &reply.references;
+            let kept = if references.len() > MAX_REFERENCES {
+                std::iter::once(&references[0])
+                    .chain(&references[references.len() - (MAX_REFERENCES - 1)..])
+                    .collect::<Vec<_>>()
+            } else {
+                references.iter().collect()
+            };
+            builder = builder.in_reply_to(format!("<{}>", parent)).references(
+                kept.iter()
+                    .map(|id| format!("<{}>", id))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+
+        let alternative =
+            MultiPart::alternative_plain_html(body.to_string(), Self::markdown_to_html(body));
+
+        if message.attachments.is_empty() {
+            return Ok(builder.multipart(alternative)?);
+        }
+
+        let mut mixed = MultiPart::mixed().multipart(alternative);
+        for att in &message.attachments {
+            let content_type = att
+                .mime_type
+                .as_deref()
+                .and_then(|m| ContentType::parse(m).ok())
+                .unwrap_or_else(|| {
+                    ContentType::parse("application/octet-stream").expect("hardcoded MIME type")
+                });
+
+            let attachment =
+                Attachment::new(att.file_name.clone()).body(att.data.clone(), content_type);
+
+            mixed = mixed.singlepart(attachment);
+        }
+        Ok(builder.multipart(mixed)?)
+    }
+
     fn create_smtp_transport(&self) -> Result<SmtpTransport> {
         let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
         let transport = if self.config.smtp_tls {
@@ -525,14 +923,21 @@ impl EmailChannel {
     }
 }
 
-/// Internal struct for parsed email data
-struct ParsedEmail {
-    _uid: u32,
-    msg_id: String,
-    sender: String,
-    content: String,
-    timestamp: u64,
-    attachments: Vec<zeroclaw_api::media::MediaAttachment>,
+/// Parsed email data, shared with the SMTP/LMTP listener channel
+#[derive(Clone)]
+pub(crate) struct ParsedEmail {
+    pub(crate) _uid: u32,
+    pub(crate) msg_id: String,
+    pub(crate) sender: String,
+    pub(crate) subject: String,
+    pub(crate) content: String,
+    pub(crate) timestamp: u64,
+    pub(crate) in_reply_to: Option<String>,
+    pub(crate) references: Vec<String>,
+    /// Address replies in this thread are sent from, when it differs from
+    /// the configured from address.
+    pub(crate) reply_from: Option<String>,
+    pub(crate) attachments: Vec<zeroclaw_api::media::MediaAttachment>,
 }
 
 /// Result from waiting on IDLE
@@ -549,55 +954,21 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
-        let default_subject = self.config.default_subject.as_str();
-        let (subject, body) = if let Some(ref subj) = message.subject {
-            (subj.as_str(), message.content.as_str())
-        } else if message.content.starts_with("Subject: ") {
-            if let Some(pos) = message.content.find('\n') {
-                (&message.content[9..pos], message.content[pos + 1..].trim())
-            } else {
-                (default_subject, message.content.as_str())
-            }
-        } else {
-            (default_subject, message.content.as_str())
+        let thread_root = message.thread_ts.as_deref();
+        let reply = match thread_root {
+            Some(root) => self.threads.lock().await.replies.get(root).cloned(),
+            None => None,
         };
 
-        let email = if message.attachments.is_empty() {
-            // Existing plain-text path
-            Message::builder()
-                .from(self.config.from_address.parse()?)
-                .to(message.recipient.parse()?)
-                .subject(subject)
-                .singlepart(SinglePart::plain(body.to_string()))?
-        } else {
-            // Multipart with attachments
-            let mut multipart = MultiPart::mixed().singlepart(SinglePart::plain(body.to_string()));
-
-            for att in &message.attachments {
-                let content_type = att
-                    .mime_type
-                    .as_deref()
-                    .and_then(|m| ContentType::parse(m).ok())
-                    .unwrap_or_else(|| {
-                        ContentType::parse("application/octet-stream").expect("hardcoded MIME type")
-                    });
-
-                let attachment =
-                    Attachment::new(att.file_name.clone()).body(att.data.clone(), content_type);
-
-                multipart = multipart.singlepart(attachment);
-            }
-
-            Message::builder()
-                .from(self.config.from_address.parse()?)
-                .to(message.recipient.parse()?)
-                .subject(subject)
-                .multipart(multipart)?
-        };
+        let message_id = self.generate_message_id();
+        let email = self.build_email(message, reply.as_ref(), &message_id)?;
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
+
+        if let Some(root) = thread_root {
+            self.threads.lock().await.record_outbound(root, &message_id);
+        }
         info!(
             "Email sent to {} ({} attachments)",
             message.recipient,
@@ -985,6 +1356,257 @@ mod tests {
         );
     }
 
+    // Threading and composition tests
+
+    fn parsed_email(msg_id: &str, in_reply_to: Option<&str>, references: &[&str]) -> ParsedEmail {
+        ParsedEmail {
+            _uid: 1,
+            msg_id: msg_id.to_string(),
+            sender: "alice@example.com".to_string(),
+            subject: "Quarterly report".to_string(),
+            content: String::new(),
+            timestamp: 0,
+            in_reply_to: in_reply_to.map(str::to_string),
+            references: references.iter().map(|r| r.to_string()).collect(),
+            reply_from: None,
+            attachments: vec![],
+        }
+    }
+
+    fn threaded_channel() -> EmailChannel {
+        EmailChannel::new(EmailConfig {
+            from_address: "bot@example.com".to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn extract_thread_headers_reads_message_id_chain() {
+        let raw = b"From: alice@example.com\r\n\
+            To: bot@example.com\r\n\
+            Subject: Re: Quarterly report\r\n\
+            Message-ID: <m3@example.com>\r\n\
+            In-Reply-To: <m2@example.com>\r\n\
+            References: <m1@example.com> <m2@example.com>\r\n\
+            \r\n\
+            Sounds good.\r\n";
+        let parsed = MessageParser::default().parse(raw).unwrap();
+
+        let (in_reply_to, references) = EmailChannel::extract_thread_headers(&parsed);
+        assert_eq!(in_reply_to.as_deref(), Some("m2@example.com"));
+        assert_eq!(references, vec!["m1@example.com", "m2@example.com"]);
+    }
+
+    #[test]
+    fn thread_root_is_oldest_reference() {
+        let mut threads = EmailThreads::default();
+        let root = threads.record_inbound(&parsed_email(
+            "m3@example.com",
+            Some("m2@example.com"),
+            &["m1@example.com", "m2@example.com"],
+        ));
+        assert_eq!(root, "m1@example.com");
+
+        let fresh = threads.record_inbound(&parsed_email("new@example.com", None, &[]));
+        assert_eq!(fresh, "new@example.com");
+    }
+
+    #[test]
+    fn thread_root_follows_known_ancestor_when_references_are_truncated() {
+        let mut threads = EmailThreads::default();
+        let root = threads.record_inbound(&parsed_email("m1@example.com", None, &[]));
+        threads.record_outbound(&root, "reply1@example.com");
+
+        // A client that only sends In-Reply-To still lands in the same thread.
+        let next = threads.record_inbound(&parsed_email(
+            "m2@example.com",
+            Some("reply1@example.com"),
+            &[],
+        ));
+        assert_eq!(next, "m1@example.com");
+
+        // The root stays at the head of the chain even though the client
+        // dropped it from its headers.
+        let reply = threads.replies.peek("m1@example.com").unwrap();
+        assert_eq!(reply.in_reply_to(), Some("m2@example.com"));
+        assert_eq!(
+            reply.references,
+            vec!["m1@example.com", "reply1@example.com", "m2@example.com"]
+        );
+    }
+
+    #[test]
+    fn thread_state_is_bounded_and_keeps_the_root() {
+        let mut threads = EmailThreads::default();
+        let root = threads.record_inbound(&parsed_email("m0@example.com", None, &[]));
+        for i in 1..=MAX_REFERENCES * 2 {
+            threads.record_outbound(&root, &format!("r{i}@example.com"));
+        }
+        let reply = threads.replies.peek(&root).unwrap();
+        assert_eq!(reply.references.len(), MAX_REFERENCES);
+        assert_eq!(reply.references[0], "m0@example.com");
+        assert_eq!(
+            reply.in_reply_to(),
+            Some(format!("r{}@example.com", MAX_REFERENCES * 2).as_str())
+        );
+
+        for i in 0..=MAX_THREADS {
+            threads.record_inbound(&parsed_email(&format!("t{i}@example.com"), None, &[]));
+        }
+        assert_eq!(threads.replies.len(), MAX_THREADS);
+        assert!(threads.replies.peek(&root).is_none());
+        assert!(threads.roots.len() <= MAX_THREAD_IDS);
+    }
+
+    #[test]
+    fn thread_state_survives_a_restart() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("state").join("email_threads.json");
+
+        let mut threads = EmailThreads::load(path.clone());
+        let root = threads.record_inbound(&parsed_email("m1@example.com", None, &[]));
+        threads.record_outbound(&root, "reply1@example.com");
+        drop(threads);
+
+        let mut threads = EmailThreads::load(path);
+        let next = threads.record_inbound(&parsed_email(
+            "m2@example.com",
+            Some("reply1@example.com"),
+            &[],
+        ));
+        assert_eq!(next, "m1@example.com");
+        assert_eq!(
+            threads.replies.peek(&next).unwrap().references,
+            vec!["m1@example.com", "reply1@example.com", "m2@example.com"]
+        );
+    }
+
+    #[test]
+    fn routed_threads_are_keyed_by_delivery_address() {
+        let mut threads = EmailThreads::default();
+        let mut email = parsed_email("m1@example.com", None, &[]);
+        email.reply_from = Some("ops@example.com".into());
+        let ops = threads.record_inbound(&email);
+        email.reply_from = Some("billing@example.com".into());
+        let billing = threads.record_inbound(&email);
+        assert_eq!(ops, "m1@example.com ops@example.com");
+        assert_ne!(ops, billing);
+
+        threads.record_outbound(&ops, "reply1@example.com");
+        assert_eq!(
+            threads.roots.peek("reply1@example.com").map(String::as_str),
+            Some("m1@example.com")
+        );
+        assert_eq!(
+            threads.replies.peek(&ops).unwrap().from.as_deref(),
+            Some("ops@example.com")
+        );
+        assert_eq!(
+            threads.replies.peek(&billing).unwrap().references,
+            vec!["m1@example.com"]
+        );
+    }
+
+    #[test]
+    fn reply_subject_adds_prefix_once() {
+        assert_eq!(EmailChannel::reply_subject("Hello"), "Re: Hello");
+        assert_eq!(EmailChannel::reply_subject("RE: Hello"), "RE: Hello");
+        assert_eq!(EmailChannel::reply_subject("Re"), "Re: Re");
+    }
+
+    #[test]
+    fn markdown_to_html_renders_formatting_and_escapes_raw_html() {
+        let html = EmailChannel::markdown_to_html(
+            "# Title\n\n**bold** and `code`\n\n<script>alert(1)</script>",
+        );
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<code>code</code>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn build_email_sends_threaded_multipart_alternative_reply() {
+        let channel = threaded_channel();
+        let reply = ReplyContext {
+            subject: "Quarterly report".to_string(),
+            references: vec!["m1@example.com".to_string(), "m2@example.com".to_string()],
+            from: None,
+        };
+        let message = SendMessage::new("Here is the **summary**.", "alice@example.com")
+            .in_thread(Some("m1@example.com".to_string()));
+
+        let email = channel
+            .build_email(&message, Some(&reply), "out@example.com")
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("Subject: Re: Quarterly report"));
+        assert!(raw.contains("Message-ID: <out@example.com>"));
+        assert!(raw.contains("In-Reply-To: <m2@example.com>"));
+        assert!(raw.contains("References: <m1@example.com> <m2@example.com>"));
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("Here is the **summary**."));
+        assert!(raw.contains("<strong>summary</strong>"));
+        assert!(!raw.contains("multipart/mixed"));
+    }
+
+    #[test]
+    fn build_email_trims_long_reference_chains_but_keeps_root() {
+        let channel = threaded_channel();
+        let reply = ReplyContext {
+            subject: "Long thread".to_string(),
+            references: (0..30).map(|i| format!("m{i}@example.com")).collect(),
+            from: None,
+        };
+        let message = SendMessage::new("ok", "alice@example.com");
+
+        let email = channel
+            .build_email(&message, Some(&reply), "out@example.com")
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("<m0@example.com>"));
+        assert!(!raw.contains("<m10@example.com>"));
+        assert!(raw.contains("<m11@example.com>"));
+        assert!(raw.contains("In-Reply-To: <m29@example.com>"));
+    }
+
+    #[test]
+    fn build_email_attaches_files_as_mime_parts() {
+        let channel = threaded_channel();
+        let mut message = SendMessage::with_subject("See attached.", "alice@example.com", "Chart");
+        message
+            .attachments
+            .push(zeroclaw_api::media::MediaAttachment {
+                file_name: "chart.png".to_string(),
+                data: vec![0x89, b'P', b'N', b'G'],
+                mime_type: Some("image/png".to_string()),
+            });
+
+        let email = channel
+            .build_email(&message, None, "out@example.com")
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+
+        assert!(raw.contains("Subject: Chart"));
+        assert!(raw.contains("multipart/mixed"));
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("image/png"));
+        assert!(raw.contains("filename=\"chart.png\""));
+        assert!(!raw.contains("In-Reply-To"));
+    }
+
+    #[test]
+    fn generated_message_id_uses_from_domain() {
+        let id = threaded_channel().generate_message_id();
+        assert!(id.ends_with("@example.com"));
+
+        let fallback = EmailChannel::new(EmailConfig::default()).generate_message_id();
+        assert!(fallback.ends_with("@zeroclaw.local"));
+    }
+
     // Default function tests
 
     #[test]
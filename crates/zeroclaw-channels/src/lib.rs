@@ -9,6 +9,8 @@ pub mod util;
 #[cfg(feature = "channel-acp-server")]
 pub mod acp_channel;
 pub mod cli;
+#[cfg(any(test, feature = "conformance"))]
+pub mod conformance;
 pub mod link_enricher;
 pub mod transcription;
 pub mod tts;
@@ -56,6 +58,8 @@ pub mod qq;
 pub mod reddit;
 #[cfg(feature = "channel-signal")]
 pub mod signal;
+#[cfg(feature = "channel-email")]
+pub mod smtp_listener;
 #[cfg(feature = "channel-slack")]
 pub mod slack;
 #[cfg(feature = "channel-telegram")]
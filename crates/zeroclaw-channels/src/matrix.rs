@@ -2785,6 +2785,10 @@ impl Channel for MatrixChannel {
         matches!(self.config.stream_mode, StreamMode::MultiMessage)
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     fn multi_message_delay_ms(&self) -> u64 {
         self.config.multi_message_delay_ms
     }
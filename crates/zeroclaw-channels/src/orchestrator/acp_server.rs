@@ -13,6 +13,7 @@
 //! | `initialize`      | Handshake — returns server capabilities (incl. defaultModel when configured) |
 //! | `session/new`     | Create an isolated agent session          |
 //! | `session/prompt`  | Send a prompt, stream back `session/update` events |
+//! | `session/fork`    | Branch a session at a history entry into a new session |
 //! | `session/stop`    | Gracefully terminate a session            |
 //! | `session/cancel`  | Abort an in-flight `session/prompt` turn  |
 //! | `session/update`  | Streaming events and bidirectional events |
@@ -29,6 +30,7 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::{Mutex, mpsc, oneshot};
 use tracing::{debug, error, warn};
 use uuid::Uuid;
+use zeroclaw_api::provider::ConversationMessage;
 use zeroclaw_config::schema::Config;
 use zeroclaw_runtime::agent::agent::{Agent, TurnEvent};
 
@@ -261,6 +263,8 @@ impl RpcOutbound {
 
 struct Session {
     agent: Agent,
+    /// Canonical cwd the session was created with; forks inherit it.
+    workspace_dir: String,
     #[allow(dead_code)] // WIP: intended for session expiry logic
     created_at: Instant,
     last_active: Instant,
@@ -463,6 +467,7 @@ impl AcpServer {
             "initialize" => self.handle_initialize(&request.params),
             "session/new" => self.handle_session_new(&request.params).await,
             "session/prompt" => self.handle_session_prompt(&request.params, &id).await,
+            "session/fork" => self.handle_session_fork(&request.params).await,
             "session/stop" => self.handle_session_stop(&request.params).await,
             "session/cancel" => self.handle_session_cancel(&request.params).await,
             "session/event" | "session/update" => self.handle_session_event(&request.params).await,
@@ -555,7 +560,26 @@ impl AcpServer {
             .into_owned();
 
         let session_id = Uuid::new_v4().to_string();
+        let session = self
+            .build_session(&session_id, workspace_dir.clone())
+            .await?;
+        sessions.insert(session_id.clone(), Arc::new(Mutex::new(session)));
 
+        debug!("Created session {session_id} (workspace: {workspace_dir})");
+
+        Ok(serde_json::json!({
+            "sessionId": session_id,
+            "workspaceDir": workspace_dir,
+        }))
+    }
+
+    /// Build a session's agent pinned to `workspace_dir` and wire its ACP
+    /// back-channel.
+    async fn build_session(
+        &self,
+        session_id: &str,
+        workspace_dir: String,
+    ) -> std::result::Result<Session, RpcError> {
         // Build agent from global config, with the session's cwd pinned as
         // the file/shell sandbox boundary. The agent's data directory
         // (memory DB, identity, scheduled tasks) still lives under
@@ -578,26 +602,108 @@ impl AcpServer {
         // agent picks a channel.
         let acp_channel = Arc::new(AcpChannel::new(
             "acp",
-            session_id.clone(),
+            session_id.to_string(),
             Arc::clone(&self.rpc),
             Duration::from_secs(self.acp_config.session_timeout_secs),
         ));
         agent.channel_handles().register_channel("acp", acp_channel);
 
         let now = Instant::now();
-        sessions.insert(
-            session_id.clone(),
-            Arc::new(Mutex::new(Session {
-                agent,
-                created_at: now,
-                last_active: now,
-            })),
-        );
+        Ok(Session {
+            agent,
+            workspace_dir,
+            created_at: now,
+            last_active: now,
+        })
+    }
 
-        debug!("Created session {session_id} (workspace: {workspace_dir})");
+    /// `session/fork` — start a new session carrying the first `messageIndex`
+    /// non-system history entries of `sessionId` (all of them when omitted),
+    /// including tool calls and results. The source session is untouched.
+    /// Rejected with `SESSION_BUSY` while the source has a turn in flight.
+    async fn handle_session_fork(&self, params: &Value) -> RpcResult {
+        let source_id = params
+            .get("sessionId")
+            .or_else(|| params.get("session_id"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| RpcError {
+                code: INVALID_PARAMS,
+                message: "Missing required parameter: sessionId".to_string(),
+                data: None,
+            })?
+            .to_string();
+        let requested_at = params
+            .get("messageIndex")
+            .or_else(|| params.get("message_index"))
+            .map(|v| {
+                v.as_u64().ok_or_else(|| RpcError {
+                    code: INVALID_PARAMS,
+                    message: "messageIndex must be a non-negative integer".to_string(),
+                    data: None,
+                })
+            })
+            .transpose()?;
+
+        let mut sessions = self.sessions.lock().await;
+        if sessions.len() >= self.acp_config.max_sessions {
+            return Err(RpcError {
+                code: SESSION_LIMIT_REACHED,
+                message: format!(
+                    "Maximum session limit reached ({})",
+                    self.acp_config.max_sessions
+                ),
+                data: None,
+            });
+        }
+        let source_arc = sessions.get(&source_id).cloned().ok_or_else(|| RpcError {
+            code: SESSION_NOT_FOUND,
+            message: format!("Session not found: {source_id}"),
+            data: None,
+        })?;
+        let (history, workspace_dir) = {
+            let source = source_arc.try_lock().map_err(|_| RpcError {
+                code: SESSION_BUSY,
+                message: format!("Session {source_id} has a turn in flight"),
+                data: None,
+            })?;
+            let history: Vec<ConversationMessage> = source
+                .agent
+                .history()
+                .iter()
+                .filter(|m| !matches!(m, ConversationMessage::Chat(c) if c.role == "system"))
+                .cloned()
+                .collect();
+            (history, source.workspace_dir.clone())
+        };
+
+        let at = match requested_at {
+            None => history.len(),
+            Some(n) => usize::try_from(n)
+                .ok()
+                .filter(|n| (1..=history.len()).contains(n))
+                .ok_or_else(|| RpcError {
+                    code: INVALID_PARAMS,
+                    message: format!(
+                        "messageIndex {n} is outside 1..={} for session {source_id}",
+                        history.len()
+                    ),
+                    data: None,
+                })?,
+        };
+
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = self
+            .build_session(&session_id, workspace_dir.clone())
+            .await?;
+        session.agent.seed_conversation(&history[..at]);
+        sessions.insert(session_id.clone(), Arc::new(Mutex::new(session)));
+
+        debug!("Forked session {source_id} at {at} into {session_id}");
 
         Ok(serde_json::json!({
             "sessionId": session_id,
+            "parentSessionId": source_id,
+            "forkPoint": at,
             "workspaceDir": workspace_dir,
         }))
     }
@@ -1337,6 +1443,83 @@ mod tests {
         assert!(result["sessionId"].as_str().is_some());
     }
 
+    #[tokio::test]
+    async fn session_fork_copies_history_prefix() {
+        let cwd = tempfile::tempdir().unwrap();
+        let config = Config {
+            workspace_dir: cwd.path().to_path_buf(),
+            providers: zeroclaw_config::providers::ProvidersConfig {
+                fallback: Some("openrouter".to_string()),
+                models: HashMap::from([(
+                    "openrouter".to_string(),
+                    zeroclaw_config::schema::ModelProviderConfig {
+                        model: Some("test-model".to_string()),
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let server = AcpServer::new(config, AcpServerConfig::default());
+        let created = server
+            .handle_session_new(&serde_json::json!({ "cwd": cwd.path().to_string_lossy() }))
+            .await
+            .unwrap();
+        let source_id = created["sessionId"].as_str().unwrap().to_string();
+        {
+            let sessions = server.sessions.lock().await;
+            let mut source = sessions[&source_id].lock().await;
+            source.agent.seed_history(&[
+                zeroclaw_api::provider::ChatMessage::user("one"),
+                zeroclaw_api::provider::ChatMessage::assistant("two"),
+                zeroclaw_api::provider::ChatMessage::user("three"),
+            ]);
+        }
+
+        let forked = server
+            .handle_session_fork(&serde_json::json!({
+                "sessionId": source_id,
+                "messageIndex": 2,
+            }))
+            .await
+            .unwrap();
+        assert_eq!(forked["parentSessionId"], source_id.as_str());
+        assert_eq!(forked["forkPoint"], 2);
+        let fork_id = forked["sessionId"].as_str().unwrap();
+        assert_ne!(fork_id, source_id);
+
+        {
+            let sessions = server.sessions.lock().await;
+            let fork = sessions[fork_id].lock().await;
+            let texts: Vec<_> = fork
+                .agent
+                .history()
+                .iter()
+                .filter_map(|m| match m {
+                    ConversationMessage::Chat(c) if c.role != "system" => Some(c.content.clone()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(texts, vec!["one", "two"]);
+            assert_eq!(sessions[&source_id].lock().await.agent.history().len(), 4);
+        }
+
+        let err = server
+            .handle_session_fork(&serde_json::json!({
+                "sessionId": source_id,
+                "messageIndex": 9,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+        let err = server
+            .handle_session_fork(&serde_json::json!({ "sessionId": "missing" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, SESSION_NOT_FOUND);
+    }
+
     #[test]
     fn json_rpc_error_response_serialize() {
         let resp = JsonRpcResponse {
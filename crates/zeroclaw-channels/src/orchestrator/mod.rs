@@ -49,6 +49,8 @@ pub use crate::qq::QQChannel;
 pub use crate::reddit::RedditChannel;
 pub use crate::signal::SignalChannel;
 pub use crate::slack::SlackChannel;
+#[cfg(feature = "channel-email")]
+pub use crate::smtp_listener::SmtpListenerChannel;
 pub use crate::transcription;
 pub use crate::tts::{TtsManager, TtsProvider};
 pub use crate::twitter::TwitterChannel;
@@ -268,6 +270,8 @@ enum ChannelRuntimeCommand {
     SetModel(String),
     ShowConfig,
     NewSession,
+    Fork(Option<String>),
+    Export(Option<String>),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -795,6 +799,12 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
     match base_command.as_str() {
         // `/new` is available on every channel — no model-switch gate.
         "/new" => Some(ChannelRuntimeCommand::NewSession),
+        "/fork" => Some(ChannelRuntimeCommand::Fork(
+            parts.next().map(str::to_string),
+        )),
+        "/export" => Some(ChannelRuntimeCommand::Export(
+            parts.next().map(str::to_string),
+        )),
         // Model/provider switching is channel-gated.
         "/models" if supports_runtime_model_switch(channel_name) => {
             if let Some(provider) = parts.next() {
@@ -1090,6 +1100,29 @@ fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection
     }
 }
 
+/// The `[agents.<name>]` entry an SMTP listener route hands its mail to.
+/// Routed mail arrives on `smtp_listener:<route address>`.
+fn mail_route_agent<'a>(
+    config: &'a zeroclaw_config::schema::Config,
+    channel: &str,
+) -> Option<&'a zeroclaw_config::schema::DelegateAgentConfig> {
+    let address = channel.strip_prefix("smtp_listener:")?;
+    let name = config
+        .channels
+        .smtp_listener
+        .as_ref()?
+        .routes
+        .iter()
+        .find(|route| route.address == address)?
+        .agent
+        .as_deref()?;
+    let agent = config.agents.get(name);
+    if agent.is_none() {
+        tracing::warn!("Mail route {address} names unknown agent `{name}`");
+    }
+    agent
+}
+
 fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> ChannelRouteSelection {
     ctx.route_overrides
         .lock()
@@ -1811,6 +1844,134 @@ fn build_config_block_kit(
     blocks.to_string()
 }
 
+/// `/fork <n>`: move the full conversation to a new session that becomes
+/// the parent, then re-create this sender's live session as its child forked
+/// at message `n`, so the chat continues down the new path. The sender key is
+/// the channel's conversation identity, so the live session has to keep it;
+/// the original path is what moves to a new key.
+fn fork_sender_session(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    raw_point: Option<&str>,
+) -> String {
+    let Some(ref store) = ctx.session_store else {
+        return "Forking needs session persistence (`channels.session_persistence = true`)."
+            .to_string();
+    };
+    let stored = store.load_stored(sender_key);
+    let total = stored.len();
+    let Some(meta) = store.get_session_metadata(sender_key).filter(|_| total > 0) else {
+        return "Nothing to fork yet — this conversation has no saved messages.".to_string();
+    };
+    let Some(raw_point) = raw_point else {
+        return format!(
+            "This conversation has {total} messages. Use `/fork <n>` to continue from message n; \
+             the full conversation is kept as the parent session."
+        );
+    };
+    let at = match raw_point.parse::<usize>() {
+        Ok(at) if (1..=total).contains(&at) => at,
+        _ => return format!("`/fork` takes a message number between 1 and {total}."),
+    };
+
+    let parent_key = format!("{sender_key}_fork_{}", uuid::Uuid::new_v4().simple());
+    let parent_meta = zeroclaw_infra::session_backend::SessionMetadata {
+        key: parent_key.clone(),
+        ..meta.clone()
+    };
+    match store.import_session(&parent_meta, &stored) {
+        Ok(true) => {}
+        Ok(false) => return format!("Fork failed: session '{parent_key}' already exists"),
+        Err(e) => {
+            tracing::warn!("Failed to fork session {sender_key} at {at}: {e}");
+            let _ = store.delete_session(&parent_key);
+            return format!("Fork failed: {e}");
+        }
+    }
+    let moved = store
+        .delete_session(sender_key)
+        .and_then(|_| store.fork_session(&parent_key, sender_key, at));
+    if let Err(e) = moved {
+        // Put the original conversation back under the sender key and drop
+        // the parent copy, so a failed fork leaves the chat as it was.
+        tracing::warn!("Failed to fork session {sender_key} at {at}: {e}");
+        let restored = store
+            .delete_session(sender_key)
+            .and_then(|_| store.import_session(&meta, &stored));
+        return match restored {
+            Ok(true) => {
+                let _ = store.delete_session(&parent_key);
+                format!("Fork failed: {e}")
+            }
+            Ok(false) | Err(_) => format!(
+                "Fork failed: {e}. The full conversation is kept in session `{parent_key}`."
+            ),
+        };
+    }
+
+    let mut kept: Vec<ChatMessage> = stored.into_iter().take(at).map(|m| m.message).collect();
+    if kept.len() > MAX_CHANNEL_HISTORY {
+        kept.drain(..kept.len() - MAX_CHANNEL_HISTORY);
+    }
+    ctx.conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .put(sender_key.to_string(), kept);
+
+    format!(
+        "Forked at message {at}. This chat now continues from there as a branch of \
+         session `{parent_key}`, which keeps the original {total}-message conversation."
+    )
+}
+
+/// `/export [md|html|json]`: write this sender's transcript under
+/// `{workspace}/exports/`. Channels that upload documents get a document
+/// marker; others get a summary naming the file, never its host path.
+/// Channel exports are always scrubbed, JSON included, because the file
+/// leaves the host.
+fn export_sender_session(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    raw_format: Option<&str>,
+    upload: bool,
+) -> String {
+    use zeroclaw_runtime::session_export::{self, ExportFormat};
+
+    let Some(ref store) = ctx.session_store else {
+        return "Exporting needs session persistence (`channels.session_persistence = true`)."
+            .to_string();
+    };
+    let format = match raw_format.unwrap_or("md").parse::<ExportFormat>() {
+        Ok(format) => format,
+        Err(_) => return "`/export` takes one of: md, html, json.".to_string(),
+    };
+    let tracker = ctx.cost_tracking.as_ref().map(|c| c.tracker.as_ref());
+    let Some(transcript) = session_export::export_session(store.as_ref(), sender_key, tracker)
+        .filter(|t| !t.messages.is_empty())
+    else {
+        return "Nothing to export yet — this conversation has no saved messages.".to_string();
+    };
+
+    let dir = ctx.workspace_dir.join("exports");
+    let path = dir.join(session_export::export_file_name(sender_key, format));
+    let rendered = session_export::render(&transcript.redacted(), format);
+    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, rendered)) {
+        tracing::warn!("Failed to export session {sender_key}: {e}");
+        return format!("Export failed: {e}");
+    }
+
+    let count = transcript.messages.len();
+    if upload {
+        format!("Exported {count} messages.\n[DOCUMENT:{}]", path.display())
+    } else {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        format!(
+            "Exported {count} messages to `exports/{file_name}` in the workspace. \
+             This channel cannot receive files, so fetch it from the host."
+        )
+    }
+}
+
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &zeroclaw_api::channel::ChannelMessage,
@@ -1906,11 +2067,23 @@ async fn handle_runtime_command_if_needed(
             mark_sender_for_new_session(ctx, &sender_key);
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::Fork(raw_point) => {
+            fork_sender_session(ctx, &sender_key, raw_point.as_deref())
+        }
+        ChannelRuntimeCommand::Export(raw_format) => export_sender_session(
+            ctx,
+            &sender_key,
+            raw_format.as_deref(),
+            channel.supports_document_upload(),
+        ),
     };
 
-    if let Err(err) = channel
-        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
-        .await
+    if let Err(err) = send_reply(
+        ctx,
+        channel,
+        &SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()),
+    )
+    .await
     {
         tracing::warn!(
             "Failed to send runtime command response on {}: {err}",
@@ -2820,6 +2993,16 @@ async fn process_channel_message(
         };
     }
 
+    // ── Mail routes: the route's agent answers, whatever the classifier says ──
+    let route_agent = mail_route_agent(&ctx.prompt_config, &msg.channel);
+    if let Some(agent) = route_agent {
+        route = ChannelRouteSelection {
+            provider: agent.provider.clone(),
+            model: agent.model.clone(),
+            api_key: agent.api_key.clone(),
+        };
+    }
+
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let mut active_provider = match get_or_create_provider(
         ctx.as_ref(),
@@ -3010,6 +3193,9 @@ async fn process_channel_message(
         &msg.reply_target,
         &msg.sender,
     );
+    if let Some(agent_prompt) = route_agent.and_then(|a| a.system_prompt.as_deref()) {
+        let _ = write!(system_prompt, "\n\n{agent_prompt}");
+    }
     if !memory_context.is_empty() {
         let _ = write!(system_prompt, "\n\n{memory_context}");
     }
@@ -3621,6 +3807,7 @@ async fn process_channel_message(
                         provider.as_ref(),
                         &model,
                         memory.as_ref(),
+                        None,
                         &user_msg,
                         &assistant_resp,
                     )
@@ -3666,20 +3853,22 @@ async fn process_channel_message(
                         .await
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = send_reply(
+                            ctx.as_ref(),
+                            channel,
+                            &SendMessage::new(&delivered_response, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(&delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone())
-                            .with_cancellation(cancellation_token.clone()),
-                    )
-                    .await
+                } else if let Err(e) = send_reply(
+                    ctx.as_ref(),
+                    channel,
+                    &SendMessage::new(&delivered_response, &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone())
+                        .with_cancellation(cancellation_token.clone()),
+                )
+                .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
@@ -3688,12 +3877,13 @@ async fn process_channel_message(
                 // so a dropped send must leave a log signal rather than silently
                 // disappear.
                 if let Some(ref block) = receipts_block
-                    && let Err(e) = channel
-                        .send(
-                            &SendMessage::new(block, &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await
+                    && let Err(e) = send_reply(
+                        ctx.as_ref(),
+                        channel,
+                        &SendMessage::new(block, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await
                 {
                     tracing::warn!(
                         channel = channel.name(),
@@ -3894,6 +4084,16 @@ async fn process_channel_message(
     }
 }
 
+/// Deliver a reply through the durable outbox, so a transient platform
+/// failure is retried rather than dropped.
+async fn send_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &Arc<dyn Channel>,
+    message: &SendMessage,
+) -> anyhow::Result<()> {
+    zeroclaw_runtime::outbox::dispatcher::send(&ctx.prompt_config, channel.as_ref(), message).await
+}
+
 /// Shared worker body extracted so both the normal path and the debounce path
 /// can reuse the same in-flight tracking / cancellation / process logic.
 async fn dispatch_worker(
@@ -3996,10 +4196,14 @@ async fn run_message_dispatch_loop(
             if let Some(channel) = channel {
                 let reply_target = msg.reply_target.clone();
                 let thread_ts = msg.thread_ts.clone();
+                let ctx = Arc::clone(&ctx);
                 tokio::spawn(async move {
-                    let _ = channel
-                        .send(&SendMessage::new(reply, &reply_target).in_thread(thread_ts))
-                        .await;
+                    let _ = send_reply(
+                        &ctx,
+                        &channel,
+                        &SendMessage::new(reply, &reply_target).in_thread(thread_ts),
+                    )
+                    .await;
                 });
             } else {
                 tracing::warn!(
@@ -4237,6 +4441,14 @@ fn maybe_restart_managed_daemon_service() -> Result<bool> {
     Ok(false)
 }
 
+/// Where an email channel keeps its reply-threading state.
+#[cfg(feature = "channel-email")]
+fn email_thread_store(workspace_dir: &Path, channel: &str) -> PathBuf {
+    workspace_dir
+        .join("state")
+        .join(format!("{channel}_threads.json"))
+}
+
 /// Build a single channel instance by config section name (e.g. "telegram").
 fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Channel>> {
     match channel_id {
@@ -4538,7 +4750,9 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
                 .email
                 .as_ref()
                 .context("Email channel is not configured")?;
-            Ok(Arc::new(EmailChannel::new(em.clone())))
+            Ok(Arc::new(EmailChannel::new(em.clone()).with_thread_store(
+                email_thread_store(&config.workspace_dir, "email"),
+            )))
         }
         #[cfg(feature = "channel-email")]
         "gmail_push" | "gmail-push" => {
@@ -4549,6 +4763,18 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
                 .context("Gmail Push channel is not configured")?;
             Ok(Arc::new(GmailPushChannel::new(gp.clone())))
         }
+        #[cfg(feature = "channel-email")]
+        "smtp_listener" | "smtp-listener" => {
+            let sl = config
+                .channels
+                .smtp_listener
+                .as_ref()
+                .context("SMTP listener channel is not configured")?;
+            Ok(Arc::new(
+                SmtpListenerChannel::new(sl.clone())
+                    .with_thread_store(email_thread_store(&config.workspace_dir, "smtp_listener")),
+            ))
+        }
         "irc" => {
             let irc_cfg = config
                 .channels
@@ -4653,7 +4879,7 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
         other => anyhow::bail!(
             "Unknown channel '{other}'. Supported: telegram, discord, slack, mattermost, signal, \
             matrix, whatsapp, qq, lark, feishu, dingtalk, wecom, nextcloud_talk, wati, linq, \
-            email, gmail_push, irc, twitter, mochat, discord_history, imessage, line, voice-call"
+            email, gmail_push, smtp_listener, irc, twitter, mochat, discord_history, imessage, line, voice-call"
         ),
     }
 }
@@ -4667,8 +4893,7 @@ pub async fn send_channel_message(
 ) -> Result<()> {
     let channel = build_channel_by_id(config, channel_id)?;
     let msg = SendMessage::new(message, recipient);
-    channel
-        .send(&msg)
+    zeroclaw_runtime::outbox::dispatcher::send(config, channel.as_ref(), &msg)
         .await
         .with_context(|| format!("Failed to send message via {channel_id}"))?;
     println!("Message sent via {channel_id}.");
@@ -5062,7 +5287,10 @@ fn collect_configured_channels(
         if email_cfg.enabled {
             channels.push(ConfiguredChannel {
                 display_name: "Email",
-                channel: Arc::new(EmailChannel::new(email_cfg.clone())),
+                channel: Arc::new(
+                    EmailChannel::new(email_cfg.clone())
+                        .with_thread_store(email_thread_store(&config.workspace_dir, "email")),
+                ),
             });
         } else {
             tracing::info!("Email channel configured but disabled (enabled = false)");
@@ -5079,6 +5307,19 @@ fn collect_configured_channels(
         });
     }
 
+    #[cfg(feature = "channel-email")]
+    if let Some(ref sl_cfg) = config.channels.smtp_listener
+        && sl_cfg.enabled
+    {
+        channels.push(ConfiguredChannel {
+            display_name: "SMTP Listener",
+            channel: Arc::new(
+                SmtpListenerChannel::new(sl_cfg.clone())
+                    .with_thread_store(email_thread_store(&config.workspace_dir, "smtp_listener")),
+            ),
+        });
+    }
+
     if let Some(ref irc) = config.channels.irc {
         if irc.enabled {
             channels.push(ConfiguredChannel {
@@ -5891,27 +6132,41 @@ pub async fn start_channels(
     );
     let _ = CRON_CHANNEL_REGISTRY.set(Arc::clone(&channels_by_name));
 
-    // Populate the reaction tool's channel map now that channels are initialized.
-    if let Some(ref handle) = reaction_handle_ch {
-        let mut map = handle.write();
-        for (name, ch) in channels_by_name.as_ref() {
-            map.insert(name.clone(), Arc::clone(ch));
-        }
+    // Retry failed outbound sends from the durable outbox through the live
+    // channel instances, building on-demand channels (announcements) from
+    // config. Detached: it runs for the process lifetime.
+    if zeroclaw_runtime::outbox::dispatcher::spawn(
+        config.clone(),
+        &channels_by_name,
+        Some(build_channel_by_id),
+    )
+    .is_some()
+    {
+        println!("  📮 Outbox dispatcher started");
     }
 
-    // Populate the ask_user tool's channel map now that channels are initialized.
-    if let Some(ref handle) = ask_user_handle_ch {
+    // Populate the reaction tool's channel map now that channels are initialized.
+    if let Some(ref handle) = reaction_handle_ch {
         let mut map = handle.write();
         for (name, ch) in channels_by_name.as_ref() {
             map.insert(name.clone(), Arc::clone(ch));
         }
     }
 
-    // Populate the escalate_to_human tool's channel map now that channels are initialized.
-    if let Some(ref handle) = escalate_handle_ch {
+    // Populate the ask_user and escalate_to_human tools' channel maps now that
+    // channels are initialized. Their sends go through the outbox.
+    let outbox_config = Arc::new(config.clone());
+    for handle in [&ask_user_handle_ch, &escalate_handle_ch]
+        .into_iter()
+        .flatten()
+    {
         let mut map = handle.write();
         for (name, ch) in channels_by_name.as_ref() {
-            map.insert(name.clone(), Arc::clone(ch));
+            let wrapped = zeroclaw_runtime::outbox::dispatcher::OutboxChannel::new(
+                Arc::clone(ch),
+                Arc::clone(&outbox_config),
+            );
+            map.insert(name.clone(), Arc::new(wrapped));
         }
     }
 
@@ -6020,6 +6275,11 @@ pub async fn start_channels(
             match zeroclaw_infra::make_session_backend(
                 &config.workspace_dir,
                 &config.channels.session_backend,
+                zeroclaw_infra::PostgresTarget {
+                    db_url: config.storage.provider.config.db_url.as_deref(),
+                    schema: &config.storage.provider.config.schema,
+                    connect_timeout_secs: config.storage.provider.config.connect_timeout_secs,
+                },
             ) {
                 Ok(backend) => {
                     tracing::info!(
@@ -6134,7 +6394,8 @@ pub async fn start_channels(
 }
 
 /// Deliver a cron job announcement to a configured channel.
-/// Scans for credential leaks before delivery.
+/// Scans for credential leaks before delivery. Delivery goes through the
+/// durable outbox, so a failed send is queued for retry rather than lost.
 pub async fn deliver_announcement(
     config: &zeroclaw_config::schema::Config,
     channel: &str,
@@ -6155,10 +6416,15 @@ pub async fn deliver_announcement(
     if let Some(registry) = CRON_CHANNEL_REGISTRY.get()
         && let Some(ch) = registry.get(channel.to_ascii_lowercase().as_str())
     {
-        return ch.send(&SendMessage::new(&safe_output, target)).await;
+        return zeroclaw_runtime::outbox::dispatcher::send(
+            config,
+            ch.as_ref(),
+            &SendMessage::new(&safe_output, target),
+        )
+        .await;
     }
 
-    match channel.to_ascii_lowercase().as_str() {
+    let ch: Arc<dyn Channel> = match channel.to_ascii_lowercase().as_str() {
         #[cfg(feature = "channel-telegram")]
         "telegram" => {
             let tg = config
@@ -6166,13 +6432,11 @@ pub async fn deliver_announcement(
                 .telegram
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("telegram channel not configured"))?;
-            let ch = TelegramChannel::new(
+            Arc::new(TelegramChannel::new(
                 tg.bot_token.clone(),
                 tg.allowed_users.clone(),
                 tg.mention_only,
-            );
-            zeroclaw_api::channel::Channel::send(&ch, &SendMessage::new(&safe_output, target))
-                .await?;
+            ))
         }
         "discord" => {
             let dc = config
@@ -6180,16 +6444,16 @@ pub async fn deliver_announcement(
                 .discord
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("discord channel not configured"))?;
-            let ch = DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
+            Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_workspace_dir(config.workspace_dir.clone()),
             )
-            .with_workspace_dir(config.workspace_dir.clone());
-            zeroclaw_api::channel::Channel::send(&ch, &SendMessage::new(&safe_output, target))
-                .await?;
         }
         "slack" => {
             let sl = config
@@ -6197,15 +6461,15 @@ pub async fn deliver_announcement(
                 .slack
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("slack channel not configured"))?;
-            let ch = SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.app_token.clone(),
-                sl.channel_ids.clone(),
-                sl.allowed_users.clone(),
+            Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.app_token.clone(),
+                    sl.channel_ids.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_workspace_dir(config.workspace_dir.clone()),
             )
-            .with_workspace_dir(config.workspace_dir.clone());
-            zeroclaw_api::channel::Channel::send(&ch, &SendMessage::new(&safe_output, target))
-                .await?;
         }
         "signal" => {
             let sg = config
@@ -6213,16 +6477,14 @@ pub async fn deliver_announcement(
                 .signal
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("signal channel not configured"))?;
-            let ch = SignalChannel::new(
+            Arc::new(SignalChannel::new(
                 sg.http_url.clone(),
                 sg.account.clone(),
                 sg.group_id.clone(),
                 sg.allowed_from.clone(),
                 sg.ignore_attachments,
                 sg.ignore_stories,
-            );
-            zeroclaw_api::channel::Channel::send(&ch, &SendMessage::new(&safe_output, target))
-                .await?;
+            ))
         }
         #[cfg(feature = "channel-wechat")]
         "wechat" => {
@@ -6231,23 +6493,31 @@ pub async fn deliver_announcement(
                 .wechat
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("wechat channel not configured"))?;
-            let ch = WeChatChannel::new(
-                wc.allowed_users.clone(),
-                wc.api_base_url.clone(),
-                wc.cdn_base_url.clone(),
-                wc.state_dir.as_ref().map(std::path::PathBuf::from),
-            )?
-            .with_workspace_dir(config.workspace_dir.clone());
-            zeroclaw_api::channel::Channel::send(&ch, &SendMessage::new(&safe_output, target))
-                .await?;
+            Arc::new(
+                WeChatChannel::new(
+                    wc.allowed_users.clone(),
+                    wc.api_base_url.clone(),
+                    wc.cdn_base_url.clone(),
+                    wc.state_dir.as_ref().map(std::path::PathBuf::from),
+                )?
+                .with_workspace_dir(config.workspace_dir.clone()),
+            )
         }
         #[cfg(not(feature = "channel-wechat"))]
         "wechat" => {
             anyhow::bail!("WeChat channel requires the `channel-wechat` feature");
         }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
-    }
-    Ok(())
+    };
+    // Channels built for this delivery are registered so the dispatcher can
+    // retry a failed send through them.
+    zeroclaw_runtime::outbox::dispatcher::register(Arc::clone(&ch));
+    zeroclaw_runtime::outbox::dispatcher::send(
+        config,
+        ch.as_ref(),
+        &SendMessage::new(&safe_output, target),
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -6304,6 +6574,44 @@ mod tests {
         assert_eq!(channel_message_timeout_budget_secs(300, 3), 900);
     }
 
+    #[test]
+    fn mail_routes_resolve_their_configured_agent() {
+        use zeroclaw_config::scattered_types::{MailRouteConfig, SmtpListenerConfig};
+        use zeroclaw_config::schema::DelegateAgentConfig;
+
+        let mut config = zeroclaw_config::schema::Config::default();
+        let route = |address: &str, agent: Option<&str>| MailRouteConfig {
+            address: address.into(),
+            persona: "p".into(),
+            instructions: None,
+            agent: agent.map(str::to_string),
+            allowed_senders: None,
+        };
+        config.channels.smtp_listener = Some(SmtpListenerConfig {
+            routes: vec![
+                route("ops+agent@", Some("oncall")),
+                route("@billing.example.com", None),
+                route("@legal.example.com", Some("missing")),
+            ],
+            ..SmtpListenerConfig::default()
+        });
+        config.agents.insert(
+            "oncall".into(),
+            DelegateAgentConfig {
+                provider: "anthropic".into(),
+                model: "claude-haiku".into(),
+                ..DelegateAgentConfig::default()
+            },
+        );
+
+        let agent = mail_route_agent(&config, "smtp_listener:ops+agent@").unwrap();
+        assert_eq!(agent.model, "claude-haiku");
+        assert!(mail_route_agent(&config, "smtp_listener:@billing.example.com").is_none());
+        assert!(mail_route_agent(&config, "smtp_listener:@legal.example.com").is_none());
+        assert!(mail_route_agent(&config, "smtp_listener").is_none());
+        assert!(mail_route_agent(&config, "email").is_none());
+    }
+
     #[test]
     fn parse_reply_intent_recognizes_reply_token() {
         assert!(matches!(
@@ -7102,6 +7410,246 @@ mod tests {
         assert_eq!(persisted[1].content, "ok");
     }
 
+    /// A runtime context with inert defaults; tests override the fields they
+    /// exercise with struct update syntax.
+    fn test_runtime_context() -> ChannelRuntimeContext {
+        ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(lru::LruCache::new(
+                std::num::NonZeroUsize::new(MAX_CONVERSATION_SENDERS).unwrap(),
+            ))),
+            pending_new_sessions: Arc::new(Mutex::new(HashSet::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(zeroclaw_config::schema::ReliabilityConfig::default()),
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+                discord: false,
+                mattermost: false,
+                matrix: false,
+            },
+            multimodal: zeroclaw_config::schema::MultimodalConfig::default(),
+            media_pipeline: zeroclaw_config::schema::MediaPipelineConfig::default(),
+            transcription_config: zeroclaw_config::schema::TranscriptionConfig::default(),
+            hooks: None,
+            provider_runtime_options: zeroclaw_providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            prompt_config: Arc::new(zeroclaw_config::schema::Config::default()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            autonomy_level: AutonomyLevel::default(),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            query_classification: zeroclaw_config::schema::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: None,
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &zeroclaw_config::schema::AutonomyConfig::default(),
+            )),
+            activated_tools: None,
+            cost_tracking: None,
+            pacing: zeroclaw_config::schema::PacingConfig::default(),
+            max_tool_result_chars: 0,
+            context_token_budget: 0,
+            debouncer: Arc::new(zeroclaw_infra::debounce::MessageDebouncer::new(
+                Duration::ZERO,
+            )),
+            receipt_generator: None,
+            show_receipts_in_response: false,
+        }
+    }
+
+    #[test]
+    fn fork_command_keeps_original_as_parent_and_rewinds_sender() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let jsonl: Arc<dyn zeroclaw_infra::session_backend::SessionBackend> =
+            Arc::new(zeroclaw_infra::session_store::SessionStore::new(tmp.path()).unwrap());
+        let sqlite: Arc<dyn zeroclaw_infra::session_backend::SessionBackend> = Arc::new(
+            zeroclaw_infra::session_sqlite::SqliteSessionBackend::new(tmp.path()).unwrap(),
+        );
+        for store in [jsonl, sqlite] {
+            assert_fork_keeps_original_as_parent(store);
+        }
+    }
+
+    fn assert_fork_keeps_original_as_parent(
+        store: Arc<dyn zeroclaw_infra::session_backend::SessionBackend>,
+    ) {
+        let sender = "telegram_u5".to_string();
+        let turns = vec![
+            ChatMessage::user("first"),
+            ChatMessage::assistant("ok"),
+            ChatMessage::user("second"),
+            ChatMessage::assistant("done"),
+        ];
+        for turn in &turns {
+            store.append(&sender, turn).unwrap();
+        }
+        let ctx = ChannelRuntimeContext {
+            session_store: Some(Arc::clone(&store)),
+            ..test_runtime_context()
+        };
+        ctx.conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(sender.clone(), turns);
+
+        assert!(matches!(
+            parse_runtime_command("telegram", "/fork 2"),
+            Some(ChannelRuntimeCommand::Fork(Some(ref n))) if n == "2"
+        ));
+        assert!(fork_sender_session(&ctx, &sender, Some("9")).contains("between 1 and 4"));
+
+        let reply = fork_sender_session(&ctx, &sender, Some("2"));
+        assert!(reply.starts_with("Forked at message 2"), "{reply}");
+
+        let live = store.get_session_metadata(&sender).unwrap();
+        assert_eq!(live.message_count, 2);
+        assert_eq!(live.fork_point, Some(2));
+        let parent_key = live.parent_key.expect("live chat forked from the original");
+        assert_eq!(store.load(&sender)[1].content, "ok");
+        let cached = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .peek(&sender)
+            .cloned()
+            .unwrap();
+        assert_eq!(cached.len(), 2);
+
+        let parent = store.get_session_metadata(&parent_key).unwrap();
+        assert!(parent.parent_key.is_none(), "original stays the root");
+        let original = store.load(&parent_key);
+        assert_eq!(original.len(), 4);
+        assert_eq!(original[3].content, "done");
+    }
+
+    /// Session store whose `fork_session` always fails, to exercise rollback.
+    struct ForkFailingStore(zeroclaw_infra::session_store::SessionStore);
+
+    impl zeroclaw_infra::session_backend::SessionBackend for ForkFailingStore {
+        fn load(&self, session_key: &str) -> Vec<ChatMessage> {
+            self.0.load(session_key)
+        }
+        fn load_stored(
+            &self,
+            session_key: &str,
+        ) -> Vec<zeroclaw_infra::session_backend::StoredMessage> {
+            self.0.load_stored(session_key)
+        }
+        fn append(&self, session_key: &str, message: &ChatMessage) -> std::io::Result<()> {
+            self.0.append(session_key, message)
+        }
+        fn remove_last(&self, session_key: &str) -> std::io::Result<bool> {
+            self.0.remove_last(session_key)
+        }
+        fn list_sessions(&self) -> Vec<String> {
+            self.0.list_sessions()
+        }
+        fn delete_session(&self, session_key: &str) -> std::io::Result<bool> {
+            self.0.delete_session(session_key)
+        }
+        fn get_session_metadata(
+            &self,
+            session_key: &str,
+        ) -> Option<zeroclaw_infra::session_backend::SessionMetadata> {
+            self.0.get_session_metadata(session_key)
+        }
+        fn fork_session(&self, _source: &str, _target: &str, _at: usize) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+        fn import_session(
+            &self,
+            meta: &zeroclaw_infra::session_backend::SessionMetadata,
+            messages: &[zeroclaw_infra::session_backend::StoredMessage],
+        ) -> std::io::Result<bool> {
+            self.0.import_session(meta, messages)
+        }
+    }
+
+    #[test]
+    fn failed_fork_restores_the_original_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store: Arc<dyn zeroclaw_infra::session_backend::SessionBackend> = Arc::new(
+            ForkFailingStore(zeroclaw_infra::session_store::SessionStore::new(tmp.path()).unwrap()),
+        );
+        let sender = "telegram_u7".to_string();
+        for turn in [ChatMessage::user("first"), ChatMessage::assistant("ok")] {
+            store.append(&sender, &turn).unwrap();
+        }
+        let ctx = ChannelRuntimeContext {
+            session_store: Some(Arc::clone(&store)),
+            ..test_runtime_context()
+        };
+
+        let reply = fork_sender_session(&ctx, &sender, Some("1"));
+        assert_eq!(reply, "Fork failed: disk full");
+        assert_eq!(store.load(&sender).len(), 2);
+        assert_eq!(store.list_sessions(), vec![sender]);
+    }
+
+    #[test]
+    fn export_command_writes_scrubbed_transcript_and_replies_with_document() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store: Arc<dyn zeroclaw_infra::session_backend::SessionBackend> =
+            Arc::new(zeroclaw_infra::session_store::SessionStore::new(tmp.path()).unwrap());
+
+        let sender = "telegram_u6".to_string();
+        let turns = vec![
+            ChatMessage::user("my key is sk-proj-abcdefghijklmnopqrstuvwxyz0123456789ABCD"),
+            ChatMessage::assistant("noted"),
+        ];
+        for turn in &turns {
+            store.append(&sender, turn).unwrap();
+        }
+        let ctx = ChannelRuntimeContext {
+            session_store: Some(Arc::clone(&store)),
+            workspace_dir: Arc::new(tmp.path().to_path_buf()),
+            ..test_runtime_context()
+        };
+
+        assert!(matches!(
+            parse_runtime_command("discord", "/export html"),
+            Some(ChannelRuntimeCommand::Export(Some(ref f))) if f == "html"
+        ));
+        assert!(export_sender_session(&ctx, &sender, Some("pdf"), true).contains("md, html, json"));
+        assert!(export_sender_session(&ctx, "telegram_nobody", None, true).starts_with("Nothing"));
+
+        // Channels that cannot upload files never see the host path.
+        let summary = export_sender_session(&ctx, &sender, Some("md"), false);
+        assert!(summary.contains("`exports/"), "{summary}");
+        assert!(!summary.contains("[DOCUMENT:"));
+        assert!(!summary.contains(tmp.path().to_str().unwrap()));
+
+        let reply = export_sender_session(&ctx, &sender, Some("json"), true);
+        assert!(reply.starts_with("Exported 2 messages."), "{reply}");
+        let path = reply
+            .split("[DOCUMENT:")
+            .nth(1)
+            .and_then(|rest| rest.strip_suffix(']'))
+            .expect("document marker");
+        assert!(path.starts_with(tmp.path().join("exports").to_str().unwrap()));
+        let written = std::fs::read_to_string(path).unwrap();
+        assert!(written.contains("\"redacted\": true"));
+        assert!(!written.contains("abcdefghijklmnopqrstuvwxyz0123456789"));
+        assert!(written.contains("noted"));
+    }
+
     struct DummyProvider;
 
     #[async_trait::async_trait]
@@ -982,6 +982,10 @@ impl Channel for QQChannel {
         "qq"
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let (cleaned_text, attachments) = parse_qq_attachment_markers(&message.content);
 
@@ -12,7 +12,11 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::{Mutex as AsyncMutex, oneshot};
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 use zeroclaw_api::channel::{
-    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, SendMessage,
+    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, ChannelRateLimited,
+    SendMessage,
+};
+use zeroclaw_api::rich::{
+    self, ButtonStyle, DispatchOutcome, FormFieldKind, RichBlock, RichMessage,
 };
 
 #[derive(Clone)]
@@ -60,6 +64,9 @@ pub struct SlackChannel {
     /// Seconds to wait for an operator reply to a `request_approval` prompt
     /// before treating the silence as a deny. Default 300.
     approval_timeout_secs: u64,
+    /// Base URL for the Slack Web API. Defaults to `https://slack.com/api`.
+    /// Override for testing against a mock server.
+    api_base: String,
 }
 
 const SLACK_HISTORY_MAX_RETRIES: u32 = 3;
@@ -121,7 +128,7 @@ fn extract_slack_ts(message_id: &str) -> &str {
 ///
 /// The orchestration layer passes Unicode characters (e.g. `"\u{1F440}"`).
 /// Slack's reactions API expects colon-free short-names (`"eyes"`).
-fn unicode_emoji_to_slack_name(emoji: &str) -> &str {
+pub(crate) fn unicode_emoji_to_slack_name(emoji: &str) -> &str {
     match emoji {
         "\u{1F440}" => "eyes",                        // 👀
         "\u{2705}" => "white_check_mark",             // ✅
@@ -190,9 +197,21 @@ impl SlackChannel {
             cancel_reaction: None,
             pending_approvals: Arc::new(AsyncMutex::new(HashMap::new())),
             approval_timeout_secs: 300,
+            api_base: "https://slack.com/api".to_string(),
         }
     }
 
+    /// Override the Slack Web API base URL.
+    /// Useful for testing against a mock server.
+    pub fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
+    fn api_url(&self, method: &str) -> String {
+        format!("{}/{method}", self.api_base)
+    }
+
     /// Configure group-chat trigger policy.
     pub fn with_group_reply_policy(
         mut self,
@@ -289,7 +308,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.delete")
+            .post(self.api_url("chat.delete"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -351,7 +370,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -402,7 +421,7 @@ impl SlackChannel {
 
         let _ = self
             .http_client()
-            .post("https://slack.com/api/assistant.threads.setStatus")
+            .post(self.api_url("assistant.threads.setStatus"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -431,7 +450,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -477,7 +496,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.update")
+            .post(self.api_url("chat.update"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -536,7 +555,7 @@ impl SlackChannel {
     async fn get_bot_user_id(&self) -> Option<String> {
         let resp: serde_json::Value = self
             .http_client()
-            .get("https://slack.com/api/auth.test")
+            .get(self.api_url("auth.test"))
             .bearer_auth(&self.bot_token)
             .send()
             .await
@@ -705,7 +724,7 @@ impl SlackChannel {
     async fn fetch_sender_display_name(&self, user_id: &str) -> Option<String> {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/users.info")
+            .get(self.api_url("users.info"))
             .bearer_auth(&self.bot_token)
             .query(&[("user", user_id)])
             .send()
@@ -1014,7 +1033,7 @@ impl SlackChannel {
     ) -> SlackPermalinkLookup {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/conversations.history")
+            .get(self.api_url("conversations.history"))
             .bearer_auth(&self.bot_token)
             .query(&[
                 ("channel", channel_id),
@@ -1301,7 +1320,7 @@ impl SlackChannel {
     async fn fetch_file_info(&self, file_id: &str) -> Option<serde_json::Value> {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/files.info")
+            .get(self.api_url("files.info"))
             .bearer_auth(&self.bot_token)
             .query(&[("file", file_id)])
             .send()
@@ -2389,7 +2408,7 @@ impl SlackChannel {
 
             let resp = self
                 .http_client()
-                .get("https://slack.com/api/conversations.list")
+                .get(self.api_url("conversations.list"))
                 .bearer_auth(&self.bot_token)
                 .query(&query_params)
                 .send()
@@ -2484,6 +2503,77 @@ impl SlackChannel {
         Some((token.to_string(), response))
     }
 
+    /// Route a Socket Mode `block_actions` envelope for a rich message through
+    /// [`rich::dispatch_encoded`].
+    ///
+    /// Returns the dispatch outcome together with the acting user, channel ID
+    /// and thread anchor, or `None` when the action is not a rich action.
+    fn dispatch_rich_block_action(
+        envelope: &serde_json::Value,
+    ) -> Option<(DispatchOutcome, String, String, Option<String>)> {
+        let payload = envelope.get("payload")?;
+        if payload.get("type").and_then(|v| v.as_str())? != "block_actions" {
+            return None;
+        }
+        let action = payload
+            .get("actions")
+            .and_then(|a| a.as_array())
+            .and_then(|a| a.first())?;
+        let action_id = action.get("action_id").and_then(|v| v.as_str())?;
+        let (message_id, action_ref) = rich::decode_action(action_id)?;
+        let selected = action
+            .get("selected_option")
+            .and_then(|o| o.get("value"))
+            .and_then(|v| v.as_str());
+
+        // Form values: state.values.<block_id = field id>.<action_id>.{value|selected_option}
+        let mut values = std::collections::BTreeMap::new();
+        if let Some(blocks) = payload
+            .get("state")
+            .and_then(|s| s.get("values"))
+            .and_then(|v| v.as_object())
+        {
+            for (field_id, elements) in blocks {
+                let Some(element) = elements.as_object().and_then(|e| e.values().next()) else {
+                    continue;
+                };
+                let value = element
+                    .get("value")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| {
+                        element
+                            .get("selected_option")
+                            .and_then(|o| o.get("value"))
+                            .and_then(|v| v.as_str())
+                    })
+                    .unwrap_or_default();
+                values.insert(field_id.clone(), value.to_string());
+            }
+        }
+
+        let user = payload
+            .get("user")
+            .and_then(|u| u.get("id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let channel_id = payload
+            .get("channel")
+            .and_then(|c| c.get("id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let thread_ts = payload
+            .get("message")
+            .and_then(|m| m.get("thread_ts"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        let outcome =
+            rich::dispatch_encoded(message_id, action_ref, selected, values, &user, "slack")?;
+        Some((outcome, user, channel_id, thread_ts))
+    }
+
     /// Parse a Socket Mode `interactive` envelope containing a `block_actions`
     /// payload from the `/config` Block Kit UI.  Translates provider/model
     /// dropdown selections into synthetic `/models <provider>` or `/model <id>`
@@ -2564,7 +2654,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/apps.connections.open")
+            .post(self.api_url("apps.connections.open"))
             .bearer_auth(app_token)
             .send()
             .await?;
@@ -2698,7 +2788,8 @@ impl SlackChannel {
                     break;
                 }
 
-                // Handle interactive payloads (block_actions from /config UI or approval buttons).
+                // Handle interactive payloads (block_actions from /config UI, approval buttons
+                // or rich messages).
                 if envelope_type == "interactive" {
                     if let Some((token, response)) =
                         Self::try_parse_approval_block_action(&envelope)
@@ -2709,6 +2800,54 @@ impl SlackChannel {
                         }
                         continue;
                     }
+                    if let Some((outcome, user, channel_id, thread_ts)) =
+                        Self::dispatch_rich_block_action(&envelope)
+                    {
+                        match outcome {
+                            DispatchOutcome::Delivered => {}
+                            DispatchOutcome::Unclaimed(interaction) => {
+                                if !self.is_user_allowed(&user) || channel_id.is_empty() {
+                                    continue;
+                                }
+                                let msg = ChannelMessage {
+                                    id: format!(
+                                        "slack_{channel_id}_{}_rich",
+                                        interaction.message_id
+                                    ),
+                                    sender: user,
+                                    reply_target: channel_id,
+                                    content: interaction.as_reply_text(),
+                                    channel: "slack".to_string(),
+                                    timestamp: SystemTime::now()
+                                        .duration_since(UNIX_EPOCH)
+                                        .unwrap_or_default()
+                                        .as_secs(),
+                                    thread_ts,
+                                    interruption_scope_id: None,
+                                    attachments: vec![],
+                                };
+                                if tx.send(msg).await.is_err() {
+                                    return Ok(());
+                                }
+                            }
+                            DispatchOutcome::Invalid(errors) => {
+                                let text = errors
+                                    .iter()
+                                    .map(|e| format!("• {e}"))
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
+                                let reply = SendMessage::new(
+                                    format!("⚠️ Please fix the form:\n{text}"),
+                                    channel_id,
+                                )
+                                .in_thread(thread_ts);
+                                if let Err(e) = self.send(&reply).await {
+                                    tracing::warn!("Slack: failed to report form errors: {e}");
+                                }
+                            }
+                        }
+                        continue;
+                    }
                     if let Some(msg) = Self::parse_block_action_as_command(&envelope, bot_user_id)
                         && tx.send(msg).await.is_err()
                     {
@@ -3031,7 +3170,7 @@ impl SlackChannel {
         for attempt in 0..=SLACK_HISTORY_MAX_RETRIES {
             let resp = match self
                 .http_client()
-                .get("https://slack.com/api/conversations.history")
+                .get(self.api_url("conversations.history"))
                 .bearer_auth(&self.bot_token)
                 .query(params)
                 .send()
@@ -3125,7 +3264,7 @@ impl SlackChannel {
         for attempt in 0..=SLACK_HISTORY_MAX_RETRIES {
             let resp = match self
                 .http_client()
-                .get("https://slack.com/api/conversations.replies")
+                .get(self.api_url("conversations.replies"))
                 .bearer_auth(&self.bot_token)
                 .query(&[
                     ("channel", channel_id),
@@ -3326,6 +3465,123 @@ fn split_text_into_chunks(text: &str, max_chars: usize, max_chunks: usize) -> Ve
     chunks
 }
 
+/// Render a [`RichMessage`] as Block Kit blocks.
+///
+/// Buttons and select options carry the flattened choice index so that
+/// `block_actions` callbacks can be routed through [`rich::dispatch_encoded`].
+/// Forms render as `input` blocks followed by a submit button; Slack reports
+/// the entered values in the callback's `state.values`, keyed by field id.
+fn rich_message_to_block_kit(message: &RichMessage) -> Vec<serde_json::Value> {
+    fn plain(text: &str) -> serde_json::Value {
+        serde_json::json!({ "type": "plain_text", "text": text, "emoji": true })
+    }
+
+    let mut blocks = Vec::new();
+    for (block_index, block) in message.blocks.iter().enumerate() {
+        let first_choice = message.first_choice_index(block_index);
+        match block {
+            RichBlock::Header { text } => blocks.push(serde_json::json!({
+                "type": "header",
+                "text": plain(text),
+            })),
+            RichBlock::Section { text } => blocks.push(serde_json::json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": text },
+            })),
+            RichBlock::Divider => blocks.push(serde_json::json!({ "type": "divider" })),
+            RichBlock::Buttons { buttons } => {
+                let elements: Vec<serde_json::Value> = buttons
+                    .iter()
+                    .enumerate()
+                    .map(|(i, button)| {
+                        let index = first_choice + i;
+                        let mut element = serde_json::json!({
+                            "type": "button",
+                            "text": plain(&button.label),
+                            "action_id": rich::encode_choice(&message.id, index),
+                            "value": index.to_string(),
+                        });
+                        match button.style {
+                            ButtonStyle::Primary => element["style"] = "primary".into(),
+                            ButtonStyle::Danger => element["style"] = "danger".into(),
+                            ButtonStyle::Default => {}
+                        }
+                        element
+                    })
+                    .collect();
+                blocks.push(serde_json::json!({ "type": "actions", "elements": elements }));
+            }
+            RichBlock::Select {
+                placeholder,
+                options,
+            } => {
+                let options: Vec<serde_json::Value> = options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| {
+                        serde_json::json!({
+                            "text": plain(&option.label),
+                            "value": (first_choice + i).to_string(),
+                        })
+                    })
+                    .collect();
+                blocks.push(serde_json::json!({
+                    "type": "actions",
+                    "elements": [{
+                        "type": "static_select",
+                        "placeholder": plain(if placeholder.is_empty() { "Choose" } else { placeholder }),
+                        "action_id": rich::encode_select(&message.id),
+                        "options": options,
+                    }],
+                }));
+            }
+            RichBlock::Form(form) => {
+                for field in &form.fields {
+                    let mut element = match &field.kind {
+                        FormFieldKind::Text => serde_json::json!({ "type": "plain_text_input" }),
+                        FormFieldKind::Number => serde_json::json!({
+                            "type": "number_input",
+                            "is_decimal_allowed": true,
+                        }),
+                        FormFieldKind::Email => serde_json::json!({ "type": "email_text_input" }),
+                        FormFieldKind::Choice { options } => serde_json::json!({
+                            "type": "static_select",
+                            "options": options
+                                .iter()
+                                .map(|o| serde_json::json!({ "text": plain(o), "value": o }))
+                                .collect::<Vec<_>>(),
+                        }),
+                    };
+                    element["action_id"] = field.id.clone().into();
+                    if let Some(max) = field.max_length
+                        && matches!(field.kind, FormFieldKind::Text)
+                    {
+                        element["max_length"] = max.into();
+                    }
+                    blocks.push(serde_json::json!({
+                        "type": "input",
+                        "block_id": field.id,
+                        "optional": !field.required,
+                        "label": plain(&field.label),
+                        "element": element,
+                    }));
+                }
+                blocks.push(serde_json::json!({
+                    "type": "actions",
+                    "elements": [{
+                        "type": "button",
+                        "style": "primary",
+                        "text": plain(&form.submit_label),
+                        "action_id": rich::encode_submit(&message.id),
+                    }],
+                }));
+            }
+        }
+    }
+    blocks.truncate(SLACK_MAX_BLOCKS_PER_MESSAGE);
+    blocks
+}
+
 #[async_trait]
 impl Channel for SlackChannel {
     fn name(&self) -> &str {
@@ -3333,8 +3589,20 @@ impl Channel for SlackChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        // Detect Block Kit payloads produced by the `/config` command.
-        let body = if let Some(blocks_json) =
+        // Rich messages render as Block Kit; `/config` responses arrive as
+        // prefixed raw Block Kit JSON.
+        let body = if let Some(rich_message) = &message.rich {
+            rich::track(rich_message);
+            let mut body = serde_json::json!({
+                "channel": message.recipient,
+                "text": message.content,
+                "blocks": rich_message_to_block_kit(rich_message),
+            });
+            if let Some(ts) = self.outbound_thread_ts(message) {
+                body["thread_ts"] = serde_json::json!(ts);
+            }
+            body
+        } else if let Some(blocks_json) =
             message.content.strip_prefix(crate::util::BLOCK_KIT_PREFIX)
         {
             let blocks: serde_json::Value = serde_json::from_str(blocks_json)
@@ -3399,13 +3667,19 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
             .await?;
 
         let status = resp.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ChannelRateLimited {
+                retry_after: Self::parse_retry_after_secs(resp.headers()).map(Duration::from_secs),
+            }
+            .into());
+        }
         let body = resp
             .text()
             .await
@@ -3503,6 +3777,7 @@ impl Channel for SlackChannel {
         };
 
         let client = self.http_client();
+        let url = self.api_url("chat.update");
         let token = self.bot_token.clone();
         let channel = recipient.to_string();
         tokio::spawn(async move {
@@ -3518,7 +3793,7 @@ impl Channel for SlackChannel {
                 }]);
             }
             match client
-                .post("https://slack.com/api/chat.update")
+                .post(url)
                 .bearer_auth(&token)
                 .json(&body)
                 .send()
@@ -3614,7 +3889,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.update")
+            .post(self.api_url("chat.update"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3668,7 +3943,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/reactions.add")
+            .post(self.api_url("reactions.add"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3713,7 +3988,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/reactions.remove")
+            .post(self.api_url("reactions.remove"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -4023,7 +4298,7 @@ impl Channel for SlackChannel {
     async fn health_check(&self) -> bool {
         let bot_ok = match self
             .http_client()
-            .get("https://slack.com/api/auth.test")
+            .get(self.api_url("auth.test"))
             .bearer_auth(&self.bot_token)
             .send()
             .await
@@ -4065,7 +4340,7 @@ impl Channel for SlackChannel {
         // Gracefully ignore errors — non-assistant contexts will return errors.
         if let Ok(resp) = self
             .http_client()
-            .post("https://slack.com/api/assistant.threads.setStatus")
+            .post(self.api_url("assistant.threads.setStatus"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -4126,7 +4401,7 @@ impl Channel for SlackChannel {
                 }]
             });
             self.http_client()
-                .post("https://slack.com/api/chat.postMessage")
+                .post(self.api_url("chat.postMessage"))
                 .bearer_auth(&self.bot_token)
                 .json(&body)
                 .send()
@@ -5273,4 +5548,68 @@ mod tests {
         });
         assert!(SlackChannel::try_parse_approval_block_action(&envelope).is_none());
     }
+
+    #[test]
+    fn rich_message_renders_buttons_select_and_form_inputs() {
+        use zeroclaw_api::rich::{FormField, RichButton, RichForm, RichOption};
+
+        let message = RichMessage::new()
+            .header("Deploy")
+            .buttons(vec![RichButton::new("go", "Go").primary()])
+            .select("Region", vec![RichOption::new("EU", "eu")])
+            .form(RichForm::new(
+                "notes",
+                vec![FormField::new("reason", "Reason", FormFieldKind::Text).required()],
+                "Submit",
+            ));
+        let blocks = rich_message_to_block_kit(&message);
+        let types: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+        assert_eq!(types, ["header", "actions", "actions", "input", "actions"]);
+        assert_eq!(blocks[1]["elements"][0]["style"], "primary");
+        assert_eq!(
+            blocks[1]["elements"][0]["action_id"],
+            rich::encode_choice(&message.id, 0)
+        );
+        assert_eq!(blocks[2]["elements"][0]["options"][0]["value"], "1");
+        assert_eq!(blocks[3]["block_id"], "reason");
+        assert_eq!(blocks[3]["optional"], false);
+    }
+
+    #[tokio::test]
+    async fn rich_form_submission_is_validated_and_delivered() {
+        use zeroclaw_api::rich::{FormField, RichForm};
+
+        let message = RichMessage::new().form(RichForm::new(
+            "contact",
+            vec![FormField::new("email", "Email", FormFieldKind::Email).required()],
+            "Send",
+        ));
+        let mut subscription = rich::subscribe(&message);
+        let envelope = |email: &str| {
+            serde_json::json!({
+                "type": "interactive",
+                "payload": {
+                    "type": "block_actions",
+                    "user": { "id": "U1" },
+                    "channel": { "id": "C1" },
+                    "actions": [{ "action_id": rich::encode_submit(&message.id) }],
+                    "state": { "values": {
+                        "email": { "email": { "type": "email_text_input", "value": email } }
+                    } }
+                }
+            })
+        };
+
+        let (outcome, user, channel, _) =
+            SlackChannel::dispatch_rich_block_action(&envelope("nope")).unwrap();
+        assert!(matches!(outcome, DispatchOutcome::Invalid(_)));
+        assert_eq!((user.as_str(), channel.as_str()), ("U1", "C1"));
+
+        let (outcome, _, _, _) =
+            SlackChannel::dispatch_rich_block_action(&envelope("a@b.io")).unwrap();
+        assert_eq!(outcome, DispatchOutcome::Delivered);
+        let interaction = subscription.recv().await.unwrap();
+        assert_eq!(interaction.action_id, "contact");
+        assert_eq!(interaction.fields["email"], "a@b.io");
+    }
 }
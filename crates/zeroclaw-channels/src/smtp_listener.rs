@@ -0,0 +1,1022 @@
+//! Embedded SMTP/LMTP listener channel.
+//!
+//! Lets a local MTA (Postfix, Exim, …) hand mail straight to the agent
+//! instead of going through an IMAP mailbox. Point a transport at the
+//! listener, e.g. for Postfix:
+//!
+//! ```text
+//! # main.cf
+//! mailbox_transport = lmtp:inet:127.0.0.1:2525
+//! ```
+//!
+//! Each message is checked against the receiving MTA's
+//! `Authentication-Results` (SPF, DKIM, DMARC), then routed by recipient
+//! address ([`MailRouteConfig`]): every route among the recipients gets its
+//! own copy, vetted against that route's sender allowlist and delivered on
+//! `smtp_listener:<route address>`, which the orchestrator uses to pick the
+//! route's agent. Threads are tracked from the `Message-ID` chain exactly
+//! like [`EmailChannel`], per route, and replies go out through its SMTP
+//! sender — from the routed address when the message came in on a route.
+//!
+//! The listener speaks plain SMTP/LMTP without STARTTLS or AUTH, so it should
+//! only be bound to loopback or a private network the MTA shares.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mail_parser::MessageParser;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::{debug, info, warn};
+
+use zeroclaw_api::channel::{Channel, ChannelMessage, SendMessage};
+
+use crate::email_channel::{EmailChannel, EmailConfig, ParsedEmail, sender_allowed};
+
+pub use zeroclaw_config::scattered_types::{
+    MailListenerProtocol, MailRouteConfig, SmtpListenerConfig,
+};
+
+/// Idle time allowed between client commands (RFC 5321 §4.5.3.2).
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
+/// Longest command line accepted, including CRLF.
+const MAX_COMMAND_LINE: u64 = 4096;
+/// Read size for `DATA` lines; longer lines are read in several chunks.
+const MAX_DATA_CHUNK: u64 = 64 * 1024;
+/// Maximum recipients per transaction (RFC 5321 §4.5.3.1.8 minimum).
+const MAX_RECIPIENTS: usize = 100;
+/// `Message-ID`s remembered to drop MTA redeliveries; the oldest are evicted.
+const MAX_SEEN_MESSAGES: usize = 10_000;
+
+/// SMTP/LMTP listener channel — local MTA delivery in, SMTP out.
+pub struct SmtpListenerChannel {
+    state: Arc<ListenerState>,
+}
+
+struct ListenerState {
+    config: SmtpListenerConfig,
+    /// Parses inbound mail, tracks threads and sends replies over SMTP.
+    outbound: EmailChannel,
+    seen_messages: Mutex<lru::LruCache<String, ()>>,
+    listening: AtomicBool,
+}
+
+impl SmtpListenerChannel {
+    pub fn new(config: SmtpListenerConfig) -> Self {
+        let outbound = EmailChannel::new(EmailConfig {
+            enabled: true,
+            smtp_host: config.smtp_host.clone(),
+            smtp_port: config.smtp_port,
+            smtp_tls: config.smtp_tls,
+            username: config.username.clone(),
+            password: config.password.clone(),
+            from_address: config.from_address.clone(),
+            default_subject: config.default_subject.clone(),
+            max_attachment_bytes: config.max_attachment_bytes,
+            ..EmailConfig::default()
+        });
+        Self {
+            state: Arc::new(ListenerState {
+                config,
+                outbound,
+                seen_messages: Mutex::new(lru::LruCache::new(
+                    NonZeroUsize::new(MAX_SEEN_MESSAGES).expect("non-zero capacity"),
+                )),
+                listening: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Persist reply threading at `path`; see [`EmailChannel::with_thread_store`].
+    pub fn with_thread_store(mut self, path: std::path::PathBuf) -> Self {
+        Arc::get_mut(&mut self.state)
+            .expect("listener state is not shared before listen")
+            .outbound
+            .set_thread_store(path);
+        self
+    }
+
+    /// Accept connections on `listener` until `tx` is closed.
+    pub async fn serve(
+        &self,
+        listener: TcpListener,
+        tx: mpsc::Sender<ChannelMessage>,
+    ) -> Result<()> {
+        self.state.listening.store(true, Ordering::SeqCst);
+        let result = loop {
+            tokio::select! {
+                () = tx.closed() => break Ok(()),
+                accepted = listener.accept() => {
+                    let (stream, peer) = match accepted {
+                        Ok(conn) => conn,
+                        Err(e) => break Err(e.into()),
+                    };
+                    debug!("SMTP listener: connection from {}", peer);
+                    let state = Arc::clone(&self.state);
+                    let tx = tx.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_session(&state, stream, &tx).await {
+                            debug!("SMTP listener: session with {} ended: {}", peer, e);
+                        }
+                    });
+                }
+            }
+        };
+        self.state.listening.store(false, Ordering::SeqCst);
+        result
+    }
+}
+
+// ── Recipient routing ────────────────────────────────────────────
+
+/// Whether `recipient` matches a route `pattern`: `local@domain`, `local@`,
+/// `@domain` or `*`. A pattern local part without `+tag` also matches the
+/// plus-addressed variants of that local part.
+fn address_matches(pattern: &str, recipient: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    let recipient = recipient.to_ascii_lowercase();
+    let Some((local, domain)) = recipient.rsplit_once('@') else {
+        return false;
+    };
+    let pattern = pattern.to_ascii_lowercase();
+    let (pattern_local, pattern_domain) = pattern.rsplit_once('@').unwrap_or((&pattern, ""));
+
+    let domain_matches = pattern_domain.is_empty() || pattern_domain == domain;
+    let local_matches = pattern_local.is_empty()
+        || pattern_local == local
+        || (!pattern_local.contains('+')
+            && local
+                .split_once('+')
+                .is_some_and(|(base, _)| base == pattern_local));
+    domain_matches && local_matches
+}
+
+impl ListenerState {
+    fn route(&self, recipient: &str) -> Option<&MailRouteConfig> {
+        self.config
+            .routes
+            .iter()
+            .find(|route| address_matches(&route.address, recipient))
+    }
+
+    /// With no routes configured every recipient is accepted.
+    fn accepts_recipient(&self, recipient: &str) -> bool {
+        self.config.routes.is_empty() || self.route(recipient).is_some()
+    }
+
+    /// The first recipient on each distinct route, in route-match order.
+    /// Unrouted recipients (no routes configured) share one delivery.
+    fn routed_recipients<'a>(
+        &self,
+        recipients: &'a [String],
+    ) -> Vec<(Option<&MailRouteConfig>, &'a str)> {
+        let mut routed: Vec<(Option<&MailRouteConfig>, &str)> = Vec::new();
+        for recipient in recipients {
+            let route = self.route(recipient);
+            let seen = routed.iter().any(|(r, _)| match (r, route) {
+                (Some(a), Some(b)) => std::ptr::eq(*a, b),
+                (None, None) => true,
+                _ => false,
+            });
+            if !seen {
+                routed.push((route, recipient));
+            }
+        }
+        routed
+    }
+}
+
+/// Channel name for mail delivered on `route`.
+fn route_channel(route: Option<&MailRouteConfig>) -> String {
+    match route {
+        Some(route) => format!("smtp_listener:{}", route.address),
+        None => "smtp_listener".to_string(),
+    }
+}
+
+// ── Authentication-Results (RFC 8601) ────────────────────────────
+
+/// Method results from one `Authentication-Results` header.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct AuthResults {
+    authserv_id: String,
+    spf: Vec<String>,
+    /// `(result, header.d)` for each DKIM signature checked.
+    dkim: Vec<(String, Option<String>)>,
+    dmarc: Vec<String>,
+}
+
+/// Remove RFC 5322 comments (`(…)`, possibly nested) from a header value.
+fn strip_comments(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    for ch in value.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ if depth == 0 => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn parse_authentication_results(value: &str) -> Option<AuthResults> {
+    let value = strip_comments(value);
+    let mut segments = value.split(';');
+    let authserv_id = segments.next()?.split_whitespace().next()?.to_string();
+    let mut results = AuthResults {
+        authserv_id,
+        ..AuthResults::default()
+    };
+
+    for segment in segments {
+        let mut tokens = segment.split_whitespace();
+        let Some((method, result)) = tokens.next().and_then(|t| t.split_once('=')) else {
+            continue;
+        };
+        let method = method.split('/').next().unwrap_or(method);
+        let result = result.to_ascii_lowercase();
+        match method.to_ascii_lowercase().as_str() {
+            "spf" => results.spf.push(result),
+            "dkim" => {
+                let domain = tokens.find_map(|t| {
+                    t.strip_prefix("header.d=")
+                        .map(|d| d.trim_matches('"').to_ascii_lowercase())
+                });
+                results.dkim.push((result, domain));
+            }
+            "dmarc" => results.dmarc.push(result),
+            _ => {}
+        }
+    }
+    Some(results)
+}
+
+/// Whether a DKIM signing domain is aligned (relaxed) with the `From` address.
+fn dkim_aligned(signing_domain: Option<&str>, from: &str) -> bool {
+    let Some(domain) = signing_domain else {
+        return true;
+    };
+    let from_domain = from
+        .rsplit_once('@')
+        .map(|(_, d)| d.to_ascii_lowercase())
+        .unwrap_or_default();
+    from_domain == domain || from_domain.ends_with(&format!(".{}", domain))
+}
+
+impl ListenerState {
+    /// Read the trusted `Authentication-Results` headers of a raw message.
+    fn authentication_results(&self, raw: &[u8]) -> Vec<AuthResults> {
+        let Some(parsed) = MessageParser::default().parse_headers(raw) else {
+            return Vec::new();
+        };
+        let all = parsed.headers().iter().filter_map(|header| {
+            if !header
+                .name
+                .as_str()
+                .eq_ignore_ascii_case("Authentication-Results")
+            {
+                return None;
+            }
+            let value = raw.get(header.offset_start as usize..header.offset_end as usize)?;
+            parse_authentication_results(std::str::from_utf8(value).ok()?)
+        });
+
+        let trusted = &self.config.trusted_authserv_ids;
+        if trusted.is_empty() {
+            // The receiving MTA prepends its header, so the topmost is its own.
+            all.take(1).collect()
+        } else {
+            all.filter(|r| {
+                trusted
+                    .iter()
+                    .any(|id| id.eq_ignore_ascii_case(&r.authserv_id))
+            })
+            .collect()
+        }
+    }
+
+    /// Apply the SPF/DKIM/DMARC policy. Returns why the message was refused.
+    fn check_authentication(&self, raw: &[u8], from: &str) -> Result<(), String> {
+        let config = &self.config;
+        if !config.require_spf && !config.require_dkim && !config.reject_dmarc_fail {
+            return Ok(());
+        }
+        let results = self.authentication_results(raw);
+
+        if config.reject_dmarc_fail && results.iter().any(|r| r.dmarc.iter().any(|d| d == "fail")) {
+            return Err("dmarc=fail".into());
+        }
+        if config.require_spf && !results.iter().any(|r| r.spf.iter().any(|s| s == "pass")) {
+            return Err("no spf=pass".into());
+        }
+        if config.require_dkim
+            && !results.iter().any(|r| {
+                r.dkim.iter().any(|(result, domain)| {
+                    result == "pass" && dkim_aligned(domain.as_deref(), from)
+                })
+            })
+        {
+            return Err("no aligned dkim=pass".into());
+        }
+        Ok(())
+    }
+}
+
+// ── Delivery ─────────────────────────────────────────────────────
+
+/// Outcome of handing one message to the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    /// Accepted; the agent received it, or it was dropped by policy.
+    Accepted,
+    /// The message could not be parsed.
+    Malformed,
+    /// The agent is shutting down.
+    Unavailable,
+}
+
+impl Delivery {
+    fn reply(self) -> &'static str {
+        match self {
+            Self::Accepted => "250 2.0.0 OK",
+            Self::Malformed => "554 5.6.0 Malformed message",
+            Self::Unavailable => "421 4.3.0 Service shutting down",
+        }
+    }
+}
+
+impl ListenerState {
+    /// Vet a message and forward one copy per route among `recipients`.
+    /// Policy rejections are accepted and dropped rather than bounced, so
+    /// forged senders cannot cause backscatter.
+    async fn deliver(
+        &self,
+        raw: &[u8],
+        recipients: &[String],
+        tx: &mpsc::Sender<ChannelMessage>,
+    ) -> Delivery {
+        let Some(email) = self.outbound.parse_email(raw, 0) else {
+            return Delivery::Malformed;
+        };
+
+        if let Err(reason) = self.check_authentication(raw, &email.sender) {
+            warn!(
+                "SMTP listener: dropped mail from {} to {}: {}",
+                email.sender,
+                recipients.join(", "),
+                reason
+            );
+            return Delivery::Accepted;
+        }
+        if self
+            .seen_messages
+            .lock()
+            .put(email.msg_id.clone(), ())
+            .is_some()
+        {
+            return Delivery::Accepted;
+        }
+
+        for (route, recipient) in self.routed_recipients(recipients) {
+            let allowed_senders = route
+                .and_then(|r| r.allowed_senders.as_deref())
+                .unwrap_or(&self.config.allowed_senders);
+            if !sender_allowed(allowed_senders, &email.sender) {
+                warn!(
+                    "SMTP listener: blocked mail from {} to {}",
+                    email.sender, recipient
+                );
+                continue;
+            }
+
+            let mut email = email.clone();
+            if let Some(route) = route {
+                email.content = persona_preamble(route, recipient) + &email.content;
+                email.reply_from = Some(recipient.to_string());
+            }
+            let thread_key = self.outbound.record_thread(&email).await;
+            let ParsedEmail {
+                msg_id,
+                sender,
+                content,
+                timestamp,
+                attachments,
+                ..
+            } = email;
+
+            let msg = ChannelMessage {
+                id: msg_id,
+                reply_target: sender.clone(),
+                sender,
+                content,
+                channel: route_channel(route),
+                timestamp,
+                thread_ts: Some(thread_key),
+                interruption_scope_id: None,
+                attachments,
+            };
+            if tx.send(msg).await.is_err() {
+                return Delivery::Unavailable;
+            }
+        }
+        Delivery::Accepted
+    }
+}
+
+fn persona_preamble(route: &MailRouteConfig, recipient: &str) -> String {
+    let mut preamble = format!("[Persona: {} — mail to {}]\n", route.persona, recipient);
+    if let Some(instructions) = route.instructions.as_deref().map(str::trim)
+        && !instructions.is_empty()
+    {
+        preamble.push_str(instructions);
+        preamble.push('\n');
+    }
+    preamble.push('\n');
+    preamble
+}
+
+// ── SMTP/LMTP session ────────────────────────────────────────────
+
+/// Extract the address from a `FROM:<addr> params` / `TO:<addr>` argument.
+fn parse_path(arg: &str, keyword: &str) -> Option<String> {
+    let rest = arg.get(..keyword.len())?;
+    if !rest.eq_ignore_ascii_case(keyword) {
+        return None;
+    }
+    let rest = arg[keyword.len()..].trim_start();
+    let inner = rest.strip_prefix('<')?;
+    let end = inner.find('>')?;
+    Some(inner[..end].trim().to_string())
+}
+
+/// `SIZE=` parameter of a `MAIL FROM` command, if any.
+fn size_param(arg: &str) -> Option<usize> {
+    arg.split_whitespace()
+        .skip(1)
+        .find_map(|p| {
+            p.get(..5)
+                .filter(|k| k.eq_ignore_ascii_case("SIZE="))
+                .map(|_| &p[5..])
+        })
+        .and_then(|v| v.parse().ok())
+}
+
+struct Transaction {
+    mail_from: Option<String>,
+    recipients: Vec<String>,
+}
+
+impl Transaction {
+    fn reset(&mut self) {
+        self.mail_from = None;
+        self.recipients.clear();
+    }
+}
+
+async fn reply<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\r\n").await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read one command line, or `None` at end of stream.
+async fn read_command<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>> {
+    let mut line = Vec::new();
+    let read = timeout(
+        COMMAND_TIMEOUT,
+        (&mut *reader)
+            .take(MAX_COMMAND_LINE)
+            .read_until(b'\n', &mut line),
+    )
+    .await??;
+    if read == 0 {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).trim_end().to_string()))
+}
+
+/// Read a `DATA` body up to the terminating `.` line, undoing dot-stuffing.
+/// Returns `None` when the body exceeded `max_bytes` (it is still consumed).
+async fn read_data<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    max_bytes: usize,
+) -> Result<Option<Vec<u8>>> {
+    let mut body = Vec::new();
+    let mut oversized = false;
+    let mut line_start = true;
+    loop {
+        let mut chunk = Vec::new();
+        let read = timeout(
+            COMMAND_TIMEOUT,
+            (&mut *reader)
+                .take(MAX_DATA_CHUNK)
+                .read_until(b'\n', &mut chunk),
+        )
+        .await??;
+        if read == 0 {
+            anyhow::bail!("connection closed during DATA");
+        }
+        let mut data = chunk.as_slice();
+        if line_start {
+            if data == b".\r\n" || data == b".\n" {
+                break;
+            }
+            if data.first() == Some(&b'.') {
+                data = &data[1..];
+            }
+        }
+        line_start = chunk.ends_with(b"\n");
+        if body.len() + data.len() > max_bytes {
+            oversized = true;
+        }
+        if !oversized {
+            body.extend_from_slice(data);
+        }
+    }
+    Ok((!oversized).then_some(body))
+}
+
+async fn handle_session<S: AsyncRead + AsyncWrite + Unpin>(
+    state: &ListenerState,
+    stream: S,
+    tx: &mpsc::Sender<ChannelMessage>,
+) -> Result<()> {
+    let config = &state.config;
+    let lmtp = config.protocol == MailListenerProtocol::Lmtp;
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+
+    let service = if lmtp { "LMTP" } else { "ESMTP" };
+    reply(
+        &mut writer,
+        &format!("220 {} {} ZeroClaw ready", config.hostname, service),
+    )
+    .await?;
+
+    let mut greeted = false;
+    let mut txn = Transaction {
+        mail_from: None,
+        recipients: Vec::new(),
+    };
+
+    loop {
+        let line = match read_command(&mut reader).await {
+            Ok(Some(line)) => line,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                let _ = reply(&mut writer, "421 4.4.2 Timeout, closing connection").await;
+                return Err(e);
+            }
+        };
+        let (verb, arg) = line.split_once(' ').unwrap_or((&line, ""));
+        let verb = verb.to_ascii_uppercase();
+
+        match verb.as_str() {
+            "EHLO" | "HELO" | "LHLO" => {
+                if lmtp != (verb == "LHLO") {
+                    reply(&mut writer, "500 5.5.1 Wrong greeting for this protocol").await?;
+                    continue;
+                }
+                greeted = true;
+                txn.reset();
+                if verb == "HELO" {
+                    reply(&mut writer, &format!("250 {}", config.hostname)).await?;
+                } else {
+                    let lines = format!(
+                        "250-{}\r\n250-PIPELINING\r\n250-8BITMIME\r\n250-ENHANCEDSTATUSCODES\r\n250 SIZE {}",
+                        config.hostname, config.max_message_bytes
+                    );
+                    reply(&mut writer, &lines).await?;
+                }
+            }
+            "MAIL" => {
+                if !greeted {
+                    reply(&mut writer, "503 5.5.1 Send greeting first").await?;
+                } else if txn.mail_from.is_some() {
+                    reply(&mut writer, "503 5.5.1 Sender already specified").await?;
+                } else if let Some(from) = parse_path(arg, "FROM:") {
+                    if size_param(arg).is_some_and(|size| size > config.max_message_bytes) {
+                        reply(&mut writer, "552 5.3.4 Message size exceeds limit").await?;
+                    } else {
+                        txn.mail_from = Some(from);
+                        reply(&mut writer, "250 2.1.0 OK").await?;
+                    }
+                } else {
+                    reply(&mut writer, "501 5.5.4 Syntax: MAIL FROM:<address>").await?;
+                }
+            }
+            "RCPT" => {
+                if txn.mail_from.is_none() {
+                    reply(&mut writer, "503 5.5.1 Need MAIL before RCPT").await?;
+                } else if let Some(to) = parse_path(arg, "TO:") {
+                    if txn.recipients.len() >= MAX_RECIPIENTS {
+                        reply(&mut writer, "452 4.5.3 Too many recipients").await?;
+                    } else if state.accepts_recipient(&to) {
+                        txn.recipients.push(to);
+                        reply(&mut writer, "250 2.1.5 OK").await?;
+                    } else {
+                        reply(&mut writer, "550 5.1.1 No such recipient").await?;
+                    }
+                } else {
+                    reply(&mut writer, "501 5.5.4 Syntax: RCPT TO:<address>").await?;
+                }
+            }
+            "DATA" => {
+                if txn.recipients.is_empty() {
+                    reply(&mut writer, "503 5.5.1 Need RCPT before DATA").await?;
+                    continue;
+                }
+                reply(&mut writer, "354 End data with <CR><LF>.<CR><LF>").await?;
+                let outcome = match read_data(&mut reader, config.max_message_bytes).await? {
+                    Some(body) => state.deliver(&body, &txn.recipients, tx).await.reply(),
+                    None => "552 5.3.4 Message size exceeds limit",
+                };
+                // LMTP answers once per accepted recipient (RFC 2033 §4.2).
+                let replies = if lmtp { txn.recipients.len() } else { 1 };
+                for _ in 0..replies {
+                    reply(&mut writer, outcome).await?;
+                }
+                txn.reset();
+                if outcome.starts_with("421") {
+                    return Ok(());
+                }
+            }
+            "RSET" => {
+                txn.reset();
+                reply(&mut writer, "250 2.0.0 OK").await?;
+            }
+            "NOOP" => reply(&mut writer, "250 2.0.0 OK").await?,
+            "VRFY" => reply(&mut writer, "252 2.5.0 Cannot VRFY user").await?,
+            "QUIT" => {
+                reply(&mut writer, "221 2.0.0 Bye").await?;
+                return Ok(());
+            }
+            "STARTTLS" | "AUTH" | "BDAT" => {
+                reply(&mut writer, "502 5.5.1 Command not implemented").await?;
+            }
+            _ => reply(&mut writer, "500 5.5.2 Command not recognized").await?,
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for SmtpListenerChannel {
+    fn name(&self) -> &str {
+        "smtp_listener"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        self.state.outbound.send(message).await
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let listener = TcpListener::bind(&self.state.config.bind).await?;
+        info!(
+            "SMTP listener accepting {} on {}",
+            self.state.config.protocol, self.state.config.bind
+        );
+        self.serve(listener, tx).await
+    }
+
+    async fn health_check(&self) -> bool {
+        if self.state.listening.load(Ordering::SeqCst) {
+            return true;
+        }
+        // Not started yet: healthy if the bind address is available.
+        TcpListener::bind(&self.state.config.bind).await.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpStream;
+
+    fn config() -> SmtpListenerConfig {
+        SmtpListenerConfig {
+            bind: "127.0.0.1:0".into(),
+            from_address: "agent@example.com".into(),
+            allowed_senders: vec!["*".into()],
+            routes: vec![MailRouteConfig {
+                address: "ops+agent@".into(),
+                persona: "ops".into(),
+                instructions: Some("You are the on-call operations assistant.".into()),
+                agent: None,
+                allowed_senders: None,
+            }],
+            ..SmtpListenerConfig::default()
+        }
+    }
+
+    /// Minimal SMTP client: sends a line and returns the reply lines.
+    struct Client {
+        reader: BufReader<tokio::net::tcp::OwnedReadHalf>,
+        writer: tokio::net::tcp::OwnedWriteHalf,
+    }
+
+    impl Client {
+        async fn connect(channel: &SmtpListenerChannel) -> (Self, mpsc::Receiver<ChannelMessage>) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let (tx, rx) = mpsc::channel(8);
+            let channel = SmtpListenerChannel {
+                state: Arc::clone(&channel.state),
+            };
+            tokio::spawn(async move { channel.serve(listener, tx).await });
+
+            let (reader, writer) = TcpStream::connect(addr).await.unwrap().into_split();
+            let mut client = Self {
+                reader: BufReader::new(reader),
+                writer,
+            };
+            assert!(client.read_reply().await.starts_with("220 "));
+            (client, rx)
+        }
+
+        async fn read_reply(&mut self) -> String {
+            let mut reply = String::new();
+            loop {
+                let mut line = String::new();
+                self.reader.read_line(&mut line).await.unwrap();
+                reply.push_str(&line);
+                if line.as_bytes().get(3) != Some(&b'-') {
+                    return reply;
+                }
+            }
+        }
+
+        async fn send(&mut self, line: &str) -> String {
+            self.writer.write_all(line.as_bytes()).await.unwrap();
+            self.writer.write_all(b"\r\n").await.unwrap();
+            self.read_reply().await
+        }
+    }
+
+    const MESSAGE: &str = "From: Alice <alice@example.org>\r\n\
+        To: ops+agent@example.com\r\n\
+        Subject: Disk alert\r\n\
+        Message-ID: <m2@example.org>\r\n\
+        In-Reply-To: <m1@example.org>\r\n\
+        References: <m1@example.org>\r\n\
+        Authentication-Results: mx.example.com; spf=pass smtp.mailfrom=example.org;\r\n \
+        dkim=pass header.d=example.org; dmarc=pass\r\n\
+        \r\n\
+        /var is at 95%.\r\n\
+        ..leading dot\r\n";
+
+    #[test]
+    fn address_patterns_match_recipients() {
+        assert!(address_matches("ops+agent@", "OPS+agent@example.com"));
+        assert!(address_matches("ops@example.com", "ops+pager@example.com"));
+        assert!(address_matches("@example.com", "anyone@example.com"));
+        assert!(address_matches("*", "x@y.z"));
+        assert!(!address_matches("ops+agent@", "ops@example.com"));
+        assert!(!address_matches("ops@example.com", "ops@example.org"));
+        assert!(!address_matches("ops", "operations@example.com"));
+    }
+
+    #[test]
+    fn authentication_results_are_parsed() {
+        let results = parse_authentication_results(
+            "mx.example.com (Postfix); spf=pass (sender SPF authorized) smtp.mailfrom=a.org;\r\n \
+             dkim=fail header.d=evil.test; dkim=pass header.d=\"a.org\"; dmarc/1=none",
+        )
+        .unwrap();
+        assert_eq!(results.authserv_id, "mx.example.com");
+        assert_eq!(results.spf, vec!["pass"]);
+        assert_eq!(
+            results.dkim,
+            vec![
+                ("fail".into(), Some("evil.test".into())),
+                ("pass".into(), Some("a.org".into()))
+            ]
+        );
+        assert_eq!(results.dmarc, vec!["none"]);
+        assert!(dkim_aligned(Some("a.org"), "bob@mail.a.org"));
+        assert!(!dkim_aligned(Some("a.org"), "bob@evil.test"));
+    }
+
+    #[test]
+    fn authentication_policy_reads_only_trusted_headers() {
+        let forged = "Authentication-Results: mx.example.com; dkim=pass header.d=example.org\r\n\
+            Authentication-Results: attacker.test; dkim=pass header.d=example.org\r\n\
+            From: alice@example.org\r\n\r\nhi\r\n";
+        let state = SmtpListenerChannel::new(SmtpListenerConfig {
+            require_dkim: true,
+            trusted_authserv_ids: vec!["mx.example.com".into()],
+            ..config()
+        })
+        .state;
+        assert!(
+            state
+                .check_authentication(forged.as_bytes(), "alice@example.org")
+                .is_ok()
+        );
+
+        let state = SmtpListenerChannel::new(SmtpListenerConfig {
+            require_dkim: true,
+            trusted_authserv_ids: vec!["other.example.com".into()],
+            ..config()
+        })
+        .state;
+        assert!(
+            state
+                .check_authentication(forged.as_bytes(), "alice@example.org")
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn smtp_session_routes_message_to_persona_thread() {
+        let channel = SmtpListenerChannel::new(SmtpListenerConfig {
+            require_spf: true,
+            require_dkim: true,
+            ..config()
+        });
+        let (mut client, mut rx) = Client::connect(&channel).await;
+
+        assert!(
+            client
+                .send("EHLO mta.example.com")
+                .await
+                .contains("250 SIZE")
+        );
+        assert!(
+            client
+                .send("MAIL FROM:<alice@example.org> SIZE=300")
+                .await
+                .starts_with("250")
+        );
+        assert!(
+            client
+                .send("RCPT TO:<nobody@example.com>")
+                .await
+                .starts_with("550 5.1.1")
+        );
+        assert!(
+            client
+                .send("RCPT TO:<ops+agent@example.com>")
+                .await
+                .starts_with("250")
+        );
+        assert!(client.send("DATA").await.starts_with("354"));
+        assert!(client.send(&format!("{MESSAGE}.")).await.starts_with("250"));
+        assert!(client.send("QUIT").await.starts_with("221"));
+
+        let msg = rx.recv().await.unwrap();
+        assert_eq!(msg.channel, "smtp_listener:ops+agent@");
+        assert_eq!(msg.sender, "alice@example.org");
+        assert_eq!(msg.reply_target, "alice@example.org");
+        assert_eq!(msg.id, "m2@example.org");
+        assert_eq!(
+            msg.thread_ts.as_deref(),
+            Some("m1@example.org ops+agent@example.com")
+        );
+        assert!(
+            msg.content
+                .starts_with("[Persona: ops — mail to ops+agent@example.com]\n")
+        );
+        assert!(msg.content.contains("on-call operations assistant"));
+        assert!(msg.content.contains("Subject: Disk alert"));
+        assert!(msg.content.contains("\n.leading dot"));
+    }
+
+    #[tokio::test]
+    async fn lmtp_replies_once_per_recipient() {
+        let channel = SmtpListenerChannel::new(SmtpListenerConfig {
+            protocol: MailListenerProtocol::Lmtp,
+            routes: vec![],
+            ..config()
+        });
+        let (mut client, mut rx) = Client::connect(&channel).await;
+
+        assert!(client.send("EHLO mta").await.starts_with("500"));
+        assert!(client.send("LHLO mta").await.starts_with("250-"));
+        client.send("MAIL FROM:<alice@example.org>").await;
+        client.send("RCPT TO:<a@example.com>").await;
+        client.send("RCPT TO:<b@example.com>").await;
+        client.send("DATA").await;
+        assert!(client.send(&format!("{MESSAGE}.")).await.starts_with("250"));
+        assert!(client.read_reply().await.starts_with("250"));
+
+        let msg = rx.recv().await.unwrap();
+        assert_eq!(msg.channel, "smtp_listener");
+        assert!(msg.content.starts_with("Subject: Disk alert"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn each_route_gets_its_own_copy_once() {
+        let mut config = config();
+        config.routes.push(MailRouteConfig {
+            address: "@billing.example.com".into(),
+            persona: "billing".into(),
+            instructions: None,
+            agent: Some("ledger".into()),
+            allowed_senders: Some(vec!["@example.org".into()]),
+        });
+        config.routes.push(MailRouteConfig {
+            address: "@legal.example.com".into(),
+            persona: "legal".into(),
+            instructions: None,
+            agent: None,
+            allowed_senders: Some(vec!["@example.net".into()]),
+        });
+        let channel = SmtpListenerChannel::new(config);
+        let (mut client, mut rx) = Client::connect(&channel).await;
+
+        client.send("EHLO mta").await;
+        for _ in 0..2 {
+            client.send("MAIL FROM:<alice@example.org>").await;
+            client.send("RCPT TO:<ops+agent@example.com>").await;
+            client.send("RCPT TO:<ops+agent@example.org>").await;
+            client.send("RCPT TO:<invoices@billing.example.com>").await;
+            client.send("RCPT TO:<counsel@legal.example.com>").await;
+            client.send("DATA").await;
+            assert!(client.send(&format!("{MESSAGE}.")).await.starts_with("250"));
+        }
+        client.send("QUIT").await;
+
+        let ops = rx.recv().await.unwrap();
+        assert_eq!(ops.channel, "smtp_listener:ops+agent@");
+        assert!(
+            ops.content
+                .starts_with("[Persona: ops — mail to ops+agent@example.com]")
+        );
+        let billing = rx.recv().await.unwrap();
+        assert_eq!(billing.channel, "smtp_listener:@billing.example.com");
+        assert!(
+            billing
+                .content
+                .starts_with("[Persona: billing — mail to invoices@billing.example.com]")
+        );
+        assert_ne!(ops.thread_ts, billing.thread_ts);
+        // The legal route does not allow the sender, and the redelivery is
+        // recognised by its Message-ID.
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn seen_messages_are_bounded() {
+        let state = SmtpListenerChannel::new(config()).state;
+        let mut seen = state.seen_messages.lock();
+        for i in 0..=MAX_SEEN_MESSAGES {
+            seen.put(format!("m{i}"), ());
+        }
+        assert_eq!(seen.len(), MAX_SEEN_MESSAGES);
+        assert!(!seen.contains("m0"));
+    }
+
+    #[tokio::test]
+    async fn policy_failures_are_accepted_and_dropped() {
+        let channel = SmtpListenerChannel::new(SmtpListenerConfig {
+            allowed_senders: vec!["@example.net".into()],
+            ..config()
+        });
+        let (mut client, mut rx) = Client::connect(&channel).await;
+
+        client.send("HELO mta").await;
+        client.send("MAIL FROM:<alice@example.org>").await;
+        client.send("RCPT TO:<ops+agent@example.com>").await;
+        client.send("DATA").await;
+        assert!(client.send(&format!("{MESSAGE}.")).await.starts_with("250"));
+        client.send("QUIT").await;
+
+        // Delivery finishes before the DATA reply, so nothing can still be in flight.
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_messages_are_refused() {
+        let channel = SmtpListenerChannel::new(SmtpListenerConfig {
+            max_message_bytes: 64,
+            ..config()
+        });
+        let (mut client, _rx) = Client::connect(&channel).await;
+
+        client.send("EHLO mta").await;
+        assert!(
+            client
+                .send("MAIL FROM:<alice@example.org> SIZE=1000")
+                .await
+                .starts_with("552")
+        );
+        client.send("MAIL FROM:<alice@example.org>").await;
+        client.send("RCPT TO:<ops+agent@example.com>").await;
+        client.send("DATA").await;
+        assert!(client.send(&format!("{MESSAGE}.")).await.starts_with("552"));
+    }
+}
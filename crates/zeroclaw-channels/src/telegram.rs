@@ -9,6 +9,7 @@ use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::fs;
 use zeroclaw_api::channel::{Channel, ChannelMessage, SendMessage};
+use zeroclaw_api::rich::{self, DispatchOutcome, RichBlock, RichMessage};
 use zeroclaw_config::schema::{Config, StreamMode};
 use zeroclaw_runtime::security::pairing::PairingGuard;
 
@@ -1918,6 +1919,126 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             .replace('\'', "&#39;")
     }
 
+    /// Build the inline keyboard for a rich message: one row per button
+    /// block and one row per select option. Returns `None` when the message
+    /// has no choices (forms are rendered as text).
+    fn rich_reply_markup(message: &RichMessage) -> Option<serde_json::Value> {
+        let mut rows: Vec<Vec<serde_json::Value>> = Vec::new();
+        for (block_index, block) in message.blocks.iter().enumerate() {
+            let first_choice = message.first_choice_index(block_index);
+            match block {
+                RichBlock::Buttons { buttons } => rows.push(
+                    buttons
+                        .iter()
+                        .enumerate()
+                        .map(|(i, button)| {
+                            serde_json::json!({
+                                "text": button.label,
+                                "callback_data": rich::encode_choice(&message.id, first_choice + i),
+                            })
+                        })
+                        .collect(),
+                ),
+                RichBlock::Select { options, .. } => {
+                    for (i, option) in options.iter().enumerate() {
+                        rows.push(vec![serde_json::json!({
+                            "text": option.label,
+                            "callback_data": rich::encode_choice(&message.id, first_choice + i),
+                        })]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        rows.retain(|row| !row.is_empty());
+        (!rows.is_empty()).then(|| serde_json::json!({ "inline_keyboard": rows }))
+    }
+
+    /// Send a rich message with its choices as an inline keyboard.
+    async fn send_rich(
+        &self,
+        message: &RichMessage,
+        chat_id: &str,
+        thread_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let Some(reply_markup) = Self::rich_reply_markup(message) else {
+            return self
+                .send_text_chunks(&message.to_plain_text(), chat_id, thread_id)
+                .await;
+        };
+        rich::track(message);
+
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": Self::markdown_to_telegram_html(&message.body_text()),
+            "parse_mode": "HTML",
+            "reply_markup": reply_markup,
+        });
+        if let Some(tid) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(tid.to_string());
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("sendMessage"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram sendMessage (rich) failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
+    /// Route an inline keyboard tap on a rich message.
+    ///
+    /// Returns the dispatch outcome with the sender identity and reply target,
+    /// or `None` when the callback is not a rich action or the sender is not
+    /// allowed.
+    fn dispatch_rich_callback(
+        &self,
+        callback: &serde_json::Value,
+    ) -> Option<(DispatchOutcome, String, String, Option<String>)> {
+        let data = callback.get("data").and_then(serde_json::Value::as_str)?;
+        let (message_id, action) = rich::decode_action(data)?;
+
+        let (username, sender_id, sender_identity) = Self::extract_sender_info(callback);
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        let message = callback.get("message");
+        let chat_id = message
+            .and_then(|m| m.get("chat"))
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())?;
+        let thread_id = message
+            .and_then(|m| m.get("message_thread_id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let reply_target = match &thread_id {
+            Some(tid) => format!("{chat_id}:{tid}"),
+            None => chat_id,
+        };
+
+        let outcome = rich::dispatch_encoded(
+            message_id,
+            action,
+            None,
+            Default::default(),
+            &sender_identity,
+            "telegram",
+        )?;
+        Some((outcome, sender_identity, reply_target, thread_id))
+    }
+
     async fn send_text_chunks(
         &self,
         message: &str,
@@ -2551,6 +2672,10 @@ impl Channel for TelegramChannel {
         self.stream_mode != StreamMode::Off
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         if self.stream_mode == StreamMode::Off {
             return Ok(None);
@@ -2867,6 +2992,11 @@ impl Channel for TelegramChannel {
             None => (message.recipient.as_str(), None),
         };
 
+        // Interactive prompts are never voiced.
+        if let Some(rich_message) = &message.rich {
+            return self.send_rich(rich_message, chat_id, thread_id).await;
+        }
+
         // Voice chat mode: send text normally AND queue a voice note of the
         // final answer. Text in → text out. Voice in → text + voice out.
         let is_voice_chat = self
@@ -3135,6 +3265,54 @@ Ensure only one `zeroclaw` process is using this bot token."
                             .and_then(serde_json::Value::as_str)
                             .unwrap_or_default();
 
+                        if let Some((outcome, sender, reply_target, thread_id)) =
+                            self.dispatch_rich_callback(cb)
+                        {
+                            let answer_text = match outcome {
+                                DispatchOutcome::Delivered => "✅".to_string(),
+                                DispatchOutcome::Unclaimed(interaction) => {
+                                    let reply = interaction.as_reply_text();
+                                    let msg = ChannelMessage {
+                                        id: format!("telegram_{reply_target}_{cb_id}"),
+                                        sender,
+                                        reply_target,
+                                        content: reply.clone(),
+                                        channel: "telegram".to_string(),
+                                        timestamp: std::time::SystemTime::now()
+                                            .duration_since(std::time::UNIX_EPOCH)
+                                            .unwrap_or_default()
+                                            .as_secs(),
+                                        thread_ts: thread_id,
+                                        interruption_scope_id: None,
+                                        attachments: vec![],
+                                    };
+                                    if tx.send(msg).await.is_err() {
+                                        return Ok(());
+                                    }
+                                    format!("✅ {reply}")
+                                }
+                                DispatchOutcome::Invalid(errors) => errors
+                                    .iter()
+                                    .map(ToString::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join("; "),
+                            };
+                            let answer_body = serde_json::json!({
+                                "callback_query_id": cb_id,
+                                "text": answer_text,
+                            });
+                            if let Err(e) = self
+                                .http_client()
+                                .post(self.api_url("answerCallbackQuery"))
+                                .json(&answer_body)
+                                .send()
+                                .await
+                            {
+                                tracing::warn!("answerCallbackQuery failed: {e}");
+                            }
+                            continue;
+                        }
+
                         if let Some(rest) = cb_data.strip_prefix("approval:")
                             && let Some((approval_id, action)) = rest.rsplit_once(':')
                         {
@@ -5804,4 +5982,27 @@ mod tests {
         let cb_data = "some_other_action:data";
         assert!(cb_data.strip_prefix("approval:").is_none());
     }
+
+    #[test]
+    fn rich_reply_markup_puts_select_options_on_own_rows() {
+        use zeroclaw_api::rich::{RichButton, RichOption};
+
+        let message = RichMessage::new()
+            .buttons(vec![RichButton::new("a", "A"), RichButton::new("b", "B")])
+            .select(
+                "Or",
+                vec![RichOption::new("C", "c"), RichOption::new("D", "d")],
+            );
+        let markup = TelegramChannel::rich_reply_markup(&message).unwrap();
+        let rows = markup["inline_keyboard"].as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].as_array().unwrap().len(), 2);
+        assert_eq!(
+            rows[2][0]["callback_data"],
+            rich::encode_choice(&message.id, 3)
+        );
+
+        let text_only = RichMessage::new().section("no buttons");
+        assert!(TelegramChannel::rich_reply_markup(&text_only).is_none());
+    }
 }
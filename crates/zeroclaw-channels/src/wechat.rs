@@ -1650,6 +1650,10 @@ impl Channel for WeChatChannel {
         "wechat"
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let recipient = &message.recipient;
         let content = crate::util::strip_tool_call_tags(&message.content);
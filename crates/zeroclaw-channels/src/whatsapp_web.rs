@@ -974,6 +974,10 @@ impl Channel for WhatsAppWebChannel {
         "whatsapp"
     }
 
+    fn supports_document_upload(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
         let client = self.client.lock().clone();
         let Some(client) = client else {
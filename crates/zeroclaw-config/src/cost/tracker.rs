@@ -173,6 +173,23 @@ impl CostTracker {
         let storage = self.lock_storage();
         storage.get_cost_for_month(year, month)
     }
+
+    /// Persisted usage events with `from <= timestamp <= to`, across all
+    /// tracker sessions, in recording order.
+    pub fn usage_between(
+        &self,
+        from: chrono::DateTime<Utc>,
+        to: chrono::DateTime<Utc>,
+    ) -> Result<Vec<TokenUsage>> {
+        let storage = self.lock_storage();
+        let mut usage = Vec::new();
+        storage.for_each_record(|record| {
+            if record.usage.timestamp >= from && record.usage.timestamp <= to {
+                usage.push(record.usage);
+            }
+        })?;
+        Ok(usage)
+    }
 }
 
 // ── Process-global singleton ────────────────────────────────────────
@@ -476,6 +493,26 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn usage_between_filters_by_timestamp() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let mut old = TokenUsage::new("test/old", 10, 10, 1.0, 1.0);
+        old.timestamp -= chrono::Duration::hours(2);
+        tracker.record_usage(old).unwrap();
+        tracker
+            .record_usage(TokenUsage::new("test/new", 10, 10, 1.0, 1.0))
+            .unwrap();
+
+        let now = Utc::now();
+        let recent = tracker
+            .usage_between(now - chrono::Duration::hours(1), now)
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].model, "test/new");
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
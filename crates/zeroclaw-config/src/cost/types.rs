@@ -111,6 +111,7 @@ pub enum BudgetCheck {
 
 /// Cost summary for reporting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CostSummary {
     /// Total cost for the session
     pub session_cost_usd: f64,
@@ -128,6 +129,7 @@ pub struct CostSummary {
 
 /// Statistics for a specific model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ModelStats {
     /// Model name
     pub model: String,
@@ -45,6 +45,8 @@ pub struct PairingGuard {
     require_pairing: bool,
     /// One-time pairing code (generated on startup, consumed on first pair).
     pairing_code: Arc<Mutex<Option<String>>>,
+    /// Gateway user the current pairing code was issued for, if any.
+    code_owner: Arc<Mutex<Option<String>>>,
     /// Set of SHA-256 hashed bearer tokens (persisted across restarts).
     paired_tokens: Arc<Mutex<HashSet<String>>>,
     /// Brute-force protection: per-client failed attempt state + last sweep timestamp.
@@ -81,6 +83,7 @@ impl PairingGuard {
         Self {
             require_pairing,
             pairing_code: Arc::new(Mutex::new(code)),
+            code_owner: Arc::new(Mutex::new(None)),
             paired_tokens: Arc::new(Mutex::new(tokens)),
             failed_attempts: Arc::new(Mutex::new((HashMap::new(), Instant::now()))),
         }
@@ -96,7 +99,11 @@ impl PairingGuard {
         self.require_pairing
     }
 
-    fn try_pair_blocking(&self, code: &str, client_id: &str) -> Result<Option<String>, u64> {
+    fn try_pair_blocking(
+        &self,
+        code: &str,
+        client_id: &str,
+    ) -> Result<Option<(String, Option<String>)>, u64> {
         let client_id = normalize_client_key(client_id);
         let now = Instant::now();
 
@@ -140,8 +147,9 @@ impl PairingGuard {
 
                 // Consume the pairing code so it cannot be reused
                 *pairing_code = None;
+                let owner = self.code_owner.lock().take();
 
-                return Ok(Some(token));
+                return Ok(Some((token, owner)));
             }
         }
 
@@ -186,6 +194,19 @@ impl PairingGuard {
     /// Returns `Err(lockout_seconds)` if locked out due to brute force.
     /// `client_id` identifies the client for per-client lockout accounting.
     pub async fn try_pair(&self, code: &str, client_id: &str) -> Result<Option<String>, u64> {
+        Ok(self
+            .try_pair_with_owner(code, client_id)
+            .await?
+            .map(|(token, _)| token))
+    }
+
+    /// Like [`Self::try_pair`], but also returns the gateway user the code
+    /// was issued for by [`Self::generate_pairing_code_for`].
+    pub async fn try_pair_with_owner(
+        &self,
+        code: &str,
+        client_id: &str,
+    ) -> Result<Option<(String, Option<String>)>, u64> {
         let this = self.clone();
         let code = code.to_string();
         let client_id = client_id.to_string();
@@ -219,16 +240,30 @@ impl PairingGuard {
         tokens.iter().cloned().collect()
     }
 
+    /// Forget a paired token by its hash. Returns true if it was paired.
+    pub fn revoke_token_hash(&self, hash: &str) -> bool {
+        self.paired_tokens.lock().remove(hash)
+    }
+
     /// Generate a new pairing code, even if already paired.
     ///
     /// This allows adding additional clients without restarting the gateway.
     /// The new code can be used exactly once to pair a new client.
     pub fn generate_new_pairing_code(&self) -> Option<String> {
+        self.generate_pairing_code_for(None)
+    }
+
+    /// Generate a new pairing code whose token will be bound to `owner`.
+    ///
+    /// Replaces any outstanding code, like [`Self::generate_new_pairing_code`].
+    pub fn generate_pairing_code_for(&self, owner: Option<&str>) -> Option<String> {
         if !self.require_pairing {
             return None;
         }
         let new_code = generate_code();
-        *self.pairing_code.lock() = Some(new_code.clone());
+        let mut pairing_code = self.pairing_code.lock();
+        *pairing_code = Some(new_code.clone());
+        *self.code_owner.lock() = owner.map(str::to_string);
         Some(new_code)
     }
 
@@ -585,6 +620,28 @@ mod tests {
         );
     }
 
+    #[test]
+    async fn user_pairing_code_reports_owner_once() {
+        let guard = PairingGuard::new(true, &["zc_existing".into()]);
+        let code = guard.generate_pairing_code_for(Some("alice")).unwrap();
+        let (token, owner) = guard
+            .try_pair_with_owner(&code, "client")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(guard.is_authenticated(&token));
+        assert_eq!(owner.as_deref(), Some("alice"));
+
+        // A plain code issued afterwards is not bound to the previous owner.
+        let code = guard.generate_new_pairing_code().unwrap();
+        let (_, owner) = guard
+            .try_pair_with_owner(&code, "client")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(owner, None);
+    }
+
     #[test]
     async fn correct_code_resets_failed_attempts() {
         let guard = PairingGuard::new(true, &[]);
@@ -456,6 +456,166 @@ impl Default for GmailPushConfig {
     }
 }
 
+/// Wire protocol spoken by the embedded mail listener.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum MailListenerProtocol {
+    /// SMTP (RFC 5321): one reply per message after `DATA`.
+    #[default]
+    Smtp,
+    /// LMTP (RFC 2033): `LHLO` greeting and one reply per recipient.
+    Lmtp,
+}
+
+impl HasPropKind for MailListenerProtocol {
+    const PROP_KIND: PropKind = PropKind::Enum;
+}
+
+impl fmt::Display for MailListenerProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Smtp => write!(f, "smtp"),
+            Self::Lmtp => write!(f, "lmtp"),
+        }
+    }
+}
+
+/// Maps a recipient address to the persona, and optionally the agent, that
+/// handles it.
+///
+/// ```toml
+/// [[channels.smtp_listener.routes]]
+/// address = "ops+agent@"
+/// persona = "ops"
+/// agent = "oncall"   # an [agents.oncall] entry
+/// instructions = "You are the on-call operations assistant."
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct MailRouteConfig {
+    /// Recipient pattern: `local@domain`, `local@` (any domain), `@domain`
+    /// (any local part) or `*`. A local part without `+tag` also matches
+    /// its plus-addressed variants.
+    pub address: String,
+    /// Persona name, shown to the agent with every message on this route.
+    pub persona: String,
+    /// Extra instructions prepended to messages on this route.
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// `[agents.<name>]` entry whose provider, model and system prompt answer
+    /// mail on this route. Unset uses the default agent.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Route-specific sender allowlist; overrides the channel allowlist.
+    #[serde(default)]
+    pub allowed_senders: Option<Vec<String>>,
+}
+
+fn default_smtp_listener_bind() -> String {
+    "127.0.0.1:2525".into()
+}
+fn default_smtp_listener_hostname() -> String {
+    "localhost".into()
+}
+fn default_max_message_bytes() -> usize {
+    25 * 1024 * 1024
+}
+
+/// Embedded SMTP/LMTP listener so a local MTA can hand mail straight to the
+/// agent. Replies go out through the SMTP settings below.
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "channels.smtp-listener"]
+pub struct SmtpListenerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the listener binds to. Keep it on loopback or a private
+    /// network: the listener does not offer STARTTLS or AUTH.
+    #[serde(default = "default_smtp_listener_bind")]
+    pub bind: String,
+    #[serde(default)]
+    pub protocol: MailListenerProtocol,
+    /// Host name announced in the greeting and `EHLO`/`LHLO` reply.
+    #[serde(default = "default_smtp_listener_hostname")]
+    pub hostname: String,
+    /// Largest message accepted after `DATA`, in bytes.
+    #[serde(default = "default_max_message_bytes")]
+    pub max_message_bytes: usize,
+    /// Recipient routes, matched in order. When empty every recipient is
+    /// accepted; otherwise unmatched recipients are rejected at `RCPT TO`.
+    #[serde(default)]
+    pub routes: Vec<MailRouteConfig>,
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+    /// `authserv-id`s whose `Authentication-Results` headers are trusted.
+    /// When empty only the topmost header (added by the receiving MTA) is read.
+    #[serde(default)]
+    pub trusted_authserv_ids: Vec<String>,
+    /// Drop messages without an `spf=pass` result.
+    #[serde(default)]
+    pub require_spf: bool,
+    /// Drop messages without a `dkim=pass` result aligned with the `From` domain.
+    #[serde(default)]
+    pub require_dkim: bool,
+    /// Drop messages with an explicit `dmarc=fail` result.
+    #[serde(default = "default_true")]
+    pub reject_dmarc_fail: bool,
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default = "default_true")]
+    pub smtp_tls: bool,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    #[secret]
+    pub password: String,
+    /// Default `From` address; replies in a routed thread are sent from the
+    /// address the message was delivered to.
+    pub from_address: String,
+    #[serde(default = "default_subject")]
+    pub default_subject: String,
+    #[serde(default = "default_max_attachment_bytes")]
+    pub max_attachment_bytes: usize,
+}
+
+impl ChannelConfig for SmtpListenerConfig {
+    fn name() -> &'static str {
+        "SMTP Listener"
+    }
+    fn desc() -> &'static str {
+        "Embedded SMTP/LMTP listener for local MTA delivery"
+    }
+}
+
+impl Default for SmtpListenerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: default_smtp_listener_bind(),
+            protocol: MailListenerProtocol::default(),
+            hostname: default_smtp_listener_hostname(),
+            max_message_bytes: default_max_message_bytes(),
+            routes: Vec::new(),
+            allowed_senders: Vec::new(),
+            trusted_authserv_ids: Vec::new(),
+            require_spf: false,
+            require_dkim: false,
+            reject_dmarc_fail: default_true(),
+            smtp_host: String::new(),
+            smtp_port: default_smtp_port(),
+            smtp_tls: true,
+            username: String::new(),
+            password: String::new(),
+            from_address: String::new(),
+            default_subject: default_subject(),
+            max_attachment_bytes: default_max_attachment_bytes(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, zeroclaw_macros::Configurable)]
 #[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[prefix = "channels.clawdtalk"]
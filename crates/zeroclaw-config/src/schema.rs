@@ -185,6 +185,22 @@ pub struct Config {
     #[nested]
     pub cron: CronConfig,
 
+    /// Durable outbound message outbox (`[outbox]`).
+    #[serde(default)]
+    #[nested]
+    pub outbox: OutboxConfig,
+
+    /// Scheduled autonomous agent packages (`[hands]`).
+    #[serde(default)]
+    #[nested]
+    pub hands: HandsConfig,
+
+    /// Named holiday and blackout calendars (`[calendars.<name>]`) that cron
+    /// jobs, heartbeat tasks and routines reference.
+    #[serde(default)]
+    #[nested]
+    pub calendars: HashMap<String, CalendarConfig>,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels]`).
     #[serde(default, alias = "channels_config")]
     #[nested]
@@ -2389,6 +2405,23 @@ pub struct GatewayConfig {
     #[serde(default)]
     #[nested]
     pub tls: Option<GatewayTlsConfig>,
+
+    /// OpenAI-compatible `/v1/*` endpoints (`[gateway.openai_compat]`).
+    #[serde(default)]
+    #[nested]
+    pub openai_compat: OpenAiCompatConfig,
+
+    /// User accounts (`[[gateway.users]]`). Paired tokens bound to a user
+    /// carry that user's role. With no users every paired token is an
+    /// admin; once users exist, unbound tokens are viewers.
+    #[serde(default)]
+    pub users: Vec<GatewayUserConfig>,
+
+    /// Named signed-webhook sources served at `/webhook/{name}`
+    /// (`[gateway.webhooks.<name>]`).
+    #[serde(default)]
+    #[nested]
+    pub webhooks: HashMap<String, WebhookSourceConfig>,
 }
 
 fn default_gateway_port() -> u16 {
@@ -2447,6 +2480,134 @@ impl Default for GatewayConfig {
             pairing_dashboard: PairingDashboardConfig::default(),
             web_dist_dir: None,
             tls: None,
+            openai_compat: OpenAiCompatConfig::default(),
+            users: Vec::new(),
+            webhooks: HashMap::new(),
+        }
+    }
+}
+
+/// How a webhook source proves that a delivery is genuine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookVerification {
+    /// HMAC-SHA256 of the body (or `{timestamp}.{body}`) keyed by `secret` (default).
+    #[default]
+    Hmac,
+    /// `signature_header` must carry `secret` verbatim.
+    SharedSecret,
+    /// The connection presented a client certificate the gateway's
+    /// `[gateway.tls.client_auth]` CA verified.
+    Mtls,
+    /// Accept every delivery. Only for trusted networks.
+    None,
+}
+
+/// Where a verified webhook delivery is handed off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookTarget {
+    /// Run an agent turn with the rendered prompt (default).
+    #[default]
+    Agent,
+    /// Start an SOP run with the payload.
+    Sop,
+    /// Dispatch a `webhook` event through `routines.toml`.
+    Routine,
+}
+
+/// A named webhook source (`[gateway.webhooks.<name>]`), served at
+/// `POST /webhook/<name>`.
+///
+/// GitHub style: `signature_header = "X-Hub-Signature-256"`,
+/// `signature_prefix = "sha256="`, `idempotency_header = "X-GitHub-Delivery"`.
+/// Stripe style: `signature_header = timestamp_header = "Stripe-Signature"`,
+/// `signature_prefix = "v1="`.
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "gateway.webhooks"]
+pub struct WebhookSourceConfig {
+    /// Accept deliveries for this source (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// `hmac`, `shared_secret`, `mtls` or `none` (default: hmac).
+    #[serde(default)]
+    pub verification: WebhookVerification,
+    /// HMAC signing key, or the expected header value for `shared_secret`.
+    #[serde(default)]
+    #[secret]
+    #[cfg_attr(feature = "schema-export", schemars(extend("x-secret" = true)))]
+    pub secret: Option<String>,
+    /// Header carrying the signature or shared secret (default: `X-Signature-256`).
+    #[serde(default = "default_webhook_signature_header")]
+    pub signature_header: String,
+    /// Prefix in front of the hex digest, e.g. `sha256=` or `v1=`.
+    #[serde(default)]
+    pub signature_prefix: String,
+    /// Header carrying the signing time in Unix seconds. When set, the signed
+    /// content is `{timestamp}.{body}` and stale deliveries are rejected. Name
+    /// the signature header here for Stripe-style `t=…,v1=…` values.
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+    /// Allowed distance between the signed timestamp and now (default: 300).
+    #[serde(default = "default_webhook_timestamp_tolerance_secs")]
+    pub timestamp_tolerance_secs: u64,
+    /// `mtls` only: SHA-256 fingerprints of the client certificates allowed
+    /// for this source. Empty accepts any certificate the CA verified.
+    #[serde(default)]
+    pub client_cert_fingerprints: Vec<String>,
+    /// Header with a unique delivery id for replay protection (default:
+    /// `X-Idempotency-Key`). Signed deliveries without one are deduplicated
+    /// by signature.
+    #[serde(default = "default_webhook_idempotency_header")]
+    pub idempotency_header: String,
+    /// `agent`, `sop` or `routine` (default: agent).
+    #[serde(default)]
+    pub target: WebhookTarget,
+    /// Prompt rendered from the delivery. `{{payload}}` is the raw body,
+    /// `{{source}}` the source name and `{{a.b.0}}` a JSON field of the body.
+    /// Default: the source name followed by the raw body.
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    /// `agent` only: session the turn runs in (default: `webhook-<name>`).
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// `sop` only: SOP to start. When unset, every SOP with a webhook trigger
+    /// on `/webhook/<name>` starts.
+    #[serde(default)]
+    pub sop: Option<String>,
+}
+
+fn default_webhook_signature_header() -> String {
+    "X-Signature-256".into()
+}
+
+fn default_webhook_timestamp_tolerance_secs() -> u64 {
+    300
+}
+
+fn default_webhook_idempotency_header() -> String {
+    "X-Idempotency-Key".into()
+}
+
+impl Default for WebhookSourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            verification: WebhookVerification::default(),
+            secret: None,
+            signature_header: default_webhook_signature_header(),
+            signature_prefix: String::new(),
+            timestamp_header: None,
+            timestamp_tolerance_secs: default_webhook_timestamp_tolerance_secs(),
+            client_cert_fingerprints: Vec::new(),
+            idempotency_header: default_webhook_idempotency_header(),
+            target: WebhookTarget::default(),
+            prompt_template: None,
+            session_id: None,
+            sop: None,
         }
     }
 }
@@ -2501,6 +2662,98 @@ impl Default for PairingDashboardConfig {
     }
 }
 
+/// Gateway role, from most to least privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum GatewayRole {
+    /// Full access, including config, pairing, users and daemon control.
+    Admin,
+    /// Chat, run and manage cron jobs, sessions and memory (default).
+    #[default]
+    Operator,
+    /// Read-only dashboard access.
+    Viewer,
+}
+
+impl std::fmt::Display for GatewayRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Admin => "admin",
+            Self::Operator => "operator",
+            Self::Viewer => "viewer",
+        })
+    }
+}
+
+/// A gateway user account (`[[gateway.users]]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct GatewayUserConfig {
+    /// Account name (letters, digits, `-` and `_`). Shown in the audit log.
+    pub name: String,
+    /// Role granted to every token bound to this user.
+    #[serde(default)]
+    pub role: GatewayRole,
+    /// SHA-256 hashes of the paired bearer tokens bound to this user
+    /// (managed by `/api/users/{name}/paircode`).
+    #[serde(default)]
+    pub tokens: Vec<String>,
+}
+
+/// How `/v1/chat/completions` requests are answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAiCompatMode {
+    /// Run a full agent turn (tools, memory, security policy) (default).
+    #[default]
+    Agent,
+    /// Forward the conversation to the configured provider unchanged.
+    Passthrough,
+}
+
+/// OpenAI-compatible API configuration (`[gateway.openai_compat]`).
+///
+/// Serves `/v1/chat/completions` and `/v1/models` so OpenAI SDK clients can
+/// talk to the gateway. Requests authenticate with paired bearer tokens.
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "gateway.openai-compat"]
+pub struct OpenAiCompatConfig {
+    /// Serve the `/v1/*` endpoints (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// `agent` runs a full agent turn; `passthrough` forwards to the provider (default: agent).
+    #[serde(default)]
+    pub mode: OpenAiCompatMode,
+    /// Model id advertised for agent turns (default: "zeroclaw").
+    /// In `agent` mode, requests naming a model in `passthrough_models` are
+    /// passed through to the provider; any other model runs the agent.
+    #[serde(default = "default_openai_compat_agent_model")]
+    pub agent_model: String,
+    /// Provider model ids `agent`-mode clients may name to reach the
+    /// provider directly, bypassing the agent (default: none).
+    #[serde(default)]
+    pub passthrough_models: Vec<String>,
+}
+
+fn default_openai_compat_agent_model() -> String {
+    "zeroclaw".into()
+}
+
+impl Default for OpenAiCompatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: OpenAiCompatMode::default(),
+            agent_model: default_openai_compat_agent_model(),
+            passthrough_models: Vec::new(),
+        }
+    }
+}
+
 /// TLS configuration for the gateway server (`[gateway.tls]`).
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Configurable)]
 #[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
@@ -6366,6 +6619,11 @@ pub struct HeartbeatConfig {
     /// Default: `600` (10 minutes).
     #[serde(default = "default_heartbeat_task_timeout")]
     pub task_timeout_secs: u64,
+    /// Named escalation policies (`[heartbeat.escalations.<name>]`) that
+    /// tasks in `HEARTBEAT.md` reference with an `escalate=<name>` tag.
+    #[serde(default)]
+    #[nested]
+    pub escalations: HashMap<String, HeartbeatEscalationConfig>,
 }
 
 fn default_heartbeat_interval() -> u32 {
@@ -6410,6 +6668,68 @@ impl Default for HeartbeatConfig {
             max_run_history: default_heartbeat_max_run_history(),
             load_session_context: false,
             task_timeout_secs: default_heartbeat_task_timeout(),
+            escalations: HashMap::new(),
+        }
+    }
+}
+
+/// Escalation ladder for a failing heartbeat task
+/// (`[heartbeat.escalations.<name>]`).
+///
+/// ```toml
+/// [heartbeat.escalations.database]
+/// notify_after = 2
+/// channel = "slack"
+/// to = "#ops"
+/// escalate_after = 5
+/// urgency = "critical"
+/// sop = "restart-database"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "heartbeat.escalations"]
+pub struct HeartbeatEscalationConfig {
+    /// Consecutive failures before a notice goes to `channel`. Default: `3`.
+    #[serde(default = "default_escalation_notify_after")]
+    pub notify_after: u32,
+    /// Channel for notices and escalations. Falls back to the heartbeat
+    /// delivery channel.
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`. Falls back to the heartbeat `to`.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Consecutive failures before the task is escalated to a human. `0`
+    /// disables. Default: `0`.
+    #[serde(default)]
+    pub escalate_after: u32,
+    /// Urgency of the escalation: `low`, `medium`, `high` or `critical`.
+    /// `high` and `critical` also alert `[escalation] alert_channels`.
+    /// Default: `high`.
+    #[serde(default = "default_escalation_urgency")]
+    pub urgency: String,
+    /// Remediation SOP run by an agent turn when the notice goes out.
+    #[serde(default)]
+    pub sop: Option<String>,
+}
+
+fn default_escalation_notify_after() -> u32 {
+    3
+}
+
+fn default_escalation_urgency() -> String {
+    "high".into()
+}
+
+impl Default for HeartbeatEscalationConfig {
+    fn default() -> Self {
+        Self {
+            notify_after: default_escalation_notify_after(),
+            channel: None,
+            to: None,
+            escalate_after: 0,
+            urgency: default_escalation_urgency(),
+            sop: None,
         }
     }
 }
@@ -6492,6 +6812,9 @@ pub struct CronJobDecl {
     /// Delivery configuration.
     #[serde(default)]
     pub delivery: Option<DeliveryConfigDecl>,
+    /// Calendar that filters the job's occurrences.
+    #[serde(default)]
+    pub calendar: Option<CalendarRule>,
 }
 
 /// Schedule variant for declarative cron jobs.
@@ -6552,6 +6875,227 @@ impl Default for CronConfig {
     }
 }
 
+// ── Outbox ──────────────────────────────────────────────────────
+
+/// Durable outbound message outbox (`[outbox]` section).
+///
+/// Outbound channel sends (agent replies, cron and heartbeat announcements)
+/// are persisted to `workspace/outbox/outbox.db` before delivery. Failed
+/// sends are retried per channel with exponential backoff, honouring the
+/// platform's `Retry-After` hint when the channel reports one. Messages that
+/// exhaust `max_attempts` move to the dead-letter queue, where they can be
+/// inspected and replayed via `zeroclaw outbox` or `/api/outbox`.
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "outbox"]
+pub struct OutboxConfig {
+    /// Route outbound sends through the persistent outbox. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Delivery attempts before a message is dead-lettered. Default: `8`.
+    #[serde(default = "default_outbox_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each failure. Default: `5`.
+    #[serde(default = "default_outbox_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+    /// Upper bound for the retry delay. Default: `900` (15 minutes).
+    #[serde(default = "default_outbox_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// How often the dispatcher scans for due retries. Default: `5`.
+    #[serde(default = "default_outbox_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Days to keep delivered messages before purging them. Default: `7`.
+    #[serde(default = "default_outbox_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_outbox_max_attempts() -> u32 {
+    8
+}
+
+fn default_outbox_initial_backoff_secs() -> u64 {
+    5
+}
+
+fn default_outbox_max_backoff_secs() -> u64 {
+    900
+}
+
+fn default_outbox_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_outbox_retention_days() -> u32 {
+    7
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_attempts: default_outbox_max_attempts(),
+            initial_backoff_secs: default_outbox_initial_backoff_secs(),
+            max_backoff_secs: default_outbox_max_backoff_secs(),
+            poll_interval_secs: default_outbox_poll_interval_secs(),
+            retention_days: default_outbox_retention_days(),
+        }
+    }
+}
+
+// ── Hands ───────────────────────────────────────────────────────
+
+/// Scheduled autonomous agents (`[hands]` section).
+///
+/// Each `*.toml` file in the hands directory defines one hand: a prompt,
+/// a schedule, an optional tool allowlist and model override, and where to
+/// deliver its reports. The daemon runs every active hand on its schedule
+/// and keeps a rolling context of findings in `{dir}/{name}/context.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "hands"]
+pub struct HandsConfig {
+    /// Run hands from the daemon. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Directory holding hand definitions. Default: `hands/` next to the
+    /// config file.
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// How often the daemon checks for due hands. Default: `30`.
+    #[serde(default = "default_hands_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Most hands run at the same time. Default: `4`.
+    #[serde(default = "default_hands_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+fn default_hands_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_hands_max_concurrent() -> usize {
+    4
+}
+
+impl Default for HandsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            poll_interval_secs: default_hands_poll_interval_secs(),
+            max_concurrent: default_hands_max_concurrent(),
+        }
+    }
+}
+
+// ── Calendars ───────────────────────────────────────────────────
+
+/// A named calendar (`[calendars.<name>]`): holidays, maintenance blackouts
+/// or any other set of dates and time windows.
+///
+/// ```toml
+/// [calendars.holidays-de]
+/// tz = "Europe/Berlin"
+/// dates = ["2026-12-25", "2026-12-24..2026-12-26"]
+/// ics = "calendars/feiertage.ics"
+/// windows = [{ start = "2026-11-07T22:00:00Z", end = "2026-11-08T04:00:00Z" }]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Configurable)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[prefix = "calendars"]
+pub struct CalendarConfig {
+    /// Whole days in the calendar: `YYYY-MM-DD`, or an inclusive range
+    /// `YYYY-MM-DD..YYYY-MM-DD`.
+    #[serde(default)]
+    pub dates: Vec<String>,
+    /// Time windows in the calendar, e.g. maintenance blackouts.
+    #[serde(default)]
+    pub windows: Vec<CalendarWindowConfig>,
+    /// ICS file whose events are added to the calendar. Relative paths
+    /// resolve against the workspace.
+    #[serde(default)]
+    pub ics: Option<String>,
+    /// IANA time zone whole days are counted in. Default: the schedule's
+    /// time zone, else the system's.
+    #[serde(default)]
+    pub tz: Option<String>,
+    /// Days of the week that are never business days (default: `sat`, `sun`).
+    #[serde(default = "default_calendar_weekend")]
+    pub weekend: Vec<String>,
+}
+
+fn default_calendar_weekend() -> Vec<String> {
+    vec!["sat".into(), "sun".into()]
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            dates: Vec::new(),
+            windows: Vec::new(),
+            ics: None,
+            tz: None,
+            weekend: default_calendar_weekend(),
+        }
+    }
+}
+
+/// A time window of a calendar, in RFC 3339. The end is exclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CalendarWindowConfig {
+    pub start: String,
+    pub end: String,
+}
+
+/// How a schedule uses a calendar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarMode {
+    /// Skip occurrences on the calendar's days and windows.
+    #[default]
+    Exclude,
+    /// Run only on the calendar's days and windows.
+    Include,
+    /// Run only on business days: not a weekend, not in the calendar.
+    BusinessDays,
+    /// Move occurrences that miss a business day to the next one, at the
+    /// same time of day.
+    NextBusinessDay,
+}
+
+impl CalendarMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exclude => "exclude",
+            Self::Include => "include",
+            Self::BusinessDays => "business_days",
+            Self::NextBusinessDay => "next_business_day",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "exclude" => Some(Self::Exclude),
+            "include" => Some(Self::Include),
+            "business_days" => Some(Self::BusinessDays),
+            "next_business_day" => Some(Self::NextBusinessDay),
+            _ => None,
+        }
+    }
+}
+
+/// A reference from a job, heartbeat task or routine to a named calendar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CalendarRule {
+    /// Name of a `[calendars.<name>]` entry.
+    pub name: String,
+    #[serde(default)]
+    pub mode: CalendarMode,
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -6826,6 +7370,11 @@ pub struct ChannelsConfig {
     #[display_name = "Gmail Push"]
     #[description = "Pub/Sub push notifications for Gmail"]
     pub gmail_push: Option<crate::scattered_types::GmailPushConfig>,
+    /// Embedded SMTP/LMTP listener channel configuration.
+    #[nested]
+    #[display_name = "SMTP Listener"]
+    #[description = "Local MTA delivery over SMTP or LMTP"]
+    pub smtp_listener: Option<crate::scattered_types::SmtpListenerConfig>,
     /// IRC channel configuration.
     #[nested]
     #[display_name = "IRC"]
@@ -6937,8 +7486,11 @@ pub struct ChannelsConfig {
     /// daemon restarts. Files are stored in `{workspace}/sessions/`. Default: `true`.
     #[serde(default = "default_true")]
     pub session_persistence: bool,
-    /// Session persistence backend: `"jsonl"` (legacy) or `"sqlite"` (new default).
-    /// SQLite provides FTS5 search, metadata tracking, and TTL cleanup.
+    /// Session persistence backend: `"jsonl"` (legacy), `"sqlite"` (new default),
+    /// or `"postgres"` (shared across replicas; requires the `session-postgres`
+    /// feature and `[storage.provider.config].db_url`).
+    /// SQLite provides FTS5 search, metadata tracking, and TTL cleanup; Postgres
+    /// provides the same with `tsvector` search.
     #[serde(default = "default_session_backend")]
     pub session_backend: String,
     /// Auto-archive stale sessions older than this many hours. `0` disables. Default: `0`.
@@ -7035,6 +7587,10 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(self.gmail_push.as_ref())),
                 self.gmail_push.is_some(),
             ),
+            (
+                Box::new(ConfigWrapper::new(self.smtp_listener.as_ref())),
+                self.smtp_listener.is_some(),
+            ),
             (
                 Box::new(ConfigWrapper::new(self.irc.as_ref())),
                 self.irc.is_some()
@@ -7129,6 +7685,7 @@ impl Default for ChannelsConfig {
             nextcloud_talk: None,
             email: None,
             gmail_push: None,
+            smtp_listener: None,
             irc: None,
             lark: None,
             line: None,
@@ -9641,6 +10198,9 @@ impl Default for Config {
             pipeline: PipelineConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            outbox: OutboxConfig::default(),
+            hands: HandsConfig::default(),
+            calendars: HashMap::new(),
             channels: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -10622,6 +11182,93 @@ impl Config {
                 }
             }
         }
+        for (name, source) in &self.gateway.webhooks {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "gateway.webhooks.{name}: source names may only contain letters, digits, '-' and '_'"
+                );
+            }
+            let needs_secret = matches!(
+                source.verification,
+                WebhookVerification::Hmac | WebhookVerification::SharedSecret
+            );
+            if needs_secret && source.secret.as_deref().is_none_or(str::is_empty) {
+                anyhow::bail!(
+                    "gateway.webhooks.{name}.secret is required for hmac and shared_secret verification"
+                );
+            }
+        }
+
+        for (name, policy) in &self.heartbeat.escalations {
+            if policy.notify_after == 0 {
+                anyhow::bail!("heartbeat.escalations.{name}.notify_after must be at least 1");
+            }
+            if policy.escalate_after != 0 && policy.escalate_after < policy.notify_after {
+                anyhow::bail!(
+                    "heartbeat.escalations.{name}.escalate_after must be 0 or at least notify_after"
+                );
+            }
+            if !["low", "medium", "high", "critical"].contains(&policy.urgency.as_str()) {
+                anyhow::bail!(
+                    "heartbeat.escalations.{name}.urgency must be one of low, medium, high, critical"
+                );
+            }
+            if policy.channel.is_some() != policy.to.is_some() {
+                anyhow::bail!("heartbeat.escalations.{name}: channel and to must be set together");
+            }
+        }
+        for (name, calendar) in &self.calendars {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "calendars.{name}: calendar names may only contain letters, digits, '-' and '_'"
+                );
+            }
+            for date in &calendar.dates {
+                let valid = date.split_once("..").map_or_else(
+                    || chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").is_ok(),
+                    |(from, to)| {
+                        matches!(
+                            (
+                                chrono::NaiveDate::parse_from_str(from.trim(), "%Y-%m-%d"),
+                                chrono::NaiveDate::parse_from_str(to.trim(), "%Y-%m-%d"),
+                            ),
+                            (Ok(from), Ok(to)) if from <= to
+                        )
+                    },
+                );
+                if !valid {
+                    anyhow::bail!(
+                        "calendars.{name}.dates: '{date}' is not YYYY-MM-DD or an ascending YYYY-MM-DD..YYYY-MM-DD range"
+                    );
+                }
+            }
+            for window in &calendar.windows {
+                match (
+                    chrono::DateTime::parse_from_rfc3339(&window.start),
+                    chrono::DateTime::parse_from_rfc3339(&window.end),
+                ) {
+                    (Ok(start), Ok(end)) if start < end => {}
+                    _ => anyhow::bail!(
+                        "calendars.{name}.windows: '{}'..'{}' must be RFC 3339 timestamps with start before end",
+                        window.start,
+                        window.end
+                    ),
+                }
+            }
+            for day in &calendar.weekend {
+                if day.parse::<chrono::Weekday>().is_err() {
+                    anyhow::bail!("calendars.{name}.weekend: '{day}' is not a day of the week");
+                }
+            }
+        }
 
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
@@ -11999,6 +12646,10 @@ impl_enum_prop_kind!(
     SearchMode,
     CronScheduleDecl,
     StreamMode,
+    OpenAiCompatMode,
+    GatewayRole,
+    WebhookVerification,
+    WebhookTarget,
     WhatsAppWebMode,
     WhatsAppChatPolicy,
     LineDmPolicy,
@@ -12585,6 +13236,9 @@ auto_save = true
                 ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
+            outbox: OutboxConfig::default(),
+            hands: HandsConfig::default(),
+            calendars: HashMap::new(),
             channels: ChannelsConfig {
                 cli: true,
                 telegram: Some(TelegramConfig {
@@ -12613,6 +13267,7 @@ auto_save = true
                 nextcloud_talk: None,
                 email: None,
                 gmail_push: None,
+                smtp_listener: None,
                 irc: None,
                 lark: None,
                 line: None,
@@ -13211,6 +13866,9 @@ default_temperature = 0.7
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            outbox: OutboxConfig::default(),
+            hands: HandsConfig::default(),
+            calendars: HashMap::new(),
             channels: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -13787,6 +14445,7 @@ allowed_users = ["@u:matrix.org"]
             nextcloud_talk: None,
             email: None,
             gmail_push: None,
+            smtp_listener: None,
             irc: None,
             lark: None,
             line: None,
@@ -14169,6 +14828,7 @@ bot_token = "xoxb-tok"
             nextcloud_talk: None,
             email: None,
             gmail_push: None,
+            smtp_listener: None,
             irc: None,
             lark: None,
             line: None,
@@ -14286,6 +14946,9 @@ bot_token = "xoxb-tok"
             pairing_dashboard: PairingDashboardConfig::default(),
             web_dist_dir: None,
             tls: None,
+            openai_compat: OpenAiCompatConfig::default(),
+            users: Vec::new(),
+            webhooks: HashMap::new(),
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -14322,6 +14985,104 @@ default_temperature = 0.7
         );
     }
 
+    #[test]
+    async fn gateway_webhook_sources_validate_name_and_secret() {
+        let mut config = Config::default();
+        config
+            .gateway
+            .webhooks
+            .insert("github".into(), WebhookSourceConfig::default());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("gateway.webhooks.github.secret"));
+
+        config.gateway.webhooks.clear();
+        config.gateway.webhooks.insert(
+            "bad/name".into(),
+            WebhookSourceConfig {
+                verification: WebhookVerification::None,
+                ..WebhookSourceConfig::default()
+            },
+        );
+        assert!(config.validate().is_err());
+
+        config.gateway.webhooks.clear();
+        config.gateway.webhooks.insert(
+            "github".into(),
+            WebhookSourceConfig {
+                secret: Some("s3cret".into()),
+                ..WebhookSourceConfig::default()
+            },
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    async fn calendars_validate_dates_windows_and_weekend() {
+        let mut config = Config::default();
+        config.calendars.insert(
+            "holidays".into(),
+            CalendarConfig {
+                dates: vec!["2026-12-25".into(), "2026-12-24..2026-12-26".into()],
+                windows: vec![CalendarWindowConfig {
+                    start: "2026-11-07T22:00:00Z".into(),
+                    end: "2026-11-08T04:00:00Z".into(),
+                }],
+                ..CalendarConfig::default()
+            },
+        );
+        assert!(config.validate().is_ok());
+
+        config.calendars.get_mut("holidays").unwrap().dates = vec!["2026-12-26..2026-12-24".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("calendars.holidays.dates"));
+
+        config.calendars.get_mut("holidays").unwrap().dates.clear();
+        config.calendars.get_mut("holidays").unwrap().weekend = vec!["funday".into()];
+        assert!(config.validate().is_err());
+
+        let rule: CalendarRule = toml::from_str(r#"name = "holidays""#).unwrap();
+        assert_eq!(rule.mode, CalendarMode::Exclude);
+        assert_eq!(
+            CalendarMode::parse("next_business_day"),
+            Some(CalendarMode::NextBusinessDay)
+        );
+    }
+
+    #[test]
+    async fn heartbeat_escalations_validate_ladder() {
+        let mut config = Config::default();
+        config.heartbeat.escalations.insert(
+            "database".into(),
+            toml::from_str(
+                r##"
+                notify_after = 2
+                channel = "slack"
+                to = "#ops"
+                escalate_after = 5
+                sop = "restart-database"
+                "##,
+            )
+            .unwrap(),
+        );
+        assert!(config.validate().is_ok());
+        assert_eq!(config.heartbeat.escalations["database"].urgency, "high");
+
+        let policy = config.heartbeat.escalations.get_mut("database").unwrap();
+        policy.escalate_after = 1;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("heartbeat.escalations.database.escalate_after"));
+
+        let policy = config.heartbeat.escalations.get_mut("database").unwrap();
+        policy.escalate_after = 0;
+        policy.urgency = "urgent".into();
+        assert!(config.validate().is_err());
+
+        let policy = config.heartbeat.escalations.get_mut("database").unwrap();
+        policy.urgency = "low".into();
+        policy.to = None;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     async fn checklist_autonomy_default_is_workspace_scoped() {
         let a = AutonomyConfig::default();
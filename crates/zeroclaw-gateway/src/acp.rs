@@ -1,7 +1,9 @@
 //! ACP-over-WebSocket gateway endpoint.
 
 use super::AppState;
+use super::rbac::{self, Principal};
 use axum::{
+    Extension,
     extract::{
         Query, State, WebSocketUpgrade,
         ws::{Message, WebSocket},
@@ -19,6 +21,7 @@ use zeroclaw_channels::orchestrator::acp_server::{AcpServer, AcpServerConfig};
 const ACP_WS_PROTOCOL: &str = "zeroclaw.acp.v1";
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct AcpQuery {
     token: Option<String>,
 }
@@ -27,6 +30,7 @@ pub async fn handle_ws_acp(
     State(state): State<AppState>,
     Query(params): Query<AcpQuery>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     if state.pairing.require_pairing() {
@@ -50,11 +54,12 @@ pub async fn handle_ws_acp(
         ws
     };
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    let principal = principal.map(|Extension(p)| p);
+    ws.on_upgrade(move |socket| handle_socket(socket, state, principal))
         .into_response()
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(socket: WebSocket, state: AppState, principal: Option<Principal>) {
     let (mut sender, mut receiver) = socket.split();
     let (input_tx, input_rx) = mpsc::channel::<String>(256);
     let (output_tx, mut output_rx) = mpsc::channel::<String>(256);
@@ -79,12 +84,14 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     while let Some(message) = receiver.next().await {
         match message {
             Ok(Message::Text(text)) => {
+                audit_request(&state, principal.as_ref(), &text);
                 if input_tx.send(text.to_string()).await.is_err() {
                     break;
                 }
             }
             Ok(Message::Binary(bytes)) => match String::from_utf8(bytes.to_vec()) {
                 Ok(text) => {
+                    audit_request(&state, principal.as_ref(), &text);
                     if input_tx.send(text).await.is_err() {
                         break;
                     }
@@ -116,6 +123,20 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     debug!("ACP WebSocket disconnected");
 }
 
+/// Audit a JSON-RPC frame by its method. Responses carry no method and
+/// are not recorded.
+fn audit_request(state: &AppState, principal: Option<&Principal>, frame: &str) {
+    if principal.is_none() {
+        return;
+    }
+    let Ok(request) = serde_json::from_str::<serde_json::Value>(frame) else {
+        return;
+    };
+    if let Some(method) = request.get("method").and_then(serde_json::Value::as_str) {
+        rbac::audit_action(state, principal, &format!("WS /acp {method}"));
+    }
+}
+
 fn extract_ws_token<'a>(headers: &'a HeaderMap, query_token: Option<&'a str>) -> Option<&'a str> {
     headers
         .get(axum::http::header::AUTHORIZATION)
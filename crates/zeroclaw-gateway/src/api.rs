@@ -3,12 +3,15 @@
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
 use super::AppState;
+use super::rbac::{self, Principal};
 use axum::{
+    Extension,
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Json},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // ── Bearer token auth extractor ─────────────────────────────────
 
@@ -45,6 +48,7 @@ pub(super) fn require_auth(
 // ── Query parameters ─────────────────────────────────────────────
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct MemoryQuery {
     pub query: Option<String>,
     pub category: Option<String>,
@@ -55,6 +59,7 @@ pub struct MemoryQuery {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct MemoryStoreBody {
     pub key: String,
     pub content: String,
@@ -62,11 +67,44 @@ pub struct MemoryStoreBody {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CronRunsQuery {
     pub limit: Option<u32>,
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxQuery {
+    /// Filter by status (`pending`, `in_flight`, `delivered`, `dead`)
+    pub status: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HandHistoryQuery {
+    pub limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionExportQuery {
+    /// `md`, `html`, or `json` (default `md`)
+    pub format: Option<String>,
+    /// Scrub secrets from JSON exports (default `true`); pass `false` for a
+    /// raw export. Markdown and HTML are always scrubbed
+    pub redact: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionImportQuery {
+    /// Session ID to import under (defaults to the transcript's own, or a new UUID)
+    pub session_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CronAddBody {
     pub name: Option<String>,
     pub schedule: String,
@@ -78,14 +116,435 @@ pub struct CronAddBody {
     pub model: Option<String>,
     pub allowed_tools: Option<Vec<String>>,
     pub delete_after_run: Option<bool>,
+    pub depends_on: Option<Vec<zeroclaw_runtime::cron::JobDependency>>,
+    pub retry: Option<zeroclaw_runtime::cron::RetryPolicy>,
+    pub overlap: Option<zeroclaw_runtime::cron::OverlapPolicy>,
+    /// Named calendar from `[calendars]` that filters run dates.
+    pub calendar: Option<zeroclaw_runtime::calendar::CalendarRule>,
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CronPatchBody {
     pub name: Option<String>,
     pub schedule: Option<String>,
     pub command: Option<String>,
     pub prompt: Option<String>,
+    /// Replaces the dependency list; an empty list clears it.
+    pub depends_on: Option<Vec<zeroclaw_runtime::cron::JobDependency>>,
+    /// Replaces the retry policy; `max_attempts: 0` clears it.
+    pub retry: Option<zeroclaw_runtime::cron::RetryPolicy>,
+    pub overlap: Option<zeroclaw_runtime::cron::OverlapPolicy>,
+    /// Replaces the calendar; an empty name clears it.
+    pub calendar: Option<zeroclaw_runtime::calendar::CalendarRule>,
+}
+
+/// Reject references to calendars missing from `[calendars]`.
+fn check_calendar(
+    config: &zeroclaw_config::schema::Config,
+    rule: Option<&zeroclaw_runtime::calendar::CalendarRule>,
+) -> anyhow::Result<()> {
+    match rule {
+        Some(rule) if !rule.name.trim().is_empty() => {
+            zeroclaw_runtime::calendar::Calendar::load(config, &rule.name).map(|_| ())
+        }
+        _ => Ok(()),
+    }
+}
+
+// ── Responses ───────────────────────────────────────────────────
+
+/// `GET /api/status` response.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct StatusResponse {
+    pub provider: Option<String>,
+    pub model: String,
+    pub temperature: f64,
+    pub uptime_seconds: u64,
+    pub gateway_port: u16,
+    pub locale: String,
+    pub memory_backend: String,
+    pub paired: bool,
+    /// Channel name to whether it is configured.
+    pub channels: BTreeMap<String, bool>,
+    pub health: zeroclaw_runtime::health::HealthSnapshot,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ToolsResponse {
+    pub tools: Vec<zeroclaw_api::tool::ToolSpec>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronJobsResponse {
+    pub jobs: Vec<zeroclaw_runtime::cron::CronJob>,
+}
+
+/// A created or updated cron job.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronJobResponse {
+    /// Always `ok`.
+    pub status: String,
+    pub job: zeroclaw_runtime::cron::CronJob,
+}
+
+/// One recorded run of a cron job.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronRunInfo {
+    pub id: i64,
+    pub job_id: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub status: String,
+    pub output: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronRunsResponse {
+    pub runs: Vec<CronRunInfo>,
+}
+
+/// Outcome of running a cron job on demand.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronRunResult {
+    /// `ok` or `error`.
+    pub status: String,
+    pub job_id: String,
+    pub success: bool,
+    pub output: String,
+    pub duration_ms: i64,
+    pub attempts: u32,
+    pub started_at: String,
+    pub finished_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronSettings {
+    pub enabled: bool,
+    pub catch_up_on_startup: bool,
+    pub max_run_history: u32,
+}
+
+impl From<&zeroclaw_config::schema::CronConfig> for CronSettings {
+    fn from(cron: &zeroclaw_config::schema::CronConfig) -> Self {
+        Self {
+            enabled: cron.enabled,
+            catch_up_on_startup: cron.catch_up_on_startup,
+            max_run_history: cron.max_run_history,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CronSettingsUpdated {
+    /// Always `ok`.
+    pub status: String,
+    #[serde(flatten)]
+    pub settings: CronSettings,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxEntriesResponse {
+    pub entries: Vec<zeroclaw_runtime::outbox::OutboxEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxEntryResponse {
+    pub entry: zeroclaw_runtime::outbox::OutboxEntry,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxReplayResponse {
+    /// Always `ok`.
+    pub status: String,
+    pub entry: zeroclaw_runtime::outbox::OutboxEntry,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxReplayDeadResponse {
+    /// Always `ok`.
+    pub status: String,
+    /// Number of dead deliveries requeued.
+    pub replayed: usize,
+}
+
+/// A hand with its schedule, next run and most recent run.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HandSummary {
+    pub name: String,
+    pub description: String,
+    pub schedule: zeroclaw_runtime::cron::Schedule,
+    pub active: bool,
+    pub model: Option<String>,
+    pub allowed_tools: Option<Vec<String>>,
+    /// `null` when the hand is inactive or has no upcoming occurrence.
+    pub next_run: Option<String>,
+    pub last_run: Option<zeroclaw_runtime::hands::HandRun>,
+    pub total_runs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HandsResponse {
+    pub hands: Vec<HandSummary>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HandRunResponse {
+    pub run: zeroclaw_runtime::hands::HandRun,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HandHistoryResponse {
+    /// Runs, newest first.
+    pub runs: Vec<zeroclaw_runtime::hands::HandRun>,
+    pub learned_facts: Vec<String>,
+    pub total_runs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct IntegrationInfo {
+    pub name: String,
+    pub description: String,
+    pub category: zeroclaw_runtime::integrations::IntegrationCategory,
+    pub status: zeroclaw_runtime::integrations::IntegrationStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct IntegrationsResponse {
+    pub integrations: Vec<IntegrationInfo>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct IntegrationSetting {
+    pub enabled: bool,
+    pub category: zeroclaw_runtime::integrations::IntegrationCategory,
+    pub status: zeroclaw_runtime::integrations::IntegrationStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct IntegrationSettingsResponse {
+    /// Integration name to its settings.
+    pub settings: BTreeMap<String, IntegrationSetting>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct DoctorSummary {
+    pub ok: usize,
+    pub warnings: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct DoctorResponse {
+    pub results: Vec<zeroclaw_runtime::doctor::DiagResult>,
+    pub summary: DoctorSummary,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct MemoryEntriesResponse {
+    pub entries: Vec<zeroclaw_memory::MemoryEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct MemoryDeleteResponse {
+    /// Always `ok`.
+    pub status: String,
+    /// Whether an entry existed under the key.
+    pub deleted: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CostResponse {
+    pub cost: zeroclaw_config::cost::CostSummary,
+}
+
+/// One installed plugin in `GET /api/plugins`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PluginSummary {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    /// `tool`, `channel`, `memory`, `observer` or `skill`.
+    pub capabilities: Vec<String>,
+    pub loaded: bool,
+}
+
+/// `GET /api/plugins` response (the route needs the `plugins-wasm` feature).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PluginsResponse {
+    pub plugins_enabled: bool,
+    pub plugins_dir: String,
+    pub plugins: Vec<PluginSummary>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CliToolsResponse {
+    pub cli_tools: Vec<zeroclaw_tools::cli_discovery::DiscoveredCli>,
+}
+
+/// A configured channel as the dashboard lists it.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ChannelInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub enabled: bool,
+    pub status: String,
+    pub message_count: u64,
+    pub last_message_at: Option<String>,
+    pub health: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ChannelsResponse {
+    pub channels: Vec<ChannelInfo>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ComponentHealthResponse {
+    pub health: zeroclaw_runtime::health::HealthSnapshot,
+}
+
+/// A persisted gateway session.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub created_at: String,
+    pub last_activity: String,
+    pub message_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Session this one was forked from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_session_id: Option<String>,
+    /// Leading messages shared with the parent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork_point: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionInfo>,
+    /// Set when session persistence is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SessionsResponse {
+    fn persistence_disabled() -> Self {
+        Self {
+            sessions: Vec::new(),
+            message: Some("Session persistence is disabled".into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionMessagesResponse {
+    pub session_id: String,
+    pub messages: Vec<zeroclaw_api::provider::ChatMessage>,
+    pub session_persistence: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionRenamedResponse {
+    pub session_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionDeletedResponse {
+    pub deleted: bool,
+    pub session_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionForkResponse {
+    pub session_id: String,
+    pub parent_session_id: String,
+    /// Number of messages copied into the fork.
+    pub fork_point: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionImportResponse {
+    pub session_id: String,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionStateResponse {
+    pub session_id: String,
+    /// `idle`, `running`, or `error`.
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_started_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AbortStatus {
+    /// A running turn was cancelled.
+    Aborted,
+    /// The session was idle.
+    NoActiveResponse,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SessionAbortResponse {
+    pub status: AbortStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HookAck {
+    pub ok: bool,
 }
 
 // ── Handlers ────────────────────────────────────────────────────
@@ -102,11 +561,12 @@ pub async fn handle_api_status(
     let config = state.config.lock().clone();
     let health = zeroclaw_runtime::health::snapshot();
 
-    let mut channels = serde_json::Map::new();
-
-    for (channel, present) in config.channels.channels() {
-        channels.insert(channel.name().to_string(), serde_json::Value::Bool(present));
-    }
+    let channels = config
+        .channels
+        .channels()
+        .into_iter()
+        .map(|(channel, present)| (channel.name().to_string(), present))
+        .collect();
 
     let locale = config
         .locale
@@ -115,18 +575,18 @@ pub async fn handle_api_status(
         .map(String::from)
         .unwrap_or_else(zeroclaw_runtime::i18n::detect_locale);
 
-    let body = serde_json::json!({
-        "provider": config.providers.fallback,
-        "model": state.model,
-        "temperature": state.temperature,
-        "uptime_seconds": health.uptime_seconds,
-        "gateway_port": config.gateway.port,
-        "locale": locale,
-        "memory_backend": state.mem.name(),
-        "paired": state.pairing.is_paired(),
-        "channels": channels,
-        "health": health,
-    });
+    let body = StatusResponse {
+        provider: config.providers.fallback.clone(),
+        model: state.model.clone(),
+        temperature: state.temperature,
+        uptime_seconds: health.uptime_seconds,
+        gateway_port: config.gateway.port,
+        locale,
+        memory_backend: state.mem.name().to_string(),
+        paired: state.pairing.is_paired(),
+        channels,
+        health,
+    };
 
     Json(body).into_response()
 }
@@ -140,19 +600,9 @@ pub async fn handle_api_tools(
         return e.into_response();
     }
 
-    let tools: Vec<serde_json::Value> = state
-        .tools_registry
-        .iter()
-        .map(|spec| {
-            serde_json::json!({
-                "name": spec.name,
-                "description": spec.description,
-                "parameters": spec.parameters,
-            })
-        })
-        .collect();
+    let tools = state.tools_registry.as_ref().clone();
 
-    Json(serde_json::json!({"tools": tools})).into_response()
+    Json(ToolsResponse { tools }).into_response()
 }
 
 /// GET /api/cron — list cron jobs
@@ -166,7 +616,7 @@ pub async fn handle_api_cron_list(
 
     let config = state.config.lock().clone();
     match zeroclaw_runtime::cron::list_jobs(&config) {
-        Ok(jobs) => Json(serde_json::json!({"jobs": jobs})).into_response(),
+        Ok(jobs) => Json(CronJobsResponse { jobs }).into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": format!("Failed to list cron jobs: {e}")})),
@@ -179,6 +629,7 @@ pub async fn handle_api_cron_list(
 pub async fn handle_api_cron_add(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Json(body): Json<CronAddBody>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
@@ -196,6 +647,10 @@ pub async fn handle_api_cron_add(
         model,
         allowed_tools,
         delete_after_run,
+        depends_on,
+        retry,
+        overlap,
+        calendar,
     } = body;
 
     let config = state.config.lock().clone();
@@ -210,10 +665,41 @@ pub async fn handle_api_cron_add(
         )
             .into_response();
     }
+    let depends_on = depends_on.unwrap_or_default();
+    if let Err(e) = zeroclaw_runtime::cron::validate_dependencies(&config, None, &depends_on) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Failed to add cron job: {e}")})),
+        )
+            .into_response();
+    }
+    if let Err(e) = check_calendar(&config, calendar.as_ref()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Failed to add cron job: {e}")})),
+        )
+            .into_response();
+    }
+    if retry.as_ref().is_some_and(|r| r.max_attempts == 0) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "Failed to add cron job: retry.max_attempts must be at least 1"
+            })),
+        )
+            .into_response();
+    }
 
     // Determine job type: explicit field, or infer "agent" when prompt is provided.
     let is_agent =
         matches!(job_type.as_deref(), Some("agent")) || (job_type.is_none() && prompt.is_some());
+    // Shell jobs run arbitrary commands on the host.
+    if !is_agent
+        && let Err(e) =
+            rbac::require_admin(principal.as_ref().map(|Extension(p)| p), "a shell cron job")
+    {
+        return e.into_response();
+    }
 
     let result = if is_agent {
         let prompt = match prompt.as_deref() {
@@ -263,8 +749,34 @@ pub async fn handle_api_cron_add(
         )
     };
 
+    let result = match result {
+        Ok(job)
+            if !depends_on.is_empty()
+                || retry.is_some()
+                || overlap.is_some()
+                || calendar.is_some() =>
+        {
+            zeroclaw_runtime::cron::update_job(
+                &config,
+                &job.id,
+                zeroclaw_runtime::cron::CronJobPatch {
+                    depends_on: Some(depends_on),
+                    retry,
+                    overlap,
+                    calendar,
+                    ..zeroclaw_runtime::cron::CronJobPatch::default()
+                },
+            )
+        }
+        other => other,
+    };
+
     match result {
-        Ok(job) => Json(serde_json::json!({"status": "ok", "job": job})).into_response(),
+        Ok(job) => Json(CronJobResponse {
+            status: "ok".into(),
+            job,
+        })
+        .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": format!("Failed to add cron job: {e}")})),
@@ -298,21 +810,20 @@ pub async fn handle_api_cron_runs(
 
     match zeroclaw_runtime::cron::list_runs(&config, &id, limit) {
         Ok(runs) => {
-            let runs_json: Vec<serde_json::Value> = runs
-                .iter()
-                .map(|r| {
-                    serde_json::json!({
-                        "id": r.id,
-                        "job_id": r.job_id,
-                        "started_at": r.started_at.to_rfc3339(),
-                        "finished_at": r.finished_at.to_rfc3339(),
-                        "status": r.status,
-                        "output": r.output,
-                        "duration_ms": r.duration_ms,
-                    })
+            let runs = runs
+                .into_iter()
+                .map(|r| CronRunInfo {
+                    id: r.id,
+                    job_id: r.job_id,
+                    started_at: r.started_at.to_rfc3339(),
+                    finished_at: r.finished_at.to_rfc3339(),
+                    status: r.status,
+                    output: r.output,
+                    duration_ms: r.duration_ms,
+                    attempts: r.attempts,
                 })
                 .collect();
-            Json(serde_json::json!({"runs": runs_json})).into_response()
+            Json(CronRunsResponse { runs }).into_response()
         }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -326,6 +837,7 @@ pub async fn handle_api_cron_runs(
 pub async fn handle_api_cron_run(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
@@ -344,10 +856,26 @@ pub async fn handle_api_cron_run(
                 .into_response();
         }
     };
+    if !matches!(job.job_type, zeroclaw_runtime::cron::JobType::Agent)
+        && let Err(e) =
+            rbac::require_admin(principal.as_ref().map(|Extension(p)| p), "a shell cron job")
+    {
+        return e.into_response();
+    }
 
+    Json(run_cron_job_now(&state, &config, &job).await).into_response()
+}
+
+/// Run `job` immediately, record the run and broadcast the result. Shared by
+/// `POST /api/cron/{id}/run` and routines fired from webhook sources.
+pub(crate) async fn run_cron_job_now(
+    state: &AppState,
+    config: &zeroclaw_config::schema::Config,
+    job: &zeroclaw_runtime::cron::CronJob,
+) -> CronRunResult {
     let started_at = chrono::Utc::now();
-    let (mut success, output) =
-        zeroclaw_runtime::cron::scheduler::execute_job_now(&config, &job).await;
+    let (mut success, output, attempts) =
+        zeroclaw_runtime::cron::scheduler::execute_job_now(config, job).await;
     let finished_at = chrono::Utc::now();
     let duration_ms = (finished_at - started_at).num_milliseconds();
 
@@ -355,7 +883,7 @@ pub async fn handle_api_cron_run(
         && let (Some(channel), Some(target)) =
             (job.delivery.channel.as_deref(), job.delivery.to.as_deref())
         && let Err(e) = zeroclaw_runtime::cron::scheduler::deliver_announcement(
-            &config, channel, target, &output,
+            config, channel, target, &output,
         )
         .await
     {
@@ -377,13 +905,14 @@ pub async fn handle_api_cron_run(
 
     let status = if success { "ok" } else { "error" };
     if let Err(e) = zeroclaw_runtime::cron::record_run(
-        &config,
+        config,
         &job.id,
         started_at,
         finished_at,
         status,
         Some(&output),
         duration_ms,
+        attempts,
     ) {
         tracing::warn!(
             job_id = %job.id,
@@ -392,7 +921,7 @@ pub async fn handle_api_cron_run(
         );
     }
     if let Err(e) =
-        zeroclaw_runtime::cron::record_last_run(&config, &job.id, finished_at, success, &output)
+        zeroclaw_runtime::cron::record_last_run(config, &job.id, finished_at, success, &output)
     {
         tracing::warn!(
             job_id = %job.id,
@@ -400,6 +929,13 @@ pub async fn handle_api_cron_run(
             "manual cron trigger: failed to update last_run state",
         );
     }
+    if let Err(e) = zeroclaw_runtime::cron::trigger_dependents(config, &job.id) {
+        tracing::warn!(
+            job_id = %job.id,
+            error = %e,
+            "manual cron trigger: failed to trigger dependent jobs",
+        );
+    }
 
     // Broadcast the result so dashboard/SSE clients refresh in real time,
     // matching the scheduler's automatic-execution behavior.
@@ -412,22 +948,23 @@ pub async fn handle_api_cron_run(
         "timestamp": finished_at.to_rfc3339(),
     }));
 
-    Json(serde_json::json!({
-        "status": status,
-        "job_id": job.id,
-        "success": success,
-        "output": output,
-        "duration_ms": duration_ms,
-        "started_at": started_at.to_rfc3339(),
-        "finished_at": finished_at.to_rfc3339(),
-    }))
-    .into_response()
+    CronRunResult {
+        status: status.to_string(),
+        job_id: job.id.clone(),
+        success,
+        output,
+        duration_ms,
+        attempts,
+        started_at: started_at.to_rfc3339(),
+        finished_at: finished_at.to_rfc3339(),
+    }
 }
 
 /// PATCH /api/cron/:id — update an existing cron job
 pub async fn handle_api_cron_patch(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
     Json(body): Json<CronPatchBody>,
 ) -> impl IntoResponse {
@@ -459,7 +996,20 @@ pub async fn handle_api_cron_patch(
                 .into_response();
         }
     };
+    if let Err(e) = check_calendar(&config, body.calendar.as_ref()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Failed to update cron job: {e}")})),
+        )
+            .into_response();
+    }
     let is_agent = matches!(existing.job_type, zeroclaw_runtime::cron::JobType::Agent);
+    if !is_agent
+        && let Err(e) =
+            rbac::require_admin(principal.as_ref().map(|Extension(p)| p), "a shell cron job")
+    {
+        return e.into_response();
+    }
     let (patch_command, patch_prompt) = if is_agent {
         (None, body.command.or(body.prompt))
     } else {
@@ -471,11 +1021,19 @@ pub async fn handle_api_cron_patch(
         schedule,
         command: patch_command,
         prompt: patch_prompt,
+        depends_on: body.depends_on,
+        retry: body.retry,
+        overlap: body.overlap,
+        calendar: body.calendar,
         ..zeroclaw_runtime::cron::CronJobPatch::default()
     };
 
     match zeroclaw_runtime::cron::update_shell_job_with_approval(&config, &id, patch, false) {
-        Ok(job) => Json(serde_json::json!({"status": "ok", "job": job})).into_response(),
+        Ok(job) => Json(CronJobResponse {
+            status: "ok".into(),
+            job,
+        })
+        .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": format!("Failed to update cron job: {e}")})),
@@ -505,6 +1063,261 @@ pub async fn handle_api_cron_delete(
     }
 }
 
+/// GET /api/outbox — list outbound messages, newest first
+pub async fn handle_api_outbox_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<OutboxQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let status = match params
+        .status
+        .as_deref()
+        .map(zeroclaw_runtime::outbox::OutboxStatus::try_from)
+        .transpose()
+    {
+        Ok(status) => status,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e})),
+            )
+                .into_response();
+        }
+    };
+    let limit = params.limit.unwrap_or(50).clamp(1, 500) as usize;
+    let config = state.config.lock().clone();
+
+    match zeroclaw_runtime::outbox::list_entries(&config, status, limit) {
+        Ok(entries) => Json(OutboxEntriesResponse { entries }).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list outbox: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/outbox/:id — fetch a single outbound message
+pub async fn handle_api_outbox_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match zeroclaw_runtime::outbox::get_entry(&config, &id) {
+        Ok(entry) => Json(OutboxEntryResponse { entry }).into_response(),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/outbox/:id/replay — requeue a failed message for immediate delivery
+pub async fn handle_api_outbox_replay(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if let Err(e) = zeroclaw_runtime::outbox::get_entry(&config, &id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response();
+    }
+    match zeroclaw_runtime::outbox::replay_entry(&config, &id) {
+        Ok(entry) => Json(OutboxReplayResponse {
+            status: "ok".into(),
+            entry,
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/outbox/replay-dead — requeue every dead-lettered message
+pub async fn handle_api_outbox_replay_dead(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match zeroclaw_runtime::outbox::replay_dead_letters(&config) {
+        Ok(replayed) => Json(OutboxReplayDeadResponse {
+            status: "ok".into(),
+            replayed,
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to replay outbox: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /api/outbox/:id — drop an outbound message without delivering it
+pub async fn handle_api_outbox_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match zeroclaw_runtime::outbox::remove_entry(&config, &id) {
+        Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/hands — list hands with their next and last run
+pub async fn handle_api_hands_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let dir = zeroclaw_runtime::hands::hands_dir(&config);
+    let hands = match zeroclaw_runtime::hands::load_hands(&dir) {
+        Ok(hands) => hands,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to load hands: {e}")})),
+            )
+                .into_response();
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let hands = hands
+        .into_iter()
+        .map(|hand| {
+            let context = zeroclaw_runtime::hands::load_hand_context(&dir, &hand.name)
+                .unwrap_or_else(|_| zeroclaw_runtime::hands::HandContext::new(&hand.name));
+            let next_run = zeroclaw_runtime::hands::next_run_at(&hand, &context, now)
+                .ok()
+                .flatten()
+                .filter(|_| hand.active);
+            HandSummary {
+                next_run: next_run.map(|t| t.to_rfc3339()),
+                last_run: context.history.into_iter().next(),
+                total_runs: context.total_runs,
+                name: hand.name,
+                description: hand.description,
+                schedule: hand.schedule,
+                active: hand.active,
+                model: hand.model,
+                allowed_tools: hand.allowed_tools,
+            }
+        })
+        .collect();
+
+    Json(HandsResponse { hands }).into_response()
+}
+
+/// POST /api/hands/:name/run — run a hand now and record the result
+pub async fn handle_api_hands_run(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let dir = zeroclaw_runtime::hands::hands_dir(&config);
+    let hand = match zeroclaw_runtime::hands::find_hand(&dir, &name) {
+        Ok(hand) => hand,
+        Err(e) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    match Box::pin(zeroclaw_runtime::hands::run_hand(&config, &hand)).await {
+        Ok(run) => Json(HandRunResponse { run }).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to record hand run: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/hands/:name/history — recent runs and learned facts of a hand
+pub async fn handle_api_hands_history(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Query(params): Query<HandHistoryQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100) as usize;
+    let config = state.config.lock().clone();
+    let dir = zeroclaw_runtime::hands::hands_dir(&config);
+    if let Err(e) = zeroclaw_runtime::hands::find_hand(&dir, &name) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response();
+    }
+
+    match zeroclaw_runtime::hands::load_hand_context(&dir, &name) {
+        Ok(context) => Json(HandHistoryResponse {
+            runs: context.history.into_iter().take(limit).collect(),
+            learned_facts: context.learned_facts,
+            total_runs: context.total_runs,
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load hand history: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cron/settings — return cron subsystem settings
 pub async fn handle_api_cron_settings_get(
     State(state): State<AppState>,
@@ -515,12 +1328,7 @@ pub async fn handle_api_cron_settings_get(
     }
 
     let config = state.config.lock().clone();
-    Json(serde_json::json!({
-        "enabled": config.cron.enabled,
-        "catch_up_on_startup": config.cron.catch_up_on_startup,
-        "max_run_history": config.cron.max_run_history,
-    }))
-    .into_response()
+    Json(CronSettings::from(&config.cron)).into_response()
 }
 
 /// PATCH /api/cron/settings — update cron subsystem settings
@@ -555,12 +1363,10 @@ pub async fn handle_api_cron_settings_patch(
 
     *state.config.lock() = config.clone();
 
-    Json(serde_json::json!({
-        "status": "ok",
-        "enabled": config.cron.enabled,
-        "catch_up_on_startup": config.cron.catch_up_on_startup,
-        "max_run_history": config.cron.max_run_history,
-    }))
+    Json(CronSettingsUpdated {
+        status: "ok".into(),
+        settings: CronSettings::from(&config.cron),
+    })
     .into_response()
 }
 
@@ -576,19 +1382,17 @@ pub async fn handle_api_integrations(
     let config = state.config.lock().clone();
     let entries = zeroclaw_runtime::integrations::registry::all_integrations(&config);
 
-    let integrations: Vec<serde_json::Value> = entries
-        .iter()
-        .map(|entry| {
-            serde_json::json!({
-                "name": entry.name,
-                "description": entry.description,
-                "category": entry.category,
-                "status": entry.status,
-            })
+    let integrations = entries
+        .into_iter()
+        .map(|entry| IntegrationInfo {
+            name: entry.name,
+            description: entry.description,
+            category: entry.category,
+            status: entry.status,
         })
         .collect();
 
-    Json(serde_json::json!({"integrations": integrations})).into_response()
+    Json(IntegrationsResponse { integrations }).into_response()
 }
 
 /// GET /api/integrations/settings — return per-integration settings (enabled + category)
@@ -603,23 +1407,23 @@ pub async fn handle_api_integrations_settings(
     let config = state.config.lock().clone();
     let entries = zeroclaw_runtime::integrations::registry::all_integrations(&config);
 
-    let mut settings = serde_json::Map::new();
-    for entry in &entries {
-        let enabled = matches!(
-            entry.status,
-            zeroclaw_runtime::integrations::IntegrationStatus::Active
-        );
-        settings.insert(
-            entry.name.clone(),
-            serde_json::json!({
-                "enabled": enabled,
-                "category": entry.category,
-                "status": entry.status,
-            }),
-        );
-    }
+    let settings = entries
+        .into_iter()
+        .map(|entry| {
+            let enabled = matches!(
+                entry.status,
+                zeroclaw_runtime::integrations::IntegrationStatus::Active
+            );
+            let setting = IntegrationSetting {
+                enabled,
+                category: entry.category,
+                status: entry.status,
+            };
+            (entry.name, setting)
+        })
+        .collect();
 
-    Json(serde_json::json!({"settings": settings})).into_response()
+    Json(IntegrationSettingsResponse { settings }).into_response()
 }
 
 /// POST /api/doctor — run diagnostics
@@ -647,14 +1451,14 @@ pub async fn handle_api_doctor(
         .filter(|r| r.severity == zeroclaw_runtime::doctor::Severity::Error)
         .count();
 
-    Json(serde_json::json!({
-        "results": results,
-        "summary": {
-            "ok": ok_count,
-            "warnings": warn_count,
-            "errors": error_count,
-        }
-    }))
+    Json(DoctorResponse {
+        results,
+        summary: DoctorSummary {
+            ok: ok_count,
+            warnings: warn_count,
+            errors: error_count,
+        },
+    })
     .into_response()
 }
 
@@ -662,19 +1466,26 @@ pub async fn handle_api_doctor(
 pub async fn handle_api_memory_list(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Query(params): Query<MemoryQuery>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    // Non-admin users only see memory stored under their own session.
+    let scope = rbac::memory_scope(principal.as_ref().map(|Extension(p)| p));
 
     // Use recall when query or time range is provided
     if params.query.is_some() || params.since.is_some() || params.until.is_some() {
         let query = params.query.as_deref().unwrap_or("");
         let since = params.since.as_deref();
         let until = params.until.as_deref();
-        match state.mem.recall(query, 50, None, since, until).await {
-            Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
+        match state
+            .mem
+            .recall(query, 50, scope.as_deref(), since, until)
+            .await
+        {
+            Ok(entries) => Json(MemoryEntriesResponse { entries }).into_response(),
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": format!("Memory recall failed: {e}")})),
@@ -690,8 +1501,8 @@ pub async fn handle_api_memory_list(
             other => zeroclaw_memory::MemoryCategory::Custom(other.to_string()),
         });
 
-        match state.mem.list(category.as_ref(), None).await {
-            Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
+        match state.mem.list(category.as_ref(), scope.as_deref()).await {
+            Ok(entries) => Json(MemoryEntriesResponse { entries }).into_response(),
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": format!("Memory list failed: {e}")})),
@@ -705,11 +1516,14 @@ pub async fn handle_api_memory_list(
 pub async fn handle_api_memory_store(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Json(body): Json<MemoryStoreBody>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    // Non-admin users only see memory stored under their own session.
+    let scope = rbac::memory_scope(principal.as_ref().map(|Extension(p)| p));
 
     let category = body
         .category
@@ -722,9 +1536,13 @@ pub async fn handle_api_memory_store(
         })
         .unwrap_or(zeroclaw_memory::MemoryCategory::Core);
 
+    if let Some(response) = foreign_memory_key(&state, scope.as_deref(), &body.key).await {
+        return response;
+    }
+
     match state
         .mem
-        .store(&body.key, &body.content, category, None)
+        .store(&body.key, &body.content, category, scope.as_deref())
         .await
     {
         Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
@@ -740,16 +1558,25 @@ pub async fn handle_api_memory_store(
 pub async fn handle_api_memory_delete(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(key): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    // Non-admin users only see memory stored under their own session.
+    let scope = rbac::memory_scope(principal.as_ref().map(|Extension(p)| p));
+
+    if let Some(response) = foreign_memory_key(&state, scope.as_deref(), &key).await {
+        return response;
+    }
 
     match state.mem.forget(&key).await {
-        Ok(deleted) => {
-            Json(serde_json::json!({"status": "ok", "deleted": deleted})).into_response()
-        }
+        Ok(deleted) => Json(MemoryDeleteResponse {
+            status: "ok".into(),
+            deleted,
+        })
+        .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": format!("Memory forget failed: {e}")})),
@@ -758,6 +1585,26 @@ pub async fn handle_api_memory_delete(
     }
 }
 
+/// Refuse to touch a memory entry owned by another user's session.
+async fn foreign_memory_key(
+    state: &AppState,
+    scope: Option<&str>,
+    key: &str,
+) -> Option<axum::response::Response> {
+    let scope = scope?;
+    let entry = state.mem.get(key).await.ok().flatten()?;
+    if entry.session_id.as_deref() == Some(scope) {
+        return None;
+    }
+    Some(
+        (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Memory entry belongs to another user"})),
+        )
+            .into_response(),
+    )
+}
+
 /// GET /api/cost — cost summary
 pub async fn handle_api_cost(
     State(state): State<AppState>,
@@ -769,7 +1616,7 @@ pub async fn handle_api_cost(
 
     if let Some(ref tracker) = state.cost_tracker {
         match tracker.get_summary() {
-            Ok(summary) => Json(serde_json::json!({"cost": summary})).into_response(),
+            Ok(cost) => Json(CostResponse { cost }).into_response(),
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": format!("Cost summary failed: {e}")})),
@@ -777,16 +1624,9 @@ pub async fn handle_api_cost(
                 .into_response(),
         }
     } else {
-        Json(serde_json::json!({
-            "cost": {
-                "session_cost_usd": 0.0,
-                "daily_cost_usd": 0.0,
-                "monthly_cost_usd": 0.0,
-                "total_tokens": 0,
-                "request_count": 0,
-                "by_model": {},
-            }
-        }))
+        Json(CostResponse {
+            cost: zeroclaw_config::cost::CostSummary::default(),
+        })
         .into_response()
     }
 }
@@ -800,9 +1640,9 @@ pub async fn handle_api_cli_tools(
         return e.into_response();
     }
 
-    let tools = zeroclaw_tools::cli_discovery::discover_cli_tools(&[], &[]);
+    let cli_tools = zeroclaw_tools::cli_discovery::discover_cli_tools(&[], &[]);
 
-    Json(serde_json::json!({"cli_tools": tools})).into_response()
+    Json(CliToolsResponse { cli_tools }).into_response()
 }
 
 /// GET /api/channels — list configured channels with status
@@ -815,25 +1655,23 @@ pub async fn handle_api_channels(
     }
 
     let config = state.config.lock().clone();
-    let channels: Vec<serde_json::Value> = config
+    let channels = config
         .channels
         .channels()
         .into_iter()
         .filter(|(_, present)| *present)
-        .map(|(ch, _)| {
-            serde_json::json!({
-                "name": ch.name(),
-                "type": ch.name(),
-                "enabled": true,
-                "status": "active",
-                "message_count": 0,
-                "last_message_at": null,
-                "health": "healthy",
-            })
+        .map(|(ch, _)| ChannelInfo {
+            name: ch.name().to_string(),
+            kind: ch.name().to_string(),
+            enabled: true,
+            status: "active".into(),
+            message_count: 0,
+            last_message_at: None,
+            health: "healthy".into(),
         })
         .collect();
 
-    Json(serde_json::json!({ "channels": channels })).into_response()
+    Json(ChannelsResponse { channels }).into_response()
 }
 
 /// GET /api/health — component health snapshot
@@ -845,8 +1683,8 @@ pub async fn handle_api_health(
         return e.into_response();
     }
 
-    let snapshot = zeroclaw_runtime::health::snapshot();
-    Json(serde_json::json!({"health": snapshot})).into_response()
+    let health = zeroclaw_runtime::health::snapshot();
+    Json(ComponentHealthResponse { health }).into_response()
 }
 
 // ── Helpers ─────────────────────────────────────────────────────
@@ -857,71 +1695,75 @@ pub async fn handle_api_health(
 pub async fn handle_api_sessions_list(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
-        return Json(serde_json::json!({
-            "sessions": [],
-            "message": "Session persistence is disabled"
-        }))
-        .into_response();
+        return Json(SessionsResponse::persistence_disabled()).into_response();
     };
 
     let all_metadata = backend.list_sessions_with_metadata();
-    let gw_sessions: Vec<serde_json::Value> = all_metadata
+    let sessions = all_metadata
         .into_iter()
         .filter_map(|meta| {
-            let session_id = meta.key.strip_prefix("gw_")?;
-            let mut entry = serde_json::json!({
-                "session_id": session_id,
-                "created_at": meta.created_at.to_rfc3339(),
-                "last_activity": meta.last_activity.to_rfc3339(),
-                "message_count": meta.message_count,
-            });
-            if let Some(name) = meta.name {
-                entry["name"] = serde_json::Value::String(name);
-            }
-            Some(entry)
+            let session_id = rbac::session_id_from_key(principal, &meta.key)?;
+            let parent_session_id = meta
+                .parent_key
+                .as_deref()
+                .and_then(|key| rbac::session_id_from_key(principal, key))
+                .map(str::to_string);
+            Some(SessionInfo {
+                session_id: session_id.to_string(),
+                created_at: meta.created_at.to_rfc3339(),
+                last_activity: meta.last_activity.to_rfc3339(),
+                message_count: meta.message_count,
+                name: meta.name,
+                fork_point: parent_session_id.as_ref().and(meta.fork_point),
+                parent_session_id,
+            })
         })
         .collect();
 
-    Json(serde_json::json!({ "sessions": gw_sessions })).into_response()
+    Json(SessionsResponse {
+        sessions,
+        message: None,
+    })
+    .into_response()
 }
 
 /// GET /api/sessions/{id}/messages — load persisted gateway WebSocket chat transcript
 pub async fn handle_api_session_messages(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
-        return Json(serde_json::json!({
-            "session_id": id,
-            "messages": [],
-            "session_persistence": false,
-        }))
+        return Json(SessionMessagesResponse {
+            session_id: id,
+            messages: Vec::new(),
+            session_persistence: false,
+        })
         .into_response();
     };
 
-    let session_key = format!("gw_{id}");
-    let msgs = backend.load(&session_key);
-    let messages: Vec<serde_json::Value> = msgs
-        .into_iter()
-        .map(|m| serde_json::json!({ "role": m.role, "content": m.content }))
-        .collect();
+    let session_key = rbac::session_key(principal, &id);
+    let messages = backend.load(&session_key);
 
-    Json(serde_json::json!({
-        "session_id": id,
-        "messages": messages,
-        "session_persistence": true,
-    }))
+    Json(SessionMessagesResponse {
+        session_id: id,
+        messages,
+        session_persistence: true,
+    })
     .into_response()
 }
 
@@ -929,11 +1771,13 @@ pub async fn handle_api_session_messages(
 pub async fn handle_api_session_delete(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
         return (
@@ -943,7 +1787,7 @@ pub async fn handle_api_session_delete(
             .into_response();
     };
 
-    let session_key = format!("gw_{id}");
+    let session_key = rbac::session_key(principal, &id);
 
     // If a turn is in flight for this session, cancel it and evict the entry
     // from `cancel_tokens` here rather than leaving the WebSocket handler's
@@ -962,7 +1806,11 @@ pub async fn handle_api_session_delete(
     }
 
     match backend.delete_session(&session_key) {
-        Ok(true) => Json(serde_json::json!({"deleted": true, "session_id": id})).into_response(),
+        Ok(true) => Json(SessionDeletedResponse {
+            deleted: true,
+            session_id: id,
+        })
+        .into_response(),
         Ok(false) => (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({"error": "Session not found"})),
@@ -976,16 +1824,140 @@ pub async fn handle_api_session_delete(
     }
 }
 
-/// PUT /api/sessions/{id} — rename a gateway session
-pub async fn handle_api_session_rename(
+/// PUT /api/sessions/{id} — rename a gateway session
+pub async fn handle_api_session_rename(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(id): Path<String>,
+    Json(body): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let principal = principal.as_ref().map(|Extension(p)| p);
+
+    let Some(ref backend) = state.session_backend else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Session persistence is disabled"})),
+        )
+            .into_response();
+    };
+
+    let name = body["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "name is required"})),
+        )
+            .into_response();
+    }
+
+    let session_key = rbac::session_key(principal, &id);
+
+    // Verify the session exists before renaming
+    let sessions = backend.list_sessions();
+    if !sessions.contains(&session_key) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Session not found"})),
+        )
+            .into_response();
+    }
+
+    match backend.set_session_name(&session_key, name) {
+        Ok(()) => Json(SessionRenamedResponse {
+            session_id: id,
+            name: name.to_string(),
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to rename session: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/sessions/{id}/fork — branch a session at message `at` into a new
+/// session (`session_id` in the body, or a generated one)
+pub async fn handle_api_session_fork(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(id): Path<String>,
+    Json(body): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let principal = principal.as_ref().map(|Extension(p)| p);
+
+    let Some(ref backend) = state.session_backend else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Session persistence is disabled"})),
+        )
+            .into_response();
+    };
+
+    let Some(at) = body["at"].as_u64().and_then(|n| usize::try_from(n).ok()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "at (number of messages to keep) is required"})),
+        )
+            .into_response();
+    };
+    let fork_id = body["session_id"]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string);
+
+    let source_key = rbac::session_key(principal, &id);
+    let target_key = rbac::session_key(principal, &fork_id);
+    match backend.fork_session(&source_key, &target_key, at) {
+        Ok(fork_point) => (
+            StatusCode::CREATED,
+            Json(SessionForkResponse {
+                session_id: fork_id,
+                parent_session_id: id,
+                fork_point,
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            let status = match e.kind() {
+                std::io::ErrorKind::NotFound => StatusCode::NOT_FOUND,
+                std::io::ErrorKind::InvalidInput => StatusCode::BAD_REQUEST,
+                std::io::ErrorKind::AlreadyExists => StatusCode::CONFLICT,
+                std::io::ErrorKind::Unsupported => StatusCode::NOT_IMPLEMENTED,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(serde_json::json!({"error": format!("Failed to fork session: {e}")})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /api/sessions/{id}/export — download a transcript as Markdown, HTML, or JSON
+pub async fn handle_api_session_export(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
-    Json(body): Json<serde_json::Value>,
+    Query(params): Query<SessionExportQuery>,
 ) -> impl IntoResponse {
+    use zeroclaw_runtime::session_export::{self, ExportFormat};
+
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
         return (
@@ -995,34 +1967,125 @@ pub async fn handle_api_session_rename(
             .into_response();
     };
 
-    let name = body["name"].as_str().unwrap_or("").trim();
-    if name.is_empty() {
+    let format = match params
+        .format
+        .as_deref()
+        .unwrap_or("md")
+        .parse::<ExportFormat>()
+    {
+        Ok(format) => format,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    let session_key = rbac::session_key(principal, &id);
+    let Some(mut transcript) = session_export::export_session(
+        backend.as_ref(),
+        &session_key,
+        state.cost_tracker.as_deref(),
+    ) else {
         return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "name is required"})),
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Session not found"})),
         )
             .into_response();
+    };
+    if params.redact.unwrap_or(true) {
+        transcript = transcript.redacted();
     }
 
-    let session_key = format!("gw_{id}");
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        session_export::export_file_name(&id, format)
+    );
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        session_export::render(&transcript, format),
+    )
+        .into_response()
+}
 
-    // Verify the session exists before renaming
-    let sessions = backend.list_sessions();
-    if !sessions.contains(&session_key) {
+/// POST /api/sessions/import — store a JSON transcript (from `/export?format=json`)
+/// as a new session
+pub async fn handle_api_session_import(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Query(params): Query<SessionImportQuery>,
+    Json(body): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    use zeroclaw_runtime::session_export::{self, Transcript};
+
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let principal = principal.as_ref().map(|Extension(p)| p);
+
+    let Some(ref backend) = state.session_backend else {
         return (
             StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "Session not found"})),
+            Json(serde_json::json!({"error": "Session persistence is disabled"})),
         )
             .into_response();
-    }
+    };
 
-    match backend.set_session_name(&session_key, name) {
-        Ok(()) => Json(serde_json::json!({"session_id": id, "name": name})).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to rename session: {e}")})),
+    let transcript: Transcript = match serde_json::from_value(body) {
+        Ok(t) => t,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid transcript: {e}")})),
+            )
+                .into_response();
+        }
+    };
+
+    // Imports always land in the caller's own namespace, whatever key the
+    // transcript was exported from.
+    let session_id = params
+        .session_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            rbac::session_id_from_key(principal, &transcript.session.key).map(str::to_string)
+        })
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let session_key = rbac::session_key(principal, &session_id);
+
+    match session_export::import_transcript(backend.as_ref(), &transcript, Some(&session_key)) {
+        Ok(_) => (
+            StatusCode::CREATED,
+            Json(SessionImportResponse {
+                session_id,
+                message_count: transcript.messages.len(),
+            }),
         )
             .into_response(),
+        Err(e) => {
+            let status = match e.kind() {
+                std::io::ErrorKind::AlreadyExists => StatusCode::CONFLICT,
+                std::io::ErrorKind::InvalidData | std::io::ErrorKind::InvalidInput => {
+                    StatusCode::BAD_REQUEST
+                }
+                std::io::ErrorKind::Unsupported => StatusCode::NOT_IMPLEMENTED,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(serde_json::json!({"error": format!("Failed to import session: {e}")})),
+            )
+                .into_response()
+        }
     }
 }
 
@@ -1030,45 +2093,52 @@ pub async fn handle_api_session_rename(
 pub async fn handle_api_sessions_running(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
-        return Json(serde_json::json!({
-            "sessions": [],
-            "message": "Session persistence is disabled"
-        }))
-        .into_response();
+        return Json(SessionsResponse::persistence_disabled()).into_response();
     };
 
     let running = backend.list_running_sessions();
-    let sessions: Vec<serde_json::Value> = running
+    let sessions = running
         .into_iter()
         .filter_map(|meta| {
-            let session_id = meta.key.strip_prefix("gw_")?;
-            Some(serde_json::json!({
-                "session_id": session_id,
-                "created_at": meta.created_at.to_rfc3339(),
-                "last_activity": meta.last_activity.to_rfc3339(),
-                "message_count": meta.message_count,
-            }))
+            let session_id = rbac::session_id_from_key(principal, &meta.key)?;
+            Some(SessionInfo {
+                session_id: session_id.to_string(),
+                created_at: meta.created_at.to_rfc3339(),
+                last_activity: meta.last_activity.to_rfc3339(),
+                message_count: meta.message_count,
+                name: None,
+                parent_session_id: None,
+                fork_point: None,
+            })
         })
         .collect();
 
-    Json(serde_json::json!({ "sessions": sessions })).into_response()
+    Json(SessionsResponse {
+        sessions,
+        message: None,
+    })
+    .into_response()
 }
 
 /// GET /api/sessions/{id}/state — get session state
 pub async fn handle_api_session_state(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
     let Some(ref backend) = state.session_backend else {
         return (
@@ -1078,21 +2148,15 @@ pub async fn handle_api_session_state(
             .into_response();
     };
 
-    let session_key = format!("gw_{id}");
+    let session_key = rbac::session_key(principal, &id);
     match backend.get_session_state(&session_key) {
-        Ok(Some(ss)) => {
-            let mut resp = serde_json::json!({
-                "session_id": id,
-                "state": ss.state,
-            });
-            if let Some(turn_id) = ss.turn_id {
-                resp["turn_id"] = serde_json::Value::String(turn_id);
-            }
-            if let Some(started) = ss.turn_started_at {
-                resp["turn_started_at"] = serde_json::Value::String(started.to_rfc3339());
-            }
-            Json(resp).into_response()
-        }
+        Ok(Some(ss)) => Json(SessionStateResponse {
+            session_id: id,
+            state: ss.state,
+            turn_id: ss.turn_id,
+            turn_started_at: ss.turn_started_at.map(|t| t.to_rfc3339()),
+        })
+        .into_response(),
         Ok(None) => (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({"error": "Session not found"})),
@@ -1122,13 +2186,15 @@ pub async fn handle_api_session_state(
 pub async fn handle_api_session_abort(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let principal = principal.as_ref().map(|Extension(p)| p);
 
-    let session_key = format!("gw_{id}");
+    let session_key = rbac::session_key(principal, &id);
 
     // Look up and cancel the token. Hold the lock only long enough to
     // clone the token — cancellation itself does not need the lock.
@@ -1142,9 +2208,15 @@ pub async fn handle_api_session_abort(
     if let Some(token) = token {
         token.cancel();
         tracing::info!(session_key, "session abort requested");
-        Json(serde_json::json!({ "status": "aborted" })).into_response()
+        Json(SessionAbortResponse {
+            status: AbortStatus::Aborted,
+        })
+        .into_response()
     } else {
-        Json(serde_json::json!({ "status": "no_active_response" })).into_response()
+        Json(SessionAbortResponse {
+            status: AbortStatus::NoActiveResponse,
+        })
+        .into_response()
     }
 }
 
@@ -1173,7 +2245,7 @@ pub async fn handle_claude_code_hook(
         "Claude Code hook event received"
     );
 
-    Json(serde_json::json!({ "ok": true }))
+    Json(HookAck { ok: true })
 }
 
 #[cfg(test)]
@@ -1296,6 +2368,11 @@ mod tests {
             web_dist_dir: None,
             canvas_store: zeroclaw_runtime::tools::CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(parking_lot::Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             reload_tx: None,
             #[cfg(feature = "webauthn")]
             webauthn: None,
@@ -1326,6 +2403,7 @@ mod tests {
         let add_response = handle_api_cron_add(
             State(state.clone()),
             HeaderMap::new(),
+            None,
             Json(
                 serde_json::from_value::<CronAddBody>(serde_json::json!({
                     "name": "test-job",
@@ -1375,6 +2453,7 @@ mod tests {
         let response = handle_api_cron_add(
             State(state.clone()),
             HeaderMap::new(),
+            None,
             Json(
                 serde_json::from_value::<CronAddBody>(serde_json::json!({
                     "name": "agent-job",
@@ -1399,6 +2478,325 @@ mod tests {
         assert_eq!(jobs[0].prompt.as_deref(), Some("summarize the latest logs"));
     }
 
+    #[tokio::test]
+    async fn cron_api_restricts_shell_jobs_to_admins() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = zeroclaw_config::schema::Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..zeroclaw_config::schema::Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let state = test_state(config);
+        let operator = Principal {
+            user: Some("bob".into()),
+            role: zeroclaw_config::schema::GatewayRole::Operator,
+        };
+        let add = |body: serde_json::Value| {
+            handle_api_cron_add(
+                State(state.clone()),
+                HeaderMap::new(),
+                Some(Extension(operator.clone())),
+                Json(serde_json::from_value::<CronAddBody>(body).expect("body should deserialize")),
+            )
+        };
+
+        let shell = add(serde_json::json!({
+            "name": "shell-job",
+            "schedule": "*/5 * * * *",
+            "command": "echo hello"
+        }))
+        .await
+        .into_response();
+        assert_eq!(shell.status(), StatusCode::FORBIDDEN);
+
+        let agent = add(serde_json::json!({
+            "name": "agent-job",
+            "schedule": "*/5 * * * *",
+            "job_type": "agent",
+            "prompt": "summarize the latest logs"
+        }))
+        .await
+        .into_response();
+        assert_eq!(response_json(agent).await["status"], "ok");
+
+        let config = state.config.lock().clone();
+        assert_eq!(zeroclaw_runtime::cron::list_jobs(&config).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rbac_rejects_requests_without_a_token_once_users_exist() {
+        use tower::ServiceExt;
+
+        let mut config = zeroclaw_config::schema::Config::default();
+        config
+            .gateway
+            .users
+            .push(zeroclaw_config::schema::GatewayUserConfig {
+                name: "alice".into(),
+                role: zeroclaw_config::schema::GatewayRole::Admin,
+                tokens: vec![PairingGuard::token_hash("zc_alice")],
+            });
+        let mut state = test_state(config);
+        state.pairing = Arc::new(PairingGuard::new(true, &["zc_alice".into()]));
+        let app = axum::Router::new()
+            .route("/api/status", axum::routing::get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(state, rbac::authorize));
+        let request = |token: Option<&str>| {
+            let mut builder = axum::http::Request::get("/api/status");
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {token}"));
+            }
+            builder.body(axum::body::Body::empty()).unwrap()
+        };
+
+        let anonymous = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+        let unknown = app
+            .clone()
+            .oneshot(request(Some("zc_bogus")))
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), StatusCode::UNAUTHORIZED);
+        let alice = app.oneshot(request(Some("zc_alice"))).await.unwrap();
+        assert_eq!(alice.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn first_account_can_be_created_and_paired_by_an_unbound_token() {
+        use tower::ServiceExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = zeroclaw_config::schema::Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..zeroclaw_config::schema::Config::default()
+        };
+        let mut state = test_state(config);
+        state.pairing = Arc::new(PairingGuard::new(true, &["zc_admin".into()]));
+        let app = axum::Router::new()
+            .route(
+                "/api/users",
+                axum::routing::post(crate::api_users::handle_create),
+            )
+            .route(
+                "/api/users/{name}/paircode",
+                axum::routing::post(crate::api_users::handle_paircode),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                rbac::authorize,
+            ))
+            .with_state(state.clone());
+        let post = |path: &str, body: serde_json::Value| {
+            axum::http::Request::post(path)
+                .header("Authorization", "Bearer zc_admin")
+                .header("Content-Type", "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // The documented sequence: create an account, then mint its code.
+        let created = app
+            .clone()
+            .oneshot(post(
+                "/api/users",
+                serde_json::json!({ "name": "alice", "role": "operator" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let paircode = app
+            .clone()
+            .oneshot(post("/api/users/alice/paircode", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(paircode.status(), StatusCode::OK);
+        let code = response_json(paircode).await["pairing_code"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // Pairing alice (an operator) leaves the unbound token in charge.
+        let token = state
+            .pairing
+            .try_pair(&code, "alice-laptop")
+            .await
+            .unwrap()
+            .unwrap();
+        crate::persist_pairing_tokens(
+            state.config.clone(),
+            &state.pairing,
+            Some(("alice", &token)),
+        )
+        .await
+        .unwrap();
+        let again = app
+            .oneshot(post("/api/users/alice/paircode", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(again.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn cron_api_add_and_patch_dependencies_and_policies() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = zeroclaw_config::schema::Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..zeroclaw_config::schema::Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let state = test_state(config);
+        let upstream = zeroclaw_runtime::cron::add_shell_job_with_approval(
+            &state.config.lock().clone(),
+            None,
+            zeroclaw_runtime::cron::Schedule::Cron {
+                expr: "0 2 * * *".to_string(),
+                tz: None,
+            },
+            "echo backup",
+            None,
+            true,
+        )
+        .expect("job added");
+
+        let response = handle_api_cron_add(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Json(
+                serde_json::from_value::<CronAddBody>(serde_json::json!({
+                    "schedule": "0 3 * * *",
+                    "command": "echo verify",
+                    "depends_on": [{ "job_id": upstream.id, "on": "completion" }],
+                    "retry": { "max_attempts": 4 },
+                    "overlap": "queue"
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        let json = response_json(response).await;
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["job"]["depends_on"][0]["on"], "completion");
+        assert_eq!(json["job"]["retry"]["max_attempts"], 4);
+        assert_eq!(json["job"]["overlap"], "queue");
+
+        let id = json["job"]["id"].as_str().unwrap().to_string();
+        let response = handle_api_cron_patch(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Path(id.clone()),
+            Json(
+                serde_json::from_value::<CronPatchBody>(serde_json::json!({
+                    "retry": { "max_attempts": 0 },
+                    "overlap": "kill_previous"
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        let json = response_json(response).await;
+        assert_eq!(json["status"], "ok");
+        assert!(json["job"]["retry"].is_null());
+        assert_eq!(json["job"]["overlap"], "kill_previous");
+
+        let response = handle_api_cron_add(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Json(
+                serde_json::from_value::<CronAddBody>(serde_json::json!({
+                    "schedule": "0 3 * * *",
+                    "command": "echo verify",
+                    "depends_on": [{ "job_id": "missing" }]
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn cron_api_applies_calendar_and_rejects_unknown_ones() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = zeroclaw_config::schema::Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..zeroclaw_config::schema::Config::default()
+        };
+        config.calendars.insert(
+            "holidays".into(),
+            zeroclaw_config::schema::CalendarConfig {
+                dates: vec!["2099-12-25".into()],
+                ..Default::default()
+            },
+        );
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let state = test_state(config);
+
+        let response = handle_api_cron_add(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Json(
+                serde_json::from_value::<CronAddBody>(serde_json::json!({
+                    "schedule": "0 9 * * *",
+                    "command": "echo report",
+                    "calendar": { "name": "holidays", "mode": "business_days" }
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        let json = response_json(response).await;
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["job"]["calendar"]["name"], "holidays");
+        assert_eq!(json["job"]["calendar"]["mode"], "business_days");
+
+        let id = json["job"]["id"].as_str().unwrap().to_string();
+        let response = handle_api_cron_patch(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Path(id.clone()),
+            Json(
+                serde_json::from_value::<CronPatchBody>(serde_json::json!({
+                    "calendar": { "name": "missing" }
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = handle_api_cron_patch(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Path(id),
+            Json(
+                serde_json::from_value::<CronPatchBody>(serde_json::json!({
+                    "calendar": { "name": "" }
+                }))
+                .expect("body should deserialize"),
+            ),
+        )
+        .await
+        .into_response();
+        let json = response_json(response).await;
+        assert_eq!(json["status"], "ok");
+        assert!(json["job"]["calendar"].is_null());
+    }
+
     #[tokio::test]
     async fn cron_api_rejects_announce_delivery_without_target() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -1413,6 +2811,7 @@ mod tests {
         let response = handle_api_cron_add(
             State(state.clone()),
             HeaderMap::new(),
+            None,
             Json(
                 serde_json::from_value::<CronAddBody>(serde_json::json!({
                     "name": "invalid-delivery-job",
@@ -1460,6 +2859,7 @@ mod tests {
         let response = handle_api_cron_add(
             State(state.clone()),
             HeaderMap::new(),
+            None,
             Json(
                 serde_json::from_value::<CronAddBody>(serde_json::json!({
                     "name": "invalid-delivery-job",
@@ -1518,10 +2918,14 @@ mod tests {
         )
         .expect("job added");
 
-        let response =
-            handle_api_cron_run(State(state.clone()), HeaderMap::new(), Path(job.id.clone()))
-                .await
-                .into_response();
+        let response = handle_api_cron_run(
+            State(state.clone()),
+            HeaderMap::new(),
+            None,
+            Path(job.id.clone()),
+        )
+        .await
+        .into_response();
 
         assert_eq!(response.status(), StatusCode::OK);
         let json = response_json(response).await;
@@ -1555,6 +2959,7 @@ mod tests {
         let response = handle_api_cron_run(
             State(state),
             HeaderMap::new(),
+            None,
             Path("does-not-exist".to_string()),
         )
         .await
@@ -309,8 +309,10 @@ fn section_group(key: &str) -> &'static str {
         "agent"
         | "autonomy"
         | "cron"
+        | "hands"
         | "heartbeat"
         | "hooks"
+        | "outbox"
         | "pacing"
         | "pipeline"
         | "query_classification"
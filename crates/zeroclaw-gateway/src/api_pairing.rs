@@ -15,11 +15,14 @@ use std::path::{Path, PathBuf};
 
 /// Metadata about a paired device.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DeviceInfo {
     pub id: String,
     pub name: Option<String>,
     pub device_type: Option<String>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub paired_at: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub last_seen: DateTime<Utc>,
     pub ip_address: Option<String>,
     /// macOS TCC permissions (and equivalent on other OSes) the device reports as granted.
@@ -28,6 +31,52 @@ pub struct DeviceInfo {
     pub capabilities: Option<Vec<String>>,
 }
 
+/// `POST /api/pairing/initiate` response.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PairingCodeResponse {
+    pub pairing_code: String,
+    pub message: String,
+}
+
+/// `POST /api/pair` response: the bearer token for the new device.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PairedResponse {
+    pub token: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct DevicesResponse {
+    pub devices: Vec<DeviceInfo>,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct DeviceRevokedResponse {
+    pub message: String,
+    pub device_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CapabilitiesResponse {
+    pub message: String,
+    pub capabilities: Vec<String>,
+}
+
+/// `POST /api/devices/{id}/token/rotate` response: a code to re-pair with.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct TokenRotateResponse {
+    pub device_id: String,
+    pub pairing_code: String,
+    pub message: String,
+}
+
 /// Registry of paired devices backed by SQLite.
 #[derive(Debug)]
 pub struct DeviceRegistry {
@@ -289,10 +338,10 @@ pub async fn initiate_pairing(
     }
 
     match state.pairing.generate_new_pairing_code() {
-        Some(code) => Json(serde_json::json!({
-            "pairing_code": code,
-            "message": "New pairing code generated"
-        }))
+        Some(pairing_code) => Json(PairingCodeResponse {
+            pairing_code,
+            message: "New pairing code generated".into(),
+        })
         .into_response(),
         None => (
             StatusCode::SERVICE_UNAVAILABLE,
@@ -318,8 +367,19 @@ pub async fn submit_pairing_enhanced(
         .unwrap_or("unknown")
         .to_string();
 
-    match state.pairing.try_pair(code, &client_id).await {
-        Ok(Some(token)) => {
+    match state.pairing.try_pair_with_owner(code, &client_id).await {
+        Ok(Some((token, owner))) => {
+            // A user-specific code binds the token to that account.
+            if let Some(user) = owner.as_deref()
+                && let Err(err) = Box::pin(super::persist_pairing_tokens(
+                    state.config.clone(),
+                    &state.pairing,
+                    Some((user, token.as_str())),
+                ))
+                .await
+            {
+                tracing::error!("🔐 Failed to bind paired token to user {user}: {err:#}");
+            }
             // Register the new device
             let token_hash = {
                 use sha2::{Digest, Sha256};
@@ -340,10 +400,10 @@ pub async fn submit_pairing_enhanced(
                     },
                 );
             }
-            Json(serde_json::json!({
-                "token": token,
-                "message": "Pairing successful"
-            }))
+            Json(PairedResponse {
+                token,
+                message: "Pairing successful".into(),
+            })
             .into_response()
         }
         Ok(None) => (StatusCode::BAD_REQUEST, "Invalid or expired pairing code").into_response(),
@@ -368,11 +428,7 @@ pub async fn list_devices(State(state): State<AppState>, headers: HeaderMap) ->
         .unwrap_or_default();
 
     let count = devices.len();
-    Json(serde_json::json!({
-        "devices": devices,
-        "count": count
-    }))
-    .into_response()
+    Json(DevicesResponse { devices, count }).into_response()
 }
 
 /// DELETE /api/devices/{id} — revoke a paired device
@@ -392,10 +448,10 @@ pub async fn revoke_device(
         .unwrap_or(false);
 
     if revoked {
-        Json(serde_json::json!({
-            "message": "Device revoked",
-            "device_id": device_id
-        }))
+        Json(DeviceRevokedResponse {
+            message: "Device revoked".into(),
+            device_id,
+        })
         .into_response()
     } else {
         (StatusCode::NOT_FOUND, "Device not found").into_response()
@@ -447,10 +503,10 @@ pub async fn update_my_capabilities(
     };
 
     if registry.update_capabilities(&token_hash, capabilities.clone()) {
-        Json(serde_json::json!({
-            "message": "Capabilities updated",
-            "capabilities": capabilities,
-        }))
+        Json(CapabilitiesResponse {
+            message: "Capabilities updated".into(),
+            capabilities,
+        })
         .into_response()
     } else {
         (StatusCode::NOT_FOUND, "Device not found for this token").into_response()
@@ -469,11 +525,11 @@ pub async fn rotate_token(
 
     // Generate a new pairing code for re-pairing
     match state.pairing.generate_new_pairing_code() {
-        Some(code) => Json(serde_json::json!({
-            "device_id": device_id,
-            "pairing_code": code,
-            "message": "Use this code to re-pair the device"
-        }))
+        Some(pairing_code) => Json(TokenRotateResponse {
+            device_id,
+            pairing_code,
+            message: "Use this code to re-pair the device".into(),
+        })
         .into_response(),
         None => (
             StatusCode::SERVICE_UNAVAILABLE,
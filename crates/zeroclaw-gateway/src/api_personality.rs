@@ -34,6 +34,7 @@ use super::api::require_auth;
 // ── Request / response shapes ───────────────────────────────────────
 
 #[derive(Debug, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct AgentQuery {
     /// Reserved for #5890. Accepted today, has no effect.
     #[serde(default)]
@@ -41,6 +42,7 @@ pub struct AgentQuery {
 }
 
 #[derive(Debug, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct TemplateQuery {
     /// Preset name. Only `default` is recognised today; unknown values
     /// fall through to the default preset rather than 400-ing.
@@ -64,18 +66,21 @@ pub struct TemplateQuery {
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct TemplateFile {
     pub filename: &'static str,
     pub content: String,
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct TemplateResponse {
     pub preset: &'static str,
     pub files: Vec<TemplateFile>,
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityIndexEntry {
     pub filename: &'static str,
     pub exists: bool,
@@ -84,12 +89,14 @@ pub struct PersonalityIndexEntry {
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityIndex {
     pub files: Vec<PersonalityIndexEntry>,
     pub max_chars: usize,
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityFileResponse {
     pub filename: String,
     pub content: String,
@@ -99,6 +106,7 @@ pub struct PersonalityFileResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityPutBody {
     pub content: String,
     /// Last `mtime_ms` the editor saw via GET. When provided and the
@@ -109,12 +117,14 @@ pub struct PersonalityPutBody {
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityPutResponse {
     pub bytes_written: u64,
     pub mtime_ms: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PersonalityConflict {
     pub error: &'static str,
     pub filename: String,
@@ -9,6 +9,19 @@ pub mod plugin_routes {
     };
 
     use super::super::AppState;
+    use crate::api::{PluginSummary, PluginsResponse};
+    use zeroclaw_plugins::PluginCapability;
+
+    fn capability_name(capability: &PluginCapability) -> String {
+        match capability {
+            PluginCapability::Tool => "tool",
+            PluginCapability::Channel => "channel",
+            PluginCapability::Memory => "memory",
+            PluginCapability::Observer => "observer",
+            PluginCapability::Skill => "skill",
+        }
+        .to_string()
+    }
 
     /// `GET /api/plugins` — list loaded plugins and their status.
     pub async fn list_plugins(
@@ -32,7 +45,7 @@ pub mod plugin_routes {
         let plugins_dir = config.plugins.plugins_dir.clone();
         drop(config);
 
-        let plugins: Vec<serde_json::Value> = if plugins_enabled {
+        let plugins: Vec<PluginSummary> = if plugins_enabled {
             let plugin_path = if plugins_dir.starts_with("~/") {
                 directories::UserDirs::new()
                     .map(|u| u.home_dir().join(&plugins_dir[2..]))
@@ -48,14 +61,12 @@ pub mod plugin_routes {
                     Ok(host) => host
                         .list_plugins()
                         .into_iter()
-                        .map(|p| {
-                            serde_json::json!({
-                                "name": p.name,
-                                "version": p.version,
-                                "description": p.description,
-                                "capabilities": p.capabilities,
-                                "loaded": p.loaded,
-                            })
+                        .map(|p| PluginSummary {
+                            capabilities: p.capabilities.iter().map(capability_name).collect(),
+                            name: p.name,
+                            version: p.version,
+                            description: p.description,
+                            loaded: p.loaded,
                         })
                         .collect(),
                     Err(_) => vec![],
@@ -67,11 +78,11 @@ pub mod plugin_routes {
             vec![]
         };
 
-        Json(serde_json::json!({
-            "plugins_enabled": plugins_enabled,
-            "plugins_dir": plugins_dir,
-            "plugins": plugins,
-        }))
+        Json(PluginsResponse {
+            plugins_enabled,
+            plugins_dir,
+            plugins,
+        })
         .into_response()
     }
 }
@@ -0,0 +1,740 @@
+//! SOP management and approval API handlers.
+//!
+//! The gateway works on the process-wide [`SopEngine`] that the agent's
+//! `sop_*` tools and the SOP triggers share, so runs started anywhere in the
+//! daemon can be listed and approved here. It records every transition through
+//! [`SopAuditLogger`] and [`SopMetricsCollector`]. Each run transition is
+//! broadcast on the SSE bus as a `sop_run` event. Approvals are attributed to
+//! the account behind the bearer token.
+
+use super::AppState;
+use super::api::require_auth;
+use super::rbac::Principal;
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use zeroclaw_config::schema::Config;
+use zeroclaw_memory::Memory;
+use zeroclaw_runtime::sop::engine::now_iso8601;
+use zeroclaw_runtime::sop::metrics::MetricsSnapshot;
+use zeroclaw_runtime::sop::{
+    DeterministicSavings, Sop, SopAuditLogger, SopEngine, SopEvent, SopExecutionMode,
+    SopMetricsCollector, SopPriority, SopRun, SopRunAction, SopRunStatus, SopStep, SopStepResult,
+    SopStepStatus, SopTriggerSource,
+};
+
+/// SOP engine plus its audit and metrics sinks, shared by the handlers.
+pub struct SopApi {
+    pub engine: Arc<Mutex<SopEngine>>,
+    pub audit: Arc<SopAuditLogger>,
+    pub metrics: Arc<SopMetricsCollector>,
+}
+
+impl SopApi {
+    /// Attach to the shared SOP engine when `[sop].sops_dir` is configured;
+    /// `None` otherwise.
+    pub async fn load(config: &Config, mem: Arc<dyn Memory>) -> Option<Self> {
+        let engine = zeroclaw_runtime::sop::shared_engine(config)?;
+        let audit = SopAuditLogger::new(mem.clone());
+        let metrics = SopMetricsCollector::rebuild_from_memory(mem.as_ref())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("SOP metrics rebuild failed, starting empty: {e:#}");
+                SopMetricsCollector::new()
+            });
+        Some(Self {
+            engine,
+            audit: Arc::new(audit),
+            metrics: Arc::new(metrics),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RunsQuery {
+    /// Only runs of this SOP
+    pub sop: Option<String>,
+    /// Only runs in this status (e.g. `waiting_approval`)
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct StartRunBody {
+    /// Trigger payload handed to the first step
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct AdvanceBody {
+    pub status: SopStepStatus,
+    #[serde(default)]
+    pub output: serde_json::Value,
+    /// Parallel branch being reported; defaults to the current step
+    #[serde(default)]
+    pub step: Option<u32>,
+}
+
+pub(crate) type ApiError = (StatusCode, Json<serde_json::Value>);
+
+fn api_error(status: StatusCode, message: impl Into<String>) -> ApiError {
+    (status, Json(serde_json::json!({ "error": message.into() })))
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    api_error(status, message).into_response()
+}
+
+pub(crate) fn sop_api(state: &AppState) -> Result<&SopApi, ApiError> {
+    state.sops.as_deref().ok_or_else(|| {
+        api_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SOPs are not enabled (set [sop].sops_dir)",
+        )
+    })
+}
+
+fn lock_engine(api: &SopApi) -> Result<std::sync::MutexGuard<'_, SopEngine>, ApiError> {
+    api.engine.lock().map_err(|e| {
+        api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("SOP engine lock poisoned: {e}"),
+        )
+    })
+}
+
+fn is_terminal(status: SopRunStatus) -> bool {
+    matches!(
+        status,
+        SopRunStatus::Completed | SopRunStatus::Failed | SopRunStatus::Cancelled
+    )
+}
+
+fn action_run_id(action: &SopRunAction) -> &str {
+    match action {
+        SopRunAction::ExecuteStep { run_id, .. }
+        | SopRunAction::WaitApproval { run_id, .. }
+        | SopRunAction::DeterministicStep { run_id, .. }
+        | SopRunAction::CheckpointWait { run_id, .. }
+        | SopRunAction::Parallel { run_id, .. }
+        | SopRunAction::AwaitBranches { run_id, .. }
+        | SopRunAction::Completed { run_id, .. }
+        | SopRunAction::Failed { run_id, .. } => run_id,
+    }
+}
+
+/// What the run needs next, tagged by `kind`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NextAction {
+    /// The agent should carry out `step`.
+    ExecuteStep {
+        step: SopStep,
+        context: String,
+    },
+    /// `step` is waiting for an operator approval.
+    WaitApproval {
+        step: SopStep,
+        context: String,
+    },
+    /// `step` runs without the LLM on the piped `input`.
+    DeterministicStep {
+        step: SopStep,
+        input: serde_json::Value,
+    },
+    /// A deterministic checkpoint is waiting for approval.
+    CheckpointWait {
+        step: SopStep,
+    },
+    /// `step` fans out into concurrent branches.
+    Parallel {
+        step: SopStep,
+        branches: Vec<NextAction>,
+    },
+    /// The run is waiting on the listed branch steps.
+    AwaitBranches {
+        pending: Vec<u32>,
+    },
+    Completed,
+    Failed {
+        reason: String,
+    },
+}
+
+impl From<&SopRunAction> for NextAction {
+    fn from(action: &SopRunAction) -> Self {
+        match action {
+            SopRunAction::ExecuteStep { step, context, .. } => Self::ExecuteStep {
+                step: step.clone(),
+                context: context.clone(),
+            },
+            SopRunAction::WaitApproval { step, context, .. } => Self::WaitApproval {
+                step: step.clone(),
+                context: context.clone(),
+            },
+            SopRunAction::DeterministicStep { step, input, .. } => Self::DeterministicStep {
+                step: step.clone(),
+                input: input.clone(),
+            },
+            SopRunAction::CheckpointWait { step, .. } => {
+                Self::CheckpointWait { step: step.clone() }
+            }
+            SopRunAction::Parallel { step, branches, .. } => Self::Parallel {
+                step: step.clone(),
+                branches: branches.iter().map(Self::from).collect(),
+            },
+            SopRunAction::AwaitBranches { pending, .. } => Self::AwaitBranches {
+                pending: pending.clone(),
+            },
+            SopRunAction::Completed { .. } => Self::Completed,
+            SopRunAction::Failed { reason, .. } => Self::Failed {
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+/// One loaded SOP in `GET /api/sops`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopSummary {
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub priority: SopPriority,
+    pub execution_mode: SopExecutionMode,
+    /// Triggers in their display form, e.g. `webhook:/deploy`.
+    pub triggers: Vec<String>,
+    /// Number of steps.
+    pub steps: usize,
+    pub active_runs: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopsResponse {
+    pub sops: Vec<SopSummary>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopDetailResponse {
+    pub sop: Sop,
+    /// Whether a new run would be admitted right now.
+    pub can_start: bool,
+}
+
+/// A run after a transition, with what it needs next.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopTransitionResponse {
+    pub run: SopRun,
+    pub next: NextAction,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopRunsResponse {
+    pub runs: Vec<SopRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopRunResponse {
+    pub run: SopRun,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopMetricsResponse {
+    pub metrics: MetricsSnapshot,
+    pub deterministic_savings: DeterministicSavings,
+}
+
+/// Broadcast a run transition to SSE subscribers.
+fn publish(state: &AppState, event: &str, run: &SopRun, actor: Option<&str>) {
+    let _ = state.event_tx.send(serde_json::json!({
+        "type": "sop_run",
+        "event": event,
+        "run_id": run.run_id,
+        "sop_name": run.sop_name,
+        "status": run.status,
+        "current_step": run.current_step,
+        "total_steps": run.total_steps,
+        "actor": actor,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    }));
+}
+
+/// Record a finished run in the audit log and metrics.
+async fn record_if_finished(api: &SopApi, run: &SopRun) {
+    if !is_terminal(run.status) {
+        return;
+    }
+    if let Err(e) = api.audit.log_run_complete(run).await {
+        tracing::warn!("SOP audit log_run_complete failed: {e}");
+    }
+    api.metrics.record_run_complete(run);
+}
+
+fn transition_response(run: SopRun, action: &SopRunAction) -> Response {
+    Json(SopTransitionResponse {
+        run,
+        next: NextAction::from(action),
+    })
+    .into_response()
+}
+
+/// GET /api/sops — loaded SOP definitions
+pub async fn handle_list(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let engine = match lock_engine(api) {
+        Ok(engine) => engine,
+        Err(e) => return e.into_response(),
+    };
+    let sops = engine
+        .sops()
+        .iter()
+        .map(|sop| {
+            let active = engine
+                .active_runs()
+                .values()
+                .filter(|r| r.sop_name == sop.name)
+                .count();
+            SopSummary {
+                name: sop.name.clone(),
+                description: sop.description.clone(),
+                version: sop.version.clone(),
+                priority: sop.priority,
+                execution_mode: sop.execution_mode,
+                triggers: sop.triggers.iter().map(ToString::to_string).collect(),
+                steps: sop.steps.len(),
+                active_runs: active,
+            }
+        })
+        .collect();
+    Json(SopsResponse { sops }).into_response()
+}
+
+/// GET /api/sops/{name} — one SOP definition with its steps
+pub async fn handle_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let engine = match lock_engine(api) {
+        Ok(engine) => engine,
+        Err(e) => return e.into_response(),
+    };
+    match engine.get_sop(&name) {
+        Some(sop) => Json(SopDetailResponse {
+            sop: sop.clone(),
+            can_start: engine.can_start(&name),
+        })
+        .into_response(),
+        None => error(StatusCode::NOT_FOUND, format!("SOP '{name}' not found")),
+    }
+}
+
+/// POST /api/sops/{name}/run — start a manual run
+pub async fn handle_start(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(name): Path<String>,
+    body: Option<Json<StartRunBody>>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let payload = body.and_then(|Json(b)| b.payload).map(|p| match p {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    });
+    let event = SopEvent {
+        source: SopTriggerSource::Manual,
+        topic: None,
+        payload,
+        timestamp: now_iso8601(),
+    };
+
+    let actor = principal.as_ref().map(|Extension(p)| p.display_name());
+    let (run, action) = match start_run(&state, api, &name, event, actor).await {
+        Ok(started) => started,
+        Err(e) => return e.into_response(),
+    };
+
+    let mut resp = transition_response(run, &action);
+    *resp.status_mut() = StatusCode::CREATED;
+    resp
+}
+
+/// Start a run of `name`, record it and broadcast `started`. Shared with the
+/// signed-webhook sources, which start runs from verified deliveries.
+pub(crate) async fn start_run(
+    state: &AppState,
+    api: &SopApi,
+    name: &str,
+    event: SopEvent,
+    actor: Option<&str>,
+) -> Result<(SopRun, SopRunAction), ApiError> {
+    let (action, run) = {
+        let mut engine = lock_engine(api)?;
+        if engine.get_sop(name).is_none() {
+            return Err(api_error(
+                StatusCode::NOT_FOUND,
+                format!("SOP '{name}' not found"),
+            ));
+        }
+        let action = engine
+            .start_run(name, event)
+            .map_err(|e| api_error(StatusCode::CONFLICT, e.to_string()))?;
+        let run = engine.get_run(action_run_id(&action)).cloned();
+        (action, run)
+    };
+    let run =
+        run.ok_or_else(|| api_error(StatusCode::INTERNAL_SERVER_ERROR, "Started run not found"))?;
+
+    if let Err(e) = api.audit.log_run_start(&run).await {
+        tracing::warn!("SOP audit log_run_start failed: {e}");
+    }
+    record_if_finished(api, &run).await;
+    publish(state, "started", &run, actor);
+    Ok((run, action))
+}
+
+/// GET /api/sops/runs — active and finished runs, newest first
+pub async fn handle_runs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<RunsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let engine = match lock_engine(api) {
+        Ok(engine) => engine,
+        Err(e) => return e.into_response(),
+    };
+    let mut runs: Vec<SopRun> = engine
+        .active_runs()
+        .values()
+        .chain(engine.finished_runs(None))
+        .filter(|r| params.sop.as_deref().is_none_or(|s| r.sop_name == s))
+        .filter(|r| {
+            params
+                .status
+                .as_deref()
+                .is_none_or(|s| r.status.to_string() == s)
+        })
+        .cloned()
+        .collect();
+    runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Json(SopRunsResponse { runs }).into_response()
+}
+
+/// GET /api/sops/runs/{run_id} — one run, falling back to the audit log
+pub async fn handle_run_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(run_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let live = match lock_engine(api) {
+        Ok(engine) => engine.get_run(&run_id).cloned(),
+        Err(e) => return e.into_response(),
+    };
+    let run = match live {
+        Some(run) => Some(run),
+        // Runs from before a restart only survive in the audit log.
+        None => api.audit.get_run(&run_id).await.ok().flatten(),
+    };
+    match run {
+        Some(run) => Json(SopRunResponse { run }).into_response(),
+        None => error(StatusCode::NOT_FOUND, format!("Run '{run_id}' not found")),
+    }
+}
+
+/// POST /api/sops/runs/{run_id}/approve — approve a waiting step or checkpoint
+pub async fn handle_approve(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(run_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let approver = principal
+        .as_ref()
+        .map(|Extension(p)| p.display_name().to_string());
+
+    let (action, approved_step, run) = {
+        let mut engine = match lock_engine(api) {
+            Ok(engine) => engine,
+            Err(e) => return e.into_response(),
+        };
+        let Some(step) = engine.active_runs().get(&run_id).map(|r| r.current_step) else {
+            return error(
+                StatusCode::NOT_FOUND,
+                format!("Active run '{run_id}' not found"),
+            );
+        };
+        match engine.approve(&run_id) {
+            Ok(action) => (action, step, engine.get_run(&run_id).cloned()),
+            Err(e) => return error(StatusCode::CONFLICT, e.to_string()),
+        }
+    };
+    let Some(run) = run else {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "Approved run not found");
+    };
+
+    if let Err(e) = api
+        .audit
+        .log_approval_by(&run, approved_step, approver.as_deref())
+        .await
+    {
+        tracing::warn!("SOP audit log_approval failed: {e}");
+    }
+    api.metrics.record_approval(&run.sop_name, &run.run_id);
+    record_if_finished(api, &run).await;
+    publish(&state, "approved", &run, approver.as_deref());
+
+    transition_response(run, &action)
+}
+
+/// POST /api/sops/runs/{run_id}/advance — report the current step's result
+pub async fn handle_advance(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(run_id): Path<String>,
+    Json(body): Json<AdvanceBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+
+    let (action, step_result, run) = {
+        let mut engine = match lock_engine(api) {
+            Ok(engine) => engine,
+            Err(e) => return e.into_response(),
+        };
+        let Some(current) = engine.active_runs().get(&run_id).cloned() else {
+            return error(
+                StatusCode::NOT_FOUND,
+                format!("Active run '{run_id}' not found"),
+            );
+        };
+        if current.status != SopRunStatus::Running {
+            return error(
+                StatusCode::CONFLICT,
+                format!(
+                    "Run {run_id} is {}; approve it before reporting a result",
+                    current.status
+                ),
+            );
+        }
+        let deterministic = engine
+            .get_sop(&current.sop_name)
+            .is_some_and(|s| s.execution_mode == SopExecutionMode::Deterministic);
+
+        let now = now_iso8601();
+        let step_result = SopStepResult {
+            step_number: body.step.unwrap_or(current.current_step),
+            status: body.status,
+            output: match &body.output {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            },
+            started_at: now.clone(),
+            completed_at: Some(now),
+        };
+        // Deterministic runs pipe structured output into the next step.
+        let result = if deterministic && body.status != SopStepStatus::Failed && body.step.is_none()
+        {
+            engine.advance_deterministic_step(&run_id, body.output)
+        } else {
+            engine.advance_step(&run_id, step_result.clone())
+        };
+        match result {
+            Ok(action) => (action, step_result, engine.get_run(&run_id).cloned()),
+            Err(e) => return error(StatusCode::CONFLICT, e.to_string()),
+        }
+    };
+    let Some(run) = run else {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "Advanced run not found");
+    };
+
+    if let Err(e) = api.audit.log_step_result(&run_id, &step_result).await {
+        tracing::warn!("SOP audit log_step_result failed: {e}");
+    }
+    record_if_finished(api, &run).await;
+    let actor = principal.as_ref().map(|Extension(p)| p.display_name());
+    publish(&state, "advanced", &run, actor);
+
+    transition_response(run, &action)
+}
+
+/// POST /api/sops/runs/{run_id}/cancel — cancel an active run
+pub async fn handle_cancel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Path(run_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+
+    let run = {
+        let mut engine = match lock_engine(api) {
+            Ok(engine) => engine,
+            Err(e) => return e.into_response(),
+        };
+        if let Err(e) = engine.cancel_run(&run_id) {
+            return error(StatusCode::NOT_FOUND, e.to_string());
+        }
+        engine.get_run(&run_id).cloned()
+    };
+    let Some(run) = run else {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "Cancelled run not found");
+    };
+
+    record_if_finished(api, &run).await;
+    let actor = principal.as_ref().map(|Extension(p)| p.display_name());
+    publish(&state, "cancelled", &run, actor);
+
+    Json(SopRunResponse { run }).into_response()
+}
+
+/// GET /api/sops/metrics — aggregated SOP metrics
+pub async fn handle_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let api = match sop_api(&state) {
+        Ok(api) => api,
+        Err(e) => return e.into_response(),
+    };
+    let savings = match lock_engine(api) {
+        Ok(engine) => engine.deterministic_savings().clone(),
+        Err(e) => return e.into_response(),
+    };
+    Json(SopMetricsResponse {
+        metrics: api.metrics.snapshot(),
+        deterministic_savings: savings,
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroclaw_runtime::sop::{SopJoin, SopStepKind};
+
+    #[test]
+    fn next_action_names_the_next_move() {
+        let step = SopStep {
+            number: 1,
+            title: "Check".into(),
+            body: "Check it".into(),
+            suggested_tools: vec![],
+            requires_confirmation: true,
+            kind: SopStepKind::Execute,
+            schema: None,
+            id: None,
+            next: Vec::new(),
+            on_failure: None,
+            branches: Vec::new(),
+            join: SopJoin::default(),
+            retries: 0,
+            max_visits: None,
+        };
+        let wait = SopRunAction::WaitApproval {
+            run_id: "r1".into(),
+            step: step.clone(),
+            context: "ctx".into(),
+        };
+        let json = serde_json::to_value(NextAction::from(&wait)).unwrap();
+        assert_eq!(json["kind"], "wait_approval");
+        assert_eq!(json["step"]["title"], "Check");
+
+        let failed = SopRunAction::Failed {
+            run_id: "r1".into(),
+            sop_name: "s".into(),
+            reason: "boom".into(),
+        };
+        let json = serde_json::to_value(NextAction::from(&failed)).unwrap();
+        assert_eq!(json["kind"], "failed");
+        assert_eq!(json["reason"], "boom");
+    }
+
+    #[test]
+    fn sop_routes_do_not_conflict() {
+        // Static segments (`runs`, `metrics`) must coexist with `{name}`.
+        let _: axum::Router = axum::Router::new()
+            .route("/api/sops", axum::routing::get(|| async {}))
+            .route("/api/sops/metrics", axum::routing::get(|| async {}))
+            .route("/api/sops/runs", axum::routing::get(|| async {}))
+            .route("/api/sops/runs/{run_id}", axum::routing::get(|| async {}))
+            .route(
+                "/api/sops/runs/{run_id}/approve",
+                axum::routing::post(|| async {}),
+            )
+            .route("/api/sops/{name}", axum::routing::get(|| async {}))
+            .route("/api/sops/{name}/run", axum::routing::post(|| async {}));
+    }
+}
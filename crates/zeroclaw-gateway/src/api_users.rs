@@ -0,0 +1,272 @@
+//! Gateway user account API handlers.
+//!
+//! Accounts live in `[[gateway.users]]`; every change is saved back to
+//! config.toml. Route access is enforced by [`super::rbac::authorize`], so
+//! these handlers only repeat the bearer-token check.
+
+use super::AppState;
+use super::api::require_auth;
+use super::rbac::{self, Principal};
+use axum::{
+    Extension,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use zeroclaw_config::schema::{Config, GatewayRole, GatewayUserConfig};
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CreateUserBody {
+    pub name: String,
+    #[serde(default)]
+    pub role: GatewayRole,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct UpdateUserBody {
+    pub role: GatewayRole,
+}
+
+/// An account as the API shows it: token hashes are reduced to a count.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct UserInfo {
+    pub name: String,
+    pub role: GatewayRole,
+    /// Number of bearer tokens bound to the account.
+    pub tokens: usize,
+}
+
+impl From<&GatewayUserConfig> for UserInfo {
+    fn from(user: &GatewayUserConfig) -> Self {
+        Self {
+            name: user.name.clone(),
+            role: user.role,
+            tokens: user.tokens.len(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct MeResponse {
+    /// Account name; `null` for tokens not bound to a user.
+    pub user: Option<String>,
+    pub role: GatewayRole,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct UsersResponse {
+    pub users: Vec<UserInfo>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct UserDeletedResponse {
+    pub message: String,
+    pub name: String,
+    pub revoked_tokens: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct UserPairCodeResponse {
+    pub user: String,
+    pub pairing_code: String,
+    pub message: String,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+/// Apply `edit` to a copy of the config, save it, then publish it.
+async fn save_config<T>(
+    state: &AppState,
+    edit: impl FnOnce(&mut Config) -> Result<T, (StatusCode, String)>,
+) -> Result<T, Response> {
+    // parking_lot's guard is not Send, so edit a clone across the await.
+    let mut updated = state.config.lock().clone();
+    let out = edit(&mut updated).map_err(|(status, message)| error(status, message))?;
+    updated.save().await.map_err(|e| {
+        error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to save config: {e}"),
+        )
+    })?;
+    *state.config.lock() = updated;
+    Ok(out)
+}
+
+/// GET /api/me — the calling principal
+pub async fn handle_me(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let Some(Extension(principal)) = principal else {
+        return error(StatusCode::UNAUTHORIZED, "Unauthorized");
+    };
+    Json(MeResponse {
+        user: principal.user,
+        role: principal.role,
+    })
+    .into_response()
+}
+
+/// GET /api/users — list accounts
+pub async fn handle_list(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let users: Vec<_> = state
+        .config
+        .lock()
+        .gateway
+        .users
+        .iter()
+        .map(UserInfo::from)
+        .collect();
+    Json(UsersResponse { users }).into_response()
+}
+
+/// POST /api/users — create an account
+pub async fn handle_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<CreateUserBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    if !rbac::is_valid_user_name(&body.name) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "User names may only contain letters, digits, '-' and '_'",
+        );
+    }
+    let result = save_config(&state, |config| {
+        let users = &mut config.gateway.users;
+        if users.iter().any(|u| u.name == body.name) {
+            return Err((
+                StatusCode::CONFLICT,
+                format!("User '{}' already exists", body.name),
+            ));
+        }
+        let user = GatewayUserConfig {
+            name: body.name.clone(),
+            role: body.role,
+            tokens: Vec::new(),
+        };
+        let info = UserInfo::from(&user);
+        users.push(user);
+        Ok(info)
+    })
+    .await;
+    match result {
+        Ok(info) => (StatusCode::CREATED, Json(info)).into_response(),
+        Err(resp) => resp,
+    }
+}
+
+/// PUT /api/users/{name} — change an account's role
+pub async fn handle_update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Json(body): Json<UpdateUserBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let result = save_config(&state, |config| {
+        let user = config
+            .gateway
+            .users
+            .iter_mut()
+            .find(|u| u.name == name)
+            .ok_or_else(|| (StatusCode::NOT_FOUND, format!("User '{name}' not found")))?;
+        user.role = body.role;
+        Ok(UserInfo::from(&*user))
+    })
+    .await;
+    match result {
+        Ok(info) => Json(info).into_response(),
+        Err(resp) => resp,
+    }
+}
+
+/// DELETE /api/users/{name} — delete an account and revoke its tokens
+pub async fn handle_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let pairing = state.pairing.clone();
+    let result = save_config(&state, |config| {
+        let users = &mut config.gateway.users;
+        let index = users
+            .iter()
+            .position(|u| u.name == name)
+            .ok_or_else(|| (StatusCode::NOT_FOUND, format!("User '{name}' not found")))?;
+        let user = users.remove(index);
+        for hash in &user.tokens {
+            pairing.revoke_token_hash(hash);
+        }
+        config.gateway.paired_tokens = pairing.tokens();
+        Ok(user.tokens.len())
+    })
+    .await;
+    match result {
+        Ok(revoked_tokens) => Json(UserDeletedResponse {
+            message: "User deleted".into(),
+            name,
+            revoked_tokens,
+        })
+        .into_response(),
+        Err(resp) => resp,
+    }
+}
+
+/// POST /api/users/{name}/paircode — one-time pairing code bound to the account
+pub async fn handle_paircode(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let exists = state
+        .config
+        .lock()
+        .gateway
+        .users
+        .iter()
+        .any(|u| u.name == name);
+    if !exists {
+        return error(StatusCode::NOT_FOUND, format!("User '{name}' not found"));
+    }
+    match state.pairing.generate_pairing_code_for(Some(&name)) {
+        Some(pairing_code) => Json(UserPairCodeResponse {
+            user: name,
+            pairing_code,
+            message: "Redeem via POST /pair with the X-Pairing-Code header".into(),
+        })
+        .into_response(),
+        None => error(
+            StatusCode::BAD_REQUEST,
+            "Pairing is disabled (gateway.require_pairing = false)",
+        ),
+    }
+}
@@ -16,15 +16,48 @@ use axum::{
     response::{IntoResponse, Json},
 };
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use zeroclaw_tools::canvas::CanvasFrame;
 
 /// POST /api/canvas/:id request body.
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CanvasPostBody {
     pub content_type: Option<String>,
     pub content: String,
 }
 
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CanvasListResponse {
+    /// IDs of canvases that currently hold content.
+    pub canvases: Vec<String>,
+}
+
+/// A canvas and its current (or just-rendered) frame.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CanvasFrameResponse {
+    pub canvas_id: String,
+    pub frame: CanvasFrame,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CanvasHistoryResponse {
+    pub canvas_id: String,
+    /// Frames oldest first.
+    pub frames: Vec<CanvasFrame>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CanvasClearedResponse {
+    pub canvas_id: String,
+    /// Always `cleared`.
+    pub status: String,
+}
+
 /// GET /api/canvas — list all active canvases.
 pub async fn handle_canvas_list(
     State(state): State<AppState>,
@@ -35,7 +68,7 @@ pub async fn handle_canvas_list(
     }
 
     let ids = state.canvas_store.list();
-    Json(serde_json::json!({ "canvases": ids })).into_response()
+    Json(CanvasListResponse { canvases: ids }).into_response()
 }
 
 /// GET /api/canvas/:id — get current canvas content.
@@ -49,10 +82,10 @@ pub async fn handle_canvas_get(
     }
 
     match state.canvas_store.snapshot(&id) {
-        Some(frame) => Json(serde_json::json!({
-            "canvas_id": id,
-            "frame": frame,
-        }))
+        Some(frame) => Json(CanvasFrameResponse {
+            canvas_id: id,
+            frame,
+        })
         .into_response(),
         None => (
             StatusCode::NOT_FOUND,
@@ -73,10 +106,10 @@ pub async fn handle_canvas_history(
     }
 
     let history = state.canvas_store.history(&id);
-    Json(serde_json::json!({
-        "canvas_id": id,
-        "frames": history,
-    }))
+    Json(CanvasHistoryResponse {
+        canvas_id: id,
+        frames: history,
+    })
     .into_response()
 }
 
@@ -125,10 +158,10 @@ pub async fn handle_canvas_post(
     match state.canvas_store.render(&id, content_type, &body.content) {
         Some(frame) => (
             StatusCode::CREATED,
-            Json(serde_json::json!({
-                "canvas_id": id,
-                "frame": frame,
-            })),
+            Json(CanvasFrameResponse {
+                canvas_id: id,
+                frame,
+            }),
         )
             .into_response(),
         None => (
@@ -152,10 +185,10 @@ pub async fn handle_canvas_clear(
     }
 
     state.canvas_store.clear(&id);
-    Json(serde_json::json!({
-        "canvas_id": id,
-        "status": "cleared",
-    }))
+    Json(CanvasClearedResponse {
+        canvas_id: id,
+        status: "cleared".into(),
+    })
     .into_response()
 }
 
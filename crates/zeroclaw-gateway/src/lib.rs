@@ -15,6 +15,8 @@ pub mod api_pairing;
 pub mod api_personality;
 #[cfg(feature = "plugins-wasm")]
 pub mod api_plugins;
+pub mod api_sops;
+pub mod api_users;
 #[cfg(feature = "webauthn")]
 pub mod api_webauthn;
 pub mod auth_rate_limit;
@@ -22,13 +24,16 @@ pub mod canvas;
 pub mod hardware_context;
 pub mod node_tool;
 pub mod nodes;
+pub mod openai_compat;
 pub mod openapi;
+pub mod rbac;
 pub mod session_queue;
 pub mod sse;
 pub mod static_files;
 pub mod tls;
 #[cfg(feature = "gateway-voice-duplex")]
 pub mod voice_duplex;
+pub mod webhook_sources;
 pub mod ws;
 pub mod ws_approval;
 
@@ -38,8 +43,8 @@ use axum::{
     body::Bytes,
     extract::{ConnectInfo, Query, State},
     http::{HeaderMap, StatusCode, header},
-    response::{IntoResponse, Json},
-    routing::{delete, get, patch, post},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
 };
 use parking_lot::Mutex;
 use std::collections::HashMap;
@@ -63,6 +68,7 @@ use zeroclaw_providers::{self, Provider};
 use zeroclaw_runtime::cost::CostTracker;
 use zeroclaw_runtime::i18n;
 use zeroclaw_runtime::platform;
+use zeroclaw_runtime::security::AuditLogger;
 use zeroclaw_runtime::security::pairing::{PairingGuard, constant_time_eq, is_public_bind};
 use zeroclaw_runtime::tools;
 use zeroclaw_runtime::tools::CanvasStore;
@@ -300,6 +306,11 @@ impl IdempotencyStore {
         keys.insert(key.to_owned(), now);
         true
     }
+
+    /// Forget a recorded key so a retry of a failed request is processed.
+    fn forget(&self, key: &str) {
+        self.keys.lock().remove(key);
+    }
 }
 
 fn parse_client_ip(value: &str) -> Option<IpAddr> {
@@ -432,6 +443,12 @@ pub struct AppState {
     pub cancel_tokens: Arc<
         std::sync::Mutex<std::collections::HashMap<String, tokio_util::sync::CancellationToken>>,
     >,
+    /// Security audit log for gateway API calls (`None` when auditing is disabled)
+    pub audit_logger: Option<Arc<AuditLogger>>,
+    /// SOP engine behind `/api/sops` (`None` when `[sop].sops_dir` is unset)
+    pub sops: Option<Arc<api_sops::SopApi>>,
+    /// Routines engine fed by `/webhook/{source}` deliveries targeting `routine`
+    pub routines: Arc<Mutex<zeroclaw_runtime::routines::RoutinesEngine>>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -649,7 +666,18 @@ pub async fn run_gateway(
         let (tx, _rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
         tx
     });
-    let event_buffer = Arc::new(sse::EventBuffer::new(500));
+    // Every published event gets an ID and lands in the replay ring.
+    let event_buffer = Arc::new(
+        sse::EventBuffer::open(
+            &config.workspace_dir.join("events.db"),
+            sse::EVENT_HISTORY_CAPACITY,
+        )
+        .unwrap_or_else(|e| {
+            tracing::warn!("SSE event store unavailable, keeping history in memory: {e:#}");
+            sse::EventBuffer::new(sse::EVENT_HISTORY_CAPACITY)
+        }),
+    );
+    sse::spawn_event_recorder(&event_tx, Arc::clone(&event_buffer));
     // Extract webhook secret for authentication
     let webhook_secret_hash: Option<Arc<str>> =
         config.channels.webhook.as_ref().and_then(|webhook| {
@@ -746,6 +774,20 @@ pub async fn run_gateway(
             ))
         });
 
+    // Webhook channels reply through the durable outbox; register them so
+    // the dispatcher can retry a failed reply.
+    let webhook_channels: [Option<Arc<dyn Channel>>; 4] = [
+        whatsapp_channel.clone().map(|c| c as Arc<dyn Channel>),
+        linq_channel.clone().map(|c| c as Arc<dyn Channel>),
+        wati_channel.clone().map(|c| c as Arc<dyn Channel>),
+        nextcloud_talk_channel
+            .clone()
+            .map(|c| c as Arc<dyn Channel>),
+    ];
+    for channel in webhook_channels.into_iter().flatten() {
+        zeroclaw_runtime::outbox::dispatcher::register(channel);
+    }
+
     // Nextcloud Talk webhook secret for signature verification
     // Priority: environment variable > config file
     let nextcloud_talk_webhook_secret: Option<Arc<str>> =
@@ -784,6 +826,11 @@ pub async fn run_gateway(
         match zeroclaw_infra::make_session_backend(
             &config.workspace_dir,
             &config.channels.session_backend,
+            zeroclaw_infra::PostgresTarget {
+                db_url: config.storage.provider.config.db_url.as_deref(),
+                schema: &config.storage.provider.config.schema,
+                connect_timeout_secs: config.storage.provider.config.connect_timeout_secs,
+            },
         ) {
             Ok(backend) => {
                 tracing::info!(
@@ -951,7 +998,6 @@ pub async fn run_gateway(
         Arc::new(sse::BroadcastObserver::new(
             zeroclaw_runtime::observability::create_observer(&config.observability),
             event_tx.clone(),
-            event_buffer.clone(),
         ));
 
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
@@ -975,6 +1021,36 @@ pub async fn run_gateway(
         None
     };
 
+    // Gateway API audit trail (who did what), written alongside the agent's audit log.
+    let audit_logger = if config.security.audit.enabled {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .unwrap_or_else(|| config.workspace_dir.clone());
+        match AuditLogger::new(config.security.audit.clone(), zeroclaw_dir) {
+            Ok(logger) => Some(Arc::new(logger)),
+            Err(e) => {
+                tracing::warn!("Gateway audit logging disabled: {e:#}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let sops = api_sops::SopApi::load(&config, Arc::clone(&mem))
+        .await
+        .map(Arc::new);
+
+    let routines = Arc::new(Mutex::new(
+        zeroclaw_runtime::routines::RoutinesEngine::with_state(
+            zeroclaw_runtime::routines::load_routines(&config.workspace_dir),
+            &config.workspace_dir,
+        )
+        .with_calendars(zeroclaw_runtime::calendar::load_all(&config)),
+    ));
+
     let state = AppState {
         config: config_state,
         provider,
@@ -1012,6 +1088,9 @@ pub async fn run_gateway(
         web_dist_dir,
         canvas_store,
         cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+        audit_logger,
+        sops,
+        routines,
         #[cfg(feature = "webauthn")]
         webauthn: if config.security.webauthn.enabled {
             let secret_store = Arc::new(zeroclaw_runtime::security::SecretStore::new(
@@ -1038,6 +1117,8 @@ pub async fn run_gateway(
         },
     };
 
+    webhook_sources::spawn_routine_timer(state.clone());
+
     // Build router with middleware
     let inner = Router::new()
         // ── Admin routes (for CLI management) ──
@@ -1051,6 +1132,10 @@ pub async fn run_gateway(
         .route("/pair", post(handle_pair))
         .route("/pair/code", get(handle_pair_code))
         .route("/webhook", post(handle_webhook))
+        .route(
+            "/webhook/{source}",
+            post(webhook_sources::handle_source_webhook),
+        )
         .route("/whatsapp", get(handle_whatsapp_verify))
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
@@ -1117,9 +1202,22 @@ pub async fn run_gateway(
             delete(api::handle_api_cron_delete).patch(api::handle_api_cron_patch),
         )
         .route("/api/cron/{id}/runs", get(api::handle_api_cron_runs))
-        // Note: `/api/cron/{id}/run` is registered on a separate router below
-        // with a longer TimeoutLayer — manual cron triggers run the job
-        // synchronously and routinely exceed the 30s gateway-wide default.
+        .route("/api/outbox", get(api::handle_api_outbox_list))
+        .route(
+            "/api/outbox/replay-dead",
+            post(api::handle_api_outbox_replay_dead),
+        )
+        .route(
+            "/api/outbox/{id}",
+            get(api::handle_api_outbox_get).delete(api::handle_api_outbox_delete),
+        )
+        .route("/api/outbox/{id}/replay", post(api::handle_api_outbox_replay))
+        .route("/api/hands", get(api::handle_api_hands_list))
+        .route("/api/hands/{name}/history", get(api::handle_api_hands_history))
+        // Note: `/api/cron/{id}/run` and `/api/hands/{name}/run` are
+        // registered on a separate router below with a longer TimeoutLayer —
+        // manual triggers run the agent synchronously and routinely exceed
+        // the 30s gateway-wide default.
         .route("/api/integrations", get(api::handle_api_integrations))
         .route(
             "/api/integrations/settings",
@@ -1138,6 +1236,7 @@ pub async fn run_gateway(
         .route("/api/health", get(api::handle_api_health))
         .route("/api/sessions", get(api::handle_api_sessions_list))
         .route("/api/sessions/running", get(api::handle_api_sessions_running))
+        .route("/api/sessions/import", post(api::handle_api_session_import))
         .route(
             "/api/sessions/{id}/messages",
             get(api::handle_api_session_messages),
@@ -1145,6 +1244,11 @@ pub async fn run_gateway(
         .route("/api/sessions/{id}", delete(api::handle_api_session_delete).put(api::handle_api_session_rename))
         .route("/api/sessions/{id}/state", get(api::handle_api_session_state))
         .route("/api/sessions/{id}/abort", post(api::handle_api_session_abort))
+        .route("/api/sessions/{id}/fork", post(api::handle_api_session_fork))
+        .route(
+            "/api/sessions/{id}/export",
+            get(api::handle_api_session_export),
+        )
         // ── Pairing + Device management API ──
         .route("/api/pairing/initiate", post(api_pairing::initiate_pairing))
         .route("/api/pair", post(api_pairing::submit_pairing_enhanced))
@@ -1158,6 +1262,39 @@ pub async fn run_gateway(
             "/api/devices/{id}/token/rotate",
             post(api_pairing::rotate_token),
         )
+        // ── User accounts (RBAC) ──
+        .route("/api/me", get(api_users::handle_me))
+        .route(
+            "/api/users",
+            get(api_users::handle_list).post(api_users::handle_create),
+        )
+        .route(
+            "/api/users/{name}",
+            put(api_users::handle_update).delete(api_users::handle_delete),
+        )
+        .route(
+            "/api/users/{name}/paircode",
+            post(api_users::handle_paircode),
+        )
+        // ── SOPs ──
+        .route("/api/sops", get(api_sops::handle_list))
+        .route("/api/sops/metrics", get(api_sops::handle_metrics))
+        .route("/api/sops/runs", get(api_sops::handle_runs))
+        .route("/api/sops/runs/{run_id}", get(api_sops::handle_run_get))
+        .route(
+            "/api/sops/runs/{run_id}/approve",
+            post(api_sops::handle_approve),
+        )
+        .route(
+            "/api/sops/runs/{run_id}/advance",
+            post(api_sops::handle_advance),
+        )
+        .route(
+            "/api/sops/runs/{run_id}/cancel",
+            post(api_sops::handle_cancel),
+        )
+        .route("/api/sops/{name}", get(api_sops::handle_get))
+        .route("/api/sops/{name}/run", post(api_sops::handle_start))
         // ── Live Canvas (A2UI) routes ──
         .route("/api/canvas", get(canvas::handle_canvas_list))
         .route(
@@ -1210,6 +1347,8 @@ pub async fn run_gateway(
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         .route("/api/events/history", get(sse::handle_events_history))
+        // ── OpenAI-compatible API (completions sit on the long-running router) ──
+        .route("/v1/models", get(openai_compat::handle_models))
         // ── ACP client bridge ──
         .route("/acp", get(acp::handle_ws_acp))
         // ── WebSocket agent chat ──
@@ -1229,20 +1368,30 @@ pub async fn run_gateway(
             Duration::from_secs(gateway_request_timeout_secs()),
         ));
 
-    // Manual cron-trigger route lives on its own sub-router so it can opt out
-    // of the 30s gateway-wide TimeoutLayer. Layers attached here travel with
-    // the route through `merge`, so only this endpoint sees the longer
-    // timeout.
+    // Manual cron/hand triggers and OpenAI-compatible completion routes live
+    // on their own sub-router so they can opt out of the 30s gateway-wide
+    // TimeoutLayer: all of them run full agent turns. Layers attached here travel
+    // with the routes through `merge`, so only these endpoints see the
+    // longer timeout.
     let cron_run_router: Router = Router::new()
         .route("/api/cron/{id}/run", post(api::handle_api_cron_run))
-        .with_state(state)
+        .route("/api/hands/{name}/run", post(api::handle_api_hands_run))
+        .route(
+            "/v1/chat/completions",
+            post(openai_compat::handle_chat_completions),
+        )
+        .with_state(state.clone())
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(gateway_long_running_request_timeout_secs()),
         ));
 
-    let inner = inner.merge(cron_run_router);
+    // Role-based access control wraps both routers so the long-running routes
+    // are checked too; it runs before each handler's own token check.
+    let inner = inner
+        .merge(cron_run_router)
+        .layer(axum::middleware::from_fn_with_state(state, rbac::authorize));
 
     // Nest under path prefix when configured (axum strips prefix before routing).
     // nest() at "/prefix" handles both "/prefix" and "/prefix/*" but not "/prefix/"
@@ -1297,9 +1446,20 @@ pub async fn run_gateway(
                                 return;
                             }
                         };
+                        let peer_cert = tls_stream
+                            .get_ref()
+                            .1
+                            .peer_certificates()
+                            .and_then(|certs| certs.first())
+                            .map(|cert| tls::PeerCertificate {
+                                fingerprint: tls::cert_sha256_fingerprint(cert.as_ref()),
+                            });
                         let io = hyper_util::rt::TokioIo::new(tls_stream);
-                        let hyper_svc = hyper::service::service_fn(move |req: hyper::Request<hyper::body::Incoming>| {
+                        let hyper_svc = hyper::service::service_fn(move |mut req: hyper::Request<hyper::body::Incoming>| {
                             let mut svc = svc.clone();
+                            if let Some(cert) = &peer_cert {
+                                req.extensions_mut().insert(cert.clone());
+                            }
                             async move {
                                 tower::Service::call(&mut svc, req).await
                             }
@@ -1340,15 +1500,25 @@ pub async fn run_gateway(
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
 
+/// `GET /health` response.
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct HealthResponse {
+    /// Always `ok`.
+    pub status: String,
+    pub paired: bool,
+    pub require_pairing: bool,
+    pub runtime: zeroclaw_runtime::health::HealthSnapshot,
+}
+
 /// GET /health — always public (no secrets leaked)
 async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
-    let body = serde_json::json!({
-        "status": "ok",
-        "paired": state.pairing.is_paired(),
-        "require_pairing": state.pairing.require_pairing(),
-        "runtime": zeroclaw_runtime::health::snapshot_json(),
-    });
-    Json(body)
+    Json(HealthResponse {
+        status: "ok".into(),
+        paired: state.pairing.is_paired(),
+        require_pairing: state.pairing.require_pairing(),
+        runtime: zeroclaw_runtime::health::snapshot(),
+    })
 }
 
 /// Prometheus content type for text exposition format.
@@ -1405,13 +1575,25 @@ async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
     )
 }
 
+/// `POST /pair` response: the bearer token for the new client.
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PairResponse {
+    pub paired: bool,
+    /// Whether the token hash was written to config.toml; `false` means the
+    /// pairing only lasts for this process.
+    pub persisted: bool,
+    pub token: String,
+    pub message: String,
+}
+
 /// POST /pair — exchange one-time code for bearer token
 #[axum::debug_handler]
 async fn handle_pair(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Response {
     let rate_key =
         client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_pair(&rate_key) {
@@ -1420,7 +1602,7 @@ async fn handle_pair(
             "error": "Too many pairing requests. Please retry later.",
             "retry_after": RATE_LIMIT_WINDOW_SECS,
         });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
     }
 
     // ── Auth rate limiting (brute-force protection) ──
@@ -1430,7 +1612,7 @@ async fn handle_pair(
             "error": format!("Too many auth attempts. Try again in {}s.", e.retry_after_secs),
             "retry_after": e.retry_after_secs,
         });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
     }
 
     let code = headers
@@ -1438,35 +1620,40 @@ async fn handle_pair(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
 
-    match state.pairing.try_pair(code, &rate_key).await {
-        Ok(Some(token)) => {
+    match state.pairing.try_pair_with_owner(code, &rate_key).await {
+        Ok(Some((token, owner))) => {
             tracing::info!("🔐 New client paired successfully");
-            if let Err(err) =
-                Box::pin(persist_pairing_tokens(state.config.clone(), &state.pairing)).await
+            let bind_to = owner.as_deref().map(|user| (user, token.as_str()));
+            if let Err(err) = Box::pin(persist_pairing_tokens(
+                state.config.clone(),
+                &state.pairing,
+                bind_to,
+            ))
+            .await
             {
                 tracing::error!("🔐 Pairing succeeded but token persistence failed: {err:#}");
-                let body = serde_json::json!({
-                    "paired": true,
-                    "persisted": false,
-                    "token": token,
-                    "message": "Paired for this process, but failed to persist token to config.toml. Check config path and write permissions.",
-                });
-                return (StatusCode::OK, Json(body));
+                let body = PairResponse {
+                    paired: true,
+                    persisted: false,
+                    token,
+                    message: "Paired for this process, but failed to persist token to config.toml. Check config path and write permissions.".into(),
+                };
+                return Json(body).into_response();
             }
 
-            let body = serde_json::json!({
-                "paired": true,
-                "persisted": true,
-                "token": token,
-                "message": "Save this token — use it as Authorization: Bearer <token>"
-            });
-            (StatusCode::OK, Json(body))
+            let body = PairResponse {
+                paired: true,
+                persisted: true,
+                token,
+                message: "Save this token — use it as Authorization: Bearer <token>".into(),
+            };
+            Json(body).into_response()
         }
         Ok(None) => {
             state.auth_limiter.record_attempt(&rate_key);
             tracing::warn!("🔐 Pairing attempt with invalid code");
             let err = serde_json::json!({"error": "Invalid pairing code"});
-            (StatusCode::FORBIDDEN, Json(err))
+            (StatusCode::FORBIDDEN, Json(err)).into_response()
         }
         Err(lockout_secs) => {
             tracing::warn!(
@@ -1476,24 +1663,39 @@ async fn handle_pair(
                 "error": format!("Too many failed attempts. Try again in {lockout_secs}s."),
                 "retry_after": lockout_secs
             });
-            (StatusCode::TOO_MANY_REQUESTS, Json(err))
+            (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response()
         }
     }
 }
 
-async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
+/// Persist paired token hashes, optionally binding `(user, token)` to a
+/// `[[gateway.users]]` account whose pairing code was just redeemed.
+pub(crate) async fn persist_pairing_tokens(
+    config: Arc<Mutex<Config>>,
+    pairing: &PairingGuard,
+    bind_to: Option<(&str, &str)>,
+) -> Result<()> {
     let paired_tokens = pairing.tokens();
-    // This is needed because parking_lot's guard is not Send so we clone the inner
-    // this should be removed once async mutexes are used everywhere
-    let mut updated_cfg = { config.lock().clone() };
-    updated_cfg.gateway.paired_tokens = paired_tokens;
+    // Bind the live config first so the device is authorized for this run
+    // even if writing config.toml fails below.
+    // parking_lot's guard is not Send, so save a clone across the await.
+    let updated_cfg = {
+        let mut live = config.lock();
+        live.gateway.paired_tokens = paired_tokens;
+        if let Some((name, token)) = bind_to
+            && let Some(user) = live.gateway.users.iter_mut().find(|u| u.name == name)
+        {
+            let hash = PairingGuard::token_hash(token);
+            if !user.tokens.contains(&hash) {
+                user.tokens.push(hash);
+            }
+        }
+        live.clone()
+    };
     updated_cfg
         .save()
         .await
         .context("Failed to persist paired tokens to config.toml")?;
-
-    // Keep shared runtime config in sync with persisted tokens.
-    *config.lock() = updated_cfg;
     Ok(())
 }
 
@@ -1620,17 +1822,36 @@ async fn run_gateway_chat_with_tools(
 
 /// Webhook request body
 #[derive(serde::Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct WebhookBody {
     pub message: String,
 }
 
+/// `POST /webhook` reply.
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct WebhookResponse {
+    pub response: String,
+    pub model: String,
+}
+
+/// `POST /webhook` acknowledgement for a replayed `X-Idempotency-Key`.
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct WebhookDuplicate {
+    /// Always `duplicate`.
+    pub status: String,
+    pub idempotent: bool,
+    pub message: String,
+}
+
 /// POST /webhook — main webhook endpoint
 async fn handle_webhook(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
-) -> impl IntoResponse {
+) -> Response {
     let rate_key =
         client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_webhook(&rate_key) {
@@ -1639,7 +1860,7 @@ async fn handle_webhook(
             "error": "Too many webhook requests. Please retry later.",
             "retry_after": RATE_LIMIT_WINDOW_SECS,
         });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
     }
 
     // ── Bearer token auth (pairing) with auth rate limiting ──
@@ -1650,7 +1871,7 @@ async fn handle_webhook(
                 "error": format!("Too many auth attempts. Try again in {}s.", e.retry_after_secs),
                 "retry_after": e.retry_after_secs,
             });
-            return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+            return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
         }
         let auth = headers
             .get(header::AUTHORIZATION)
@@ -1663,7 +1884,7 @@ async fn handle_webhook(
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
             });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+            return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
         }
     }
 
@@ -1680,7 +1901,7 @@ async fn handle_webhook(
             _ => {
                 tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
                 let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
-                return (StatusCode::UNAUTHORIZED, Json(err));
+                return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
             }
         }
     }
@@ -1693,7 +1914,7 @@ async fn handle_webhook(
             let err = serde_json::json!({
                 "error": "Invalid JSON body. Expected: {\"message\": \"...\"}"
             });
-            return (StatusCode::BAD_REQUEST, Json(err));
+            return (StatusCode::BAD_REQUEST, Json(err)).into_response();
         }
     };
 
@@ -1706,12 +1927,12 @@ async fn handle_webhook(
         && !state.idempotency_store.record_if_new(idempotency_key)
     {
         tracing::info!("Webhook duplicate ignored (idempotency key: {idempotency_key})");
-        let body = serde_json::json!({
-            "status": "duplicate",
-            "idempotent": true,
-            "message": "Request already processed for this idempotency key"
-        });
-        return (StatusCode::OK, Json(body));
+        let body = WebhookDuplicate {
+            status: "duplicate".into(),
+            idempotent: true,
+            message: "Request already processed for this idempotency key".into(),
+        };
+        return Json(body).into_response();
     }
 
     let message = &webhook_body.message;
@@ -1795,8 +2016,11 @@ async fn handle_webhook(
                 },
             );
 
-            let body = serde_json::json!({"response": response, "model": state.model});
-            (StatusCode::OK, Json(body))
+            let body = WebhookResponse {
+                response,
+                model: state.model.clone(),
+            };
+            Json(body).into_response()
         }
         Err(e) => {
             let duration = started_at.elapsed();
@@ -1841,11 +2065,11 @@ async fn handle_webhook(
                     "error": "needs_onboarding",
                     "url": "/onboard"
                 });
-                (StatusCode::SERVICE_UNAVAILABLE, Json(body))
+                (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
             } else {
                 tracing::error!("Webhook provider error: {}", sanitized);
                 let err = serde_json::json!({"error": "LLM request failed"});
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(err)).into_response()
             }
         }
     }
@@ -1853,6 +2077,7 @@ async fn handle_webhook(
 
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct WhatsAppVerifyQuery {
     #[serde(rename = "hub.mode")]
     pub mode: Option<String>,
@@ -2010,9 +2235,12 @@ async fn handle_whatsapp_message(
         {
             Ok(GatewayChatOutcome { response, .. }) => {
                 // Send reply via WhatsApp
-                if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_channel_reply(
+                    &state,
+                    wa.as_ref(),
+                    SendMessage::new(response, &msg.reply_target),
+                )
+                .await
                 {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
                 }
@@ -2028,7 +2256,12 @@ async fn handle_whatsapp_message(
                     tracing::error!("LLM error for WhatsApp message: {e:#}");
                     "Sorry, I couldn't process your message right now.".to_string()
                 };
-                let _ = wa.send(&SendMessage::new(reply, &msg.reply_target)).await;
+                let _ = send_channel_reply(
+                    &state,
+                    wa.as_ref(),
+                    SendMessage::new(reply, &msg.reply_target),
+                )
+                .await;
             }
         }
     }
@@ -2134,9 +2367,12 @@ async fn handle_linq_webhook(
         {
             Ok(GatewayChatOutcome { response, .. }) => {
                 // Send reply via Linq
-                if let Err(e) = linq
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_channel_reply(
+                    &state,
+                    linq.as_ref(),
+                    SendMessage::new(response, &msg.reply_target),
+                )
+                .await
                 {
                     tracing::error!("Failed to send Linq reply: {e}");
                 }
@@ -2152,7 +2388,12 @@ async fn handle_linq_webhook(
                     tracing::error!("LLM error for Linq message: {e:#}");
                     "Sorry, I couldn't process your message right now.".to_string()
                 };
-                let _ = linq.send(&SendMessage::new(reply, &msg.reply_target)).await;
+                let _ = send_channel_reply(
+                    &state,
+                    linq.as_ref(),
+                    SendMessage::new(reply, &msg.reply_target),
+                )
+                .await;
             }
         }
     }
@@ -2180,6 +2421,7 @@ async fn handle_wati_verify(
 }
 
 #[derive(Debug, serde::Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct WatiVerifyQuery {
     #[serde(rename = "hub.challenge")]
     pub challenge: Option<String>,
@@ -2253,9 +2495,12 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         {
             Ok(GatewayChatOutcome { response, .. }) => {
                 // Send reply via WATI
-                if let Err(e) = wati
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_channel_reply(
+                    &state,
+                    wati.as_ref(),
+                    SendMessage::new(response, &msg.reply_target),
+                )
+                .await
                 {
                     tracing::error!("Failed to send WATI reply: {e}");
                 }
@@ -2271,7 +2516,12 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
                     tracing::error!("LLM error for WATI message: {e:#}");
                     "Sorry, I couldn't process your message right now.".to_string()
                 };
-                let _ = wati.send(&SendMessage::new(reply, &msg.reply_target)).await;
+                let _ = send_channel_reply(
+                    &state,
+                    wati.as_ref(),
+                    SendMessage::new(reply, &msg.reply_target),
+                )
+                .await;
             }
         }
     }
@@ -2372,9 +2622,12 @@ async fn handle_nextcloud_talk_webhook(
         .await
         {
             Ok(GatewayChatOutcome { response, .. }) => {
-                if let Err(e) = nextcloud_talk
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_channel_reply(
+                    &state,
+                    nextcloud_talk.as_ref(),
+                    SendMessage::new(response, &msg.reply_target),
+                )
+                .await
                 {
                     tracing::error!("Failed to send Nextcloud Talk reply: {e}");
                 }
@@ -2390,9 +2643,12 @@ async fn handle_nextcloud_talk_webhook(
                     tracing::error!("LLM error for Nextcloud Talk message: {e:#}");
                     "Sorry, I couldn't process your message right now.".to_string()
                 };
-                let _ = nextcloud_talk
-                    .send(&SendMessage::new(reply, &msg.reply_target))
-                    .await;
+                let _ = send_channel_reply(
+                    &state,
+                    nextcloud_talk.as_ref(),
+                    SendMessage::new(reply, &msg.reply_target),
+                )
+                .await;
             }
         }
     }
@@ -2400,6 +2656,17 @@ async fn handle_nextcloud_talk_webhook(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// Send a webhook channel reply through the durable outbox, so a transient
+/// platform failure is retried rather than dropped.
+async fn send_channel_reply(
+    state: &AppState,
+    channel: &dyn Channel,
+    message: SendMessage,
+) -> anyhow::Result<()> {
+    let config = state.config.lock().clone();
+    zeroclaw_runtime::outbox::dispatcher::send(&config, channel, &message).await
+}
+
 /// Maximum request body size for the Gmail webhook endpoint (1 MB).
 /// Google Pub/Sub messages are typically under 10 KB.
 const GMAIL_WEBHOOK_MAX_BODY: usize = 1024 * 1024;
@@ -2473,10 +2740,23 @@ async fn handle_gmail_push_webhook(
 // ══════════════════════════════════════════════════════════════════════════════
 
 /// Response for admin endpoints
-#[derive(serde::Serialize)]
-struct AdminResponse {
-    success: bool,
-    message: String,
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct AdminResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Pairing state returned by `/admin/paircode`, `/admin/paircode/new` and
+/// `/pair/code`; the public `/pair/code` omits `message`.
+#[derive(Debug, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct PairCodeResponse {
+    pub success: bool,
+    pub pairing_required: bool,
+    pub pairing_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 /// Reject requests that do not originate from a loopback address.
@@ -2582,25 +2862,18 @@ async fn handle_admin_paircode(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     require_localhost(&peer)?;
     let code = state.pairing.pairing_code();
-
-    let body = if let Some(c) = code {
-        serde_json::json!({
-            "success": true,
-            "pairing_required": state.pairing.require_pairing(),
-            "pairing_code": c,
-            "message": "Use this one-time code to pair"
-        })
+    let message = if code.is_some() {
+        "Use this one-time code to pair"
+    } else if state.pairing.require_pairing() {
+        "Pairing is active but no new code available (already paired or code expired)"
     } else {
-        serde_json::json!({
-            "success": true,
-            "pairing_required": state.pairing.require_pairing(),
-            "pairing_code": null,
-            "message": if state.pairing.require_pairing() {
-                "Pairing is active but no new code available (already paired or code expired)"
-            } else {
-                "Pairing is disabled for this gateway"
-            }
-        })
+        "Pairing is disabled for this gateway"
+    };
+    let body = PairCodeResponse {
+        success: true,
+        pairing_required: state.pairing.require_pairing(),
+        pairing_code: code,
+        message: Some(message.into()),
     };
 
     Ok((StatusCode::OK, Json(body)))
@@ -2615,21 +2888,21 @@ async fn handle_admin_paircode_new(
     match state.pairing.generate_new_pairing_code() {
         Some(code) => {
             tracing::info!("🔐 New pairing code generated via admin endpoint");
-            let body = serde_json::json!({
-                "success": true,
-                "pairing_required": state.pairing.require_pairing(),
-                "pairing_code": code,
-                "message": "New pairing code generated — use this one-time code to pair"
-            });
+            let body = PairCodeResponse {
+                success: true,
+                pairing_required: state.pairing.require_pairing(),
+                pairing_code: Some(code),
+                message: Some("New pairing code generated — use this one-time code to pair".into()),
+            };
             Ok((StatusCode::OK, Json(body)))
         }
         None => {
-            let body = serde_json::json!({
-                "success": false,
-                "pairing_required": false,
-                "pairing_code": null,
-                "message": "Pairing is disabled for this gateway"
-            });
+            let body = PairCodeResponse {
+                success: false,
+                pairing_required: false,
+                pairing_code: None,
+                message: Some("Pairing is disabled for this gateway".into()),
+            };
             Ok((StatusCode::BAD_REQUEST, Json(body)))
         }
     }
@@ -2653,11 +2926,12 @@ async fn handle_pair_code(State(state): State<AppState>) -> impl IntoResponse {
         None
     };
 
-    let body = serde_json::json!({
-        "success": true,
-        "pairing_required": require,
-        "pairing_code": code,
-    });
+    let body = PairCodeResponse {
+        success: true,
+        pairing_required: require,
+        pairing_code: code,
+        message: None,
+    };
 
     (StatusCode::OK, Json(body))
 }
@@ -2672,6 +2946,7 @@ mod tests {
     use parking_lot::Mutex;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use zeroclaw_api::channel::ChannelMessage;
+    use zeroclaw_config::schema::{GatewayRole, GatewayUserConfig};
     use zeroclaw_memory::{Memory, MemoryCategory, MemoryEntry};
     use zeroclaw_providers::Provider;
 
@@ -2780,6 +3055,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -2803,11 +3083,9 @@ mod tests {
     #[tokio::test]
     async fn metrics_endpoint_renders_prometheus_output() {
         let event_tx = tokio::sync::broadcast::channel(16).0;
-        let event_buffer = Arc::new(sse::EventBuffer::new(16));
         let wrapped = sse::BroadcastObserver::new(
             Box::new(zeroclaw_runtime::observability::PrometheusObserver::new()),
             event_tx.clone(),
-            event_buffer,
         );
         zeroclaw_runtime::observability::Observer::record_event(
             &wrapped,
@@ -2854,6 +3132,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -2924,6 +3207,14 @@ mod tests {
         assert!(store.record_if_new("req-2"));
     }
 
+    #[test]
+    fn idempotency_store_forgets_failed_keys() {
+        let store = IdempotencyStore::new(Duration::from_secs(30), 10);
+        assert!(store.record_if_new("req-1"));
+        store.forget("req-1");
+        assert!(store.record_if_new("req-1"));
+    }
+
     #[test]
     fn rate_limiter_bounded_cardinality_evicts_oldest_key() {
         let limiter = SlidingWindowRateLimiter::new(5, Duration::from_secs(60), 2);
@@ -3020,7 +3311,7 @@ mod tests {
         assert!(guard.is_authenticated(&token));
 
         let shared_config = Arc::new(Mutex::new(config));
-        Box::pin(persist_pairing_tokens(shared_config.clone(), &guard))
+        Box::pin(persist_pairing_tokens(shared_config.clone(), &guard, None))
             .await
             .unwrap();
 
@@ -3044,6 +3335,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn persist_pairing_tokens_binds_live_config_when_save_fails() {
+        let temp = tempfile::tempdir().unwrap();
+        let blocker = temp.path().join("not-a-dir");
+        std::fs::write(&blocker, "").unwrap();
+
+        let mut config = Config {
+            config_path: blocker.join("config.toml"),
+            workspace_dir: temp.path().join("workspace"),
+            ..Default::default()
+        };
+        config.gateway.users.push(GatewayUserConfig {
+            name: "alice".into(),
+            role: GatewayRole::Operator,
+            tokens: Vec::new(),
+        });
+
+        let guard = PairingGuard::new(true, &[]);
+        let code = guard.pairing_code().unwrap();
+        let token = guard.try_pair(&code, "test_client").await.unwrap().unwrap();
+
+        let shared_config = Arc::new(Mutex::new(config));
+        let result = Box::pin(persist_pairing_tokens(
+            shared_config.clone(),
+            &guard,
+            Some(("alice", &token)),
+        ))
+        .await;
+        assert!(result.is_err());
+
+        let live = shared_config.lock();
+        assert_eq!(
+            live.gateway.users[0].tokens,
+            vec![PairingGuard::token_hash(&token)]
+        );
+        let principal = rbac::resolve(&live.gateway, &guard, &token).unwrap();
+        assert_eq!(principal.user.as_deref(), Some("alice"));
+    }
+
     #[test]
     fn webhook_memory_key_is_unique() {
         let key1 = webhook_memory_key();
@@ -3313,6 +3643,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3395,6 +3730,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3489,6 +3829,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3555,6 +3900,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3626,6 +3976,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3702,6 +4057,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
@@ -3775,6 +4135,11 @@ mod tests {
             pending_pairings: None,
             canvas_store: CanvasStore::new(),
             cancel_tokens: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            audit_logger: None,
+            sops: None,
+            routines: Arc::new(Mutex::new(
+                zeroclaw_runtime::routines::RoutinesEngine::empty(),
+            )),
             #[cfg(feature = "webauthn")]
             webauthn: None,
         };
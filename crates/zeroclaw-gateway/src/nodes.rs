@@ -183,6 +183,7 @@ enum GatewayMessage {
 
 /// Query parameters for the `/ws/nodes` endpoint.
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct NodeWsQuery {
     pub token: Option<String>,
 }
@@ -0,0 +1,1184 @@
+//! OpenAI-compatible `/v1/chat/completions` and `/v1/models` endpoints.
+//!
+//! Lets OpenAI SDK clients, IDE plugins and chat UIs talk to the gateway.
+//! Requests authenticate with the same paired bearer tokens as `/api/*`.
+//!
+//! In `agent` mode (the default) each request runs a full agent turn with
+//! tools, memory and the security policy. Tools the agent runs have already
+//! executed, so they are reported in the vendor field `zeroclaw_tool_calls`
+//! rather than `tool_calls`, and `finish_reason` stays `stop`. Approval-gated
+//! tools are denied because HTTP has no approval back-channel. Only models
+//! listed in `passthrough_models` bypass the agent. In `passthrough` mode the
+//! conversation and any client-supplied `tools` go straight to the
+//! configured provider.
+//!
+//! Send `X-Session-Id` to bind a request to a persisted gateway session. The
+//! session shares its key with `/ws/chat`, scoped per user like every
+//! gateway session. Stored history is replayed and only the final user
+//! message of the request is used. Without the header the request's own
+//! `messages` seed a one-off agent. Either way, client `system` and
+//! `developer` messages are appended to the agent's system prompt for that
+//! request only and never stored.
+
+use super::AppState;
+use super::rbac::{self, Principal};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::StreamExt as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::convert::Infallible;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use zeroclaw_api::agent::TurnEvent;
+use zeroclaw_api::provider::{ChatMessage, ChatRequest, StreamEvent, StreamOptions};
+use zeroclaw_api::tool::ToolSpec;
+use zeroclaw_config::schema::OpenAiCompatMode;
+
+/// Header that binds a request to a persisted gateway session.
+pub const SESSION_HEADER: &str = "x-session-id";
+
+// ── Request types ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ChatCompletionRequest {
+    #[serde(default)]
+    pub model: String,
+    pub messages: Vec<RequestMessage>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub tools: Vec<RequestTool>,
+    #[serde(default)]
+    pub stream_options: Option<RequestStreamOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestStreamOptions {
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestMessage {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+    #[serde(default)]
+    pub tool_calls: Vec<RequestToolCall>,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+}
+
+/// Message content is either a plain string or a list of typed parts.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ContentPart {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+impl MessageContent {
+    /// Concatenated text parts; non-text parts are dropped.
+    fn text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Parts(parts) => parts
+                .iter()
+                .filter(|p| p.kind == "text")
+                .filter_map(|p| p.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestToolCall {
+    pub id: String,
+    pub function: RequestFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestFunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestTool {
+    pub function: RequestFunction,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RequestFunction {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<Value>,
+}
+
+// ── Conversions ─────────────────────────────────────────────────
+
+/// Convert OpenAI messages into provider history.
+///
+/// Assistant tool calls and tool results use the JSON-in-content encoding
+/// the agent loop writes to history, which providers unpack natively.
+fn to_chat_messages(messages: &[RequestMessage]) -> Vec<ChatMessage> {
+    messages
+        .iter()
+        .map(|m| {
+            let text = m.content.as_ref().map(MessageContent::text);
+            match m.role.as_str() {
+                "assistant" if !m.tool_calls.is_empty() => {
+                    let calls: Vec<Value> = m
+                        .tool_calls
+                        .iter()
+                        .map(|c| {
+                            json!({
+                                "id": c.id,
+                                "name": c.function.name,
+                                "arguments": c.function.arguments,
+                            })
+                        })
+                        .collect();
+                    ChatMessage::assistant(
+                        json!({ "content": text, "tool_calls": calls }).to_string(),
+                    )
+                }
+                "tool" => ChatMessage::tool(
+                    json!({
+                        "tool_call_id": m.tool_call_id,
+                        "content": text.unwrap_or_default(),
+                    })
+                    .to_string(),
+                ),
+                // `developer` is the newer spelling of `system`.
+                "developer" => ChatMessage::system(text.unwrap_or_default()),
+                role => ChatMessage {
+                    role: role.to_string(),
+                    content: text.unwrap_or_default(),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Client `system` and `developer` messages, joined into one block.
+fn client_instructions(messages: &[RequestMessage]) -> Option<String> {
+    let parts: Vec<String> = messages
+        .iter()
+        .filter(|m| matches!(m.role.as_str(), "system" | "developer"))
+        .filter_map(|m| m.content.as_ref().map(MessageContent::text))
+        .filter(|text| !text.trim().is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
+fn to_tool_specs(tools: &[RequestTool]) -> Vec<ToolSpec> {
+    tools
+        .iter()
+        .map(|t| ToolSpec {
+            name: t.function.name.clone(),
+            description: t.function.description.clone().unwrap_or_default(),
+            parameters: t
+                .function
+                .parameters
+                .clone()
+                .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+        })
+        .collect()
+}
+
+// ── Responses ───────────────────────────────────────────────────
+
+/// OpenAI `model` object.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ModelObject {
+    pub id: String,
+    /// Always `model`.
+    pub object: String,
+    pub created: i64,
+    pub owned_by: String,
+}
+
+/// OpenAI `list` object returned by `GET /v1/models`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ModelList {
+    /// Always `list`.
+    pub object: String,
+    pub data: Vec<ModelObject>,
+}
+
+/// OpenAI `tool_calls` entry.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ToolCall {
+    pub id: String,
+    /// Always `function`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct FunctionCall {
+    pub name: String,
+    /// JSON-encoded arguments.
+    pub arguments: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct Usage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct AssistantMessage {
+    /// Always `assistant`.
+    pub role: String,
+    pub content: String,
+    /// Provider tool calls for the client to execute (passthrough only).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// Tools the agent already ran while producing `content`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub zeroclaw_tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CompletionChoice {
+    pub index: u32,
+    pub message: AssistantMessage,
+    /// `stop` or `tool_calls`.
+    pub finish_reason: String,
+}
+
+/// OpenAI `chat.completion` object (non-streaming responses).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct ChatCompletion {
+    pub id: String,
+    /// Always `chat.completion`.
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<CompletionChoice>,
+    pub usage: Option<Usage>,
+}
+
+fn tool_call(id: &str, name: &str, arguments: &str) -> ToolCall {
+    ToolCall {
+        id: id.to_string(),
+        kind: "function".into(),
+        function: FunctionCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+fn usage(input: Option<u64>, output: Option<u64>) -> Option<Usage> {
+    if input.is_none() && output.is_none() {
+        return None;
+    }
+    let (input, output) = (input.unwrap_or(0), output.unwrap_or(0));
+    Some(Usage {
+        prompt_tokens: input,
+        completion_tokens: output,
+        total_tokens: input.saturating_add(output),
+    })
+}
+
+/// OpenAI-shaped error body.
+fn error_response(status: StatusCode, kind: &str, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": { "message": message.into(), "type": kind, "code": Value::Null }
+        })),
+    )
+        .into_response()
+}
+
+/// OpenAI-shaped 401 when the bearer token is not a paired token.
+fn unauthorized(state: &AppState, headers: &HeaderMap) -> Option<Response> {
+    super::api::require_auth(state, headers).err().map(|_| {
+        error_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid_request_error",
+            "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+        )
+    })
+}
+
+fn session_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+/// Whether a request for `model` runs through the agent.
+fn routes_to_agent(config: &zeroclaw_config::schema::OpenAiCompatConfig, model: &str) -> bool {
+    match config.mode {
+        OpenAiCompatMode::Passthrough => false,
+        OpenAiCompatMode::Agent => {
+            model == config.agent_model || !config.passthrough_models.iter().any(|m| m == model)
+        }
+    }
+}
+
+// ── Completion accumulator ──────────────────────────────────────
+
+/// Builds `chat.completion` and `chat.completion.chunk` payloads for one
+/// response.
+struct Completion {
+    id: String,
+    created: i64,
+    model: String,
+    tool_calls: Vec<ToolCall>,
+    executed_tool_calls: Vec<ToolCall>,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+}
+
+impl Completion {
+    fn new(model: &str) -> Self {
+        Self {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4().simple()),
+            created: chrono::Utc::now().timestamp(),
+            model: model.to_string(),
+            tool_calls: Vec::new(),
+            executed_tool_calls: Vec::new(),
+            input_tokens: None,
+            output_tokens: None,
+        }
+    }
+
+    fn add_usage(&mut self, input: Option<u64>, output: Option<u64>) {
+        if let Some(i) = input {
+            self.input_tokens = Some(self.input_tokens.unwrap_or(0) + i);
+        }
+        if let Some(o) = output {
+            self.output_tokens = Some(self.output_tokens.unwrap_or(0) + o);
+        }
+    }
+
+    /// Record a tool call for the client to run and return its streaming delta.
+    fn push_tool_call(&mut self, id: &str, name: &str, arguments: &str) -> Value {
+        let index = self.tool_calls.len();
+        let call = tool_call(id, name, arguments);
+        let mut delta = json!(call);
+        delta["index"] = json!(index);
+        self.tool_calls.push(call);
+        self.chunk(json!({ "tool_calls": [delta] }), None)
+    }
+
+    /// Record a tool the agent already ran and return its streaming delta.
+    fn push_executed_tool_call(&mut self, id: &str, name: &str, arguments: &str) -> Value {
+        let index = self.executed_tool_calls.len();
+        let call = tool_call(id, name, arguments);
+        let mut delta = json!(call);
+        delta["index"] = json!(index);
+        self.executed_tool_calls.push(call);
+        self.chunk(json!({ "zeroclaw_tool_calls": [delta] }), None)
+    }
+
+    fn chunk(&self, delta: Value, finish_reason: Option<&str>) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+        })
+    }
+
+    /// Trailing chunk with empty `choices`, sent when `include_usage` is set.
+    fn usage_chunk(&self) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [],
+            "usage": usage(self.input_tokens, self.output_tokens),
+        })
+    }
+
+    fn response(&self, content: &str, finish_reason: &str) -> ChatCompletion {
+        ChatCompletion {
+            id: self.id.clone(),
+            object: "chat.completion".into(),
+            created: self.created,
+            model: self.model.clone(),
+            choices: vec![CompletionChoice {
+                index: 0,
+                message: AssistantMessage {
+                    role: "assistant".into(),
+                    content: content.to_string(),
+                    tool_calls: self.tool_calls.clone(),
+                    zeroclaw_tool_calls: self.executed_tool_calls.clone(),
+                },
+                finish_reason: finish_reason.to_string(),
+            }],
+            usage: usage(self.input_tokens, self.output_tokens),
+        }
+    }
+}
+
+fn sse_data(value: &Value) -> Result<Event, Infallible> {
+    Ok(Event::default().data(value.to_string()))
+}
+
+fn sse_response(rx: mpsc::Receiver<Result<Event, Infallible>>) -> Response {
+    Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+// ── Handlers ────────────────────────────────────────────────────
+
+/// GET /v1/models — models clients may name in a completion request.
+pub async fn handle_models(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = unauthorized(&state, &headers) {
+        return rejection;
+    }
+    let compat = state.config.lock().gateway.openai_compat.clone();
+    if !compat.enabled {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            "invalid_request_error",
+            "OpenAI-compatible API is disabled",
+        );
+    }
+
+    let mut ids = Vec::new();
+    match compat.mode {
+        OpenAiCompatMode::Agent => {
+            ids.push(compat.agent_model.clone());
+            ids.extend(
+                compat
+                    .passthrough_models
+                    .iter()
+                    .filter(|m| **m != compat.agent_model)
+                    .cloned(),
+            );
+        }
+        OpenAiCompatMode::Passthrough if !state.model.is_empty() => {
+            ids.push(state.model.clone());
+        }
+        OpenAiCompatMode::Passthrough => {}
+    }
+    let data = ids
+        .into_iter()
+        .map(|id| ModelObject {
+            id,
+            object: "model".into(),
+            created: 0,
+            owned_by: "zeroclaw".into(),
+        })
+        .collect();
+    Json(ModelList {
+        object: "list".into(),
+        data,
+    })
+    .into_response()
+}
+
+/// POST /v1/chat/completions — streaming and non-streaming chat completions.
+pub async fn handle_chat_completions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Response {
+    if let Some(rejection) = unauthorized(&state, &headers) {
+        return rejection;
+    }
+    let compat = state.config.lock().gateway.openai_compat.clone();
+    if !compat.enabled {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            "invalid_request_error",
+            "OpenAI-compatible API is disabled",
+        );
+    }
+    if request.messages.is_empty() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            "messages must not be empty",
+        );
+    }
+    if let Some(err) = super::needs_onboarding_for(&state.model) {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "needs_onboarding",
+            err.to_string(),
+        );
+    }
+
+    if routes_to_agent(&compat, &request.model) {
+        let model = if request.model.is_empty() {
+            compat.agent_model
+        } else {
+            request.model.clone()
+        };
+        let principal = principal.map(|Extension(p)| p);
+        agent_completion(state, request, &model, principal, session_id(&headers)).await
+    } else {
+        passthrough_completion(state, request).await
+    }
+}
+
+// ── Agent mode ──────────────────────────────────────────────────
+
+/// Persisted session a request is bound to via [`SESSION_HEADER`].
+struct BoundSession {
+    key: String,
+    _guard: super::session_queue::SessionGuard,
+}
+
+async fn agent_completion(
+    state: AppState,
+    request: ChatCompletionRequest,
+    model: &str,
+    principal: Option<Principal>,
+    session_id: Option<String>,
+) -> Response {
+    let (last, earlier) = request
+        .messages
+        .split_last()
+        .expect("messages checked non-empty");
+    let content = last
+        .content
+        .as_ref()
+        .map(MessageContent::text)
+        .unwrap_or_default();
+    if last.role != "user" || content.trim().is_empty() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            "the last message must be a non-empty user message",
+        );
+    }
+
+    let config = state.config.lock().clone();
+    let session_cwd = match super::ws::resolve_session_cwd(None, &config.workspace_dir) {
+        Ok(cwd) => cwd,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                e.to_string(),
+            );
+        }
+    };
+    let mut agent =
+        match zeroclaw_runtime::agent::Agent::from_config_with_session_cwd_and_mcp_backchannel(
+            &config,
+            Some(&session_cwd),
+            true,
+        )
+        .await
+        {
+            Ok(agent) => agent,
+            Err(e) => {
+                tracing::error!(error = %e, "Agent initialization failed");
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "server_error",
+                    format!("Failed to initialise agent: {e}"),
+                );
+            }
+        };
+
+    let session = match session_id {
+        Some(id) => {
+            let key = rbac::session_key(principal.as_ref(), &id);
+            let guard = match state.session_queue.acquire(&key).await {
+                Ok(guard) => guard,
+                Err(e) => {
+                    return error_response(
+                        StatusCode::TOO_MANY_REQUESTS,
+                        "session_busy",
+                        e.to_string(),
+                    );
+                }
+            };
+            agent.set_memory_session_id(Some(rbac::memory_session_id(principal.as_ref(), id)));
+            if let Some(ref backend) = state.session_backend {
+                let stored = backend.load(&key);
+                if !stored.is_empty() {
+                    agent.seed_history(&stored);
+                }
+                let _ = backend.append(&key, &ChatMessage::user(&content));
+            }
+            Some(BoundSession { key, _guard: guard })
+        }
+        None => {
+            if let Some(scope) = rbac::memory_scope(principal.as_ref()) {
+                agent.set_memory_session_id(Some(scope));
+            }
+            // `seed_history` drops system entries; client instructions are
+            // applied below instead.
+            agent.seed_history(&to_chat_messages(earlier));
+            None
+        }
+    };
+    if let Some(instructions) = client_instructions(&request.messages) {
+        agent.append_system_instructions(&format!(
+            "## Client instructions (this request only)\n\n{instructions}"
+        ));
+    }
+    let served_model = agent.served_model(&content);
+
+    let cancel_token = tokio_util::sync::CancellationToken::new();
+    if let Some(ref session) = session {
+        state
+            .cancel_tokens
+            .lock()
+            .expect("cancel_tokens lock poisoned")
+            .insert(session.key.clone(), cancel_token.clone());
+        if let Some(ref backend) = state.session_backend {
+            let _ = backend.set_session_state(&session.key, "running", None);
+        }
+    }
+
+    let (event_tx, mut event_rx) = mpsc::channel::<TurnEvent>(64);
+    let turn_cancel = cancel_token.clone();
+    let scope_key = session.as_ref().map(|s| s.key.clone());
+    let turn_content = content.clone();
+    let turn = tokio::spawn(async move {
+        zeroclaw_runtime::agent::loop_::scope_session_key(
+            scope_key,
+            agent.turn_streamed(&turn_content, event_tx, Some(turn_cancel)),
+        )
+        .await
+    });
+
+    let mut completion = Completion::new(model);
+
+    if !request.stream {
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                TurnEvent::ToolCall { id, name, args } => {
+                    completion.push_executed_tool_call(&id, &name, &args.to_string());
+                }
+                TurnEvent::Usage {
+                    input_tokens,
+                    output_tokens,
+                    ..
+                } => completion.add_usage(input_tokens, output_tokens),
+                _ => {}
+            }
+        }
+        let result = join_turn(turn).await;
+        return match finish_agent_turn(
+            &state,
+            session,
+            rbac::memory_scope(principal.as_ref()),
+            &content,
+            &served_model,
+            &completion,
+            result,
+        ) {
+            Ok(text) => Json(completion.response(&text, "stop")).into_response(),
+            Err(message) => error_response(StatusCode::BAD_GATEWAY, "server_error", message),
+        };
+    }
+
+    let include_usage = request
+        .stream_options
+        .as_ref()
+        .is_some_and(|o| o.include_usage);
+    let (out_tx, out_rx) = mpsc::channel::<Result<Event, Infallible>>(64);
+    tokio::spawn(async move {
+        let mut client_gone = out_tx
+            .send(sse_data(
+                &completion.chunk(json!({ "role": "assistant", "content": "" }), None),
+            ))
+            .await
+            .is_err();
+        while let Some(event) = event_rx.recv().await {
+            let frame = match event {
+                TurnEvent::Chunk { delta } => completion.chunk(json!({ "content": delta }), None),
+                TurnEvent::Thinking { delta } => {
+                    completion.chunk(json!({ "reasoning_content": delta }), None)
+                }
+                TurnEvent::ToolCall { id, name, args } => {
+                    completion.push_executed_tool_call(&id, &name, &args.to_string())
+                }
+                TurnEvent::Usage {
+                    input_tokens,
+                    output_tokens,
+                    ..
+                } => {
+                    completion.add_usage(input_tokens, output_tokens);
+                    continue;
+                }
+                TurnEvent::ToolResult { .. } | TurnEvent::ApprovalRequest { .. } => continue,
+            };
+            if !client_gone && out_tx.send(sse_data(&frame)).await.is_err() {
+                // Stop the agent once nobody is reading, but keep draining
+                // so the turn can unwind and release the session.
+                client_gone = true;
+                cancel_token.cancel();
+            }
+        }
+        let result = join_turn(turn).await;
+        let outcome = finish_agent_turn(
+            &state,
+            session,
+            rbac::memory_scope(principal.as_ref()),
+            &content,
+            &served_model,
+            &completion,
+            result,
+        );
+        if client_gone {
+            return;
+        }
+        let tail = match outcome {
+            Ok(_) => {
+                let mut tail = vec![completion.chunk(json!({}), Some("stop"))];
+                if include_usage {
+                    tail.push(completion.usage_chunk());
+                }
+                tail
+            }
+            Err(message) => vec![json!({
+                "error": { "message": message, "type": "server_error", "code": Value::Null }
+            })],
+        };
+        for frame in tail {
+            let _ = out_tx.send(sse_data(&frame)).await;
+        }
+        let _ = out_tx.send(Ok(Event::default().data("[DONE]"))).await;
+    });
+    sse_response(out_rx)
+}
+
+async fn join_turn(
+    turn: tokio::task::JoinHandle<anyhow::Result<String>>,
+) -> anyhow::Result<String> {
+    turn.await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("agent turn panicked: {e}")))
+}
+
+/// Provider name cost records are filed under.
+fn provider_label(state: &AppState) -> String {
+    state
+        .config
+        .lock()
+        .providers
+        .fallback
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Persist the turn, record cost against `served_model` and return the reply
+/// or a sanitized error.
+fn finish_agent_turn(
+    state: &AppState,
+    session: Option<BoundSession>,
+    memory_scope: Option<String>,
+    content: &str,
+    served_model: &str,
+    completion: &Completion,
+    result: anyhow::Result<String>,
+) -> Result<String, String> {
+    if let Some(ref session) = session {
+        state
+            .cancel_tokens
+            .lock()
+            .expect("cancel_tokens lock poisoned")
+            .remove(&session.key);
+    }
+
+    match result {
+        Ok(response) => {
+            if let Some(ref session) = session
+                && let Some(ref backend) = state.session_backend
+            {
+                let _ = backend.append(&session.key, &ChatMessage::assistant(&response));
+                let _ = backend.set_session_state(&session.key, "idle", None);
+            }
+            if state.auto_save {
+                let mem = state.mem.clone();
+                let provider = state.provider.clone();
+                let model = state.model.clone();
+                let user_msg = content.to_string();
+                let assistant_resp = response.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = zeroclaw_memory::consolidation::consolidate_turn(
+                        provider.as_ref(),
+                        &model,
+                        mem.as_ref(),
+                        memory_scope.as_deref(),
+                        &user_msg,
+                        &assistant_resp,
+                    )
+                    .await
+                    {
+                        tracing::debug!("OpenAI-compat memory consolidation skipped: {e}");
+                    }
+                });
+            }
+            super::ws::record_turn_cost(
+                state,
+                &provider_label(state),
+                served_model,
+                completion.input_tokens,
+                completion.output_tokens,
+            );
+            Ok(response)
+        }
+        Err(e) => {
+            if let Some(ref session) = session
+                && let Some(ref backend) = state.session_backend
+            {
+                let _ = backend.set_session_state(&session.key, "error", None);
+            }
+            tracing::error!(error = %e, "OpenAI-compat agent turn failed");
+            Err(zeroclaw_providers::sanitize_api_error(&e.to_string()))
+        }
+    }
+}
+
+// ── Passthrough mode ────────────────────────────────────────────
+
+async fn passthrough_completion(state: AppState, request: ChatCompletionRequest) -> Response {
+    let model = if request.model.is_empty() {
+        state.model.clone()
+    } else {
+        request.model.clone()
+    };
+    let messages = to_chat_messages(&request.messages);
+    let tools = to_tool_specs(&request.tools);
+    let chat_request = ChatRequest {
+        messages: &messages,
+        tools: (!tools.is_empty()).then_some(tools.as_slice()),
+    };
+    let mut completion = Completion::new(&model);
+
+    if !request.stream || !state.provider.supports_streaming() {
+        let response = match state
+            .provider
+            .chat(chat_request, &model, request.temperature)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_GATEWAY,
+                    "server_error",
+                    zeroclaw_providers::sanitize_api_error(&e.to_string()),
+                );
+            }
+        };
+        if let Some(usage) = &response.usage {
+            completion.add_usage(usage.input_tokens, usage.output_tokens);
+        }
+        record_passthrough_cost(&state, &model, &completion);
+        for call in &response.tool_calls {
+            completion.push_tool_call(&call.id, &call.name, &call.arguments);
+        }
+        let finish_reason = if response.has_tool_calls() {
+            "tool_calls"
+        } else {
+            "stop"
+        };
+        let text = response.text_or_empty().to_string();
+        if !request.stream {
+            return Json(completion.response(&text, finish_reason)).into_response();
+        }
+
+        // The provider cannot stream: replay the whole reply as one chunk.
+        let include_usage = request
+            .stream_options
+            .as_ref()
+            .is_some_and(|o| o.include_usage);
+        let mut frames = vec![completion.chunk(
+            json!({ "role": "assistant", "content": text, "tool_calls": tool_call_deltas(&completion) }),
+            None,
+        )];
+        frames.push(completion.chunk(json!({}), Some(finish_reason)));
+        if include_usage {
+            frames.push(completion.usage_chunk());
+        }
+        let (out_tx, out_rx) = mpsc::channel(frames.len() + 1);
+        for frame in &frames {
+            let _ = out_tx.try_send(sse_data(frame));
+        }
+        let _ = out_tx.try_send(Ok(Event::default().data("[DONE]")));
+        return sse_response(out_rx);
+    }
+
+    let include_usage = request
+        .stream_options
+        .as_ref()
+        .is_some_and(|o| o.include_usage);
+    let mut stream = state.provider.stream_chat(
+        chat_request,
+        &model,
+        request.temperature,
+        StreamOptions::new(true),
+    );
+    let (out_tx, out_rx) = mpsc::channel::<Result<Event, Infallible>>(64);
+    tokio::spawn(async move {
+        if out_tx
+            .send(sse_data(
+                &completion.chunk(json!({ "role": "assistant", "content": "" }), None),
+            ))
+            .await
+            .is_err()
+        {
+            return;
+        }
+        while let Some(event) = stream.next().await {
+            let frame = match event {
+                Ok(StreamEvent::TextDelta(chunk)) => {
+                    let mut delta = json!({ "content": chunk.delta });
+                    if let Some(reasoning) = chunk.reasoning {
+                        delta["reasoning_content"] = json!(reasoning);
+                    }
+                    completion.chunk(delta, None)
+                }
+                Ok(StreamEvent::ToolCall(call)) => {
+                    completion.push_tool_call(&call.id, &call.name, &call.arguments)
+                }
+                Ok(StreamEvent::Usage(usage)) => {
+                    completion.add_usage(usage.input_tokens, usage.output_tokens);
+                    continue;
+                }
+                Ok(StreamEvent::Final) => break,
+                Ok(
+                    StreamEvent::PreExecutedToolCall { .. }
+                    | StreamEvent::PreExecutedToolResult { .. },
+                ) => continue,
+                Err(e) => json!({
+                    "error": {
+                        "message": zeroclaw_providers::sanitize_api_error(&e.to_string()),
+                        "type": "server_error",
+                        "code": Value::Null,
+                    }
+                }),
+            };
+            if out_tx.send(sse_data(&frame)).await.is_err() {
+                return;
+            }
+        }
+        record_passthrough_cost(&state, &model, &completion);
+        let finish_reason = if completion.tool_calls.is_empty() {
+            "stop"
+        } else {
+            "tool_calls"
+        };
+        let _ = out_tx
+            .send(sse_data(&completion.chunk(json!({}), Some(finish_reason))))
+            .await;
+        if include_usage {
+            let _ = out_tx.send(sse_data(&completion.usage_chunk())).await;
+        }
+        let _ = out_tx.send(Ok(Event::default().data("[DONE]"))).await;
+    });
+    sse_response(out_rx)
+}
+
+fn record_passthrough_cost(state: &AppState, model: &str, completion: &Completion) {
+    super::ws::record_turn_cost(
+        state,
+        &provider_label(state),
+        model,
+        completion.input_tokens,
+        completion.output_tokens,
+    );
+}
+
+/// Indexed `tool_calls` deltas for everything recorded so far.
+fn tool_call_deltas(completion: &Completion) -> Value {
+    let deltas: Vec<Value> = completion
+        .tool_calls
+        .iter()
+        .enumerate()
+        .map(|(index, call)| {
+            let mut delta = json!(call);
+            delta["index"] = json!(index);
+            delta
+        })
+        .collect();
+    json!(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroclaw_config::schema::OpenAiCompatConfig;
+
+    fn parse(body: Value) -> ChatCompletionRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn converts_tool_round_trip_to_history_encoding() {
+        let request = parse(json!({
+            "model": "zeroclaw",
+            "messages": [
+                { "role": "developer", "content": "be terse" },
+                { "role": "user", "content": [
+                    { "type": "text", "text": "weather?" },
+                    { "type": "image_url", "image_url": { "url": "x" } }
+                ]},
+                { "role": "assistant", "content": null, "tool_calls": [
+                    { "id": "call_1", "type": "function",
+                      "function": { "name": "weather", "arguments": "{\"city\":\"Oslo\"}" } }
+                ]},
+                { "role": "tool", "tool_call_id": "call_1", "content": "rain" }
+            ]
+        }));
+        let messages = to_chat_messages(&request.messages);
+
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[1].content, "weather?");
+        let assistant: Value = serde_json::from_str(&messages[2].content).unwrap();
+        assert_eq!(assistant["tool_calls"][0]["id"], "call_1");
+        assert_eq!(assistant["tool_calls"][0]["name"], "weather");
+        let tool: Value = serde_json::from_str(&messages[3].content).unwrap();
+        assert_eq!(tool["tool_call_id"], "call_1");
+        assert_eq!(tool["content"], "rain");
+    }
+
+    #[test]
+    fn client_instructions_collect_system_and_developer_messages() {
+        let request = parse(json!({
+            "messages": [
+                { "role": "system", "content": "be terse" },
+                { "role": "user", "content": "hi" },
+                { "role": "developer", "content": [{ "type": "text", "text": "use metric" }] },
+                { "role": "system", "content": "  " },
+                { "role": "user", "content": "weather?" }
+            ]
+        }));
+        assert_eq!(
+            client_instructions(&request.messages).as_deref(),
+            Some("be terse\n\nuse metric")
+        );
+        let request = parse(json!({ "messages": [{ "role": "user", "content": "hi" }] }));
+        assert_eq!(client_instructions(&request.messages), None);
+    }
+
+    #[test]
+    fn client_tools_become_tool_specs() {
+        let request = parse(json!({
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tools": [{ "type": "function", "function": { "name": "lookup" } }]
+        }));
+        let specs = to_tool_specs(&request.tools);
+        assert_eq!(specs[0].name, "lookup");
+        assert_eq!(specs[0].parameters["type"], "object");
+    }
+
+    #[test]
+    fn agent_mode_passes_through_only_allowlisted_models() {
+        let mut config = OpenAiCompatConfig::default();
+        assert!(!config.enabled);
+        assert!(routes_to_agent(&config, "gpt-4o"));
+
+        config.passthrough_models = vec!["gpt-4o".into(), "zeroclaw".into()];
+        assert!(routes_to_agent(&config, "zeroclaw"));
+        assert!(routes_to_agent(&config, ""));
+        assert!(routes_to_agent(&config, "o3-pro"));
+        assert!(!routes_to_agent(&config, "gpt-4o"));
+
+        config.mode = OpenAiCompatMode::Passthrough;
+        assert!(!routes_to_agent(&config, "zeroclaw"));
+    }
+
+    #[test]
+    fn completion_reports_tool_calls_and_usage_in_openai_shape() {
+        let mut completion = Completion::new("zeroclaw");
+        let chunk = completion.push_tool_call("t1", "shell", "{\"cmd\":\"ls\"}");
+        completion.add_usage(Some(10), Some(4));
+        completion.add_usage(Some(5), None);
+
+        let delta = &chunk["choices"][0]["delta"]["tool_calls"][0];
+        assert_eq!(delta["index"], 0);
+        assert_eq!(delta["function"]["name"], "shell");
+        assert_eq!(chunk["object"], "chat.completion.chunk");
+
+        let response = json!(completion.response("done", "stop"));
+        assert_eq!(response["object"], "chat.completion");
+        let message = &response["choices"][0]["message"];
+        assert_eq!(message["content"], "done");
+        assert_eq!(message["tool_calls"][0]["type"], "function");
+        assert!(message.get("zeroclaw_tool_calls").is_none());
+        assert_eq!(response["usage"]["prompt_tokens"], 15);
+        assert_eq!(response["usage"]["total_tokens"], 19);
+    }
+
+    #[test]
+    fn executed_agent_tools_use_the_vendor_field() {
+        let mut completion = Completion::new("zeroclaw");
+        let chunk = completion.push_executed_tool_call("t1", "shell", "{\"cmd\":\"ls\"}");
+        let delta = &chunk["choices"][0]["delta"];
+        assert!(delta.get("tool_calls").is_none());
+        assert_eq!(delta["zeroclaw_tool_calls"][0]["function"]["name"], "shell");
+
+        let response = json!(completion.response("done", "stop"));
+        let message = &response["choices"][0]["message"];
+        assert!(message.get("tool_calls").is_none());
+        assert_eq!(message["zeroclaw_tool_calls"][0]["id"], "t1");
+        assert_eq!(response["choices"][0]["finish_reason"], "stop");
+    }
+
+    #[test]
+    fn session_header_is_trimmed_and_blank_ignored() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_id(&headers), None);
+        headers.insert(SESSION_HEADER, " abc ".parse().unwrap());
+        assert_eq!(session_id(&headers).as_deref(), Some("abc"));
+        headers.insert(SESSION_HEADER, "  ".parse().unwrap());
+        assert_eq!(session_id(&headers), None);
+    }
+}
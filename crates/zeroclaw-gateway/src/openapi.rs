@@ -1,12 +1,12 @@
-//! Runtime-generated OpenAPI 3.1 document for the new `/api/config/*` surface.
+//! Runtime-generated OpenAPI 3.1 document for every gateway route.
 //!
 //! Built from the same `schemars::JsonSchema` derives the request/response
 //! types carry. The generator does not introspect the axum router — instead it
-//! walks a hand-maintained `(method, path, request_type, response_type)` list
-//! local to this module. New endpoints under the same surface should be added
-//! to that list when they land. CI checks (forthcoming) can diff the rendered
-//! spec against a committed snapshot to fail builds when handlers are added
-//! without a corresponding OpenAPI entry.
+//! walks hand-maintained `(method, path, request_type, response_type)` entries
+//! local to this module: the `/api/config/*` surface below, and everything
+//! else in [`gateway_paths`]. `route_table_covers_every_registered_route`
+//! parses the `.route(...)` calls in `lib.rs` and fails when a route lands
+//! without an entry here.
 //!
 //! Cached behind a `OnceCell` because the spec is static per build.
 //!
@@ -40,9 +40,9 @@ pub async fn handle_docs() -> Response {
     response
 }
 
-/// `GET /api/openapi.json` — returns the OpenAPI 3.1 document for the gateway
-/// surface that is documented today (`/api/config/*`). Static per build;
-/// browsers and the eventual Scalar explorer consume this as their data source.
+/// `GET /api/openapi.json` — returns the OpenAPI 3.1 document for the whole
+/// gateway surface. Static per build; the Scalar explorer at `/api/docs` and
+/// the web client generator consume this as their data source.
 pub async fn handle_openapi_json() -> Response {
     let body = CACHED.get_or_init(build_spec).clone();
     let mut response = (StatusCode::OK, axum::Json(body)).into_response();
@@ -61,17 +61,29 @@ pub async fn handle_openapi_json() -> Response {
 /// update fails the build.
 #[cfg(feature = "schema-export")]
 pub fn build_spec() -> serde_json::Value {
+    use crate::WebhookBody;
+    use crate::api::{CronAddBody, CronPatchBody, MemoryStoreBody};
     use crate::api_config::{
         DriftEntry, DriftResponse, InitQuery, InitResponse, ListResponse, MigrateResponse, PatchOp,
         PatchResponse, PropPutBody, PropResponse, SecretResponse,
     };
+    use crate::api_config::{MapKeyResponse, TemplatesResponse};
+    use crate::api_onboard::{
+        CatalogResponse, ModelsResponse, OnboardStatusResponse, PickerResponse, SectionsResponse,
+        SelectItemResponse,
+    };
+    use crate::api_pairing::DeviceInfo;
+    use crate::api_personality::{
+        PersonalityConflict, PersonalityFileResponse, PersonalityIndex, PersonalityPutBody,
+        PersonalityPutResponse, TemplateResponse,
+    };
+    use crate::api_sops::{AdvanceBody, StartRunBody};
+    use crate::api_users::{CreateUserBody, UpdateUserBody};
+    use crate::canvas::CanvasPostBody;
+    use crate::openai_compat::ChatCompletionRequest;
     use zeroclaw_config::api_error::ConfigApiError;
 
-    fn schema_value<T: JsonSchema>() -> serde_json::Value {
-        serde_json::to_value(schema_for!(T)).unwrap_or(serde_json::Value::Null)
-    }
-
-    let components = serde_json::json!({
+    let mut components = serde_json::json!({
         "schemas": {
             "ConfigApiError":   schema_value::<ConfigApiError>(),
             "PropPutBody":      schema_value::<PropPutBody>(),
@@ -86,6 +98,36 @@ pub fn build_spec() -> serde_json::Value {
             "DriftEntry":       schema_value::<DriftEntry>(),
             "DriftResponse":    schema_value::<DriftResponse>(),
             "Config":           schema_value::<zeroclaw_config::schema::Config>(),
+            "MapKeyResponse":   schema_value::<MapKeyResponse>(),
+            "TemplatesResponse": schema_value::<TemplatesResponse>(),
+            "Error": {
+                "type": "object",
+                "properties": { "error": { "type": "string" } },
+                "required": ["error"],
+            },
+            "CatalogResponse":        schema_value::<CatalogResponse>(),
+            "ModelsResponse":         schema_value::<ModelsResponse>(),
+            "OnboardStatusResponse":  schema_value::<OnboardStatusResponse>(),
+            "SectionsResponse":       schema_value::<SectionsResponse>(),
+            "PickerResponse":         schema_value::<PickerResponse>(),
+            "SelectItemResponse":     schema_value::<SelectItemResponse>(),
+            "PersonalityIndex":        schema_value::<PersonalityIndex>(),
+            "TemplateResponse":        schema_value::<TemplateResponse>(),
+            "PersonalityFileResponse": schema_value::<PersonalityFileResponse>(),
+            "PersonalityPutBody":      schema_value::<PersonalityPutBody>(),
+            "PersonalityPutResponse":  schema_value::<PersonalityPutResponse>(),
+            "PersonalityConflict":     schema_value::<PersonalityConflict>(),
+            "CronAddBody":      schema_value::<CronAddBody>(),
+            "CronPatchBody":    schema_value::<CronPatchBody>(),
+            "MemoryStoreBody":  schema_value::<MemoryStoreBody>(),
+            "DeviceInfo":       schema_value::<DeviceInfo>(),
+            "CreateUserBody":   schema_value::<CreateUserBody>(),
+            "UpdateUserBody":   schema_value::<UpdateUserBody>(),
+            "StartRunBody":     schema_value::<StartRunBody>(),
+            "AdvanceBody":      schema_value::<AdvanceBody>(),
+            "CanvasPostBody":   schema_value::<CanvasPostBody>(),
+            "WebhookBody":      schema_value::<WebhookBody>(),
+            "ChatCompletionRequest": schema_value::<ChatCompletionRequest>(),
         },
         "securitySchemes": {
             "bearerAuth": {
@@ -272,12 +314,29 @@ pub fn build_spec() -> serde_json::Value {
         }
     });
 
+    let mut registry = Components::default();
+    let mut paths = paths;
+    if let Some(paths) = paths.as_object_mut() {
+        for (path, ops) in gateway_paths(&mut registry) {
+            let entry = paths.entry(path).or_insert_with(|| serde_json::json!({}));
+            if let (Some(entry), serde_json::Value::Object(ops)) = (entry.as_object_mut(), ops) {
+                entry.extend(ops);
+            }
+        }
+    }
+
+    if let Some(schemas) = components["schemas"].as_object_mut() {
+        for (name, schema) in registry.0 {
+            schemas.entry(name).or_insert(schema);
+        }
+    }
+
     let mut spec = serde_json::json!({
         "openapi": "3.1.0",
         "info": {
-            "title": "ZeroClaw Gateway — Config CRUD",
+            "title": "ZeroClaw Gateway",
             "version": env!("CARGO_PKG_VERSION"),
-            "description": "Per-property CRUD endpoints over the same `Config` mutation core that `zeroclaw config get/set/list/init/migrate` uses on the CLI. See https://github.com/zeroclaw-labs/zeroclaw/issues/6175 for the full surface and acceptance checklist.",
+            "description": "Every HTTP and WebSocket route the gateway serves: the dashboard API under `/api`, pairing and admin endpoints, channel webhooks, the OpenAI-compatible `/v1` surface, and the per-property config CRUD endpoints (see https://github.com/zeroclaw-labs/zeroclaw/issues/6175).\n\nEndpoints without a lock icon are public or authenticate themselves (webhook signatures, pairing codes, localhost-only admin).",
         },
         "security": [{"bearerAuth": []}],
         "paths": paths,
@@ -287,6 +346,1368 @@ pub fn build_spec() -> serde_json::Value {
     spec
 }
 
+/// One operation in [`gateway_paths`]. Path parameters are filled in from the
+/// `{name}` segments of the route when the operation is inserted.
+#[cfg(feature = "schema-export")]
+struct Op(serde_json::Value);
+
+#[cfg(feature = "schema-export")]
+impl Op {
+    fn new(tag: &str, summary: &str) -> Self {
+        Self(serde_json::json!({
+            "tags": [tag],
+            "summary": summary,
+            "parameters": [],
+            "responses": {},
+        }))
+    }
+
+    fn describe(mut self, text: &str) -> Self {
+        self.0["description"] = text.into();
+        self
+    }
+
+    /// Query parameters read off the handler's `Query<T>` extractor type.
+    fn query<T: JsonSchema>(mut self) -> Self {
+        let schema = serde_json::to_value(schema_for!(T)).unwrap_or_default();
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if let Some(props) = schema["properties"].as_object() {
+            for (name, prop) in props {
+                let mut prop = prop.clone();
+                let description = prop.as_object_mut().and_then(|p| p.remove("description"));
+                let mut param = serde_json::json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&name.as_str()),
+                    "schema": prop,
+                });
+                if let Some(description) = description {
+                    param["description"] = description;
+                }
+                self.push_param(param);
+            }
+        }
+        self
+    }
+
+    fn header(mut self, name: &str, description: &str) -> Self {
+        self.push_param(serde_json::json!({
+            "name": name,
+            "in": "header",
+            "required": false,
+            "schema": { "type": "string" },
+            "description": description,
+        }));
+        self
+    }
+
+    fn push_param(&mut self, param: serde_json::Value) {
+        if let Some(params) = self.0["parameters"].as_array_mut() {
+            params.push(param);
+        }
+    }
+
+    fn body(mut self, schema: serde_json::Value) -> Self {
+        self.0["requestBody"] = serde_json::json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema } },
+        });
+        self
+    }
+
+    /// Signed or vendor-shaped payloads the handler reads as raw bytes.
+    fn raw_body(mut self, description: &str) -> Self {
+        self.0["requestBody"] = serde_json::json!({
+            "required": true,
+            "description": description,
+            "content": { "application/json": { "schema": { "type": "object" } } },
+        });
+        self
+    }
+
+    fn ok(self, description: &str, schema: serde_json::Value) -> Self {
+        self.respond("200", "application/json", description, schema)
+    }
+
+    fn respond(
+        mut self,
+        status: &str,
+        content_type: &str,
+        description: &str,
+        schema: serde_json::Value,
+    ) -> Self {
+        self.0["responses"][status] = serde_json::json!({
+            "description": description,
+            "content": { content_type: { "schema": schema } },
+        });
+        self
+    }
+
+    fn error(self, status: &str, description: &str) -> Self {
+        self.respond(status, "application/json", description, schema_ref("Error"))
+    }
+
+    /// WebSocket upgrade endpoints: the only HTTP-level response is the 101.
+    fn upgrade(mut self) -> Self {
+        self.0["responses"]["101"] = serde_json::json!({
+            "description": "Switching Protocols — the connection continues as a WebSocket.",
+        });
+        self
+    }
+
+    /// Operations that skip the bearer token (they authenticate themselves,
+    /// if at all).
+    fn public(mut self) -> Self {
+        self.0["security"] = serde_json::json!([]);
+        self
+    }
+}
+
+#[cfg(feature = "schema-export")]
+fn schema_ref(name: &str) -> serde_json::Value {
+    serde_json::json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// `{ "<key>": <schema> }` — most dashboard handlers wrap their payload in a
+/// single named field.
+#[cfg(feature = "schema-export")]
+fn wrapped(key: &str, schema: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": { key: schema },
+        "required": [key],
+    })
+}
+
+/// A payload whose shape is defined outside this gateway (JSON Schema and
+/// OpenAPI documents, WebAuthn browser structures). `description` says what
+/// it is.
+#[cfg(feature = "schema-export")]
+fn free_form(description: &str) -> serde_json::Value {
+    serde_json::json!({ "type": "object", "description": description })
+}
+
+#[cfg(feature = "schema-export")]
+fn one_of(schemas: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({ "oneOf": schemas })
+}
+
+#[cfg(feature = "schema-export")]
+fn array_of(items: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({ "type": "array", "items": items })
+}
+
+#[cfg(feature = "schema-export")]
+fn status_ok() -> serde_json::Value {
+    status_const("ok")
+}
+
+#[cfg(feature = "schema-export")]
+fn status_const(status: &str) -> serde_json::Value {
+    wrapped(
+        "status",
+        serde_json::json!({ "type": "string", "const": status }),
+    )
+}
+
+/// A registered hardware key. The WebAuthn handlers only exist with the
+/// `webauthn` build feature, so their shapes are spelled out here.
+#[cfg(feature = "schema-export")]
+fn credential(with_sign_count: bool) -> serde_json::Value {
+    let mut schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "credential_id": { "type": "string", "description": "Base64url credential id." },
+            "label": { "type": "string" },
+            "registered_at": { "type": "string", "description": "RFC 3339 timestamp." },
+        },
+        "required": ["credential_id", "label", "registered_at"],
+    });
+    if with_sign_count {
+        schema["properties"]["sign_count"] = serde_json::json!({ "type": "integer", "minimum": 0 });
+        if let Some(required) = schema["required"].as_array_mut() {
+            required.push("sign_count".into());
+        }
+    }
+    schema
+}
+
+/// Response schemas registered while [`gateway_paths`] is built, keyed by
+/// `JsonSchema::schema_name()`.
+#[cfg(feature = "schema-export")]
+#[derive(Default)]
+struct Components(serde_json::Map<String, serde_json::Value>);
+
+#[cfg(feature = "schema-export")]
+impl Components {
+    /// Register `T` under `#/components/schemas` and return a `$ref` to it.
+    fn of<T: JsonSchema>(&mut self) -> serde_json::Value {
+        let name = T::schema_name();
+        let schema = schema_value::<T>();
+        if let Some(existing) = self.0.get(name.as_ref()) {
+            debug_assert_eq!(
+                existing, &schema,
+                "two response types share the schema name `{name}`"
+            );
+        } else {
+            self.0.insert(name.to_string(), schema);
+        }
+        schema_ref(&name)
+    }
+}
+
+#[cfg(feature = "schema-export")]
+fn schema_value<T: JsonSchema>() -> serde_json::Value {
+    serde_json::to_value(schema_for!(T)).unwrap_or(serde_json::Value::Null)
+}
+
+/// Every route outside the `/api/config/*` CRUD surface, keyed by path.
+#[cfg(feature = "schema-export")]
+fn gateway_paths(c: &mut Components) -> serde_json::Map<String, serde_json::Value> {
+    use crate::api::{
+        ChannelsResponse, CliToolsResponse, ComponentHealthResponse, CostResponse, CronJobResponse,
+        CronJobsResponse, CronRunResult, CronRunsQuery, CronRunsResponse, CronSettings,
+        CronSettingsUpdated, DoctorResponse, HandHistoryQuery, HandHistoryResponse,
+        HandRunResponse, HandsResponse, HookAck, IntegrationSettingsResponse, IntegrationsResponse,
+        MemoryDeleteResponse, MemoryEntriesResponse, MemoryQuery, OutboxEntriesResponse,
+        OutboxEntryResponse, OutboxQuery, OutboxReplayDeadResponse, OutboxReplayResponse,
+        SessionAbortResponse, SessionDeletedResponse, SessionExportQuery, SessionForkResponse,
+        SessionImportQuery, SessionImportResponse, SessionMessagesResponse, SessionRenamedResponse,
+        SessionStateResponse, SessionsResponse, StatusResponse, ToolsResponse,
+    };
+    use crate::api_config::MapKeyQuery;
+    use crate::api_onboard::ModelsQuery;
+    use crate::api_pairing::{
+        CapabilitiesResponse, DeviceRevokedResponse, DevicesResponse, PairedResponse,
+        PairingCodeResponse, TokenRotateResponse,
+    };
+    use crate::api_personality::{AgentQuery, TemplateQuery};
+    use crate::api_sops::RunsQuery;
+    use crate::api_sops::{
+        SopDetailResponse, SopMetricsResponse, SopRunResponse, SopRunsResponse,
+        SopTransitionResponse, SopsResponse,
+    };
+    use crate::api_users::{
+        MeResponse, UserDeletedResponse, UserInfo, UserPairCodeResponse, UsersResponse,
+    };
+    use crate::canvas::{
+        CanvasClearedResponse, CanvasFrameResponse, CanvasHistoryResponse, CanvasListResponse,
+    };
+    use crate::openai_compat::{ChatCompletion, ModelList};
+    use crate::sse::EventsHistoryResponse;
+    use crate::sse::EventsQuery;
+    use crate::webhook_sources::{SourceAgentReply, SourceRoutineResults, SourceSopRuns};
+    use crate::ws::WsQuery;
+    use crate::{WatiVerifyQuery, WhatsAppVerifyQuery};
+    use zeroclaw_runtime::session_export::Transcript;
+
+    let mut paths = serde_json::Map::new();
+    let mut add = |method: &str, path: &str, op: Op| {
+        let mut op = op.0;
+        if op.get("security").is_none() {
+            op["responses"]["401"] = serde_json::json!({
+                "description": "Missing or invalid bearer token, or a role below the route's minimum.",
+                "content": { "application/json": { "schema": schema_ref("Error") } },
+            });
+        }
+        if let Some(params) = op["parameters"].as_array_mut() {
+            let names = path
+                .split('/')
+                .filter_map(|seg| seg.strip_prefix('{')?.strip_suffix('}'));
+            for (i, name) in names.enumerate() {
+                params.insert(
+                    i,
+                    serde_json::json!({
+                        "name": name,
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string" },
+                    }),
+                );
+            }
+        }
+        let entry = paths
+            .entry(path.to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        entry[method] = op;
+    };
+
+    // ── Admin (localhost only) ──
+    let localhost = "Only accepted from a loopback peer; used by the `zeroclaw` CLI.";
+    add(
+        "post",
+        "/admin/shutdown",
+        Op::new("admin", "Shut the daemon down")
+            .describe(localhost)
+            .public()
+            .ok("Shutdown initiated.", c.of::<crate::AdminResponse>())
+            .error("403", "Request did not come from localhost."),
+    );
+    add(
+        "post",
+        "/admin/reload",
+        Op::new("admin", "Reload configuration from disk")
+            .describe(localhost)
+            .public()
+            .ok("Reload result.", c.of::<crate::AdminResponse>())
+            .error("403", "Request did not come from localhost."),
+    );
+    add(
+        "get",
+        "/admin/paircode",
+        Op::new("admin", "Show the current pairing code")
+            .describe(localhost)
+            .public()
+            .ok(
+                "Current pairing code, if any.",
+                c.of::<crate::PairCodeResponse>(),
+            )
+            .error("403", "Request did not come from localhost."),
+    );
+    add(
+        "post",
+        "/admin/paircode/new",
+        Op::new("admin", "Generate a fresh pairing code")
+            .describe(localhost)
+            .public()
+            .ok("The new pairing code.", c.of::<crate::PairCodeResponse>())
+            .error("403", "Request did not come from localhost."),
+    );
+
+    // ── Health, metrics, pairing ──
+    add(
+        "get",
+        "/health",
+        Op::new("system", "Liveness probe").public().ok(
+            "Gateway is up; includes pairing and runtime summary.",
+            c.of::<crate::HealthResponse>(),
+        ),
+    );
+    add(
+        "get",
+        "/metrics",
+        Op::new("system", "Prometheus metrics").public().respond(
+            "200",
+            "text/plain",
+            "Prometheus text exposition format.",
+            serde_json::json!({ "type": "string" }),
+        ),
+    );
+    add(
+        "post",
+        "/pair",
+        Op::new(
+            "pairing",
+            "Exchange a one-time pairing code for a bearer token",
+        )
+        .public()
+        .header("X-Pairing-Code", "The one-time pairing code.")
+        .ok(
+            "Paired; the response carries the new bearer token.",
+            c.of::<crate::PairResponse>(),
+        )
+        .error("403", "Invalid pairing code.")
+        .error("429", "Rate limited; see `retry_after`."),
+    );
+    add(
+        "get",
+        "/pair/code",
+        Op::new("pairing", "Pairing code during initial setup")
+            .describe("Returns the code only before the first device pairs.")
+            .public()
+            .ok(
+                "Pairing status and, during setup, the code.",
+                c.of::<crate::PairCodeResponse>(),
+            ),
+    );
+    add(
+        "post",
+        "/api/pairing/initiate",
+        Op::new("pairing", "Start pairing a new device").ok(
+            "A pairing code for the new device.",
+            c.of::<PairingCodeResponse>(),
+        ),
+    );
+    add(
+        "post",
+        "/api/pair",
+        Op::new("pairing", "Submit a pairing code with device metadata")
+            .public()
+            .body(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string" },
+                    "device_name": { "type": "string" },
+                    "device_type": { "type": "string" },
+                },
+                "required": ["code"],
+            }))
+            .ok(
+                "Paired; carries the bearer token and device id.",
+                c.of::<PairedResponse>(),
+            )
+            .error("403", "Invalid pairing code."),
+    );
+    add(
+        "get",
+        "/api/devices",
+        Op::new("devices", "List paired devices").ok("Paired devices.", c.of::<DevicesResponse>()),
+    );
+    add(
+        "post",
+        "/api/devices/me/capabilities",
+        Op::new(
+            "devices",
+            "Report the calling device's granted capabilities",
+        )
+        .body(wrapped(
+            "capabilities",
+            array_of(serde_json::json!({ "type": "string" })),
+        ))
+        .ok("Capabilities stored.", c.of::<CapabilitiesResponse>()),
+    );
+    add(
+        "delete",
+        "/api/devices/{id}",
+        Op::new("devices", "Revoke a paired device")
+            .ok("Device revoked.", c.of::<DeviceRevokedResponse>())
+            .error("404", "Unknown device."),
+    );
+    add(
+        "post",
+        "/api/devices/{id}/token/rotate",
+        Op::new("devices", "Rotate a device's bearer token")
+            .ok("The replacement token.", c.of::<TokenRotateResponse>())
+            .error("404", "Unknown device."),
+    );
+
+    // ── Users ──
+    add(
+        "get",
+        "/api/me",
+        Op::new("users", "Who the current token belongs to")
+            .ok("Caller's user name and role.", c.of::<MeResponse>()),
+    );
+    add(
+        "get",
+        "/api/users",
+        Op::new("users", "List gateway users").ok("Configured users.", c.of::<UsersResponse>()),
+    );
+    add(
+        "post",
+        "/api/users",
+        Op::new("users", "Create a gateway user")
+            .body(schema_ref("CreateUserBody"))
+            .respond(
+                "201",
+                "application/json",
+                "User created.",
+                c.of::<UserInfo>(),
+            )
+            .error("409", "A user with that name exists."),
+    );
+    add(
+        "put",
+        "/api/users/{name}",
+        Op::new("users", "Change a user's role")
+            .body(schema_ref("UpdateUserBody"))
+            .ok("Updated user.", c.of::<UserInfo>())
+            .error("404", "Unknown user."),
+    );
+    add(
+        "delete",
+        "/api/users/{name}",
+        Op::new("users", "Delete a user and revoke their tokens")
+            .ok("User deleted.", c.of::<UserDeletedResponse>())
+            .error("404", "Unknown user."),
+    );
+    add(
+        "post",
+        "/api/users/{name}/paircode",
+        Op::new("users", "Issue a pairing code bound to a user")
+            .ok("The pairing code.", c.of::<UserPairCodeResponse>())
+            .error("404", "Unknown user."),
+    );
+
+    // ── Channel webhooks ──
+    add(
+        "post",
+        "/webhook",
+        Op::new("webhooks", "Send a message to the agent")
+            .describe("Authenticated by bearer token when pairing is required, and by `X-Webhook-Secret` when a webhook secret is configured.")
+            .public()
+            .header("X-Webhook-Secret", "Shared secret, when configured.")
+            .header("X-Idempotency-Key", "Replays with the same key return the first response.")
+            .body(schema_ref("WebhookBody"))
+            .ok(
+                "Agent reply, or the first reply's acknowledgement for a replayed key.",
+                one_of(vec![
+                    c.of::<crate::WebhookResponse>(),
+                    c.of::<crate::WebhookDuplicate>(),
+                ]),
+            )
+            .error("401", "Missing or invalid credentials.")
+            .error("429", "Rate limited."),
+    );
+    add(
+        "post",
+        "/webhook/{source}",
+        Op::new("webhooks", "Deliver a signed event from a configured source")
+            .describe("Verified per `[gateway.webhooks.<source>]` (HMAC, shared secret, mTLS or none), then routed to an agent session, an SOP run, or the routines engine. Deliveries whose idempotency key or signature was already seen are acknowledged as duplicates.")
+            .public()
+            .raw_body("Source-defined payload, passed to the prompt template.")
+            .ok(
+                "Agent reply, routine results, duplicate acknowledgement, or no matching SOP.",
+                one_of(vec![
+                    c.of::<SourceAgentReply>(),
+                    c.of::<SourceRoutineResults>(),
+                    c.of::<crate::WebhookDuplicate>(),
+                    c.of::<SourceSopRuns>(),
+                ]),
+            )
+            .respond(
+                "202",
+                "application/json",
+                "SOP runs started.",
+                c.of::<SourceSopRuns>(),
+            )
+            .error("401", "Verification failed.")
+            .error("404", "Unknown or disabled source, or unknown SOP.")
+            .error("429", "Rate limited."),
+    );
+    add(
+        "get",
+        "/whatsapp",
+        Op::new("webhooks", "WhatsApp Cloud API verification handshake")
+            .public()
+            .query::<WhatsAppVerifyQuery>()
+            .respond(
+                "200",
+                "text/plain",
+                "Echoes `hub.challenge`.",
+                serde_json::json!({ "type": "string" }),
+            )
+            .error("403", "Verify token mismatch."),
+    );
+    add(
+        "post",
+        "/whatsapp",
+        Op::new("webhooks", "WhatsApp Cloud API message webhook")
+            .describe("Verified with `X-Hub-Signature-256` when an app secret is configured.")
+            .public()
+            .header("X-Hub-Signature-256", "HMAC-SHA256 of the body.")
+            .raw_body("Meta webhook payload.")
+            .ok("Accepted.", status_ok()),
+    );
+    add(
+        "post",
+        "/linq",
+        Op::new("webhooks", "Linq message webhook")
+            .public()
+            .header("X-Webhook-Signature", "HMAC signature of the body.")
+            .header("X-Webhook-Timestamp", "Signing timestamp.")
+            .raw_body("Linq webhook payload.")
+            .ok("Accepted.", status_ok()),
+    );
+    add(
+        "get",
+        "/wati",
+        Op::new("webhooks", "WATI verification handshake")
+            .public()
+            .query::<WatiVerifyQuery>()
+            .respond(
+                "200",
+                "text/plain",
+                "Echoes `hub.challenge`.",
+                serde_json::json!({ "type": "string" }),
+            ),
+    );
+    add(
+        "post",
+        "/wati",
+        Op::new("webhooks", "WATI message webhook")
+            .public()
+            .raw_body("WATI webhook payload.")
+            .ok("Accepted.", status_ok()),
+    );
+    add(
+        "post",
+        "/nextcloud-talk",
+        Op::new("webhooks", "Nextcloud Talk bot webhook")
+            .public()
+            .header(
+                "X-Nextcloud-Talk-Signature",
+                "HMAC-SHA256 of random + body.",
+            )
+            .header("X-Nextcloud-Talk-Random", "Signing nonce.")
+            .raw_body("Nextcloud Talk activity payload.")
+            .ok("Accepted.", status_ok()),
+    );
+    add(
+        "post",
+        "/webhook/gmail",
+        Op::new("webhooks", "Gmail Pub/Sub push notification")
+            .public()
+            .raw_body("Pub/Sub push envelope.")
+            .ok("Accepted.", status_ok()),
+    );
+    add(
+        "post",
+        "/hooks/claude-code",
+        Op::new("webhooks", "Claude Code runner hook event")
+            .body(c.of::<zeroclaw_tools::claude_code_runner::ClaudeCodeHookEvent>())
+            .ok("Accepted.", c.of::<HookAck>()),
+    );
+
+    // ── Config extras ──
+    let schema_doc = "JSON Schema for the addressed part of `Config` (CORS preflights get 204).";
+    add(
+        "options",
+        "/api/config",
+        Op::new("config", "JSON Schema for the whole config")
+            .public()
+            .ok(schema_doc, free_form(schema_doc)),
+    );
+    add(
+        "options",
+        "/api/config/prop",
+        Op::new("config", "JSON Schema for one property")
+            .public()
+            .query::<crate::api_config::PropQuery>()
+            .ok(schema_doc, free_form(schema_doc)),
+    );
+    add(
+        "get",
+        "/api/config/templates",
+        Op::new(
+            "config",
+            "Map- and list-keyed sections that accept new entries",
+        )
+        .ok("Templates.", schema_ref("TemplatesResponse")),
+    );
+    add(
+        "post",
+        "/api/config/map-key",
+        Op::new(
+            "config",
+            "Insert a defaulted entry under a map-keyed section",
+        )
+        .query::<MapKeyQuery>()
+        .ok(
+            "Key created (or already present).",
+            schema_ref("MapKeyResponse"),
+        )
+        .error("400", "Path is not a map-keyed section."),
+    );
+
+    // ── Onboarding ──
+    add(
+        "get",
+        "/api/onboard/catalog",
+        Op::new("onboard", "Provider catalog")
+            .ok("Known providers.", schema_ref("CatalogResponse")),
+    );
+    add(
+        "get",
+        "/api/onboard/catalog/models",
+        Op::new("onboard", "Models offered by a provider")
+            .query::<ModelsQuery>()
+            .ok("Models.", schema_ref("ModelsResponse")),
+    );
+    add(
+        "get",
+        "/api/onboard/status",
+        Op::new("onboard", "Onboarding progress")
+            .ok("Completed sections.", schema_ref("OnboardStatusResponse")),
+    );
+    add(
+        "get",
+        "/api/onboard/sections",
+        Op::new("onboard", "Onboarding sections")
+            .ok("Sections in wizard order.", schema_ref("SectionsResponse")),
+    );
+    add(
+        "get",
+        "/api/onboard/sections/{section}",
+        Op::new("onboard", "Picker items for a section")
+            .ok("Picker items.", schema_ref("PickerResponse"))
+            .error("404", "Unknown section."),
+    );
+    add(
+        "post",
+        "/api/onboard/sections/{section}/items/{key}",
+        Op::new("onboard", "Select a picker item")
+            .ok("Selection applied.", schema_ref("SelectItemResponse"))
+            .error("404", "Unknown section or item."),
+    );
+
+    // ── Personality files ──
+    add(
+        "get",
+        "/api/personality",
+        Op::new("personality", "List workspace personality files")
+            .query::<AgentQuery>()
+            .ok("Files with size and mtime.", schema_ref("PersonalityIndex")),
+    );
+    add(
+        "get",
+        "/api/personality/templates",
+        Op::new("personality", "Render the starter personality templates")
+            .query::<TemplateQuery>()
+            .ok("Rendered files.", schema_ref("TemplateResponse")),
+    );
+    add(
+        "get",
+        "/api/personality/{filename}",
+        Op::new("personality", "Read one personality file")
+            .query::<AgentQuery>()
+            .ok("File content.", schema_ref("PersonalityFileResponse"))
+            .error("400", "Filename is not an editable personality file."),
+    );
+    add(
+        "put",
+        "/api/personality/{filename}",
+        Op::new("personality", "Write one personality file")
+            .query::<AgentQuery>()
+            .body(schema_ref("PersonalityPutBody"))
+            .ok("Written.", schema_ref("PersonalityPutResponse"))
+            .respond(
+                "409",
+                "application/json",
+                "File changed on disk since `expected_mtime_ms`.",
+                schema_ref("PersonalityConflict"),
+            ),
+    );
+
+    // ── Docs ──
+    add(
+        "get",
+        "/api/openapi.json",
+        Op::new("docs", "This document").public().ok(
+            "OpenAPI 3.1 document.",
+            free_form("This OpenAPI 3.1 document."),
+        ),
+    );
+    add(
+        "get",
+        "/api/docs",
+        Op::new("docs", "Interactive API explorer")
+            .public()
+            .respond(
+                "200",
+                "text/html",
+                "Scalar explorer page.",
+                serde_json::json!({ "type": "string" }),
+            ),
+    );
+
+    // ── Status and inventory ──
+    add(
+        "get",
+        "/api/status",
+        Op::new("system", "System status overview").ok(
+            "Provider, model, uptime, channels.",
+            c.of::<StatusResponse>(),
+        ),
+    );
+    add(
+        "get",
+        "/api/health",
+        Op::new("system", "Component health snapshot")
+            .ok("Health.", c.of::<ComponentHealthResponse>()),
+    );
+    add(
+        "get",
+        "/api/tools",
+        Op::new("system", "Registered agent tools").ok("Tool specs.", c.of::<ToolsResponse>()),
+    );
+    add(
+        "get",
+        "/api/cli-tools",
+        Op::new("system", "Discovered CLI tools").ok("CLI tools.", c.of::<CliToolsResponse>()),
+    );
+    add(
+        "get",
+        "/api/channels",
+        Op::new("system", "Configured channels")
+            .ok("Channels and their state.", c.of::<ChannelsResponse>()),
+    );
+    add(
+        "get",
+        "/api/integrations",
+        Op::new("system", "Integrations and their status")
+            .ok("Integrations.", c.of::<IntegrationsResponse>()),
+    );
+    add(
+        "get",
+        "/api/integrations/settings",
+        Op::new("system", "Per-integration settings")
+            .ok("Settings.", c.of::<IntegrationSettingsResponse>()),
+    );
+    add(
+        "get",
+        "/api/doctor",
+        Op::new("system", "Run diagnostics").ok("Diagnostic results.", c.of::<DoctorResponse>()),
+    );
+    add(
+        "post",
+        "/api/doctor",
+        Op::new("system", "Run diagnostics").ok("Diagnostic results.", c.of::<DoctorResponse>()),
+    );
+    add(
+        "get",
+        "/api/cost",
+        Op::new("cost", "Token usage and spend summary")
+            .ok("Cost summary.", c.of::<CostResponse>()),
+    );
+    add(
+        "get",
+        "/api/plugins",
+        Op::new("system", "Installed WASM plugins")
+            .describe("Requires the `plugins-wasm` build feature.")
+            .ok("Plugins.", c.of::<crate::api::PluginsResponse>()),
+    );
+
+    // ── Cron ──
+    add(
+        "get",
+        "/api/cron",
+        Op::new("cron", "List scheduled jobs").ok("Jobs.", c.of::<CronJobsResponse>()),
+    );
+    add(
+        "post",
+        "/api/cron",
+        Op::new("cron", "Add a scheduled job")
+            .body(schema_ref("CronAddBody"))
+            .ok("Job created.", c.of::<CronJobResponse>())
+            .error("400", "Invalid schedule or job type."),
+    );
+    add(
+        "get",
+        "/api/cron/settings",
+        Op::new("cron", "Cron subsystem settings").ok("Settings.", c.of::<CronSettings>()),
+    );
+    add(
+        "patch",
+        "/api/cron/settings",
+        Op::new("cron", "Update cron subsystem settings")
+            .body(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "enabled": { "type": "boolean" },
+                    "catch_up_on_startup": { "type": "boolean" },
+                    "max_run_history": { "type": "integer", "minimum": 0 },
+                },
+            }))
+            .ok("Updated settings.", c.of::<CronSettingsUpdated>()),
+    );
+    add(
+        "patch",
+        "/api/cron/{id}",
+        Op::new("cron", "Edit a scheduled job")
+            .body(schema_ref("CronPatchBody"))
+            .ok("Job updated.", c.of::<CronJobResponse>())
+            .error("404", "Unknown job."),
+    );
+    add(
+        "delete",
+        "/api/cron/{id}",
+        Op::new("cron", "Remove a scheduled job")
+            .ok("Job removed.", status_ok())
+            .error("404", "Unknown job."),
+    );
+    add(
+        "get",
+        "/api/cron/{id}/runs",
+        Op::new("cron", "Recent runs of a job")
+            .query::<CronRunsQuery>()
+            .ok("Runs, newest first.", c.of::<CronRunsResponse>()),
+    );
+    add(
+        "post",
+        "/api/cron/{id}/run",
+        Op::new("cron", "Run a job now")
+            .describe("Runs synchronously on the long-running router (extended timeout).")
+            .ok("Run result.", c.of::<CronRunResult>())
+            .error("404", "Unknown job."),
+    );
+
+    // ── Outbox ──
+    add(
+        "get",
+        "/api/outbox",
+        Op::new("outbox", "List outbound deliveries")
+            .query::<OutboxQuery>()
+            .ok("Entries.", c.of::<OutboxEntriesResponse>()),
+    );
+    add(
+        "post",
+        "/api/outbox/replay-dead",
+        Op::new("outbox", "Requeue every dead delivery")
+            .ok("Number requeued.", c.of::<OutboxReplayDeadResponse>()),
+    );
+    add(
+        "get",
+        "/api/outbox/{id}",
+        Op::new("outbox", "One outbound delivery")
+            .ok("Entry.", c.of::<OutboxEntryResponse>())
+            .error("404", "Unknown entry."),
+    );
+    add(
+        "delete",
+        "/api/outbox/{id}",
+        Op::new("outbox", "Drop an outbound delivery")
+            .ok("Dropped.", status_ok())
+            .error("404", "Unknown entry."),
+    );
+    add(
+        "post",
+        "/api/outbox/{id}/replay",
+        Op::new("outbox", "Requeue one delivery")
+            .ok("Requeued.", c.of::<OutboxReplayResponse>())
+            .error("404", "Unknown entry."),
+    );
+
+    // ── Hands ──
+    add(
+        "get",
+        "/api/hands",
+        Op::new("hands", "List hands")
+            .describe("Each hand with its schedule, next run and most recent run.")
+            .ok("Hands.", c.of::<HandsResponse>()),
+    );
+    add(
+        "post",
+        "/api/hands/{name}/run",
+        Op::new("hands", "Run a hand now")
+            .describe("Runs synchronously on the long-running router (extended timeout).")
+            .ok("The recorded run.", c.of::<HandRunResponse>())
+            .error("404", "Unknown hand."),
+    );
+    add(
+        "get",
+        "/api/hands/{name}/history",
+        Op::new("hands", "Recent runs of a hand")
+            .query::<HandHistoryQuery>()
+            .ok(
+                "Runs, newest first, and learned facts.",
+                c.of::<HandHistoryResponse>(),
+            )
+            .error("404", "Unknown hand."),
+    );
+
+    // ── Memory ──
+    add(
+        "get",
+        "/api/memory",
+        Op::new("memory", "List or search memories")
+            .query::<MemoryQuery>()
+            .ok("Entries.", c.of::<MemoryEntriesResponse>()),
+    );
+    add(
+        "post",
+        "/api/memory",
+        Op::new("memory", "Store a memory")
+            .body(schema_ref("MemoryStoreBody"))
+            .ok("Stored.", status_ok()),
+    );
+    add(
+        "delete",
+        "/api/memory/{key}",
+        Op::new("memory", "Forget a memory").ok(
+            "Whether anything was deleted.",
+            c.of::<MemoryDeleteResponse>(),
+        ),
+    );
+
+    // ── Sessions ──
+    add(
+        "get",
+        "/api/sessions",
+        Op::new("sessions", "List persisted gateway sessions")
+            .ok("Sessions.", c.of::<SessionsResponse>()),
+    );
+    add(
+        "get",
+        "/api/sessions/running",
+        Op::new("sessions", "Sessions with a turn in flight")
+            .ok("Running sessions.", c.of::<SessionsResponse>()),
+    );
+    add(
+        "get",
+        "/api/sessions/{id}/messages",
+        Op::new("sessions", "Transcript of a session")
+            .ok("Messages in order.", c.of::<SessionMessagesResponse>()),
+    );
+    add(
+        "put",
+        "/api/sessions/{id}",
+        Op::new("sessions", "Rename a session")
+            .body(wrapped("name", serde_json::json!({ "type": "string" })))
+            .ok("Renamed.", c.of::<SessionRenamedResponse>())
+            .error("404", "Unknown session, or persistence is disabled."),
+    );
+    add(
+        "delete",
+        "/api/sessions/{id}",
+        Op::new("sessions", "Delete a session")
+            .ok("Deleted.", c.of::<SessionDeletedResponse>())
+            .error("404", "Unknown session, or persistence is disabled."),
+    );
+    add(
+        "get",
+        "/api/sessions/{id}/state",
+        Op::new("sessions", "Run state of a session")
+            .ok("State.", c.of::<SessionStateResponse>())
+            .error("404", "Unknown session."),
+    );
+    add(
+        "post",
+        "/api/sessions/{id}/abort",
+        Op::new("sessions", "Abort the in-flight turn")
+            .ok("Abort requested.", c.of::<SessionAbortResponse>()),
+    );
+    add(
+        "post",
+        "/api/sessions/{id}/fork",
+        Op::new("sessions", "Fork a session at a message")
+            .body(serde_json::json!({
+                "type": "object",
+                "required": ["at"],
+                "properties": {
+                    "at": { "type": "integer", "minimum": 1, "description": "Leading messages to copy." },
+                    "session_id": { "type": "string", "description": "Id for the fork; generated when omitted." }
+                }
+            }))
+            .respond(
+                "201",
+                "application/json",
+                "Forked; returns the new session id and lineage.",
+                c.of::<SessionForkResponse>(),
+            )
+            .error("400", "`at` is missing or outside the session's length.")
+            .error("404", "Unknown session, or persistence is disabled.")
+            .error("409", "A session with the requested id already exists."),
+    );
+    add(
+        "get",
+        "/api/sessions/{id}/export",
+        Op::new("sessions", "Export a session transcript")
+            .describe(
+                "Markdown and HTML are readable transcripts with collapsible tool blocks, \
+                 timestamps, and model/cost annotations; secrets are always scrubbed. \
+                 JSON is scrubbed too unless `redact=false`, which exports the raw \
+                 stored content; it can be posted back to `/api/sessions/import`.",
+            )
+            .query::<SessionExportQuery>()
+            .respond(
+                "200",
+                "text/markdown",
+                "Transcript as an attachment; `text/html` or `application/json` per `format`.",
+                serde_json::json!({ "type": "string" }),
+            )
+            .error("400", "Unknown `format`.")
+            .error("404", "Unknown session, or persistence is disabled."),
+    );
+    add(
+        "post",
+        "/api/sessions/import",
+        Op::new("sessions", "Import a JSON session transcript")
+            .query::<SessionImportQuery>()
+            .body(c.of::<Transcript>())
+            .respond(
+                "201",
+                "application/json",
+                "Imported; returns the session id and message count.",
+                c.of::<SessionImportResponse>(),
+            )
+            .error("400", "The body is not a supported transcript.")
+            .error("409", "A session with the target id already exists."),
+    );
+
+    // ── SOPs ──
+    add(
+        "get",
+        "/api/sops",
+        Op::new("sops", "Loaded SOP definitions").ok("SOPs.", c.of::<SopsResponse>()),
+    );
+    add(
+        "get",
+        "/api/sops/metrics",
+        Op::new("sops", "SOP run metrics").ok("Metrics.", c.of::<SopMetricsResponse>()),
+    );
+    add(
+        "get",
+        "/api/sops/runs",
+        Op::new("sops", "Active and finished SOP runs")
+            .query::<RunsQuery>()
+            .ok("Runs.", c.of::<SopRunsResponse>()),
+    );
+    add(
+        "get",
+        "/api/sops/runs/{run_id}",
+        Op::new("sops", "One SOP run")
+            .ok("Run.", c.of::<SopRunResponse>())
+            .error("404", "Unknown run."),
+    );
+    add(
+        "post",
+        "/api/sops/runs/{run_id}/approve",
+        Op::new("sops", "Approve a run waiting on a gate")
+            .ok("Next action.", c.of::<SopTransitionResponse>())
+            .error("409", "Run is not waiting for approval."),
+    );
+    add(
+        "post",
+        "/api/sops/runs/{run_id}/advance",
+        Op::new("sops", "Report a step result and advance")
+            .body(schema_ref("AdvanceBody"))
+            .ok("Next action.", c.of::<SopTransitionResponse>())
+            .error("404", "Unknown run."),
+    );
+    add(
+        "post",
+        "/api/sops/runs/{run_id}/cancel",
+        Op::new("sops", "Cancel a run")
+            .ok("Cancelled.", c.of::<SopRunResponse>())
+            .error("404", "Unknown run."),
+    );
+    add(
+        "get",
+        "/api/sops/{name}",
+        Op::new("sops", "One SOP definition")
+            .ok("SOP.", c.of::<SopDetailResponse>())
+            .error("404", "Unknown SOP."),
+    );
+    add(
+        "post",
+        "/api/sops/{name}/run",
+        Op::new("sops", "Start an SOP run")
+            .body(schema_ref("StartRunBody"))
+            .respond(
+                "201",
+                "application/json",
+                "Run started.",
+                c.of::<SopTransitionResponse>(),
+            )
+            .error("404", "Unknown SOP."),
+    );
+
+    // ── Canvas ──
+    add(
+        "get",
+        "/api/canvas",
+        Op::new("canvas", "Active canvases").ok("Canvas ids.", c.of::<CanvasListResponse>()),
+    );
+    add(
+        "get",
+        "/api/canvas/{id}",
+        Op::new("canvas", "Current canvas frame")
+            .ok("Frame.", c.of::<CanvasFrameResponse>())
+            .error("404", "Unknown canvas."),
+    );
+    add(
+        "post",
+        "/api/canvas/{id}",
+        Op::new("canvas", "Push a frame to a canvas")
+            .body(schema_ref("CanvasPostBody"))
+            .ok("Frame stored and broadcast.", c.of::<CanvasFrameResponse>()),
+    );
+    add(
+        "delete",
+        "/api/canvas/{id}",
+        Op::new("canvas", "Clear a canvas").ok("Cleared.", c.of::<CanvasClearedResponse>()),
+    );
+    add(
+        "get",
+        "/api/canvas/{id}/history",
+        Op::new("canvas", "Frame history of a canvas")
+            .ok("Frames, oldest first.", c.of::<CanvasHistoryResponse>()),
+    );
+
+    // ── WebAuthn (requires the `webauthn` build feature) ──
+    let webauthn = "Requires the `webauthn` build feature.";
+    let user_id = serde_json::json!({
+        "type": "object",
+        "properties": { "user_id": { "type": "string" } },
+        "required": ["user_id"],
+    });
+    add(
+        "post",
+        "/api/webauthn/register/start",
+        Op::new("webauthn", "Begin registering a hardware key")
+            .describe(webauthn)
+            .body(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "user_id": { "type": "string" },
+                    "user_name": { "type": "string" },
+                },
+                "required": ["user_id", "user_name"],
+            }))
+            .ok(
+                "Registration challenge.",
+                free_form(
+                    "`PublicKeyCredentialCreationOptions` for `navigator.credentials.create()`.",
+                ),
+            ),
+    );
+    add(
+        "post",
+        "/api/webauthn/register/finish",
+        Op::new("webauthn", "Finish registering a hardware key")
+            .describe(webauthn)
+            .body(free_form(
+                "`challenge` plus the browser's attestation response (`id`, `attestation_object`, `client_data_json`, optional `label`), base64url-encoded.",
+            ))
+            .ok("Credential stored.", credential(false)),
+    );
+    add(
+        "post",
+        "/api/webauthn/auth/start",
+        Op::new("webauthn", "Begin a hardware key assertion")
+            .describe(webauthn)
+            .body(user_id)
+            .ok(
+                "Authentication challenge.",
+                free_form("`PublicKeyCredentialRequestOptions` for `navigator.credentials.get()`."),
+            ),
+    );
+    add(
+        "post",
+        "/api/webauthn/auth/finish",
+        Op::new("webauthn", "Verify a hardware key assertion")
+            .describe(webauthn)
+            .body(free_form(
+                "`challenge` plus the browser's assertion response (`id`, `authenticator_data`, `client_data_json`, `signature`), base64url-encoded.",
+            ))
+            .ok("Assertion verified.", status_const("authenticated")),
+    );
+    let user_id_query = serde_json::json!({
+        "name": "user_id",
+        "in": "query",
+        "required": true,
+        "schema": { "type": "string" },
+    });
+    let mut list = Op::new("webauthn", "Registered hardware keys").describe(webauthn);
+    list.push_param(user_id_query.clone());
+    add(
+        "get",
+        "/api/webauthn/credentials",
+        list.ok(
+            "Credentials.",
+            wrapped("credentials", array_of(credential(true))),
+        ),
+    );
+    let mut remove = Op::new("webauthn", "Remove a hardware key").describe(webauthn);
+    remove.push_param(user_id_query);
+    add(
+        "delete",
+        "/api/webauthn/credentials/{id}",
+        remove
+            .ok("Removed.", status_const("deleted"))
+            .error("404", "Unknown credential."),
+    );
+
+    // ── Event stream ──
+    add(
+        "get",
+        "/api/events",
+        Op::new("events", "Live event stream (SSE)")
+            .describe("Each event carries an `id:`; reconnect with `Last-Event-ID` to resume.")
+            .query::<EventsQuery>()
+            .header("Last-Event-ID", "Resume after this event id.")
+            .respond(
+                "200",
+                "text/event-stream",
+                "Server-sent events.",
+                serde_json::json!({ "type": "string" }),
+            ),
+    );
+    add(
+        "get",
+        "/api/events/history",
+        Op::new("events", "Recent events")
+            .query::<EventsQuery>()
+            .ok("Buffered events with ids.", c.of::<EventsHistoryResponse>()),
+    );
+
+    // ── OpenAI-compatible ──
+    add(
+        "get",
+        "/v1/models",
+        Op::new("openai", "List models").ok("OpenAI `list` object.", c.of::<ModelList>()),
+    );
+    add(
+        "post",
+        "/v1/chat/completions",
+        Op::new("openai", "Chat completion")
+            .describe("Streams `text/event-stream` chunks when `stream` is true.")
+            .header(
+                "X-Session-Id",
+                "Bind the request to a persisted gateway session.",
+            )
+            .body(schema_ref("ChatCompletionRequest"))
+            .ok("OpenAI `chat.completion` object.", c.of::<ChatCompletion>())
+            .error("400", "Malformed request."),
+    );
+
+    // ── WebSockets ──
+    add(
+        "get",
+        "/ws/chat",
+        Op::new("websocket", "Agent chat socket")
+            .describe("Subprotocol `zeroclaw.v1`; the token may ride in `?token=` or a `bearer.<token>` subprotocol.")
+            .public()
+            .query::<WsQuery>()
+            .upgrade(),
+    );
+    add(
+        "get",
+        "/ws/canvas/{id}",
+        Op::new("websocket", "Canvas update socket")
+            .public()
+            .upgrade(),
+    );
+    add(
+        "get",
+        "/ws/nodes",
+        Op::new("websocket", "Node discovery socket")
+            .public()
+            .query::<crate::nodes::NodeWsQuery>()
+            .upgrade(),
+    );
+    add(
+        "get",
+        "/acp",
+        Op::new("websocket", "Agent Client Protocol bridge")
+            .describe("Subprotocol `zeroclaw.acp.v1`.")
+            .public()
+            .query::<crate::acp::AcpQuery>()
+            .upgrade(),
+    );
+
+    // ── Dashboard assets ──
+    add(
+        "get",
+        "/_app/{path}",
+        Op::new("dashboard", "Web dashboard static asset")
+            .public()
+            .respond(
+                "200",
+                "application/octet-stream",
+                "Asset bytes with a content type from the file extension.",
+                serde_json::json!({ "type": "string", "format": "binary" }),
+            )
+            .error("404", "No such asset."),
+    );
+
+    paths
+}
+
 /// schemars emits nested types under each component's `$defs` and
 /// references them as `#/$defs/<Name>`. OpenAPI 3.1 tooling
 /// (openapi-typescript, Scalar, codegen) expects them at top-level
@@ -396,6 +1817,7 @@ pub fn build_spec() -> serde_json::Value {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "schema-export")]
     use super::*;
 
     #[cfg(feature = "schema-export")]
@@ -419,4 +1841,149 @@ mod tests {
             .and_then(|v| v.as_str());
         assert_eq!(scheme, Some("bearer"));
     }
+
+    /// `(method, path)` for every `.route("<path>", <method router>)` call in
+    /// `lib.rs`, with axum's `{*rest}` wildcard written the OpenAPI way.
+    /// Routes built from `format!` (the path-prefix redirect) are skipped.
+    fn registered_routes() -> Vec<(String, String)> {
+        let source = include_str!("lib.rs");
+        let mut routes = Vec::new();
+        for chunk in source.split(".route(").skip(1) {
+            let Some(rest) = chunk.trim_start().strip_prefix('"') else {
+                continue;
+            };
+            let Some(end) = rest.find('"') else { continue };
+            let path = rest[..end].replace("{*", "{");
+            let mut depth = 1;
+            let mut stop = rest.len();
+            for (i, c) in rest[end..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            stop = end + i;
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let router = &rest[end..stop];
+            for method in ["get", "post", "put", "patch", "delete", "options"] {
+                let call = format!("{method}(");
+                let called = router.match_indices(&call).any(|(i, _)| {
+                    !router[..i]
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                });
+                if called {
+                    routes.push((method.to_string(), path.clone()));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn registered_routes_parses_chained_method_routers() {
+        let routes = registered_routes();
+        assert!(routes.contains(&("delete".into(), "/api/config/prop".into())));
+        assert!(routes.contains(&("options".into(), "/api/config/prop".into())));
+        assert!(routes.contains(&("post".into(), "/api/cron/{id}/run".into())));
+        assert!(routes.contains(&("get".into(), "/_app/{path}".into())));
+        assert!(routes.len() > 100, "parsed only {} routes", routes.len());
+    }
+
+    #[cfg(feature = "schema-export")]
+    #[test]
+    fn route_table_covers_every_registered_route() {
+        let spec = build_spec();
+        let missing: Vec<String> = registered_routes()
+            .into_iter()
+            .filter(|(method, path)| spec["paths"][path.as_str()].get(method).is_none())
+            .map(|(method, path)| format!("{} {path}", method.to_uppercase()))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "routes registered in lib.rs without an OpenAPI entry in openapi.rs: {missing:?}"
+        );
+    }
+
+    #[cfg(feature = "schema-export")]
+    #[test]
+    fn spec_refs_resolve() {
+        fn walk(value: &serde_json::Value, spec: &serde_json::Value, bad: &mut Vec<String>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(target)) = map.get("$ref")
+                        && spec.pointer(target.trim_start_matches('#')).is_none()
+                    {
+                        bad.push(target.clone());
+                    }
+                    map.values().for_each(|v| walk(v, spec, bad));
+                }
+                serde_json::Value::Array(items) => items.iter().for_each(|v| walk(v, spec, bad)),
+                _ => {}
+            }
+        }
+        let spec = build_spec();
+        let mut bad = Vec::new();
+        walk(&spec, &spec, &mut bad);
+        assert!(bad.is_empty(), "dangling $refs: {bad:?}");
+    }
+
+    #[cfg(feature = "schema-export")]
+    #[test]
+    fn json_responses_have_typed_schemas() {
+        // Payloads whose shape the gateway does not own.
+        let free_form = [
+            "options /api/config",
+            "options /api/config/prop",
+            "get /api/openapi.json",
+            "post /api/webauthn/register/start",
+            "post /api/webauthn/auth/start",
+        ];
+        let spec = build_spec();
+        let mut untyped = Vec::new();
+        for (path, ops) in spec["paths"].as_object().unwrap() {
+            for (method, op) in ops.as_object().unwrap() {
+                let route = format!("{method} {path}");
+                for (status, response) in op["responses"].as_object().unwrap() {
+                    let Some(schema) = response.pointer("/content/application~1json/schema") else {
+                        continue;
+                    };
+                    let typed = ["$ref", "oneOf", "properties"]
+                        .iter()
+                        .any(|key| schema.get(key).is_some());
+                    if !typed && !free_form.contains(&route.as_str()) {
+                        untyped.push(format!("{route} {status}"));
+                    }
+                }
+            }
+        }
+        assert!(untyped.is_empty(), "untyped JSON responses: {untyped:?}");
+    }
+
+    #[cfg(feature = "schema-export")]
+    #[test]
+    fn path_parameters_match_templates() {
+        let spec = build_spec();
+        let params = spec
+            .pointer("/paths/~1api~1sops~1runs~1{run_id}~1approve/post/parameters")
+            .and_then(|v| v.as_array())
+            .unwrap();
+        assert_eq!(params[0]["name"], "run_id");
+        assert_eq!(params[0]["in"], "path");
+        let memory = spec
+            .pointer("/paths/~1api~1memory/get/parameters")
+            .and_then(|v| v.as_array())
+            .unwrap();
+        assert!(
+            memory
+                .iter()
+                .any(|p| p["name"] == "since" && p["in"] == "query")
+        );
+    }
 }
@@ -0,0 +1,543 @@
+//! Gateway user accounts and role-based access control.
+//!
+//! Every paired bearer token resolves to a [`Principal`]: the
+//! `[[gateway.users]]` entry the token is bound to, or an unbound token that
+//! stays admin until some admin account has a paired token and drops to
+//! viewer after that. The [`authorize`]
+//! middleware checks the principal against [`required_role`] for the route,
+//! stores it in the request extensions for handlers, and writes mutating
+//! calls and denials to the audit log. WebSocket sessions audit each frame
+//! that acts (chat messages, approval answers) through [`audit_action`].
+//!
+//! Once `[[gateway.users]]` are configured, guarded routes reject requests
+//! without a valid token. Before that, such requests pass through untouched
+//! so each handler keeps answering with its own 401 (or localhost check).
+//!
+//! Non-admin users are scoped: their chat sessions live under
+//! `gw_<user>~<id>`, their agent memory under the `user:<name>` session and
+//! their gateway events carry an `owner`, so they only see their own.
+//! Admins are unscoped and see everything.
+
+use super::AppState;
+use axum::{
+    Json,
+    extract::{Query, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use zeroclaw_config::pairing::PairingGuard;
+use zeroclaw_config::schema::{GatewayConfig, GatewayRole};
+use zeroclaw_runtime::security::{AuditEvent, AuditEventType};
+
+/// Separates the owner from the session id in scoped session keys.
+const SCOPE_SEPARATOR: char = '~';
+
+/// Scope shared by non-admin tokens not bound to a user. It contains the
+/// scope separator, which account names may not, so no user can own it.
+const UNBOUND_SCOPE: &str = "~unbound";
+
+/// The caller behind a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Account name; `None` for tokens not bound to a user.
+    pub user: Option<String>,
+    pub role: GatewayRole,
+}
+
+impl Principal {
+    /// Whether this principal holds `required` or a more privileged role.
+    pub fn has(&self, required: GatewayRole) -> bool {
+        // `GatewayRole` is ordered from most to least privileged.
+        self.role <= required
+    }
+
+    /// Owner that sessions and memory are scoped to; `None` for admins.
+    pub fn scope(&self) -> Option<&str> {
+        if self.role == GatewayRole::Admin {
+            None
+        } else {
+            Some(self.user.as_deref().unwrap_or(UNBOUND_SCOPE))
+        }
+    }
+
+    /// Name recorded in the audit log.
+    pub fn display_name(&self) -> &str {
+        self.user.as_deref().unwrap_or("unbound-token")
+    }
+}
+
+/// Resolve a bearer token to a principal. `None` means the token is not paired.
+pub fn resolve(gateway: &GatewayConfig, pairing: &PairingGuard, token: &str) -> Option<Principal> {
+    if !pairing.require_pairing() {
+        return Some(Principal {
+            user: None,
+            role: GatewayRole::Admin,
+        });
+    }
+    let hash = pairing.authenticate_and_hash(token)?;
+    if let Some(user) = gateway.users.iter().find(|u| u.tokens.contains(&hash)) {
+        return Some(Principal {
+            user: Some(user.name.clone()),
+            role: user.role,
+        });
+    }
+    // Unbound tokens keep admin rights until an admin account holds a token,
+    // so creating the first account cannot lock its creator out of pairing it.
+    let admin_bound = gateway
+        .users
+        .iter()
+        .any(|u| u.role == GatewayRole::Admin && !u.tokens.is_empty());
+    let role = if admin_bound {
+        GatewayRole::Viewer
+    } else {
+        GatewayRole::Admin
+    };
+    Some(Principal { user: None, role })
+}
+
+/// Role a route requires, or `None` for routes that authenticate on their
+/// own terms (health probes, pairing, webhooks, docs, static assets).
+pub fn required_role(method: &Method, path: &str) -> Option<GatewayRole> {
+    let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    let under = |prefix: &str| path == prefix || path.starts_with(&format!("{prefix}/"));
+
+    let guarded = ["/api", "/ws", "/v1", "/acp", "/admin"]
+        .iter()
+        .any(|p| under(p));
+    if !guarded
+        || matches!(path, "/api/pair" | "/api/openapi.json" | "/api/docs")
+        || under("/ws/nodes")
+        || under("/api/webauthn/auth")
+    {
+        return None;
+    }
+
+    if path == "/api/devices/me/capabilities" {
+        return Some(GatewayRole::Viewer);
+    }
+    if [
+        "/admin",
+        "/api/users",
+        "/api/devices",
+        "/api/outbox",
+        "/api/pairing",
+        "/api/plugins",
+        "/api/webauthn",
+    ]
+    .iter()
+    .any(|p| under(p))
+    {
+        return Some(GatewayRole::Admin);
+    }
+    if [
+        "/api/config",
+        "/api/onboard",
+        "/api/personality",
+        "/api/integrations",
+    ]
+    .iter()
+    .any(|p| under(p))
+    {
+        return Some(if read {
+            GatewayRole::Operator
+        } else {
+            GatewayRole::Admin
+        });
+    }
+    if path == "/ws/chat" || path == "/acp" {
+        return Some(GatewayRole::Operator);
+    }
+    Some(if read {
+        GatewayRole::Viewer
+    } else {
+        GatewayRole::Operator
+    })
+}
+
+/// Session-backend key for a gateway session id, scoped to the caller.
+pub fn session_key(principal: Option<&Principal>, id: &str) -> String {
+    match principal.and_then(Principal::scope) {
+        Some(owner) => format!("gw_{owner}{SCOPE_SEPARATOR}{id}"),
+        None => format!("gw_{id}"),
+    }
+}
+
+/// Inverse of [`session_key`]: the session id the caller knows a key by, or
+/// `None` when the key belongs to someone else.
+pub fn session_id_from_key<'a>(principal: Option<&Principal>, key: &'a str) -> Option<&'a str> {
+    let id = key.strip_prefix("gw_")?;
+    match principal.and_then(Principal::scope) {
+        Some(owner) => id
+            .strip_prefix(owner)
+            .and_then(|rest| rest.strip_prefix(SCOPE_SEPARATOR)),
+        None => Some(id),
+    }
+}
+
+/// Memory session for an agent turn: the caller's `user:<name>` scope, or
+/// `session_id` for unscoped callers.
+pub fn memory_session_id(principal: Option<&Principal>, session_id: String) -> String {
+    match principal.and_then(Principal::scope) {
+        Some(owner) => format!("user:{owner}"),
+        None => session_id,
+    }
+}
+
+/// Memory session filter for `/api/memory`; `None` lists everything.
+pub fn memory_scope(principal: Option<&Principal>) -> Option<String> {
+    principal
+        .and_then(Principal::scope)
+        .map(|owner| format!("user:{owner}"))
+}
+
+/// Whether a gateway event may be shown to `principal`. Non-admin callers
+/// only see events tagged with their own scope as `owner`; system events
+/// (cron, heartbeat, SOP runs, tool metrics) carry none.
+pub fn event_visible(principal: Option<&Principal>, event: &serde_json::Value) -> bool {
+    match principal.and_then(Principal::scope) {
+        Some(owner) => {
+            event.get("owner").and_then(serde_json::Value::as_str) == Some(owner)
+                // Gap notices carry no user data; everyone may have lost events.
+                || event.get("type").and_then(serde_json::Value::as_str)
+                    == Some("events_truncated")
+        }
+        None => true,
+    }
+}
+
+/// 403 unless `principal` is an admin. For actions that run arbitrary
+/// commands, such as shell cron jobs, whatever role the route requires.
+pub fn require_admin(
+    principal: Option<&Principal>,
+    what: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    match principal {
+        Some(p) if !p.has(GatewayRole::Admin) => Err((
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": format!("Forbidden — {what} requires the admin role"),
+                "role": p.role,
+            })),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Account names may only use letters, digits, `-` and `_`.
+pub fn is_valid_user_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// Enforce [`required_role`] for the route and record the principal.
+pub async fn authorize(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let Some(required) = required_role(&method, &path) else {
+        return next.run(request).await;
+    };
+
+    let query_token = Query::<TokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(q)| q.token);
+    let token = super::ws::extract_ws_token(request.headers(), query_token.as_deref())
+        .unwrap_or("")
+        .to_string();
+    let (principal, rbac_enabled) = {
+        let config = state.config.lock();
+        (
+            resolve(&config.gateway, &state.pairing, &token),
+            state.pairing.require_pairing() && !config.gateway.users.is_empty(),
+        )
+    };
+    let Some(principal) = principal else {
+        if !rbac_enabled {
+            return next.run(request).await;
+        }
+        audit(
+            &state,
+            None,
+            &format!("{method} {path}"),
+            false,
+            StatusCode::UNAUTHORIZED,
+        );
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "error": "Unauthorized — provide Authorization: Bearer <token>",
+            })),
+        )
+            .into_response();
+    };
+
+    if !principal.has(required) {
+        audit(
+            &state,
+            Some(&principal),
+            &format!("{method} {path}"),
+            false,
+            StatusCode::FORBIDDEN,
+        );
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": format!("Forbidden — this route requires the {required} role"),
+                "role": principal.role,
+            })),
+        )
+            .into_response();
+    }
+
+    let mutating = !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+    request.extensions_mut().insert(principal.clone());
+    let response = next.run(request).await;
+    if mutating {
+        audit(
+            &state,
+            Some(&principal),
+            &format!("{method} {path}"),
+            true,
+            response.status(),
+        );
+    }
+    response
+}
+
+/// Record an allowed action that did not arrive as its own HTTP request,
+/// e.g. `"WS /ws/chat message"`. No-op without a principal (RBAC off).
+pub fn audit_action(state: &AppState, principal: Option<&Principal>, action: &str) {
+    if let Some(principal) = principal {
+        audit(state, Some(principal), action, true, StatusCode::OK);
+    }
+}
+
+/// Write one gateway audit event. `principal` is `None` for requests that
+/// carried no valid token.
+fn audit(
+    state: &AppState,
+    principal: Option<&Principal>,
+    action: &str,
+    allowed: bool,
+    status: StatusCode,
+) {
+    let Some(ref logger) = state.audit_logger else {
+        return;
+    };
+    let path = action.split_whitespace().nth(1).unwrap_or_default();
+    let event_type = if !allowed {
+        AuditEventType::PolicyViolation
+    } else if path.starts_with("/api/config") || path.starts_with("/api/users") {
+        AuditEventType::ConfigChange
+    } else {
+        AuditEventType::ApiRequest
+    };
+    let mut event = AuditEvent::new(event_type)
+        .with_actor(
+            "gateway".into(),
+            principal.and_then(|p| p.user.clone()),
+            Some(
+                principal
+                    .map_or("anonymous", Principal::display_name)
+                    .to_string(),
+            ),
+        )
+        .with_action(
+            action.to_string(),
+            principal.map_or_else(|| "none".to_string(), |p| p.role.to_string()),
+            allowed,
+            allowed,
+        )
+        .with_result(
+            status.is_success(),
+            Some(i32::from(status.as_u16())),
+            0,
+            None,
+        );
+    event.security.policy_violation = !allowed;
+    if let Err(e) = logger.log(&event) {
+        tracing::warn!("Failed to write gateway audit event: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroclaw_config::schema::GatewayUserConfig;
+
+    fn principal(user: &str, role: GatewayRole) -> Principal {
+        Principal {
+            user: Some(user.into()),
+            role,
+        }
+    }
+
+    #[test]
+    fn tokens_resolve_to_bound_user_or_fallback_role() {
+        let token = "zc_alice";
+        let pairing = PairingGuard::new(true, &[token.into(), "zc_other".into()]);
+        let mut gateway = GatewayConfig::default();
+
+        // No users: every paired token keeps full access.
+        let p = resolve(&gateway, &pairing, "zc_other").unwrap();
+        assert_eq!(p.role, GatewayRole::Admin);
+        assert!(resolve(&gateway, &pairing, "zc_unknown").is_none());
+
+        gateway.users.push(GatewayUserConfig {
+            name: "alice".into(),
+            role: GatewayRole::Operator,
+            tokens: vec![PairingGuard::token_hash(token)],
+        });
+        assert_eq!(
+            resolve(&gateway, &pairing, token),
+            Some(principal("alice", GatewayRole::Operator))
+        );
+        // Without a paired admin account, unbound tokens stay admin.
+        let p = resolve(&gateway, &pairing, "zc_other").unwrap();
+        assert_eq!(p.role, GatewayRole::Admin);
+        gateway.users.push(GatewayUserConfig {
+            name: "root".into(),
+            role: GatewayRole::Admin,
+            tokens: Vec::new(),
+        });
+        let p = resolve(&gateway, &pairing, "zc_other").unwrap();
+        assert_eq!(p.role, GatewayRole::Admin);
+
+        // Once an admin account holds a token, unbound tokens drop to read-only.
+        gateway.users[1]
+            .tokens
+            .push(PairingGuard::token_hash("zc_root"));
+        let p = resolve(&gateway, &pairing, "zc_other").unwrap();
+        assert_eq!(p.role, GatewayRole::Viewer);
+        assert_eq!(p.user, None);
+    }
+
+    #[test]
+    fn route_policy_separates_admin_operator_and_viewer() {
+        let role = |m: Method, p: &str| required_role(&m, p);
+        assert_eq!(
+            role(Method::PUT, "/api/config/prop"),
+            Some(GatewayRole::Admin)
+        );
+        assert_eq!(
+            role(Method::GET, "/api/config/list"),
+            Some(GatewayRole::Operator)
+        );
+        assert_eq!(
+            role(Method::POST, "/admin/shutdown"),
+            Some(GatewayRole::Admin)
+        );
+        assert_eq!(role(Method::GET, "/api/users"), Some(GatewayRole::Admin));
+        // Queued messages carry recipients and content of every user.
+        assert_eq!(role(Method::GET, "/api/outbox"), Some(GatewayRole::Admin));
+        assert_eq!(role(Method::GET, "/ws/chat"), Some(GatewayRole::Operator));
+        assert_eq!(
+            role(Method::DELETE, "/api/memory/k"),
+            Some(GatewayRole::Operator)
+        );
+        assert_eq!(
+            role(Method::POST, "/v1/chat/completions"),
+            Some(GatewayRole::Operator)
+        );
+        assert_eq!(role(Method::GET, "/api/memory"), Some(GatewayRole::Viewer));
+        assert_eq!(role(Method::GET, "/api/me"), Some(GatewayRole::Viewer));
+        assert_eq!(role(Method::POST, "/pair"), None);
+        assert_eq!(role(Method::POST, "/api/pair"), None);
+        assert_eq!(role(Method::POST, "/webhook"), None);
+        assert_eq!(role(Method::GET, "/dashboard"), None);
+        // Prefix matching is per path segment.
+        assert_eq!(
+            role(Method::GET, "/api/usersettings"),
+            Some(GatewayRole::Viewer)
+        );
+    }
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        let operator = principal("bob", GatewayRole::Operator);
+        assert!(operator.has(GatewayRole::Viewer));
+        assert!(operator.has(GatewayRole::Operator));
+        assert!(!operator.has(GatewayRole::Admin));
+    }
+
+    #[test]
+    fn sessions_are_scoped_to_non_admin_owners() {
+        let alice = principal("alice", GatewayRole::Operator);
+        let admin = principal("root", GatewayRole::Admin);
+
+        assert_eq!(session_key(Some(&alice), "s1"), "gw_alice~s1");
+        assert_eq!(session_key(Some(&admin), "s1"), "gw_s1");
+        assert_eq!(session_key(None, "s1"), "gw_s1");
+
+        assert_eq!(session_id_from_key(Some(&alice), "gw_alice~s1"), Some("s1"));
+        assert_eq!(session_id_from_key(Some(&alice), "gw_bob~s1"), None);
+        assert_eq!(session_id_from_key(Some(&alice), "gw_s1"), None);
+        assert_eq!(
+            session_id_from_key(Some(&admin), "gw_alice~s1"),
+            Some("alice~s1")
+        );
+
+        assert_eq!(memory_session_id(Some(&alice), "s1".into()), "user:alice");
+        assert_eq!(memory_session_id(Some(&admin), "s1".into()), "s1");
+        assert_eq!(memory_scope(Some(&alice)).as_deref(), Some("user:alice"));
+        assert_eq!(memory_scope(Some(&admin)), None);
+    }
+
+    #[test]
+    fn unbound_tokens_never_share_a_named_users_scope() {
+        let unbound = Principal {
+            user: None,
+            role: GatewayRole::Operator,
+        };
+        let named = principal("unbound", GatewayRole::Operator);
+        assert!(!is_valid_user_name(UNBOUND_SCOPE));
+        assert_ne!(unbound.scope(), named.scope());
+
+        let key = session_key(Some(&named), "s1");
+        assert_eq!(session_id_from_key(Some(&unbound), &key), None);
+        let key = session_key(Some(&unbound), "s1");
+        assert_eq!(session_id_from_key(Some(&named), &key), None);
+        assert_ne!(memory_scope(Some(&unbound)), memory_scope(Some(&named)));
+    }
+
+    #[test]
+    fn events_and_admin_actions_follow_the_callers_role() {
+        let alice = principal("alice", GatewayRole::Operator);
+        let admin = principal("root", GatewayRole::Admin);
+        let own = serde_json::json!({ "type": "chunk", "owner": "alice" });
+        let other = serde_json::json!({ "type": "chunk", "owner": "bob" });
+        let system = serde_json::json!({ "type": "cron_result" });
+
+        assert!(event_visible(Some(&alice), &own));
+        assert!(!event_visible(Some(&alice), &other));
+        assert!(!event_visible(Some(&alice), &system));
+        assert!(event_visible(Some(&admin), &other));
+        assert!(event_visible(None, &system));
+
+        assert!(require_admin(Some(&admin), "x").is_ok());
+        assert!(require_admin(None, "x").is_ok());
+        let (status, _) = require_admin(Some(&alice), "x").unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn user_names_reject_separators() {
+        assert!(is_valid_user_name("alice_01"));
+        assert!(!is_valid_user_name(""));
+        assert!(!is_valid_user_name("a~b"));
+        assert!(!is_valid_user_name("a b"));
+    }
+}
@@ -1,49 +1,366 @@
 //! Server-Sent Events (SSE) stream for real-time event delivery.
 //!
-//! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
+//! Every event published on the AppState broadcast channel is stamped with a
+//! monotonically increasing ID by [`spawn_event_recorder`] and kept in a
+//! bounded [`EventBuffer`] (persisted to `events.db` in the workspace by a
+//! background writer thread). SSE frames carry that ID, so a reconnecting
+//! client that sends `Last-Event-ID` resumes exactly after the last event it
+//! saw. If the recorder falls behind the broadcast channel, it records an
+//! `events_truncated` notice in place of the dropped events. Both endpoints accept
+//! `type`, `session_id` and `channel` filters, and show non-admin users
+//! only their own events (see [`rbac::event_visible`]).
 
 use super::AppState;
+use super::rbac::{self, Principal};
+use anyhow::{Context, Result};
 use axum::{
-    Json,
-    extract::State,
+    Extension, Json,
+    extract::{Query, State},
     http::{HeaderMap, StatusCode, header},
     response::{
         IntoResponse,
         sse::{Event, KeepAlive, Sse},
     },
 };
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::convert::Infallible;
-use std::sync::{Arc, Mutex};
-use tokio_stream::StreamExt;
-use tokio_stream::wrappers::BroadcastStream;
+use std::path::Path;
+use std::sync::{Arc, Mutex, mpsc};
+use tokio::sync::broadcast::{self, error::RecvError};
 
-/// Thread-safe ring buffer that retains recent events for history replay.
+/// Number of events retained for replay.
+pub const EVENT_HISTORY_CAPACITY: usize = 500;
+
+/// Most events the writer thread commits in one transaction.
+const WRITE_BATCH_SIZE: usize = 64;
+
+/// Header a reconnecting `EventSource` sends with the last ID it received.
+const LAST_EVENT_ID: &str = "last-event-id";
+
+/// An event together with the ID it was recorded under.
+#[derive(Debug, Clone)]
+pub struct StoredEvent {
+    pub id: u64,
+    pub event: serde_json::Value,
+}
+
+struct Ring {
+    events: VecDeque<StoredEvent>,
+    next_id: u64,
+}
+
+/// Background thread that owns the SQLite connection and writes recorded
+/// events in batches, so [`EventBuffer::push`] never blocks on disk.
+struct EventWriter {
+    tx: Option<mpsc::Sender<(u64, String)>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl EventWriter {
+    fn spawn(conn: Connection, capacity: usize) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::Builder::new()
+            .name("sse-event-store".into())
+            .spawn(move || run_writer(conn, &rx, capacity as u64))
+            .context("Failed to start event store writer")?;
+        Ok(Self {
+            tx: Some(tx),
+            handle: Some(handle),
+        })
+    }
+
+    fn send(&self, id: u64, payload: String) {
+        if let Some(tx) = &self.tx
+            && tx.send((id, payload)).is_err()
+        {
+            tracing::warn!("SSE event store writer stopped; event {id} not persisted");
+        }
+    }
+}
+
+impl Drop for EventWriter {
+    /// Flush queued events before the buffer goes away.
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run_writer(mut conn: Connection, rx: &mpsc::Receiver<(u64, String)>, capacity: u64) {
+    while let Ok(first) = rx.recv() {
+        let mut batch = vec![first];
+        batch.extend(rx.try_iter().take(WRITE_BATCH_SIZE - 1));
+        if let Err(e) = write_batch(&mut conn, &batch, capacity) {
+            let (first, last) = (batch[0].0, batch[batch.len() - 1].0);
+            tracing::warn!("Failed to persist SSE events {first}..={last}: {e}");
+        }
+    }
+}
+
+fn write_batch(conn: &mut Connection, batch: &[(u64, String)], capacity: u64) -> Result<()> {
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare_cached("INSERT INTO events (id, payload) VALUES (?1, ?2)")?;
+        for (id, payload) in batch {
+            insert.execute(params![*id as i64, payload])?;
+        }
+    }
+    let last = batch.last().map_or(0, |(id, _)| *id);
+    tx.execute(
+        "DELETE FROM events WHERE id <= ?1",
+        params![last.saturating_sub(capacity) as i64],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Bounded ring of recent events with monotonically increasing IDs.
 pub struct EventBuffer {
-    inner: Mutex<VecDeque<serde_json::Value>>,
+    inner: Mutex<Ring>,
     capacity: usize,
+    live: broadcast::Sender<StoredEvent>,
+    writer: Option<EventWriter>,
 }
 
 impl EventBuffer {
+    /// In-memory buffer; IDs start at 1 and reset on restart.
     pub fn new(capacity: usize) -> Self {
+        Self::with_ring(
+            Ring {
+                events: VecDeque::with_capacity(capacity),
+                next_id: 1,
+            },
+            capacity,
+            None,
+        )
+    }
+
+    /// Buffer persisted to a SQLite file, so IDs keep increasing and the
+    /// retained events survive a gateway restart.
+    pub fn open(path: &Path, capacity: usize) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open event store: {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS events (
+                id INTEGER PRIMARY KEY,
+                payload TEXT NOT NULL
+            )",
+        )
+        .context("Failed to create events table")?;
+
+        let mut events = VecDeque::with_capacity(capacity);
+        {
+            let mut stmt = conn.prepare(
+                "SELECT id, payload FROM (SELECT id, payload FROM events ORDER BY id DESC LIMIT ?1)
+                 ORDER BY id ASC",
+            )?;
+            let rows = stmt.query_map(params![capacity as i64], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                let (id, payload) = row?;
+                if let Ok(event) = serde_json::from_str(&payload) {
+                    events.push_back(StoredEvent {
+                        id: id.unsigned_abs(),
+                        event,
+                    });
+                }
+            }
+        }
+        let max_id: Option<i64> =
+            conn.query_row("SELECT MAX(id) FROM events", [], |row| row.get(0))?;
+        let next_id = max_id.map_or(1, |id| id.unsigned_abs() + 1);
+
+        let writer = EventWriter::spawn(conn, capacity)?;
+        Ok(Self::with_ring(
+            Ring { events, next_id },
+            capacity,
+            Some(writer),
+        ))
+    }
+
+    fn with_ring(ring: Ring, capacity: usize, writer: Option<EventWriter>) -> Self {
+        let (live, _) = broadcast::channel(capacity.max(16));
         Self {
-            inner: Mutex::new(VecDeque::with_capacity(capacity)),
+            inner: Mutex::new(ring),
             capacity,
+            live,
+            writer,
         }
     }
 
-    /// Push an event into the buffer, evicting the oldest if at capacity.
-    pub fn push(&self, event: serde_json::Value) {
-        let mut buf = self.inner.lock().unwrap();
-        if buf.len() == self.capacity {
-            buf.pop_front();
+    /// Record an event, evicting the oldest if at capacity. Returns its ID.
+    /// Persistence is handed to the writer thread.
+    pub fn push(&self, event: serde_json::Value) -> u64 {
+        let payload = self.writer.as_ref().map(|_| event.to_string());
+        let mut ring = self.inner.lock().unwrap();
+        let id = ring.next_id;
+        ring.next_id += 1;
+
+        // Queued under the lock so the writer receives IDs in order.
+        if let (Some(writer), Some(payload)) = (&self.writer, payload) {
+            writer.send(id, payload);
+        }
+
+        if ring.events.len() == self.capacity {
+            ring.events.pop_front();
+        }
+        let stored = StoredEvent { id, event };
+        ring.events.push_back(stored.clone());
+        // Sent under the lock so subscribers see IDs in order.
+        let _ = self.live.send(stored);
+        id
+    }
+
+    /// Retained events with an ID greater than `after` (oldest first).
+    pub fn since(&self, after: u64) -> Vec<StoredEvent> {
+        self.inner
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|e| e.id > after)
+            .cloned()
+            .collect()
+    }
+
+    /// ID of the most recent event (0 when nothing has been recorded).
+    pub fn last_id(&self) -> u64 {
+        self.inner.lock().unwrap().next_id - 1
+    }
+
+    /// ID of the oldest retained event, if any.
+    pub fn oldest_id(&self) -> Option<u64> {
+        self.inner.lock().unwrap().events.front().map(|e| e.id)
+    }
+
+    /// Subscribe to events as they are recorded.
+    pub fn subscribe(&self) -> broadcast::Receiver<StoredEvent> {
+        self.live.subscribe()
+    }
+}
+
+/// Record every event published on `event_tx` into `buffer`.
+pub fn spawn_event_recorder(
+    event_tx: &broadcast::Sender<serde_json::Value>,
+    buffer: Arc<EventBuffer>,
+) -> tokio::task::JoinHandle<()> {
+    let mut rx = event_tx.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    buffer.push(event);
+                }
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!("SSE event recorder lagged; {n} event(s) were not recorded");
+                    // Take an ID in their place so resuming clients learn
+                    // that events are missing rather than silently skipping them.
+                    buffer.push(serde_json::json!({
+                        "type": "events_truncated",
+                        "dropped": n,
+                    }));
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Query parameters for `/api/events` and `/api/events/history`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct EventsQuery {
+    /// Comma-separated event types (e.g. `tool_call,agent_end`)
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    /// Only events carrying this `session_id`
+    pub session_id: Option<String>,
+    /// Only events carrying this `channel`
+    pub channel: Option<String>,
+    /// Resume point for clients that cannot send the `Last-Event-ID` header
+    pub last_event_id: Option<u64>,
+    /// History only: return events after this ID
+    pub after: Option<u64>,
+    /// History only: return at most this many of the newest matching events
+    pub limit: Option<usize>,
+}
+
+impl EventsQuery {
+    /// Whether `event` passes every filter that is set.
+    pub fn matches(&self, event: &serde_json::Value) -> bool {
+        let field = |name: &str| event.get(name).and_then(serde_json::Value::as_str);
+        if let Some(ref types) = self.event_type {
+            let Some(event_type) = field("type") else {
+                return false;
+            };
+            if !types.split(',').any(|t| t.trim() == event_type) {
+                return false;
+            }
+        }
+        if let Some(ref session_id) = self.session_id
+            && field("session_id") != Some(session_id.as_str())
+        {
+            return false;
+        }
+        if let Some(ref channel) = self.channel
+            && field("channel") != Some(channel.as_str())
+        {
+            return false;
         }
-        buf.push_back(event);
+        true
     }
+}
+
+/// Per-connection replay and live-delivery state.
+struct Cursor {
+    buffer: Arc<EventBuffer>,
+    rx: broadcast::Receiver<StoredEvent>,
+    filter: EventsQuery,
+    principal: Option<Principal>,
+    last_id: u64,
+    pending: VecDeque<StoredEvent>,
+    /// Oldest retained ID when the requested resume point was already evicted.
+    gap: Option<u64>,
+}
 
-    /// Return a snapshot of all buffered events (oldest first).
-    pub fn snapshot(&self) -> Vec<serde_json::Value> {
-        self.inner.lock().unwrap().iter().cloned().collect()
+impl Cursor {
+    async fn next(mut self) -> Option<(Result<Event, Infallible>, Self)> {
+        if let Some(oldest_id) = self.gap.take() {
+            let notice = serde_json::json!({
+                "type": "events_truncated",
+                "resume_from": self.last_id,
+                "oldest_id": oldest_id,
+            });
+            return Some((Ok(Event::default().data(notice.to_string())), self));
+        }
+        loop {
+            if let Some(stored) = self.pending.pop_front() {
+                if stored.id <= self.last_id {
+                    continue;
+                }
+                self.last_id = stored.id;
+                if !self.filter.matches(&stored.event)
+                    || !rbac::event_visible(self.principal.as_ref(), &stored.event)
+                {
+                    continue;
+                }
+                let event = Event::default()
+                    .id(stored.id.to_string())
+                    .data(stored.event.to_string());
+                return Some((Ok(event), self));
+            }
+            match self.rx.recv().await {
+                Ok(stored) => self.pending.push_back(stored),
+                // Fell behind the live channel: catch up from the ring.
+                Err(RecvError::Lagged(_)) => self.pending.extend(self.buffer.since(self.last_id)),
+                Err(RecvError::Closed) => return None,
+            }
+        }
     }
 }
 
@@ -51,6 +368,8 @@ impl EventBuffer {
 pub async fn handle_sse_events(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Query(filter): Query<EventsQuery>,
 ) -> impl IntoResponse {
     // Auth check
     if state.pairing.require_pairing() {
@@ -69,52 +388,114 @@ pub async fn handle_sse_events(
         }
     }
 
-    let rx = state.event_tx.subscribe();
-    let stream = BroadcastStream::new(rx).filter_map(
-        |result: Result<
-            serde_json::Value,
-            tokio_stream::wrappers::errors::BroadcastStreamRecvError,
-        >| {
-            match result {
-                Ok(value) => Some(Ok::<_, Infallible>(
-                    Event::default().data(value.to_string()),
-                )),
-                Err(_) => None, // Skip lagged messages
-            }
-        },
-    );
+    let resume_from = headers
+        .get(LAST_EVENT_ID)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or(filter.last_event_id);
+
+    // Subscribe before reading the ring so nothing falls in between.
+    let buffer = Arc::clone(&state.event_buffer);
+    let rx = buffer.subscribe();
+    let (last_id, pending, gap) = match resume_from {
+        Some(last_id) => {
+            let gap = buffer
+                .oldest_id()
+                .filter(|&oldest| oldest > last_id.saturating_add(1));
+            (last_id, buffer.since(last_id).into(), gap)
+        }
+        None => (buffer.last_id(), VecDeque::new(), None),
+    };
+    let cursor = Cursor {
+        buffer,
+        rx,
+        filter,
+        principal: principal.map(|Extension(p)| p),
+        last_id,
+        pending,
+        gap,
+    };
+    let stream = futures_util::stream::unfold(cursor, Cursor::next);
 
     Sse::new(stream)
         .keep_alive(KeepAlive::default())
         .into_response()
 }
 
-/// GET /api/events/history — return buffered recent events as JSON.
+/// A buffered event: the broadcast payload (`type`, `timestamp`, and the
+/// event's own fields) plus its replay `id`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct BufferedEvent {
+    pub id: u64,
+    #[serde(flatten)]
+    pub event: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<StoredEvent> for BufferedEvent {
+    fn from(stored: StoredEvent) -> Self {
+        let mut event = match stored.event {
+            serde_json::Value::Object(fields) => fields,
+            _ => serde_json::Map::new(),
+        };
+        // The replay id takes the place of any `id` the payload carried.
+        event.remove("id");
+        Self {
+            id: stored.id,
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct EventsHistoryResponse {
+    pub events: Vec<BufferedEvent>,
+    /// Id of the newest buffered event; resume the stream after it.
+    pub last_id: u64,
+}
+
+/// GET /api/events/history — buffered events as JSON, each with its `id`.
 pub async fn handle_events_history(
     State(state): State<AppState>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
+    Query(filter): Query<EventsQuery>,
 ) -> impl IntoResponse {
     if let Err(e) = super::api::require_auth(&state, &headers) {
         return e.into_response();
     }
-    let events = state.event_buffer.snapshot();
-    Json(serde_json::json!({ "events": events })).into_response()
+    let principal = principal.map(|Extension(p)| p);
+    let mut events: Vec<_> = state
+        .event_buffer
+        .since(filter.after.unwrap_or(0))
+        .into_iter()
+        .filter(|stored| {
+            filter.matches(&stored.event) && rbac::event_visible(principal.as_ref(), &stored.event)
+        })
+        .collect();
+    if let Some(limit) = filter.limit {
+        events.drain(..events.len().saturating_sub(limit));
+    }
+    Json(EventsHistoryResponse {
+        events: events.into_iter().map(BufferedEvent::from).collect(),
+        last_id: state.event_buffer.last_id(),
+    })
+    .into_response()
 }
 
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn zeroclaw_runtime::observability::Observer>,
     tx: tokio::sync::broadcast::Sender<serde_json::Value>,
-    buffer: Arc<EventBuffer>,
 }
 
 impl BroadcastObserver {
     pub fn new(
         inner: Box<dyn zeroclaw_runtime::observability::Observer>,
         tx: tokio::sync::broadcast::Sender<serde_json::Value>,
-        buffer: Arc<EventBuffer>,
     ) -> Self {
-        Self { inner, tx, buffer }
+        Self { inner, tx }
     }
 
     pub fn inner(&self) -> &dyn zeroclaw_runtime::observability::Observer {
@@ -189,7 +570,7 @@ impl zeroclaw_runtime::observability::Observer for BroadcastObserver {
             _ => return, // Skip events we don't broadcast
         };
 
-        self.buffer.push(json.clone());
+        // The event recorder stamps and buffers it for replay.
         let _ = self.tx.send(json);
     }
 
@@ -209,3 +590,125 @@ impl zeroclaw_runtime::observability::Observer for BroadcastObserver {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn ids_increase_and_ring_is_bounded() {
+        let buffer = EventBuffer::new(3);
+        let ids: Vec<_> = (0..5).map(|i| buffer.push(json!({ "n": i }))).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(buffer.last_id(), 5);
+        assert_eq!(buffer.oldest_id(), Some(3));
+        let after: Vec<_> = buffer.since(3).iter().map(|e| e.id).collect();
+        assert_eq!(after, vec![4, 5]);
+    }
+
+    #[test]
+    fn persisted_buffer_continues_ids_after_reopen() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("events.db");
+        {
+            let buffer = EventBuffer::open(&path, 2).unwrap();
+            for i in 0..3 {
+                buffer.push(json!({ "n": i }));
+            }
+        }
+        let buffer = EventBuffer::open(&path, 2).unwrap();
+        assert_eq!(buffer.last_id(), 3);
+        let kept: Vec<_> = buffer.since(0).iter().map(|e| e.id).collect();
+        assert_eq!(kept, vec![2, 3]);
+        assert_eq!(buffer.push(json!({ "n": 3 })), 4);
+    }
+
+    #[tokio::test]
+    async fn recorder_marks_lagged_events_with_a_truncation_notice() {
+        let (event_tx, _) = broadcast::channel(2);
+        let buffer = Arc::new(EventBuffer::new(16));
+        let recorder = spawn_event_recorder(&event_tx, Arc::clone(&buffer));
+        for i in 0..5 {
+            event_tx.send(json!({ "n": i })).unwrap();
+        }
+        drop(event_tx);
+        recorder.await.unwrap();
+
+        let recorded: Vec<_> = buffer.since(0).into_iter().map(|e| e.event).collect();
+        assert_eq!(
+            recorded,
+            vec![
+                json!({ "type": "events_truncated", "dropped": 3 }),
+                json!({ "n": 3 }),
+                json!({ "n": 4 }),
+            ]
+        );
+        let scoped = Principal {
+            user: Some("bob".into()),
+            role: zeroclaw_config::schema::GatewayRole::Viewer,
+        };
+        assert!(rbac::event_visible(Some(&scoped), &recorded[0]));
+    }
+
+    #[test]
+    fn filters_match_type_session_and_channel() {
+        let event = json!({ "type": "agent_end", "session_id": "s1", "channel": "ws" });
+        let query = |q: &str| -> EventsQuery {
+            Query::try_from_uri(&format!("/api/events?{q}").parse().unwrap())
+                .unwrap()
+                .0
+        };
+        assert!(query("").matches(&event));
+        assert!(query("type=tool_call,agent_end").matches(&event));
+        assert!(!query("type=tool_call").matches(&event));
+        assert!(query("session_id=s1&channel=ws").matches(&event));
+        assert!(!query("session_id=s2").matches(&event));
+        assert!(!query("channel=telegram").matches(&json!({ "type": "agent_end" })));
+    }
+
+    #[tokio::test]
+    async fn cursor_resumes_after_last_event_id_and_follows_live() {
+        let buffer = Arc::new(EventBuffer::new(10));
+        for i in 1..=3 {
+            buffer.push(json!({ "type": "tick", "n": i }));
+        }
+        let cursor = Cursor {
+            rx: buffer.subscribe(),
+            pending: buffer.since(2).into(),
+            buffer: Arc::clone(&buffer),
+            filter: EventsQuery::default(),
+            principal: None,
+            last_id: 2,
+            gap: None,
+        };
+        let (_, cursor) = cursor.next().await.unwrap();
+        assert_eq!(cursor.last_id, 3);
+
+        buffer.push(json!({ "type": "tick", "n": 4 }));
+        let (_, cursor) = cursor.next().await.unwrap();
+        assert_eq!(cursor.last_id, 4);
+    }
+
+    #[tokio::test]
+    async fn cursor_skips_events_owned_by_other_users() {
+        let buffer = Arc::new(EventBuffer::new(10));
+        buffer.push(json!({ "type": "agent_start", "owner": "bob" }));
+        buffer.push(json!({ "type": "cron_result" }));
+        buffer.push(json!({ "type": "agent_start", "owner": "alice" }));
+        let cursor = Cursor {
+            rx: buffer.subscribe(),
+            pending: buffer.since(0).into(),
+            buffer: Arc::clone(&buffer),
+            filter: EventsQuery::default(),
+            principal: Some(Principal {
+                user: Some("alice".into()),
+                role: zeroclaw_config::schema::GatewayRole::Operator,
+            }),
+            last_id: 0,
+            gap: None,
+        };
+        let (_, cursor) = cursor.next().await.unwrap();
+        assert_eq!(cursor.last_id, 3);
+    }
+}
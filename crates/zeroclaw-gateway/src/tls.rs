@@ -90,6 +90,15 @@ fn build_client_verifier(config: &GatewayClientAuthConfig) -> Result<Arc<dyn Cli
     }
 }
 
+/// Client certificate presented during the TLS handshake. The accept loop
+/// attaches it to every request on the connection so handlers (webhook
+/// sources with `mtls` verification) can check who is calling.
+#[derive(Debug, Clone)]
+pub struct PeerCertificate {
+    /// Lowercase hex SHA-256 of the DER certificate.
+    pub fingerprint: String,
+}
+
 /// Compute the SHA-256 fingerprint of a DER-encoded certificate.
 pub fn cert_sha256_fingerprint(cert_der: &[u8]) -> String {
     let mut hasher = Sha256::new();
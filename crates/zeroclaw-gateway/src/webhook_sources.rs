@@ -0,0 +1,750 @@
+//! Named signed-webhook sources (`POST /webhook/{source}`).
+//!
+//! Each `[gateway.webhooks.<name>]` entry picks how deliveries are verified
+//! (HMAC with optional signed timestamp, shared secret, mTLS, or none), how
+//! the payload becomes a prompt, and where it goes: an agent turn, an SOP
+//! run (which gets the raw body), or the routines engine. Replays are refused
+//! through the gateway's idempotency store, keyed by the source's delivery-id
+//! header or, failing that, the signature itself. Keys of deliveries that
+//! failed on our side are released so the sender's retry goes through.
+
+use super::tls::PeerCertificate;
+use super::{AppState, RATE_LIMIT_WINDOW_SECS, client_key_from_request};
+use axum::{
+    Extension,
+    body::Bytes,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::net::SocketAddr;
+use std::time::Duration;
+use zeroclaw_config::schema::{WebhookSourceConfig, WebhookTarget, WebhookVerification};
+use zeroclaw_runtime::routines::{RoutineAction, RoutineDispatchResult, RoutineEvent};
+use zeroclaw_runtime::security::pairing::constant_time_eq;
+use zeroclaw_runtime::sop::engine::now_iso8601;
+use zeroclaw_runtime::sop::{SopEvent, SopRunStatus, SopTriggerSource};
+use zeroclaw_runtime::util::{json_path_text, render_template};
+
+/// Check a delivery against the source's verification scheme. On success
+/// returns the matched signature, which doubles as the replay key when the
+/// sender supplies no delivery id.
+pub fn verify(
+    source: &WebhookSourceConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+    peer: Option<&PeerCertificate>,
+    now_unix: i64,
+) -> Result<Option<String>, String> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let secret = source.secret.as_deref().unwrap_or_default();
+
+    match source.verification {
+        WebhookVerification::None => Ok(None),
+        WebhookVerification::SharedSecret => match header(&source.signature_header) {
+            Some(value) if !secret.is_empty() && constant_time_eq(value, secret) => Ok(None),
+            _ => Err(format!("invalid or missing {}", source.signature_header)),
+        },
+        WebhookVerification::Mtls => {
+            let Some(peer) = peer else {
+                return Err("no client certificate presented".into());
+            };
+            let allowed = source.client_cert_fingerprints.is_empty()
+                || source
+                    .client_cert_fingerprints
+                    .iter()
+                    .any(|fp| fp.replace(':', "").eq_ignore_ascii_case(&peer.fingerprint));
+            if allowed {
+                Ok(None)
+            } else {
+                Err("client certificate is not allowed for this source".into())
+            }
+        }
+        WebhookVerification::Hmac => {
+            if secret.is_empty() {
+                return Err("source has no signing secret".into());
+            }
+            let signature = header(&source.signature_header)
+                .ok_or_else(|| format!("missing {}", source.signature_header))?;
+
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .map_err(|_| "invalid signing secret".to_string())?;
+            if let Some(ts_header) = &source.timestamp_header {
+                let raw = if ts_header.eq_ignore_ascii_case(&source.signature_header) {
+                    signature
+                        .split(',')
+                        .find_map(|part| part.trim().strip_prefix("t="))
+                } else {
+                    header(ts_header)
+                };
+                let timestamp: i64 = raw
+                    .and_then(|t| t.trim().parse().ok())
+                    .ok_or_else(|| format!("missing or invalid timestamp in {ts_header}"))?;
+                if now_unix.abs_diff(timestamp) > source.timestamp_tolerance_secs {
+                    return Err("timestamp outside the allowed tolerance".into());
+                }
+                mac.update(format!("{timestamp}.").as_bytes());
+            }
+            mac.update(body);
+            let expected = hex::encode(mac.finalize().into_bytes());
+
+            signature
+                .split(',')
+                .filter_map(|part| part.trim().strip_prefix(source.signature_prefix.as_str()))
+                .find(|candidate| constant_time_eq(&candidate.to_ascii_lowercase(), &expected))
+                .map(|matched| Some(matched.to_string()))
+                .ok_or_else(|| "signature mismatch".to_string())
+        }
+    }
+}
+
+/// Render the source's prompt template. `{{payload}}` is the raw body,
+/// `{{source}}` the source name, anything else a dotted path into the JSON
+/// body (`{{pull_request.title}}`, `{{commits.0.id}}`). Unknown fields render
+/// empty.
+pub fn render_prompt(
+    template: Option<&str>,
+    source: &str,
+    body: &str,
+    json: Option<&serde_json::Value>,
+) -> String {
+    let Some(template) = template else {
+        return format!("Webhook delivery from '{source}':\n\n{body}");
+    };
+    render_template(template, |key| match key {
+        "payload" => Some(body.to_string()),
+        "source" => Some(source.to_string()),
+        path => json_path_text(json?, path),
+    })
+}
+
+fn reject(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<serde_json::Value>) {
+    (status, Json(serde_json::json!({ "error": message.into() })))
+}
+
+/// Reply to a delivery routed to an agent session.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SourceAgentReply {
+    /// Always `ok`.
+    pub status: String,
+    pub source: String,
+    pub session_id: String,
+    pub response: String,
+}
+
+/// An SOP run a delivery started.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SopRunRef {
+    pub run_id: String,
+    pub sop_name: String,
+    pub status: SopRunStatus,
+}
+
+/// Reply to a delivery routed to SOPs.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SourceSopRuns {
+    /// `accepted` when runs started, `ignored` when no SOP matched.
+    pub status: String,
+    pub source: String,
+    pub runs: Vec<SopRunRef>,
+}
+
+/// What a fired routine did.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum RoutineDetail {
+    Runs {
+        runs: Vec<SopRunRef>,
+    },
+    CronJob {
+        cron_job: String,
+    },
+    /// Always `started`; the agent turn runs in the background.
+    Agent {
+        agent: String,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/// One routines-engine result, tagged by `outcome`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RoutineOutcome {
+    NoMatch,
+    Pending {
+        routine: String,
+    },
+    Disabled {
+        routine: String,
+    },
+    OutsideCalendar {
+        routine: String,
+    },
+    Cooldown {
+        routine: String,
+        remaining_secs: u64,
+    },
+    Fired {
+        routine: String,
+        /// Events that fired the routine.
+        events: usize,
+        detail: RoutineDetail,
+    },
+}
+
+/// Reply to a delivery routed to the routines engine.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct SourceRoutineResults {
+    /// Always `ok`.
+    pub status: String,
+    pub source: String,
+    pub routines: Vec<RoutineOutcome>,
+}
+
+/// POST /webhook/{source} — verified delivery from a configured source
+pub async fn handle_source_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    peer_cert: Option<Extension<PeerCertificate>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/webhook/{name} rate limit exceeded");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "error": "Too many webhook requests. Please retry later.",
+                "retry_after": RATE_LIMIT_WINDOW_SECS,
+            })),
+        )
+            .into_response();
+    }
+
+    let source = state.config.lock().gateway.webhooks.get(&name).cloned();
+    let Some(source) = source.filter(|s| s.enabled) else {
+        return reject(
+            StatusCode::NOT_FOUND,
+            format!("Unknown webhook source '{name}'"),
+        )
+        .into_response();
+    };
+
+    let peer_cert = peer_cert.as_ref().map(|Extension(cert)| cert);
+    let now = chrono::Utc::now().timestamp();
+    let signature = match verify(&source, &headers, &body, peer_cert, now) {
+        Ok(signature) => signature,
+        Err(reason) => {
+            tracing::warn!("Webhook source '{name}': rejected delivery — {reason}");
+            return reject(StatusCode::UNAUTHORIZED, reason).into_response();
+        }
+    };
+
+    // ── Replay protection ──
+    let delivery_id = headers
+        .get(source.idempotency_header.as_str())
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .or(signature);
+    let idempotency_key = delivery_id.map(|key| format!("webhook:{name}:{key}"));
+    if let Some(key) = &idempotency_key
+        && !state.idempotency_store.record_if_new(key)
+    {
+        tracing::info!("Webhook source '{name}': duplicate delivery ignored ({key})");
+        return Json(super::WebhookDuplicate {
+            status: "duplicate".into(),
+            idempotent: true,
+            message: "Delivery already processed".into(),
+        })
+        .into_response();
+    }
+
+    let text = String::from_utf8_lossy(&body);
+    let json = serde_json::from_slice::<serde_json::Value>(&body).ok();
+    let prompt = render_prompt(
+        source.prompt_template.as_deref(),
+        &name,
+        &text,
+        json.as_ref(),
+    );
+    let topic = format!("/webhook/{name}");
+
+    let _ = state.event_tx.send(serde_json::json!({
+        "type": "webhook",
+        "source": name,
+        "target": source.target,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    }));
+
+    let response = match source.target {
+        WebhookTarget::Agent => {
+            let session_id = source
+                .session_id
+                .clone()
+                .unwrap_or_else(|| format!("webhook-{name}"));
+            match super::run_gateway_chat_with_tools(&state, &prompt, Some(&session_id)).await {
+                Ok(outcome) => Json(SourceAgentReply {
+                    status: "ok".into(),
+                    source: name,
+                    session_id,
+                    response: outcome.response,
+                })
+                .into_response(),
+                Err(e) if super::is_needs_onboarding_err(&e) => (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(serde_json::json!({ "error": "needs_onboarding", "url": "/onboard" })),
+                )
+                    .into_response(),
+                Err(e) => {
+                    let sanitized = zeroclaw_providers::sanitize_api_error(&e.to_string());
+                    tracing::error!("Webhook source '{name}': agent turn failed: {sanitized}");
+                    reject(StatusCode::INTERNAL_SERVER_ERROR, "LLM request failed").into_response()
+                }
+            }
+        }
+        WebhookTarget::Sop => {
+            let event = SopEvent {
+                source: SopTriggerSource::Webhook,
+                topic: Some(topic),
+                payload: Some(text.into_owned()),
+                timestamp: now_iso8601(),
+            };
+            match start_sops(&state, &name, source.sop.as_deref(), event).await {
+                Ok(runs) => (
+                    if runs.is_empty() {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::ACCEPTED
+                    },
+                    Json(SourceSopRuns {
+                        status: if runs.is_empty() {
+                            "ignored"
+                        } else {
+                            "accepted"
+                        }
+                        .into(),
+                        source: name,
+                        runs,
+                    }),
+                )
+                    .into_response(),
+                Err(e) => e.into_response(),
+            }
+        }
+        WebhookTarget::Routine => {
+            let event = RoutineEvent {
+                source: "webhook".into(),
+                topic,
+                payload: Some(prompt),
+                timestamp: now_iso8601(),
+            };
+            let results = state.routines.lock().dispatch(&event);
+            let mut fired = Vec::new();
+            for result in results {
+                fired.push(run_routine_result(&state, &name, result).await);
+            }
+            Json(SourceRoutineResults {
+                status: "ok".into(),
+                source: name,
+                routines: fired,
+            })
+            .into_response()
+        }
+    };
+
+    // A delivery that failed on our side is not a duplicate when the sender
+    // retries it.
+    if response.status().is_server_error()
+        && let Some(key) = &idempotency_key
+    {
+        state.idempotency_store.forget(key);
+    }
+    response
+}
+
+/// Start the configured SOP, or every SOP whose webhook trigger matches.
+async fn start_sops(
+    state: &AppState,
+    source: &str,
+    sop: Option<&str>,
+    event: SopEvent,
+) -> Result<Vec<SopRunRef>, (StatusCode, Json<serde_json::Value>)> {
+    let api = super::api_sops::sop_api(state)?;
+    let names: Vec<String> = match sop {
+        Some(name) => vec![name.to_string()],
+        None => {
+            let engine = api.engine.lock().map_err(|e| {
+                reject(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("SOP engine lock poisoned: {e}"),
+                )
+            })?;
+            engine
+                .match_trigger(&event)
+                .into_iter()
+                .map(|s| s.name.clone())
+                .collect()
+        }
+    };
+    let actor = format!("webhook:{source}");
+    let mut runs = Vec::new();
+    for name in names {
+        let (run, _) =
+            super::api_sops::start_run(state, api, &name, event.clone(), Some(&actor)).await?;
+        runs.push(SopRunRef {
+            run_id: run.run_id,
+            sop_name: run.sop_name,
+            status: run.status,
+        });
+    }
+    Ok(runs)
+}
+
+/// How often absence windows are checked.
+const ROUTINE_TICK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The only event source the gateway feeds into the routines engine.
+const ROUTINE_EVENT_SOURCE: &str = "webhook";
+
+/// `routine/source` pairs for patterns no event will ever reach: only
+/// webhook deliveries are dispatched to the routines engine.
+fn unfed_routine_patterns(routines: &[zeroclaw_runtime::routines::Routine]) -> Vec<String> {
+    routines
+        .iter()
+        .flat_map(|r| {
+            r.patterns
+                .iter()
+                .filter(|p| p.source != ROUTINE_EVENT_SOURCE)
+                .map(move |p| format!("{}/{}", r.name, p.source))
+        })
+        .collect()
+}
+
+/// Periodically fire routines whose absence windows have elapsed, until the
+/// gateway shuts down. Routines live in the gateway: without it running,
+/// neither webhook events nor absence windows are evaluated.
+pub(crate) fn spawn_routine_timer(state: AppState) {
+    let unfed = unfed_routine_patterns(state.routines.lock().routines());
+    if !unfed.is_empty() {
+        tracing::warn!(
+            patterns = ?unfed,
+            "Routine patterns on sources other than `webhook` never match; \
+             only webhook deliveries reach the routines engine"
+        );
+    }
+    let mut shutdown = state.shutdown_tx.subscribe();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ROUTINE_TICK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+            let results = state.routines.lock().tick(chrono::Utc::now());
+            for result in results {
+                let outcome = run_routine_result(&state, "routine-timer", result).await;
+                tracing::info!(?outcome, "routine absence window fired");
+            }
+        }
+    });
+}
+
+/// Carry out one routines-engine result and describe what happened.
+async fn run_routine_result(
+    state: &AppState,
+    source: &str,
+    result: RoutineDispatchResult,
+) -> RoutineOutcome {
+    match result {
+        RoutineDispatchResult::NoMatch => RoutineOutcome::NoMatch,
+        RoutineDispatchResult::Pending { routine_name } => RoutineOutcome::Pending {
+            routine: routine_name,
+        },
+        RoutineDispatchResult::Disabled { routine_name } => RoutineOutcome::Disabled {
+            routine: routine_name,
+        },
+        RoutineDispatchResult::OutsideCalendar { routine_name } => {
+            RoutineOutcome::OutsideCalendar {
+                routine: routine_name,
+            }
+        }
+        RoutineDispatchResult::Cooldown {
+            routine_name,
+            remaining_secs,
+        } => RoutineOutcome::Cooldown {
+            routine: routine_name,
+            remaining_secs,
+        },
+        RoutineDispatchResult::Fired {
+            routine_name,
+            action,
+            events,
+        } => {
+            let detail = match action {
+                RoutineAction::Sop { name } => {
+                    let sop_event = bundle_sop_event(&events);
+                    match start_sops(state, source, Some(&name), sop_event).await {
+                        Ok(runs) => RoutineDetail::Runs { runs },
+                        Err((_, Json(error))) => RoutineDetail::Error {
+                            error: error["error"].as_str().unwrap_or_default().to_string(),
+                        },
+                    }
+                }
+                RoutineAction::CronJob { job_name } => {
+                    let config = state.config.lock().clone();
+                    let job = zeroclaw_runtime::cron::list_jobs(&config)
+                        .ok()
+                        .and_then(|jobs| {
+                            jobs.into_iter()
+                                .find(|j| j.name.as_deref() == Some(job_name.as_str()))
+                        });
+                    match job {
+                        Some(job) => {
+                            let state = state.clone();
+                            let job_id = job.id.clone();
+                            tokio::spawn(async move {
+                                super::api::run_cron_job_now(&state, &config, &job).await;
+                            });
+                            RoutineDetail::CronJob { cron_job: job_id }
+                        }
+                        None => RoutineDetail::Error {
+                            error: format!("Cron job '{job_name}' not found"),
+                        },
+                    }
+                }
+                RoutineAction::Agent(agent) => {
+                    let config = state.config.lock().clone();
+                    let routine = routine_name.clone();
+                    let events = events.clone();
+                    tokio::spawn(async move {
+                        match zeroclaw_runtime::routines::run_agent_action(
+                            &config, &routine, &agent, &events,
+                        )
+                        .await
+                        {
+                            Ok(_) => {
+                                tracing::info!(routine = %routine, "routine agent turn finished")
+                            }
+                            Err(e) => {
+                                tracing::warn!(routine = %routine, "routine agent turn failed: {e:#}");
+                            }
+                        }
+                    });
+                    RoutineDetail::Agent {
+                        agent: "started".into(),
+                    }
+                }
+                RoutineAction::Message { .. } | RoutineAction::Shell { .. } => {
+                    RoutineDetail::Error {
+                        error:
+                            "message and shell routine actions are not run for webhook deliveries"
+                                .into(),
+                    }
+                }
+            };
+            RoutineOutcome::Fired {
+                routine: routine_name,
+                events: events.len(),
+                detail,
+            }
+        }
+    }
+}
+
+/// Turn the events that fired a routine into the SOP trigger event: a single
+/// event passes its payload through, a window bundle becomes a JSON array.
+fn bundle_sop_event(events: &[RoutineEvent]) -> SopEvent {
+    let last = events.last();
+    let payload = match events {
+        [event] => event.payload.clone(),
+        _ => Some(serde_json::to_string(events).unwrap_or_default()),
+    };
+    SopEvent {
+        source: SopTriggerSource::Webhook,
+        topic: last.map(|e| e.topic.clone()),
+        payload,
+        timestamp: last.map_or_else(now_iso8601, |e| e.timestamp.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn sign(secret: &str, content: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(content.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn github_source() -> WebhookSourceConfig {
+        WebhookSourceConfig {
+            secret: Some("gh-secret".into()),
+            signature_header: "X-Hub-Signature-256".into(),
+            signature_prefix: "sha256=".into(),
+            idempotency_header: "X-GitHub-Delivery".into(),
+            ..WebhookSourceConfig::default()
+        }
+    }
+
+    #[test]
+    fn routine_bundle_becomes_json_array_payload() {
+        let event = |payload: &str| RoutineEvent {
+            source: "webhook".into(),
+            topic: "/auth/failed".into(),
+            payload: Some(payload.into()),
+            timestamp: "2026-03-24T00:00:00Z".into(),
+        };
+
+        let single = bundle_sop_event(&[event("one")]);
+        assert_eq!(single.payload.as_deref(), Some("one"));
+
+        let bundle = bundle_sop_event(&[event("one"), event("two")]);
+        assert_eq!(bundle.topic.as_deref(), Some("/auth/failed"));
+        let payloads: Vec<RoutineEvent> =
+            serde_json::from_str(bundle.payload.as_deref().unwrap()).unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[1].payload.as_deref(), Some("two"));
+    }
+
+    #[test]
+    fn patterns_on_sources_other_than_webhook_are_reported() {
+        let manifest: zeroclaw_runtime::routines::engine::RoutinesManifest = toml::from_str(
+            r#"
+            [[routines]]
+            name = "deploys"
+            patterns = [
+                { source = "webhook", pattern = "/webhook/deploy" },
+                { source = "mqtt", pattern = "sensors/#" },
+            ]
+            action = { type = "sop", name = "check" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            unfed_routine_patterns(&manifest.routines),
+            vec!["deploys/mqtt"]
+        );
+    }
+
+    #[test]
+    fn hmac_accepts_github_style_signature() {
+        let body = br#"{"action":"opened"}"#;
+        let mut headers = HeaderMap::new();
+        let sig = format!(
+            "sha256={}",
+            sign("gh-secret", std::str::from_utf8(body).unwrap())
+        );
+        headers.insert("X-Hub-Signature-256", HeaderValue::from_str(&sig).unwrap());
+        let matched = verify(&github_source(), &headers, body, None, 0).unwrap();
+        assert!(matched.is_some());
+
+        headers.insert(
+            "X-Hub-Signature-256",
+            HeaderValue::from_static("sha256=deadbeef"),
+        );
+        assert!(verify(&github_source(), &headers, body, None, 0).is_err());
+        assert!(verify(&github_source(), &HeaderMap::new(), body, None, 0).is_err());
+    }
+
+    #[test]
+    fn hmac_checks_stripe_style_timestamp() {
+        let source = WebhookSourceConfig {
+            secret: Some("whsec".into()),
+            signature_header: "Stripe-Signature".into(),
+            signature_prefix: "v1=".into(),
+            timestamp_header: Some("Stripe-Signature".into()),
+            timestamp_tolerance_secs: 300,
+            ..WebhookSourceConfig::default()
+        };
+        let body = br#"{"id":"evt_1"}"#;
+        let ts = 1_700_000_000_i64;
+        let sig = sign(
+            "whsec",
+            &format!("{ts}.{}", std::str::from_utf8(body).unwrap()),
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Stripe-Signature",
+            HeaderValue::from_str(&format!("t={ts},v1=stale,v1={sig}")).unwrap(),
+        );
+
+        assert!(verify(&source, &headers, body, None, ts + 10).is_ok());
+        let err = verify(&source, &headers, body, None, ts + 301).unwrap_err();
+        assert!(err.contains("tolerance"));
+    }
+
+    #[test]
+    fn shared_secret_and_mtls_verification() {
+        let shared = WebhookSourceConfig {
+            verification: WebhookVerification::SharedSecret,
+            secret: Some("token-1".into()),
+            signature_header: "X-Token".into(),
+            ..WebhookSourceConfig::default()
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Token", HeaderValue::from_static("token-1"));
+        assert!(verify(&shared, &headers, b"", None, 0).is_ok());
+        headers.insert("X-Token", HeaderValue::from_static("token-2"));
+        assert!(verify(&shared, &headers, b"", None, 0).is_err());
+
+        let mtls = WebhookSourceConfig {
+            verification: WebhookVerification::Mtls,
+            client_cert_fingerprints: vec!["AB:CD".into()],
+            ..WebhookSourceConfig::default()
+        };
+        let cert = PeerCertificate {
+            fingerprint: "abcd".into(),
+        };
+        let other = PeerCertificate {
+            fingerprint: "ffff".into(),
+        };
+        assert!(verify(&mtls, &HeaderMap::new(), b"", Some(&cert), 0).is_ok());
+        assert!(verify(&mtls, &HeaderMap::new(), b"", Some(&other), 0).is_err());
+        assert!(verify(&mtls, &HeaderMap::new(), b"", None, 0).is_err());
+    }
+
+    #[test]
+    fn render_prompt_fills_json_fields() {
+        let json = serde_json::json!({
+            "pull_request": { "title": "Fix it", "number": 7 },
+            "commits": [{ "id": "abc" }],
+        });
+        let prompt = render_prompt(
+            Some(
+                "[{{source}}] #{{pull_request.number}} {{ pull_request.title }} {{commits.0.id}}{{missing}}",
+            ),
+            "github",
+            "{}",
+            Some(&json),
+        );
+        assert_eq!(prompt, "[github] #7 Fix it abc");
+
+        let fallback = render_prompt(None, "github", "raw", None);
+        assert!(fallback.contains("github") && fallback.ends_with("raw"));
+    }
+}
@@ -56,8 +56,10 @@
 //! - `token` — bearer auth token (alternative to Authorization header)
 
 use super::AppState;
+use super::rbac::{self, Principal};
 use crate::ws_approval::{PendingApprovals, WsApprovalChannel, new_pending_approvals};
 use axum::{
+    Extension,
     extract::{
         Query, State, WebSocketUpgrade,
         ws::{Message, WebSocket},
@@ -109,6 +111,7 @@ const WS_PROTOCOL: &str = "zeroclaw.v1";
 const BEARER_SUBPROTO_PREFIX: &str = "bearer.";
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct WsQuery {
     pub token: Option<String>,
     pub session_id: Option<String>,
@@ -130,7 +133,10 @@ pub struct WsQuery {
 ///
 /// Browsers cannot set custom headers on `new WebSocket(url)`, so the query
 /// parameter and subprotocol paths are required for browser-based clients.
-fn extract_ws_token<'a>(headers: &'a HeaderMap, query_token: Option<&'a str>) -> Option<&'a str> {
+pub(crate) fn extract_ws_token<'a>(
+    headers: &'a HeaderMap,
+    query_token: Option<&'a str>,
+) -> Option<&'a str> {
     // 1. Authorization header
     if let Some(t) = headers
         .get(header::AUTHORIZATION)
@@ -171,6 +177,7 @@ pub async fn handle_ws_chat(
     State(state): State<AppState>,
     Query(params): Query<WsQuery>,
     headers: HeaderMap,
+    principal: Option<Extension<Principal>>,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Auth: check header, subprotocol, then query param (precedence order)
@@ -199,16 +206,24 @@ pub async fn handle_ws_chat(
     let session_id = params.session_id;
     let session_name = params.name;
     let session_cwd = params.cwd.or(params.workspace_dir);
-    ws.on_upgrade(move |socket| handle_socket(socket, state, session_id, session_name, session_cwd))
-        .into_response()
+    let principal = principal.map(|Extension(p)| p);
+    ws.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            state,
+            principal,
+            session_id,
+            session_name,
+            session_cwd,
+        )
+    })
+    .into_response()
 }
 
-/// Gateway session key prefix to avoid collisions with channel sessions.
-const GW_SESSION_PREFIX: &str = "gw_";
-
 async fn handle_socket(
     socket: WebSocket,
     state: AppState,
+    principal: Option<Principal>,
     session_id: Option<String>,
     session_name: Option<String>,
     session_cwd: Option<String>,
@@ -217,8 +232,10 @@ async fn handle_socket(
 
     // Resolve session ID: use provided or generate a new UUID
     let session_id = session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-    let session_key = format!("{GW_SESSION_PREFIX}{session_id}");
-    let mut memory_session_id = session_id.clone();
+    // Sessions and memory of non-admin users are scoped to their account.
+    let session_key = rbac::session_key(principal.as_ref(), &session_id);
+    let mut memory_session_id = rbac::memory_session_id(principal.as_ref(), session_id.clone());
+    let scoped = principal.as_ref().and_then(Principal::scope).is_some();
 
     // Hydrate session metadata from persistence (if available). Agent
     // construction is deferred until after the optional `connect` frame so the
@@ -283,7 +300,9 @@ async fn handle_socket(
                             cwd = ?cp.cwd,
                             "WebSocket connect params received"
                         );
-                        if let Some(sid) = &cp.session_id {
+                        if let Some(sid) = &cp.session_id
+                            && !scoped
+                        {
                             memory_session_id = sid.clone();
                             debug!(
                                 session_id = sid,
@@ -392,6 +411,7 @@ async fn handle_socket(
             if parsed["type"].as_str() == Some("message") {
                 let content = parsed["content"].as_str().unwrap_or("").to_string();
                 if !content.is_empty() {
+                    rbac::audit_action(&state, principal.as_ref(), "WS /ws/chat message");
                     // Persist user message
                     if let Some(ref backend) = state.session_backend {
                         let user_msg = zeroclaw_providers::ChatMessage::user(&content);
@@ -405,7 +425,9 @@ async fn handle_socket(
                         &mut approval_event_rx,
                         &pending_approvals,
                         &content,
+                        &session_id,
                         &session_key,
+                        principal.as_ref(),
                     )
                     .await;
                 }
@@ -499,6 +521,7 @@ async fn handle_socket(
                         continue;
                     }
                     if let Some(tx) = pending_approvals.lock().remove(request_id) {
+                        rbac::audit_action(&state, principal.as_ref(), &format!("WS /ws/chat approval_response {decision_str}"));
                         let _ = tx.send(decision.expect("checked above"));
                     } else {
                         debug!(%request_id, "approval_response with no matching pending request");
@@ -543,6 +566,8 @@ async fn handle_socket(
                     }
                 };
 
+                rbac::audit_action(&state, principal.as_ref(), "WS /ws/chat message");
+
                 // Persist user message
                 if let Some(ref backend) = state.session_backend {
                     let user_msg = zeroclaw_providers::ChatMessage::user(&content);
@@ -557,14 +582,18 @@ async fn handle_socket(
                     &mut approval_event_rx,
                     &pending_approvals,
                     &content,
+                    &session_id,
                     &session_key,
+                    principal.as_ref(),
                 )
                 .await;
             }
 
             // ── Broadcast event (cron/heartbeat results) ──────────────
             event = broadcast_rx.recv() => {
-                if let Ok(event) = event {
+                if let Ok(event) = event
+                    && rbac::event_visible(principal.as_ref(), &event)
+                {
                     let _ = sender.send(Message::Text(event.to_string().into())).await;
                 }
             }
@@ -604,7 +633,7 @@ async fn handle_socket(
     }
 }
 
-fn resolve_session_cwd(
+pub(crate) fn resolve_session_cwd(
     requested_cwd: Option<&str>,
     default_workspace: &Path,
 ) -> anyhow::Result<PathBuf> {
@@ -641,11 +670,16 @@ async fn process_chat_message(
     approval_event_rx: &mut tokio::sync::mpsc::Receiver<zeroclaw_api::agent::TurnEvent>,
     pending_approvals: &PendingApprovals,
     content: &str,
+    session_id: &str,
     session_key: &str,
+    principal: Option<&Principal>,
 ) {
     use futures_util::StreamExt as _;
     use zeroclaw_runtime::agent::TurnEvent;
 
+    // Tags gateway events so SSE only shows them to their owner.
+    let owner = principal.and_then(Principal::scope);
+
     let provider_label = state
         .config
         .lock()
@@ -659,6 +693,9 @@ async fn process_chat_message(
         "type": "agent_start",
         "provider": provider_label,
         "model": state.model,
+        "session_id": session_id,
+        "owner": owner,
+        "channel": "ws",
     }));
 
     // Set session state to running
@@ -752,6 +789,11 @@ async fn process_chat_message(
                         continue;
                     }
                     if let Some(tx) = pending_approvals.lock().remove(request_id) {
+                        rbac::audit_action(
+                            state,
+                            principal,
+                            &format!("WS /ws/chat approval_response {}", parsed["decision"].as_str().unwrap_or_default()),
+                        );
                         let _ = tx.send(decision.expect("checked above"));
                     } else {
                         debug!(%request_id, "approval_response with no matching pending request (mid-turn)");
@@ -890,6 +932,9 @@ async fn process_chat_message(
             "type": "agent_end",
             "provider": provider_label,
             "model": state.model,
+            "session_id": session_id,
+            "owner": owner,
+            "channel": "ws",
         }));
 
         // Trace the cancelled turn so the doctor / replay tool sees it
@@ -929,11 +974,13 @@ async fn process_chat_message(
                 let model = state.model.clone();
                 let user_msg = content.to_string();
                 let assistant_resp = response.clone();
+                let memory_scope = rbac::memory_scope(principal);
                 tokio::spawn(async move {
                     if let Err(e) = zeroclaw_memory::consolidation::consolidate_turn(
                         provider.as_ref(),
                         &model,
                         mem.as_ref(),
+                        memory_scope.as_deref(),
                         &user_msg,
                         &assistant_resp,
                     )
@@ -988,6 +1035,9 @@ async fn process_chat_message(
                 "type": "agent_end",
                 "provider": provider_label,
                 "model": state.model,
+                "session_id": session_id,
+                "owner": owner,
+                "channel": "ws",
             }));
 
             // Append a runtime-trace.jsonl record so a `zeroclaw doctor`
@@ -1042,6 +1092,9 @@ async fn process_chat_message(
                 "type": "error",
                 "component": "ws_chat",
                 "message": sanitized,
+                "session_id": session_id,
+                "owner": owner,
+                "channel": "ws",
             }));
 
             // Trace the failed turn so the doctor / replay tool sees the
@@ -1064,7 +1117,7 @@ async fn process_chat_message(
 /// Record token usage for the just-completed turn against the gateway's
 /// cost tracker, returning the computed cost in USD (or `None` when no
 /// tracker is configured or no usage was reported).
-fn record_turn_cost(
+pub(crate) fn record_turn_cost(
     state: &AppState,
     provider_name: &str,
     model: &str,
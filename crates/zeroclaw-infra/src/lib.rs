@@ -4,6 +4,8 @@
 
 pub mod debounce;
 pub mod session_backend;
+#[cfg(feature = "session-postgres")]
+pub mod session_postgres;
 pub mod session_sqlite;
 pub mod session_store;
 pub mod stall_watchdog;
@@ -13,21 +15,35 @@ use std::sync::Arc;
 
 use crate::session_backend::SessionBackend;
 
+/// Where the `"postgres"` session backend connects: the `db_url`, `schema`
+/// and `connect_timeout_secs` fields of `[storage.provider.config]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostgresTarget<'a> {
+    pub db_url: Option<&'a str>,
+    pub schema: &'a str,
+    pub connect_timeout_secs: Option<u64>,
+}
+
 /// Construct the configured session-persistence backend.
 ///
 /// `backend` is the value of `[channels].session_backend` from config:
 /// `"sqlite"` (default) opens `{workspace}/sessions/sessions.db`, `"jsonl"`
-/// opens `{workspace}/sessions/*.jsonl`. Unknown values fall back to
-/// SQLite with a warning so a typo in config never silently disables
-/// persistence. The `Arc<dyn SessionBackend>` return type keeps every
-/// call site (channel orchestrator, runtime tools) reading from the
-/// same store.
+/// opens `{workspace}/sessions/*.jsonl`, and `"postgres"` connects to
+/// `[storage.provider.config].db_url` (requires the `session-postgres`
+/// feature). Unknown values fall back to SQLite with a warning so a typo in
+/// config never silently disables persistence. The `Arc<dyn SessionBackend>`
+/// return type keeps every call site (channel orchestrator, runtime tools)
+/// reading from the same store.
 ///
 /// Errors propagate from the underlying backend constructor (typically
-/// filesystem permissions on the sessions directory).
+/// filesystem permissions on the sessions directory, or an unreachable
+/// database). A `"postgres"` backend that cannot be opened is an error
+/// rather than a SQLite fallback: replicas quietly writing to local files
+/// would split the shared history the operator asked for.
 pub fn make_session_backend(
     workspace_dir: &Path,
     backend: &str,
+    postgres: PostgresTarget<'_>,
 ) -> std::io::Result<Arc<dyn SessionBackend>> {
     match backend {
         "jsonl" => {
@@ -35,16 +51,43 @@ pub fn make_session_backend(
             Ok(Arc::new(store))
         }
         "sqlite" => Ok(Arc::new(open_sqlite_with_jsonl_import(workspace_dir)?)),
+        "postgres" => open_postgres(postgres),
         other => {
             tracing::warn!(
                 "Unknown session_backend '{other}'; falling back to sqlite. \
-                 Valid values: 'sqlite' (default), 'jsonl'."
+                 Valid values: 'sqlite' (default), 'jsonl', 'postgres'."
             );
             Ok(Arc::new(open_sqlite_with_jsonl_import(workspace_dir)?))
         }
     }
 }
 
+#[cfg(feature = "session-postgres")]
+fn open_postgres(target: PostgresTarget<'_>) -> std::io::Result<Arc<dyn SessionBackend>> {
+    let db_url = target.db_url.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "session backend 'postgres' requires [storage.provider.config].db_url",
+        )
+    })?;
+    let backend = session_postgres::PostgresSessionBackend::new(
+        db_url,
+        target.schema,
+        target.connect_timeout_secs,
+    )
+    .map_err(|e| std::io::Error::other(format!("{e:#}")))?;
+    Ok(Arc::new(backend))
+}
+
+#[cfg(not(feature = "session-postgres"))]
+fn open_postgres(_target: PostgresTarget<'_>) -> std::io::Result<Arc<dyn SessionBackend>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "session backend 'postgres' requested but this build was compiled without \
+         `session-postgres`; rebuild with `--features session-postgres`",
+    ))
+}
+
 /// Open the SQLite backend and, on first open, import any pre-existing
 /// `sessions/*.jsonl` files left over from the legacy JSONL store. Renames
 /// the imported files to `*.jsonl.migrated` so re-runs are no-ops; preserves
@@ -83,10 +126,14 @@ mod tests {
         ChatMessage::user(content)
     }
 
+    fn storage() -> PostgresTarget<'static> {
+        PostgresTarget::default()
+    }
+
     #[test]
     fn make_session_backend_jsonl_round_trips_through_session_store() {
         let tmp = TempDir::new().unwrap();
-        let backend = make_session_backend(tmp.path(), "jsonl").unwrap();
+        let backend = make_session_backend(tmp.path(), "jsonl", storage()).unwrap();
         backend.append("k1", &user_msg("hello-jsonl")).unwrap();
         let loaded = backend.load("k1");
         assert_eq!(loaded.len(), 1);
@@ -98,7 +145,7 @@ mod tests {
     #[test]
     fn make_session_backend_sqlite_round_trips_through_sqlite_db() {
         let tmp = TempDir::new().unwrap();
-        let backend = make_session_backend(tmp.path(), "sqlite").unwrap();
+        let backend = make_session_backend(tmp.path(), "sqlite", storage()).unwrap();
         backend.append("k1", &user_msg("hello-sqlite")).unwrap();
         let loaded = backend.load("k1");
         assert_eq!(loaded.len(), 1);
@@ -111,7 +158,7 @@ mod tests {
     #[test]
     fn make_session_backend_unknown_value_falls_back_to_sqlite() {
         let tmp = TempDir::new().unwrap();
-        let backend = make_session_backend(tmp.path(), "totally-not-a-backend").unwrap();
+        let backend = make_session_backend(tmp.path(), "totally-not-a-backend", storage()).unwrap();
         backend.append("k1", &user_msg("hello-fallback")).unwrap();
         let db = tmp.path().join("sessions").join("sessions.db");
         assert!(
@@ -128,10 +175,10 @@ mod tests {
         // operator can roll back.
         let tmp = TempDir::new().unwrap();
         {
-            let jsonl = make_session_backend(tmp.path(), "jsonl").unwrap();
+            let jsonl = make_session_backend(tmp.path(), "jsonl", storage()).unwrap();
             jsonl.append("legacy", &user_msg("from-jsonl")).unwrap();
         }
-        let sqlite = make_session_backend(tmp.path(), "sqlite").unwrap();
+        let sqlite = make_session_backend(tmp.path(), "sqlite", storage()).unwrap();
         let loaded = sqlite.load("legacy");
         assert_eq!(
             loaded.len(),
@@ -147,4 +194,15 @@ mod tests {
             ".jsonl.migrated rollback file should remain"
         );
     }
+
+    #[test]
+    fn make_session_backend_postgres_without_db_url_errors_instead_of_falling_back() {
+        let tmp = TempDir::new().unwrap();
+        let result = make_session_backend(tmp.path(), "postgres", storage());
+        assert!(result.is_err(), "postgres must not silently fall back");
+        assert!(
+            !tmp.path().join("sessions").join("sessions.db").exists(),
+            "no local sqlite db may be created for a postgres backend"
+        );
+    }
 }
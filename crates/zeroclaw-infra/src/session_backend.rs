@@ -3,6 +3,10 @@
 //! Backends store per-sender conversation histories. The trait is intentionally
 //! minimal — load, append, remove_last, clear_messages, list — so that JSONL
 //! and SQLite (and future backends) share a common interface.
+//!
+//! Sessions can be forked: `fork_session` copies a prefix of one session into
+//! a new key and records the parent and fork point on the child, so callers
+//! can rebuild the conversation tree from `list_sessions_with_metadata`.
 
 use chrono::{DateTime, Utc};
 use zeroclaw_api::provider::ChatMessage;
@@ -20,6 +24,20 @@ pub struct SessionMetadata {
     pub last_activity: DateTime<Utc>,
     /// Total number of messages in the session.
     pub message_count: usize,
+    /// Session this one was forked from, if any.
+    pub parent_key: Option<String>,
+    /// Number of leading messages shared with the parent at fork time.
+    pub fork_point: Option<usize>,
+}
+
+/// A persisted message together with the time it was stored, for backends
+/// that record one.
+#[derive(Debug, Clone)]
+pub struct StoredMessage {
+    pub message: ChatMessage,
+    /// When the message was appended. `None` for backends without
+    /// per-message timestamps (JSONL).
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 /// Query parameters for listing sessions.
@@ -38,6 +56,19 @@ pub trait SessionBackend: Send + Sync {
     /// Load all messages for a session. Returns empty vec if session doesn't exist.
     fn load(&self, session_key: &str) -> Vec<ChatMessage>;
 
+    /// Load all messages with their storage timestamps. The default wraps
+    /// [`SessionBackend::load`] without timestamps; backends that store them
+    /// override this.
+    fn load_stored(&self, session_key: &str) -> Vec<StoredMessage> {
+        self.load(session_key)
+            .into_iter()
+            .map(|message| StoredMessage {
+                message,
+                created_at: None,
+            })
+            .collect()
+    }
+
     /// Append a single message to a session.
     fn append(&self, session_key: &str, message: &ChatMessage) -> std::io::Result<()>;
 
@@ -74,6 +105,8 @@ pub trait SessionBackend: Send + Sync {
                     created_at: Utc::now(),
                     last_activity: Utc::now(),
                     message_count: messages.len(),
+                    parent_key: None,
+                    fork_point: None,
                 }
             })
             .collect()
@@ -140,9 +173,46 @@ pub trait SessionBackend: Send + Sync {
             created_at: Utc::now(),
             last_activity: Utc::now(),
             message_count: messages.len(),
+            parent_key: None,
+            fork_point: None,
         })
     }
 
+    /// Fork `source_key` at message `at`: copy its first `at` messages into a
+    /// new session `target_key` and record the lineage on the child. Returns
+    /// the number of messages copied.
+    ///
+    /// Fails with `NotFound` for an empty or unknown source, `InvalidInput`
+    /// when `at` is outside `1..=len`, and `AlreadyExists` when the target
+    /// session exists. Backends without lineage storage return `Unsupported`.
+    fn fork_session(
+        &self,
+        _source_key: &str,
+        _target_key: &str,
+        _at: usize,
+    ) -> std::io::Result<usize> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "session forking is not supported by this backend",
+        ))
+    }
+
+    /// Import a whole session from another backend or an exported
+    /// transcript, keeping its name, timestamps and lineage where the backend
+    /// can store them. Messages without a timestamp are stamped with
+    /// `meta.last_activity`. Returns `false` without writing when `meta.key`
+    /// already exists, so re-running a migration is a no-op.
+    fn import_session(
+        &self,
+        _meta: &SessionMetadata,
+        _messages: &[StoredMessage],
+    ) -> std::io::Result<bool> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "session import is not supported by this backend",
+        ))
+    }
+
     /// Set the session state (e.g. "idle", "running", "error").
     /// `turn_id` identifies the current turn (set when running, cleared on idle).
     fn set_session_state(
@@ -170,6 +240,24 @@ pub trait SessionBackend: Send + Sync {
     }
 }
 
+/// Validate a fork point against the source session's length. Shared by the
+/// backends so every one reports the same errors for the same inputs.
+pub fn check_fork_point(source_key: &str, source_len: usize, at: usize) -> std::io::Result<()> {
+    if source_len == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("session '{source_key}' not found"),
+        ));
+    }
+    if at == 0 || at > source_len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("fork point {at} is outside 1..={source_len}"),
+        ));
+    }
+    Ok(())
+}
+
 /// Session state information.
 #[derive(Debug, Clone)]
 pub struct SessionState {
@@ -193,11 +281,32 @@ mod tests {
             created_at: Utc::now(),
             last_activity: Utc::now(),
             message_count: 5,
+            parent_key: None,
+            fork_point: None,
         };
         assert_eq!(meta.key, "test");
         assert_eq!(meta.message_count, 5);
     }
 
+    #[test]
+    fn check_fork_point_bounds() {
+        use std::io::ErrorKind;
+        assert!(check_fork_point("s", 3, 1).is_ok());
+        assert!(check_fork_point("s", 3, 3).is_ok());
+        assert_eq!(
+            check_fork_point("s", 0, 1).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            check_fork_point("s", 3, 0).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            check_fork_point("s", 3, 4).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+
     #[test]
     fn session_query_defaults() {
         let q = SessionQuery::default();
@@ -4,7 +4,9 @@
 //! Provides full-text search via FTS5 and automatic TTL-based cleanup.
 //! Designed as the default backend, replacing JSONL for new installations.
 
-use crate::session_backend::{SessionBackend, SessionMetadata, SessionQuery, SessionState};
+use crate::session_backend::{
+    SessionBackend, SessionMetadata, SessionQuery, SessionState, StoredMessage, check_fork_point,
+};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use parking_lot::Mutex;
@@ -12,6 +14,33 @@ use rusqlite::{Connection, params};
 use std::path::{Path, PathBuf};
 use zeroclaw_api::provider::ChatMessage;
 
+/// Columns read by [`metadata_from_row`], in order.
+const METADATA_COLUMNS: &str =
+    "session_key, created_at, last_activity, message_count, name, parent_key, fork_point";
+
+fn parse_timestamp(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn metadata_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionMetadata> {
+    let created: String = row.get(1)?;
+    let activity: String = row.get(2)?;
+    let count: i64 = row.get(3)?;
+    let fork_point: Option<i64> = row.get(6)?;
+    Ok(SessionMetadata {
+        key: row.get(0)?,
+        name: row.get(4)?,
+        created_at: parse_timestamp(&created),
+        last_activity: parse_timestamp(&activity),
+        message_count: count as usize,
+        parent_key: row.get(5)?,
+        fork_point: fork_point.map(|n| n as usize),
+    })
+}
+
 /// SQLite-backed session store with FTS5 and WAL mode.
 pub struct SqliteSessionBackend {
     conn: Mutex<Connection>,
@@ -108,6 +137,25 @@ impl SqliteSessionBackend {
             );
         }
 
+        // Migration: add fork lineage columns
+        let has_parent: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('session_metadata') WHERE name = 'parent_key'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        if !has_parent {
+            let _ = conn.execute(
+                "ALTER TABLE session_metadata ADD COLUMN parent_key TEXT",
+                [],
+            );
+            let _ = conn.execute(
+                "ALTER TABLE session_metadata ADD COLUMN fork_point INTEGER",
+                [],
+            );
+        }
+
         Ok(Self {
             conn: Mutex::new(conn),
             db_path,
@@ -160,12 +208,38 @@ impl SqliteSessionBackend {
             if count > 0 {
                 let migrated_path = path.with_extension("jsonl.migrated");
                 let _ = std::fs::rename(&path, &migrated_path);
+                self.migrate_lineage(&sessions_dir.join(format!("{key}.lineage.json")), key);
                 migrated += 1;
             }
         }
 
         Ok(migrated)
     }
+
+    /// Carry a JSONL fork's lineage sidecar over to the metadata row.
+    fn migrate_lineage(&self, sidecar: &Path, session_key: &str) {
+        let Ok(raw) = std::fs::read_to_string(sidecar) else {
+            return;
+        };
+        let Ok(lineage) = serde_json::from_str::<serde_json::Value>(&raw) else {
+            return;
+        };
+        let parent = lineage["parent_key"].as_str();
+        let fork_point = lineage["fork_point"].as_i64();
+        if parent.is_none() {
+            return;
+        }
+        let conn = self.conn.lock();
+        if conn
+            .execute(
+                "UPDATE session_metadata SET parent_key = ?1, fork_point = ?2 WHERE session_key = ?3",
+                params![parent, fork_point, session_key],
+            )
+            .is_ok()
+        {
+            let _ = std::fs::rename(sidecar, sidecar.with_extension("json.migrated"));
+        }
+    }
 }
 
 impl SessionBackend for SqliteSessionBackend {
@@ -191,6 +265,34 @@ impl SessionBackend for SqliteSessionBackend {
         rows.filter_map(|r| r.ok()).collect()
     }
 
+    fn load_stored(&self, session_key: &str) -> Vec<StoredMessage> {
+        let conn = self.conn.lock();
+        let mut stmt = match conn.prepare(
+            "SELECT role, content, created_at FROM sessions WHERE session_key = ?1 ORDER BY id ASC",
+        ) {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        let rows = match stmt.query_map(params![session_key], |row| {
+            let created: String = row.get(2)?;
+            Ok(StoredMessage {
+                message: ChatMessage {
+                    role: row.get(0)?,
+                    content: row.get(1)?,
+                },
+                created_at: DateTime::parse_from_rfc3339(&created)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc)),
+            })
+        }) {
+            Ok(r) => r,
+            Err(_) => return Vec::new(),
+        };
+
+        rows.filter_map(|r| r.ok()).collect()
+    }
+
     fn append(&self, session_key: &str, message: &ChatMessage) -> std::io::Result<()> {
         let conn = self.conn.lock();
         let now = Utc::now().to_rfc3339();
@@ -302,37 +404,15 @@ impl SessionBackend for SqliteSessionBackend {
 
     fn list_sessions_with_metadata(&self) -> Vec<SessionMetadata> {
         let conn = self.conn.lock();
-        let mut stmt = match conn.prepare(
-            "SELECT session_key, created_at, last_activity, message_count, name
-             FROM session_metadata ORDER BY last_activity DESC",
-        ) {
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT {METADATA_COLUMNS}
+             FROM session_metadata ORDER BY last_activity DESC"
+        )) {
             Ok(s) => s,
             Err(_) => return Vec::new(),
         };
 
-        let rows = match stmt.query_map([], |row| {
-            let key: String = row.get(0)?;
-            let created_str: String = row.get(1)?;
-            let activity_str: String = row.get(2)?;
-            let count: i64 = row.get(3)?;
-            let name: Option<String> = row.get(4)?;
-
-            let created = DateTime::parse_from_rfc3339(&created_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            let activity = DateTime::parse_from_rfc3339(&activity_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-
-            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-            Ok(SessionMetadata {
-                key,
-                name,
-                created_at: created,
-                last_activity: activity,
-                message_count: count as usize,
-            })
-        }) {
+        let rows = match stmt.query_map([], metadata_from_row) {
             Ok(r) => r,
             Err(_) => return Vec::new(),
         };
@@ -446,36 +526,124 @@ impl SessionBackend for SqliteSessionBackend {
     fn get_session_metadata(&self, session_key: &str) -> Option<SessionMetadata> {
         let conn = self.conn.lock();
         conn.query_row(
-            "SELECT session_key, created_at, last_activity, message_count, name
-             FROM session_metadata WHERE session_key = ?1",
+            &format!(
+                "SELECT {METADATA_COLUMNS}
+             FROM session_metadata WHERE session_key = ?1"
+            ),
             params![session_key],
-            |row| {
-                let key: String = row.get(0)?;
-                let created_str: String = row.get(1)?;
-                let activity_str: String = row.get(2)?;
-                let count: i64 = row.get(3)?;
-                let name: Option<String> = row.get(4)?;
-
-                let created = DateTime::parse_from_rfc3339(&created_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now());
-                let activity = DateTime::parse_from_rfc3339(&activity_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now());
-
-                #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-                Ok(SessionMetadata {
-                    key,
-                    name,
-                    created_at: created,
-                    last_activity: activity,
-                    message_count: count as usize,
-                })
-            },
+            metadata_from_row,
         )
         .ok()
     }
 
+    fn fork_session(
+        &self,
+        source_key: &str,
+        target_key: &str,
+        at: usize,
+    ) -> std::io::Result<usize> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(std::io::Error::other)?;
+
+        let source_len: i64 = tx
+            .query_row(
+                "SELECT COUNT(*) FROM sessions WHERE session_key = ?1",
+                params![source_key],
+                |row| row.get(0),
+            )
+            .map_err(std::io::Error::other)?;
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        check_fork_point(source_key, source_len as usize, at)?;
+
+        let target_exists: bool = tx
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM session_metadata WHERE session_key = ?1",
+                params![target_key],
+                |row| row.get(0),
+            )
+            .map_err(std::io::Error::other)?;
+        if target_exists {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("session '{target_key}' already exists"),
+            ));
+        }
+
+        // Copied rows keep their original timestamps; the FTS insert trigger
+        // indexes them under the new key.
+        #[allow(clippy::cast_possible_wrap)]
+        let at_i64 = at as i64;
+        tx.execute(
+            "INSERT INTO sessions (session_key, role, content, created_at)
+             SELECT ?1, role, content, created_at FROM sessions
+             WHERE session_key = ?2 ORDER BY id ASC LIMIT ?3",
+            params![target_key, source_key, at_i64],
+        )
+        .map_err(std::io::Error::other)?;
+
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO session_metadata
+                (session_key, created_at, last_activity, message_count, parent_key, fork_point)
+             VALUES (?1, ?2, ?2, ?3, ?4, ?3)",
+            params![target_key, now, at_i64, source_key],
+        )
+        .map_err(std::io::Error::other)?;
+
+        tx.commit().map_err(std::io::Error::other)?;
+        Ok(at)
+    }
+
+    fn import_session(
+        &self,
+        meta: &SessionMetadata,
+        messages: &[StoredMessage],
+    ) -> std::io::Result<bool> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(std::io::Error::other)?;
+
+        #[allow(clippy::cast_possible_wrap)]
+        let count = messages.len() as i64;
+        #[allow(clippy::cast_possible_wrap)]
+        let fork_point = meta.fork_point.map(|n| n as i64);
+        let inserted = tx
+            .execute(
+                "INSERT OR IGNORE INTO session_metadata
+                    (session_key, created_at, last_activity, message_count, name, parent_key, fork_point)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    meta.key,
+                    meta.created_at.to_rfc3339(),
+                    meta.last_activity.to_rfc3339(),
+                    count,
+                    meta.name,
+                    meta.parent_key,
+                    fork_point
+                ],
+            )
+            .map_err(std::io::Error::other)?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        for stored in messages {
+            tx.execute(
+                "INSERT INTO sessions (session_key, role, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    meta.key,
+                    stored.message.role,
+                    stored.message.content,
+                    stored.created_at.unwrap_or(meta.last_activity).to_rfc3339()
+                ],
+            )
+            .map_err(std::io::Error::other)?;
+        }
+
+        tx.commit().map_err(std::io::Error::other)?;
+        Ok(true)
+    }
+
     fn set_session_state(
         &self,
         session_key: &str,
@@ -528,35 +696,15 @@ impl SessionBackend for SqliteSessionBackend {
 
     fn list_running_sessions(&self) -> Vec<SessionMetadata> {
         let conn = self.conn.lock();
-        let mut stmt = match conn.prepare(
-            "SELECT session_key, created_at, last_activity, message_count, name
-             FROM session_metadata WHERE state = 'running' ORDER BY turn_started_at DESC",
-        ) {
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT {METADATA_COLUMNS}
+             FROM session_metadata WHERE state = 'running' ORDER BY turn_started_at DESC"
+        )) {
             Ok(s) => s,
             Err(_) => return Vec::new(),
         };
 
-        let rows = match stmt.query_map([], |row| {
-            let key: String = row.get(0)?;
-            let created_str: String = row.get(1)?;
-            let activity_str: String = row.get(2)?;
-            let count: i64 = row.get(3)?;
-            let name: Option<String> = row.get(4)?;
-            let created = DateTime::parse_from_rfc3339(&created_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            let activity = DateTime::parse_from_rfc3339(&activity_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-            Ok(SessionMetadata {
-                key,
-                name,
-                created_at: created,
-                last_activity: activity,
-                message_count: count as usize,
-            })
-        }) {
+        let rows = match stmt.query_map([], metadata_from_row) {
             Ok(r) => r,
             Err(_) => return Vec::new(),
         };
@@ -568,37 +716,17 @@ impl SessionBackend for SqliteSessionBackend {
         let conn = self.conn.lock();
         #[allow(clippy::cast_possible_wrap)]
         let cutoff = (Utc::now() - chrono::Duration::seconds(threshold_secs as i64)).to_rfc3339();
-        let mut stmt = match conn.prepare(
-            "SELECT session_key, created_at, last_activity, message_count, name
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT {METADATA_COLUMNS}
              FROM session_metadata
              WHERE state = 'running' AND turn_started_at < ?1
-             ORDER BY turn_started_at ASC",
-        ) {
+             ORDER BY turn_started_at ASC"
+        )) {
             Ok(s) => s,
             Err(_) => return Vec::new(),
         };
 
-        let rows = match stmt.query_map(params![cutoff], |row| {
-            let key: String = row.get(0)?;
-            let created_str: String = row.get(1)?;
-            let activity_str: String = row.get(2)?;
-            let count: i64 = row.get(3)?;
-            let name: Option<String> = row.get(4)?;
-            let created = DateTime::parse_from_rfc3339(&created_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            let activity = DateTime::parse_from_rfc3339(&activity_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-            Ok(SessionMetadata {
-                key,
-                name,
-                created_at: created,
-                last_activity: activity,
-                message_count: count as usize,
-            })
-        }) {
+        let rows = match stmt.query_map(params![cutoff], metadata_from_row) {
             Ok(r) => r,
             Err(_) => return Vec::new(),
         };
@@ -639,32 +767,9 @@ impl SessionBackend for SqliteSessionBackend {
         };
 
         // Look up metadata for matched sessions
+        let sql = format!("SELECT {METADATA_COLUMNS} FROM session_metadata WHERE session_key = ?1");
         keys.iter()
-            .filter_map(|key| {
-                conn.query_row(
-                    "SELECT created_at, last_activity, message_count, name FROM session_metadata WHERE session_key = ?1",
-                    params![key],
-                    |row| {
-                        let created_str: String = row.get(0)?;
-                        let activity_str: String = row.get(1)?;
-                        let count: i64 = row.get(2)?;
-                        let name: Option<String> = row.get(3)?;
-                        Ok(SessionMetadata {
-                            key: key.clone(),
-                            name,
-                            created_at: DateTime::parse_from_rfc3339(&created_str)
-                                .map(|dt| dt.with_timezone(&Utc))
-                                .unwrap_or_else(|_| Utc::now()),
-                            last_activity: DateTime::parse_from_rfc3339(&activity_str)
-                                .map(|dt| dt.with_timezone(&Utc))
-                                .unwrap_or_else(|_| Utc::now()),
-                            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-                            message_count: count as usize,
-                        })
-                    },
-                )
-                .ok()
-            })
+            .filter_map(|key| conn.query_row(&sql, params![key], metadata_from_row).ok())
             .collect()
     }
 }
@@ -1163,4 +1268,127 @@ mod tests {
         assert_eq!(single.created_at, from_list.created_at);
         assert_eq!(single.last_activity, from_list.last_activity);
     }
+
+    #[test]
+    fn fork_session_copies_prefix_and_records_lineage() {
+        let tmp = TempDir::new().unwrap();
+        let backend = SqliteSessionBackend::new(tmp.path()).unwrap();
+
+        backend.append("root", &ChatMessage::user("one")).unwrap();
+        backend
+            .append("root", &ChatMessage::assistant("two"))
+            .unwrap();
+        backend.append("root", &ChatMessage::user("three")).unwrap();
+
+        assert_eq!(backend.fork_session("root", "branch", 2).unwrap(), 2);
+        let branch = backend.load("branch");
+        assert_eq!(branch.len(), 2);
+        assert_eq!(branch[1].content, "two");
+        assert_eq!(backend.load("root").len(), 3);
+
+        // The branch diverges independently of its parent.
+        backend
+            .append("branch", &ChatMessage::user("other"))
+            .unwrap();
+        assert_eq!(backend.load("root")[2].content, "three");
+
+        let meta = backend.get_session_metadata("branch").unwrap();
+        assert_eq!(meta.parent_key.as_deref(), Some("root"));
+        assert_eq!(meta.fork_point, Some(2));
+        assert_eq!(meta.message_count, 3);
+        assert!(
+            backend
+                .get_session_metadata("root")
+                .unwrap()
+                .parent_key
+                .is_none()
+        );
+
+        // Copied content is searchable under the new key.
+        let hits = backend.search(&SessionQuery {
+            keyword: Some("two".into()),
+            limit: None,
+        });
+        assert!(hits.iter().any(|m| m.key == "branch"));
+    }
+
+    #[test]
+    fn fork_session_rejects_bad_input() {
+        use std::io::ErrorKind;
+        let tmp = TempDir::new().unwrap();
+        let backend = SqliteSessionBackend::new(tmp.path()).unwrap();
+        backend.append("root", &ChatMessage::user("one")).unwrap();
+        backend.append("taken", &ChatMessage::user("x")).unwrap();
+
+        let kind = |r: std::io::Result<usize>| r.unwrap_err().kind();
+        assert_eq!(
+            kind(backend.fork_session("missing", "b", 1)),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            kind(backend.fork_session("root", "b", 2)),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(backend.fork_session("root", "taken", 1)),
+            ErrorKind::AlreadyExists
+        );
+        assert!(backend.load("b").is_empty());
+    }
+
+    #[test]
+    fn migrate_from_jsonl_keeps_fork_lineage() {
+        let tmp = TempDir::new().unwrap();
+        let store = crate::session_store::SessionStore::new(tmp.path()).unwrap();
+        store.append("root", &ChatMessage::user("one")).unwrap();
+        store.append("root", &ChatMessage::user("two")).unwrap();
+        store.fork_session("root", "branch", 1).unwrap();
+
+        let backend = SqliteSessionBackend::new(tmp.path()).unwrap();
+        assert_eq!(backend.migrate_from_jsonl(tmp.path()).unwrap(), 2);
+
+        let meta = backend.get_session_metadata("branch").unwrap();
+        assert_eq!(meta.parent_key.as_deref(), Some("root"));
+        assert_eq!(meta.fork_point, Some(1));
+    }
+
+    #[test]
+    fn import_session_preserves_timestamps_and_skips_existing() {
+        let tmp = TempDir::new().unwrap();
+        let backend = SqliteSessionBackend::new(tmp.path()).unwrap();
+        let stamp = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let meta = SessionMetadata {
+            key: "imported".into(),
+            name: Some("ticket-42".into()),
+            created_at: stamp,
+            last_activity: stamp,
+            message_count: 0,
+            parent_key: Some("root".into()),
+            fork_point: Some(1),
+        };
+        let messages = vec![
+            StoredMessage {
+                message: ChatMessage::user("hi"),
+                created_at: Some(stamp),
+            },
+            StoredMessage {
+                message: ChatMessage::assistant("hello"),
+                created_at: None,
+            },
+        ];
+
+        assert!(backend.import_session(&meta, &messages).unwrap());
+        assert!(!backend.import_session(&meta, &messages).unwrap());
+
+        let stored = backend.load_stored("imported");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].created_at, Some(stamp));
+        assert_eq!(stored[1].created_at, Some(stamp));
+        let loaded = backend.get_session_metadata("imported").unwrap();
+        assert_eq!(loaded.message_count, 2);
+        assert_eq!(loaded.name.as_deref(), Some("ticket-42"));
+        assert_eq!(loaded.fork_point, Some(1));
+    }
 }
@@ -4,12 +4,23 @@
 //! as an append-only JSONL file in `{workspace}/sessions/`. Messages are appended
 //! one-per-line as JSON, never modifying old lines. On daemon restart, sessions
 //! are loaded from disk to restore conversation context.
+//!
+//! Forked sessions carry a `{key}.lineage.json` sidecar naming the parent
+//! session and fork point; sessions without one are roots.
 
-use crate::session_backend::SessionBackend;
+use crate::session_backend::{SessionBackend, SessionMetadata, StoredMessage, check_fork_point};
+use serde::{Deserialize, Serialize};
 use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
 use zeroclaw_api::provider::ChatMessage;
 
+/// Parent link stored next to a forked session's JSONL file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Lineage {
+    parent_key: String,
+    fork_point: usize,
+}
+
 /// Append-only JSONL session store for channel conversations.
 pub struct SessionStore {
     sessions_dir: PathBuf,
@@ -25,7 +36,17 @@ impl SessionStore {
 
     /// Compute the file path for a session key, sanitizing for filesystem safety.
     fn session_path(&self, session_key: &str) -> PathBuf {
-        let safe_key: String = session_key
+        self.sessions_dir
+            .join(format!("{}.jsonl", Self::safe_key(session_key)))
+    }
+
+    fn lineage_path(&self, session_key: &str) -> PathBuf {
+        self.sessions_dir
+            .join(format!("{}.lineage.json", Self::safe_key(session_key)))
+    }
+
+    fn safe_key(session_key: &str) -> String {
+        session_key
             .chars()
             .map(|c| {
                 if c.is_alphanumeric() || c == '_' || c == '-' {
@@ -34,8 +55,12 @@ impl SessionStore {
                     '_'
                 }
             })
-            .collect();
-        self.sessions_dir.join(format!("{safe_key}.jsonl"))
+            .collect()
+    }
+
+    fn read_lineage(&self, session_key: &str) -> Option<Lineage> {
+        let raw = std::fs::read_to_string(self.lineage_path(session_key)).ok()?;
+        serde_json::from_str(&raw).ok()
     }
 
     /// Load all messages for a session from its JSONL file.
@@ -127,6 +152,60 @@ impl SessionStore {
             return Ok(false);
         }
         std::fs::remove_file(&path)?;
+        let _ = std::fs::remove_file(self.lineage_path(session_key));
+        Ok(true)
+    }
+
+    /// Copy the first `at` messages of `source_key` into a new session and
+    /// write its lineage sidecar. See [`SessionBackend::fork_session`].
+    pub fn fork_session(
+        &self,
+        source_key: &str,
+        target_key: &str,
+        at: usize,
+    ) -> std::io::Result<usize> {
+        let messages = self.load(source_key);
+        check_fork_point(source_key, messages.len(), at)?;
+        if self.session_path(target_key).exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("session '{target_key}' already exists"),
+            ));
+        }
+
+        let lineage = Lineage {
+            parent_key: source_key.to_string(),
+            fork_point: at,
+        };
+        let json = serde_json::to_string(&lineage)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(self.lineage_path(target_key), json)?;
+        self.rewrite(target_key, &messages[..at])?;
+        Ok(at)
+    }
+
+    /// Write an imported session and its lineage sidecar. Names and
+    /// timestamps have nowhere to live in JSONL and are dropped. See
+    /// [`SessionBackend::import_session`].
+    pub fn import_session(
+        &self,
+        meta: &SessionMetadata,
+        messages: &[StoredMessage],
+    ) -> std::io::Result<bool> {
+        if self.session_path(&meta.key).exists() {
+            return Ok(false);
+        }
+        if let (Some(parent_key), Some(fork_point)) = (&meta.parent_key, meta.fork_point) {
+            let lineage = Lineage {
+                parent_key: parent_key.clone(),
+                fork_point,
+            };
+            let json = serde_json::to_string(&lineage)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            std::fs::write(self.lineage_path(&meta.key), json)?;
+        }
+        let messages: Vec<ChatMessage> = messages.iter().map(|m| m.message.clone()).collect();
+        self.rewrite(&meta.key, &messages)?;
         Ok(true)
     }
 
@@ -176,7 +255,7 @@ impl SessionBackend for SessionStore {
     /// The trait default stamps every key with `Utc::now()`, which makes
     /// the orchestrator's `sort_by_key(|m| Reverse(m.last_activity))`
     /// arbitrary once more than that many sessions are persisted.
-    fn list_sessions_with_metadata(&self) -> Vec<crate::session_backend::SessionMetadata> {
+    fn list_sessions_with_metadata(&self) -> Vec<SessionMetadata> {
         use chrono::{DateTime, Utc};
         self.list_sessions()
             .into_iter()
@@ -185,11 +264,14 @@ impl SessionBackend for SessionStore {
                     .session_mtime(&key)
                     .map(DateTime::<Utc>::from)
                     .unwrap_or_else(Utc::now);
-                crate::session_backend::SessionMetadata {
+                let lineage = self.read_lineage(&key);
+                SessionMetadata {
                     name: None,
                     created_at: last_activity,
                     last_activity,
                     message_count: 0,
+                    parent_key: lineage.as_ref().map(|l| l.parent_key.clone()),
+                    fork_point: lineage.map(|l| l.fork_point),
                     key,
                 }
             })
@@ -207,6 +289,44 @@ impl SessionBackend for SessionStore {
     fn delete_session(&self, session_key: &str) -> std::io::Result<bool> {
         self.delete_session(session_key)
     }
+
+    fn get_session_metadata(&self, session_key: &str) -> Option<SessionMetadata> {
+        let messages = self.load(session_key);
+        if messages.is_empty() {
+            return None;
+        }
+        let last_activity = self
+            .session_mtime(session_key)
+            .map(chrono::DateTime::<chrono::Utc>::from)
+            .unwrap_or_else(chrono::Utc::now);
+        let lineage = self.read_lineage(session_key);
+        Some(SessionMetadata {
+            key: session_key.to_string(),
+            name: None,
+            created_at: last_activity,
+            last_activity,
+            message_count: messages.len(),
+            parent_key: lineage.as_ref().map(|l| l.parent_key.clone()),
+            fork_point: lineage.map(|l| l.fork_point),
+        })
+    }
+
+    fn fork_session(
+        &self,
+        source_key: &str,
+        target_key: &str,
+        at: usize,
+    ) -> std::io::Result<usize> {
+        self.fork_session(source_key, target_key, at)
+    }
+
+    fn import_session(
+        &self,
+        meta: &SessionMetadata,
+        messages: &[StoredMessage],
+    ) -> std::io::Result<bool> {
+        self.import_session(meta, messages)
+    }
 }
 
 #[cfg(test)]
@@ -462,7 +582,38 @@ mod tests {
         assert!(backend.load("trait_delete").is_empty());
     }
 
-    // ── get_session_metadata (trait default) tests ──────────────────
+    #[test]
+    fn fork_session_copies_prefix_and_records_lineage() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+        let backend: &dyn SessionBackend = &store;
+
+        for text in ["one", "two", "three"] {
+            backend.append("root", &ChatMessage::user(text)).unwrap();
+        }
+        assert_eq!(backend.fork_session("root", "branch", 2).unwrap(), 2);
+
+        let branch = backend.load("branch");
+        assert_eq!(branch.len(), 2);
+        assert_eq!(branch[1].content, "two");
+        assert_eq!(backend.load("root").len(), 3);
+
+        let meta = backend.get_session_metadata("branch").unwrap();
+        assert_eq!(meta.parent_key.as_deref(), Some("root"));
+        assert_eq!(meta.fork_point, Some(2));
+        let listed = backend.list_sessions_with_metadata();
+        let root = listed.iter().find(|m| m.key == "root").unwrap();
+        assert!(root.parent_key.is_none());
+        assert_eq!(listed.len(), 2, "lineage sidecar is not a session");
+
+        let err = backend.fork_session("root", "branch", 1).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        backend.delete_session("branch").unwrap();
+        assert!(!store.lineage_path("branch").exists());
+    }
+
+    // ── get_session_metadata tests ──────────────────────────────────
 
     #[test]
     fn get_session_metadata_returns_none_for_missing() {
@@ -490,4 +641,31 @@ mod tests {
         assert_eq!(meta.message_count, 2);
         assert!(meta.name.is_none());
     }
+
+    #[test]
+    fn import_session_writes_messages_and_lineage_once() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+        let backend: &dyn SessionBackend = &store;
+        let now = chrono::Utc::now();
+        let meta = SessionMetadata {
+            key: "imported".into(),
+            name: None,
+            created_at: now,
+            last_activity: now,
+            message_count: 1,
+            parent_key: Some("root".into()),
+            fork_point: Some(1),
+        };
+        let messages = [StoredMessage {
+            message: ChatMessage::user("hi"),
+            created_at: None,
+        }];
+
+        assert!(backend.import_session(&meta, &messages).unwrap());
+        assert!(!backend.import_session(&meta, &messages).unwrap());
+        assert_eq!(backend.load("imported").len(), 1);
+        let loaded = backend.get_session_metadata("imported").unwrap();
+        assert_eq!(loaded.parent_key.as_deref(), Some("root"));
+    }
 }
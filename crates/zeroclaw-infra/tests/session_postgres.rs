@@ -0,0 +1,128 @@
+//! Integration tests for the PostgreSQL session backend against a real
+//! database.
+//!
+//! Requires the `session-postgres` feature and a reachable server whose
+//! role may create schemas, e.g.
+//! `ZEROCLAW_TEST_POSTGRES_URL=postgres://zeroclaw@127.0.0.1:5432/postgres`.
+//! Each test works in its own schema and drops it afterwards.
+//!
+//! Run: `ZEROCLAW_TEST_POSTGRES_URL=... cargo test -p zeroclaw-infra
+//! --features session-postgres --test session_postgres -- --ignored`
+
+#![cfg(feature = "session-postgres")]
+
+use postgres::{Client, NoTls};
+use zeroclaw_api::provider::ChatMessage;
+use zeroclaw_infra::session_backend::{SessionBackend, SessionQuery};
+use zeroclaw_infra::session_postgres::PostgresSessionBackend;
+
+fn db_url() -> String {
+    std::env::var("ZEROCLAW_TEST_POSTGRES_URL").expect("ZEROCLAW_TEST_POSTGRES_URL must be set")
+}
+
+/// A schema private to one test, dropped when the guard goes out of scope.
+struct TestSchema {
+    name: String,
+}
+
+impl TestSchema {
+    fn new(test: &str) -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        Self {
+            name: format!("zc_test_{test}_{}_{nanos}", std::process::id()),
+        }
+    }
+}
+
+impl Drop for TestSchema {
+    fn drop(&mut self) {
+        if let Ok(mut admin) = Client::connect(&db_url(), NoTls) {
+            let _ = admin.batch_execute(&format!("DROP SCHEMA IF EXISTS {} CASCADE", self.name));
+        }
+    }
+}
+
+fn msg(role: &str, content: &str) -> ChatMessage {
+    ChatMessage {
+        role: role.into(),
+        content: content.into(),
+    }
+}
+
+#[test]
+#[ignore = "requires ZEROCLAW_TEST_POSTGRES_URL"]
+fn round_trips_history_forks_and_search() {
+    let schema = TestSchema::new("roundtrip");
+    let backend = PostgresSessionBackend::new(&db_url(), &schema.name, Some(5)).unwrap();
+
+    backend
+        .append("s1", &msg("user", "deploy the canary"))
+        .unwrap();
+    backend
+        .append("s1", &msg("assistant", "canary deployed"))
+        .unwrap();
+    backend.append("s1", &msg("user", "roll it out")).unwrap();
+
+    let history = backend.load("s1");
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].content, "deploy the canary");
+    assert_eq!(backend.get_session_metadata("s1").unwrap().message_count, 3);
+
+    assert!(
+        backend
+            .update_last("s1", &msg("user", "roll it back"))
+            .unwrap()
+    );
+    assert_eq!(backend.load("s1")[2].content, "roll it back");
+
+    assert_eq!(backend.fork_session("s1", "s1-fork", 2).unwrap(), 2);
+    let fork = backend.get_session_metadata("s1-fork").unwrap();
+    assert_eq!(fork.parent_key.as_deref(), Some("s1"));
+    assert_eq!(fork.fork_point, Some(2));
+    assert_eq!(backend.load("s1-fork").len(), 2);
+    assert!(backend.fork_session("s1", "s1-fork", 1).is_err());
+
+    let hits = backend.search(&SessionQuery {
+        keyword: Some("canary".into()),
+        limit: Some(10),
+    });
+    let keys: Vec<_> = hits.iter().map(|m| m.key.as_str()).collect();
+    assert!(keys.contains(&"s1") && keys.contains(&"s1-fork"));
+
+    assert!(backend.remove_last("s1").unwrap());
+    assert_eq!(backend.load("s1").len(), 2);
+    assert!(backend.delete_session("s1").unwrap());
+    assert!(backend.load("s1").is_empty());
+}
+
+#[test]
+#[ignore = "requires ZEROCLAW_TEST_POSTGRES_URL"]
+fn reconnects_after_the_server_drops_the_connection() {
+    let schema = TestSchema::new("reconnect");
+    let app = format!("zeroclaw_{}", schema.name);
+    let separator = if db_url().contains('?') { '&' } else { '?' };
+    let url = format!("{}{separator}application_name={app}", db_url());
+    let backend = PostgresSessionBackend::new(&url, &schema.name, Some(5)).unwrap();
+    backend.append("s1", &msg("user", "before")).unwrap();
+
+    let mut admin = Client::connect(&db_url(), NoTls).unwrap();
+    let terminated = admin
+        .query(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE application_name = $1",
+            &[&app],
+        )
+        .unwrap();
+    assert_eq!(terminated.len(), 1);
+
+    // The call that discovers the dead connection may fail; the next one
+    // must go through on a fresh connection.
+    if backend.append("s1", &msg("user", "after")).is_err() {
+        backend.append("s1", &msg("user", "after")).unwrap();
+    }
+    let history = backend.load("s1");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].content, "after");
+}
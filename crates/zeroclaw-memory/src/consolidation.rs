@@ -40,6 +40,9 @@ Do not include any text outside the JSON object."#;
 /// Phase 1: Write a history entry to the Daily memory category.
 /// Phase 2: Write a memory update to the Core category (if the LLM identified new facts).
 ///
+/// Both entries are stored under `session_id`, so callers with a scoped
+/// memory session keep consolidated facts in that scope.
+///
 /// This function is designed to be called fire-and-forget via `tokio::spawn`.
 /// Strip channel media markers (e.g. `[IMAGE:/local/path]`, `[DOCUMENT:...]`)
 /// that contain local filesystem paths.  These must never be forwarded to
@@ -56,6 +59,7 @@ pub async fn consolidate_turn(
     provider: &dyn Provider,
     model: &str,
     memory: &dyn Memory,
+    session_id: Option<&str>,
     user_message: &str,
     assistant_response: &str,
 ) -> anyhow::Result<()> {
@@ -101,7 +105,7 @@ pub async fn consolidate_turn(
             &history_key,
             &result.history_entry,
             MemoryCategory::Daily,
-            None,
+            session_id,
         )
         .await?;
 
@@ -133,7 +137,7 @@ pub async fn consolidate_turn(
                 &mem_key,
                 update,
                 MemoryCategory::Core,
-                None,
+                session_id,
                 None,
                 Some(imp),
             )
@@ -23,6 +23,7 @@ cli-auth-about = Manage provider subscription authentication profiles
 cli-hardware-about = Discover and introspect USB hardware
 cli-peripheral-about = Manage hardware peripherals
 cli-memory-about = Manage agent memory entries
+cli-session-about = Export, import, and list conversation sessions
 cli-config-about = Manage ZeroClaw configuration
 cli-update-about = Check for and apply ZeroClaw updates
 cli-self-test-about = Run diagnostic self-tests
@@ -94,6 +95,10 @@ cli-memory-get-about = Get a specific memory entry by key
 cli-memory-stats-about = Show memory backend statistics and health
 cli-memory-clear-about = Clear memories by category, by key, or clear all
 
+cli-session-list-about = List stored sessions, most recently active first
+cli-session-export-about = Export a session transcript as Markdown, HTML, or JSON
+cli-session-import-about = Import a JSON transcript as a new session
+
 cli-estop-status-about = Print current estop status
 cli-estop-resume-about = Resume from an engaged estop level
 
@@ -118,6 +123,7 @@ cli-sop-validate-about = Validate SOP definitions
 cli-sop-show-about = Show details of an SOP
 
 cli-migrate-openclaw-about = Import memory from an OpenClaw workspace into this ZeroClaw workspace
+cli-migrate-sessions-about = Copy channel session history from the local SQLite store into PostgreSQL
 
 cli-agent-long-about =
     Start the AI agent loop.
@@ -145,7 +151,7 @@ cli-acp-long-about =
 
     Launches a JSON-RPC 2.0 server on stdin/stdout for IDE and tool integration. Supports session management and streaming agent responses as notifications.
 
-    Methods: initialize, session/new, session/prompt, session/stop.
+    Methods: initialize, session/new, session/prompt, session/fork, session/stop.
 
     Examples:
       zeroclaw acp                        # start ACP server
@@ -228,6 +234,17 @@ cli-memory-long-about =
       zeroclaw memory get KEY
       zeroclaw memory clear --category conversation --yes
 
+cli-session-long-about =
+    Export, import, and list conversation sessions.
+
+    Sessions live in the backend selected by [channels].session_backend. Markdown and HTML exports are readable transcripts with collapsible tool blocks, timestamps, and model/cost annotations; secrets are always scrubbed. JSON exports are lossless and can be re-imported.
+
+    Examples:
+      zeroclaw session list
+      zeroclaw session export telegram_alice --format html -o alice.html
+      zeroclaw session export gw_abc123 --format json > backup.json
+      zeroclaw session import backup.json --key restored
+
 cli-config-long-about =
     Manage ZeroClaw configuration.
 
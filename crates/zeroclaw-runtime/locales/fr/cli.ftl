@@ -22,6 +22,7 @@ cli-auth-about = Gérer les profils d'authentification des abonnements fournisse
 cli-hardware-about = Découvrir et analyser le matériel USB
 cli-peripheral-about = Gérer les périphériques matériels
 cli-memory-about = Gérer les entrées de mémoire de l'agent
+cli-session-about = Exporter, importer et lister les sessions de conversation
 cli-config-about = Gérer la configuration de ZeroClaw
 cli-update-about = Vérifier et appliquer les mises à jour de ZeroClaw
 cli-self-test-about = Exécuter les tests d'autodiagnostic
@@ -70,6 +71,9 @@ cli-memory-list-about = Lister les entrées de mémoire avec des filtres optionn
 cli-memory-get-about = Obtenir une entrée de mémoire spécifique par clé
 cli-memory-stats-about = Afficher les statistiques et l'état de santé du backend mémoire
 cli-memory-clear-about = Effacer les mémoires par catégorie, par clé, ou tout effacer
+cli-session-list-about = Lister les sessions stockées, les plus récemment actives en premier
+cli-session-export-about = Exporter la transcription d'une session en Markdown, HTML ou JSON
+cli-session-import-about = Importer une transcription JSON comme nouvelle session
 cli-estop-status-about = Imprimer le statut actuel d'arrêt d'urgence
 cli-estop-resume-about = Reprendre depuis un niveau d'arrêt d'urgence engagé
 cli-models-refresh-about = Actualiser et mettre en cache les modèles du fournisseur
@@ -88,6 +92,7 @@ cli-sop-list-about = Lister les SOP (Procédures Opérationnelles Standard) char
 cli-sop-validate-about = Valider les définitions des SOP
 cli-sop-show-about = Afficher les détails d'une SOP
 cli-migrate-openclaw-about = Importer la mémoire d'un espace de travail OpenClaw vers cet espace de travail ZeroClaw
+cli-migrate-sessions-about = Copier l'historique des sessions de canaux du stockage SQLite local vers PostgreSQL
 cli-agent-long-about =
     Démarrer la boucle de l'agent IA.
 
@@ -112,7 +117,7 @@ cli-acp-long-about =
 
     Lance un serveur JSON-RPC 2.0 sur stdin/stdout pour l'intégration avec des IDE et des outils. Gère la session et diffuse les réponses de l'agent sous forme de notifications.
 
-    Méthodes : initialize, session/new, session/prompt, session/stop.
+    Méthodes : initialize, session/new, session/prompt, session/fork, session/stop.
 
     Exemples :
     zeroclaw acp                        # démarrer le serveur ACP
@@ -191,6 +196,16 @@ cli-memory-long-about =
     zeroclaw memory clear
 
     La complétion par tabulation est automatiquement incluse dans les sous-commandes de complétion.
+cli-session-long-about =
+    Exporter, importer et lister les sessions de conversation.
+
+    Les sessions sont stockées dans le backend choisi par [channels].session_backend. Les exports Markdown et HTML sont des transcriptions lisibles avec des blocs d'outils repliables, des horodatages et des annotations de modèle et de coût ; les secrets sont toujours masqués. Les exports JSON sont sans perte et peuvent être réimportés.
+
+    Exemples :
+    zeroclaw session list
+    zeroclaw session export telegram_alice --format html -o alice.html
+    zeroclaw session export gw_abc123 --format json > backup.json
+    zeroclaw session import backup.json --key restored
 cli-config-long-about =
     Gérer la configuration de ZeroClaw.
 
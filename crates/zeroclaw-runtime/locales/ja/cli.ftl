@@ -22,6 +22,7 @@ cli-auth-about = プロバイダー サブスクリプション認証プロフ
 cli-hardware-about = USBハードウェアを発見・内省
 cli-peripheral-about = ハードウェアペリフェラルを管理
 cli-memory-about = エージェントメモリエントリを管理
+cli-session-about = 会話セッションのエクスポート、インポート、一覧表示
 cli-config-about = ZeroClaw設定を管理
 cli-update-about = ZeroClaw更新を確認・適用
 cli-self-test-about = 診断自己テストを実行
@@ -70,6 +71,9 @@ cli-memory-list-about = オプションのフィルター付きでメモリエ
 cli-memory-get-about = キーで特定のメモリエントリを取得
 cli-memory-stats-about = メモリバックエンド統計とヘルスを表示
 cli-memory-clear-about = カテゴリ別、キー別、またはすべてをクリアしてメモリをクリア
+cli-session-list-about = 保存済みセッションを最近アクティブな順に一覧表示
+cli-session-export-about = セッションのトランスクリプトを Markdown、HTML、JSON でエクスポート
+cli-session-import-about = JSON トランスクリプトを新しいセッションとしてインポート
 cli-estop-status-about = 現在の estop ステータスを表示
 cli-estop-resume-about = エンゲージされた estop レベルから再開
 cli-models-refresh-about = プロバイダーモデルをリフレッシュしてキャッシュ
@@ -88,6 +92,7 @@ cli-sop-list-about = ロードされた SOP を一覧表示
 cli-sop-validate-about = SOP 定義を検証
 cli-sop-show-about = SOP の詳細を表示
 cli-migrate-openclaw-about = OpenClaw ワークスペースからこの ZeroClaw ワークスペースにメモリをインポート
+cli-migrate-sessions-about = ローカル SQLite ストアのチャネルセッション履歴を PostgreSQL にコピー
 cli-agent-long-about =
     AI エージェントループを起動します。
 
@@ -112,7 +117,7 @@ cli-acp-long-about =
 
     IDE とツール統合用に stdin/stdout で JSON-RPC 2.0 サーバーを起動します。セッション管理と通知としてのストリーミングエージェント応答に対応しています。
 
-    メソッド: initialize、session/new、session/prompt、session/stop。
+    メソッド: initialize、session/new、session/prompt、session/fork、session/stop。
 
     例:
     zeroclaw acp                        # ACP サーバーを起動
@@ -188,6 +193,16 @@ cli-memory-long-about =
     zeroclaw memory list --category core --limit 10
     zeroclaw memory get KEY
     zeroclaw memory clear --category conversation --yes
+cli-session-long-about =
+    会話セッションのエクスポート、インポート、一覧表示を行います。
+
+    セッションは [channels].session_backend で選択されたバックエンドに保存されます。Markdown と HTML のエクスポートは、折りたたみ可能なツールブロック、タイムスタンプ、モデルとコストの注釈付きの読みやすいトランスクリプトで、シークレットは常にマスクされます。JSON エクスポートはロスレスで、再インポートできます。
+
+    例:
+    zeroclaw session list
+    zeroclaw session export telegram_alice --format html -o alice.html
+    zeroclaw session export gw_abc123 --format json > backup.json
+    zeroclaw session import backup.json --key restored
 cli-config-long-about =
     ZeroClaw 設定を管理します。
 
@@ -463,6 +463,35 @@ impl Agent {
         }
     }
 
+    /// Append caller-supplied instructions to this agent's system prompt.
+    ///
+    /// They live only in this agent's in-memory history: nothing is written
+    /// to memory or a session backend, so they shape the turns of this
+    /// agent alone.
+    pub fn append_system_instructions(&mut self, instructions: &str) {
+        self.seed_history(&[]);
+        if let Some(ConversationMessage::Chat(system)) = self.history.first_mut()
+            && system.role == "system"
+        {
+            system.content.push_str("\n\n");
+            system.content.push_str(instructions);
+        }
+    }
+
+    /// Hydrate the agent with another agent's history, tool calls included
+    /// (e.g. when forking a live session). Like [`Self::seed_history`], this
+    /// agent keeps its own system prompt and system entries in `history` are
+    /// skipped.
+    pub fn seed_conversation(&mut self, history: &[ConversationMessage]) {
+        self.seed_history(&[]);
+        self.history.extend(
+            history
+                .iter()
+                .filter(|m| !matches!(m, ConversationMessage::Chat(c) if c.role == "system"))
+                .cloned(),
+        );
+    }
+
     pub async fn from_config(config: &Config) -> Result<Self> {
         Self::from_config_with_session_cwd(config, None).await
     }
@@ -1100,6 +1129,16 @@ impl Agent {
         futures_util::future::join_all(futs).await
     }
 
+    /// Concrete model a turn for `user_message` runs on: the classified
+    /// route resolved through `route_model_by_hint`, or the default model.
+    pub fn served_model(&self, user_message: &str) -> String {
+        let model = self.classify_model(user_message);
+        match model.strip_prefix("hint:") {
+            Some(hint) => self.route_model_by_hint.get(hint).cloned().unwrap_or(model),
+            None => model,
+        }
+    }
+
     fn classify_model(&self, user_message: &str) -> String {
         if let Some(decision) =
             super::classifier::classify_with_decision(&self.classification_config, user_message)
@@ -2538,6 +2577,12 @@ mod tests {
             .build()
             .expect("agent builder should succeed with valid config");
 
+        assert_eq!(
+            agent.served_model("quick summary please"),
+            "anthropic/claude-haiku-4-5"
+        );
+        assert_eq!(agent.served_model("explain this"), "<unconfigured>");
+
         let response = agent.turn("quick summary please").await.unwrap();
         assert_eq!(response, "classified");
         let seen = seen_models.lock();
@@ -2779,6 +2824,13 @@ mod tests {
             matches!(&history[2], ConversationMessage::Chat(m) if m.role == "assistant" && m.content == "hi there")
         );
         assert_eq!(history.len(), 3);
+
+        agent.append_system_instructions("answer in French");
+        let history = agent.history();
+        assert!(
+            matches!(&history[0], ConversationMessage::Chat(m) if m.role == "system" && m.content.ends_with("\n\nanswer in French"))
+        );
+        assert_eq!(history.len(), 3);
     }
 
     /// Mock provider that captures whether tool specs were passed to `stream_chat`
@@ -0,0 +1,192 @@
+//! Minimal ICS (RFC 5545) reader: the start and end of each `VEVENT`.
+//!
+//! All-day events become whole days, timed events become windows.
+//! Recurrence rules are not expanded, so feeds must list each occurrence.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::str::FromStr;
+
+/// One event of an ICS file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcsEvent {
+    /// Whole days from `start` up to, not including, `end`.
+    Days { start: NaiveDate, end: NaiveDate },
+    /// A time window; the end is exclusive.
+    Window {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+/// Parse the events of an ICS document.  Floating times (no `Z`, no
+/// `TZID`) are read in `floating_tz`, else UTC.
+pub fn parse(content: &str, floating_tz: Option<chrono_tz::Tz>) -> Result<Vec<IcsEvent>> {
+    let mut events = Vec::new();
+    let mut start: Option<Value> = None;
+    let mut end: Option<Value> = None;
+    let mut in_event = false;
+
+    for line in unfold(content) {
+        let Some((name_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut parts = name_params.split(';');
+        let name = parts.next().unwrap_or_default().to_ascii_uppercase();
+        let params: Vec<&str> = parts.collect();
+
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => {
+                in_event = true;
+                start = None;
+                end = None;
+            }
+            ("END", "VEVENT") => {
+                in_event = false;
+                if let Some(event) = to_event(start.take(), end.take()) {
+                    events.push(event);
+                }
+            }
+            ("DTSTART", raw) if in_event => {
+                start = Some(parse_value(raw, &params, floating_tz).context("Invalid DTSTART")?);
+            }
+            ("DTEND", raw) if in_event => {
+                end = Some(parse_value(raw, &params, floating_tz).context("Invalid DTEND")?);
+            }
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+enum Value {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+fn to_event(start: Option<Value>, end: Option<Value>) -> Option<IcsEvent> {
+    match (start?, end) {
+        (Value::Date(start), None) => Some(IcsEvent::Days {
+            start,
+            end: start + Duration::days(1),
+        }),
+        (Value::Date(start), Some(Value::Date(end))) => Some(IcsEvent::Days {
+            start,
+            end: end.max(start + Duration::days(1)),
+        }),
+        (Value::DateTime(start), Some(Value::DateTime(end))) if start < end => {
+            Some(IcsEvent::Window { start, end })
+        }
+        // Zero-length and mixed events cover no time.
+        _ => None,
+    }
+}
+
+fn parse_value(raw: &str, params: &[&str], floating_tz: Option<chrono_tz::Tz>) -> Result<Value> {
+    let param = |key: &str| {
+        params.iter().find_map(|p| {
+            p.split_once('=')
+                .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v.trim_matches('"'))
+        })
+    };
+
+    if param("VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE")) || raw.len() == 8 {
+        let date = NaiveDate::parse_from_str(raw, "%Y%m%d")
+            .with_context(|| format!("Invalid ICS date: {raw}"))?;
+        return Ok(Value::Date(date));
+    }
+
+    if let Some(utc) = raw.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .with_context(|| format!("Invalid ICS date-time: {raw}"))?;
+        return Ok(Value::DateTime(Utc.from_utc_datetime(&naive)));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(raw, "%Y%m%dT%H%M%S")
+        .with_context(|| format!("Invalid ICS date-time: {raw}"))?;
+    let tz = match param("TZID") {
+        Some(tzid) => Some(
+            chrono_tz::Tz::from_str(tzid)
+                .map_err(|_| anyhow::anyhow!("Unknown ICS TZID: {tzid}"))?,
+        ),
+        None => floating_tz,
+    };
+    let at = match tz {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|at| at.with_timezone(&Utc))
+            .ok_or_else(|| anyhow::anyhow!("ICS time {raw} does not exist in {tz}"))?,
+        None => Utc.from_utc_datetime(&naive),
+    };
+    Ok(Value::DateTime(at))
+}
+
+/// Join folded continuation lines (those starting with a space or tab).
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(previous)) => previous.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_day_and_timed_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Christmas\r\n\
+            DTSTART;VALUE=DATE:20261225\r\n\
+            DTEND;VALUE=DATE:20261227\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20261107T220000Z\r\n\
+            DTEND:20261108T040000Z\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;TZID=Europe/Berlin:20260301T\r\n \
+            090000\r\n\
+            DTEND;TZID=Europe/Berlin:20260301T100000\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse(ics, None).unwrap();
+        assert_eq!(
+            events[0],
+            IcsEvent::Days {
+                start: NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(),
+                end: NaiveDate::from_ymd_opt(2026, 12, 27).unwrap(),
+            }
+        );
+        assert_eq!(
+            events[1],
+            IcsEvent::Window {
+                start: "2026-11-07T22:00:00Z".parse().unwrap(),
+                end: "2026-11-08T04:00:00Z".parse().unwrap(),
+            }
+        );
+        // Folded line, Berlin is UTC+1 in March.
+        assert_eq!(
+            events[2],
+            IcsEvent::Window {
+                start: "2026-03-01T08:00:00Z".parse().unwrap(),
+                end: "2026-03-01T09:00:00Z".parse().unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        let ics = "BEGIN:VEVENT\nDTSTART;VALUE=DATE:2026-12-25\nEND:VEVENT\n";
+        assert!(parse(ics, None).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! Named calendars — holidays, maintenance blackouts and business days.
+//!
+//! A calendar (`[calendars.<name>]`) is a set of whole days and time
+//! windows, built from inline dates and an optional ICS file.  Cron jobs,
+//! heartbeat tasks and routines reference one through a [`CalendarRule`]:
+//! `exclude` skips the calendar's days, `include` runs only on them,
+//! `business_days` runs only on weekdays outside it, and `next_business_day`
+//! moves an occurrence that misses a business day to the next one.
+
+pub mod ics;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use zeroclaw_config::schema::Config;
+
+pub use zeroclaw_config::schema::{CalendarConfig, CalendarMode, CalendarRule};
+
+use crate::cron::{Schedule, next_run_for_schedule};
+
+/// Occurrences examined before a schedule is declared never to pass its
+/// calendar.
+const MAX_CALENDAR_SKIPS: usize = 1000;
+
+/// A loaded calendar.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    days: BTreeSet<NaiveDate>,
+    windows: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    tz: Option<chrono_tz::Tz>,
+    weekend: Vec<Weekday>,
+}
+
+impl Calendar {
+    /// Build a calendar from its config, reading the ICS file if any.
+    pub fn from_config(config: &CalendarConfig, workspace_dir: &Path) -> Result<Self> {
+        let tz = config
+            .tz
+            .as_deref()
+            .map(|name| {
+                chrono_tz::Tz::from_str(name)
+                    .map_err(|_| anyhow::anyhow!("Invalid IANA timezone: {name}"))
+            })
+            .transpose()?;
+
+        let mut days = BTreeSet::new();
+        for entry in &config.dates {
+            let (from, to) = entry.split_once("..").unwrap_or((entry, entry));
+            let from = parse_date(from)?;
+            let to = parse_date(to)?;
+            days.extend(from.iter_days().take_while(|day| *day <= to));
+        }
+
+        let mut windows = Vec::new();
+        for window in &config.windows {
+            let start = DateTime::parse_from_rfc3339(&window.start)
+                .with_context(|| format!("Invalid window start: {}", window.start))?;
+            let end = DateTime::parse_from_rfc3339(&window.end)
+                .with_context(|| format!("Invalid window end: {}", window.end))?;
+            windows.push((start.with_timezone(&Utc), end.with_timezone(&Utc)));
+        }
+
+        if let Some(path) = &config.ics {
+            let path = resolve_path(path, workspace_dir);
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read ICS file: {}", path.display()))?;
+            let events = ics::parse(&content, tz)
+                .with_context(|| format!("Failed to parse ICS file: {}", path.display()))?;
+            for event in events {
+                match event {
+                    ics::IcsEvent::Days { start, end } => {
+                        days.extend(start.iter_days().take_while(|day| *day < end));
+                    }
+                    ics::IcsEvent::Window { start, end } => windows.push((start, end)),
+                }
+            }
+        }
+        windows.sort();
+
+        let weekend = config
+            .weekend
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| anyhow::anyhow!("Invalid weekend day: {day}"))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            days,
+            windows,
+            tz,
+            weekend,
+        })
+    }
+
+    /// Load the calendar `name` from `config`.
+    pub fn load(config: &Config, name: &str) -> Result<Self> {
+        let calendar = config
+            .calendars
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown calendar '{name}'"))?;
+        Self::from_config(calendar, &config.workspace_dir)
+            .with_context(|| format!("Invalid calendar '{name}'"))
+    }
+
+    /// Whether `at` falls on one of the calendar's days or in a window.
+    /// Days are counted in the calendar's time zone, else `tz`, else the
+    /// system's.
+    pub fn contains(&self, at: DateTime<Utc>, tz: Option<chrono_tz::Tz>) -> bool {
+        self.window_end(at).is_some() || self.days.contains(&self.local_date(at, tz))
+    }
+
+    /// Whether a rule with `mode` lets something happen at `at`.  Used where
+    /// there is no schedule to shift, so `next_business_day` acts like
+    /// `business_days`.
+    pub fn allows(&self, mode: CalendarMode, at: DateTime<Utc>, tz: Option<chrono_tz::Tz>) -> bool {
+        match mode {
+            CalendarMode::Exclude => !self.contains(at, tz),
+            CalendarMode::Include => self.contains(at, tz),
+            CalendarMode::BusinessDays | CalendarMode::NextBusinessDay => {
+                self.window_end(at).is_none() && self.is_business_day(self.local_date(at, tz))
+            }
+        }
+    }
+
+    /// Whether `date` is neither a weekend day nor one of the calendar's days.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.weekend.contains(&chrono::Datelike::weekday(&date)) && !self.days.contains(&date)
+    }
+
+    /// Decide what happens to an occurrence at `at`; `None` when no later
+    /// time can pass.
+    fn check(
+        &self,
+        mode: CalendarMode,
+        at: DateTime<Utc>,
+        tz: Option<chrono_tz::Tz>,
+    ) -> Option<Verdict> {
+        let tz = self.tz.or(tz);
+        match mode {
+            CalendarMode::Exclude => Some(match self.window_end(at) {
+                Some(end) => Verdict::SkipUntil(end),
+                None if self.days.contains(&self.local_date(at, tz)) => {
+                    Verdict::SkipUntil(self.next_day_start(at, tz)?)
+                }
+                None => Verdict::Allowed,
+            }),
+            CalendarMode::Include => {
+                if self.contains(at, tz) {
+                    return Some(Verdict::Allowed);
+                }
+                let date = self.local_date(at, tz);
+                let next_day = self
+                    .days
+                    .range(date.succ_opt()?..)
+                    .next()
+                    .and_then(|day| local_to_utc(day.and_hms_opt(0, 0, 0)?, tz));
+                let next_window = self
+                    .windows
+                    .iter()
+                    .map(|(start, _)| *start)
+                    .find(|start| *start > at);
+                next_day
+                    .into_iter()
+                    .chain(next_window)
+                    .min()
+                    .map(Verdict::SkipUntil)
+            }
+            CalendarMode::BusinessDays => Some(match self.window_end(at) {
+                Some(end) => Verdict::SkipUntil(end),
+                None if !self.is_business_day(self.local_date(at, tz)) => {
+                    Verdict::SkipUntil(self.next_day_start(at, tz)?)
+                }
+                None => Verdict::Allowed,
+            }),
+            CalendarMode::NextBusinessDay => {
+                let mut moved = at;
+                for _ in 0..MAX_CALENDAR_SKIPS {
+                    if let Some(end) = self.window_end(moved) {
+                        moved = end;
+                    } else if self.is_business_day(self.local_date(moved, tz)) {
+                        return Some(if moved == at {
+                            Verdict::Allowed
+                        } else {
+                            Verdict::MoveTo(moved)
+                        });
+                    } else {
+                        let local = utc_to_local(moved, tz);
+                        moved = local_to_utc(local + Duration::days(1), tz)?;
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    fn window_end(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.windows
+            .iter()
+            .filter(|(start, end)| *start <= at && at < *end)
+            .map(|(_, end)| *end)
+            .max()
+    }
+
+    fn local_date(&self, at: DateTime<Utc>, tz: Option<chrono_tz::Tz>) -> NaiveDate {
+        utc_to_local(at, self.tz.or(tz)).date()
+    }
+
+    fn next_day_start(
+        &self,
+        at: DateTime<Utc>,
+        tz: Option<chrono_tz::Tz>,
+    ) -> Option<DateTime<Utc>> {
+        let tomorrow = self.local_date(at, tz).succ_opt()?;
+        local_to_utc(tomorrow.and_hms_opt(0, 0, 0)?, self.tz.or(tz))
+    }
+}
+
+enum Verdict {
+    Allowed,
+    /// Blocked; the next occurrence must be at or after this time.
+    SkipUntil(DateTime<Utc>),
+    /// Run at this later time instead.
+    MoveTo(DateTime<Utc>),
+}
+
+/// Load every configured calendar, dropping (and logging) invalid ones.
+pub fn load_all(config: &Config) -> HashMap<String, Calendar> {
+    config
+        .calendars
+        .keys()
+        .filter_map(|name| match Calendar::load(config, name) {
+            Ok(calendar) => Some((name.clone(), calendar)),
+            Err(e) => {
+                tracing::warn!("Skipping calendar '{name}': {e:#}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// The next run of `schedule` after `from` that `rule` lets through.
+/// One-shot `at` schedules are not filtered.
+pub fn next_run(
+    config: &Config,
+    schedule: &Schedule,
+    rule: Option<&CalendarRule>,
+    from: DateTime<Utc>,
+) -> Result<DateTime<Utc>> {
+    let Some(rule) = rule else {
+        return next_run_for_schedule(schedule, from);
+    };
+    if matches!(schedule, Schedule::At { .. }) {
+        return next_run_for_schedule(schedule, from);
+    }
+    let calendar = Calendar::load(config, &rule.name)?;
+    next_run_in(&calendar, schedule, rule.mode, from)
+}
+
+/// [`next_run`] against an already loaded calendar.
+pub fn next_run_in(
+    calendar: &Calendar,
+    schedule: &Schedule,
+    mode: CalendarMode,
+    from: DateTime<Utc>,
+) -> Result<DateTime<Utc>> {
+    let tz = match schedule {
+        Schedule::Cron { tz: Some(name), .. } => chrono_tz::Tz::from_str(name).ok(),
+        _ => None,
+    };
+
+    let mut from = from;
+    for _ in 0..MAX_CALENDAR_SKIPS {
+        let candidate = next_run_for_schedule(schedule, from)?;
+        match calendar.check(mode, candidate, tz) {
+            Some(Verdict::Allowed) => return Ok(candidate),
+            Some(Verdict::MoveTo(at)) => return Ok(at),
+            // Schedules have second resolution, so one second before the
+            // block ends lets an occurrence right at its end through.
+            Some(Verdict::SkipUntil(until)) => {
+                from = (until - Duration::seconds(1)).max(candidate);
+            }
+            None => break,
+        }
+    }
+    anyhow::bail!(
+        "No upcoming occurrence of the schedule passes the {} calendar",
+        mode.as_str()
+    )
+}
+
+/// Whether `rule` lets something happen at `at`.  Without a rule, always.
+pub fn allows(config: &Config, rule: Option<&CalendarRule>, at: DateTime<Utc>) -> Result<bool> {
+    let Some(rule) = rule else {
+        return Ok(true);
+    };
+    Ok(Calendar::load(config, &rule.name)?.allows(rule.mode, at, None))
+}
+
+fn parse_date(raw: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .with_context(|| format!("Invalid calendar date: {raw}"))
+}
+
+fn resolve_path(path: &str, workspace_dir: &Path) -> PathBuf {
+    let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+    if path.is_absolute() {
+        path
+    } else {
+        workspace_dir.join(path)
+    }
+}
+
+fn utc_to_local(at: DateTime<Utc>, tz: Option<chrono_tz::Tz>) -> NaiveDateTime {
+    match tz {
+        Some(tz) => at.with_timezone(&tz).naive_local(),
+        None => at.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// Map a local time to UTC, moving times that fall in a DST gap forward.
+fn local_to_utc(local: NaiveDateTime, tz: Option<chrono_tz::Tz>) -> Option<DateTime<Utc>> {
+    [local, local + Duration::hours(1)]
+        .into_iter()
+        .find_map(|local| match tz {
+            Some(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            None => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroclaw_config::schema::CalendarWindowConfig;
+
+    fn calendar(dates: &[&str], windows: &[(&str, &str)]) -> Calendar {
+        Calendar::from_config(
+            &CalendarConfig {
+                dates: dates.iter().map(|d| (*d).to_string()).collect(),
+                windows: windows
+                    .iter()
+                    .map(|(start, end)| CalendarWindowConfig {
+                        start: (*start).into(),
+                        end: (*end).into(),
+                    })
+                    .collect(),
+                tz: Some("UTC".into()),
+                ..CalendarConfig::default()
+            },
+            Path::new("/nonexistent"),
+        )
+        .unwrap()
+    }
+
+    fn daily_at_nine() -> Schedule {
+        Schedule::Cron {
+            expr: "0 9 * * *".into(),
+            tz: Some("UTC".into()),
+        }
+    }
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn exclude_skips_holidays_and_windows() {
+        // Thursday 2026-12-24 .. Saturday 2026-12-26 are holidays.
+        let holidays = calendar(
+            &["2026-12-24..2026-12-26"],
+            &[("2026-12-27T08:00:00Z", "2026-12-27T10:00:00Z")],
+        );
+        let next = next_run_in(
+            &holidays,
+            &daily_at_nine(),
+            CalendarMode::Exclude,
+            at("2026-12-23T10:00:00Z"),
+        )
+        .unwrap();
+        // 24th–26th are holidays and the 27th's run falls in the window.
+        assert_eq!(next, at("2026-12-28T09:00:00Z"));
+    }
+
+    #[test]
+    fn include_runs_only_on_listed_days() {
+        let month_end = calendar(&["2026-01-31", "2026-02-28"], &[]);
+        let next = next_run_in(
+            &month_end,
+            &daily_at_nine(),
+            CalendarMode::Include,
+            at("2026-02-01T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(next, at("2026-02-28T09:00:00Z"));
+
+        let err = next_run_in(
+            &month_end,
+            &daily_at_nine(),
+            CalendarMode::Include,
+            at("2026-03-01T00:00:00Z"),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn business_days_skip_weekends_and_holidays() {
+        // Friday 2026-12-25 is a holiday; the 26th/27th are a weekend.
+        let holidays = calendar(&["2026-12-25"], &[]);
+        let next = next_run_in(
+            &holidays,
+            &daily_at_nine(),
+            CalendarMode::BusinessDays,
+            at("2026-12-24T10:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(next, at("2026-12-28T09:00:00Z"));
+    }
+
+    #[test]
+    fn next_business_day_moves_the_occurrence() {
+        let holidays = calendar(&["2026-12-28"], &[]);
+        // Monthly on the 26th (a Saturday): Monday the 28th is a holiday too.
+        let schedule = Schedule::Cron {
+            expr: "0 9 26 * *".into(),
+            tz: Some("UTC".into()),
+        };
+        let next = next_run_in(
+            &holidays,
+            &schedule,
+            CalendarMode::NextBusinessDay,
+            at("2026-12-01T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(next, at("2026-12-29T09:00:00Z"));
+    }
+
+    #[test]
+    fn allows_checks_the_current_time() {
+        let holidays = calendar(&["2026-12-25"], &[]);
+        let christmas = at("2026-12-25T12:00:00Z");
+        let monday = at("2026-12-28T12:00:00Z");
+        assert!(!holidays.allows(CalendarMode::Exclude, christmas, None));
+        assert!(holidays.allows(CalendarMode::Include, christmas, None));
+        assert!(holidays.allows(CalendarMode::BusinessDays, monday, None));
+        assert!(!holidays.allows(
+            CalendarMode::NextBusinessDay,
+            at("2026-12-27T12:00:00Z"),
+            None
+        ));
+    }
+
+    #[test]
+    fn loads_ics_relative_to_workspace() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("holidays.ics"),
+            "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20261225\nEND:VEVENT\n",
+        )
+        .unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.calendars.insert(
+            "holidays".into(),
+            CalendarConfig {
+                ics: Some("holidays.ics".into()),
+                tz: Some("UTC".into()),
+                ..CalendarConfig::default()
+            },
+        );
+        let rule = CalendarRule {
+            name: "holidays".into(),
+            mode: CalendarMode::Exclude,
+        };
+        assert!(!allows(&config, Some(&rule), at("2026-12-25T09:00:00Z")).unwrap());
+        assert!(allows(&config, Some(&rule), at("2026-12-26T09:00:00Z")).unwrap());
+
+        let unknown = CalendarRule {
+            name: "missing".into(),
+            mode: CalendarMode::Exclude,
+        };
+        assert!(next_run(&config, &daily_at_nine(), Some(&unknown), Utc::now()).is_err());
+    }
+}
@@ -14,12 +14,14 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, all_overdue_jobs, due_jobs, get_job, list_jobs, list_runs, record_last_run,
-    record_run, remove_job, reschedule_after_run, sync_declarative_jobs, update_job,
+    add_agent_job, all_overdue_jobs, dependencies_satisfied, due_jobs, get_job, list_jobs,
+    list_runs, record_last_run, record_run, remove_job, reschedule_after_run, set_next_run,
+    sync_declarative_jobs, trigger_dependents, update_job, validate_dependencies,
 };
+pub(crate) use store::add_column_if_missing;
 pub use types::{
-    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
-    deserialize_maybe_stringified,
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, DependencyCondition, JobDependency, JobType,
+    OverlapPolicy, RetryPolicy, Schedule, SessionTarget, deserialize_maybe_stringified,
 };
 
 /// Validate a shell command against the full security policy (allowlist + risk gate).
@@ -1,15 +1,23 @@
 use crate::cron::{
-    CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget, all_overdue_jobs,
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    sync_declarative_jobs, update_job,
+    CronJob, CronJobPatch, DeliveryConfig, JobType, OverlapPolicy, Schedule, SessionTarget,
+    all_overdue_jobs, dependencies_satisfied, due_jobs, get_job, next_run_for_schedule,
+    record_last_run, record_run, remove_job, reschedule_after_run, set_next_run,
+    sync_declarative_jobs, trigger_dependents, update_job,
 };
 use crate::security::SecurityPolicy;
+use crate::sop::SopAuditLogger;
+use crate::sop::dispatch::{DispatchResult, SopCronCache, check_sop_cron_triggers};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{StreamExt, stream};
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
 use tokio::time::{self, Duration};
 use zeroclaw_config::schema::Config;
 use zeroclaw_config::schema::{CronJobDecl, CronScheduleDecl};
@@ -53,6 +61,7 @@ pub async fn run(config: Config, event_tx: EventBroadcast) -> Result<()> {
             uses_memory: true,
             session_target: None,
             delivery: None,
+            calendar: None,
         };
         tracing::debug!(
             schedule = %schedule_cron,
@@ -85,11 +94,35 @@ pub async fn run(config: Config, event_tx: EventBroadcast) -> Result<()> {
         tracing::info!("Scheduler startup: catch-up disabled by config");
     }
 
+    // Runs are dispatched as background tasks so a slow job does not hold
+    // up the polling loop; the dispatcher applies each job's overlap policy
+    // when it comes due again while still running.
+    let dispatcher = Dispatcher::new(config.clone(), security, event_tx);
+
+    // SOP cron triggers fire on the shared SOP engine; the steps they start
+    // are executed headlessly.
+    let sop_triggers = crate::sop::shared_engine(&config).map(|engine| {
+        let cache = SopCronCache::from_engine(&engine);
+        (engine, cache)
+    });
+    let sop_audit = SopAuditLogger::new(Arc::new(zeroclaw_memory::NoneMemory));
+    let mut sop_last_check = Utc::now();
+
     loop {
         interval.tick().await;
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        if let Some((engine, cache)) = &sop_triggers {
+            let results =
+                check_sop_cron_triggers(engine, &sop_audit, cache, &mut sop_last_check).await;
+            for result in results {
+                if let DispatchResult::Started { action, .. } = result {
+                    crate::sop::shared::spawn_drive(&config, *action);
+                }
+            }
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -99,7 +132,238 @@ pub async fn run(config: Config, event_tx: EventBroadcast) -> Result<()> {
             }
         };
 
-        process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT, &event_tx).await;
+        for job in jobs {
+            dispatcher.dispatch(job);
+        }
+    }
+}
+
+/// A run started by the [`Dispatcher`] that has not finished yet.
+struct RunningJob {
+    token: u64,
+    started_at: DateTime<Utc>,
+    abort: AbortHandle,
+    /// Set when [`OverlapPolicy::Queue`] held back an occurrence; a follow-up
+    /// run starts when this one finishes. Several held-back occurrences
+    /// collapse into one.
+    queued: bool,
+}
+
+/// Starts due jobs as background tasks, bounded by `scheduler.max_concurrent`,
+/// and tracks them per job so overlapping occurrences follow the job's
+/// [`OverlapPolicy`].
+#[derive(Clone)]
+struct Dispatcher {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+    event_tx: EventBroadcast,
+    permits: Arc<Semaphore>,
+    running: Arc<Mutex<HashMap<String, RunningJob>>>,
+    next_token: Arc<AtomicU64>,
+}
+
+impl Dispatcher {
+    fn new(config: Config, security: Arc<SecurityPolicy>, event_tx: EventBroadcast) -> Self {
+        let permits = Arc::new(Semaphore::new(config.scheduler.max_concurrent.max(1)));
+        Self {
+            config: Arc::new(config),
+            security,
+            event_tx,
+            permits,
+            running: Arc::new(Mutex::new(HashMap::new())),
+            next_token: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn dispatch(&self, job: CronJob) {
+        let config = self.config.as_ref();
+
+        if self.running.lock().contains_key(&job.id) {
+            // A one-shot job stays due until its run finishes and disables
+            // it, so it is simply left alone while in flight.
+            if matches!(job.schedule, Schedule::At { .. }) {
+                return;
+            }
+            match job.overlap {
+                OverlapPolicy::Skip => {
+                    tracing::info!(
+                        "Cron job '{}' skipped: previous run still in progress",
+                        job.id
+                    );
+                    claim_next_occurrence(config, &job);
+                    return;
+                }
+                OverlapPolicy::Queue => {
+                    let queued = self
+                        .running
+                        .lock()
+                        .get_mut(&job.id)
+                        .map(|entry| entry.queued = true)
+                        .is_some();
+                    // The previous run may have finished in the meantime, in
+                    // which case the job is simply started below.
+                    if queued {
+                        tracing::info!("Cron job '{}' queued behind its previous run", job.id);
+                        claim_next_occurrence(config, &job);
+                        return;
+                    }
+                }
+                OverlapPolicy::KillPrevious => {
+                    let previous = self.running.lock().remove(&job.id);
+                    if let Some(previous) = previous {
+                        previous.abort.abort();
+                        record_killed_run(config, &job.id, previous.started_at);
+                    }
+                }
+            }
+        }
+
+        if !dependency_gate(config, &job) {
+            return;
+        }
+        claim_next_occurrence(config, &job);
+        self.spawn(job);
+    }
+
+    fn spawn(&self, job: CronJob) {
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        let job_id = job.id.clone();
+        let this = self.clone();
+
+        // Hold the lock across spawn + insert so a run that finishes
+        // immediately cannot look for its entry before it exists.
+        let mut running = self.running.lock();
+        let handle = tokio::spawn(async move {
+            let Ok(permit) = Arc::clone(&this.permits).acquire_owned().await else {
+                return;
+            };
+            let (job_id, success, output) = Box::pin(execute_and_persist_job(
+                this.config.as_ref(),
+                this.security.as_ref(),
+                &job,
+                SCHEDULER_COMPONENT,
+            ))
+            .await;
+            drop(permit);
+            report_result(&this.event_tx, &job_id, success, &output);
+            this.finish(&job_id, token);
+        });
+        running.insert(
+            job_id,
+            RunningJob {
+                token,
+                started_at: Utc::now(),
+                abort: handle.abort_handle(),
+                queued: false,
+            },
+        );
+    }
+
+    fn finish(&self, job_id: &str, token: u64) {
+        let queued = {
+            let mut running = self.running.lock();
+            match running.get(job_id) {
+                Some(entry) if entry.token == token => {
+                    running.remove(job_id).is_some_and(|entry| entry.queued)
+                }
+                _ => false,
+            }
+        };
+        if !queued {
+            return;
+        }
+
+        // Re-read the job so a queued run sees edits, pauses and deletions
+        // made while the previous run was in flight.
+        match get_job(self.config.as_ref(), job_id) {
+            Ok(job) if job.enabled => self.spawn(job),
+            Ok(_) => tracing::info!("Queued run of cron job '{job_id}' dropped: job is paused"),
+            Err(e) => tracing::warn!("Queued run of cron job '{job_id}' dropped: {e}"),
+        }
+    }
+}
+
+/// Advance a recurring job's `next_run` when a run is claimed or skipped so
+/// the next poll does not pick up the same occurrence again.
+fn claim_next_occurrence(config: &Config, job: &CronJob) {
+    if matches!(job.schedule, Schedule::At { .. }) {
+        return;
+    }
+    let result =
+        crate::calendar::next_run(config, &job.schedule, job.calendar.as_ref(), Utc::now())
+            .and_then(|next_run| set_next_run(config, &job.id, next_run));
+    if let Err(e) = result {
+        tracing::warn!("Failed to advance next_run for cron job '{}': {e}", job.id);
+    }
+}
+
+/// Whether `job` may run now. A job whose dependencies are not satisfied
+/// waits for its next occurrence; it is also made due directly when an
+/// upstream job finishes.
+fn dependency_gate(config: &Config, job: &CronJob) -> bool {
+    if job.depends_on.is_empty() {
+        return true;
+    }
+    match dependencies_satisfied(config, job) {
+        Ok(true) => true,
+        Ok(false) => {
+            tracing::debug!(
+                "Cron job '{}' waiting on dependencies; skipping this occurrence",
+                job.id
+            );
+            claim_next_occurrence(config, job);
+            false
+        }
+        Err(e) => {
+            tracing::warn!("Failed to check dependencies of cron job '{}': {e}", job.id);
+            claim_next_occurrence(config, job);
+            false
+        }
+    }
+}
+
+/// Record a run aborted by [`OverlapPolicy::KillPrevious`] and release any
+/// jobs waiting on its failure or completion.
+fn record_killed_run(config: &Config, job_id: &str, started_at: DateTime<Utc>) {
+    let finished_at = Utc::now();
+    tracing::warn!("Cron job '{job_id}' previous run killed by overlap policy");
+    let _ = record_run(
+        config,
+        job_id,
+        started_at,
+        finished_at,
+        "killed",
+        Some("killed: superseded by a newer run (overlap = kill_previous)"),
+        (finished_at - started_at).num_milliseconds(),
+        1,
+    );
+    release_dependents(config, job_id);
+}
+
+fn release_dependents(config: &Config, job_id: &str) {
+    match trigger_dependents(config, job_id) {
+        Ok(triggered) => {
+            for dependent in triggered {
+                tracing::info!("Cron job '{dependent}' triggered by dependency '{job_id}'");
+            }
+        }
+        Err(e) => tracing::warn!("Failed to trigger dependents of cron job '{job_id}': {e}"),
+    }
+}
+
+fn report_result(event_tx: &EventBroadcast, job_id: &str, success: bool, output: &str) {
+    if !success {
+        tracing::warn!("Scheduler job '{job_id}' failed: {output}");
+    }
+    // Broadcast cron result to dashboard/SSE clients.
+    if let Some(tx) = event_tx {
+        let _ = tx.send(serde_json::json!({
+            "type": "cron_result",
+            "job_id": job_id,
+            "success": success,
+            "output": output,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        }));
     }
 }
 
@@ -136,7 +400,9 @@ async fn catch_up_overdue_jobs(
     tracing::info!("Scheduler startup: catch-up complete");
 }
 
-pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
+/// Run `job` once (with retries) outside the polling loop. Returns the
+/// outcome, the output of the last attempt and the number of attempts made.
+pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String, u32) {
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
     Box::pin(execute_job_with_retry(config, &security, job)).await
 }
@@ -145,10 +411,21 @@ async fn execute_job_with_retry(
     config: &Config,
     security: &SecurityPolicy,
     job: &CronJob,
-) -> (bool, String) {
+) -> (bool, String, u32) {
     let mut last_output = String::new();
-    let retries = config.reliability.scheduler_retries;
-    let mut backoff_ms = config.reliability.provider_backoff_ms.max(200);
+    // A per-job retry policy replaces the global reliability settings.
+    let (retries, mut backoff_ms, max_backoff_ms) = match &job.retry {
+        Some(policy) => (
+            policy.max_attempts.max(1) - 1,
+            policy.backoff_ms,
+            policy.max_backoff_ms,
+        ),
+        None => (
+            config.reliability.scheduler_retries,
+            config.reliability.provider_backoff_ms.max(200),
+            30_000,
+        ),
+    };
 
     for attempt in 0..=retries {
         let (success, output) = match job.job_type {
@@ -158,22 +435,22 @@ async fn execute_job_with_retry(
         last_output = output;
 
         if success {
-            return (true, last_output);
+            return (true, last_output, attempt + 1);
         }
 
         if last_output.starts_with("blocked by security policy:") {
             // Deterministic policy violations are not retryable.
-            return (false, last_output);
+            return (false, last_output, attempt + 1);
         }
 
         if attempt < retries {
             let jitter_ms = u64::from(Utc::now().timestamp_subsec_millis() % 250);
             time::sleep(Duration::from_millis(backoff_ms + jitter_ms)).await;
-            backoff_ms = (backoff_ms.saturating_mul(2)).min(30_000);
+            backoff_ms = (backoff_ms.saturating_mul(2)).min(max_backoff_ms);
         }
     }
 
-    (false, last_output)
+    (false, last_output, retries + 1)
 }
 
 async fn process_due_jobs(
@@ -187,7 +464,11 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
+    let runnable: Vec<CronJob> = jobs
+        .into_iter()
+        .filter(|job| dependency_gate(config, job))
+        .collect();
+    let mut in_flight = stream::iter(runnable.into_iter().map(|job| {
         let config = config.clone();
         let security = Arc::clone(security);
         let component = component.to_owned();
@@ -204,19 +485,7 @@ async fn process_due_jobs(
     .buffer_unordered(max_concurrent);
 
     while let Some((job_id, success, output)) = in_flight.next().await {
-        if !success {
-            tracing::warn!("Scheduler job '{job_id}' failed: {output}");
-        }
-        // Broadcast cron result to dashboard/SSE clients.
-        if let Some(tx) = event_tx {
-            let _ = tx.send(serde_json::json!({
-                "type": "cron_result",
-                "job_id": job_id,
-                "success": success,
-                "output": output,
-                "timestamp": chrono::Utc::now().to_rfc3339(),
-            }));
-        }
+        report_result(event_tx, &job_id, success, &output);
     }
 }
 
@@ -230,13 +499,14 @@ async fn execute_and_persist_job(
     warn_if_high_frequency_agent_job(job);
 
     let started_at = Utc::now();
-    let (success, output) = Box::pin(execute_job_with_retry(config, security, job)).await;
+    let (success, output, attempts) = Box::pin(execute_job_with_retry(config, security, job)).await;
     let finished_at = Utc::now();
     let success = Box::pin(persist_job_result(
         config,
         job,
         success,
         &output,
+        attempts,
         started_at,
         finished_at,
     ))
@@ -250,25 +520,8 @@ async fn run_agent_job(
     security: &SecurityPolicy,
     job: &CronJob,
 ) -> (bool, String) {
-    if !security.can_act() {
-        return (
-            false,
-            "blocked by security policy: autonomy is read-only".to_string(),
-        );
-    }
-
-    if security.is_rate_limited() {
-        return (
-            false,
-            "blocked by security policy: rate limit exceeded".to_string(),
-        );
-    }
-
-    if !security.record_action() {
-        return (
-            false,
-            "blocked by security policy: action budget exhausted".to_string(),
-        );
+    if let Err(e) = authorize_agent_run(security) {
+        return (false, e.to_string());
     }
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
@@ -378,12 +631,13 @@ async fn persist_job_result(
     job: &CronJob,
     mut success: bool,
     output: &str,
+    attempts: u32,
     started_at: DateTime<Utc>,
     finished_at: DateTime<Utc>,
 ) -> bool {
     let duration_ms = (finished_at - started_at).num_milliseconds();
 
-    if let Err(e) = deliver_if_configured(config, job, output).await {
+    if let Err(e) = deliver_if_configured(config, &job.delivery, output).await {
         if job.delivery.best_effort {
             tracing::warn!("Cron delivery failed (best_effort): {e}");
         } else {
@@ -400,7 +654,9 @@ async fn persist_job_result(
         if success { "ok" } else { "error" },
         Some(output),
         duration_ms,
+        attempts,
     );
+    release_dependents(config, &job.id);
 
     if is_one_shot_auto_delete(job) {
         if success {
@@ -469,9 +725,30 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
     }
 }
 
-async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) -> Result<()> {
-    let delivery: &DeliveryConfig = &job.delivery;
-    if !delivery.mode.eq_ignore_ascii_case("announce") {
+/// Security gate for a background agent turn (cron agent jobs, Hands,
+/// routine agent actions, headless SOP steps): autonomy must allow acting,
+/// and the turn is charged to the hourly action budget.
+pub(crate) fn authorize_agent_run(security: &SecurityPolicy) -> Result<()> {
+    if !security.can_act() {
+        anyhow::bail!("blocked by security policy: autonomy is read-only");
+    }
+    if security.is_rate_limited() {
+        anyhow::bail!("blocked by security policy: rate limit exceeded");
+    }
+    if !security.record_action() {
+        anyhow::bail!("blocked by security policy: action budget exhausted");
+    }
+    Ok(())
+}
+
+/// Announce `output` as `delivery` describes. Shared by cron jobs, Hands
+/// and routine agent actions; empty output is never announced.
+pub(crate) async fn deliver_if_configured(
+    config: &Config,
+    delivery: &DeliveryConfig,
+    output: &str,
+) -> Result<()> {
+    if !delivery.mode.eq_ignore_ascii_case("announce") || output.trim().is_empty() {
         return Ok(());
     }
 
@@ -701,6 +978,10 @@ mod tests {
             allowed_tools: None,
             uses_memory: true,
             source: "imperative".into(),
+            depends_on: Vec::new(),
+            retry: None,
+            overlap: OverlapPolicy::default(),
+            calendar: None,
             created_at: Utc::now(),
             next_run: Utc::now(),
             last_run: None,
@@ -952,7 +1233,7 @@ mod tests {
         .unwrap();
         let job = test_job("sh ./retry-once.sh");
 
-        let (success, output) = Box::pin(execute_job_with_retry(&config, &security, &job)).await;
+        let (success, output, _) = Box::pin(execute_job_with_retry(&config, &security, &job)).await;
         assert!(success);
         assert!(output.contains("recovered"));
     }
@@ -967,9 +1248,31 @@ mod tests {
 
         let job = test_job("ls always_missing_for_retry_test");
 
-        let (success, output) = Box::pin(execute_job_with_retry(&config, &security, &job)).await;
+        let (success, output, attempts) =
+            Box::pin(execute_job_with_retry(&config, &security, &job)).await;
         assert!(!success);
         assert!(output.contains("always_missing_for_retry_test"));
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn execute_job_with_retry_prefers_per_job_policy() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.reliability.scheduler_retries = 0;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let mut job = test_job("ls always_missing_for_job_retry_test");
+        job.retry = Some(cron::RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 1,
+            max_backoff_ms: 2,
+        });
+
+        let (success, _, attempts) =
+            Box::pin(execute_job_with_retry(&config, &security, &job)).await;
+        assert!(!success);
+        assert_eq!(attempts, 3);
     }
 
     #[tokio::test]
@@ -1065,7 +1368,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
 
         let runs = cron::list_runs(&config, &job.id, 10).unwrap();
@@ -1094,7 +1397,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -1120,7 +1423,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success = persist_job_result(&config, &job, false, "boom", 1, started, finished).await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
@@ -1137,7 +1440,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -1153,7 +1456,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success = persist_job_result(&config, &job, false, "boom", 1, started, finished).await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
@@ -1189,7 +1492,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
 
         let updated = cron::get_job(&config, &job.id).unwrap();
@@ -1228,7 +1531,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
 
         let updated = cron::get_job(&config, &job.id).unwrap();
@@ -1261,7 +1564,7 @@ mod tests {
 
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", 1, started, finished).await;
         assert!(success);
 
         // After reschedule_after_run, At schedule jobs should be disabled
@@ -1281,7 +1584,11 @@ mod tests {
         let job = test_job("echo ok");
 
         // Default delivery mode is not "announce", so should be a no-op.
-        assert!(deliver_if_configured(&config, &job, "x").await.is_ok());
+        assert!(
+            deliver_if_configured(&config, &job.delivery, "x")
+                .await
+                .is_ok()
+        );
     }
 
     #[tokio::test]
@@ -1343,6 +1650,109 @@ mod tests {
         assert_eq!(overdue.len(), 3, "all_overdue_jobs must return all");
     }
 
+    #[tokio::test]
+    async fn persist_job_result_triggers_dependents() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let upstream = cron::add_job(&config, "0 0 1 1 *", "echo upstream").unwrap();
+        let downstream = cron::add_job(&config, "0 0 1 1 *", "echo downstream").unwrap();
+        cron::update_job(
+            &config,
+            &downstream.id,
+            CronJobPatch {
+                depends_on: Some(vec![cron::JobDependency {
+                    job_id: upstream.id.clone(),
+                    on: cron::DependencyCondition::Success,
+                }]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        let started = Utc::now();
+        persist_job_result(&config, &upstream, true, "ok", 1, started, Utc::now()).await;
+
+        let downstream = cron::get_job(&config, &downstream.id).unwrap();
+        assert!(downstream.next_run <= Utc::now());
+    }
+
+    fn overlap_job(config: &Config, overlap: OverlapPolicy) -> CronJob {
+        let job = cron::add_job(config, "* * * * *", "echo overlap").unwrap();
+        cron::update_job(
+            config,
+            &job.id,
+            CronJobPatch {
+                overlap: Some(overlap),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap()
+    }
+
+    fn test_dispatcher(config: &Config) -> Dispatcher {
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        Dispatcher::new(config.clone(), security, None)
+    }
+
+    #[tokio::test]
+    async fn dispatcher_skip_policy_keeps_previous_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = overlap_job(&config, OverlapPolicy::Skip);
+        let dispatcher = test_dispatcher(&config);
+
+        dispatcher.dispatch(job.clone());
+        dispatcher.dispatch(job.clone());
+
+        let running = dispatcher.running.lock();
+        let entry = &running[&job.id];
+        assert_eq!(entry.token, 0);
+        assert!(!entry.queued);
+        entry.abort.abort();
+        drop(running);
+        assert!(cron::get_job(&config, &job.id).unwrap().next_run > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn dispatcher_queue_policy_marks_follow_up_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = overlap_job(&config, OverlapPolicy::Queue);
+        let dispatcher = test_dispatcher(&config);
+
+        dispatcher.dispatch(job.clone());
+        dispatcher.dispatch(job.clone());
+
+        let running = dispatcher.running.lock();
+        let entry = &running[&job.id];
+        assert_eq!(entry.token, 0);
+        assert!(entry.queued);
+        entry.abort.abort();
+    }
+
+    #[tokio::test]
+    async fn dispatcher_kill_previous_records_killed_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = overlap_job(&config, OverlapPolicy::KillPrevious);
+        let dispatcher = test_dispatcher(&config);
+
+        dispatcher.dispatch(job.clone());
+        dispatcher.dispatch(job.clone());
+
+        let running = dispatcher.running.lock();
+        assert_eq!(running[&job.id].token, 1);
+        running[&job.id].abort.abort();
+        drop(running);
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "killed");
+    }
+
     // scan_and_redact_output tests moved to zeroclaw-channels orchestrator
 
     // ── Broadcast / EventBroadcast tests ─────────────────────────────
@@ -1,6 +1,8 @@
+use crate::calendar::{self, CalendarRule};
 use crate::cron::{
-    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
-    next_run_for_schedule, schedule_cron_expression, validate_delivery_config, validate_schedule,
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobDependency, JobType, OverlapPolicy,
+    RetryPolicy, Schedule, SessionTarget, next_run_for_schedule, schedule_cron_expression,
+    validate_delivery_config, validate_schedule,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -125,7 +127,7 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source, uses_memory
+                    allowed_tools, source, uses_memory, depends_on, retry, overlap, calendar
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -144,7 +146,7 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source, uses_memory
+                    allowed_tools, source, uses_memory, depends_on, retry, overlap, calendar
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -158,6 +160,18 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
 }
 
 pub fn remove_job(config: &Config, id: &str) -> Result<()> {
+    let dependents: Vec<String> = list_jobs(config)?
+        .into_iter()
+        .filter(|job| job.depends_on.iter().any(|dep| dep.job_id == id))
+        .map(|job| job.id)
+        .collect();
+    if !dependents.is_empty() {
+        anyhow::bail!(
+            "Cron job '{id}' is a dependency of: {}. Remove or update those jobs first",
+            dependents.join(", ")
+        );
+    }
+
     let changed = with_connection(config, |conn| {
         conn.execute("DELETE FROM cron_jobs WHERE id = ?1", params![id])
             .context("Failed to delete cron job")
@@ -178,7 +192,7 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source, uses_memory
+                    allowed_tools, source, uses_memory, depends_on, retry, overlap, calendar
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -208,7 +222,7 @@ pub fn all_overdue_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJ
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source, uses_memory
+                    allowed_tools, source, uses_memory, depends_on, retry, overlap, calendar
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC",
@@ -273,9 +287,34 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(uses_memory) = patch.uses_memory {
         job.uses_memory = uses_memory;
     }
+    if let Some(depends_on) = patch.depends_on {
+        validate_dependencies(config, Some(&job.id), &depends_on)?;
+        job.depends_on = depends_on;
+    }
+    if let Some(retry) = patch.retry {
+        // `max_attempts: 0` clears the per-job policy and falls back to
+        // `reliability.scheduler_retries`.
+        job.retry = if retry.max_attempts == 0 {
+            None
+        } else {
+            Some(retry)
+        };
+    }
+    if let Some(overlap) = patch.overlap {
+        job.overlap = overlap;
+    }
+    if let Some(calendar) = patch.calendar {
+        job.calendar = if calendar.name.trim().is_empty() {
+            None
+        } else {
+            Some(calendar)
+        };
+        schedule_changed = true;
+    }
 
     if schedule_changed {
-        job.next_run = next_run_for_schedule(&job.schedule, Utc::now())?;
+        job.next_run =
+            calendar::next_run(config, &job.schedule, job.calendar.as_ref(), Utc::now())?;
     }
 
     with_connection(config, |conn| {
@@ -283,8 +322,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 allowed_tools = ?12, next_run = ?13, uses_memory = ?14
-             WHERE id = ?15",
+                 allowed_tools = ?12, next_run = ?13, uses_memory = ?14, depends_on = ?15,
+                 retry = ?16, overlap = ?17, calendar = ?18
+             WHERE id = ?19",
             params![
                 job.expression,
                 job.command,
@@ -300,6 +340,10 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 encode_allowed_tools(job.allowed_tools.as_ref())?,
                 job.next_run.to_rfc3339(),
                 if job.uses_memory { 1 } else { 0 },
+                encode_depends_on(&job.depends_on)?,
+                job.retry.as_ref().map(serde_json::to_string).transpose()?,
+                job.overlap.as_str(),
+                encode_calendar(job.calendar.as_ref())?,
                 job.id,
             ],
         )
@@ -355,7 +399,7 @@ pub fn reschedule_after_run(
             Ok(())
         })
     } else {
-        let next_run = next_run_for_schedule(&job.schedule, now)?;
+        let next_run = calendar::next_run(config, &job.schedule, job.calendar.as_ref(), now)?;
         with_connection(config, |conn| {
             conn.execute(
                 "UPDATE cron_jobs
@@ -383,6 +427,7 @@ pub fn record_run(
     status: &str,
     output: Option<&str>,
     duration_ms: i64,
+    attempts: u32,
 ) -> Result<()> {
     let bounded_output = output.map(truncate_cron_output);
     with_connection(config, |conn| {
@@ -392,8 +437,8 @@ pub fn record_run(
         let tx = conn.unchecked_transaction()?;
 
         tx.execute(
-            "INSERT INTO cron_runs (job_id, started_at, finished_at, status, output, duration_ms)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO cron_runs (job_id, started_at, finished_at, status, output, duration_ms, attempts)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 job_id,
                 started_at.to_rfc3339(),
@@ -401,6 +446,7 @@ pub fn record_run(
                 status,
                 bounded_output.as_deref(),
                 duration_ms,
+                attempts,
             ],
         )
         .context("Failed to insert cron run")?;
@@ -448,7 +494,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     with_connection(config, |conn| {
         let lim = i64::try_from(limit.max(1)).context("Run history limit overflow")?;
         let mut stmt = conn.prepare(
-            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms
+            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms, attempts
              FROM cron_runs
              WHERE job_id = ?1
              ORDER BY started_at DESC, id DESC
@@ -466,6 +512,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
                 status: row.get(4)?,
                 output: row.get(5)?,
                 duration_ms: row.get(6)?,
+                attempts: row.get(7)?,
             })
         })?;
 
@@ -477,6 +524,98 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     })
 }
 
+/// Check a `depends_on` list before it is stored: every upstream job must
+/// exist, a job cannot depend on itself, and the edges must not form a cycle.
+/// `job_id` is `None` for a job that has not been created yet.
+pub fn validate_dependencies(
+    config: &Config,
+    job_id: Option<&str>,
+    depends_on: &[JobDependency],
+) -> Result<()> {
+    if depends_on.is_empty() {
+        return Ok(());
+    }
+
+    let jobs = list_jobs(config)?;
+    for dep in depends_on {
+        if job_id == Some(dep.job_id.as_str()) {
+            anyhow::bail!("Cron job cannot depend on itself");
+        }
+        if !jobs.iter().any(|job| job.id == dep.job_id) {
+            anyhow::bail!("Dependency '{}' is not an existing cron job", dep.job_id);
+        }
+    }
+
+    // A new job has no dependents yet, so it cannot close a cycle.
+    let Some(job_id) = job_id else {
+        return Ok(());
+    };
+
+    // Walk upstream from the proposed dependencies; reaching `job_id` again
+    // means the new edges would close a cycle.
+    let mut stack: Vec<&str> = depends_on.iter().map(|dep| dep.job_id.as_str()).collect();
+    let mut seen = std::collections::HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == job_id {
+            anyhow::bail!("Cron job dependencies would form a cycle through '{job_id}'");
+        }
+        if !seen.insert(current) {
+            continue;
+        }
+        if let Some(job) = jobs.iter().find(|job| job.id == current) {
+            stack.extend(job.depends_on.iter().map(|dep| dep.job_id.as_str()));
+        }
+    }
+    Ok(())
+}
+
+/// Whether every upstream job of `job` has finished, with an outcome matching
+/// its condition, since `job` last started (or was created).
+pub fn dependencies_satisfied(config: &Config, job: &CronJob) -> Result<bool> {
+    let since = list_runs(config, &job.id, 1)?
+        .first()
+        .map_or(job.created_at, |run| run.started_at);
+
+    for dep in &job.depends_on {
+        let Some(run) = list_runs(config, &dep.job_id, 1)?.into_iter().next() else {
+            return Ok(false);
+        };
+        if run.finished_at <= since || !dep.on.matches(&run.status) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Make enabled jobs that depend on `job_id` due now when their dependencies
+/// are satisfied. Returns the ids of the jobs that were triggered.
+pub fn trigger_dependents(config: &Config, job_id: &str) -> Result<Vec<String>> {
+    let now = Utc::now();
+    let mut triggered = Vec::new();
+    for job in list_jobs(config)? {
+        if !job.enabled || !job.depends_on.iter().any(|dep| dep.job_id == job_id) {
+            continue;
+        }
+        if dependencies_satisfied(config, &job)? {
+            set_next_run(config, &job.id, now)?;
+            triggered.push(job.id);
+        }
+    }
+    Ok(triggered)
+}
+
+/// Move a job's `next_run` without touching its last-run state.
+pub fn set_next_run(config: &Config, job_id: &str, next_run: DateTime<Utc>) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET next_run = ?1 WHERE id = ?2",
+            params![next_run.to_rfc3339(), job_id],
+        )
+        .context("Failed to update cron job next_run")?;
+        Ok(())
+    })
+}
+
 fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
     let parsed = DateTime::parse_from_rfc3339(raw)
         .with_context(|| format!("Invalid RFC3339 timestamp in cron DB: {raw}"))?;
@@ -502,6 +641,10 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let allowed_tools_raw: Option<String> = row.get(17)?;
     let source: Option<String> = row.get(18)?;
     let uses_memory: Option<i64> = row.get(19)?;
+    let depends_on_raw: Option<String> = row.get(20)?;
+    let retry_raw: Option<String> = row.get(21)?;
+    let overlap: Option<String> = row.get(22)?;
+    let calendar_raw: Option<String> = row.get(23)?;
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -518,6 +661,12 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         delete_after_run: row.get::<_, i64>(11)? != 0,
         source: source.unwrap_or_else(|| "imperative".to_string()),
         uses_memory: uses_memory != Some(0),
+        depends_on: decode_depends_on(depends_on_raw.as_deref()).map_err(sql_conversion_error)?,
+        retry: decode_retry(retry_raw.as_deref()).map_err(sql_conversion_error)?,
+        overlap: overlap
+            .as_deref()
+            .map_or_else(OverlapPolicy::default, OverlapPolicy::parse),
+        calendar: decode_calendar(calendar_raw.as_deref()).map_err(sql_conversion_error)?,
         created_at: parse_rfc3339(&created_at_raw).map_err(sql_conversion_error)?,
         next_run: parse_rfc3339(&next_run_raw).map_err(sql_conversion_error)?,
         last_run: match last_run_raw {
@@ -580,6 +729,57 @@ fn decode_allowed_tools(raw: Option<&str>) -> Result<Option<Vec<String>>> {
     Ok(None)
 }
 
+fn encode_depends_on(depends_on: &[JobDependency]) -> Result<Option<String>> {
+    if depends_on.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(depends_on)
+        .map(Some)
+        .context("Failed to serialize cron depends_on")
+}
+
+fn decode_depends_on(raw: Option<&str>) -> Result<Vec<JobDependency>> {
+    if let Some(raw) = raw {
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            return serde_json::from_str(trimmed)
+                .with_context(|| format!("Failed to parse cron depends_on JSON: {trimmed}"));
+        }
+    }
+    Ok(Vec::new())
+}
+
+fn decode_retry(raw: Option<&str>) -> Result<Option<RetryPolicy>> {
+    if let Some(raw) = raw {
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            return serde_json::from_str(trimmed)
+                .map(Some)
+                .with_context(|| format!("Failed to parse cron retry JSON: {trimmed}"));
+        }
+    }
+    Ok(None)
+}
+
+fn encode_calendar(calendar: Option<&CalendarRule>) -> Result<Option<String>> {
+    calendar
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize cron calendar")
+}
+
+fn decode_calendar(raw: Option<&str>) -> Result<Option<CalendarRule>> {
+    if let Some(raw) = raw {
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            return serde_json::from_str(trimmed)
+                .map(Some)
+                .with_context(|| format!("Failed to parse cron calendar JSON: {trimmed}"));
+        }
+    }
+    Ok(None)
+}
+
 /// Synchronize declarative cron job definitions from config into the database.
 ///
 /// For each declarative job (identified by `id`):
@@ -660,6 +860,7 @@ pub fn sync_declarative_jobs(
             let allowed_tools_json = encode_allowed_tools(decl.allowed_tools.as_ref())?;
             let command = decl.command.as_deref().unwrap_or("");
             let delete_after_run = matches!(decl.schedule, CronScheduleDecl::At { .. });
+            let calendar_json = encode_calendar(decl.calendar.as_ref())?;
 
             // Check if job already exists.
             let exists: bool = conn
@@ -672,23 +873,27 @@ pub fn sync_declarative_jobs(
                 // Update existing declarative job — preserve runtime state
                 // (next_run, last_run, last_status, last_output, created_at).
                 // Only update the schedule's next_run if the schedule itself changed.
-                let current_schedule_raw: Option<String> = conn
-                    .prepare("SELECT schedule FROM cron_jobs WHERE id = ?1")?
-                    .query_row(params![decl.id], |row| row.get(0))
+                let current: Option<(Option<String>, Option<String>)> = conn
+                    .prepare("SELECT schedule, calendar FROM cron_jobs WHERE id = ?1")?
+                    .query_row(params![decl.id], |row| Ok((row.get(0)?, row.get(1)?)))
                     .ok();
 
-                let schedule_changed = current_schedule_raw.as_deref() != Some(&schedule_json);
+                // A new calendar moves occurrences just like a new schedule.
+                let schedule_changed = current.as_ref().is_none_or(|(schedule, calendar)| {
+                    schedule.as_deref() != Some(&schedule_json) || *calendar != calendar_json
+                });
 
                 if schedule_changed {
-                    let next_run = next_run_for_schedule(&schedule, now)?;
+                    let next_run =
+                        calendar::next_run(config, &schedule, decl.calendar.as_ref(), now)?;
                     conn.execute(
                         "UPDATE cron_jobs
                          SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4,
                              prompt = ?5, name = ?6, session_target = ?7, model = ?8,
                              enabled = ?9, delivery = ?10, delete_after_run = ?11,
                              allowed_tools = ?12, source = 'declarative', next_run = ?13,
-                             uses_memory = ?14
-                         WHERE id = ?15",
+                             uses_memory = ?14, calendar = ?15
+                         WHERE id = ?16",
                         params![
                             expression,
                             command,
@@ -704,6 +909,7 @@ pub fn sync_declarative_jobs(
                             allowed_tools_json,
                             next_run.to_rfc3339(),
                             if decl.uses_memory { 1 } else { 0 },
+                            calendar_json,
                             decl.id,
                         ],
                     )
@@ -717,8 +923,8 @@ pub fn sync_declarative_jobs(
                              prompt = ?5, name = ?6, session_target = ?7, model = ?8,
                              enabled = ?9, delivery = ?10, delete_after_run = ?11,
                              allowed_tools = ?12, source = 'declarative',
-                             uses_memory = ?13
-                         WHERE id = ?14",
+                             uses_memory = ?13, calendar = ?14
+                         WHERE id = ?15",
                         params![
                             expression,
                             command,
@@ -733,6 +939,7 @@ pub fn sync_declarative_jobs(
                             if delete_after_run { 1 } else { 0 },
                             allowed_tools_json,
                             if decl.uses_memory { 1 } else { 0 },
+                            calendar_json,
                             decl.id,
                         ],
                     )
@@ -744,13 +951,13 @@ pub fn sync_declarative_jobs(
                 tracing::debug!(job_id = %decl.id, "Updated declarative cron job");
             } else {
                 // Insert new declarative job.
-                let next_run = next_run_for_schedule(&schedule, now)?;
+                let next_run = calendar::next_run(config, &schedule, decl.calendar.as_ref(), now)?;
                 conn.execute(
                     "INSERT INTO cron_jobs (
                         id, expression, command, schedule, job_type, prompt, name,
                         session_target, model, enabled, delivery, delete_after_run,
-                        allowed_tools, source, uses_memory, created_at, next_run
-                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 'declarative', ?14, ?15, ?16)",
+                        allowed_tools, source, uses_memory, created_at, next_run, calendar
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 'declarative', ?14, ?15, ?16, ?17)",
                     params![
                         decl.id,
                         expression,
@@ -768,6 +975,7 @@ pub fn sync_declarative_jobs(
                         if decl.uses_memory { 1 } else { 0 },
                         now.to_rfc3339(),
                         next_run.to_rfc3339(),
+                        calendar_json,
                     ],
                 )
                 .with_context(|| {
@@ -852,8 +1060,8 @@ fn convert_delivery_decl(decl: &zeroclaw_config::schema::DeliveryConfigDecl) ->
     }
 }
 
-fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
-    let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
+pub(crate) fn add_column_if_missing(conn: &Connection, table: &str, name: &str, sql_type: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
     let mut rows = stmt.query([])?;
     while let Some(row) = rows.next()? {
         let col_name: String = row.get(1)?;
@@ -868,17 +1076,17 @@ fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Resul
     // Tolerate "duplicate column name" errors to handle the race where
     // another process adds the column between our PRAGMA check and ALTER.
     match conn.execute(
-        &format!("ALTER TABLE cron_jobs ADD COLUMN {name} {sql_type}"),
+        &format!("ALTER TABLE {table} ADD COLUMN {name} {sql_type}"),
         [],
     ) {
         Ok(_) => Ok(()),
         Err(rusqlite::Error::SqliteFailure(err, Some(ref msg)))
             if msg.contains("duplicate column name") =>
         {
-            tracing::debug!("Column cron_jobs.{name} already exists (concurrent migration): {err}");
+            tracing::debug!("Column {table}.{name} already exists (concurrent migration): {err}");
             Ok(())
         }
-        Err(e) => Err(e).with_context(|| format!("Failed to add cron_jobs.{name}")),
+        Err(e) => Err(e).with_context(|| format!("Failed to add {table}.{name}")),
     }
 }
 
@@ -932,18 +1140,48 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     )
     .context("Failed to initialize cron schema")?;
 
-    add_column_if_missing(&conn, "schedule", "TEXT")?;
-    add_column_if_missing(&conn, "job_type", "TEXT NOT NULL DEFAULT 'shell'")?;
-    add_column_if_missing(&conn, "prompt", "TEXT")?;
-    add_column_if_missing(&conn, "name", "TEXT")?;
-    add_column_if_missing(&conn, "session_target", "TEXT NOT NULL DEFAULT 'isolated'")?;
-    add_column_if_missing(&conn, "model", "TEXT")?;
-    add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
-    add_column_if_missing(&conn, "delivery", "TEXT")?;
-    add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
-    add_column_if_missing(&conn, "allowed_tools", "TEXT")?;
-    add_column_if_missing(&conn, "source", "TEXT DEFAULT 'imperative'")?;
-    add_column_if_missing(&conn, "uses_memory", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(&conn, "cron_jobs", "schedule", "TEXT")?;
+    add_column_if_missing(
+        &conn,
+        "cron_jobs",
+        "job_type",
+        "TEXT NOT NULL DEFAULT 'shell'",
+    )?;
+    add_column_if_missing(&conn, "cron_jobs", "prompt", "TEXT")?;
+    add_column_if_missing(&conn, "cron_jobs", "name", "TEXT")?;
+    add_column_if_missing(
+        &conn,
+        "cron_jobs",
+        "session_target",
+        "TEXT NOT NULL DEFAULT 'isolated'",
+    )?;
+    add_column_if_missing(&conn, "cron_jobs", "model", "TEXT")?;
+    add_column_if_missing(&conn, "cron_jobs", "enabled", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(&conn, "cron_jobs", "delivery", "TEXT")?;
+    add_column_if_missing(
+        &conn,
+        "cron_jobs",
+        "delete_after_run",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(&conn, "cron_jobs", "allowed_tools", "TEXT")?;
+    add_column_if_missing(&conn, "cron_jobs", "source", "TEXT DEFAULT 'imperative'")?;
+    add_column_if_missing(
+        &conn,
+        "cron_jobs",
+        "uses_memory",
+        "INTEGER NOT NULL DEFAULT 1",
+    )?;
+    add_column_if_missing(&conn, "cron_jobs", "depends_on", "TEXT")?;
+    add_column_if_missing(&conn, "cron_jobs", "retry", "TEXT")?;
+    add_column_if_missing(
+        &conn,
+        "cron_jobs",
+        "overlap",
+        "TEXT NOT NULL DEFAULT 'skip'",
+    )?;
+    add_column_if_missing(&conn, "cron_jobs", "calendar", "TEXT")?;
+    add_column_if_missing(&conn, "cron_runs", "attempts", "INTEGER NOT NULL DEFAULT 1")?;
 
     f(&conn)
 }
@@ -1358,7 +1596,7 @@ mod tests {
         for idx in 0..3 {
             let start = base + ChronoDuration::seconds(idx);
             let end = start + ChronoDuration::milliseconds(100);
-            record_run(&config, &job.id, start, end, "ok", Some("done"), 100).unwrap();
+            record_run(&config, &job.id, start, end, "ok", Some("done"), 100, 1).unwrap();
         }
 
         let runs = list_runs(&config, &job.id, 10).unwrap();
@@ -1379,6 +1617,7 @@ mod tests {
             "ok",
             Some("ok"),
             5,
+            1,
         )
         .unwrap();
 
@@ -1387,6 +1626,210 @@ mod tests {
         assert!(runs.is_empty());
     }
 
+    #[test]
+    fn update_job_persists_dependencies_retry_and_overlap() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let upstream = add_job(&config, "*/5 * * * *", "echo upstream").unwrap();
+        let job = add_job(&config, "*/5 * * * *", "echo downstream").unwrap();
+
+        update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                depends_on: Some(vec![JobDependency {
+                    job_id: upstream.id.clone(),
+                    on: crate::cron::DependencyCondition::Failure,
+                }]),
+                retry: Some(RetryPolicy {
+                    max_attempts: 3,
+                    ..RetryPolicy::default()
+                }),
+                overlap: Some(OverlapPolicy::KillPrevious),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.depends_on.len(), 1);
+        assert_eq!(stored.depends_on[0].job_id, upstream.id);
+        assert_eq!(stored.retry.as_ref().unwrap().max_attempts, 3);
+        assert_eq!(stored.overlap, OverlapPolicy::KillPrevious);
+
+        let cleared = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                depends_on: Some(Vec::new()),
+                retry: Some(RetryPolicy {
+                    max_attempts: 0,
+                    ..RetryPolicy::default()
+                }),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(cleared.depends_on.is_empty());
+        assert!(cleared.retry.is_none());
+        assert_eq!(cleared.overlap, OverlapPolicy::KillPrevious);
+    }
+
+    #[test]
+    fn update_job_applies_calendar_to_next_run() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        // Every day of the coming week is a holiday.
+        let today = Utc::now().date_naive();
+        config.calendars.insert(
+            "holidays".into(),
+            zeroclaw_config::schema::CalendarConfig {
+                dates: vec![format!("{today}..{}", today + ChronoDuration::days(7))],
+                tz: Some("UTC".into()),
+                ..zeroclaw_config::schema::CalendarConfig::default()
+            },
+        );
+        let job = add_job(&config, "0 9 * * *", "echo report").unwrap();
+
+        let rule = CalendarRule {
+            name: "holidays".into(),
+            mode: zeroclaw_config::schema::CalendarMode::Exclude,
+        };
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                calendar: Some(rule.clone()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.calendar, Some(rule));
+        assert!(updated.next_run.date_naive() > today + ChronoDuration::days(7));
+
+        let cleared = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                calendar: Some(CalendarRule {
+                    name: String::new(),
+                    mode: zeroclaw_config::schema::CalendarMode::Exclude,
+                }),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(cleared.calendar.is_none());
+        assert!(cleared.next_run < updated.next_run);
+
+        let unknown = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                calendar: Some(CalendarRule {
+                    name: "missing".into(),
+                    mode: zeroclaw_config::schema::CalendarMode::Exclude,
+                }),
+                ..CronJobPatch::default()
+            },
+        );
+        assert!(unknown.is_err());
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_missing_self_and_cycles() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let a = add_job(&config, "*/5 * * * *", "echo a").unwrap();
+        let b = add_job(&config, "*/5 * * * *", "echo b").unwrap();
+        let dep = |id: &str| JobDependency {
+            job_id: id.to_string(),
+            on: crate::cron::DependencyCondition::Success,
+        };
+
+        let err = validate_dependencies(&config, None, &[dep("missing")]).unwrap_err();
+        assert!(err.to_string().contains("not an existing cron job"));
+        let err = validate_dependencies(&config, Some(&a.id), &[dep(&a.id)]).unwrap_err();
+        assert!(err.to_string().contains("itself"));
+
+        update_job(
+            &config,
+            &b.id,
+            CronJobPatch {
+                depends_on: Some(vec![dep(&a.id)]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        let err = update_job(
+            &config,
+            &a.id,
+            CronJobPatch {
+                depends_on: Some(vec![dep(&b.id)]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        let err = remove_job(&config, &a.id).unwrap_err();
+        assert!(err.to_string().contains("is a dependency of"));
+    }
+
+    #[test]
+    fn trigger_dependents_matches_condition_and_run_order() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let upstream = add_job(&config, "0 0 1 1 *", "echo upstream").unwrap();
+        let on_success = add_job(&config, "0 0 1 1 *", "echo success").unwrap();
+        let on_failure = add_job(&config, "0 0 1 1 *", "echo failure").unwrap();
+        for (job, on) in [
+            (&on_success, crate::cron::DependencyCondition::Success),
+            (&on_failure, crate::cron::DependencyCondition::Failure),
+        ] {
+            update_job(
+                &config,
+                &job.id,
+                CronJobPatch {
+                    depends_on: Some(vec![JobDependency {
+                        job_id: upstream.id.clone(),
+                        on,
+                    }]),
+                    ..CronJobPatch::default()
+                },
+            )
+            .unwrap();
+        }
+
+        assert!(
+            trigger_dependents(&config, &upstream.id)
+                .unwrap()
+                .is_empty()
+        );
+
+        let finished = Utc::now();
+        record_run(&config, &upstream.id, finished, finished, "ok", None, 0, 1).unwrap();
+        let triggered = trigger_dependents(&config, &upstream.id).unwrap();
+        assert_eq!(triggered, vec![on_success.id.clone()]);
+        assert!(get_job(&config, &on_success.id).unwrap().next_run <= Utc::now());
+
+        // Once the dependent has started after the upstream finished, the
+        // same upstream run no longer releases it.
+        record_run(
+            &config,
+            &on_success.id,
+            finished + ChronoDuration::seconds(1),
+            finished + ChronoDuration::seconds(2),
+            "ok",
+            None,
+            0,
+            1,
+        )
+        .unwrap();
+        let job = get_job(&config, &on_success.id).unwrap();
+        assert!(!dependencies_satisfied(&config, &job).unwrap());
+    }
+
     #[test]
     fn record_run_truncates_large_output() {
         let tmp = TempDir::new().unwrap();
@@ -1402,6 +1845,7 @@ mod tests {
             "ok",
             Some(&output),
             1,
+            1,
         )
         .unwrap();
 
@@ -1480,6 +1924,7 @@ mod tests {
             uses_memory: true,
             session_target: None,
             delivery: None,
+            calendar: None,
         }
     }
 
@@ -1500,6 +1945,7 @@ mod tests {
             uses_memory: true,
             session_target: None,
             delivery: None,
+            calendar: None,
         }
     }
 
@@ -1655,6 +2101,7 @@ mod tests {
             uses_memory: true,
             session_target: None,
             delivery: None,
+            calendar: None,
         };
 
         sync_declarative_jobs(&config, &[decl]).unwrap();
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use zeroclaw_config::schema::CalendarRule;
 
 /// Try to deserialize a `serde_json::Value` as `T`.  If the value is a JSON
 /// string that looks like an object (i.e. the LLM double-serialized it), parse
@@ -28,6 +29,7 @@ pub fn deserialize_maybe_stringified<T: serde::de::DeserializeOwned>(
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum JobType {
     #[default]
@@ -60,6 +62,7 @@ impl TryFrom<&str> for JobType {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SessionTarget {
     #[default]
@@ -85,6 +88,7 @@ impl SessionTarget {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum Schedule {
     Cron {
@@ -93,6 +97,7 @@ pub enum Schedule {
         tz: Option<String>,
     },
     At {
+        #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
         at: DateTime<Utc>,
     },
     Every {
@@ -101,6 +106,7 @@ pub enum Schedule {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DeliveryConfig {
     #[serde(default)]
     pub mode: String,
@@ -127,11 +133,120 @@ pub fn default_true() -> bool {
     true
 }
 
+/// Which outcome of an upstream job releases a dependent job.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyCondition {
+    /// Upstream run finished with status `ok`.
+    #[default]
+    Success,
+    /// Upstream run finished with status `error` or was killed.
+    Failure,
+    /// Upstream run finished, whatever the outcome.
+    Completion,
+}
+
+impl DependencyCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Completion => "completion",
+        }
+    }
+
+    /// Whether a recorded run status satisfies this condition.
+    pub fn matches(&self, status: &str) -> bool {
+        match self {
+            Self::Success => status == "ok",
+            Self::Failure => matches!(status, "error" | "killed"),
+            Self::Completion => matches!(status, "ok" | "error" | "killed"),
+        }
+    }
+}
+
+/// A `depends_on` edge: run this job after `job_id` finishes with `on`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct JobDependency {
+    pub job_id: String,
+    #[serde(default)]
+    pub on: DependencyCondition,
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    60_000
+}
+
+/// Per-job retry policy. Overrides `reliability.scheduler_retries` and
+/// `reliability.provider_backoff_ms` for this job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct RetryPolicy {
+    /// Total attempts including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further attempt.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Upper bound for the doubled delay.
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff_ms: default_retry_backoff_ms(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+        }
+    }
+}
+
+/// What the scheduler does when a job comes due while its previous run is
+/// still in flight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// Drop the new occurrence and wait for the next one.
+    #[default]
+    Skip,
+    /// Start the new occurrence as soon as the previous run finishes.
+    Queue,
+    /// Abort the previous run (recorded as `killed`) and start the new one.
+    KillPrevious,
+}
+
+impl OverlapPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Queue => "queue",
+            Self::KillPrevious => "kill_previous",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "queue" => Self::Queue,
+            "kill_previous" => Self::KillPrevious,
+            _ => Self::Skip,
+        }
+    }
+}
+
 fn default_source() -> String {
     "imperative".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CronJob {
     pub id: String,
     pub expression: String,
@@ -158,8 +273,25 @@ pub struct CronJob {
     /// How the job was created: `"imperative"` (CLI/API) or `"declarative"` (config).
     #[serde(default = "default_source")]
     pub source: String,
+    /// Upstream jobs that must finish (per their condition) before this job
+    /// runs. A job with dependencies also runs as soon as they are satisfied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<JobDependency>,
+    /// Per-job retry policy; `None` uses the global reliability settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// Behaviour when the job comes due while a previous run is in flight.
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+    /// Calendar that filters the job's occurrences; `next_run` already
+    /// honours it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<CalendarRule>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub created_at: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub next_run: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "Option<String>"))]
     pub last_run: Option<DateTime<Utc>>,
     pub last_status: Option<String>,
     pub last_output: Option<String>,
@@ -174,6 +306,13 @@ pub struct CronRun {
     pub status: String,
     pub output: Option<String>,
     pub duration_ms: Option<i64>,
+    /// Attempts made for this run, including retries.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+}
+
+fn default_attempts() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -189,6 +328,13 @@ pub struct CronJobPatch {
     pub delete_after_run: Option<bool>,
     pub allowed_tools: Option<Vec<String>>,
     pub uses_memory: Option<bool>,
+    /// Replaces the dependency list; an empty list clears it.
+    pub depends_on: Option<Vec<JobDependency>>,
+    /// Replaces the retry policy; `max_attempts: 0` clears it.
+    pub retry: Option<RetryPolicy>,
+    pub overlap: Option<OverlapPolicy>,
+    /// Replaces the calendar rule; an empty `name` clears it.
+    pub calendar: Option<CalendarRule>,
 }
 
 #[cfg(test)]
@@ -242,6 +388,33 @@ mod tests {
         assert_eq!(JobType::try_from("AgEnT").unwrap(), JobType::Agent);
     }
 
+    #[test]
+    fn dependency_condition_matches_run_status() {
+        assert!(DependencyCondition::Success.matches("ok"));
+        assert!(!DependencyCondition::Success.matches("error"));
+        assert!(DependencyCondition::Failure.matches("error"));
+        assert!(DependencyCondition::Failure.matches("killed"));
+        assert!(!DependencyCondition::Failure.matches("ok"));
+        assert!(DependencyCondition::Completion.matches("ok"));
+        assert!(DependencyCondition::Completion.matches("killed"));
+        assert!(!DependencyCondition::Completion.matches("skipped"));
+    }
+
+    #[test]
+    fn dependency_and_overlap_deserialize_with_defaults() {
+        let dep: JobDependency =
+            serde_json::from_value(serde_json::json!({"job_id": "a"})).unwrap();
+        assert_eq!(dep.on, DependencyCondition::Success);
+        let policy: OverlapPolicy =
+            serde_json::from_value(serde_json::json!("kill_previous")).unwrap();
+        assert_eq!(policy, OverlapPolicy::KillPrevious);
+        assert_eq!(OverlapPolicy::parse(policy.as_str()), policy);
+        let retry: RetryPolicy =
+            serde_json::from_value(serde_json::json!({"max_attempts": 3})).unwrap();
+        assert_eq!(retry.backoff_ms, 1_000);
+        assert_eq!(retry.max_backoff_ms, 60_000);
+    }
+
     #[test]
     fn job_type_try_from_rejects_invalid_values() {
         assert!(JobType::try_from("").is_err());
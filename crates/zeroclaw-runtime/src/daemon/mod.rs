@@ -266,9 +266,25 @@ pub async fn run(
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.hands.enabled {
+        let hands_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "hands",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = hands_cfg.clone();
+                async move { Box::pin(crate::hands::scheduler::run(cfg)).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("hands");
+        tracing::info!("Hands disabled; hands supervisor not started");
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
+    println!("   Components: gateway, channels, heartbeat, scheduler, hands");
     if config.gateway.require_pairing {
         println!("   Pairing:    enabled (code appears in gateway output above)");
     }
@@ -445,20 +461,54 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
 
         // Collect runnable tasks (active only, sorted by priority)
         let mut tasks = engine.collect_runnable_tasks().await?;
+
+        // Drop tasks whose calendar rules out today
+        let now = Utc::now();
+        let scheduled = tasks.len();
+        tasks.retain(|task| {
+            crate::calendar::allows(&config, task.calendar.as_ref(), now).unwrap_or_else(|e| {
+                tracing::warn!("Heartbeat task calendar check failed: {e}");
+                true
+            })
+        });
+
+        // Suppress tasks while an upstream task is failing
+        let upstreams = tasks.clone();
+        tasks.retain(|task| {
+            let failing = crate::heartbeat::escalation::failing_upstreams(
+                &config.workspace_dir,
+                task,
+                &upstreams,
+            );
+            if !failing.is_empty() {
+                tracing::info!(
+                    task = %task.text,
+                    upstream = %failing.join(","),
+                    "💓 Heartbeat task suppressed: upstream failing"
+                );
+            }
+            failing.is_empty()
+        });
+        let filtered = tasks.len() < scheduled;
         let has_high_priority = tasks.iter().any(|t| t.priority == TaskPriority::High);
 
         if tasks.is_empty() {
-            if let Some(fallback) = config
-                .heartbeat
-                .message
-                .as_deref()
-                .map(str::trim)
-                .filter(|m| !m.is_empty())
+            if !filtered
+                && let Some(fallback) = config
+                    .heartbeat
+                    .message
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
             {
                 tasks.push(HeartbeatTask {
                     text: fallback.to_string(),
                     priority: TaskPriority::Medium,
                     status: TaskStatus::Active,
+                    calendar: None,
+                    id: None,
+                    depends_on: Vec::new(),
+                    escalation: None,
                 });
             } else {
                 #[allow(clippy::cast_precision_loss)]
@@ -722,6 +772,26 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                     );
                     crate::health::mark_component_error("heartbeat", e.to_string());
                     tracing::warn!("Heartbeat task failed: {e}");
+                    if task.escalation.is_some() {
+                        match crate::heartbeat::store::consecutive_failures(
+                            &config.workspace_dir,
+                            &task.text,
+                        ) {
+                            Ok(failures) => {
+                                crate::heartbeat::escalation::escalate(
+                                    &config,
+                                    task,
+                                    failures,
+                                    &e.to_string(),
+                                    delivery.as_ref(),
+                                )
+                                .await;
+                            }
+                            Err(err) => {
+                                tracing::warn!("Heartbeat failure count unavailable: {err}");
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -12,6 +12,7 @@ const COMMAND_VERSION_PREVIEW_CHARS: usize = 60;
 // ── Diagnostic item ──────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Ok,
@@ -21,6 +22,7 @@ pub enum Severity {
 
 /// Structured diagnostic result for programmatic consumption (web dashboard, API).
 #[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DiagResult {
     pub severity: Severity,
     pub category: String,
@@ -83,6 +85,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
     check_config_semantics(config, &mut items);
     check_workspace(config, &mut items);
     check_daemon_state(config, &mut items);
+    check_sop_runs(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
 
@@ -787,6 +790,51 @@ fn workspace_probe_path(workspace_dir: &Path) -> std::path::PathBuf {
     ))
 }
 
+// ── SOP run journal ──────────────────────────────────────────────
+
+fn check_sop_runs(config: &Config, items: &mut Vec<DiagItem>) {
+    use crate::sop::store;
+
+    let cat = "sop";
+    if !store::db_path(&config.workspace_dir).exists() {
+        return;
+    }
+
+    let runs = match store::load_active_runs(&config.workspace_dir) {
+        Ok(runs) => runs,
+        Err(e) => {
+            items.push(DiagItem::error(
+                cat,
+                format!("cannot read SOP run journal: {e:#}"),
+            ));
+            return;
+        }
+    };
+
+    let sops = crate::sop::load_sops(
+        &config.workspace_dir,
+        config.sop.sops_dir.as_deref(),
+        crate::sop::parse_execution_mode(&config.sop.default_execution_mode),
+    );
+    let mut resumable = 0;
+    for run in &runs {
+        match store::orphan_reason(&sops, run) {
+            Some(reason) => items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "orphaned run {} ({}, step {}): {reason}",
+                    run.run_id, run.status, run.current_step
+                ),
+            )),
+            None => resumable += 1,
+        }
+    }
+    items.push(DiagItem::ok(
+        cat,
+        format!("{resumable} unfinished run(s) resume on restart"),
+    ));
+}
+
 // ── Daemon state (original logic, preserved) ─────────────────────
 
 fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
@@ -1284,6 +1332,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sop_runs_check_reports_orphaned_runs() {
+        use crate::sop::{SopEvent, SopRun, SopRunStatus, SopTriggerSource};
+
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+
+        let mut items = Vec::new();
+        check_sop_runs(&config, &mut items);
+        assert!(items.is_empty(), "no journal, nothing to report");
+
+        let run = SopRun {
+            run_id: "run-1".into(),
+            sop_name: "retired".into(),
+            trigger_event: SopEvent {
+                source: SopTriggerSource::Manual,
+                topic: None,
+                payload: None,
+                timestamp: "2026-01-01T00:00:00Z".into(),
+            },
+            status: SopRunStatus::WaitingApproval,
+            current_step: 1,
+            total_steps: 1,
+            started_at: "2026-01-01T00:00:00Z".into(),
+            completed_at: None,
+            step_results: Vec::new(),
+            waiting_since: Some("2026-01-01T00:00:00Z".into()),
+            llm_calls_saved: 0,
+            step_visits: std::collections::HashMap::new(),
+            pending_branches: Vec::new(),
+        };
+        crate::sop::store::record_run(&config.workspace_dir, &run, 0).unwrap();
+
+        check_sop_runs(&config, &mut items);
+        let orphan = items
+            .iter()
+            .find(|item| item.severity == Severity::Warn)
+            .expect("orphaned run reported");
+        assert!(orphan.message.contains("run-1"));
+        assert!(orphan.message.contains("'retired' is no longer loaded"));
+    }
+
     #[test]
     fn config_validation_reports_delegate_agents_in_sorted_order() {
         let mut config = Config::default();
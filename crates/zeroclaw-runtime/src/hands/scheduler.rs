@@ -0,0 +1,400 @@
+use super::{
+    Hand, HandContext, HandRun, HandRunStatus, hands_dir, load_hand_context, load_hands,
+    save_hand_context,
+};
+use crate::cron::scheduler::{authorize_agent_run, deliver_if_configured};
+use crate::cron::{Schedule, next_run_for_schedule};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use crate::security::SecurityPolicy;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, stream};
+use std::fmt::Write as _;
+use tokio::time::{self, Duration};
+use zeroclaw_config::schema::Config;
+
+const MIN_POLL_SECONDS: u64 = 5;
+const HANDS_COMPONENT: &str = "hands";
+/// Learned facts carried into each prompt, most recent last.
+const MAX_PROMPT_FACTS: usize = 50;
+const FINDING_PREFIX: &str = "FINDING:";
+const LEARNED_PREFIX: &str = "LEARNED:";
+
+/// Daemon loop: run every active hand whenever its schedule comes due,
+/// at most `hands.max_concurrent` at a time.
+///
+/// Hand definitions are re-read on every tick, so adding, editing or
+/// deactivating a hand takes effect without restarting the daemon.
+pub async fn run(config: Config) -> Result<()> {
+    let poll_secs = config.hands.poll_interval_secs.max(MIN_POLL_SECONDS);
+    let mut interval = time::interval(Duration::from_secs(poll_secs));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+    let dir = hands_dir(&config);
+    let started_at = Utc::now();
+
+    crate::health::mark_component_ok(HANDS_COMPONENT);
+
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok(HANDS_COMPONENT);
+
+        let hands = match load_hands(&dir) {
+            Ok(hands) => hands,
+            Err(e) => {
+                crate::health::mark_component_error(HANDS_COMPONENT, e.to_string());
+                tracing::warn!("Failed to load hands: {e}");
+                continue;
+            }
+        };
+
+        let now = Utc::now();
+        let due: Vec<Hand> = hands
+            .into_iter()
+            .filter(|hand| hand.active)
+            .filter(|hand| {
+                let context = match load_hand_context(&dir, &hand.name) {
+                    Ok(context) => context,
+                    Err(e) => {
+                        tracing::warn!(hand = %hand.name, "Failed to load hand context: {e}");
+                        return false;
+                    }
+                };
+                match next_run_at(hand, &context, started_at) {
+                    Ok(Some(due)) => due <= now,
+                    Ok(None) => false,
+                    Err(e) => {
+                        tracing::warn!(hand = %hand.name, "Invalid hand schedule: {e}");
+                        false
+                    }
+                }
+            })
+            .collect();
+
+        let mut in_flight = stream::iter(due.into_iter().map(|hand| {
+            let config = config.clone();
+            async move {
+                let result = Box::pin(run_hand(&config, &hand)).await;
+                (hand.name, result)
+            }
+        }))
+        .buffer_unordered(config.hands.max_concurrent.max(1));
+        while let Some((name, result)) = in_flight.next().await {
+            if let Err(e) = result {
+                tracing::warn!(hand = %name, "Failed to record hand run: {e}");
+            }
+        }
+    }
+}
+
+/// When a hand is next due.
+///
+/// Schedules are anchored on the most recent attempt, so a failing hand
+/// waits for its next slot instead of retrying every tick. Hands that have
+/// never run are anchored on `since` (daemon start). One-shot `at` hands
+/// return `None` once they have run.
+pub fn next_run_at(
+    hand: &Hand,
+    context: &HandContext,
+    since: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    let last_attempt = context.history.first().map(|run| run.started_at);
+    if let Schedule::At { at } = hand.schedule {
+        return Ok(match last_attempt {
+            Some(last) if last >= at => None,
+            _ => Some(at),
+        });
+    }
+    next_run_for_schedule(&hand.schedule, last_attempt.unwrap_or(since)).map(Some)
+}
+
+/// Execute one hand run and fold the result into its rolling context.
+///
+/// Agent and delivery failures are recorded as a failed [`HandRun`]; the
+/// `Err` path is reserved for failures to load or save the context.
+pub async fn run_hand(config: &Config, hand: &Hand) -> Result<HandRun> {
+    let dir = hands_dir(config);
+    let mut context = load_hand_context(&dir, &hand.name)?;
+    let observer = crate::observability::create_observer(&config.observability);
+    observer.record_event(&ObserverEvent::HandStarted {
+        hand_name: hand.name.clone(),
+    });
+
+    let started_at = Utc::now();
+    let started = std::time::Instant::now();
+    let outcome = Box::pin(execute_hand(config, hand, &context)).await;
+
+    let mut run = HandRun {
+        hand_name: hand.name.clone(),
+        run_id: uuid::Uuid::new_v4().to_string(),
+        started_at,
+        finished_at: None,
+        status: HandRunStatus::Completed,
+        findings: Vec::new(),
+        knowledge_added: Vec::new(),
+        duration_ms: None,
+    };
+    match outcome {
+        Ok(report) => {
+            (run.findings, run.knowledge_added) = parse_report(&report);
+            if let Err(e) = deliver_if_configured(config, &hand.delivery, &report).await {
+                if hand.delivery.best_effort {
+                    tracing::warn!(hand = %hand.name, "Hand report delivery failed: {e}");
+                } else {
+                    run.status = HandRunStatus::Failed {
+                        error: format!("delivery failed: {e}"),
+                    };
+                }
+            }
+        }
+        Err(e) => {
+            run.status = HandRunStatus::Failed {
+                error: e.to_string(),
+            };
+        }
+    }
+
+    let elapsed = started.elapsed();
+    let duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    run.finished_at = Some(Utc::now());
+    run.duration_ms = Some(duration_ms);
+
+    record_outcome(observer.as_ref(), &run, elapsed);
+    context.record_run(run.clone(), hand.max_history);
+    save_hand_context(&dir, &context)?;
+    Ok(run)
+}
+
+async fn execute_hand(config: &Config, hand: &Hand, context: &HandContext) -> Result<String> {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    authorize_agent_run(&security)?;
+
+    let mut hand_config = config.clone();
+    hand_config.memory.auto_save = false;
+    let session_path =
+        std::path::PathBuf::from(format!("hand-{}-{}", hand.name, uuid::Uuid::new_v4()));
+
+    Box::pin(crate::agent::run(
+        hand_config,
+        Some(build_prompt(hand, context)),
+        None,
+        hand.model.clone(),
+        config
+            .providers
+            .fallback_provider()
+            .and_then(|e| e.temperature)
+            .unwrap_or(0.7),
+        vec![],
+        false,
+        Some(session_path),
+        hand.allowed_tools.clone(),
+    ))
+    .await
+}
+
+fn record_outcome(observer: &dyn Observer, run: &HandRun, elapsed: std::time::Duration) {
+    let duration_ms = run.duration_ms.unwrap_or_default();
+    let success = match &run.status {
+        HandRunStatus::Failed { error } => {
+            observer.record_event(&ObserverEvent::HandFailed {
+                hand_name: run.hand_name.clone(),
+                error: error.clone(),
+                duration_ms,
+            });
+            false
+        }
+        _ => {
+            observer.record_event(&ObserverEvent::HandCompleted {
+                hand_name: run.hand_name.clone(),
+                duration_ms,
+                findings_count: run.findings.len(),
+            });
+            true
+        }
+    };
+    observer.record_metric(&ObserverMetric::HandRunDuration {
+        hand_name: run.hand_name.clone(),
+        duration: elapsed,
+    });
+    observer.record_metric(&ObserverMetric::HandFindingsCount {
+        hand_name: run.hand_name.clone(),
+        count: run.findings.len() as u64,
+    });
+    observer.record_metric(&ObserverMetric::HandSuccessRate {
+        hand_name: run.hand_name.clone(),
+        success,
+    });
+}
+
+/// Build the prompt for one run: the hand's own prompt, its domain
+/// knowledge, what earlier runs learned, and the previous run's findings.
+pub(crate) fn build_prompt(hand: &Hand, context: &HandContext) -> String {
+    let mut prompt = format!("[hand:{}] {}\n", hand.name, hand.prompt.trim());
+
+    if !hand.knowledge.is_empty() {
+        prompt.push_str("\nDomain knowledge:\n");
+        for line in &hand.knowledge {
+            let _ = writeln!(prompt, "- {line}");
+        }
+    }
+
+    let skip = context.learned_facts.len().saturating_sub(MAX_PROMPT_FACTS);
+    if !context.learned_facts.is_empty() {
+        prompt.push_str("\nFacts learned in earlier runs:\n");
+        for fact in &context.learned_facts[skip..] {
+            let _ = writeln!(prompt, "- {fact}");
+        }
+    }
+
+    if let Some(previous) = context.history.iter().find(|run| !run.findings.is_empty()) {
+        let _ = writeln!(
+            prompt,
+            "\nFindings from the previous run ({}):",
+            previous.started_at.to_rfc3339()
+        );
+        for finding in &previous.findings {
+            let _ = writeln!(prompt, "- {finding}");
+        }
+    }
+
+    let _ = write!(
+        prompt,
+        "\nEnd your report with one `{FINDING_PREFIX} ...` line per key finding and one \
+         `{LEARNED_PREFIX} ...` line per durable fact worth remembering for future runs."
+    );
+    prompt
+}
+
+/// Split a report into findings and newly learned facts.
+///
+/// Lines tagged `FINDING:` / `LEARNED:` (case-insensitive, optionally as
+/// list items) are extracted. A report without any tagged finding is kept
+/// as a single finding so the run is never recorded as empty.
+pub(crate) fn parse_report(report: &str) -> (Vec<String>, Vec<String>) {
+    let mut findings = Vec::new();
+    let mut learned = Vec::new();
+
+    for line in report.lines() {
+        let line = line.trim().trim_start_matches(['-', '*']).trim_start();
+        if let Some(text) = strip_tag(line, FINDING_PREFIX) {
+            findings.push(text);
+        } else if let Some(text) = strip_tag(line, LEARNED_PREFIX) {
+            learned.push(text);
+        }
+    }
+
+    if findings.is_empty() && !report.trim().is_empty() {
+        findings.push(crate::util::truncate_with_ellipsis(report.trim(), 500));
+    }
+    (findings, learned)
+}
+
+fn strip_tag(line: &str, tag: &str) -> Option<String> {
+    let head = line.get(..tag.len())?;
+    if !head.eq_ignore_ascii_case(tag) {
+        return None;
+    }
+    let text = line[tag.len()..].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::DeliveryConfig;
+    use chrono::TimeZone;
+
+    fn hand(schedule: Schedule) -> Hand {
+        Hand {
+            name: "scanner".into(),
+            description: "Scans things".into(),
+            schedule,
+            prompt: "Scan the market.".into(),
+            knowledge: vec!["Focus on tech.".into()],
+            allowed_tools: None,
+            model: None,
+            active: true,
+            max_history: 10,
+            delivery: DeliveryConfig::default(),
+        }
+    }
+
+    fn run_at(started_at: DateTime<Utc>, findings: &[&str]) -> HandRun {
+        HandRun {
+            hand_name: "scanner".into(),
+            run_id: uuid::Uuid::new_v4().to_string(),
+            started_at,
+            finished_at: Some(started_at),
+            status: HandRunStatus::Failed {
+                error: "boom".into(),
+            },
+            findings: findings.iter().map(|f| (*f).to_string()).collect(),
+            knowledge_added: Vec::new(),
+            duration_ms: Some(10),
+        }
+    }
+
+    #[test]
+    fn next_run_is_anchored_on_last_attempt() {
+        let hand = hand(Schedule::Every { every_ms: 60_000 });
+        let since = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut context = HandContext::new("scanner");
+
+        let first = next_run_at(&hand, &context, since).unwrap().unwrap();
+        assert_eq!(first, since + chrono::Duration::minutes(1));
+
+        // A failed attempt still pushes the next run out by one interval.
+        context.record_run(run_at(first, &[]), 10);
+        let second = next_run_at(&hand, &context, since).unwrap().unwrap();
+        assert_eq!(second, first + chrono::Duration::minutes(1));
+    }
+
+    #[test]
+    fn one_shot_hand_runs_once() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        let hand = hand(Schedule::At { at });
+        let mut context = HandContext::new("scanner");
+        let since = at - chrono::Duration::days(1);
+
+        assert_eq!(next_run_at(&hand, &context, since).unwrap(), Some(at));
+        context.record_run(run_at(at, &[]), 10);
+        assert_eq!(next_run_at(&hand, &context, since).unwrap(), None);
+    }
+
+    #[test]
+    fn prompt_carries_knowledge_and_previous_findings() {
+        let hand = hand(Schedule::Every { every_ms: 60_000 });
+        let mut context = HandContext::new("scanner");
+        context.learned_facts.push("ACME reports on Fridays".into());
+        context.record_run(run_at(Utc::now(), &["ACME up 4%"]), 10);
+
+        let prompt = build_prompt(&hand, &context);
+        assert!(prompt.starts_with("[hand:scanner] Scan the market."));
+        assert!(prompt.contains("- Focus on tech."));
+        assert!(prompt.contains("- ACME reports on Fridays"));
+        assert!(prompt.contains("- ACME up 4%"));
+        assert!(prompt.contains(FINDING_PREFIX));
+    }
+
+    #[test]
+    fn report_tags_are_extracted() {
+        let report = "Summary of the day.\n\
+                      FINDING: ACME up 4%\n\
+                      - finding: Globex flat\n\
+                      LEARNED: ACME reports on Fridays\n\
+                      LEARNED:\n";
+        let (findings, learned) = parse_report(report);
+        assert_eq!(findings, vec!["ACME up 4%", "Globex flat"]);
+        assert_eq!(learned, vec!["ACME reports on Fridays"]);
+    }
+
+    #[test]
+    fn untagged_report_becomes_single_finding() {
+        let (findings, learned) = parse_report("  Nothing notable today.  \n");
+        assert_eq!(findings, vec!["Nothing notable today."]);
+        assert!(learned.is_empty());
+
+        let (findings, _) = parse_report("   ");
+        assert!(findings.is_empty());
+    }
+}
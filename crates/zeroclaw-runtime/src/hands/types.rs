@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::cron::Schedule;
+use crate::cron::{DeliveryConfig, Schedule};
 
 // ── Hand ───────────────────────────────────────────────────────
 
@@ -36,6 +36,9 @@ pub struct Hand {
     /// Maximum runs to keep in history
     #[serde(default = "default_max_runs")]
     pub max_history: usize,
+    /// Where each run's report is announced (defaults to no delivery)
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
 }
 
 fn default_true() -> bool {
@@ -50,6 +53,7 @@ fn default_max_runs() -> usize {
 
 /// The status of a single hand execution.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case", tag = "status")]
 pub enum HandRunStatus {
     Running,
@@ -57,16 +61,29 @@ pub enum HandRunStatus {
     Failed { error: String },
 }
 
+impl HandRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
 /// Record of a single hand execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct HandRun {
     /// Name of the hand that produced this run
     pub hand_name: String,
     /// Unique identifier for this run
     pub run_id: String,
     /// When the run started
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub started_at: DateTime<Utc>,
     /// When the run finished (None if still running)
+    #[cfg_attr(feature = "schema-export", schemars(with = "Option<String>"))]
     pub finished_at: Option<DateTime<Utc>>,
     /// Outcome of the run
     pub status: HandRunStatus,
@@ -139,7 +156,6 @@ impl HandContext {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cron::Schedule;
 
     fn sample_hand() -> Hand {
         Hand {
@@ -155,6 +171,7 @@ mod tests {
             model: Some("claude-opus-4-6".into()),
             active: true,
             max_history: 50,
+            delivery: DeliveryConfig::default(),
         }
     }
 
@@ -193,6 +210,7 @@ tz = "America/New_York"
         assert!(hand.knowledge.is_empty());
         assert!(hand.allowed_tools.is_none());
         assert!(hand.model.is_none());
+        assert_eq!(hand.delivery.mode, "none");
     }
 
     #[test]
@@ -210,6 +228,11 @@ max_history = 25
 [schedule]
 kind = "every"
 every_ms = 3600000
+
+[delivery]
+mode = "announce"
+channel = "telegram"
+to = "123456"
 "#;
         let hand: Hand = toml::from_str(toml_str).unwrap();
         assert_eq!(hand.name, "news-digest");
@@ -218,6 +241,7 @@ every_ms = 3600000
         assert_eq!(hand.knowledge.len(), 2);
         assert_eq!(hand.allowed_tools.as_ref().unwrap().len(), 1);
         assert_eq!(hand.model.as_deref(), Some("claude-opus-4-6"));
+        assert_eq!(hand.delivery.channel.as_deref(), Some("telegram"));
         assert!(matches!(
             hand.schedule,
             Schedule::Every {
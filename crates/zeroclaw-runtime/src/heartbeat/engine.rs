@@ -8,7 +8,7 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::time::{self, Duration};
 use tracing::{info, warn};
-use zeroclaw_config::schema::HeartbeatConfig;
+use zeroclaw_config::schema::{CalendarMode, CalendarRule, HeartbeatConfig};
 
 // ── Structured task types ────────────────────────────────────────
 
@@ -56,6 +56,20 @@ pub struct HeartbeatTask {
     pub text: String,
     pub priority: TaskPriority,
     pub status: TaskStatus,
+    /// Calendar that decides on which dates the task runs.
+    #[serde(default)]
+    pub calendar: Option<CalendarRule>,
+    /// Name other tasks use to depend on this one.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Ids of upstream tasks; while one of them is failing, this task is
+    /// suppressed.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Escalation policy (`[heartbeat.escalations.<name>]`) for repeated
+    /// failures.
+    #[serde(default)]
+    pub escalation: Option<String>,
 }
 
 impl HeartbeatTask {
@@ -264,6 +278,11 @@ impl HeartbeatEngine {
     ///   `- [high] Check email`           →  high priority, active
     ///   `- [low|paused] Review old PRs`  →  low priority, paused
     ///   `- [completed] Old task`         →  medium priority, completed
+    ///   `- [calendar=holidays:business_days] Send report`  →  runs on
+    ///   business days of the `holidays` calendar (mode defaults to `exclude`)
+    ///   `- [id=db|escalate=database] Check the database`  →  named task with
+    ///   the `database` escalation policy
+    ///   `- [depends=db] Check replication`  →  suppressed while `db` fails
     fn parse_tasks(content: &str) -> Vec<HeartbeatTask> {
         content
             .lines()
@@ -282,45 +301,66 @@ impl HeartbeatEngine {
     ///
     /// Format: `[priority|status] task text` or just `task text`.
     fn parse_task_line(text: &str) -> HeartbeatTask {
+        // No metadata — default to medium/active
+        let mut task = HeartbeatTask {
+            text: text.to_string(),
+            priority: TaskPriority::Medium,
+            status: TaskStatus::Active,
+            calendar: None,
+            id: None,
+            depends_on: Vec::new(),
+            escalation: None,
+        };
         if let Some(rest) = text.strip_prefix('[')
             && let Some((meta, task_text)) = rest.split_once(']')
         {
             let task_text = task_text.trim();
             if !task_text.is_empty() {
-                let (priority, status) = Self::parse_meta(meta);
-                return HeartbeatTask {
-                    text: task_text.to_string(),
-                    priority,
-                    status,
-                };
+                task.text = task_text.to_string();
+                Self::parse_meta(meta, &mut task);
             }
         }
-        // No metadata — default to medium/active
-        HeartbeatTask {
-            text: text.to_string(),
-            priority: TaskPriority::Medium,
-            status: TaskStatus::Active,
-        }
+        task
     }
 
-    /// Parse metadata tags like `high`, `low|paused`, `completed`.
-    fn parse_meta(meta: &str) -> (TaskPriority, TaskStatus) {
-        let mut priority = TaskPriority::Medium;
-        let mut status = TaskStatus::Active;
+    /// Parse metadata tags like `high`, `low|paused`, `completed`,
+    /// `calendar=name[:mode]`, `id=name`, `depends=a,b`, `escalate=policy`.
+    fn parse_meta(meta: &str, task: &mut HeartbeatTask) {
+        let non_empty = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
 
         for part in meta.split('|') {
-            match part.trim().to_ascii_lowercase().as_str() {
-                "high" => priority = TaskPriority::High,
-                "medium" | "med" => priority = TaskPriority::Medium,
-                "low" => priority = TaskPriority::Low,
-                "active" => status = TaskStatus::Active,
-                "paused" | "pause" => status = TaskStatus::Paused,
-                "completed" | "complete" | "done" => status = TaskStatus::Completed,
+            let part = part.trim();
+            if let Some((key, value)) = part.split_once('=') {
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "calendar" => {
+                        let (name, mode) = value.split_once(':').unwrap_or((value, ""));
+                        task.calendar = non_empty(name).map(|name| CalendarRule {
+                            name,
+                            mode: CalendarMode::parse(mode).unwrap_or_default(),
+                        });
+                    }
+                    "id" => task.id = non_empty(value),
+                    "depends" => {
+                        task.depends_on = value.split(',').filter_map(non_empty).collect();
+                    }
+                    "escalate" => task.escalation = non_empty(value),
+                    _ => {}
+                }
+                continue;
+            }
+            match part.to_ascii_lowercase().as_str() {
+                "high" => task.priority = TaskPriority::High,
+                "medium" | "med" => task.priority = TaskPriority::Medium,
+                "low" => task.priority = TaskPriority::Low,
+                "active" => task.status = TaskStatus::Active,
+                "paused" | "pause" => task.status = TaskStatus::Paused,
+                "completed" | "complete" | "done" => task.status = TaskStatus::Completed,
                 _ => {}
             }
         }
-
-        (priority, status)
     }
 
     /// Build the Phase 1 LLM decision prompt for two-phase heartbeat.
@@ -568,6 +608,42 @@ mod tests {
         assert_eq!(runnable[0].text, "Active");
     }
 
+    #[test]
+    fn parse_task_with_calendar_tag() {
+        let tasks = HeartbeatEngine::parse_tasks(
+            "- [high|calendar=holidays:next_business_day] Send report\n\
+             - [calendar=office] Water plants\n\
+             - [calendar=] Untagged",
+        );
+        let rule = tasks[0].calendar.as_ref().unwrap();
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(rule.name, "holidays");
+        assert_eq!(rule.mode, CalendarMode::NextBusinessDay);
+        assert_eq!(
+            tasks[1].calendar.as_ref().unwrap().mode,
+            CalendarMode::Exclude
+        );
+        assert!(tasks[2].calendar.is_none());
+    }
+
+    #[test]
+    fn parse_task_with_dependencies_and_escalation() {
+        let tasks = HeartbeatEngine::parse_tasks(
+            "- [high|id=db|escalate=database] Check the database\n\
+             - [depends=db, net|low] Check replication\n\
+             - [id=|depends=] Plain",
+        );
+        assert_eq!(tasks[0].id.as_deref(), Some("db"));
+        assert_eq!(tasks[0].escalation.as_deref(), Some("database"));
+        assert_eq!(tasks[0].text, "Check the database");
+        assert_eq!(
+            tasks[1].depends_on,
+            vec!["db".to_string(), "net".to_string()]
+        );
+        assert_eq!(tasks[1].priority, TaskPriority::Low);
+        assert!(tasks[2].id.is_none() && tasks[2].depends_on.is_empty());
+    }
+
     // ── Two-phase decision tests ────────────────────────────────
 
     #[test]
@@ -577,11 +653,19 @@ mod tests {
                 text: "Check email".into(),
                 priority: TaskPriority::High,
                 status: TaskStatus::Active,
+                calendar: None,
+                id: None,
+                depends_on: Vec::new(),
+                escalation: None,
             },
             HeartbeatTask {
                 text: "Review calendar".into(),
                 priority: TaskPriority::Medium,
                 status: TaskStatus::Active,
+                calendar: None,
+                id: None,
+                depends_on: Vec::new(),
+                escalation: None,
             },
         ];
         let prompt = HeartbeatEngine::build_decision_prompt(&tasks);
@@ -640,6 +724,10 @@ mod tests {
             text: "Check email".into(),
             priority: TaskPriority::High,
             status: TaskStatus::Active,
+            calendar: None,
+            id: None,
+            depends_on: Vec::new(),
+            escalation: None,
         };
         assert_eq!(format!("{task}"), "[high] Check email");
     }
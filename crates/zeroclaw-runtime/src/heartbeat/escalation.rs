@@ -0,0 +1,352 @@
+//! Escalation ladders and dependency suppression for heartbeat tasks.
+//!
+//! A task tagged `escalate=<policy>` climbs the ladder of
+//! `[heartbeat.escalations.<policy>]` as its consecutive failures grow: a
+//! notice (plus an optional remediation SOP) at `notify_after`, then an
+//! escalation to a human at `escalate_after`.  Each step fires once per
+//! failure streak.  A task tagged `depends=<id>` is suppressed while one of
+//! its upstream tasks is failing, so one outage raises one alarm.
+
+use std::path::Path;
+use std::sync::{Arc, PoisonError};
+
+use async_trait::async_trait;
+use zeroclaw_api::channel::{Channel, ChannelMessage, SendMessage};
+use zeroclaw_config::schema::{Config, HeartbeatEscalationConfig};
+
+use super::engine::HeartbeatTask;
+use super::store;
+use crate::security::SecurityPolicy;
+use crate::sop::engine::now_iso8601;
+use crate::sop::types::{SopEvent, SopTriggerSource};
+use crate::tools::EscalateToHumanTool;
+
+/// One rung of an escalation ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationStep {
+    /// Notify the policy channel and start the remediation SOP.
+    Notify,
+    /// Escalate to a human with the policy urgency.
+    Escalate,
+}
+
+/// The steps `policy` takes when a task has just failed for the
+/// `failures`-th time in a row.
+pub fn steps_for(policy: &HeartbeatEscalationConfig, failures: u32) -> Vec<EscalationStep> {
+    let mut steps = Vec::new();
+    if failures == policy.notify_after {
+        steps.push(EscalationStep::Notify);
+    }
+    if policy.escalate_after > 0 && failures == policy.escalate_after {
+        steps.push(EscalationStep::Escalate);
+    }
+    steps
+}
+
+/// Ids of `task`'s upstream tasks whose last run failed.  Ids that match no
+/// task in `tasks` are ignored.
+pub fn failing_upstreams(
+    workspace_dir: &Path,
+    task: &HeartbeatTask,
+    tasks: &[HeartbeatTask],
+) -> Vec<String> {
+    task.depends_on
+        .iter()
+        .filter(|id| {
+            let Some(upstream) = tasks.iter().find(|t| t.id.as_ref() == Some(*id)) else {
+                tracing::debug!(task = %task.text, upstream = %id, "unknown heartbeat dependency");
+                return false;
+            };
+            store::consecutive_failures(workspace_dir, &upstream.text).is_ok_and(|n| n > 0)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Climb `task`'s escalation ladder after its `failures`-th failure in a
+/// row.  `fallback` is the heartbeat delivery target.  Best effort: problems
+/// are logged, never returned.
+pub async fn escalate(
+    config: &Config,
+    task: &HeartbeatTask,
+    failures: u32,
+    error: &str,
+    fallback: Option<&(String, String)>,
+) {
+    let Some(name) = task.escalation.as_deref() else {
+        return;
+    };
+    let Some(policy) = config.heartbeat.escalations.get(name) else {
+        tracing::warn!(task = %task.text, policy = %name, "unknown heartbeat escalation policy");
+        return;
+    };
+    let target = match (&policy.channel, &policy.to) {
+        (Some(channel), Some(to)) => Some((channel.clone(), to.clone())),
+        _ => fallback.cloned(),
+    };
+
+    for step in steps_for(policy, failures) {
+        match step {
+            EscalationStep::Notify => {
+                let notice = format!(
+                    "⚠️ Heartbeat task failed {failures} times in a row: {}\nLast error: {error}",
+                    task.text
+                );
+                deliver(config, target.as_ref(), &notice).await;
+                if let Some(sop) = &policy.sop
+                    && let Err(e) = start_remediation(config, sop, task, failures, error)
+                {
+                    tracing::warn!(sop = %sop, "Heartbeat remediation failed to start: {e:#}");
+                }
+            }
+            EscalationStep::Escalate => {
+                let Some((channel, to)) = &target else {
+                    tracing::warn!(task = %task.text, "Heartbeat escalation has no delivery channel");
+                    continue;
+                };
+                let tool = escalation_tool(config, channel);
+                let escalated = tool
+                    .escalate(
+                        channel,
+                        to,
+                        &policy.urgency,
+                        &format!("Heartbeat task keeps failing: {}", task.text),
+                        Some(&format!(
+                            "{failures} consecutive failures. Last error: {error}"
+                        )),
+                    )
+                    .await;
+                match escalated {
+                    Ok(()) => tracing::warn!(
+                        task = %task.text,
+                        urgency = %policy.urgency,
+                        failures,
+                        "Heartbeat task escalated to a human"
+                    ),
+                    Err(e) => tracing::warn!(
+                        task = %task.text,
+                        "Heartbeat escalation failed: {e:#}"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Start `sop` on the shared SOP engine with the failure as its trigger
+/// payload. The run's steps execute headlessly in agent turns limited to
+/// the `sop_*` tools and each step's suggested tools; a step that needs
+/// approval waits for an operator like any other run.
+fn start_remediation(
+    config: &Config,
+    sop: &str,
+    task: &HeartbeatTask,
+    failures: u32,
+    error: &str,
+) -> anyhow::Result<()> {
+    let engine = crate::sop::shared_engine(config)
+        .ok_or_else(|| anyhow::anyhow!("[sop].sops_dir is not configured"))?;
+    let event = SopEvent {
+        source: SopTriggerSource::Manual,
+        topic: Some("heartbeat".into()),
+        payload: Some(
+            serde_json::json!({
+                "task": task.text,
+                "failures": failures,
+                "error": error,
+            })
+            .to_string(),
+        ),
+        timestamp: now_iso8601(),
+    };
+    let action = engine
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .start_run(sop, event)?;
+    crate::sop::shared::spawn_drive(config, action);
+    Ok(())
+}
+
+/// An `escalate_to_human` tool whose channel map reaches `channel` and the
+/// `[escalation] alert_channels` through the channel delivery function.
+fn escalation_tool(config: &Config, channel: &str) -> EscalateToHumanTool {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let tool =
+        EscalateToHumanTool::new(Arc::new(security), config.escalation.alert_channels.clone());
+    let handle = tool.channel_map_handle();
+    let mut channels = handle.write();
+    for name in
+        std::iter::once(channel).chain(config.escalation.alert_channels.iter().map(String::as_str))
+    {
+        channels.insert(
+            name.to_string(),
+            Arc::new(AnnouncementChannel {
+                config: config.clone(),
+                name: name.to_string(),
+            }),
+        );
+    }
+    drop(channels);
+    tool
+}
+
+/// Sends through [`crate::cron::scheduler::deliver_announcement`], so
+/// heartbeat escalations reach the live channel (or one built from config)
+/// without the channel crate.
+struct AnnouncementChannel {
+    config: Config,
+    name: String,
+}
+
+#[async_trait]
+impl Channel for AnnouncementChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            crate::cron::scheduler::deliver_announcement(
+                &self.config,
+                &self.name,
+                &message.recipient,
+                &message.content,
+            ),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("delivery timed out (30s)"))?
+    }
+
+    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        anyhow::bail!("heartbeat escalations do not listen for replies")
+    }
+}
+
+async fn deliver(config: &Config, target: Option<&(String, String)>, text: &str) {
+    let Some((channel, to)) = target else {
+        tracing::warn!("Heartbeat escalation has no delivery channel: {text}");
+        return;
+    };
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        crate::cron::scheduler::deliver_announcement(config, channel, to, text),
+    )
+    .await;
+    match delivery {
+        Ok(Err(e)) => {
+            tracing::warn!(channel = %channel, "Heartbeat escalation delivery failed: {e}")
+        }
+        Err(_) => {
+            tracing::warn!(channel = %channel, "Heartbeat escalation delivery timed out (30s)")
+        }
+        Ok(Ok(())) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heartbeat::engine::{TaskPriority, TaskStatus};
+    use chrono::Utc;
+
+    fn task(text: &str, id: Option<&str>, depends_on: &[&str]) -> HeartbeatTask {
+        HeartbeatTask {
+            text: text.into(),
+            priority: TaskPriority::Medium,
+            status: TaskStatus::Active,
+            calendar: None,
+            id: id.map(Into::into),
+            depends_on: depends_on.iter().map(|d| (*d).to_string()).collect(),
+            escalation: None,
+        }
+    }
+
+    #[test]
+    fn ladder_steps_fire_once_per_streak() {
+        let policy = HeartbeatEscalationConfig {
+            notify_after: 2,
+            escalate_after: 4,
+            ..HeartbeatEscalationConfig::default()
+        };
+        assert!(steps_for(&policy, 1).is_empty());
+        assert_eq!(steps_for(&policy, 2), vec![EscalationStep::Notify]);
+        assert!(steps_for(&policy, 3).is_empty());
+        assert_eq!(steps_for(&policy, 4), vec![EscalationStep::Escalate]);
+        assert!(steps_for(&policy, 5).is_empty());
+
+        let same_rung = HeartbeatEscalationConfig {
+            notify_after: 1,
+            escalate_after: 1,
+            ..HeartbeatEscalationConfig::default()
+        };
+        assert_eq!(
+            steps_for(&same_rung, 1),
+            vec![EscalationStep::Notify, EscalationStep::Escalate]
+        );
+    }
+
+    #[test]
+    fn dependents_are_suppressed_while_upstream_fails() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = vec![
+            task("Check the database", Some("db"), &[]),
+            task("Check replication", None, &["db", "missing"]),
+        ];
+        let now = Utc::now();
+        let record = |status: &str| {
+            store::record_run(
+                tmp.path(),
+                "Check the database",
+                "medium",
+                now,
+                now,
+                status,
+                None,
+                10,
+                50,
+            )
+            .unwrap();
+        };
+
+        assert!(failing_upstreams(tmp.path(), &tasks[1], &tasks).is_empty());
+        record("error");
+        assert_eq!(failing_upstreams(tmp.path(), &tasks[1], &tasks), vec!["db"]);
+        record("ok");
+        assert!(failing_upstreams(tmp.path(), &tasks[1], &tasks).is_empty());
+    }
+
+    #[test]
+    fn remediation_starts_a_run_on_the_shared_engine() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let failing = task("Check backups", None, &[]);
+        assert!(start_remediation(&config, "restart-backup", &failing, 3, "timeout").is_err());
+
+        let sop_dir = tmp.path().join("sops").join("restart-backup");
+        std::fs::create_dir_all(&sop_dir).unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.toml"),
+            "[sop]\nname = \"restart-backup\"\ndescription = \"Restart the backup job\"\n\n[[triggers]]\ntype = \"manual\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.md"),
+            "## Steps\n\n1. **Restart** the backup job.\n",
+        )
+        .unwrap();
+        config.sop.sops_dir = Some(tmp.path().join("sops").display().to_string());
+
+        // No async runtime here, so the step is left for an operator.
+        start_remediation(&config, "restart-backup", &failing, 3, "timeout").unwrap();
+        let engine = crate::sop::shared_engine(&config).unwrap();
+        let engine = engine.lock().unwrap();
+        let run = engine.active_runs().values().next().unwrap();
+        assert_eq!(run.sop_name, "restart-backup");
+        let payload = run.trigger_event.payload.as_deref().unwrap();
+        assert!(payload.contains("Check backups") && payload.contains("timeout"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod engine;
+pub mod escalation;
 pub mod store;
 
 #[cfg(test)]
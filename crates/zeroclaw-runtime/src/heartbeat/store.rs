@@ -125,6 +125,23 @@ pub fn run_stats(workspace_dir: &Path) -> Result<(u64, u64, u64)> {
     })
 }
 
+/// Number of failed runs of `task_text` since its last successful run.
+pub fn consecutive_failures(workspace_dir: &Path, task_text: &str) -> Result<u32> {
+    with_connection(workspace_dir, |conn| {
+        let failures: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM heartbeat_runs
+             WHERE task_text = ?1 AND status = 'error'
+               AND id > COALESCE(
+                   (SELECT MAX(id) FROM heartbeat_runs WHERE task_text = ?1 AND status = 'ok'),
+                   0
+               )",
+            params![task_text],
+            |r| r.get(0),
+        )?;
+        u32::try_from(failures).context("Failure count overflow")
+    })
+}
+
 fn db_path(workspace_dir: &Path) -> PathBuf {
     workspace_dir.join("heartbeat").join("history.db")
 }
@@ -278,6 +295,27 @@ mod tests {
         assert_eq!(err, 1);
     }
 
+    #[test]
+    fn consecutive_failures_reset_on_success() {
+        let tmp = TempDir::new().unwrap();
+        let now = Utc::now();
+        let record = |task: &str, status: &str| {
+            record_run(tmp.path(), task, "medium", now, now, status, None, 10, 50).unwrap();
+        };
+
+        assert_eq!(consecutive_failures(tmp.path(), "db").unwrap(), 0);
+        record("db", "error");
+        record("db", "ok");
+        record("db", "error");
+        record("other", "ok");
+        record("db", "error");
+        assert_eq!(consecutive_failures(tmp.path(), "db").unwrap(), 2);
+        assert_eq!(consecutive_failures(tmp.path(), "other").unwrap(), 0);
+
+        record("db", "ok");
+        assert_eq!(consecutive_failures(tmp.path(), "db").unwrap(), 0);
+    }
+
     #[test]
     fn truncates_large_output() {
         let tmp = TempDir::new().unwrap();
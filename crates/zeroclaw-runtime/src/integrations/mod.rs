@@ -10,6 +10,7 @@ use zeroclaw_config::schema::Config;
 /// exists in the schema but isn't configured (`Available`). There is no
 /// "coming soon" state — if it is not real, it does not get listed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub enum IntegrationStatus {
     /// Fully implemented and ready to use
     Available,
@@ -19,6 +20,7 @@ pub enum IntegrationStatus {
 
 /// Integration category
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub enum IntegrationCategory {
     Chat,
     AiModel,
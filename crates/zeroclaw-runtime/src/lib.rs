@@ -7,10 +7,12 @@ pub mod util;
 
 pub mod agent;
 pub mod approval;
+pub mod calendar;
 pub mod cost;
 pub mod cron;
 pub mod daemon;
 pub mod doctor;
+pub mod hands;
 pub mod health;
 pub mod heartbeat;
 pub mod hooks;
@@ -19,11 +21,13 @@ pub mod integrations;
 pub mod nodes;
 pub mod observability;
 pub mod onboard;
+pub mod outbox;
 pub mod platform;
 pub mod rag;
 pub mod routines;
 pub mod security;
 pub mod service;
+pub mod session_export;
 pub mod skillforge;
 pub mod skills;
 pub mod sop;
@@ -5,6 +5,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use zeroclaw_config::schema::Config;
+use zeroclaw_infra::session_backend::SessionBackend;
 use zeroclaw_memory::{self, Memory, MemoryCategory};
 
 #[derive(Debug, Clone)]
@@ -105,6 +106,102 @@ pub async fn migrate_openclaw_memory(
     Ok(())
 }
 
+#[derive(Debug, Default)]
+struct SessionMigrationStats {
+    imported: usize,
+    skipped_existing: usize,
+    skipped_empty: usize,
+    messages: usize,
+}
+
+/// Copy channel session history from the local SQLite store (plus any
+/// legacy JSONL files it imports on open) into the PostgreSQL session
+/// backend configured under `[storage.provider.config]`. The SQLite file is
+/// left in place so operators can roll back.
+pub async fn migrate_sessions(config: &Config, dry_run: bool) -> Result<()> {
+    let storage = &config.storage.provider.config;
+    if storage.db_url.is_none() {
+        bail!("session migration requires [storage.provider.config].db_url");
+    }
+    let postgres = zeroclaw_infra::PostgresTarget {
+        db_url: storage.db_url.as_deref(),
+        schema: &storage.schema,
+        connect_timeout_secs: storage.connect_timeout_secs,
+    };
+
+    let source = zeroclaw_infra::make_session_backend(&config.workspace_dir, "sqlite", postgres)
+        .context("failed to open the SQLite session store")?;
+    let sessions = source.list_sessions_with_metadata();
+
+    if sessions.is_empty() {
+        println!(
+            "No sessions found in {}",
+            config.workspace_dir.join("sessions").display()
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        let messages: usize = sessions.iter().map(|s| s.message_count).sum();
+        println!("🔎 Dry run: session migration preview");
+        println!(
+            "  Source: {}",
+            config.workspace_dir.join("sessions").display()
+        );
+        println!("  Target: PostgreSQL schema '{}'", storage.schema);
+        println!("  Sessions: {}", sessions.len());
+        println!("  Messages: {messages}");
+        println!();
+        println!("Run without --dry-run to copy these sessions.");
+        return Ok(());
+    }
+
+    let target = zeroclaw_infra::make_session_backend(&config.workspace_dir, "postgres", postgres)
+        .context("failed to open the PostgreSQL session backend")?;
+    let stats = copy_sessions(source.as_ref(), target.as_ref())?;
+
+    println!("✅ Session migration complete");
+    println!(
+        "  Source: {}",
+        config.workspace_dir.join("sessions").display()
+    );
+    println!("  Target: PostgreSQL schema '{}'", storage.schema);
+    println!("  Imported:          {}", stats.imported);
+    println!("  Messages:          {}", stats.messages);
+    println!("  Skipped existing:  {}", stats.skipped_existing);
+    println!("  Skipped empty:     {}", stats.skipped_empty);
+    if config.channels.session_backend != "postgres" {
+        println!();
+        println!("Set [channels].session_backend = \"postgres\" to start using it.");
+    }
+
+    Ok(())
+}
+
+fn copy_sessions(
+    source: &dyn SessionBackend,
+    target: &dyn SessionBackend,
+) -> Result<SessionMigrationStats> {
+    let mut stats = SessionMigrationStats::default();
+    for meta in source.list_sessions_with_metadata() {
+        let messages = source.load_stored(&meta.key);
+        if messages.is_empty() {
+            stats.skipped_empty += 1;
+            continue;
+        }
+        if target
+            .import_session(&meta, &messages)
+            .with_context(|| format!("failed to import session '{}'", meta.key))?
+        {
+            stats.imported += 1;
+            stats.messages += messages.len();
+        } else {
+            stats.skipped_existing += 1;
+        }
+    }
+    Ok(stats)
+}
+
 fn target_memory_backend(config: &Config) -> Result<Box<dyn Memory>> {
     zeroclaw_memory::create_memory_for_migration(&config.memory.backend, &config.workspace_dir)
 }
@@ -550,6 +647,52 @@ mod tests {
         assert!(err.to_string().contains("disables persistence"));
     }
 
+    #[tokio::test]
+    async fn session_migration_requires_db_url() {
+        let target = TempDir::new().unwrap();
+        let config = test_config(target.path());
+
+        let err = migrate_sessions(&config, true).await.unwrap_err();
+        assert!(err.to_string().contains("db_url"));
+    }
+
+    #[test]
+    fn copy_sessions_keeps_metadata_and_skips_existing() {
+        use zeroclaw_api::provider::ChatMessage;
+        use zeroclaw_infra::session_sqlite::SqliteSessionBackend;
+
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        let source = SqliteSessionBackend::new(source_dir.path()).unwrap();
+        let target = SqliteSessionBackend::new(target_dir.path()).unwrap();
+        source.append("alice", &ChatMessage::user("hi")).unwrap();
+        source
+            .append("alice", &ChatMessage::assistant("hello"))
+            .unwrap();
+        source.set_session_name("alice", "greeting").unwrap();
+        source.fork_session("alice", "alice_fork", 1).unwrap();
+
+        let stats = copy_sessions(&source, &target).unwrap();
+        assert_eq!(stats.imported, 2);
+        assert_eq!(stats.messages, 3);
+
+        let alice = target.get_session_metadata("alice").unwrap();
+        assert_eq!(alice.name.as_deref(), Some("greeting"));
+        assert_eq!(alice.message_count, 2);
+        let fork = target.get_session_metadata("alice_fork").unwrap();
+        assert_eq!(fork.parent_key.as_deref(), Some("alice"));
+        assert_eq!(fork.fork_point, Some(1));
+        assert_eq!(
+            source.load_stored("alice")[0].created_at,
+            target.load_stored("alice")[0].created_at,
+            "message timestamps must survive the copy"
+        );
+
+        let again = copy_sessions(&source, &target).unwrap();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped_existing, 2);
+    }
+
     // ── §7.1 / §7.2 Config backward compatibility & migration tests ──
 
     #[test]
@@ -0,0 +1,603 @@
+//! Outbox delivery: the first attempt made by callers and the background
+//! retry loop that drains pending entries through the live channel registry.
+//!
+//! Every outbound channel send goes through [`send`]. Tools reach it through
+//! [`OutboxChannel`], which wraps the channels handed to them.
+
+use crate::outbox::{self, OutboxEntry, OutboxStatus};
+use anyhow::Result;
+use chrono::{Duration as ChronoDuration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
+use zeroclaw_api::channel::{Channel, SendMessage};
+use zeroclaw_config::schema::Config;
+
+/// Maximum entries claimed per dispatcher tick.
+const DISPATCH_BATCH_SIZE: usize = 50;
+/// How often delivered entries past their retention window are purged.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Set once a dispatcher is draining the outbox in this process.
+///
+/// Sends only go through the outbox when someone will retry their failures;
+/// one-shot CLI invocations and cron runs without a daemon send directly.
+static DISPATCHER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Channels the dispatcher retries through, keyed by channel name.
+static CHANNELS: LazyLock<RwLock<HashMap<String, Arc<dyn Channel>>>> =
+    LazyLock::new(RwLock::default);
+
+/// Builds a channel from config by name. The dispatcher uses it for entries
+/// whose channel is not registered, e.g. a channel built on demand for an
+/// announcement before the process restarted.
+pub type ChannelBuilder = fn(&Config, &str) -> Result<Arc<dyn Channel>>;
+
+/// Whether a retry dispatcher is running in this process.
+pub fn is_running() -> bool {
+    DISPATCHER_RUNNING.load(Ordering::Relaxed)
+}
+
+/// Make `channel` available to the dispatcher's retries, unless a channel
+/// of that name is already registered. Channels built on demand for a single
+/// delivery register themselves so a failed send can still be retried.
+pub fn register(channel: Arc<dyn Channel>) {
+    let mut channels = CHANNELS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    channels
+        .entry(channel.name().to_string())
+        .or_insert(channel);
+}
+
+/// Send `message` on `channel`, persisting it first so a failed attempt is
+/// retried by the dispatcher instead of being lost.
+///
+/// Returns `Ok(())` once the message is delivered or durably queued for
+/// retry. Without a running dispatcher nothing would retry a queued message,
+/// so the message is sent directly and that send's result is returned; the
+/// same applies when the outbox database itself is unavailable. Messages
+/// with attachments or interactive rich content bypass the outbox too:
+/// attachment payloads are not persisted, and a prompt delivered late is no
+/// longer useful.
+pub async fn send(config: &Config, channel: &dyn Channel, message: &SendMessage) -> Result<()> {
+    if !is_running() {
+        return channel.send(message).await;
+    }
+    send_queued(config, channel, message).await
+}
+
+async fn send_queued(config: &Config, channel: &dyn Channel, message: &SendMessage) -> Result<()> {
+    if !config.outbox.enabled || !message.attachments.is_empty() || message.rich.is_some() {
+        return channel.send(message).await;
+    }
+
+    let entry = match outbox::enqueue(config, channel.name(), message) {
+        Ok(entry) => entry,
+        Err(e) => {
+            tracing::warn!(
+                channel = channel.name(),
+                error = %e,
+                "Outbox unavailable; sending without retry"
+            );
+            return channel.send(message).await;
+        }
+    };
+
+    match channel.send(message).await {
+        Ok(()) => {
+            outbox::mark_delivered(config, &entry.id)?;
+            Ok(())
+        }
+        Err(e) => {
+            if message
+                .cancellation_token
+                .as_ref()
+                .is_some_and(|token| token.is_cancelled())
+            {
+                // The caller abandoned this delivery (e.g. superseded by a
+                // newer message); retrying it later would resurrect stale output.
+                outbox::remove_entry(config, &entry.id)?;
+                return Err(e);
+            }
+            match record_attempt_failure(config, &entry, &e)? {
+                OutboxStatus::Dead => Err(e.context("outbound message dead-lettered")),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+/// Spawn the retry dispatcher, registering the live `channels` it retries
+/// through. Entries for any other channel are sent through a channel `build`
+/// creates from config. Returns `None` when the outbox is disabled.
+pub fn spawn(
+    config: Config,
+    channels: &HashMap<String, Arc<dyn Channel>>,
+    build: Option<ChannelBuilder>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if !config.outbox.enabled {
+        return None;
+    }
+    {
+        let mut registry = CHANNELS
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for (name, channel) in channels {
+            registry.insert(name.clone(), Arc::clone(channel));
+        }
+    }
+    DISPATCHER_RUNNING.store(true, Ordering::Relaxed);
+    Some(tokio::spawn(run(config, build)))
+}
+
+async fn run(config: Config, build: Option<ChannelBuilder>) {
+    let poll = Duration::from_secs(config.outbox.poll_interval_secs.max(1));
+    let mut interval = tokio::time::interval(poll);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_purge: Option<Instant> = None;
+
+    loop {
+        interval.tick().await;
+        let channels = CHANNELS
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        if let Err(e) = drain_once(&config, &channels, build).await {
+            tracing::warn!("Outbox dispatch failed: {e}");
+        }
+        // Housekeeping: requeue entries abandoned by a crashed process (on
+        // startup and then hourly) and purge old deliveries.
+        if last_purge.is_none_or(|at| at.elapsed() >= PURGE_INTERVAL) {
+            last_purge = Some(Instant::now());
+            match outbox::recover_in_flight(&config, Utc::now()) {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("📮 Requeued {n} abandoned outbox message(s)"),
+                Err(e) => tracing::warn!("Outbox recovery failed: {e}"),
+            }
+            match outbox::purge_delivered(&config) {
+                Ok(0) => {}
+                Ok(n) => tracing::debug!("Purged {n} delivered outbox message(s)"),
+                Err(e) => tracing::warn!("Outbox purge failed: {e}"),
+            }
+        }
+    }
+}
+
+/// Attempt every due entry once. Returns how many were delivered.
+///
+/// Entries for a channel missing from `channels` go through one `build`
+/// creates from config, which is then registered for later retries. Only
+/// when that fails too does the entry spend an attempt as "not running".
+/// When a channel reports a rate limit, its remaining entries in this batch
+/// are deferred to the same retry time without spending an attempt.
+pub async fn drain_once(
+    config: &Config,
+    channels: &HashMap<String, Arc<dyn Channel>>,
+    build: Option<ChannelBuilder>,
+) -> Result<usize> {
+    let entries = outbox::claim_due(config, Utc::now(), DISPATCH_BATCH_SIZE)?;
+    let mut throttled: HashMap<String, chrono::DateTime<Utc>> = HashMap::new();
+    let mut built: HashMap<String, Arc<dyn Channel>> = HashMap::new();
+    let mut missing: HashSet<String> = HashSet::new();
+    let mut delivered = 0;
+
+    for entry in entries {
+        if let Some(until) = throttled.get(&entry.channel) {
+            outbox::defer_entry(config, &entry.id, *until)?;
+            continue;
+        }
+
+        if lookup_channel(channels, &entry.channel).is_none()
+            && !built.contains_key(&entry.channel)
+            && !missing.contains(&entry.channel)
+            && let Some(build) = build
+        {
+            match build(config, &entry.channel) {
+                Ok(channel) => {
+                    register(Arc::clone(&channel));
+                    built.insert(entry.channel.clone(), channel);
+                }
+                Err(e) => tracing::debug!(
+                    channel = %entry.channel,
+                    error = %e,
+                    "Could not build outbox channel from config"
+                ),
+            }
+        }
+        let Some(channel) =
+            lookup_channel(channels, &entry.channel).or_else(|| built.get(&entry.channel))
+        else {
+            if missing.insert(entry.channel.clone()) {
+                tracing::warn!(
+                    channel = %entry.channel,
+                    "Outbox entry targets a channel that is not running"
+                );
+            }
+            let err = anyhow::anyhow!("channel '{}' is not running", entry.channel);
+            record_attempt_failure(config, &entry, &err)?;
+            continue;
+        };
+
+        match channel.send(&entry.to_send_message()).await {
+            Ok(()) => {
+                outbox::mark_delivered(config, &entry.id)?;
+                delivered += 1;
+            }
+            Err(e) => {
+                record_attempt_failure(config, &entry, &e)?;
+                if outbox::is_rate_limited(&e) {
+                    let until = outbox::get_entry(config, &entry.id)
+                        .map(|updated| updated.next_attempt_at)
+                        .unwrap_or_else(|_| Utc::now() + ChronoDuration::seconds(60));
+                    throttled.insert(entry.channel.clone(), until);
+                }
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+fn lookup_channel<'a>(
+    channels: &'a HashMap<String, Arc<dyn Channel>>,
+    name: &str,
+) -> Option<&'a Arc<dyn Channel>> {
+    channels.get(name).or_else(|| {
+        channels
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, ch)| ch)
+    })
+}
+
+fn record_attempt_failure(
+    config: &Config,
+    entry: &OutboxEntry,
+    err: &anyhow::Error,
+) -> Result<OutboxStatus> {
+    let status = outbox::record_failure(
+        config,
+        &entry.id,
+        &format!("{err:#}"),
+        outbox::retry_after_hint(err),
+    )?;
+    if status == OutboxStatus::Dead {
+        tracing::error!(
+            channel = %entry.channel,
+            recipient = %entry.recipient,
+            id = %entry.id,
+            error = %err,
+            "Outbound message dead-lettered; replay with `zeroclaw outbox replay {}`",
+            entry.id
+        );
+    } else {
+        tracing::warn!(
+            channel = %entry.channel,
+            id = %entry.id,
+            error = %err,
+            "Outbound send failed; queued for retry"
+        );
+    }
+    Ok(status)
+}
+
+/// A channel whose sends go through the outbox; everything else is passed
+/// to the wrapped channel unchanged. Handed to tools so their messages get
+/// the same retries as replies and announcements.
+pub struct OutboxChannel {
+    inner: Arc<dyn Channel>,
+    config: Arc<Config>,
+}
+
+impl OutboxChannel {
+    pub fn new(inner: Arc<dyn Channel>, config: Arc<Config>) -> Self {
+        Self { inner, config }
+    }
+}
+
+#[async_trait::async_trait]
+impl Channel for OutboxChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        send(&self.config, self.inner.as_ref(), message).await
+    }
+
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<zeroclaw_api::channel::ChannelMessage>,
+    ) -> Result<()> {
+        self.inner.listen(tx).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    async fn start_typing(&self, recipient: &str) -> Result<()> {
+        self.inner.start_typing(recipient).await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> Result<()> {
+        self.inner.stop_typing(recipient).await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.inner.supports_draft_updates()
+    }
+
+    fn supports_multi_message_streaming(&self) -> bool {
+        self.inner.supports_multi_message_streaming()
+    }
+
+    fn supports_document_upload(&self) -> bool {
+        self.inner.supports_document_upload()
+    }
+
+    fn multi_message_delay_ms(&self) -> u64 {
+        self.inner.multi_message_delay_ms()
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> Result<Option<String>> {
+        self.inner.send_draft(message).await
+    }
+
+    async fn update_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        self.inner.update_draft(recipient, message_id, text).await
+    }
+
+    async fn update_draft_progress(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> Result<()> {
+        self.inner
+            .update_draft_progress(recipient, message_id, text)
+            .await
+    }
+
+    async fn finalize_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        self.inner.finalize_draft(recipient, message_id, text).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> Result<()> {
+        self.inner.cancel_draft(recipient, message_id).await
+    }
+
+    async fn add_reaction(&self, channel_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        self.inner.add_reaction(channel_id, message_id, emoji).await
+    }
+
+    async fn remove_reaction(&self, channel_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        self.inner
+            .remove_reaction(channel_id, message_id, emoji)
+            .await
+    }
+
+    async fn pin_message(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        self.inner.pin_message(channel_id, message_id).await
+    }
+
+    async fn unpin_message(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        self.inner.unpin_message(channel_id, message_id).await
+    }
+
+    async fn redact_message(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        reason: Option<String>,
+    ) -> Result<()> {
+        self.inner
+            .redact_message(channel_id, message_id, reason)
+            .await
+    }
+
+    async fn request_approval(
+        &self,
+        recipient: &str,
+        request: &zeroclaw_api::channel::ChannelApprovalRequest,
+    ) -> Result<Option<zeroclaw_api::channel::ChannelApprovalResponse>> {
+        self.inner.request_approval(recipient, request).await
+    }
+
+    async fn request_choice(
+        &self,
+        question: &str,
+        choices: &[String],
+        timeout: Duration,
+    ) -> Result<Option<String>> {
+        self.inner.request_choice(question, choices, timeout).await
+    }
+
+    fn supports_free_form_ask(&self) -> bool {
+        self.inner.supports_free_form_ask()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::TempDir;
+    use zeroclaw_api::channel::{ChannelMessage, ChannelRateLimited};
+
+    /// Channel that fails its first `failures` sends, then succeeds.
+    struct FlakyChannel {
+        failures: usize,
+        rate_limited: bool,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyChannel {
+        fn new(failures: usize, rate_limited: bool) -> Self {
+            Self {
+                failures,
+                rate_limited,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                if self.rate_limited {
+                    return Err(ChannelRateLimited {
+                        retry_after: Some(Duration::from_secs(120)),
+                    }
+                    .into());
+                }
+                anyhow::bail!("platform unreachable");
+            }
+            Ok(())
+        }
+
+        async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn test_config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    fn registry(channel: Arc<FlakyChannel>) -> HashMap<String, Arc<dyn Channel>> {
+        let mut map: HashMap<String, Arc<dyn Channel>> = HashMap::new();
+        map.insert("flaky".into(), channel);
+        map
+    }
+
+    #[tokio::test]
+    async fn send_marks_successful_delivery() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = FlakyChannel::new(0, false);
+
+        send_queued(&config, &channel, &SendMessage::new("hi", "u1"))
+            .await
+            .unwrap();
+
+        let entries = outbox::list_entries(&config, None, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, OutboxStatus::Delivered);
+        assert_eq!(entries[0].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn send_without_dispatcher_fails_inline() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = FlakyChannel::new(1, false);
+
+        let result = send(&config, &channel, &SendMessage::new("hi", "u1")).await;
+        assert!(result.is_err());
+        assert!(outbox::list_entries(&config, None, 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_send_is_queued_and_retried_by_dispatcher() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.outbox.initial_backoff_secs = 1;
+        let channel = Arc::new(FlakyChannel::new(1, false));
+
+        send_queued(&config, channel.as_ref(), &SendMessage::new("hi", "u1"))
+            .await
+            .unwrap();
+        let pending = outbox::list_entries(&config, Some(OutboxStatus::Pending), 10).unwrap();
+        assert_eq!(pending.len(), 1);
+
+        // Not due yet: the dispatcher leaves it alone.
+        let channels = registry(Arc::clone(&channel));
+        assert_eq!(drain_once(&config, &channels, None).await.unwrap(), 0);
+
+        outbox::defer_entry(&config, &pending[0].id, Utc::now()).unwrap();
+        assert_eq!(drain_once(&config, &channels, None).await.unwrap(), 1);
+        let entry = outbox::get_entry(&config, &pending[0].id).unwrap();
+        assert_eq!(entry.status, OutboxStatus::Delivered);
+        assert_eq!(entry.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_defers_remaining_entries_for_the_channel() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = Arc::new(FlakyChannel::new(usize::MAX, true));
+
+        let first = outbox::enqueue(&config, "flaky", &SendMessage::new("a", "u1")).unwrap();
+        let second = outbox::enqueue(&config, "flaky", &SendMessage::new("b", "u1")).unwrap();
+        let now = Utc::now();
+        outbox::defer_entry(&config, &first.id, now).unwrap();
+        outbox::defer_entry(&config, &second.id, now).unwrap();
+
+        let channels = registry(Arc::clone(&channel));
+        assert_eq!(drain_once(&config, &channels, None).await.unwrap(), 0);
+        assert_eq!(channel.calls.load(Ordering::SeqCst), 1);
+
+        let first = outbox::get_entry(&config, &first.id).unwrap();
+        let second = outbox::get_entry(&config, &second.id).unwrap();
+        assert_eq!(first.attempts, 1);
+        assert_eq!(second.attempts, 0);
+        assert!(first.next_attempt_at >= Utc::now() + ChronoDuration::seconds(110));
+        assert_eq!(second.next_attempt_at, first.next_attempt_at);
+    }
+
+    #[tokio::test]
+    async fn unknown_channel_counts_as_failed_attempt() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = outbox::enqueue(&config, "gone", &SendMessage::new("a", "u1")).unwrap();
+        outbox::defer_entry(&config, &entry.id, Utc::now()).unwrap();
+
+        let channels = registry(Arc::new(FlakyChannel::new(0, false)));
+        drain_once(&config, &channels, None).await.unwrap();
+
+        let entry = outbox::get_entry(&config, &entry.id).unwrap();
+        assert_eq!(entry.attempts, 1);
+        assert!(entry.last_error.unwrap().contains("not running"));
+    }
+
+    #[tokio::test]
+    async fn unregistered_channel_is_built_from_config_before_failing() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = outbox::enqueue(&config, "flaky", &SendMessage::new("a", "u1")).unwrap();
+        outbox::defer_entry(&config, &entry.id, Utc::now()).unwrap();
+
+        let build: ChannelBuilder = |_, name| {
+            if name == "flaky" {
+                Ok(Arc::new(FlakyChannel::new(0, false)))
+            } else {
+                anyhow::bail!("unknown channel {name}")
+            }
+        };
+        assert_eq!(
+            drain_once(&config, &HashMap::new(), Some(build))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let entry = outbox::get_entry(&config, &entry.id).unwrap();
+        assert_eq!(entry.status, OutboxStatus::Delivered);
+        assert_eq!(entry.attempts, 1);
+    }
+}
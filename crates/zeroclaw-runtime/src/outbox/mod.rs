@@ -0,0 +1,77 @@
+//! Durable outbound message outbox.
+//!
+//! Outbound channel sends are persisted to SQLite before delivery so that a
+//! rate-limited or briefly unreachable platform does not drop cron
+//! announcements or agent replies. Failed sends are retried per channel with
+//! exponential backoff (or the platform's `Retry-After` hint) and move to a
+//! dead-letter queue once `outbox.max_attempts` is exhausted.
+
+use std::time::Duration;
+use zeroclaw_api::channel::ChannelRateLimited;
+use zeroclaw_config::schema::OutboxConfig;
+
+mod store;
+mod types;
+
+pub mod dispatcher;
+
+pub use store::{
+    claim_due, defer_entry, enqueue, get_entry, list_entries, mark_delivered, purge_delivered,
+    record_failure, recover_in_flight, remove_entry, replay_dead_letters, replay_entry,
+};
+pub use types::{OutboxEntry, OutboxStatus};
+
+/// Retry delay after `attempts` failed deliveries: `initial * 2^(attempts-1)`,
+/// capped at `max_backoff_secs`.
+pub fn backoff_delay(config: &OutboxConfig, attempts: u32) -> Duration {
+    let initial = config.initial_backoff_secs.max(1);
+    let cap = config.max_backoff_secs.max(initial);
+    let exponent = attempts.saturating_sub(1).min(31);
+    Duration::from_secs(initial.saturating_mul(1u64 << exponent).min(cap))
+}
+
+/// Extract the platform's `Retry-After` hint from a channel send error.
+pub fn retry_after_hint(err: &anyhow::Error) -> Option<Duration> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<ChannelRateLimited>())
+        .and_then(|limited| limited.retry_after)
+}
+
+/// Whether a channel send error was a platform rate limit.
+pub fn is_rate_limited(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.downcast_ref::<ChannelRateLimited>().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delay_doubles_and_caps() {
+        let config = OutboxConfig {
+            initial_backoff_secs: 5,
+            max_backoff_secs: 60,
+            ..OutboxConfig::default()
+        };
+        assert_eq!(backoff_delay(&config, 1), Duration::from_secs(5));
+        assert_eq!(backoff_delay(&config, 2), Duration::from_secs(10));
+        assert_eq!(backoff_delay(&config, 4), Duration::from_secs(40));
+        assert_eq!(backoff_delay(&config, 5), Duration::from_secs(60));
+        assert_eq!(backoff_delay(&config, 200), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn retry_after_hint_reads_wrapped_rate_limit_errors() {
+        let err = anyhow::Error::new(ChannelRateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        })
+        .context("slack send failed");
+        assert!(is_rate_limited(&err));
+        assert_eq!(retry_after_hint(&err), Some(Duration::from_secs(30)));
+
+        let plain = anyhow::anyhow!("connection reset");
+        assert!(!is_rate_limited(&plain));
+        assert_eq!(retry_after_hint(&plain), None);
+    }
+}
@@ -0,0 +1,525 @@
+use crate::outbox::{OutboxEntry, OutboxStatus};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::time::Duration;
+use uuid::Uuid;
+use zeroclaw_api::channel::SendMessage;
+use zeroclaw_config::schema::Config;
+
+/// Longest error string kept per entry; platform error bodies can be large.
+const MAX_OUTBOX_ERROR_BYTES: usize = 2 * 1024;
+
+/// How long a claimed entry belongs to the process attempting it. An entry
+/// still `in_flight` after its lease was abandoned by a crashed process.
+const IN_FLIGHT_LEASE_SECS: i64 = 600;
+
+/// Persist `message` for `channel` and claim it for an immediate attempt.
+///
+/// The entry is created `in_flight`, under a lease, so the dispatcher does
+/// not race the caller's first attempt; callers must follow up with
+/// [`mark_delivered`] or [`record_failure`].
+pub fn enqueue(config: &Config, channel: &str, message: &SendMessage) -> Result<OutboxEntry> {
+    let now = Utc::now();
+    let entry = OutboxEntry {
+        id: Uuid::new_v4().to_string(),
+        channel: channel.to_ascii_lowercase(),
+        recipient: message.recipient.clone(),
+        content: message.content.clone(),
+        subject: message.subject.clone(),
+        thread_ts: message.thread_ts.clone(),
+        status: OutboxStatus::InFlight,
+        attempts: 0,
+        last_error: None,
+        created_at: now,
+        next_attempt_at: now,
+        delivered_at: None,
+    };
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO outbox (
+                id, channel, recipient, content, subject, thread_ts, status, attempts,
+                created_at, next_attempt_at, lease_until
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8, ?9, ?10)",
+            params![
+                entry.id,
+                entry.channel,
+                entry.recipient,
+                entry.content,
+                entry.subject,
+                entry.thread_ts,
+                entry.status.as_str(),
+                now.to_rfc3339(),
+                now.to_rfc3339(),
+                lease_until(now),
+            ],
+        )
+        .context("Failed to insert outbox entry")?;
+        Ok(())
+    })?;
+
+    Ok(entry)
+}
+
+pub fn get_entry(config: &Config, id: &str) -> Result<OutboxEntry> {
+    with_connection(config, |conn| {
+        conn.query_row(
+            "SELECT id, channel, recipient, content, subject, thread_ts, status, attempts,
+                    last_error, created_at, next_attempt_at, delivered_at
+             FROM outbox WHERE id = ?1",
+            params![id],
+            map_outbox_row,
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("Outbox entry '{id}' not found"))
+    })
+}
+
+/// List entries newest first, optionally filtered by status.
+pub fn list_entries(
+    config: &Config,
+    status: Option<OutboxStatus>,
+    limit: usize,
+) -> Result<Vec<OutboxEntry>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, recipient, content, subject, thread_ts, status, attempts,
+                    last_error, created_at, next_attempt_at, delivered_at
+             FROM outbox
+             WHERE ?1 IS NULL OR status = ?1
+             ORDER BY created_at DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(
+            params![status.map(OutboxStatus::as_str), limit as i64],
+            map_outbox_row,
+        )?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    })
+}
+
+/// Claim up to `limit` pending entries whose retry time has passed.
+///
+/// Claimed entries flip to `in_flight` under a lease in the same
+/// transaction, so two dispatchers sharing a workspace never pick up the
+/// same message.
+pub fn claim_due(config: &Config, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxEntry>> {
+    with_connection(config, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let entries = {
+            let mut stmt = tx.prepare(
+                "SELECT id, channel, recipient, content, subject, thread_ts, status, attempts,
+                        last_error, created_at, next_attempt_at, delivered_at
+                 FROM outbox
+                 WHERE status = 'pending' AND next_attempt_at <= ?1
+                 ORDER BY next_attempt_at ASC, created_at ASC
+                 LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![now.to_rfc3339(), limit as i64], map_outbox_row)?;
+            let mut entries = Vec::new();
+            for row in rows {
+                let mut entry = row?;
+                entry.status = OutboxStatus::InFlight;
+                entries.push(entry);
+            }
+            entries
+        };
+        for entry in &entries {
+            tx.execute(
+                "UPDATE outbox SET status = 'in_flight', lease_until = ?2 WHERE id = ?1",
+                params![entry.id, lease_until(now)],
+            )?;
+        }
+        tx.commit()?;
+        Ok(entries)
+    })
+}
+
+pub fn mark_delivered(config: &Config, id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE outbox
+             SET status = 'delivered', attempts = attempts + 1, last_error = NULL, delivered_at = ?2
+             WHERE id = ?1",
+            params![id, Utc::now().to_rfc3339()],
+        )
+        .context("Failed to mark outbox entry delivered")?;
+        Ok(())
+    })
+}
+
+/// Record a failed attempt and schedule the next one.
+///
+/// The entry is dead-lettered once it reaches `outbox.max_attempts`.
+/// Otherwise the retry waits for the exponential backoff delay, or for the
+/// platform's `retry_after` hint when that is longer. Returns the new status.
+pub fn record_failure(
+    config: &Config,
+    id: &str,
+    error: &str,
+    retry_after: Option<Duration>,
+) -> Result<OutboxStatus> {
+    let entry = get_entry(config, id)?;
+    let attempts = entry.attempts.saturating_add(1);
+    let now = Utc::now();
+
+    let (status, next_attempt_at) = if attempts >= config.outbox.max_attempts.max(1) {
+        (OutboxStatus::Dead, now)
+    } else {
+        let backoff = super::backoff_delay(&config.outbox, attempts);
+        let delay = retry_after.map_or(backoff, |hint| hint.max(backoff));
+        let delay = ChronoDuration::from_std(delay).unwrap_or(ChronoDuration::MAX);
+        (
+            OutboxStatus::Pending,
+            now.checked_add_signed(delay)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        )
+    };
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE outbox
+             SET status = ?2, attempts = ?3, last_error = ?4, next_attempt_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                status.as_str(),
+                attempts,
+                truncate_error(error),
+                next_attempt_at.to_rfc3339(),
+            ],
+        )
+        .context("Failed to record outbox delivery failure")?;
+        Ok(())
+    })?;
+
+    Ok(status)
+}
+
+/// Return a claimed entry to the queue without counting an attempt.
+///
+/// Used when a channel is throttled mid-batch: the remaining messages for it
+/// wait for the same `Retry-After` window instead of burning attempts.
+pub fn defer_entry(config: &Config, id: &str, until: DateTime<Utc>) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE outbox SET status = 'pending', next_attempt_at = ?2 WHERE id = ?1",
+            params![id, until.to_rfc3339()],
+        )
+        .context("Failed to defer outbox entry")?;
+        Ok(())
+    })
+}
+
+/// Put a dead-lettered (or pending) entry back in the queue for immediate
+/// delivery with a fresh attempt budget.
+pub fn replay_entry(config: &Config, id: &str) -> Result<OutboxEntry> {
+    let entry = get_entry(config, id)?;
+    if matches!(
+        entry.status,
+        OutboxStatus::Delivered | OutboxStatus::InFlight
+    ) {
+        anyhow::bail!(
+            "Outbox entry '{id}' is {} and cannot be replayed",
+            entry.status.as_str()
+        );
+    }
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE outbox SET status = 'pending', attempts = 0, next_attempt_at = ?2 WHERE id = ?1",
+            params![id, Utc::now().to_rfc3339()],
+        )
+        .context("Failed to replay outbox entry")?;
+        Ok(())
+    })?;
+    get_entry(config, id)
+}
+
+/// Requeue every dead-lettered entry. Returns how many were requeued.
+pub fn replay_dead_letters(config: &Config) -> Result<usize> {
+    with_connection(config, |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE outbox SET status = 'pending', attempts = 0, next_attempt_at = ?1
+                 WHERE status = 'dead'",
+                params![Utc::now().to_rfc3339()],
+            )
+            .context("Failed to replay dead-lettered outbox entries")?;
+        Ok(updated)
+    })
+}
+
+/// Return entries whose `in_flight` lease ran out by `now` to the queue.
+///
+/// Only entries abandoned by a crashed process qualify; those another live
+/// process is still attempting keep their lease. Delivery is at-least-once:
+/// a message whose send completed just before the crash may be delivered
+/// twice.
+pub fn recover_in_flight(config: &Config, now: DateTime<Utc>) -> Result<usize> {
+    with_connection(config, |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE outbox SET status = 'pending', lease_until = NULL
+                 WHERE status = 'in_flight' AND (lease_until IS NULL OR lease_until <= ?1)",
+                params![now.to_rfc3339()],
+            )
+            .context("Failed to recover in-flight outbox entries")?;
+        Ok(updated)
+    })
+}
+
+/// Delete a single entry regardless of status.
+pub fn remove_entry(config: &Config, id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        let removed = conn
+            .execute("DELETE FROM outbox WHERE id = ?1", params![id])
+            .context("Failed to delete outbox entry")?;
+        if removed == 0 {
+            anyhow::bail!("Outbox entry '{id}' not found");
+        }
+        Ok(())
+    })
+}
+
+/// Delete delivered entries older than `outbox.retention_days`.
+pub fn purge_delivered(config: &Config) -> Result<usize> {
+    let cutoff = Utc::now() - ChronoDuration::days(i64::from(config.outbox.retention_days));
+    with_connection(config, |conn| {
+        let removed = conn
+            .execute(
+                "DELETE FROM outbox WHERE status = 'delivered' AND delivered_at < ?1",
+                params![cutoff.to_rfc3339()],
+            )
+            .context("Failed to purge delivered outbox entries")?;
+        Ok(removed)
+    })
+}
+
+fn lease_until(now: DateTime<Utc>) -> String {
+    (now + ChronoDuration::seconds(IN_FLIGHT_LEASE_SECS)).to_rfc3339()
+}
+
+fn truncate_error(error: &str) -> String {
+    if error.len() <= MAX_OUTBOX_ERROR_BYTES {
+        return error.to_string();
+    }
+    let mut end = MAX_OUTBOX_ERROR_BYTES;
+    while !error.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...[truncated]", &error[..end])
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in outbox DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+fn sql_conversion_error(err: anyhow::Error) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(err.into())
+}
+
+fn map_outbox_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<OutboxEntry> {
+    let status_raw: String = row.get(6)?;
+    let created_at_raw: String = row.get(9)?;
+    let next_attempt_raw: String = row.get(10)?;
+    let delivered_at_raw: Option<String> = row.get(11)?;
+
+    Ok(OutboxEntry {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        recipient: row.get(2)?,
+        content: row.get(3)?,
+        subject: row.get(4)?,
+        thread_ts: row.get(5)?,
+        status: OutboxStatus::try_from(status_raw.as_str())
+            .map_err(|e| sql_conversion_error(anyhow::anyhow!(e)))?,
+        attempts: row.get(7)?,
+        last_error: row.get(8)?,
+        created_at: parse_rfc3339(&created_at_raw).map_err(sql_conversion_error)?,
+        next_attempt_at: parse_rfc3339(&next_attempt_raw).map_err(sql_conversion_error)?,
+        delivered_at: delivered_at_raw
+            .as_deref()
+            .map(parse_rfc3339)
+            .transpose()
+            .map_err(sql_conversion_error)?,
+    })
+}
+
+fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = config.workspace_dir.join("outbox").join("outbox.db");
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create outbox directory: {}", parent.display()))?;
+    }
+
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open outbox DB: {}", db_path.display()))?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS outbox (
+            id              TEXT PRIMARY KEY,
+            channel         TEXT NOT NULL,
+            recipient       TEXT NOT NULL,
+            content         TEXT NOT NULL,
+            subject         TEXT,
+            thread_ts       TEXT,
+            status          TEXT NOT NULL DEFAULT 'pending',
+            attempts        INTEGER NOT NULL DEFAULT 0,
+            last_error      TEXT,
+            created_at      TEXT NOT NULL,
+            next_attempt_at TEXT NOT NULL,
+            delivered_at    TEXT,
+            lease_until     TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_outbox_status_next ON outbox(status, next_attempt_at);
+        CREATE INDEX IF NOT EXISTS idx_outbox_created_at ON outbox(created_at);",
+    )
+    .context("Failed to initialize outbox schema")?;
+    crate::cron::add_column_if_missing(&conn, "outbox", "lease_until", "TEXT")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.outbox.max_attempts = 3;
+        config
+    }
+
+    #[test]
+    fn enqueue_persists_message_fields() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let message = SendMessage::with_subject("hello", "C123", "Digest")
+            .in_thread(Some("1700000000.000100".into()));
+        let entry = enqueue(&config, "Slack", &message).unwrap();
+
+        let stored = get_entry(&config, &entry.id).unwrap();
+        assert_eq!(stored.channel, "slack");
+        assert_eq!(stored.recipient, "C123");
+        assert_eq!(stored.content, "hello");
+        assert_eq!(stored.subject.as_deref(), Some("Digest"));
+        assert_eq!(stored.thread_ts.as_deref(), Some("1700000000.000100"));
+        assert_eq!(stored.status, OutboxStatus::InFlight);
+
+        let rebuilt = stored.to_send_message();
+        assert_eq!(rebuilt.subject.as_deref(), Some("Digest"));
+        assert_eq!(rebuilt.thread_ts.as_deref(), Some("1700000000.000100"));
+    }
+
+    #[test]
+    fn record_failure_schedules_retry_then_dead_letters() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = enqueue(&config, "telegram", &SendMessage::new("hi", "42")).unwrap();
+
+        let status = record_failure(&config, &entry.id, "boom", None).unwrap();
+        assert_eq!(status, OutboxStatus::Pending);
+        let stored = get_entry(&config, &entry.id).unwrap();
+        assert_eq!(stored.attempts, 1);
+        assert_eq!(stored.last_error.as_deref(), Some("boom"));
+        assert!(stored.next_attempt_at > Utc::now());
+
+        record_failure(&config, &entry.id, "boom", None).unwrap();
+        let status = record_failure(&config, &entry.id, "still down", None).unwrap();
+        assert_eq!(status, OutboxStatus::Dead);
+        let dead = list_entries(&config, Some(OutboxStatus::Dead), 10).unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].last_error.as_deref(), Some("still down"));
+    }
+
+    #[test]
+    fn record_failure_honours_longer_retry_after() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = enqueue(&config, "slack", &SendMessage::new("hi", "C1")).unwrap();
+
+        record_failure(
+            &config,
+            &entry.id,
+            "rate limited",
+            Some(Duration::from_secs(600)),
+        )
+        .unwrap();
+        let stored = get_entry(&config, &entry.id).unwrap();
+        assert!(stored.next_attempt_at >= Utc::now() + ChronoDuration::seconds(590));
+    }
+
+    #[test]
+    fn claim_due_only_returns_pending_entries_past_their_retry_time() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let in_flight = enqueue(&config, "slack", &SendMessage::new("a", "C1")).unwrap();
+        let waiting = enqueue(&config, "slack", &SendMessage::new("b", "C1")).unwrap();
+        record_failure(&config, &waiting.id, "boom", None).unwrap();
+
+        assert!(claim_due(&config, Utc::now(), 10).unwrap().is_empty());
+
+        let later = Utc::now() + ChronoDuration::hours(1);
+        let claimed = claim_due(&config, later, 10).unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, waiting.id);
+        assert_eq!(
+            get_entry(&config, &waiting.id).unwrap().status,
+            OutboxStatus::InFlight
+        );
+        assert!(claim_due(&config, later, 10).unwrap().is_empty());
+
+        // Both leases are still held by this process.
+        assert_eq!(recover_in_flight(&config, Utc::now()).unwrap(), 0);
+        let expired = later + ChronoDuration::seconds(IN_FLIGHT_LEASE_SECS);
+        assert_eq!(recover_in_flight(&config, expired).unwrap(), 2);
+        assert_eq!(
+            get_entry(&config, &in_flight.id).unwrap().status,
+            OutboxStatus::Pending
+        );
+    }
+
+    #[test]
+    fn replay_resets_dead_letters() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = enqueue(&config, "discord", &SendMessage::new("hi", "1")).unwrap();
+        for _ in 0..3 {
+            record_failure(&config, &entry.id, "boom", None).unwrap();
+        }
+
+        let replayed = replay_entry(&config, &entry.id).unwrap();
+        assert_eq!(replayed.status, OutboxStatus::Pending);
+        assert_eq!(replayed.attempts, 0);
+
+        mark_delivered(&config, &entry.id).unwrap();
+        assert!(replay_entry(&config, &entry.id).is_err());
+        assert_eq!(replay_dead_letters(&config).unwrap(), 0);
+    }
+
+    #[test]
+    fn purge_delivered_keeps_recent_entries() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let entry = enqueue(&config, "slack", &SendMessage::new("hi", "C1")).unwrap();
+        mark_delivered(&config, &entry.id).unwrap();
+
+        assert_eq!(purge_delivered(&config).unwrap(), 0);
+        remove_entry(&config, &entry.id).unwrap();
+        assert!(get_entry(&config, &entry.id).is_err());
+    }
+}
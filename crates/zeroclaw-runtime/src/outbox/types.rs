@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of an outbox entry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    /// Waiting for its next delivery attempt.
+    Pending,
+    /// Claimed by a sender; an attempt is in progress.
+    InFlight,
+    /// Accepted by the platform.
+    Delivered,
+    /// Exhausted its attempts; kept in the dead-letter queue until replayed.
+    Dead,
+}
+
+impl OutboxStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::InFlight => "in_flight",
+            Self::Delivered => "delivered",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+impl TryFrom<&str> for OutboxStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "pending" => Ok(Self::Pending),
+            "in_flight" => Ok(Self::InFlight),
+            "delivered" => Ok(Self::Delivered),
+            "dead" => Ok(Self::Dead),
+            _ => Err(format!(
+                "Invalid outbox status '{value}'. Expected one of: 'pending', 'in_flight', 'delivered', 'dead'"
+            )),
+        }
+    }
+}
+
+/// A persisted outbound message and its delivery bookkeeping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct OutboxEntry {
+    pub id: String,
+    /// Channel name as registered with the orchestrator (e.g. `"slack"`).
+    pub channel: String,
+    pub recipient: String,
+    pub content: String,
+    pub subject: Option<String>,
+    pub thread_ts: Option<String>,
+    pub status: OutboxStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
+    pub created_at: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
+    pub next_attempt_at: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "Option<String>"))]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxEntry {
+    /// Rebuild the `SendMessage` this entry was created from.
+    ///
+    /// Cancellation tokens and attachments are never persisted, so retries
+    /// always carry plain content.
+    pub fn to_send_message(&self) -> zeroclaw_api::channel::SendMessage {
+        let mut message = zeroclaw_api::channel::SendMessage::new(&self.content, &self.recipient)
+            .in_thread(self.thread_ts.clone());
+        message.subject = self.subject.clone();
+        message
+    }
+}
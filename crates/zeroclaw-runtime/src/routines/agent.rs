@@ -0,0 +1,168 @@
+//! Agent routine action — renders a prompt from the triggering events and
+//! runs an isolated agent turn, like cron agent jobs and Hands.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use zeroclaw_config::schema::Config;
+
+use super::event_matcher::RoutineEvent;
+use crate::cron::DeliveryConfig;
+use crate::cron::scheduler::{authorize_agent_run, deliver_if_configured};
+use crate::security::SecurityPolicy;
+use crate::util::{json_path_text, render_template};
+
+/// Run the agent on a prompt rendered from the events that fired a routine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentAction {
+    /// Prompt template.  `{{source}}`, `{{topic}}`, `{{payload}}` and
+    /// `{{timestamp}}` refer to the triggering event, `{{payload.a.b}}` to a
+    /// field of its JSON payload, `{{routine}}` to the routine name,
+    /// `{{count}}` to the number of bundled events and `{{events}}` to the
+    /// bundle as a JSON array.
+    pub prompt: String,
+    /// Model override (None = default provider model)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Tools the agent may use (None = all available)
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Where the agent's reply is announced (defaults to no delivery)
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
+}
+
+/// Fill `template` from the events that fired `routine`.  The triggering
+/// event is the last one; unknown placeholders render empty.
+pub fn render_prompt(template: &str, routine: &str, events: &[RoutineEvent]) -> String {
+    let trigger = events.last();
+    let payload_json = trigger
+        .and_then(|e| e.payload.as_deref())
+        .and_then(|p| serde_json::from_str::<serde_json::Value>(p).ok());
+
+    render_template(template, |key| match key {
+        "routine" => Some(routine.to_string()),
+        "count" => Some(events.len().to_string()),
+        "events" => serde_json::to_string(events).ok(),
+        "source" => trigger.map(|e| e.source.clone()),
+        "topic" => trigger.map(|e| e.topic.clone()),
+        "timestamp" => trigger.map(|e| e.timestamp.clone()),
+        "payload" => trigger.and_then(|e| e.payload.clone()),
+        key => {
+            let path = key.strip_prefix("payload.")?;
+            json_path_text(payload_json.as_ref()?, path)
+        }
+    })
+}
+
+/// Run `action` for `routine` and deliver the reply.  Returns the reply.
+pub async fn run_agent_action(
+    config: &Config,
+    routine: &str,
+    action: &AgentAction,
+    events: &[RoutineEvent],
+) -> Result<String> {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    authorize_agent_run(&security)?;
+
+    let mut agent_config = config.clone();
+    agent_config.memory.auto_save = false;
+    let session_path =
+        std::path::PathBuf::from(format!("routine-{routine}-{}", uuid::Uuid::new_v4()));
+    let prompt = format!(
+        "[routine:{routine}] {}",
+        render_prompt(&action.prompt, routine, events)
+    );
+
+    let reply = Box::pin(crate::agent::run(
+        agent_config,
+        Some(prompt),
+        None,
+        action.model.clone(),
+        config
+            .providers
+            .fallback_provider()
+            .and_then(|e| e.temperature)
+            .unwrap_or(0.7),
+        vec![],
+        false,
+        Some(session_path),
+        action.allowed_tools.clone(),
+    ))
+    .await?;
+
+    if let Err(e) = deliver_if_configured(config, &action.delivery, &reply).await {
+        if !action.delivery.best_effort {
+            return Err(e.context("delivery failed"));
+        }
+        tracing::warn!(routine = %routine, "Routine agent reply delivery failed: {e}");
+    }
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(topic: &str, payload: &str) -> RoutineEvent {
+        RoutineEvent {
+            source: "webhook".into(),
+            topic: topic.into(),
+            payload: Some(payload.into()),
+            timestamp: "2026-03-24T00:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn render_prompt_fills_event_fields() {
+        let events = [
+            event("/webhook/github", "{}"),
+            event(
+                "/webhook/github",
+                r#"{"issue":{"title":"Crash on start","labels":[{"name":"bug"}]}}"#,
+            ),
+        ];
+        let prompt = render_prompt(
+            "[{{routine}}/{{count}}] {{topic}}: {{ payload.issue.title }} ({{payload.issue.labels.0.name}}){{payload.missing}}{{unknown}}",
+            "triage",
+            &events,
+        );
+        assert_eq!(prompt, "[triage/2] /webhook/github: Crash on start (bug)");
+    }
+
+    #[test]
+    fn render_prompt_handles_plain_payloads_and_bundles() {
+        let events = [event("/deploy", "not json")];
+        assert_eq!(
+            render_prompt(
+                "{{payload}} {{payload.field}}at {{timestamp}}",
+                "r",
+                &events
+            ),
+            "not json at 2026-03-24T00:00:00Z"
+        );
+        let bundle: Vec<RoutineEvent> =
+            serde_json::from_str(&render_prompt("{{events}}", "r", &events)).unwrap();
+        assert_eq!(bundle.len(), 1);
+        assert_eq!(render_prompt("left {{open", "r", &events), "left {{open");
+    }
+
+    #[test]
+    fn agent_action_deserializes_from_toml() {
+        let action: crate::routines::RoutineAction = toml::from_str(
+            r##"
+            type = "agent"
+            prompt = "Triage {{payload.issue.title}}"
+            model = "fast"
+            allowed_tools = ["web_fetch"]
+            delivery = { mode = "announce", channel = "slack", to = "#eng" }
+            "##,
+        )
+        .unwrap();
+        let crate::routines::RoutineAction::Agent(agent) = action else {
+            panic!("expected agent action");
+        };
+        assert_eq!(agent.model.as_deref(), Some("fast"));
+        assert_eq!(agent.allowed_tools, Some(vec!["web_fetch".to_string()]));
+        assert_eq!(agent.delivery.to.as_deref(), Some("#eng"));
+    }
+}
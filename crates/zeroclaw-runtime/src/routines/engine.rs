@@ -5,14 +5,29 @@
 //! its patterns, the associated action fires (provided cooldown has elapsed).
 //! The engine bridges channel messages, cron ticks, webhooks, and system events
 //! into the existing SOP pipeline.
+//!
+//! Patterns with an [`EventWindow`] fire on aggregates instead: a count or
+//! rate of matching events inside a sliding window, or the absence of any
+//! matching event for a while (checked by [`RoutinesEngine::tick`]).  Window
+//! state is persisted in the workspace when the engine is built with
+//! [`RoutinesEngine::with_state`].
+//!
+//! A routine may name a calendar; outside the dates it allows, matches are
+//! reported as [`RoutineDispatchResult::OutsideCalendar`] and absence
+//! windows wait.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
-use super::event_matcher::{EventPattern, RoutineEvent, matches_any};
+use super::agent::AgentAction;
+use super::event_matcher::{EventPattern, EventWindow, RoutineEvent, matches};
+use super::store::{self, WindowState};
+use crate::calendar::{Calendar, CalendarRule};
 
 /// What happens when a routine fires.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +41,8 @@ pub enum RoutineAction {
     Message { channel: String, text: String },
     /// Run a cron job by name.
     CronJob { job_name: String },
+    /// Run an isolated agent turn on a prompt rendered from the events.
+    Agent(AgentAction),
 }
 
 /// A single automation routine definition.
@@ -46,6 +63,9 @@ pub struct Routine {
     /// Whether this routine is enabled.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Calendar that decides on which dates the routine may fire.
+    #[serde(default)]
+    pub calendar: Option<CalendarRule>,
 }
 
 fn default_enabled() -> bool {
@@ -66,7 +86,12 @@ pub enum RoutineDispatchResult {
     Fired {
         routine_name: String,
         action: RoutineAction,
+        /// Events that fired the routine: the matching event, every event of
+        /// a count/rate window, or a synthetic event for an absence window.
+        events: Vec<RoutineEvent>,
     },
+    /// The event fed a window whose condition is not met yet.
+    Pending { routine_name: String },
     /// The routine matched but is in cooldown.
     Cooldown {
         routine_name: String,
@@ -74,6 +99,8 @@ pub enum RoutineDispatchResult {
     },
     /// The routine matched but is disabled.
     Disabled { routine_name: String },
+    /// The routine matched but its calendar rules out this time.
+    OutsideCalendar { routine_name: String },
     /// No routine matched the event.
     NoMatch,
 }
@@ -83,6 +110,12 @@ pub struct RoutinesEngine {
     routines: Vec<Routine>,
     /// Last-fired timestamp per routine name.
     cooldowns: HashMap<String, Instant>,
+    /// Window state per routine name and [`pattern_key`].
+    windows: HashMap<(String, String), WindowState>,
+    /// Workspace the window state is persisted in; `None` keeps it in memory.
+    state_dir: Option<PathBuf>,
+    /// Calendars the routines may reference, by name.
+    calendars: HashMap<String, Calendar>,
 }
 
 impl RoutinesEngine {
@@ -91,9 +124,33 @@ impl RoutinesEngine {
         Self {
             routines,
             cooldowns: HashMap::new(),
+            windows: HashMap::new(),
+            state_dir: None,
+            calendars: HashMap::new(),
+        }
+    }
+
+    /// Create an engine whose window state is loaded from and saved to the
+    /// workspace, so restarts do not reset counts or absence timers.
+    pub fn with_state(routines: Vec<Routine>, workspace_dir: &Path) -> Self {
+        let windows = store::load_windows(workspace_dir).unwrap_or_else(|e| {
+            warn!("Failed to load routine window state: {e}");
+            HashMap::new()
+        });
+        Self {
+            windows,
+            state_dir: Some(workspace_dir.to_path_buf()),
+            ..Self::new(routines)
         }
     }
 
+    /// Use `calendars` for routines that name one.
+    #[must_use]
+    pub fn with_calendars(mut self, calendars: HashMap<String, Calendar>) -> Self {
+        self.calendars = calendars;
+        self
+    }
+
     /// Create an empty engine.
     pub fn empty() -> Self {
         Self::new(Vec::new())
@@ -124,19 +181,40 @@ impl RoutinesEngine {
         let before = self.routines.len();
         self.routines.retain(|r| r.name != name);
         self.cooldowns.remove(name);
+        self.windows.retain(|(routine, _), _| routine != name);
+        if let Some(dir) = &self.state_dir
+            && let Err(e) = store::delete_windows(dir, name)
+        {
+            warn!(routine = %name, "failed to delete routine window state: {e}");
+        }
         self.routines.len() < before
     }
 
     /// Dispatch an event to all matching routines.
     ///
-    /// Returns a result for each matching routine (fired, cooldown, or
-    /// disabled).  If no routine matches, returns `[NoMatch]`.
+    /// Returns a result for each matching routine (fired, pending, cooldown,
+    /// or disabled).  If no routine matches, returns `[NoMatch]`.
     pub fn dispatch(&mut self, event: &RoutineEvent) -> Vec<RoutineDispatchResult> {
+        self.dispatch_at(event, Utc::now())
+    }
+
+    /// [`dispatch`](Self::dispatch) with an explicit arrival time for the
+    /// window bookkeeping.
+    pub fn dispatch_at(
+        &mut self,
+        event: &RoutineEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<RoutineDispatchResult> {
         let mut results = Vec::new();
-        let now = Instant::now();
 
         for routine in &self.routines {
-            if !matches_any(&routine.patterns, event) {
+            let matched: Vec<(usize, &EventPattern)> = routine
+                .patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, pattern)| matches(pattern, event))
+                .collect();
+            if matched.is_empty() {
                 continue;
             }
 
@@ -148,32 +226,77 @@ impl RoutinesEngine {
                 continue;
             }
 
-            // Check cooldown
-            if routine.cooldown_secs > 0
-                && let Some(last_fired) = self.cooldowns.get(&routine.name)
-            {
-                let elapsed = now.saturating_duration_since(*last_fired);
-                let cooldown = Duration::from_secs(routine.cooldown_secs);
-                if elapsed < cooldown {
-                    let remaining = cooldown.saturating_sub(elapsed).as_secs();
-                    debug!(
-                        routine = %routine.name,
-                        remaining_secs = remaining,
-                        "routine in cooldown"
-                    );
-                    results.push(RoutineDispatchResult::Cooldown {
-                        routine_name: routine.name.clone(),
-                        remaining_secs: remaining,
-                    });
+            // Feed every matched window, then fire on the first pattern whose
+            // condition holds: a plain pattern, or an exceeded count/rate.
+            let mut trigger = None;
+            for (index, pattern) in matched {
+                let Some(window) = pattern.window else {
+                    trigger.get_or_insert(None);
                     continue;
+                };
+                let key = (routine.name.clone(), pattern_key(index, pattern));
+                let state = self
+                    .windows
+                    .entry(key.clone())
+                    .or_insert_with(|| WindowState::new(now));
+                let count = state.record(now, event, window.window_secs());
+                if window.is_exceeded(count) && trigger.is_none() {
+                    trigger = Some(Some(key.clone()));
                 }
+                save_window(self.state_dir.as_deref(), &key, state);
+            }
+
+            let Some(trigger) = trigger else {
+                debug!(routine = %routine.name, "routine window condition not met");
+                results.push(RoutineDispatchResult::Pending {
+                    routine_name: routine.name.clone(),
+                });
+                continue;
+            };
+
+            if outside_calendar(&self.calendars, routine, now) {
+                debug!(routine = %routine.name, "routine matched outside its calendar");
+                results.push(RoutineDispatchResult::OutsideCalendar {
+                    routine_name: routine.name.clone(),
+                });
+                continue;
+            }
+
+            if let Some(remaining) = cooldown_remaining(&self.cooldowns, routine) {
+                debug!(
+                    routine = %routine.name,
+                    remaining_secs = remaining,
+                    "routine in cooldown"
+                );
+                results.push(RoutineDispatchResult::Cooldown {
+                    routine_name: routine.name.clone(),
+                    remaining_secs: remaining,
+                });
+                continue;
             }
 
-            info!(routine = %routine.name, source = %event.source, topic = %event.topic, "routine fired");
-            self.cooldowns.insert(routine.name.clone(), now);
+            let events = match trigger {
+                Some(key) => {
+                    let state = self.windows.get_mut(&key).expect("window recorded above");
+                    let events = state.take_bundle();
+                    save_window(self.state_dir.as_deref(), &key, state);
+                    events
+                }
+                None => vec![event.clone()],
+            };
+
+            info!(
+                routine = %routine.name,
+                source = %event.source,
+                topic = %event.topic,
+                events = events.len(),
+                "routine fired"
+            );
+            self.cooldowns.insert(routine.name.clone(), Instant::now());
             results.push(RoutineDispatchResult::Fired {
                 routine_name: routine.name.clone(),
                 action: routine.action.clone(),
+                events,
             });
         }
 
@@ -184,12 +307,126 @@ impl RoutinesEngine {
         results
     }
 
+    /// Check absence windows at `now` and fire routines whose patterns have
+    /// seen no matching event for their window.  Each silence fires once;
+    /// the next matching event re-arms the window.  Call periodically.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<RoutineDispatchResult> {
+        let mut results = Vec::new();
+
+        for routine in self.routines.iter().filter(|r| r.enabled) {
+            for (index, pattern) in routine.patterns.iter().enumerate() {
+                let Some(EventWindow::Absence { window_secs }) = pattern.window else {
+                    continue;
+                };
+                let key = (routine.name.clone(), pattern_key(index, pattern));
+                let state = self.windows.entry(key.clone()).or_insert_with(|| {
+                    let state = WindowState::new(now);
+                    save_window(self.state_dir.as_deref(), &key, &state);
+                    state
+                });
+                if state.absence_fired {
+                    continue;
+                }
+
+                let since = state.last_seen.unwrap_or(state.armed_at);
+                let silent_secs = (now - since).num_seconds();
+                if silent_secs < i64::try_from(window_secs).unwrap_or(i64::MAX) {
+                    continue;
+                }
+                // Outside the calendar the silence keeps counting and fires
+                // on the first tick the calendar allows.
+                if cooldown_remaining(&self.cooldowns, routine).is_some()
+                    || outside_calendar(&self.calendars, routine, now)
+                {
+                    continue;
+                }
+
+                state.absence_fired = true;
+                save_window(self.state_dir.as_deref(), &key, state);
+
+                info!(
+                    routine = %routine.name,
+                    source = %pattern.source,
+                    pattern = %pattern.pattern,
+                    silent_secs,
+                    "routine fired on absence"
+                );
+                self.cooldowns.insert(routine.name.clone(), Instant::now());
+                results.push(RoutineDispatchResult::Fired {
+                    routine_name: routine.name.clone(),
+                    action: routine.action.clone(),
+                    events: vec![RoutineEvent {
+                        source: pattern.source.clone(),
+                        topic: pattern.pattern.clone(),
+                        payload: Some(
+                            serde_json::json!({
+                                "absence": true,
+                                "silent_secs": silent_secs,
+                                "last_seen": state.last_seen.map(|at| at.to_rfc3339()),
+                            })
+                            .to_string(),
+                        ),
+                        timestamp: now.to_rfc3339(),
+                    }],
+                });
+                // One firing per routine per tick, like one per event.
+                break;
+            }
+        }
+
+        results
+    }
+
     /// Clear all cooldown state.
     pub fn reset_cooldowns(&mut self) {
         self.cooldowns.clear();
     }
 }
 
+/// Seconds left on `routine`'s cooldown, if it is cooling down.
+fn cooldown_remaining(cooldowns: &HashMap<String, Instant>, routine: &Routine) -> Option<u64> {
+    if routine.cooldown_secs == 0 {
+        return None;
+    }
+    let last_fired = cooldowns.get(&routine.name)?;
+    let elapsed = Instant::now().saturating_duration_since(*last_fired);
+    let cooldown = Duration::from_secs(routine.cooldown_secs);
+    (elapsed < cooldown).then(|| cooldown.saturating_sub(elapsed).as_secs())
+}
+
+/// Whether `routine` names a calendar that rules out `now`.  Unknown
+/// calendars do not block the routine.
+fn outside_calendar(
+    calendars: &HashMap<String, Calendar>,
+    routine: &Routine,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(rule) = &routine.calendar else {
+        return false;
+    };
+    match calendars.get(&rule.name) {
+        Some(calendar) => !calendar.allows(rule.mode, now, None),
+        None => {
+            warn!(routine = %routine.name, calendar = %rule.name, "unknown routine calendar");
+            false
+        }
+    }
+}
+
+/// Identify a pattern's window by position and content, so editing a
+/// routine's patterns starts the affected windows afresh.
+fn pattern_key(index: usize, pattern: &EventPattern) -> String {
+    format!("{index}:{}:{}", pattern.source, pattern.pattern)
+}
+
+fn save_window(state_dir: Option<&Path>, key: &(String, String), state: &WindowState) {
+    if let Some(dir) = state_dir
+        && let Err(e) = store::save_window(dir, &key.0, &key.1, state)
+    {
+        warn!(routine = %key.0, "failed to persist routine window state: {e}");
+    }
+}
+
 /// Load routines from a TOML file.
 pub fn load_routines_from_file(path: &std::path::Path) -> Vec<Routine> {
     match std::fs::read_to_string(path) {
@@ -235,12 +472,14 @@ mod tests {
                 source: source.into(),
                 pattern: pattern.into(),
                 strategy,
+                window: None,
             }],
             action: RoutineAction::Sop {
                 name: "test-sop".into(),
             },
             cooldown_secs: 0,
             enabled: true,
+            calendar: None,
         }
     }
 
@@ -437,4 +676,139 @@ name = "check-build"
         let parsed: RoutineAction = serde_json::from_str(&json).unwrap();
         assert!(matches!(parsed, RoutineAction::Sop { name } if name == "test-sop"));
     }
+
+    fn windowed_routine(window: EventWindow) -> Routine {
+        let mut routine = test_routine("guard", "webhook", "/auth/failed", MatchStrategy::Exact);
+        routine.patterns[0].window = Some(window);
+        routine
+    }
+
+    #[test]
+    fn count_window_fires_with_bundle() {
+        let mut engine = RoutinesEngine::new(vec![windowed_routine(EventWindow::Count {
+            threshold: 3,
+            window_secs: 60,
+        })]);
+        let start = Utc::now();
+        let event = test_event("webhook", "/auth/failed");
+
+        for offset in [0, 10] {
+            let results = engine.dispatch_at(&event, start + chrono::Duration::seconds(offset));
+            assert!(matches!(results[0], RoutineDispatchResult::Pending { .. }));
+        }
+        // The first event has slid out of the window by now.
+        let results = engine.dispatch_at(&event, start + chrono::Duration::seconds(65));
+        assert!(matches!(results[0], RoutineDispatchResult::Pending { .. }));
+
+        let results = engine.dispatch_at(&event, start + chrono::Duration::seconds(66));
+        match &results[0] {
+            RoutineDispatchResult::Fired { events, .. } => assert_eq!(events.len(), 3),
+            other => panic!("expected Fired, got {other:?}"),
+        }
+        // Firing drains the window.
+        let results = engine.dispatch_at(&event, start + chrono::Duration::seconds(67));
+        assert!(matches!(results[0], RoutineDispatchResult::Pending { .. }));
+    }
+
+    #[test]
+    fn absence_window_fires_once_per_silence() {
+        let mut engine = RoutinesEngine::new(vec![windowed_routine(EventWindow::Absence {
+            window_secs: 300,
+        })]);
+        let start = Utc::now();
+
+        assert!(engine.tick(start).is_empty());
+        assert!(
+            engine
+                .tick(start + chrono::Duration::seconds(299))
+                .is_empty()
+        );
+
+        let results = engine.tick(start + chrono::Duration::seconds(300));
+        match &results[0] {
+            RoutineDispatchResult::Fired { events, .. } => {
+                assert_eq!(events[0].topic, "/auth/failed");
+                assert!(
+                    events[0]
+                        .payload
+                        .as_deref()
+                        .unwrap()
+                        .contains("\"absence\":true")
+                );
+            }
+            other => panic!("expected Fired, got {other:?}"),
+        }
+        assert!(
+            engine
+                .tick(start + chrono::Duration::seconds(900))
+                .is_empty()
+        );
+
+        // A matching event re-arms the window.
+        let seen_at = start + chrono::Duration::seconds(1000);
+        let results = engine.dispatch_at(&test_event("webhook", "/auth/failed"), seen_at);
+        assert!(matches!(results[0], RoutineDispatchResult::Pending { .. }));
+        assert!(
+            engine
+                .tick(seen_at + chrono::Duration::seconds(100))
+                .is_empty()
+        );
+        assert_eq!(
+            engine.tick(seen_at + chrono::Duration::seconds(300)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn window_state_survives_restart() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let window = EventWindow::Count {
+            threshold: 2,
+            window_secs: 600,
+        };
+        let event = test_event("webhook", "/auth/failed");
+        let now = Utc::now();
+
+        let mut engine = RoutinesEngine::with_state(vec![windowed_routine(window)], tmp.path());
+        let results = engine.dispatch_at(&event, now);
+        assert!(matches!(results[0], RoutineDispatchResult::Pending { .. }));
+
+        let mut engine = RoutinesEngine::with_state(vec![windowed_routine(window)], tmp.path());
+        let results = engine.dispatch_at(&event, now + chrono::Duration::seconds(1));
+        assert!(matches!(results[0], RoutineDispatchResult::Fired { .. }));
+
+        assert!(engine.remove_routine("guard"));
+        assert!(store::load_windows(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn calendar_suppresses_routine_outside_allowed_dates() {
+        let calendar = Calendar::from_config(
+            &zeroclaw_config::schema::CalendarConfig {
+                dates: vec!["2026-12-25".into()],
+                ..Default::default()
+            },
+            Path::new("."),
+        )
+        .unwrap();
+        let mut routine = test_routine("xmas", "webhook", "/ping", MatchStrategy::Exact);
+        routine.calendar = Some(CalendarRule {
+            name: "holidays".into(),
+            mode: crate::calendar::CalendarMode::Exclude,
+        });
+        let mut engine = RoutinesEngine::new(vec![routine])
+            .with_calendars(HashMap::from([("holidays".to_string(), calendar)]));
+        let event = test_event("webhook", "/ping");
+
+        let holiday = "2026-12-25T12:00:00Z".parse().unwrap();
+        let results = engine.dispatch_at(&event, holiday);
+        assert!(matches!(
+            results[0],
+            RoutineDispatchResult::OutsideCalendar { .. }
+        ));
+
+        let workday = "2026-12-28T12:00:00Z".parse().unwrap();
+        let results = engine.dispatch_at(&event, workday);
+        assert!(matches!(results[0], RoutineDispatchResult::Fired { .. }));
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! Supports three match strategies: exact, glob, and regex.  Each routine
 //! declares one or more [`EventPattern`]s; an incoming [`RoutineEvent`] fires
-//! the routine when **any** pattern matches.
+//! the routine when **any** pattern matches.  A pattern with an
+//! [`EventWindow`] instead feeds an aggregate condition (count, rate or
+//! absence over a sliding window) that the engine evaluates.
 
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +35,61 @@ pub struct EventPattern {
     /// How to interpret `pattern`.
     #[serde(default)]
     pub strategy: MatchStrategy,
+
+    /// Aggregate condition over matching events.  Without a window every
+    /// matching event fires the routine.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<EventWindow>,
+}
+
+/// Aggregate condition evaluated over the events matching a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventWindow {
+    /// Fire once `threshold` matching events arrive within `window_secs`.
+    Count { threshold: u32, window_secs: u64 },
+    /// Fire when the matching-event rate over the last `window_secs`,
+    /// scaled to an hour, rises above `per_hour`.
+    Rate {
+        per_hour: f64,
+        #[serde(default = "default_rate_window_secs")]
+        window_secs: u64,
+    },
+    /// Fire when no matching event has arrived for `window_secs`.  Fires
+    /// once per silence and re-arms on the next matching event.
+    Absence { window_secs: u64 },
+}
+
+fn default_rate_window_secs() -> u64 {
+    3600
+}
+
+impl EventWindow {
+    /// Length of the sliding window.
+    pub fn window_secs(&self) -> u64 {
+        match self {
+            Self::Count { window_secs, .. }
+            | Self::Rate { window_secs, .. }
+            | Self::Absence { window_secs } => *window_secs,
+        }
+    }
+
+    /// Whether `count` events inside the window satisfy a count or rate
+    /// condition.  Always `false` for [`EventWindow::Absence`].
+    pub fn is_exceeded(&self, count: usize) -> bool {
+        match *self {
+            Self::Count { threshold, .. } => count >= threshold.max(1) as usize,
+            Self::Rate {
+                per_hour,
+                window_secs,
+            } => {
+                #[allow(clippy::cast_precision_loss)]
+                let rate = count as f64 * 3600.0 / window_secs.max(1) as f64;
+                rate > per_hour
+            }
+            Self::Absence { .. } => false,
+        }
+    }
 }
 
 /// An event emitted by the system that may trigger routines.
@@ -94,6 +151,7 @@ mod tests {
             source: "webhook".into(),
             pattern: "/api/deploy".into(),
             strategy: MatchStrategy::Exact,
+            window: None,
         };
         assert!(matches(&pat, &event("webhook", "/api/deploy")));
         assert!(!matches(&pat, &event("webhook", "/api/deploy/staging")));
@@ -106,6 +164,7 @@ mod tests {
             source: "channel".into(),
             pattern: "telegram-*".into(),
             strategy: MatchStrategy::Glob,
+            window: None,
         };
         assert!(matches(&pat, &event("channel", "telegram-main")));
         assert!(matches(&pat, &event("channel", "telegram-alerts")));
@@ -118,6 +177,7 @@ mod tests {
             source: "system".into(),
             pattern: r"^build\.(success|failure)$".into(),
             strategy: MatchStrategy::Regex,
+            window: None,
         };
         assert!(matches(&pat, &event("system", "build.success")));
         assert!(matches(&pat, &event("system", "build.failure")));
@@ -131,11 +191,13 @@ mod tests {
                 source: "webhook".into(),
                 pattern: "/deploy".into(),
                 strategy: MatchStrategy::Exact,
+                window: None,
             },
             EventPattern {
                 source: "channel".into(),
                 pattern: "slack-*".into(),
                 strategy: MatchStrategy::Glob,
+                window: None,
             },
         ];
         assert!(matches_any(&patterns, &event("channel", "slack-general")));
@@ -151,6 +213,7 @@ mod tests {
             source: "cron".into(),
             pattern: "*".into(),
             strategy: MatchStrategy::Glob,
+            window: None,
         };
         assert!(!matches(&pat, &event("webhook", "anything")));
     }
@@ -161,6 +224,7 @@ mod tests {
             source: "system".into(),
             pattern: "[invalid".into(),
             strategy: MatchStrategy::Regex,
+            window: None,
         };
         assert!(!matches(&pat, &event("system", "anything")));
     }
@@ -171,11 +235,50 @@ mod tests {
             source: "system".into(),
             pattern: "[!invalid".into(),
             strategy: MatchStrategy::Glob,
+            window: None,
         };
         // glob::Pattern::new will fail for malformed patterns
         assert!(!matches(&pat, &event("system", "anything")));
     }
 
+    #[test]
+    fn count_and_rate_windows_apply_thresholds() {
+        let count = EventWindow::Count {
+            threshold: 5,
+            window_secs: 120,
+        };
+        assert!(!count.is_exceeded(4));
+        assert!(count.is_exceeded(5));
+
+        // 3 events in 10 minutes is 18/hour.
+        let rate = EventWindow::Rate {
+            per_hour: 12.0,
+            window_secs: 600,
+        };
+        assert!(!rate.is_exceeded(2));
+        assert!(rate.is_exceeded(3));
+        assert!(!EventWindow::Absence { window_secs: 60 }.is_exceeded(100));
+    }
+
+    #[test]
+    fn window_deserializes_from_toml() {
+        let pat: EventPattern = toml::from_str(
+            r#"
+source = "webhook"
+pattern = "/auth/failed-login"
+window = { kind = "rate", per_hour = 30.0 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pat.window,
+            Some(EventWindow::Rate {
+                per_hour: 30.0,
+                window_secs: 3600
+            })
+        );
+    }
+
     #[test]
     fn default_strategy_is_exact() {
         assert_eq!(MatchStrategy::default(), MatchStrategy::Exact);
@@ -3,8 +3,13 @@
 //! Routines are lightweight automation rules that match incoming events (from
 //! channels, cron, webhooks, or system signals) using configurable pattern
 //! strategies (exact, glob, regex) and fire actions (SOP triggers, shell
-//! commands, messages, cron jobs).  Each routine supports per-routine cooldown
-//! to prevent rapid re-triggering.
+//! commands, messages, cron jobs, agent turns).  Each routine supports
+//! per-routine cooldown to prevent rapid re-triggering.
+//!
+//! The engine itself is source-agnostic, but today only the gateway drives
+//! it: it dispatches webhook deliveries and calls [`RoutinesEngine::tick`]
+//! for absence windows.  Windowed and absence triggers therefore only cover
+//! webhook sources.
 //!
 //! ## Loading
 //!
@@ -26,12 +31,32 @@
 //! channel = "slack-general"
 //! text = "Deploy triggered!"
 //! ```
+//!
+//! ## Windows
+//!
+//! A pattern with a `window` fires on aggregates of matching events rather
+//! than on each one: `count` (at least `threshold` events in `window_secs`),
+//! `rate` (more than `per_hour` events, measured over `window_secs`) or
+//! `absence` (no matching event for `window_secs`).  The matched events are
+//! handed to the action as a bundle, and window state survives restarts.
+//!
+//! ```toml
+//! [[routines.patterns]]
+//! source = "webhook"
+//! pattern = "/auth/failed"
+//! window = { kind = "count", threshold = 5, window_secs = 120 }
+//! ```
 
+pub mod agent;
 pub mod engine;
 pub mod event_matcher;
+pub mod store;
 
+pub use agent::{AgentAction, render_prompt, run_agent_action};
 pub use engine::{
     Routine, RoutineAction, RoutineDispatchResult, RoutinesEngine, load_routines,
     load_routines_from_file,
 };
-pub use event_matcher::{EventPattern, MatchStrategy, RoutineEvent, matches, matches_any};
+pub use event_matcher::{
+    EventPattern, EventWindow, MatchStrategy, RoutineEvent, matches, matches_any,
+};
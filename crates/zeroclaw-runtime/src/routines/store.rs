@@ -0,0 +1,230 @@
+//! SQLite persistence for routine window state.
+//!
+//! Count, rate and absence windows keep their matching events and timers
+//! here so a daemon restart does not reset them. Mirrors the
+//! `heartbeat/store.rs` pattern: fresh connection per call, schema
+//! auto-created.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::event_matcher::RoutineEvent;
+
+/// Matching events kept per window for the bundle handed to the action.
+/// Older events still count towards the threshold through `seen`.
+const MAX_BUNDLE_EVENTS: usize = 50;
+
+/// Sliding-window state of one windowed pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    /// When the window started watching; the reference point for absence
+    /// detection until the first matching event arrives.
+    pub armed_at: DateTime<Utc>,
+    /// Arrival times of matching events inside the window, oldest first.
+    #[serde(default)]
+    pub seen: Vec<DateTime<Utc>>,
+    /// The most recent matching events, oldest first.
+    #[serde(default)]
+    pub bundle: Vec<(DateTime<Utc>, RoutineEvent)>,
+    /// Arrival time of the last matching event.
+    #[serde(default)]
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Whether the current silence has already fired an absence window.
+    #[serde(default)]
+    pub absence_fired: bool,
+}
+
+impl WindowState {
+    pub fn new(armed_at: DateTime<Utc>) -> Self {
+        Self {
+            armed_at,
+            seen: Vec::new(),
+            bundle: Vec::new(),
+            last_seen: None,
+            absence_fired: false,
+        }
+    }
+
+    /// Record a matching event and drop everything older than `window_secs`.
+    /// Returns the number of events now inside the window.
+    pub fn record(&mut self, now: DateTime<Utc>, event: &RoutineEvent, window_secs: u64) -> usize {
+        self.seen.push(now);
+        self.bundle.push((now, event.clone()));
+        if self.bundle.len() > MAX_BUNDLE_EVENTS {
+            self.bundle.remove(0);
+        }
+        self.last_seen = Some(now);
+        self.absence_fired = false;
+        self.prune(now, window_secs);
+        self.seen.len()
+    }
+
+    /// Drop events that have slid out of the window.
+    pub fn prune(&mut self, now: DateTime<Utc>, window_secs: u64) {
+        let Some(cutoff) = i64::try_from(window_secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .and_then(|window| now.checked_sub_signed(window))
+        else {
+            return;
+        };
+        self.seen.retain(|at| *at > cutoff);
+        self.bundle.retain(|(at, _)| *at > cutoff);
+    }
+
+    /// Hand over the events in the window and start counting afresh.
+    pub fn take_bundle(&mut self) -> Vec<RoutineEvent> {
+        self.seen.clear();
+        self.bundle.drain(..).map(|(_, event)| event).collect()
+    }
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("routines").join("state.db")
+}
+
+/// Load every persisted window, keyed by routine name and pattern key.
+pub fn load_windows(workspace_dir: &Path) -> Result<HashMap<(String, String), WindowState>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare("SELECT routine, pattern, state FROM routine_windows")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut windows = HashMap::new();
+        for row in rows {
+            let (routine, pattern, raw) = row?;
+            match serde_json::from_str::<WindowState>(&raw) {
+                Ok(state) => {
+                    windows.insert((routine, pattern), state);
+                }
+                Err(e) => {
+                    tracing::warn!("Dropping unreadable window state for routine '{routine}': {e}");
+                }
+            }
+        }
+        Ok(windows)
+    })
+}
+
+/// Persist the state of one window.
+pub fn save_window(
+    workspace_dir: &Path,
+    routine: &str,
+    pattern: &str,
+    state: &WindowState,
+) -> Result<()> {
+    let raw = serde_json::to_string(state).context("Failed to serialize routine window")?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO routine_windows (routine, pattern, state, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(routine, pattern) DO UPDATE SET
+                state = excluded.state,
+                updated_at = excluded.updated_at",
+            params![routine, pattern, raw, Utc::now().to_rfc3339()],
+        )
+        .context("Failed to save routine window")?;
+        Ok(())
+    })
+}
+
+/// Forget every window of `routine`.
+pub fn delete_windows(workspace_dir: &Path, routine: &str) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "DELETE FROM routine_windows WHERE routine = ?1",
+            params![routine],
+        )
+        .context("Failed to delete routine windows")?;
+        Ok(())
+    })
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| {
+            format!("Failed to create routines directory: {}", parent.display())
+        })?;
+    }
+
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open routines state DB: {}", path.display()))?;
+
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+
+         CREATE TABLE IF NOT EXISTS routine_windows (
+            routine    TEXT NOT NULL,
+            pattern    TEXT NOT NULL,
+            state      TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (routine, pattern)
+         );",
+    )
+    .context("Failed to initialize routines state schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(topic: &str) -> RoutineEvent {
+        RoutineEvent {
+            source: "webhook".into(),
+            topic: topic.into(),
+            payload: None,
+            timestamp: "2026-03-24T00:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn record_prunes_events_outside_window() {
+        let start = Utc::now();
+        let mut state = WindowState::new(start);
+        assert_eq!(state.record(start, &event("a"), 60), 1);
+        assert_eq!(
+            state.record(start + chrono::Duration::seconds(45), &event("b"), 60),
+            2
+        );
+        assert_eq!(
+            state.record(start + chrono::Duration::seconds(90), &event("c"), 60),
+            2
+        );
+
+        let bundle = state.take_bundle();
+        assert_eq!(
+            bundle.iter().map(|e| e.topic.as_str()).collect::<Vec<_>>(),
+            ["b", "c"]
+        );
+        assert!(state.seen.is_empty());
+    }
+
+    #[test]
+    fn windows_roundtrip_through_sqlite() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut state = WindowState::new(Utc::now());
+        state.record(Utc::now(), &event("/login"), 120);
+
+        save_window(tmp.path(), "brute-force", "0:webhook:/login", &state).unwrap();
+        let loaded = load_windows(tmp.path()).unwrap();
+        let restored = &loaded[&("brute-force".to_string(), "0:webhook:/login".to_string())];
+        assert_eq!(restored.seen.len(), 1);
+        assert_eq!(restored.bundle[0].1.topic, "/login");
+
+        delete_windows(tmp.path(), "brute-force").unwrap();
+        assert!(load_windows(tmp.path()).unwrap().is_empty());
+    }
+}
@@ -28,6 +28,8 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    /// A state-changing call to the gateway HTTP/WebSocket API.
+    ApiRequest,
 }
 
 /// Actor information (who performed the action)
@@ -0,0 +1,868 @@
+//! Session transcript export and import.
+//!
+//! Renders a persisted session as readable Markdown, self-contained HTML, or
+//! a lossless JSON document that [`import_transcript`] can load back into any
+//! [`SessionBackend`]. Markdown and HTML are always scrubbed with the
+//! [`LeakDetector`]; JSON is only scrubbed on request so that it stays
+//! re-importable verbatim.
+
+use crate::cost::CostTracker;
+use crate::cost::types::TokenUsage;
+use crate::security::{LeakDetector, LeakResult};
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::str::FromStr;
+use zeroclaw_api::provider::ChatMessage;
+use zeroclaw_infra::session_backend::{SessionBackend, SessionMetadata, StoredMessage};
+
+/// Current transcript document version. Bumped on incompatible changes.
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+/// Output format for [`render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for exported files, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+
+    /// MIME type for HTTP responses.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" | "htm" => Ok(Self::Html),
+            "json" => Ok(Self::Json),
+            other => bail!("Unknown export format '{other}' (expected md, html or json)"),
+        }
+    }
+}
+
+/// Lossless, re-importable representation of one session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct Transcript {
+    pub version: u32,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
+    pub exported_at: DateTime<Utc>,
+    pub session: TranscriptSession,
+    pub messages: Vec<TranscriptMessage>,
+    /// Whether message content has been passed through the leak detector.
+    #[serde(default)]
+    pub redacted: bool,
+}
+
+/// Session-level metadata carried in a [`Transcript`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct TranscriptSession {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
+    pub created_at: DateTime<Utc>,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
+    pub last_activity: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_point: Option<usize>,
+}
+
+/// One persisted message, with its raw stored content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct TranscriptMessage {
+    pub role: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema-export", schemars(with = "Option<String>"))]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Provider usage attributed to this message (assistant messages only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TranscriptUsage>,
+}
+
+/// Token and cost totals for the provider calls behind one message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct TranscriptUsage {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl Transcript {
+    /// Total cost across all annotated messages.
+    pub fn total_cost_usd(&self) -> f64 {
+        self.messages
+            .iter()
+            .filter_map(|m| m.usage.as_ref())
+            .map(|u| u.cost_usd)
+            .sum()
+    }
+
+    /// Copy of this transcript with secrets scrubbed from every message and
+    /// the session name.
+    pub fn redacted(&self) -> Self {
+        let detector = LeakDetector::new();
+        let mut out = self.clone();
+        for message in &mut out.messages {
+            message.content = scrub(&detector, &message.content);
+        }
+        out.session.name = out.session.name.map(|n| scrub(&detector, &n));
+        out.redacted = true;
+        out
+    }
+}
+
+fn scrub(detector: &LeakDetector, content: &str) -> String {
+    match detector.scan(content) {
+        LeakResult::Clean => content.to_string(),
+        LeakResult::Detected { redacted, .. } => redacted,
+    }
+}
+
+/// Build a transcript for `session_key`, or `None` if the session does not exist.
+///
+/// `usage` is the cost tracker's record for the session's lifetime. Cost
+/// records are not keyed by session, so each record is attributed to the
+/// assistant message whose window (previous message, this message] contains
+/// its timestamp. This is best-effort: concurrent sessions sharing a tracker
+/// can be misattributed, and backends without per-message timestamps get no
+/// annotations.
+pub fn build_transcript(
+    backend: &dyn SessionBackend,
+    session_key: &str,
+    usage: &[TokenUsage],
+) -> Option<Transcript> {
+    let meta = backend.get_session_metadata(session_key)?;
+    let stored = backend.load_stored(session_key);
+
+    let mut messages: Vec<TranscriptMessage> = stored
+        .into_iter()
+        .map(|s| TranscriptMessage {
+            role: s.message.role,
+            content: s.message.content,
+            timestamp: s.created_at,
+            usage: None,
+        })
+        .collect();
+    attribute_usage(&mut messages, usage);
+
+    Some(Transcript {
+        version: TRANSCRIPT_VERSION,
+        exported_at: Utc::now(),
+        session: TranscriptSession {
+            key: meta.key,
+            name: meta.name,
+            created_at: meta.created_at,
+            last_activity: meta.last_activity,
+            parent_key: meta.parent_key,
+            fork_point: meta.fork_point,
+        },
+        messages,
+        redacted: false,
+    })
+}
+
+/// [`build_transcript`] with usage read from `tracker` for the session's
+/// lifetime. Without a tracker the transcript carries no cost annotations.
+pub fn export_session(
+    backend: &dyn SessionBackend,
+    session_key: &str,
+    tracker: Option<&CostTracker>,
+) -> Option<Transcript> {
+    let usage = match (tracker, backend.get_session_metadata(session_key)) {
+        (Some(tracker), Some(meta)) => tracker
+            .usage_between(meta.created_at, meta.last_activity)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to read cost records for transcript export: {e}");
+                Vec::new()
+            }),
+        _ => Vec::new(),
+    };
+    build_transcript(backend, session_key, &usage)
+}
+
+fn attribute_usage(messages: &mut [TranscriptMessage], usage: &[TokenUsage]) {
+    let mut window_start: Option<DateTime<Utc>> = None;
+    for message in messages.iter_mut() {
+        let Some(ts) = message.timestamp else {
+            continue;
+        };
+        if message.role == "assistant" {
+            let mut models: Vec<&str> = Vec::new();
+            let mut total = TranscriptUsage {
+                model: String::new(),
+                input_tokens: 0,
+                output_tokens: 0,
+                cost_usd: 0.0,
+            };
+            for u in usage
+                .iter()
+                .filter(|u| u.timestamp <= ts && window_start.is_none_or(|s| u.timestamp > s))
+            {
+                if !models.contains(&u.model.as_str()) {
+                    models.push(&u.model);
+                }
+                total.input_tokens += u.input_tokens;
+                total.output_tokens += u.output_tokens;
+                total.cost_usd += u.cost_usd;
+            }
+            if !models.is_empty() {
+                total.model = models.join(", ");
+                message.usage = Some(total);
+            }
+        }
+        window_start = Some(ts);
+    }
+}
+
+/// Import a transcript as a new session and return the key it was stored under.
+///
+/// Fails with `AlreadyExists` if the target key is taken, so importing never
+/// overwrites live history.
+pub fn import_transcript(
+    backend: &dyn SessionBackend,
+    transcript: &Transcript,
+    key_override: Option<&str>,
+) -> std::io::Result<String> {
+    if transcript.version > TRANSCRIPT_VERSION {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Transcript version {} is newer than supported version {TRANSCRIPT_VERSION}",
+                transcript.version
+            ),
+        ));
+    }
+    let key = key_override
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .unwrap_or(&transcript.session.key)
+        .to_string();
+    if key.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Transcript has no session key",
+        ));
+    }
+
+    let meta = SessionMetadata {
+        key: key.clone(),
+        name: transcript.session.name.clone(),
+        created_at: transcript.session.created_at,
+        last_activity: transcript.session.last_activity,
+        message_count: transcript.messages.len(),
+        parent_key: transcript.session.parent_key.clone(),
+        fork_point: transcript.session.fork_point,
+    };
+    let messages: Vec<StoredMessage> = transcript
+        .messages
+        .iter()
+        .map(|m| StoredMessage {
+            message: ChatMessage {
+                role: m.role.clone(),
+                content: m.content.clone(),
+            },
+            created_at: m.timestamp,
+        })
+        .collect();
+
+    if backend.import_session(&meta, &messages)? {
+        Ok(key)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("Session '{key}' already exists"),
+        ))
+    }
+}
+
+/// Render a transcript. Markdown and HTML are always scrubbed; JSON is
+/// rendered as given (call [`Transcript::redacted`] first to scrub it).
+pub fn render(transcript: &Transcript, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(transcript).unwrap_or_else(|_| "{}".into())
+        }
+        ExportFormat::Markdown => render_markdown(&scrubbed(transcript)),
+        ExportFormat::Html => render_html(&scrubbed(transcript)),
+    }
+}
+
+fn scrubbed(transcript: &Transcript) -> Transcript {
+    if transcript.redacted {
+        transcript.clone()
+    } else {
+        transcript.redacted()
+    }
+}
+
+/// Suggested file name for an exported transcript.
+pub fn export_file_name(session_key: &str, format: ExportFormat) -> String {
+    let safe: String = session_key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!(
+        "{safe}-{}.{}",
+        Utc::now().format("%Y%m%dT%H%M%SZ"),
+        format.extension()
+    )
+}
+
+// ── Message body parsing ────────────────────────────────────────
+
+struct ToolCallView {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+enum Body {
+    Text(String),
+    ToolCalls {
+        text: String,
+        reasoning: Option<String>,
+        calls: Vec<ToolCallView>,
+    },
+    ToolResult {
+        call_id: Option<String>,
+        content: String,
+    },
+}
+
+/// Interpret the structured JSON payloads the agent loop persists for
+/// native tool calling; anything else is plain text.
+fn parse_body(message: &TranscriptMessage) -> Body {
+    let parsed = || serde_json::from_str::<serde_json::Value>(&message.content).ok();
+    match message.role.as_str() {
+        "assistant" if message.content.trim_start().starts_with('{') => {
+            let Some(value) = parsed() else {
+                return Body::Text(message.content.clone());
+            };
+            let Some(calls) = value.get("tool_calls").and_then(|c| c.as_array()) else {
+                return Body::Text(message.content.clone());
+            };
+            let calls = calls
+                .iter()
+                .map(|call| ToolCallView {
+                    id: json_str(call, "id"),
+                    name: json_str(call, "name"),
+                    arguments: pretty_arguments(call.get("arguments")),
+                })
+                .collect();
+            Body::ToolCalls {
+                text: json_str(&value, "content"),
+                reasoning: value
+                    .get("reasoning_content")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string),
+                calls,
+            }
+        }
+        "tool" => match parsed() {
+            Some(value) if value.get("content").is_some() => Body::ToolResult {
+                call_id: value
+                    .get("tool_call_id")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                content: json_str(&value, "content"),
+            },
+            _ => Body::ToolResult {
+                call_id: None,
+                content: message.content.clone(),
+            },
+        },
+        _ => Body::Text(message.content.clone()),
+    }
+}
+
+fn json_str(value: &serde_json::Value, field: &str) -> String {
+    match value.get(field) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn pretty_arguments(arguments: Option<&serde_json::Value>) -> String {
+    match arguments {
+        Some(serde_json::Value::String(raw)) => serde_json::from_str::<serde_json::Value>(raw)
+            .ok()
+            .and_then(|v| serde_json::to_string_pretty(&v).ok())
+            .unwrap_or_else(|| raw.clone()),
+        Some(other) => serde_json::to_string_pretty(other).unwrap_or_default(),
+        None => String::new(),
+    }
+}
+
+fn role_label(role: &str) -> &str {
+    match role {
+        "user" => "User",
+        "assistant" => "Assistant",
+        "tool" => "Tool",
+        "system" => "System",
+        other => other,
+    }
+}
+
+fn heading_details(message: &TranscriptMessage) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(ts) = message.timestamp {
+        parts.push(ts.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+    }
+    if let Some(usage) = &message.usage {
+        parts.push(usage.model.clone());
+        parts.push(format!(
+            "{} in / {} out tokens",
+            usage.input_tokens, usage.output_tokens
+        ));
+        parts.push(format!("${:.4}", usage.cost_usd));
+    }
+    parts
+}
+
+// ── Markdown ────────────────────────────────────────────────────
+
+/// Code fence longer than any backtick run in `content`.
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in content.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn md_code_block(out: &mut String, lang: &str, content: &str) {
+    let fence = fence_for(content);
+    let _ = writeln!(out, "{fence}{lang}\n{}\n{fence}", content.trim_end());
+}
+
+fn md_details(out: &mut String, summary: &str, lang: &str, content: &str) {
+    let _ = writeln!(
+        out,
+        "<details>\n<summary>{}</summary>\n",
+        escape_html(summary)
+    );
+    md_code_block(out, lang, content);
+    out.push_str("\n</details>\n");
+}
+
+fn render_markdown(t: &Transcript) -> String {
+    let mut out = String::new();
+    let title = t.session.name.as_deref().unwrap_or(&t.session.key);
+    let _ = writeln!(out, "# Session transcript: {title}\n");
+    let _ = writeln!(out, "- **Session:** `{}`", t.session.key);
+    let _ = writeln!(out, "- **Created:** {}", t.session.created_at.to_rfc3339());
+    let _ = writeln!(
+        out,
+        "- **Last activity:** {}",
+        t.session.last_activity.to_rfc3339()
+    );
+    if let Some(parent) = &t.session.parent_key {
+        let at = t
+            .session
+            .fork_point
+            .map(|p| format!(" at message {p}"))
+            .unwrap_or_default();
+        let _ = writeln!(out, "- **Forked from:** `{parent}`{at}");
+    }
+    let _ = writeln!(out, "- **Messages:** {}", t.messages.len());
+    let cost = t.total_cost_usd();
+    if cost > 0.0 {
+        let _ = writeln!(out, "- **Cost:** ${cost:.4}");
+    }
+    let _ = writeln!(out, "- **Exported:** {}", t.exported_at.to_rfc3339());
+    out.push_str("\n---\n");
+
+    for message in &t.messages {
+        let mut heading = vec![role_label(&message.role).to_string()];
+        heading.extend(heading_details(message));
+        let _ = writeln!(out, "\n### {}\n", heading.join(" · "));
+
+        match parse_body(message) {
+            Body::Text(text) if message.role == "system" => {
+                md_details(&mut out, "System prompt", "", &text);
+            }
+            Body::Text(text) => {
+                let _ = writeln!(out, "{}", text.trim_end());
+            }
+            Body::ToolCalls {
+                text,
+                reasoning,
+                calls,
+            } => {
+                if let Some(reasoning) = reasoning.filter(|r| !r.trim().is_empty()) {
+                    md_details(&mut out, "Reasoning", "", &reasoning);
+                    out.push('\n');
+                }
+                if !text.trim().is_empty() {
+                    let _ = writeln!(out, "{}\n", text.trim_end());
+                }
+                for call in calls {
+                    md_details(
+                        &mut out,
+                        &format!("Tool call: {} ({})", call.name, call.id),
+                        "json",
+                        &call.arguments,
+                    );
+                }
+            }
+            Body::ToolResult { call_id, content } => {
+                let summary = match call_id {
+                    Some(id) => format!("Tool result ({id})"),
+                    None => "Tool result".to_string(),
+                };
+                md_details(&mut out, &summary, "", &content);
+            }
+        }
+    }
+    out
+}
+
+// ── HTML ────────────────────────────────────────────────────────
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,-apple-system,sans-serif;max-width:56rem;margin:2rem auto;padding:0 1rem;color:#1f2328;background:#fff}\
+header{border-bottom:1px solid #d0d7de;margin-bottom:1.5rem}\
+dl{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem}dt{font-weight:600}dd{margin:0}\
+.msg{border:1px solid #d0d7de;border-radius:6px;margin:1rem 0;padding:.75rem 1rem}\
+.msg h2{font-size:.9rem;margin:0 0 .5rem;color:#57606a}\
+.user{background:#f6f8fa}.tool,.system{background:#fbfbfb}\
+.text{white-space:pre-wrap;word-wrap:break-word}\
+pre{background:#f6f8fa;padding:.5rem;overflow-x:auto;white-space:pre-wrap}\
+summary{cursor:pointer;font-family:ui-monospace,monospace}";
+
+fn html_details(out: &mut String, summary: &str, content: &str) {
+    let _ = write!(
+        out,
+        "<details><summary>{}</summary><pre>{}</pre></details>",
+        escape_html(summary),
+        escape_html(content.trim_end())
+    );
+}
+
+fn render_html(t: &Transcript) -> String {
+    let title = escape_html(t.session.name.as_deref().unwrap_or(&t.session.key));
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width,initial-scale=1\">\
+         <title>Session transcript: {title}</title><style>{HTML_STYLE}</style></head><body>\
+         <header><h1>Session transcript: {title}</h1><dl>"
+    );
+    let mut meta = vec![
+        ("Session", escape_html(&t.session.key)),
+        ("Created", t.session.created_at.to_rfc3339()),
+        ("Last activity", t.session.last_activity.to_rfc3339()),
+    ];
+    if let Some(parent) = &t.session.parent_key {
+        let at = t
+            .session
+            .fork_point
+            .map(|p| format!(" at message {p}"))
+            .unwrap_or_default();
+        meta.push(("Forked from", format!("{}{at}", escape_html(parent))));
+    }
+    meta.push(("Messages", t.messages.len().to_string()));
+    let cost = t.total_cost_usd();
+    if cost > 0.0 {
+        meta.push(("Cost", format!("${cost:.4}")));
+    }
+    meta.push(("Exported", t.exported_at.to_rfc3339()));
+    for (label, value) in meta {
+        let _ = write!(out, "<dt>{label}</dt><dd>{value}</dd>");
+    }
+    out.push_str("</dl></header><main>\n");
+
+    for message in &t.messages {
+        let class = match message.role.as_str() {
+            "user" | "assistant" | "tool" | "system" => message.role.as_str(),
+            _ => "other",
+        };
+        let mut heading = vec![escape_html(role_label(&message.role))];
+        heading.extend(heading_details(message).iter().map(|d| escape_html(d)));
+        let _ = write!(
+            out,
+            "<section class=\"msg {class}\"><h2>{}</h2>",
+            heading.join(" · ")
+        );
+
+        match parse_body(message) {
+            Body::Text(text) if message.role == "system" => {
+                html_details(&mut out, "System prompt", &text);
+            }
+            Body::Text(text) => {
+                let _ = write!(out, "<div class=\"text\">{}</div>", escape_html(&text));
+            }
+            Body::ToolCalls {
+                text,
+                reasoning,
+                calls,
+            } => {
+                if let Some(reasoning) = reasoning.filter(|r| !r.trim().is_empty()) {
+                    html_details(&mut out, "Reasoning", &reasoning);
+                }
+                if !text.trim().is_empty() {
+                    let _ = write!(out, "<div class=\"text\">{}</div>", escape_html(&text));
+                }
+                for call in calls {
+                    html_details(
+                        &mut out,
+                        &format!("Tool call: {} ({})", call.name, call.id),
+                        &call.arguments,
+                    );
+                }
+            }
+            Body::ToolResult { call_id, content } => {
+                let summary = match call_id {
+                    Some(id) => format!("Tool result ({id})"),
+                    None => "Tool result".to_string(),
+                };
+                html_details(&mut out, &summary, &content);
+            }
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</main></body></html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+    use zeroclaw_infra::session_sqlite::SqliteSessionBackend;
+
+    fn at(base: DateTime<Utc>, secs: i64) -> DateTime<Utc> {
+        base + Duration::seconds(secs)
+    }
+
+    fn sample(base: DateTime<Utc>) -> Transcript {
+        let message = |role: &str, content: &str, secs: i64| TranscriptMessage {
+            role: role.into(),
+            content: content.into(),
+            timestamp: Some(at(base, secs)),
+            usage: None,
+        };
+        Transcript {
+            version: TRANSCRIPT_VERSION,
+            exported_at: at(base, 100),
+            session: TranscriptSession {
+                key: "gw_abc".into(),
+                name: Some("Deploy <notes>".into()),
+                created_at: base,
+                last_activity: at(base, 40),
+                parent_key: None,
+                fork_point: None,
+            },
+            messages: vec![
+                message("system", "You are helpful.", 0),
+                message("user", "list files & show ```code```", 10),
+                message(
+                    "assistant",
+                    r#"{"content":"Checking","tool_calls":[{"id":"call_1","name":"shell","arguments":"{\"command\":\"ls\"}"}]}"#,
+                    20,
+                ),
+                message(
+                    "tool",
+                    r#"{"tool_call_id":"call_1","content":"a.txt\nb.txt"}"#,
+                    30,
+                ),
+                message("assistant", "Two files: a.txt and b.txt", 40),
+            ],
+            redacted: false,
+        }
+    }
+
+    #[test]
+    fn export_format_parses_aliases() {
+        assert_eq!(
+            "md".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!(
+            "Markdown".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!("html".parse::<ExportFormat>().unwrap(), ExportFormat::Html);
+        assert_eq!("JSON".parse::<ExportFormat>().unwrap(), ExportFormat::Json);
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn markdown_collapses_tool_blocks_and_sizes_fences() {
+        let md = render(&sample(Utc::now()), ExportFormat::Markdown);
+        assert!(md.starts_with("# Session transcript: Deploy <notes>"));
+        assert!(md.contains("<summary>Tool call: shell (call_1)</summary>"));
+        assert!(md.contains("\"command\": \"ls\""));
+        assert!(md.contains("<summary>Tool result (call_1)</summary>"));
+        assert!(md.contains("<summary>System prompt</summary>"));
+        assert!(md.contains("Two files: a.txt and b.txt"));
+        assert_eq!(fence_for("a ```` b"), "`````");
+        assert_eq!(fence_for("plain"), "```");
+    }
+
+    #[test]
+    fn html_is_escaped_and_self_contained() {
+        let html = render(&sample(Utc::now()), ExportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("Deploy &lt;notes&gt;"));
+        assert!(html.contains("list files &amp; show"));
+        assert!(html.contains("<details><summary>Tool call: shell (call_1)</summary>"));
+        assert!(!html.contains("<script"));
+    }
+
+    #[test]
+    fn markdown_and_html_scrub_secrets_but_json_is_lossless() {
+        let mut transcript = sample(Utc::now());
+        let secret = "my key is sk-proj-abcdefghijklmnopqrstuvwxyz0123456789ABCD";
+        transcript.messages[1].content = secret.into();
+
+        for format in [ExportFormat::Markdown, ExportFormat::Html] {
+            let out = render(&transcript, format);
+            assert!(
+                !out.contains("abcdefghijklmnopqrstuvwxyz0123456789"),
+                "{format:?}"
+            );
+        }
+        let json = render(&transcript, ExportFormat::Json);
+        assert!(json.contains(secret));
+        let redacted = render(&transcript.redacted(), ExportFormat::Json);
+        assert!(!redacted.contains("abcdefghijklmnopqrstuvwxyz0123456789"));
+        assert!(redacted.contains("\"redacted\": true"));
+    }
+
+    #[test]
+    fn usage_is_attributed_to_the_answering_assistant_message() {
+        let base = Utc::now() - Duration::hours(1);
+        let mut transcript = sample(base);
+        let usage_at = |model: &str, secs: i64, cost: f64| {
+            let mut u = TokenUsage::new(model, 100, 50, 0.0, 0.0);
+            u.cost_usd = cost;
+            u.timestamp = at(base, secs);
+            u
+        };
+        let usage = vec![
+            usage_at("openai/gpt-4o", 15, 0.01),
+            usage_at("openai/gpt-4o", 35, 0.02),
+            usage_at("openai/gpt-4o", 39, 0.03),
+            usage_at("openai/gpt-4o", 500, 9.0),
+        ];
+        attribute_usage(&mut transcript.messages, &usage);
+
+        assert!(transcript.messages[1].usage.is_none());
+        let first = transcript.messages[2].usage.as_ref().unwrap();
+        assert_eq!(first.input_tokens, 100);
+        assert!((first.cost_usd - 0.01).abs() < 1e-9);
+        let last = transcript.messages[4].usage.as_ref().unwrap();
+        assert_eq!(last.model, "openai/gpt-4o");
+        assert_eq!(last.output_tokens, 100);
+        assert!((transcript.total_cost_usd() - 0.06).abs() < 1e-9);
+    }
+
+    #[test]
+    fn json_round_trips_through_import() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        let source = SqliteSessionBackend::new(source_dir.path()).unwrap();
+        let target = SqliteSessionBackend::new(target_dir.path()).unwrap();
+
+        source.append("s1", &ChatMessage::user("hello")).unwrap();
+        source
+            .append("s1", &ChatMessage::assistant("hi there"))
+            .unwrap();
+        source.set_session_name("s1", "Greeting").unwrap();
+
+        let transcript = build_transcript(&source, "s1", &[]).unwrap();
+        let json = render(&transcript, ExportFormat::Json);
+        let parsed: Transcript = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, transcript);
+
+        let key = import_transcript(&target, &parsed, None).unwrap();
+        assert_eq!(key, "s1");
+        let reloaded = build_transcript(&target, "s1", &[]).unwrap();
+        assert_eq!(reloaded.messages, transcript.messages);
+        assert_eq!(reloaded.session.name.as_deref(), Some("Greeting"));
+
+        let err = import_transcript(&target, &parsed, None).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            import_transcript(&target, &parsed, Some("s1-copy")).unwrap(),
+            "s1-copy"
+        );
+    }
+
+    #[test]
+    fn build_transcript_missing_session_is_none() {
+        let dir = TempDir::new().unwrap();
+        let backend = SqliteSessionBackend::new(dir.path()).unwrap();
+        assert!(build_transcript(&backend, "nope", &[]).is_none());
+    }
+
+    #[test]
+    fn import_rejects_newer_versions() {
+        let dir = TempDir::new().unwrap();
+        let backend = SqliteSessionBackend::new(dir.path()).unwrap();
+        let mut transcript = sample(Utc::now());
+        transcript.version = TRANSCRIPT_VERSION + 1;
+        let err = import_transcript(&backend, &transcript, None).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn export_file_name_is_filesystem_safe() {
+        let name = export_file_name("telegram:123/abc", ExportFormat::Html);
+        assert!(name.starts_with("telegram_123_abc-"));
+        assert!(name.ends_with(".html"));
+    }
+}
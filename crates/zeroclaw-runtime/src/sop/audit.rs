@@ -56,12 +56,28 @@ impl SopAuditLogger {
 
     /// Log an operator approval event for a specific step.
     pub async fn log_approval(&self, run: &SopRun, step_number: u32) -> Result<()> {
+        self.log_approval_by(run, step_number, None).await
+    }
+
+    /// Log an approval attributed to a named approver. The run record gains an
+    /// `approved_by` field, which `SopRun` readers ignore.
+    pub async fn log_approval_by(
+        &self,
+        run: &SopRun,
+        step_number: u32,
+        approver: Option<&str>,
+    ) -> Result<()> {
         let key = format!("sop_approval_{}_{step_number}", run.run_id);
-        let content = serde_json::to_string_pretty(run)?;
+        let mut record = serde_json::to_value(run)?;
+        if let (Some(approver), Some(fields)) = (approver, record.as_object_mut()) {
+            fields.insert("approved_by".into(), approver.into());
+        }
+        let content = serde_json::to_string_pretty(&record)?;
         self.memory.store(&key, &content, category(), None).await?;
         info!(
-            "SOP audit: run {} step {step_number} approved by operator",
-            run.run_id
+            "SOP audit: run {} step {step_number} approved by {}",
+            run.run_id,
+            approver.unwrap_or("operator")
         );
         Ok(())
     }
@@ -121,6 +137,7 @@ fn category() -> MemoryCategory {
 mod tests {
     use super::*;
     use crate::sop::types::{SopEvent, SopRunStatus, SopStepStatus, SopTriggerSource};
+    use std::collections::HashMap;
 
     fn test_run() -> SopRun {
         SopRun {
@@ -140,6 +157,8 @@ mod tests {
             step_results: Vec::new(),
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         }
     }
 
@@ -214,6 +233,35 @@ mod tests {
         assert!(approval_keys[0].key.contains("run-test-001"));
     }
 
+    #[tokio::test]
+    async fn log_approval_by_records_approver() {
+        let mem_cfg = zeroclaw_config::schema::MemoryConfig {
+            backend: "sqlite".into(),
+            ..zeroclaw_config::schema::MemoryConfig::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn Memory> =
+            Arc::from(zeroclaw_memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+
+        let logger = SopAuditLogger::new(memory.clone());
+        let run = test_run();
+        logger
+            .log_approval_by(&run, 2, Some("alice"))
+            .await
+            .unwrap();
+
+        let entry = memory
+            .get("sop_approval_run-test-001_2")
+            .await
+            .unwrap()
+            .unwrap();
+        let record: serde_json::Value = serde_json::from_str(&entry.content).unwrap();
+        assert_eq!(record["approved_by"], "alice");
+        // Metrics rebuild still reads the entry as a run.
+        let parsed: SopRun = serde_json::from_str(&entry.content).unwrap();
+        assert_eq!(parsed.run_id, "run-test-001");
+    }
+
     #[tokio::test]
     async fn log_timeout_auto_approve_persists_entry() {
         let mem_cfg = zeroclaw_config::schema::MemoryConfig {
@@ -1,150 +1,288 @@
+//! Trigger and transition conditions.
+//!
+//! A small expression language evaluated against a JSON payload:
+//!
+//! - JSON paths: `$.data.temp`, `$.readings[0]`, `$.readings.0`, `$["odd-key"]`
+//! - Literals: numbers, `"strings"` or `'strings'`, `true`, `false`, `null`,
+//!   lists `["a", "b"]`
+//! - Comparisons: `==`, `!=`, `>`, `>=`, `<`, `<=`
+//! - Boolean combinators: `&&`, `||`, `!`, parentheses
+//! - Arithmetic: `+`, `-`, `*`, `/`, `%` (numbers only)
+//! - Membership: `x in [..]` (list or array), `"sub" in $.text` (substring)
+//! - Functions: `exists(path)`, `contains(a, b)`, `starts_with(s, p)`,
+//!   `ends_with(s, p)`, `matches(s, "regex")`, `lower(s)`, `upper(s)`, `len(x)`
+//!
+//! A condition that starts with a comparison operator (`> 0`) compares the
+//! whole payload. Conditions are parsed up front so malformed ones are
+//! reported when the SOP loads; evaluation is fail-closed — a missing path,
+//! a type mismatch or a non-JSON payload makes the whole condition false.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use anyhow::{Result, bail};
+use parking_lot::Mutex;
+use regex::Regex;
 use serde_json::Value;
 
+/// Longest condition accepted, in bytes.
+const MAX_CONDITION_LEN: usize = 4096;
+/// Deepest nesting of sub-expressions accepted by the parser.
+const MAX_DEPTH: usize = 64;
+/// Compiled size limit for `matches()` patterns.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+/// Most parsed conditions kept in the cache before it is reset.
+const MAX_CACHED: usize = 1024;
+
+/// Parsed conditions keyed by source text, filled when SOPs load.
+static PARSED: LazyLock<Mutex<HashMap<String, Arc<Condition>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Evaluate a trigger condition against an event payload.
 ///
-/// Condition syntax:
-///   - JSON path comparison: `$.key.subkey > 85`
-///   - Direct numeric comparison: `> 0` (used by peripheral triggers)
-///
-/// Supported operators: `>=`, `<=`, `!=`, `>`, `<`, `==`
-///
-/// Returns `false` (fail-closed) when:
-///   - payload is missing or empty
-///   - condition cannot be parsed
-///   - JSON path does not resolve to a value
-///   - extracted value and comparand are not comparable
+/// An empty condition always matches. Returns `false` (fail-closed) when
+/// the payload is missing or empty, the condition does not parse, or
+/// evaluation hits a missing path or type mismatch.
 pub fn evaluate_condition(condition: &str, payload: Option<&str>) -> bool {
-    let condition = condition.trim();
-    if condition.is_empty() {
+    if condition.trim().is_empty() {
         return true; // empty condition = unconditional match
     }
-
-    let payload = match payload {
-        Some(p) if !p.is_empty() => p,
-        _ => return false, // no payload to evaluate against
-    };
-
-    if let Some(rest) = condition.strip_prefix('$') {
-        // JSON path condition: $.key.sub >= 85
-        evaluate_json_path_condition(rest, payload)
-    } else {
-        // Direct comparison: > 0
-        evaluate_direct_condition(condition, payload)
+    match Condition::cached(condition) {
+        Ok(parsed) => parsed.evaluate(payload),
+        Err(e) => {
+            tracing::debug!("SOP condition '{condition}' rejected: {e}");
+            false
+        }
     }
 }
 
-/// Evaluate `$.path.to.field op value` against a JSON payload.
-fn evaluate_json_path_condition(path_and_op: &str, payload: &str) -> bool {
-    let json: Value = match serde_json::from_str(payload) {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-
-    // Split into (dot_path, operator, comparand)
-    let (dot_path, op, comparand) = match parse_path_op_value(path_and_op) {
-        Some(t) => t,
-        None => return false,
-    };
-
-    let extracted = resolve_json_path(&json, &dot_path);
-    let extracted = match extracted {
-        Some(v) => v,
-        None => return false,
-    };
-
-    compare_values(extracted, op, &comparand)
-}
-
-/// Evaluate `op value` directly against the payload (treated as a number).
-fn evaluate_direct_condition(condition: &str, payload: &str) -> bool {
-    let (op, comparand) = match parse_op_value(condition) {
-        Some(t) => t,
-        None => return false,
-    };
-
-    // Try to parse payload as a number
-    let payload_num: f64 = match payload.trim().parse() {
-        Ok(n) => n,
-        Err(_) => return false,
-    };
-
-    let comparand_num: f64 = match comparand.parse() {
-        Ok(n) => n,
-        Err(_) => return false,
-    };
-
-    apply_op_f64(payload_num, op, comparand_num)
+/// A parsed condition, ready to evaluate against many payloads.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    expr: Expr,
 }
 
-// ── Parsing helpers ─────────────────────────────────────────────
+impl Condition {
+    /// Parse a condition, reporting the column of the first syntax error.
+    pub fn parse(source: &str) -> Result<Self> {
+        if source.len() > MAX_CONDITION_LEN {
+            bail!("condition is longer than {MAX_CONDITION_LEN} bytes");
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = if parser.peek_comparison().is_some() {
+            // `> 0`: compare the whole payload
+            parser.comparison_tail(Expr::Path(Vec::new()))?
+        } else {
+            parser.expr()?
+        };
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            bail!("unexpected {} at column {}", token.kind, token.column);
+        }
+        Ok(Self { expr })
+    }
 
-/// Operators in order of longest-first to avoid prefix ambiguity.
-const OPERATORS: &[&str] = &[">=", "<=", "!=", "==", ">", "<"];
-
-/// Parse `".path.to.field op value"` → `(["path","to","field"], Op, "value")`.
-fn parse_path_op_value(input: &str) -> Option<(Vec<&str>, Op, String)> {
-    // Input starts after `$`, e.g. `.value > 85` or `.data.temp >= 100`
-    // Find operator position
-    for &op_str in OPERATORS {
-        if let Some(pos) = input.find(op_str) {
-            let path_part = input[..pos].trim();
-            let value_part = input[pos + op_str.len()..].trim();
-
-            if value_part.is_empty() {
-                return None;
-            }
+    /// Like [`Condition::parse`], but reuses the AST of a condition that
+    /// was already parsed. Parse errors are not cached.
+    pub fn cached(source: &str) -> Result<Arc<Self>> {
+        if let Some(parsed) = PARSED.lock().get(source) {
+            return Ok(Arc::clone(parsed));
+        }
+        let parsed = Arc::new(Self::parse(source)?);
+        let mut cache = PARSED.lock();
+        if cache.len() >= MAX_CACHED {
+            cache.clear();
+        }
+        cache.insert(source.to_string(), Arc::clone(&parsed));
+        Ok(parsed)
+    }
 
-            let op = Op::from_str(op_str)?;
-            let segments: Vec<&str> = path_part.split('.').filter(|s| !s.is_empty()).collect();
+    /// Evaluate against a payload. Payloads that are not JSON are treated
+    /// as a plain string (or number, when they parse as one).
+    pub fn evaluate(&self, payload: Option<&str>) -> bool {
+        let payload = match payload {
+            Some(p) if !p.trim().is_empty() => p,
+            _ => return false, // no payload to evaluate against
+        };
+        let root = serde_json::from_str(payload)
+            .unwrap_or_else(|_| Value::String(payload.trim().to_string()));
+        matches!(eval(&self.expr, &root), Ok(Value::Bool(true)))
+    }
+}
 
-            if segments.is_empty() {
-                return None;
-            }
+// ── Tokenizer ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Dollar,
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Op(&'static str),
+}
 
-            return Some((segments, op, value_part.to_string()));
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "number {n}"),
+            Self::Str(s) => write!(f, "string {s:?}"),
+            Self::Ident(name) => write!(f, "'{name}'"),
+            Self::Dollar => write!(f, "'$'"),
+            Self::Dot => write!(f, "'.'"),
+            Self::Comma => write!(f, "','"),
+            Self::LParen => write!(f, "'('"),
+            Self::RParen => write!(f, "')'"),
+            Self::LBracket => write!(f, "'['"),
+            Self::RBracket => write!(f, "']'"),
+            Self::Op(op) => write!(f, "'{op}'"),
         }
     }
-    None
 }
 
-/// Parse `"op value"` → `(Op, "value")`.
-fn parse_op_value(input: &str) -> Option<(Op, String)> {
-    let input = input.trim();
-    for &op_str in OPERATORS {
-        if let Some(rest) = input.strip_prefix(op_str) {
-            let value = rest.trim();
-            if value.is_empty() {
-                return None;
-            }
-            let op = Op::from_str(op_str)?;
-            return Some((op, value.to_string()));
-        }
-    }
-    None
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based column of the token's first character.
+    column: usize,
 }
 
-/// Walk a JSON value by dot-separated path segments.
-fn resolve_json_path<'a>(value: &'a Value, segments: &[&str]) -> Option<&'a Value> {
-    let mut current = value;
-    for &seg in segments {
-        // Try object key
-        if let Some(next) = current.get(seg) {
-            current = next;
+/// Operators in order of longest-first to avoid prefix ambiguity.
+const OPERATORS: &[&str] = &[
+    "&&", "||", ">=", "<=", "!=", "==", ">", "<", "!", "+", "-", "*", "/", "%",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
             continue;
         }
-        // Try array index
-        if let Ok(idx) = seg.parse::<usize>()
-            && let Some(next) = current.get(idx)
-        {
-            current = next;
-            continue;
+        let kind = match c {
+            '$' => TokenKind::Dollar,
+            '.' if !chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                || tokens.last().is_some_and(|t: &Token| {
+                    matches!(
+                        t.kind,
+                        TokenKind::Ident(_) | TokenKind::Dollar | TokenKind::RBracket
+                    )
+                }) =>
+            {
+                TokenKind::Dot
+            }
+            ',' => TokenKind::Comma,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '[' => TokenKind::LBracket,
+            ']' => TokenKind::RBracket,
+            '"' | '\'' => {
+                let (text, end) = read_string(&chars, i)?;
+                i = end;
+                tokens.push(Token {
+                    kind: TokenKind::Str(text),
+                    column,
+                });
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                // Inside a path (`$.readings.1.value`) only the index is a number
+                let in_path = tokens
+                    .last()
+                    .is_some_and(|t: &Token| t.kind == TokenKind::Dot);
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || (!in_path
+                            && (chars[i] == '.'
+                                || chars[i] == 'e'
+                                || chars[i] == 'E'
+                                || ((chars[i] == '+' || chars[i] == '-')
+                                    && matches!(chars[i - 1], 'e' | 'E')))))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let n = text
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid number '{text}' at column {column}"))?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(n),
+                    column,
+                });
+                continue;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Ident(chars[start..i].iter().collect()),
+                    column,
+                });
+                continue;
+            }
+            _ => {
+                let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+                let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) else {
+                    bail!("unexpected character '{c}' at column {column}");
+                };
+                i += op.len();
+                tokens.push(Token {
+                    kind: TokenKind::Op(op),
+                    column,
+                });
+                continue;
+            }
+        };
+        tokens.push(Token { kind, column });
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// Read a quoted string starting at `start`, returning it and the index
+/// just past the closing quote.
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let quote = chars[start];
+    let mut out = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            c if c == quote => return Ok((out, i + 1)),
+            '\\' => {
+                let escaped = chars.get(i + 1).copied().unwrap_or('\\');
+                out.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                });
+                i += 2;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
         }
-        return None;
     }
-    Some(current)
+    bail!("unterminated string starting at column {}", start + 1)
 }
 
-// ── Comparison ──────────────────────────────────────────────────
+// ── Parser ──────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Op {
@@ -170,37 +308,496 @@ impl Op {
     }
 }
 
-/// Compare a JSON value against a string comparand using the given operator.
-fn compare_values(extracted: &Value, op: Op, comparand: &str) -> bool {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Arith {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Exists,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Lower,
+    Upper,
+    Len,
+}
+
+impl Func {
+    fn lookup(name: &str) -> Option<(Self, usize)> {
+        match name {
+            "exists" => Some((Self::Exists, 1)),
+            "contains" => Some((Self::Contains, 2)),
+            "starts_with" => Some((Self::StartsWith, 2)),
+            "ends_with" => Some((Self::EndsWith, 2)),
+            "lower" => Some((Self::Lower, 1)),
+            "upper" => Some((Self::Upper, 1)),
+            "len" => Some((Self::Len, 1)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    /// JSON path segments below the payload root (empty = the whole payload).
+    Path(Vec<String>),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Op, Box<Expr>),
+    Arith(Box<Expr>, Arith, Box<Expr>),
+    In(Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+    /// `matches(s, "regex")`; the pattern is compiled at parse time.
+    Matches(Box<Expr>, Regex),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |t| t.column)
+    }
+
+    fn next(&mut self) -> Result<TokenKind> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| anyhow::anyhow!("the condition ended unexpectedly"))?;
+        self.pos += 1;
+        Ok(token.kind.clone())
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek() == Some(kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> Result<()> {
+        if self.eat(kind) {
+            return Ok(());
+        }
+        match self.tokens.get(self.pos) {
+            Some(t) => bail!(
+                "expected {kind} but found {} at column {}",
+                t.kind,
+                t.column
+            ),
+            None => bail!("expected {kind} but the condition ended"),
+        }
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            bail!("condition nests deeper than {MAX_DEPTH} levels");
+        }
+        Ok(())
+    }
+
+    fn peek_op(&self, ops: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(TokenKind::Op(op)) if ops.contains(op) => Some(op),
+            _ => None,
+        }
+    }
+
+    fn peek_comparison(&self) -> Option<Op> {
+        self.peek_op(&["==", "!=", ">=", "<=", ">", "<"])
+            .and_then(Op::from_str)
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        self.enter()?;
+        let mut lhs = self.and()?;
+        while self.peek_op(&["||"]).is_some() {
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        self.depth -= 1;
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut lhs = self.not()?;
+        while self.peek_op(&["&&"]).is_some() {
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.not()?));
+        }
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.peek_op(&["!"]).is_some() {
+            self.pos += 1;
+            self.enter()?;
+            let inner = self.not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        let lhs = self.sum()?;
+        self.comparison_tail(lhs)
+    }
+
+    /// Parse an optional comparison or `in` test following `lhs`.
+    fn comparison_tail(&mut self, lhs: Expr) -> Result<Expr> {
+        if let Some(op) = self.peek_comparison() {
+            self.pos += 1;
+            let rhs = self.sum()?;
+            return Ok(Expr::Compare(Box::new(lhs), op, Box::new(rhs)));
+        }
+        if self.peek() == Some(&TokenKind::Ident("in".into())) {
+            self.pos += 1;
+            let rhs = self.sum()?;
+            return Ok(Expr::In(Box::new(lhs), Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn sum(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        while let Some(op) = self.peek_op(&["+", "-"]) {
+            self.pos += 1;
+            let op = if op == "+" { Arith::Add } else { Arith::Sub };
+            lhs = Expr::Arith(Box::new(lhs), op, Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        while let Some(op) = self.peek_op(&["*", "/", "%"]) {
+            self.pos += 1;
+            let op = match op {
+                "*" => Arith::Mul,
+                "/" => Arith::Div,
+                _ => Arith::Rem,
+            };
+            lhs = Expr::Arith(Box::new(lhs), op, Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek_op(&["-"]).is_some() {
+            self.pos += 1;
+            self.enter()?;
+            let inner = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let column = self.column();
+        match self.next()? {
+            TokenKind::Number(n) => Ok(Expr::Literal(number(n)?)),
+            TokenKind::Str(s) => Ok(Expr::Literal(Value::String(s))),
+            TokenKind::Dollar => self.path(),
+            TokenKind::LParen => {
+                let inner = self.expr()?;
+                self.expect(&TokenKind::RParen)?;
+                Ok(inner)
+            }
+            TokenKind::LBracket => {
+                let mut items = Vec::new();
+                if !self.eat(&TokenKind::RBracket) {
+                    loop {
+                        items.push(self.expr()?);
+                        if self.eat(&TokenKind::RBracket) {
+                            break;
+                        }
+                        self.expect(&TokenKind::Comma)?;
+                    }
+                }
+                Ok(Expr::List(items))
+            }
+            TokenKind::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.peek() == Some(&TokenKind::LParen) => self.call(&name, column),
+                _ => bail!("unknown name '{name}' at column {column}; paths start with '$'"),
+            },
+            other => bail!("unexpected {other} at column {column}"),
+        }
+    }
+
+    fn path(&mut self) -> Result<Expr> {
+        let mut segments = Vec::new();
+        loop {
+            if self.eat(&TokenKind::Dot) {
+                let column = self.column();
+                match self.next()? {
+                    TokenKind::Ident(name) => segments.push(name),
+                    TokenKind::Number(n) if n.fract() == 0.0 && n >= 0.0 => {
+                        segments.push(format!("{n}"));
+                    }
+                    other => bail!("expected a field name but found {other} at column {column}"),
+                }
+            } else if self.eat(&TokenKind::LBracket) {
+                let column = self.column();
+                match self.next()? {
+                    TokenKind::Str(key) => segments.push(key),
+                    TokenKind::Number(n) if n.fract() == 0.0 && n >= 0.0 => {
+                        segments.push(format!("{n}"));
+                    }
+                    other => bail!(
+                        "expected an index or quoted key but found {other} at column {column}"
+                    ),
+                }
+                self.expect(&TokenKind::RBracket)?;
+            } else {
+                return Ok(Expr::Path(segments));
+            }
+        }
+    }
+
+    fn call(&mut self, name: &str, column: usize) -> Result<Expr> {
+        self.expect(&TokenKind::LParen)?;
+        let mut args = Vec::new();
+        if !self.eat(&TokenKind::RParen) {
+            loop {
+                args.push(self.expr()?);
+                if self.eat(&TokenKind::RParen) {
+                    break;
+                }
+                self.expect(&TokenKind::Comma)?;
+            }
+        }
+
+        if name == "matches" {
+            let [subject, Expr::Literal(Value::String(pattern))] = <[Expr; 2]>::try_from(args)
+                .map_err(|_| anyhow::anyhow!("matches() at column {column} takes 2 arguments"))?
+            else {
+                bail!("matches() at column {column} needs a quoted regex as its second argument");
+            };
+            let regex = regex::RegexBuilder::new(&pattern)
+                .size_limit(REGEX_SIZE_LIMIT)
+                .build()
+                .map_err(|e| {
+                    anyhow::anyhow!("invalid regex in matches() at column {column}: {e}")
+                })?;
+            return Ok(Expr::Matches(Box::new(subject), regex));
+        }
+
+        let Some((func, arity)) = Func::lookup(name) else {
+            bail!("unknown function '{name}' at column {column}");
+        };
+        if args.len() != arity {
+            bail!(
+                "{name}() at column {column} takes {arity} argument{}, got {}",
+                if arity == 1 { "" } else { "s" },
+                args.len()
+            );
+        }
+        if func == Func::Exists && !matches!(args[0], Expr::Path(_)) {
+            bail!("exists() at column {column} takes a path such as $.field");
+        }
+        Ok(Expr::Call(func, args))
+    }
+}
+
+// ── Evaluation ──────────────────────────────────────────────────
+
+/// Evaluation failures are not reported to callers; any error makes the
+/// condition false.
+type Eval = std::result::Result<Value, ()>;
+
+fn eval(expr: &Expr, root: &Value) -> Eval {
+    match expr {
+        Expr::Literal(v) => Ok(v.clone()),
+        Expr::Path(segments) => resolve_json_path(root, segments).cloned().ok_or(()),
+        Expr::List(items) => items
+            .iter()
+            .map(|item| eval(item, root))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Expr::Not(inner) => Ok(Value::Bool(!truth(&eval(inner, root)?)?)),
+        Expr::Neg(inner) => number(-as_f64(&eval(inner, root)?)?).map_err(|_| ()),
+        Expr::And(lhs, rhs) => Ok(Value::Bool(
+            truth(&eval(lhs, root)?)? && truth(&eval(rhs, root)?)?,
+        )),
+        Expr::Or(lhs, rhs) => Ok(Value::Bool(
+            truth(&eval(lhs, root)?)? || truth(&eval(rhs, root)?)?,
+        )),
+        Expr::Compare(lhs, op, rhs) => {
+            compare_values(&eval(lhs, root)?, *op, &eval(rhs, root)?).map(Value::Bool)
+        }
+        Expr::Arith(lhs, op, rhs) => {
+            let (a, b) = (as_f64(&eval(lhs, root)?)?, as_f64(&eval(rhs, root)?)?);
+            let n = match op {
+                Arith::Add => a + b,
+                Arith::Sub => a - b,
+                Arith::Mul => a * b,
+                Arith::Div | Arith::Rem if b == 0.0 => return Err(()),
+                Arith::Div => a / b,
+                Arith::Rem => a % b,
+            };
+            number(n).map_err(|_| ())
+        }
+        Expr::In(needle, haystack) => {
+            contains(&eval(haystack, root)?, &eval(needle, root)?).map(Value::Bool)
+        }
+        Expr::Matches(subject, regex) => match eval(subject, root)? {
+            Value::String(s) => Ok(Value::Bool(regex.is_match(&s))),
+            _ => Err(()),
+        },
+        Expr::Call(func, args) => call(*func, args, root),
+    }
+}
+
+fn call(func: Func, args: &[Expr], root: &Value) -> Eval {
+    if func == Func::Exists {
+        let Expr::Path(ref segments) = args[0] else {
+            return Err(());
+        };
+        return Ok(Value::Bool(resolve_json_path(root, segments).is_some()));
+    }
+    let values = args
+        .iter()
+        .map(|arg| eval(arg, root))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    match (func, values.as_slice()) {
+        (Func::Contains, [haystack, needle]) => contains(haystack, needle).map(Value::Bool),
+        (Func::StartsWith, [Value::String(s), Value::String(p)]) => {
+            Ok(Value::Bool(s.starts_with(p.as_str())))
+        }
+        (Func::EndsWith, [Value::String(s), Value::String(p)]) => {
+            Ok(Value::Bool(s.ends_with(p.as_str())))
+        }
+        (Func::Lower, [Value::String(s)]) => Ok(Value::String(s.to_lowercase())),
+        (Func::Upper, [Value::String(s)]) => Ok(Value::String(s.to_uppercase())),
+        (Func::Len, [Value::String(s)]) => Ok(Value::from(s.chars().count())),
+        (Func::Len, [Value::Array(items)]) => Ok(Value::from(items.len())),
+        (Func::Len, [Value::Object(fields)]) => Ok(Value::from(fields.len())),
+        _ => Err(()),
+    }
+}
+
+/// Membership: element of an array, or substring of a string.
+fn contains(haystack: &Value, needle: &Value) -> std::result::Result<bool, ()> {
+    match haystack {
+        Value::Array(items) => Ok(items
+            .iter()
+            .any(|item| compare_values(item, Op::Eq, needle) == Ok(true))),
+        Value::String(s) => match needle {
+            Value::String(sub) => Ok(s.contains(sub.as_str())),
+            _ => Err(()),
+        },
+        _ => Err(()),
+    }
+}
+
+/// Boolean operands must be booleans; anything else is a type error.
+fn truth(value: &Value) -> std::result::Result<bool, ()> {
+    value.as_bool().ok_or(())
+}
+
+fn number(n: f64) -> Result<Value> {
+    serde_json::Number::from_f64(n)
+        .map(Value::Number)
+        .ok_or_else(|| anyhow::anyhow!("{n} is not a finite number"))
+}
+
+fn as_f64(value: &Value) -> std::result::Result<f64, ()> {
+    value_as_f64(value).ok_or(())
+}
+
+/// Walk a JSON value by path segments, trying each as an object key and
+/// then as an array index.
+fn resolve_json_path<'a>(value: &'a Value, segments: &[impl AsRef<str>]) -> Option<&'a Value> {
+    let mut current = value;
+    for seg in segments {
+        let seg = seg.as_ref();
+        // Try object key
+        if let Some(next) = current.get(seg) {
+            current = next;
+            continue;
+        }
+        // Try array index
+        if let Ok(idx) = seg.parse::<usize>()
+            && let Some(next) = current.get(idx)
+        {
+            current = next;
+            continue;
+        }
+        return None;
+    }
+    Some(current)
+}
+
+// ── Comparison ──────────────────────────────────────────────────
+
+/// Compare two values. Numbers (and numeric strings) compare numerically,
+/// values of the same kind compare directly, and anything else compares by
+/// its string form so `$.active == "true"` still matches a JSON `true`.
+fn compare_values(lhs: &Value, op: Op, rhs: &Value) -> std::result::Result<bool, ()> {
     // Try numeric comparison first
-    if let Some(lhs) = value_as_f64(extracted)
-        && let Ok(rhs) = comparand.parse::<f64>()
-    {
-        return apply_op_f64(lhs, op, rhs);
+    if let (Some(a), Some(b)) = (value_as_f64(lhs), value_as_f64(rhs)) {
+        return Ok(apply_op_f64(a, op, b));
     }
 
-    // Fall back to string comparison
-    let lhs = value_as_string(extracted);
-    // Strip surrounding quotes from comparand if present
-    let rhs = comparand
-        .strip_prefix('"')
-        .and_then(|s| s.strip_suffix('"'))
-        .unwrap_or(comparand);
+    let ordering = !matches!(op, Op::Eq | Op::Neq);
+    if ordering && (lhs.is_number() || rhs.is_number()) {
+        return Err(()); // a number against something non-numeric
+    }
 
-    match op {
+    match (lhs, rhs, op) {
+        (Value::Bool(a), Value::Bool(b), Op::Eq) => return Ok(a == b),
+        (Value::Bool(a), Value::Bool(b), Op::Neq) => return Ok(a != b),
+        (Value::Array(_) | Value::Object(_), _, Op::Eq | Op::Neq)
+        | (_, Value::Array(_) | Value::Object(_), Op::Eq | Op::Neq) => {
+            return Ok((lhs == rhs) == (op == Op::Eq));
+        }
+        (Value::Array(_) | Value::Object(_), _, _) | (_, Value::Array(_) | Value::Object(_), _) => {
+            return Err(());
+        }
+        _ => {}
+    }
+
+    // Fall back to string comparison
+    let lhs = value_as_string(lhs);
+    let rhs = value_as_string(rhs);
+    Ok(match op {
         Op::Eq => lhs == rhs,
         Op::Neq => lhs != rhs,
-        Op::Gt => lhs.as_str() > rhs,
-        Op::Lt => lhs.as_str() < rhs,
-        Op::Gte => lhs.as_str() >= rhs,
-        Op::Lte => lhs.as_str() <= rhs,
-    }
+        Op::Gt => lhs > rhs,
+        Op::Lt => lhs < rhs,
+        Op::Gte => lhs >= rhs,
+        Op::Lte => lhs <= rhs,
+    })
 }
 
 fn value_as_f64(v: &Value) -> Option<f64> {
     match v {
         Value::Number(n) => n.as_f64(),
-        Value::String(s) => s.parse().ok(),
+        Value::String(s) => s.trim().parse().ok(),
         _ => None,
     }
 }
@@ -237,6 +834,14 @@ mod tests {
         assert!(evaluate_condition("  ", None));
     }
 
+    #[test]
+    fn cached_reuses_parsed_condition() {
+        let first = Condition::cached("$.cache_probe > 1").unwrap();
+        let second = Condition::cached("$.cache_probe > 1").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Condition::cached("$.cache_probe >").is_err());
+    }
+
     #[test]
     fn missing_payload_fails_closed() {
         assert!(!evaluate_condition("$.value > 85", None));
@@ -381,71 +986,183 @@ mod tests {
         assert!(!evaluate_condition("> 3.14", Some("3.13")));
     }
 
-    // ── Op parsing ──────────────────────────────────────
+    // ── resolve_json_path ───────────────────────────────
 
     #[test]
-    fn parse_op_value_basic() {
-        let (op, val) = parse_op_value("> 42").unwrap();
-        assert_eq!(op, Op::Gt);
-        assert_eq!(val, "42");
+    fn resolve_path_simple() {
+        let json: Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+        let v = resolve_json_path(&json, &["a"]).unwrap();
+        assert_eq!(v, &Value::Number(1.into()));
     }
 
     #[test]
-    fn parse_op_value_gte_not_gt() {
-        let (op, val) = parse_op_value(">= 10").unwrap();
-        assert_eq!(op, Op::Gte);
-        assert_eq!(val, "10");
+    fn resolve_path_nested() {
+        let json: Value = serde_json::from_str(r#"{"a": {"b": {"c": 42}}}"#).unwrap();
+        let v = resolve_json_path(&json, &["a", "b", "c"]).unwrap();
+        assert_eq!(v, &Value::Number(42.into()));
     }
 
     #[test]
-    fn parse_op_value_no_value() {
-        assert!(parse_op_value(">").is_none());
-        assert!(parse_op_value("> ").is_none());
+    fn resolve_path_missing() {
+        let json: Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+        assert!(resolve_json_path(&json, &["b"]).is_none());
     }
 
+    // ── Expression language ─────────────────────────────
+
     #[test]
-    fn parse_path_op_value_basic() {
-        let (segments, op, val) = parse_path_op_value(".value > 85").unwrap();
-        assert_eq!(segments, vec!["value"]);
-        assert_eq!(op, Op::Gt);
-        assert_eq!(val, "85");
+    fn boolean_combinators_and_membership() {
+        let cond = r#"$.temp > 80 && $.sensor in ["a", "b"]"#;
+        assert!(evaluate_condition(
+            cond,
+            Some(r#"{"temp": 85, "sensor": "a"}"#)
+        ));
+        assert!(!evaluate_condition(
+            cond,
+            Some(r#"{"temp": 85, "sensor": "c"}"#)
+        ));
+        assert!(!evaluate_condition(
+            cond,
+            Some(r#"{"temp": 70, "sensor": "a"}"#)
+        ));
+
+        let cond = r#"$.level == "critical" || !($.ack == true)"#;
+        assert!(evaluate_condition(
+            cond,
+            Some(r#"{"level": "info", "ack": false}"#)
+        ));
+        assert!(!evaluate_condition(
+            cond,
+            Some(r#"{"level": "info", "ack": true}"#)
+        ));
     }
 
     #[test]
-    fn parse_path_op_value_nested() {
-        let (segments, op, val) = parse_path_op_value(".data.temp >= 100").unwrap();
-        assert_eq!(segments, vec!["data", "temp"]);
-        assert_eq!(op, Op::Gte);
-        assert_eq!(val, "100");
+    fn exists_guards_missing_paths() {
+        assert!(evaluate_condition(
+            "exists($.alarm)",
+            Some(r#"{"alarm": null}"#)
+        ));
+        assert!(!evaluate_condition(
+            "exists($.alarm)",
+            Some(r#"{"other": 1}"#)
+        ));
+        assert!(evaluate_condition(
+            "!exists($.alarm)",
+            Some(r#"{"other": 1}"#)
+        ));
+        // Short-circuit keeps the missing path from being evaluated
+        assert!(!evaluate_condition(
+            "exists($.alarm) && $.alarm.level > 2",
+            Some(r#"{"other": 1}"#)
+        ));
     }
 
     #[test]
-    fn parse_path_op_value_string_comparand() {
-        let (segments, op, val) = parse_path_op_value(r#".status == "critical""#).unwrap();
-        assert_eq!(segments, vec!["status"]);
-        assert_eq!(op, Op::Eq);
-        assert_eq!(val, r#""critical""#);
+    fn missing_path_fails_whole_condition() {
+        // Fail-closed: negating a failed comparison does not make it match
+        assert!(!evaluate_condition("!($.missing > 3)", Some(r#"{"a": 1}"#)));
+        assert!(!evaluate_condition(
+            "$.missing > 3 || true",
+            Some(r#"{"a": 1}"#)
+        ));
     }
 
-    // ── resolve_json_path ───────────────────────────────
+    #[test]
+    fn string_and_regex_functions() {
+        let payload = r#"{"host": "db-01.prod", "tags": ["edge", "eu"]}"#;
+        assert!(evaluate_condition(
+            r#"starts_with($.host, "db-")"#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition(
+            r#"ends_with($.host, ".prod")"#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition(
+            r#"contains($.host, "01")"#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition(
+            r#"contains($.tags, "eu")"#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition(r#""prod" in $.host"#, Some(payload)));
+        assert!(evaluate_condition(
+            r#"upper($.tags[0]) == "EDGE""#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition("len($.tags) == 2", Some(payload)));
+        assert!(evaluate_condition(
+            r#"matches($.host, "^db-[0-9]+\\.prod$")"#,
+            Some(payload)
+        ));
+        assert!(!evaluate_condition(
+            r#"matches($.host, "^web")"#,
+            Some(payload)
+        ));
+    }
 
     #[test]
-    fn resolve_path_simple() {
-        let json: Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
-        let v = resolve_json_path(&json, &["a"]).unwrap();
-        assert_eq!(v, &Value::Number(1.into()));
+    fn arithmetic_over_fields() {
+        let payload = r#"{"inlet": 40, "outlet": 95, "readings": [1, 2]}"#;
+        assert!(evaluate_condition("$.outlet - $.inlet > 50", Some(payload)));
+        assert!(evaluate_condition(
+            "($.inlet + $.outlet) / 2 == 67.5",
+            Some(payload)
+        ));
+        assert!(evaluate_condition("$.readings.1 * -2 == -4", Some(payload)));
+        assert!(evaluate_condition("$.outlet % 10 == 5", Some(payload)));
+        // Division by zero and arithmetic on strings fail closed
+        assert!(!evaluate_condition("$.outlet / 0 > 1", Some(payload)));
+        assert!(!evaluate_condition(
+            r#"$.outlet + "x" > 1"#,
+            Some(r#"{"outlet": 1}"#)
+        ));
     }
 
     #[test]
-    fn resolve_path_nested() {
-        let json: Value = serde_json::from_str(r#"{"a": {"b": {"c": 42}}}"#).unwrap();
-        let v = resolve_json_path(&json, &["a", "b", "c"]).unwrap();
-        assert_eq!(v, &Value::Number(42.into()));
+    fn bracket_paths_and_bool_literals() {
+        let payload = r#"{"odd-key": {"ok": false}, "list": [{"v": 3}]}"#;
+        assert!(evaluate_condition(
+            r#"$["odd-key"].ok == false"#,
+            Some(payload)
+        ));
+        assert!(evaluate_condition("$.list[0].v >= 3", Some(payload)));
+        assert!(evaluate_condition("$.list.0.v == 3", Some(payload)));
     }
 
     #[test]
-    fn resolve_path_missing() {
-        let json: Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
-        assert!(resolve_json_path(&json, &["b"]).is_none());
+    fn non_json_payload_is_a_string() {
+        assert!(evaluate_condition(r#"== "open""#, Some("open")));
+        assert!(evaluate_condition(r#"starts_with($, "op")"#, Some("open")));
+    }
+
+    #[test]
+    fn parse_errors_name_the_column() {
+        let err = Condition::parse("$.a > 1 &&").unwrap_err().to_string();
+        assert!(err.contains("ended"), "{err}");
+        let err = Condition::parse("$.a > 1 )").unwrap_err().to_string();
+        assert_eq!(err, "unexpected ')' at column 9");
+        let err = Condition::parse("nope($.a)").unwrap_err().to_string();
+        assert_eq!(err, "unknown function 'nope' at column 1");
+        let err = Condition::parse("exists(1)").unwrap_err().to_string();
+        assert!(err.contains("takes a path"), "{err}");
+        let err = Condition::parse(r#"matches($.a, "(")"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid regex"), "{err}");
+        let err = Condition::parse("$.a == 'x").unwrap_err().to_string();
+        assert_eq!(err, "unterminated string starting at column 8");
+        assert!(Condition::parse("status > 1").is_err());
+    }
+
+    #[test]
+    fn parser_limits_nesting() {
+        let deep = format!("{}1 == 1{}", "(".repeat(100), ")".repeat(100));
+        let err = Condition::parse(&deep).unwrap_err().to_string();
+        assert!(err.contains("nests deeper"), "{err}");
+        assert!(Condition::parse(&"!".repeat(200)).is_err());
+        assert!(Condition::parse(&"1".repeat(MAX_CONDITION_LEN + 1)).is_err());
     }
 }
@@ -41,6 +41,8 @@ fn extract_run_id_from_action(action: &SopRunAction) -> &str {
         | SopRunAction::WaitApproval { run_id, .. }
         | SopRunAction::DeterministicStep { run_id, .. }
         | SopRunAction::CheckpointWait { run_id, .. }
+        | SopRunAction::Parallel { run_id, .. }
+        | SopRunAction::AwaitBranches { run_id, .. }
         | SopRunAction::Completed { run_id, .. }
         | SopRunAction::Failed { run_id, .. } => run_id,
     }
@@ -53,6 +55,8 @@ fn action_label(action: &SopRunAction) -> &'static str {
         SopRunAction::WaitApproval { .. } => "WaitApproval",
         SopRunAction::DeterministicStep { .. } => "DeterministicStep",
         SopRunAction::CheckpointWait { .. } => "CheckpointWait",
+        SopRunAction::Parallel { .. } => "Parallel",
+        SopRunAction::AwaitBranches { .. } => "AwaitBranches",
         SopRunAction::Completed { .. } => "Completed",
         SopRunAction::Failed { .. } => "Failed",
     }
@@ -201,6 +205,21 @@ pub fn process_headless_results(results: &[DispatchResult]) {
                         state_file.display(),
                     );
                 }
+                SopRunAction::Parallel { step, branches, .. } => {
+                    info!(
+                        "SOP headless dispatch: run {run_id} ('{sop_name}') step {} '{}' started \
+                         {} parallel branch(es)",
+                        step.number,
+                        step.title,
+                        branches.len(),
+                    );
+                }
+                SopRunAction::AwaitBranches { pending, .. } => {
+                    info!(
+                        "SOP headless dispatch: run {run_id} ('{sop_name}') waiting on branches \
+                         {pending:?}"
+                    );
+                }
                 SopRunAction::Completed { .. } => {
                     info!(
                         "SOP headless dispatch: run {run_id} ('{sop_name}') completed immediately"
@@ -372,6 +391,13 @@ mod tests {
                 requires_confirmation: false,
                 kind: crate::sop::SopStepKind::default(),
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: crate::sop::SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
@@ -498,12 +524,14 @@ mod tests {
             "webhook-sop-1",
             vec![SopTrigger::Webhook {
                 path: "/api/deploy".into(),
+                condition: None,
             }],
         );
         let sop2 = test_sop(
             "webhook-sop-2",
             vec![SopTrigger::Webhook {
                 path: "/api/deploy".into(),
+                condition: None,
             }],
         );
         let engine = test_engine(vec![sop1, sop2]);
@@ -7,10 +7,11 @@ use tracing::{info, warn};
 
 use super::condition::evaluate_condition;
 use super::load_sops;
+use super::store;
 use super::types::{
-    DeterministicRunState, DeterministicSavings, Sop, SopEvent, SopExecutionMode, SopPriority,
-    SopRun, SopRunAction, SopRunStatus, SopStep, SopStepKind, SopStepResult, SopStepStatus,
-    SopTrigger, SopTriggerSource,
+    DeterministicRunState, DeterministicSavings, END_STEP, Sop, SopEvent, SopExecutionMode,
+    SopJoin, SopPriority, SopRun, SopRunAction, SopRunStatus, SopStep, SopStepKind, SopStepResult,
+    SopStepStatus, SopTrigger, SopTriggerSource,
 };
 use zeroclaw_config::schema::SopConfig;
 
@@ -24,6 +25,10 @@ pub struct SopEngine {
     run_counter: u64,
     /// Cumulative savings from deterministic execution.
     deterministic_savings: DeterministicSavings,
+    /// Workspace whose SQLite journal records every run, once opened.
+    journal_dir: Option<PathBuf>,
+    /// Simulation engine: checkpoint state files are named but not written.
+    dry_run: bool,
 }
 
 impl SopEngine {
@@ -36,9 +41,23 @@ impl SopEngine {
             config,
             run_counter: 0,
             deterministic_savings: DeterministicSavings::default(),
+            journal_dir: None,
+            dry_run: false,
         }
     }
 
+    /// Create a throwaway engine over `sops` for dry runs. It never journals
+    /// and never writes checkpoint state, and has no approval timeout.
+    pub fn for_simulation(sops: Vec<Sop>) -> Self {
+        let mut engine = Self::new(SopConfig {
+            approval_timeout_secs: 0,
+            ..SopConfig::default()
+        });
+        engine.sops = sops;
+        engine.dry_run = true;
+        engine
+    }
+
     /// Load/reload SOPs from the configured directory.
     pub fn reload(&mut self, workspace_dir: &Path) {
         self.sops = load_sops(
@@ -49,6 +68,45 @@ impl SopEngine {
         info!("SOP engine loaded {} SOPs", self.sops.len());
     }
 
+    /// Open the run journal under `workspace_dir` and resume the runs it
+    /// holds. Call after `reload()` so resumed runs can be matched to their
+    /// SOPs; runs whose SOP or current step is gone are left journaled as
+    /// orphans (see `zeroclaw doctor`).
+    ///
+    /// Runs keep their original `waiting_since`, so approval timeouts that
+    /// lapsed while the engine was down fire immediately: the returned
+    /// actions are those of `check_approval_timeouts()`.
+    pub fn open_journal(&mut self, workspace_dir: &Path) -> Result<Vec<SopRunAction>> {
+        let finished = store::load_finished_runs(workspace_dir, self.config.max_finished_runs)?;
+        let active = store::load_active_runs(workspace_dir)?;
+        self.journal_dir = Some(workspace_dir.to_path_buf());
+
+        if self.finished_runs.is_empty() {
+            self.finished_runs = finished;
+        }
+        let mut resumed = 0;
+        for run in active {
+            if let Some(reason) = store::orphan_reason(&self.sops, &run) {
+                warn!("SOP run {} not resumed: {reason}", run.run_id);
+                continue;
+            }
+            if self.active_runs.contains_key(&run.run_id) {
+                continue;
+            }
+            info!(
+                "SOP run {} resumed for '{}' at step {} ({})",
+                run.run_id, run.sop_name, run.current_step, run.status
+            );
+            self.active_runs.insert(run.run_id.clone(), run);
+            resumed += 1;
+        }
+        if resumed > 0 {
+            info!("SOP engine resumed {resumed} run(s) from its journal");
+        }
+
+        Ok(self.check_approval_timeouts())
+    }
+
     /// Return all loaded SOP definitions.
     pub fn sops(&self) -> &[Sop] {
         &self.sops
@@ -166,6 +224,8 @@ impl SopEngine {
             step_results: Vec::new(),
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
 
         self.active_runs.insert(run_id.clone(), run);
@@ -173,24 +233,23 @@ impl SopEngine {
         info!("SOP run {} started for '{}'", run_id, sop_name);
 
         // Determine first action based on execution mode
-        let step = sop.steps[0].clone();
-        let context = format_step_context(&sop, &self.active_runs[&run_id], &step);
-        let action = resolve_step_action(&sop, &step, run_id.clone(), context);
-
-        // If the action is WaitApproval, update run status and record timestamp
-        if matches!(action, SopRunAction::WaitApproval { .. })
-            && let Some(run) = self.active_runs.get_mut(&run_id)
-        {
-            run.status = SopRunStatus::WaitingApproval;
-            run.waiting_since = Some(now_iso8601());
-        }
-
-        Ok(action)
+        let first = sop.steps[0].number;
+        let action = self.enter_step(&sop, &run_id, first, serde_json::Value::Null);
+        self.journal(&run_id);
+        action
     }
 
     /// Report the result of the current step and advance the run.
     /// Returns the next action to take.
-    pub fn advance_step(&mut self, run_id: &str, result: SopStepResult) -> Result<SopRunAction> {
+    ///
+    /// While a parallel step is in flight, `result.step_number` names the
+    /// branch being reported; the parallel step's own number stands for the
+    /// first pending branch.
+    pub fn advance_step(
+        &mut self,
+        run_id: &str,
+        mut result: SopStepResult,
+    ) -> Result<SopRunAction> {
         let run = self
             .active_runs
             .get_mut(run_id)
@@ -203,43 +262,29 @@ impl SopEngine {
             .ok_or_else(|| anyhow::anyhow!("SOP '{}' no longer loaded", run.sop_name))?
             .clone();
 
-        // Record step result
-        run.step_results.push(result.clone());
-
-        // Check if step failed
-        if result.status == SopStepStatus::Failed {
-            let reason = format!("Step {} failed: {}", result.step_number, result.output);
-            warn!("SOP run {run_id}: {reason}");
-            return Ok(self.finish_run(run_id, SopRunStatus::Failed, Some(reason)));
-        }
-
-        // Advance to next step
-        let next_step_num = run.current_step + 1;
-        if next_step_num > run.total_steps {
-            // All steps completed
-            info!("SOP run {run_id} completed successfully");
-            return Ok(self.finish_run(run_id, SopRunStatus::Completed, None));
+        if run.pending_branches.is_empty() {
+            result.step_number = run.current_step;
+        } else if !run.pending_branches.contains(&result.step_number) {
+            if result.step_number != run.current_step {
+                bail!(
+                    "Run {run_id} is waiting on parallel branches {:?}, not step {}",
+                    run.pending_branches,
+                    result.step_number
+                );
+            }
+            result.step_number = run.pending_branches[0];
         }
 
-        // Update run state
-        let run = self.active_runs.get_mut(run_id).unwrap();
-        run.current_step = next_step_num;
-
-        let step_idx = (next_step_num - 1) as usize;
-        let step = sop.steps[step_idx].clone();
-        let context = format_step_context(&sop, run, &step);
-        let run_id_str = run_id.to_string();
-        let action = resolve_step_action(&sop, &step, run_id_str.clone(), context);
-
-        // If the action is WaitApproval, update run status and record timestamp
-        if matches!(action, SopRunAction::WaitApproval { .. })
-            && let Some(run) = self.active_runs.get_mut(&run_id_str)
+        // Each deterministic step saves one LLM call
+        if sop.execution_mode == SopExecutionMode::Deterministic
+            && result.status != SopStepStatus::Failed
         {
-            run.status = SopRunStatus::WaitingApproval;
-            run.waiting_since = Some(now_iso8601());
+            run.llm_calls_saved += 1;
         }
 
-        Ok(action)
+        let action = self.route_result(&sop, run_id, result);
+        self.journal(run_id);
+        action
     }
 
     /// Cancel an active run.
@@ -248,12 +293,17 @@ impl SopEngine {
             bail!("Active run not found: {run_id}");
         }
         self.finish_run(run_id, SopRunStatus::Cancelled, None);
+        self.journal(run_id);
         info!("SOP run {run_id} cancelled");
         Ok(())
     }
 
     /// Approve a step that is waiting for approval, transitioning back to Running.
     pub fn approve_step(&mut self, run_id: &str) -> Result<SopRunAction> {
+        self.approve_step_by(run_id, "operator")
+    }
+
+    fn approve_step_by(&mut self, run_id: &str, approved_by: &str) -> Result<SopRunAction> {
         let run = self
             .active_runs
             .get_mut(run_id)
@@ -276,15 +326,62 @@ impl SopEngine {
             .ok_or_else(|| anyhow::anyhow!("SOP '{}' no longer loaded", run.sop_name))?
             .clone();
 
-        let step_idx = (run.current_step - 1) as usize;
-        let step = sop.steps[step_idx].clone();
-        let context = format_step_context(&sop, run, &step);
+        let step = sop
+            .step(run.current_step)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' has no step {}", sop.name, run.current_step))?
+            .clone();
+        let number = step.number;
+        let action = if step.kind == SopStepKind::Parallel {
+            let input = last_output(run, |_| false);
+            self.fan_out(&sop, run_id, &step, input)
+        } else {
+            let context = format_step_context(&sop, run, &step);
+            Ok(SopRunAction::ExecuteStep {
+                run_id: run_id.to_string(),
+                step,
+                context,
+            })
+        };
+        self.journal_approval(run_id, number, approved_by);
+        self.journal(run_id);
+        action
+    }
 
-        Ok(SopRunAction::ExecuteStep {
-            run_id: run_id.to_string(),
-            step,
-            context,
-        })
+    /// Approve a run paused for a human, whether it is waiting on a supervised
+    /// step (`WaitingApproval`) or a deterministic checkpoint (`PausedCheckpoint`).
+    pub fn approve(&mut self, run_id: &str) -> Result<SopRunAction> {
+        let status = self
+            .active_runs
+            .get(run_id)
+            .map(|r| r.status)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+        if status == SopRunStatus::PausedCheckpoint {
+            // Approval completes the checkpoint step itself; the previous
+            // step's output passes through it to the next step.
+            let mut state = self.deterministic_state(run_id)?;
+            let (checkpoint, carried) = match self.active_runs.get_mut(run_id) {
+                Some(run) => {
+                    let checkpoint = run.current_step;
+                    let carried = last_output(run, |_| false);
+                    let now = now_iso8601();
+                    run.step_results.push(SopStepResult {
+                        step_number: checkpoint,
+                        status: SopStepStatus::Completed,
+                        output: carried.to_string(),
+                        started_at: run.waiting_since.clone().unwrap_or_else(|| now.clone()),
+                        completed_at: Some(now),
+                    });
+                    (checkpoint, carried)
+                }
+                None => bail!("Active run not found: {run_id}"),
+            };
+            state.step_outputs.insert(checkpoint, carried);
+            state.last_completed_step = checkpoint;
+            self.journal_approval(run_id, checkpoint, "operator");
+            self.resume_deterministic_run(state)
+        } else {
+            self.approve_step(run_id)
+        }
     }
 
     /// List finished runs, optionally filtered by SOP name.
@@ -354,6 +451,8 @@ impl SopEngine {
             step_results: Vec::new(),
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
 
         self.active_runs.insert(run_id.clone(), run);
@@ -363,13 +462,15 @@ impl SopEngine {
         );
 
         // Produce first step action
-        let step = sop.steps[0].clone();
-        let input = serde_json::Value::Null;
-        self.resolve_deterministic_action(&sop, &run_id, &step, input)
+        let first = sop.steps[0].number;
+        let action = self.enter_step(&sop, &run_id, first, serde_json::Value::Null);
+        self.journal(&run_id);
+        action
     }
 
     /// Advance a deterministic run with the output of the current step.
-    /// The output is piped as input to the next step.
+    /// The output is piped as input to the next step. While a parallel step
+    /// is in flight the output belongs to the first pending branch.
     pub fn advance_deterministic_step(
         &mut self,
         run_id: &str,
@@ -377,51 +478,17 @@ impl SopEngine {
     ) -> Result<SopRunAction> {
         let run = self
             .active_runs
-            .get_mut(run_id)
+            .get(run_id)
             .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
 
-        let sop = self
-            .sops
-            .iter()
-            .find(|s| s.name == run.sop_name)
-            .ok_or_else(|| anyhow::anyhow!("SOP '{}' no longer loaded", run.sop_name))?
-            .clone();
-
-        // Record step result
-        let now = now_iso8601();
         let step_result = SopStepResult {
             step_number: run.current_step,
             status: SopStepStatus::Completed,
             output: step_output.to_string(),
             started_at: run.started_at.clone(),
-            completed_at: Some(now),
+            completed_at: Some(now_iso8601()),
         };
-        run.step_results.push(step_result);
-
-        // Each deterministic step saves one LLM call
-        run.llm_calls_saved += 1;
-
-        // Advance to next step
-        let next_step_num = run.current_step + 1;
-        if next_step_num > run.total_steps {
-            info!(
-                "Deterministic SOP run {run_id} completed ({} LLM calls saved)",
-                run.llm_calls_saved
-            );
-            let saved = run.llm_calls_saved;
-            self.deterministic_savings.total_llm_calls_saved += saved;
-            self.deterministic_savings.total_runs += 1;
-            return Ok(self.finish_run(run_id, SopRunStatus::Completed, None));
-        }
-
-        let run = self.active_runs.get_mut(run_id).unwrap();
-        run.current_step = next_step_num;
-
-        let step_idx = (next_step_num - 1) as usize;
-        let step = sop.steps[step_idx].clone();
-        let run_id_owned = run_id.to_string();
-
-        self.resolve_deterministic_action(&sop, &run_id_owned, &step, step_output)
+        self.advance_step(run_id, step_result)
     }
 
     /// Resume a deterministic run from persisted state.
@@ -453,24 +520,6 @@ impl SopEngine {
         run.waiting_since = None;
         run.llm_calls_saved = state.llm_calls_saved;
 
-        // Resume from the step after the last completed one
-        let next_step_num = state.last_completed_step + 1;
-        if next_step_num > state.total_steps {
-            info!(
-                "Deterministic SOP run {} completed on resume ({} LLM calls saved)",
-                state.run_id, state.llm_calls_saved
-            );
-            self.deterministic_savings.total_llm_calls_saved += state.llm_calls_saved;
-            self.deterministic_savings.total_runs += 1;
-            return Ok(self.finish_run(&state.run_id, SopRunStatus::Completed, None));
-        }
-
-        let run = self.active_runs.get_mut(&state.run_id).unwrap();
-        run.current_step = next_step_num;
-
-        let step_idx = (next_step_num - 1) as usize;
-        let step = sop.steps[step_idx].clone();
-
         // Use last step's output as input, or Null
         let last_output = state
             .step_outputs
@@ -478,8 +527,16 @@ impl SopEngine {
             .cloned()
             .unwrap_or(serde_json::Value::Null);
 
+        // Route onwards from the last completed step
         let run_id = state.run_id.clone();
-        self.resolve_deterministic_action(&sop, &run_id, &step, last_output)
+        let action = if state.last_completed_step == 0 {
+            let first = sop.steps[0].number;
+            self.enter_step(&sop, &run_id, first, last_output)
+        } else {
+            self.leave_step(&sop, &run_id, state.last_completed_step, last_output, None)
+        };
+        self.journal(&run_id);
+        action
     }
 
     /// Resolve the action for a deterministic step (execute or checkpoint).
@@ -522,6 +579,22 @@ impl SopEngine {
 
     /// Persist the current deterministic run state to a JSON file.
     fn persist_deterministic_state(&self, run_id: &str, sop: &Sop) -> Result<PathBuf> {
+        let state = self.deterministic_state(run_id)?;
+
+        // Write to SOP location directory, or system temp dir
+        let temp_dir = std::env::temp_dir();
+        let dir = sop.location.as_deref().unwrap_or(temp_dir.as_path());
+        let state_file = dir.join(format!("{run_id}.state.json"));
+        if !self.dry_run {
+            let json = serde_json::to_string_pretty(&state)?;
+            std::fs::write(&state_file, json)?;
+        }
+
+        Ok(state_file)
+    }
+
+    /// Snapshot the resumable state of an active deterministic run.
+    fn deterministic_state(&self, run_id: &str) -> Result<DeterministicRunState> {
         let run = self
             .active_runs
             .get(run_id)
@@ -538,22 +611,19 @@ impl SopEngine {
         let state = DeterministicRunState {
             run_id: run_id.to_string(),
             sop_name: run.sop_name.clone(),
-            last_completed_step: run.current_step.saturating_sub(1),
+            last_completed_step: run
+                .step_results
+                .iter()
+                .rev()
+                .find(|r| r.status != SopStepStatus::Failed)
+                .map_or(0, |r| r.step_number),
             total_steps: run.total_steps,
             step_outputs,
             persisted_at: now_iso8601(),
             llm_calls_saved: run.llm_calls_saved,
             paused_at_checkpoint: run.status == SopRunStatus::PausedCheckpoint,
         };
-
-        // Write to SOP location directory, or system temp dir
-        let temp_dir = std::env::temp_dir();
-        let dir = sop.location.as_deref().unwrap_or(temp_dir.as_path());
-        let state_file = dir.join(format!("{run_id}.state.json"));
-        let json = serde_json::to_string_pretty(&state)?;
-        std::fs::write(&state_file, json)?;
-
-        Ok(state_file)
+        Ok(state)
     }
 
     /// Load a persisted deterministic run state from a JSON file.
@@ -563,6 +633,308 @@ impl SopEngine {
         Ok(state)
     }
 
+    // ── Step routing ────────────────────────────────────────────
+
+    /// Enter step `number`, returning the action the caller must take.
+    /// Every entry, whether a loop or a retry, counts towards the step's
+    /// visit limit; exceeding it fails the run.
+    fn enter_step(
+        &mut self,
+        sop: &Sop,
+        run_id: &str,
+        number: u32,
+        input: serde_json::Value,
+    ) -> Result<SopRunAction> {
+        let step = sop
+            .step(number)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' has no step {number}", sop.name))?
+            .clone();
+        let run = self
+            .active_runs
+            .get_mut(run_id)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+
+        let visits = run.step_visits.entry(number).or_insert(0);
+        if *visits >= step.visit_limit() {
+            let reason = format!(
+                "Step {number} was entered more than {} time(s)",
+                step.visit_limit()
+            );
+            warn!("SOP run {run_id}: {reason}");
+            return Ok(self.finish_run(run_id, SopRunStatus::Failed, Some(reason)));
+        }
+        *visits += 1;
+        run.current_step = number;
+
+        if sop.execution_mode == SopExecutionMode::Deterministic {
+            if step.kind == SopStepKind::Parallel {
+                return self.fan_out(sop, run_id, &step, input);
+            }
+            return self.resolve_deterministic_action(sop, run_id, &step, input);
+        }
+
+        let context = format_step_context(sop, run, &step);
+        let action = resolve_step_action(sop, &step, run_id.to_string(), context);
+
+        // If the action is WaitApproval, update run status and record timestamp
+        if matches!(action, SopRunAction::WaitApproval { .. }) {
+            run.status = SopRunStatus::WaitingApproval;
+            run.waiting_since = Some(now_iso8601());
+            return Ok(action);
+        }
+        if step.kind == SopStepKind::Parallel {
+            return self.fan_out(sop, run_id, &step, input);
+        }
+        Ok(action)
+    }
+
+    /// Start every branch of a parallel step.
+    fn fan_out(
+        &mut self,
+        sop: &Sop,
+        run_id: &str,
+        group: &SopStep,
+        input: serde_json::Value,
+    ) -> Result<SopRunAction> {
+        let run = self
+            .active_runs
+            .get_mut(run_id)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+
+        let mut pending = Vec::new();
+        let mut branches = Vec::new();
+        for branch in group.branches.iter().filter_map(|b| sop.resolve_target(b)) {
+            let Some(step) = sop.step(branch) else {
+                continue;
+            };
+            *run.step_visits.entry(branch).or_insert(0) += 1;
+            pending.push(branch);
+            branches.push(branch_action(sop, run, step, input.clone()));
+        }
+        run.pending_branches = pending;
+
+        info!(
+            "SOP run {run_id}: step {} '{}' started {} branch(es)",
+            group.number,
+            group.title,
+            branches.len()
+        );
+        Ok(SopRunAction::Parallel {
+            run_id: run_id.to_string(),
+            step: group.clone(),
+            branches,
+        })
+    }
+
+    /// Record a step result and route the run onwards. Completed outputs are
+    /// checked against the step's output schema; a mismatch is a failure.
+    fn route_result(
+        &mut self,
+        sop: &Sop,
+        run_id: &str,
+        mut result: SopStepResult,
+    ) -> Result<SopRunAction> {
+        let number = result.step_number;
+        let output = parse_output(&result.output);
+
+        let mut failure = (result.status == SopStepStatus::Failed)
+            .then(|| format!("Step {number} failed: {}", result.output));
+        if result.status == SopStepStatus::Completed
+            && let Some(schema) = sop.step(number).and_then(|s| s.schema.as_ref())
+            && let Err(e) = schema.validate_output(&output)
+        {
+            failure = Some(format!(
+                "Step {number} output does not match its schema: {e}"
+            ));
+            result.status = SopStepStatus::Failed;
+        }
+
+        let run = self
+            .active_runs
+            .get_mut(run_id)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+        run.step_results.push(result);
+
+        if run.pending_branches.contains(&number) {
+            return self.join_branch(sop, run_id, number, failure);
+        }
+        self.leave_step(sop, run_id, number, output, failure)
+    }
+
+    /// Route out of a finished step: retry or take `on_failure` after a
+    /// failure, otherwise follow the first matching `next` transition or fall
+    /// through to the following step.
+    fn leave_step(
+        &mut self,
+        sop: &Sop,
+        run_id: &str,
+        number: u32,
+        output: serde_json::Value,
+        failure: Option<String>,
+    ) -> Result<SopRunAction> {
+        let step = sop
+            .step(number)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' has no step {number}", sop.name))?
+            .clone();
+
+        if let Some(reason) = failure {
+            let run = self
+                .active_runs
+                .get(run_id)
+                .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+            let failures = consecutive_failures(run, number);
+            if failures <= step.retries {
+                info!(
+                    "SOP run {run_id}: {reason}; retrying (attempt {} of {})",
+                    failures + 1,
+                    step.retries + 1
+                );
+                let input = last_output(run, |n| n == number || step_owns(sop, &step, n));
+                return self.enter_step(sop, run_id, number, input);
+            }
+            if let Some(target) = step
+                .on_failure
+                .as_deref()
+                .and_then(|t| sop.resolve_target(t))
+            {
+                info!("SOP run {run_id}: {reason}; continuing at step {target}");
+                return self.enter_step(sop, run_id, target, output);
+            }
+            warn!("SOP run {run_id}: {reason}");
+            return Ok(self.finish_run(run_id, SopRunStatus::Failed, Some(reason)));
+        }
+
+        let payload = match output {
+            serde_json::Value::String(ref s) => s.clone(),
+            ref other => other.to_string(),
+        };
+        let transition = step.next.iter().find(|t| {
+            t.when
+                .as_deref()
+                .is_none_or(|cond| evaluate_condition(cond, Some(&payload)))
+        });
+        let next = match transition {
+            Some(t) if t.goto == END_STEP => None,
+            Some(t) => sop.resolve_target(&t.goto),
+            None => sop.following_step(number),
+        };
+
+        match next {
+            Some(target) => self.enter_step(sop, run_id, target, output),
+            None => Ok(self.complete_run(sop, run_id)),
+        }
+    }
+
+    /// Fold a branch result into its parallel step. Once the join is
+    /// decided, the parallel step finishes with an object mapping each
+    /// successful branch to its output.
+    fn join_branch(
+        &mut self,
+        sop: &Sop,
+        run_id: &str,
+        branch: u32,
+        failure: Option<String>,
+    ) -> Result<SopRunAction> {
+        let run = self
+            .active_runs
+            .get_mut(run_id)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+        let group = sop
+            .step(run.current_step)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' has no step {}", sop.name, run.current_step))?
+            .clone();
+        let branch_step = sop
+            .step(branch)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' has no step {branch}", sop.name))?;
+
+        if let Some(ref reason) = failure {
+            let failures = consecutive_failures(run, branch);
+            let visits = run.step_visits.get(&branch).copied().unwrap_or(0);
+            if failures <= branch_step.retries && visits < branch_step.visit_limit() {
+                info!(
+                    "SOP run {run_id}: {reason}; retrying branch (attempt {} of {})",
+                    failures + 1,
+                    branch_step.retries + 1
+                );
+                *run.step_visits.entry(branch).or_insert(0) += 1;
+                let input = last_output(run, |n| n == group.number || step_owns(sop, &group, n));
+                return Ok(branch_action(sop, run, branch_step, input));
+            }
+        }
+
+        run.pending_branches.retain(|&b| b != branch);
+        let outcome = match (group.join, failure) {
+            (SopJoin::All, Some(reason)) => Err(reason),
+            (SopJoin::Any, Some(_)) if run.pending_branches.is_empty() => Err(format!(
+                "Step {}: every parallel branch failed",
+                group.number
+            )),
+            (SopJoin::All, None) if !run.pending_branches.is_empty() => {
+                return Ok(SopRunAction::AwaitBranches {
+                    run_id: run_id.to_string(),
+                    pending: run.pending_branches.clone(),
+                });
+            }
+            (SopJoin::Any, Some(_)) => {
+                return Ok(SopRunAction::AwaitBranches {
+                    run_id: run_id.to_string(),
+                    pending: run.pending_branches.clone(),
+                });
+            }
+            (_, None) => Ok(()),
+        };
+        run.pending_branches.clear();
+
+        // Latest successful result of each branch since the group started
+        let mut outputs = serde_json::Map::new();
+        for result in run.step_results.iter().rev() {
+            if !step_owns(sop, &group, result.step_number) {
+                break;
+            }
+            if result.status == SopStepStatus::Failed {
+                continue;
+            }
+            if let Some(step) = sop.step(result.step_number) {
+                outputs
+                    .entry(step.key())
+                    .or_insert_with(|| parse_output(&result.output));
+            }
+        }
+        let output = serde_json::Value::Object(outputs);
+
+        let now = now_iso8601();
+        run.step_results.push(SopStepResult {
+            step_number: group.number,
+            status: if outcome.is_ok() {
+                SopStepStatus::Completed
+            } else {
+                SopStepStatus::Failed
+            },
+            output: output.to_string(),
+            started_at: now.clone(),
+            completed_at: Some(now),
+        });
+
+        self.leave_step(sop, run_id, group.number, output, outcome.err())
+    }
+
+    /// Finish a run successfully, crediting deterministic savings.
+    fn complete_run(&mut self, sop: &Sop, run_id: &str) -> SopRunAction {
+        if sop.execution_mode == SopExecutionMode::Deterministic
+            && let Some(run) = self.active_runs.get(run_id)
+        {
+            info!(
+                "Deterministic SOP run {run_id} completed ({} LLM calls saved)",
+                run.llm_calls_saved
+            );
+            self.deterministic_savings.total_llm_calls_saved += run.llm_calls_saved;
+            self.deterministic_savings.total_runs += 1;
+        } else {
+            info!("SOP run {run_id} completed successfully");
+        }
+        self.finish_run(run_id, SopRunStatus::Completed, None)
+    }
+
     // ── Approval timeout ──────────────────────────────────────────
 
     /// Check all WaitingApproval runs for timeout. For Critical/High-priority SOPs,
@@ -604,7 +976,7 @@ impl SopEngine {
                 info!(
                     "SOP run {run_id}: approval timeout — auto-approving (critical/high priority)"
                 );
-                match self.approve_step(&run_id) {
+                match self.approve_step_by(&run_id, "timeout") {
                     Ok(action) => actions.push(action),
                     Err(e) => warn!("SOP run {run_id}: auto-approve failed: {e}"),
                 }
@@ -616,6 +988,28 @@ impl SopEngine {
         actions
     }
 
+    // ── Journal ───────────────────────────────────────────────────
+
+    /// Write the current state of a run to the journal, if one is open.
+    /// Journal failures are logged, never surfaced: the run itself goes on.
+    fn journal(&self, run_id: &str) {
+        let (Some(dir), Some(run)) = (self.journal_dir.as_deref(), self.get_run(run_id)) else {
+            return;
+        };
+        if let Err(e) = store::record_run(dir, run, self.config.max_finished_runs) {
+            warn!("SOP run {run_id}: failed to journal run: {e:#}");
+        }
+    }
+
+    fn journal_approval(&self, run_id: &str, step_number: u32, approved_by: &str) {
+        let Some(dir) = self.journal_dir.as_deref() else {
+            return;
+        };
+        if let Err(e) = store::record_approval(dir, run_id, step_number, approved_by) {
+            warn!("SOP run {run_id}: failed to journal approval: {e:#}");
+        }
+    }
+
     // ── Test helpers ──────────────────────────────────────────────
 
     /// Replace loaded SOPs (for testing from other modules).
@@ -670,7 +1064,7 @@ impl SopEngine {
 // ── Trigger matching ────────────────────────────────────────────
 
 /// Check whether a single trigger definition matches an incoming event.
-fn trigger_matches(trigger: &SopTrigger, event: &SopEvent) -> bool {
+pub(crate) fn trigger_matches(trigger: &SopTrigger, event: &SopEvent) -> bool {
     match (trigger, event.source) {
         (SopTrigger::Mqtt { topic, condition }, SopTriggerSource::Mqtt) => {
             let topic_match = event
@@ -687,8 +1081,15 @@ fn trigger_matches(trigger: &SopTrigger, event: &SopEvent) -> bool {
             }
         }
 
-        (SopTrigger::Webhook { path }, SopTriggerSource::Webhook) => {
-            event.topic.as_deref().is_some_and(|t| t == path)
+        (SopTrigger::Webhook { path, condition }, SopTriggerSource::Webhook) => {
+            if event.topic.as_deref() != Some(path.as_str()) {
+                return false;
+            }
+            // Evaluate condition against the request body (None condition = unconditional)
+            match condition {
+                Some(cond) => evaluate_condition(cond, event.payload.as_deref()),
+                None => true,
+            }
         }
 
         (
@@ -799,6 +1200,65 @@ fn resolve_step_action(sop: &Sop, step: &SopStep, run_id: String, context: Strin
     }
 }
 
+/// The action that runs one branch of a parallel step.
+fn branch_action(
+    sop: &Sop,
+    run: &SopRun,
+    step: &SopStep,
+    input: serde_json::Value,
+) -> SopRunAction {
+    if sop.execution_mode == SopExecutionMode::Deterministic {
+        SopRunAction::DeterministicStep {
+            run_id: run.run_id.clone(),
+            step: step.clone(),
+            input,
+        }
+    } else {
+        SopRunAction::ExecuteStep {
+            run_id: run.run_id.clone(),
+            step: step.clone(),
+            context: format_step_context(sop, run, step),
+        }
+    }
+}
+
+/// Whether `number` is one of `group`'s parallel branches.
+fn step_owns(sop: &Sop, group: &SopStep, number: u32) -> bool {
+    group
+        .branches
+        .iter()
+        .any(|b| sop.resolve_target(b) == Some(number))
+}
+
+/// Parse a recorded step output as JSON, falling back to a string value.
+fn parse_output(output: &str) -> serde_json::Value {
+    serde_json::from_str(output).unwrap_or_else(|_| serde_json::Value::String(output.to_string()))
+}
+
+/// How many times in a row step `number` has failed, counting its latest
+/// result. Retries are charged against this, not against the visit count,
+/// so loops through a step do not use up its retries.
+fn consecutive_failures(run: &SopRun, number: u32) -> u32 {
+    let failures = run
+        .step_results
+        .iter()
+        .rev()
+        .filter(|r| r.step_number == number)
+        .take_while(|r| r.status == SopStepStatus::Failed)
+        .count();
+    u32::try_from(failures).unwrap_or(u32::MAX)
+}
+
+/// Output of the most recent successful result not excluded by `skip`,
+/// used as the input when a step is re-entered.
+fn last_output(run: &SopRun, skip: impl Fn(u32) -> bool) -> serde_json::Value {
+    run.step_results
+        .iter()
+        .rev()
+        .find(|r| r.status != SopStepStatus::Failed && !skip(r.step_number))
+        .map_or(serde_json::Value::Null, |r| parse_output(&r.output))
+}
+
 // ── Step context formatting ─────────────────────────────────────
 
 /// Build the structured context message that gets injected into the agent.
@@ -830,6 +1290,14 @@ fn format_step_context(sop: &Sop, run: &SopRun, step: &SopStep) -> String {
 
     let _ = write!(ctx, "\nCurrent step: **{}**\n{}\n", step.title, step.body);
 
+    if sop.is_branch(step.number) {
+        let _ = writeln!(
+            ctx,
+            "\nThis is a parallel branch: report its result for step {}.",
+            step.number
+        );
+    }
+
     if !step.suggested_tools.is_empty() {
         let _ = write!(
             ctx,
@@ -925,7 +1393,7 @@ fn parse_iso8601_secs(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sop::types::SopExecutionMode;
+    use crate::sop::types::{SopExecutionMode, StepTransition};
 
     fn manual_event() -> SopEvent {
         SopEvent {
@@ -962,6 +1430,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
                 SopStep {
                     number: 2,
@@ -971,6 +1446,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
             ],
             cooldown_secs: 0,
@@ -993,6 +1475,8 @@ mod tests {
             | SopRunAction::WaitApproval { run_id, .. }
             | SopRunAction::DeterministicStep { run_id, .. }
             | SopRunAction::CheckpointWait { run_id, .. }
+            | SopRunAction::Parallel { run_id, .. }
+            | SopRunAction::AwaitBranches { run_id, .. }
             | SopRunAction::Completed { run_id, .. }
             | SopRunAction::Failed { run_id, .. } => run_id,
         }
@@ -1120,6 +1604,7 @@ mod tests {
         let sop = Sop {
             triggers: vec![SopTrigger::Webhook {
                 path: "/webhook".into(),
+                condition: None,
             }],
             ..test_sop("webhook-sop", SopExecutionMode::Auto, SopPriority::Normal)
         };
@@ -1140,6 +1625,7 @@ mod tests {
         let sop = Sop {
             triggers: vec![SopTrigger::Webhook {
                 path: "/sop/deploy".into(),
+                condition: None,
             }],
             ..test_sop("deploy-sop", SopExecutionMode::Auto, SopPriority::Normal)
         };
@@ -1164,6 +1650,37 @@ mod tests {
         assert_eq!(engine.match_trigger(&event).len(), 1);
     }
 
+    #[test]
+    fn webhook_trigger_evaluates_condition_against_body() {
+        let sop = Sop {
+            triggers: vec![SopTrigger::Webhook {
+                path: "/sop/deploy".into(),
+                condition: Some("$.ref == \"refs/heads/main\"".into()),
+            }],
+            ..test_sop("deploy-sop", SopExecutionMode::Auto, SopPriority::Normal)
+        };
+        let engine = engine_with_sops(vec![sop]);
+
+        let event = |body: &str| SopEvent {
+            source: SopTriggerSource::Webhook,
+            topic: Some("/sop/deploy".into()),
+            payload: Some(body.into()),
+            timestamp: now_iso8601(),
+        };
+        assert_eq!(
+            engine
+                .match_trigger(&event(r#"{"ref":"refs/heads/main"}"#))
+                .len(),
+            1
+        );
+        assert!(
+            engine
+                .match_trigger(&event(r#"{"ref":"refs/heads/dev"}"#))
+                .is_empty()
+        );
+        assert!(engine.match_trigger(&event("not json")).is_empty());
+    }
+
     // ── Cron trigger matching ─────────────────────────
 
     #[test]
@@ -1650,6 +2167,8 @@ mod tests {
             step_results: Vec::new(),
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         let ctx = format_step_context(&sop, &run, &sop.steps[0]);
         assert!(ctx.contains("pump-shutdown"));
@@ -1791,6 +2310,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn journaled_run_resumes_waiting_for_approval() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sop = test_sop("s1", SopExecutionMode::Supervised, SopPriority::Normal);
+
+        let mut before = engine_with_sops(vec![sop.clone()]);
+        before.open_journal(tmp.path()).unwrap();
+        let action = before.start_run("s1", manual_event()).unwrap();
+        assert!(matches!(action, SopRunAction::WaitApproval { .. }));
+        let run_id = extract_run_id(&action).to_string();
+        drop(before);
+
+        let mut after = engine_with_sops(vec![sop]);
+        assert!(after.open_journal(tmp.path()).unwrap().is_empty());
+        let run = after.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::WaitingApproval);
+        assert_eq!(run.trigger_event.source, SopTriggerSource::Manual);
+
+        let action = after.approve(&run_id).unwrap();
+        assert!(matches!(action, SopRunAction::ExecuteStep { .. }));
+        let approvals = store::list_approvals(tmp.path(), &run_id).unwrap();
+        assert_eq!(approvals.len(), 1);
+        assert_eq!(approvals[0].approved_by, "operator");
+    }
+
+    #[test]
+    fn open_journal_auto_approves_lapsed_critical_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = SopConfig {
+            approval_timeout_secs: 1,
+            ..SopConfig::default()
+        };
+        let sop = test_sop("s1", SopExecutionMode::Supervised, SopPriority::Critical);
+
+        let mut before = SopEngine::new(config.clone());
+        before.set_sops_for_test(vec![sop.clone()]);
+        before.open_journal(tmp.path()).unwrap();
+        let action = before.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        // The daemon was down while the approval timeout lapsed
+        before.active_runs.get_mut(&run_id).unwrap().waiting_since =
+            Some("2020-01-01T00:00:00Z".into());
+        before.journal(&run_id);
+        drop(before);
+
+        let mut after = SopEngine::new(config);
+        after.set_sops_for_test(vec![sop]);
+        let actions = after.open_journal(tmp.path()).unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], SopRunAction::ExecuteStep { .. }));
+        assert_eq!(
+            after.get_run(&run_id).unwrap().status,
+            SopRunStatus::Running
+        );
+        let approvals = store::list_approvals(tmp.path(), &run_id).unwrap();
+        assert_eq!(approvals[0].approved_by, "timeout");
+    }
+
+    #[test]
+    fn open_journal_leaves_orphaned_runs_behind() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut before = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Supervised,
+            SopPriority::Normal,
+        )]);
+        before.open_journal(tmp.path()).unwrap();
+        before.start_run("s1", manual_event()).unwrap();
+        drop(before);
+
+        let mut after = engine_with_sops(Vec::new());
+        after.open_journal(tmp.path()).unwrap();
+        assert!(after.active_runs().is_empty());
+        assert_eq!(store::load_active_runs(tmp.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn finished_runs_are_journaled_for_cooldowns() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut sop = test_sop("s1", SopExecutionMode::Auto, SopPriority::Normal);
+        sop.cooldown_secs = 3600;
+
+        let mut before = engine_with_sops(vec![sop.clone()]);
+        before.open_journal(tmp.path()).unwrap();
+        let action = before.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        before.cancel_run(&run_id).unwrap();
+        drop(before);
+
+        let mut after = engine_with_sops(vec![sop]);
+        after.open_journal(tmp.path()).unwrap();
+        assert_eq!(
+            after.get_run(&run_id).unwrap().status,
+            SopRunStatus::Cancelled
+        );
+        assert!(!after.can_start("s1"));
+    }
+
     #[test]
     fn timeout_zero_disables_check() {
         let mut engine = SopEngine::new(SopConfig {
@@ -1939,6 +2556,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::Execute,
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
                 SopStep {
                     number: 2,
@@ -1948,6 +2572,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::Checkpoint,
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
                 SopStep {
                     number: 3,
@@ -1957,6 +2588,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::Execute,
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
             ],
             cooldown_secs: 0,
@@ -2025,6 +2663,26 @@ mod tests {
         assert!(run.waiting_since.is_some());
     }
 
+    #[test]
+    fn approve_resumes_deterministic_checkpoint() {
+        let mut engine = engine_with_sops(vec![deterministic_sop("det-sop")]);
+        let action = engine.start_run("det-sop", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        engine
+            .advance_deterministic_step(&run_id, serde_json::json!({"ok": true}))
+            .unwrap();
+
+        let action = engine.approve(&run_id).unwrap();
+        assert!(
+            matches!(action, SopRunAction::DeterministicStep { ref step, ref input, .. }
+                if step.number == 3 && *input == serde_json::json!({"ok": true})),
+            "approval should resume after the checkpoint with the last output"
+        );
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::Running);
+        assert!(engine.approve(&run_id).is_err());
+    }
+
     #[test]
     fn deterministic_completion_tracks_savings() {
         let mut sop = deterministic_sop("det-sop");
@@ -2038,6 +2696,13 @@ mod tests {
                 requires_confirmation: false,
                 kind: SopStepKind::Execute,
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             },
             SopStep {
                 number: 2,
@@ -2047,6 +2712,13 @@ mod tests {
                 requires_confirmation: false,
                 kind: SopStepKind::Execute,
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             },
         ];
         let mut engine = engine_with_sops(vec![sop]);
@@ -2136,4 +2808,327 @@ type = "manual"
         );
         assert_eq!(engine.sops()[0].name, "test-sop");
     }
+
+    // ── Branching, parallel steps and retries ─────────────
+
+    fn flow_step(number: u32, id: &str) -> SopStep {
+        SopStep {
+            number,
+            title: format!("Step {id}"),
+            body: format!("Do {id}"),
+            suggested_tools: vec![],
+            requires_confirmation: false,
+            kind: SopStepKind::Execute,
+            schema: None,
+            id: Some(id.into()),
+            next: Vec::new(),
+            on_failure: None,
+            branches: Vec::new(),
+            join: SopJoin::default(),
+            retries: 0,
+            max_visits: None,
+        }
+    }
+
+    fn flow_sop(mode: SopExecutionMode, steps: Vec<SopStep>) -> Sop {
+        let mut sop = test_sop("flow", mode, SopPriority::Normal);
+        sop.steps = steps;
+        sop
+    }
+
+    fn step_result(number: u32, status: SopStepStatus, output: &str) -> SopStepResult {
+        SopStepResult {
+            step_number: number,
+            status,
+            output: output.into(),
+            started_at: now_iso8601(),
+            completed_at: Some(now_iso8601()),
+        }
+    }
+
+    fn action_step(action: &SopRunAction) -> u32 {
+        match action {
+            SopRunAction::ExecuteStep { step, .. }
+            | SopRunAction::WaitApproval { step, .. }
+            | SopRunAction::DeterministicStep { step, .. }
+            | SopRunAction::CheckpointWait { step, .. }
+            | SopRunAction::Parallel { step, .. } => step.number,
+            other => panic!("expected a step action, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn next_transition_follows_matching_condition() {
+        let mut triage = flow_step(1, "triage");
+        triage.next = vec![
+            StepTransition {
+                when: Some(r#"$.severity == "high""#.into()),
+                goto: "page".into(),
+            },
+            StepTransition {
+                when: None,
+                goto: "end".into(),
+            },
+        ];
+        let sop = flow_sop(
+            SopExecutionMode::Auto,
+            vec![triage, flow_step(2, "ticket"), flow_step(3, "page")],
+        );
+
+        let mut engine = engine_with_sops(vec![sop.clone()]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(1, SopStepStatus::Completed, r#"{"severity": "high"}"#),
+            )
+            .unwrap();
+        assert_eq!(action_step(&action), 3);
+
+        let mut engine = engine_with_sops(vec![sop]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(1, SopStepStatus::Completed, r#"{"severity": "low"}"#),
+            )
+            .unwrap();
+        assert!(matches!(action, SopRunAction::Completed { .. }));
+    }
+
+    #[test]
+    fn failed_step_retries_then_takes_on_failure() {
+        let mut flaky = flow_step(1, "flaky");
+        flaky.retries = 1;
+        flaky.on_failure = Some("rollback".into());
+        flaky.next = vec![StepTransition {
+            when: None,
+            goto: "end".into(),
+        }];
+        let mut engine = engine_with_sops(vec![flow_sop(
+            SopExecutionMode::Auto,
+            vec![flaky, flow_step(2, "rollback")],
+        )]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Failed, "timeout"))
+            .unwrap();
+        assert_eq!(action_step(&action), 1, "first failure is retried");
+
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Failed, "timeout"))
+            .unwrap();
+        assert_eq!(
+            action_step(&action),
+            2,
+            "exhausted retries go to on_failure"
+        );
+        assert_eq!(engine.get_run(&run_id).unwrap().step_visits[&1], 2);
+    }
+
+    #[test]
+    fn loops_are_bounded_by_max_visits() {
+        let mut poll = flow_step(1, "poll");
+        poll.max_visits = Some(2);
+        poll.next = vec![StepTransition {
+            when: Some(r#"$.ready == false"#.into()),
+            goto: "poll".into(),
+        }];
+        let mut engine = engine_with_sops(vec![flow_sop(SopExecutionMode::Auto, vec![poll])]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let not_ready = r#"{"ready": false}"#;
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, not_ready))
+            .unwrap();
+        assert_eq!(action_step(&action), 1);
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, not_ready))
+            .unwrap();
+        assert!(
+            matches!(action, SopRunAction::Failed { ref reason, .. } if reason.contains("more than 2")),
+            "got {action:?}"
+        );
+    }
+
+    #[test]
+    fn loops_do_not_use_up_retries() {
+        let mut poll = flow_step(1, "poll");
+        poll.retries = 1;
+        poll.next = vec![StepTransition {
+            when: Some(r#"$.ready == false"#.into()),
+            goto: "poll".into(),
+        }];
+        let mut engine = engine_with_sops(vec![flow_sop(SopExecutionMode::Auto, vec![poll])]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let not_ready = r#"{"ready": false}"#;
+        for _ in 0..3 {
+            let action = engine
+                .advance_step(&run_id, step_result(1, SopStepStatus::Completed, not_ready))
+                .unwrap();
+            assert_eq!(action_step(&action), 1);
+        }
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Failed, "timeout"))
+            .unwrap();
+        assert_eq!(
+            action_step(&action),
+            1,
+            "a looping step still gets its retry"
+        );
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Failed, "timeout"))
+            .unwrap();
+        assert!(
+            matches!(action, SopRunAction::Failed { .. }),
+            "got {action:?}"
+        );
+    }
+
+    #[test]
+    fn output_schema_mismatch_fails_step() {
+        let mut check = flow_step(1, "check");
+        check.schema = Some(crate::sop::types::StepSchema {
+            input: None,
+            output: Some(serde_json::json!({
+                "type": "object",
+                "required": ["status"],
+            })),
+        });
+        let mut engine = engine_with_sops(vec![flow_sop(SopExecutionMode::Auto, vec![check])]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "{}"))
+            .unwrap();
+        assert!(
+            matches!(action, SopRunAction::Failed { ref reason, .. } if reason.contains("status")),
+            "got {action:?}"
+        );
+    }
+
+    fn parallel_steps(join: SopJoin) -> Vec<SopStep> {
+        let mut fan = flow_step(1, "fan");
+        fan.kind = SopStepKind::Parallel;
+        fan.branches = vec!["a".into(), "b".into()];
+        fan.join = join;
+        vec![
+            fan,
+            flow_step(2, "a"),
+            flow_step(3, "b"),
+            flow_step(4, "after"),
+        ]
+    }
+
+    #[test]
+    fn parallel_all_join_waits_for_every_branch() {
+        let mut engine = engine_with_sops(vec![flow_sop(
+            SopExecutionMode::Auto,
+            parallel_steps(SopJoin::All),
+        )]);
+        let action = engine.start_run("flow", manual_event()).unwrap();
+        let SopRunAction::Parallel {
+            ref run_id,
+            ref branches,
+            ..
+        } = action
+        else {
+            panic!("expected Parallel, got {action:?}");
+        };
+        let run_id = run_id.clone();
+        let started: Vec<u32> = branches.iter().map(action_step).collect();
+        assert_eq!(started, vec![2, 3]);
+
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(3, SopStepStatus::Completed, r#""b done""#),
+            )
+            .unwrap();
+        assert!(
+            matches!(action, SopRunAction::AwaitBranches { ref pending, .. } if pending == &[2])
+        );
+
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(2, SopStepStatus::Completed, r#"{"n": 1}"#),
+            )
+            .unwrap();
+        assert_eq!(action_step(&action), 4, "fall-through skips branch steps");
+
+        let run = engine.get_run(&run_id).unwrap();
+        let joined = run
+            .step_results
+            .iter()
+            .find(|r| r.step_number == 1)
+            .unwrap();
+        let joined: serde_json::Value = serde_json::from_str(&joined.output).unwrap();
+        assert_eq!(joined, serde_json::json!({"a": {"n": 1}, "b": "b done"}));
+    }
+
+    #[test]
+    fn parallel_all_join_fails_on_branch_failure() {
+        let mut engine = engine_with_sops(vec![flow_sop(
+            SopExecutionMode::Auto,
+            parallel_steps(SopJoin::All),
+        )]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(2, SopStepStatus::Failed, "boom"))
+            .unwrap();
+        assert!(matches!(action, SopRunAction::Failed { .. }));
+    }
+
+    #[test]
+    fn parallel_any_join_completes_on_first_success() {
+        let mut engine = engine_with_sops(vec![flow_sop(
+            SopExecutionMode::Auto,
+            parallel_steps(SopJoin::Any),
+        )]);
+        let run_id = extract_run_id(&engine.start_run("flow", manual_event()).unwrap()).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(2, SopStepStatus::Failed, "boom"))
+            .unwrap();
+        assert!(matches!(action, SopRunAction::AwaitBranches { .. }));
+        let action = engine
+            .advance_step(&run_id, step_result(3, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        assert_eq!(action_step(&action), 4);
+        assert!(engine.get_run(&run_id).unwrap().pending_branches.is_empty());
+    }
+
+    #[test]
+    fn deterministic_parallel_pipes_input_to_branches() {
+        let mut engine = engine_with_sops(vec![flow_sop(
+            SopExecutionMode::Deterministic,
+            parallel_steps(SopJoin::All),
+        )]);
+        let action = engine.start_run("flow", manual_event()).unwrap();
+        let SopRunAction::Parallel { ref branches, .. } = action else {
+            panic!("expected Parallel, got {action:?}");
+        };
+        assert!(
+            branches
+                .iter()
+                .all(|b| matches!(b, SopRunAction::DeterministicStep { .. }))
+        );
+        let run_id = extract_run_id(&action).to_string();
+
+        engine
+            .advance_deterministic_step(&run_id, serde_json::json!(1))
+            .unwrap();
+        let action = engine
+            .advance_deterministic_step(&run_id, serde_json::json!(2))
+            .unwrap();
+        let SopRunAction::DeterministicStep { step, input, .. } = action else {
+            panic!("expected DeterministicStep");
+        };
+        assert_eq!(step.number, 4);
+        assert_eq!(input, serde_json::json!({"a": 1, "b": 2}));
+    }
 }
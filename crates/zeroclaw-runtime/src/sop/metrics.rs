@@ -1,8 +1,9 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::RwLock;
 use std::time::Instant;
 
 use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
 use serde_json::json;
 use tracing::warn;
 
@@ -74,6 +75,36 @@ struct CollectorState {
     pending_timeout_approvals: HashMap<String, (Instant, u64)>,
 }
 
+// ── Snapshots ──────────────────────────────────────────────────
+
+/// All-time counters for one SOP (or the global aggregate).
+#[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct CounterSnapshot {
+    pub runs_completed: u64,
+    pub runs_failed: u64,
+    pub runs_cancelled: u64,
+    pub steps_executed: u64,
+    pub steps_defined: u64,
+    pub steps_failed: u64,
+    pub steps_skipped: u64,
+    pub human_approvals: u64,
+    pub timeout_auto_approvals: u64,
+    /// Terminal runs currently held for windowed metrics.
+    pub recent_runs_depth: usize,
+}
+
+/// Point-in-time view of the collector returned by
+/// [`SopMetricsCollector::snapshot`].
+#[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct MetricsSnapshot {
+    pub global: CounterSnapshot,
+    pub per_sop: BTreeMap<String, CounterSnapshot>,
+    pub pending_approvals: usize,
+    pub pending_timeout_approvals: usize,
+}
+
 // ── SopMetricsCollector ────────────────────────────────────────
 
 /// Thread-safe SOP metrics aggregator.
@@ -387,23 +418,22 @@ impl SopMetricsCollector {
     }
 
     /// Return a full snapshot of collector state for health/debug purposes.
-    pub fn snapshot(&self) -> serde_json::Value {
+    pub fn snapshot(&self) -> MetricsSnapshot {
         let Ok(state) = self.inner.read() else {
-            return json!({"error": "lock poisoned"});
+            warn!("SOP metrics collector lock poisoned in snapshot");
+            return MetricsSnapshot::default();
         };
 
-        let per_sop: serde_json::Map<String, serde_json::Value> = state
-            .per_sop
-            .iter()
-            .map(|(name, c)| (name.clone(), counters_to_json(c)))
-            .collect();
-
-        json!({
-            "global": counters_to_json(&state.global),
-            "per_sop": per_sop,
-            "pending_approvals": state.pending_approvals.len(),
-            "pending_timeout_approvals": state.pending_timeout_approvals.len(),
-        })
+        MetricsSnapshot {
+            global: CounterSnapshot::from(&state.global),
+            per_sop: state
+                .per_sop
+                .iter()
+                .map(|(name, c)| (name.clone(), CounterSnapshot::from(c)))
+                .collect(),
+            pending_approvals: state.pending_approvals.len(),
+            pending_timeout_approvals: state.pending_timeout_approvals.len(),
+        }
     }
 }
 
@@ -570,20 +600,22 @@ fn resolve_from_counters(c: &MetricCounters, metric: &str) -> Option<serde_json:
     }
 }
 
-fn counters_to_json(sop: &SopCounters) -> serde_json::Value {
-    let c = &sop.counters;
-    json!({
-        "runs_completed": c.runs_completed,
-        "runs_failed": c.runs_failed,
-        "runs_cancelled": c.runs_cancelled,
-        "steps_executed": c.steps_executed,
-        "steps_defined": c.steps_defined,
-        "steps_failed": c.steps_failed,
-        "steps_skipped": c.steps_skipped,
-        "human_approvals": c.human_approvals,
-        "timeout_auto_approvals": c.timeout_auto_approvals,
-        "recent_runs_depth": sop.recent_runs.len(),
-    })
+impl From<&SopCounters> for CounterSnapshot {
+    fn from(sop: &SopCounters) -> Self {
+        let c = &sop.counters;
+        Self {
+            runs_completed: c.runs_completed,
+            runs_failed: c.runs_failed,
+            runs_cancelled: c.runs_cancelled,
+            steps_executed: c.steps_executed,
+            steps_defined: c.steps_defined,
+            steps_failed: c.steps_failed,
+            steps_skipped: c.steps_skipped,
+            human_approvals: c.human_approvals,
+            timeout_auto_approvals: c.timeout_auto_approvals,
+            recent_runs_depth: sop.recent_runs.len(),
+        }
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────
@@ -624,6 +656,8 @@ mod tests {
             step_results,
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         }
     }
 
@@ -901,9 +935,8 @@ mod tests {
         c.record_run_complete(&run);
 
         let snap = c.snapshot();
-        let global = &snap["global"];
-        assert_eq!(global["human_approvals"], json!(1u64));
-        assert_eq!(global["runs_completed"], json!(1u64));
+        assert_eq!(snap.global.human_approvals, 1);
+        assert_eq!(snap.global.runs_completed, 1);
 
         let hic = c
             .get_metric_value("sop.human_intervention_count_7d")
@@ -952,11 +985,9 @@ mod tests {
         c.record_run_complete(&run);
 
         let snap = c.snapshot();
-        assert!(snap["global"].is_object());
-        assert!(snap["per_sop"].is_object());
-        assert_eq!(snap["global"]["runs_completed"], json!(1u64));
-        assert_eq!(snap["global"]["recent_runs_depth"], json!(1));
-        assert!(snap["per_sop"]["test-sop"].is_object());
+        assert_eq!(snap.global.runs_completed, 1);
+        assert_eq!(snap.global.recent_runs_depth, 1);
+        assert!(snap.per_sop.contains_key("test-sop"));
     }
 
     #[test]
@@ -1045,7 +1076,7 @@ mod tests {
         );
         // Ring buffer capped at MAX_RECENT_RUNS
         let snap = c.snapshot();
-        assert_eq!(snap["global"]["recent_runs_depth"], json!(MAX_RECENT_RUNS));
+        assert_eq!(snap.global.recent_runs_depth, MAX_RECENT_RUNS);
         // Windowed returns up to cap (all recent, all within 7d)
         let w = c
             .get_metric_value("sop.runs_completed_7d")
@@ -1187,6 +1218,8 @@ mod tests {
             step_results: vec![],
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         audit.log_run_start(&run).await.unwrap();
 
@@ -1288,6 +1321,8 @@ mod tests {
             step_results: vec![],
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         audit.log_run_start(&running_run).await.unwrap();
         audit.log_approval(&running_run, 1).await.unwrap();
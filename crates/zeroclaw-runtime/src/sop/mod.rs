@@ -3,23 +3,28 @@ pub mod condition;
 pub mod dispatch;
 pub mod engine;
 pub mod metrics;
+pub mod shared;
+pub mod simulate;
+pub mod store;
 pub mod types;
 
 pub use audit::SopAuditLogger;
 pub use engine::SopEngine;
 pub use metrics::SopMetricsCollector;
+pub use shared::shared_engine;
 #[allow(unused_imports)]
 pub use types::{
-    DeterministicRunState, DeterministicSavings, Sop, SopEvent, SopExecutionMode, SopPriority,
-    SopRun, SopRunAction, SopRunStatus, SopStep, SopStepKind, SopStepResult, SopStepStatus,
-    SopTrigger, SopTriggerSource, StepSchema,
+    DEFAULT_MAX_VISITS, DeterministicRunState, DeterministicSavings, END_STEP, Sop, SopEvent,
+    SopExecutionMode, SopJoin, SopPriority, SopRun, SopRunAction, SopRunStatus, SopStep,
+    SopStepKind, SopStepResult, SopStepStatus, SopTrigger, SopTriggerSource, StepSchema,
+    StepTransition,
 };
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use std::path::{Path, PathBuf};
 use tracing::warn;
 
-use types::{SopManifest, SopMeta};
+use types::{SopManifest, SopMeta, SopStepManifest, StepRef};
 
 /// Parse an execution mode string into `SopExecutionMode`, falling back to
 /// `Supervised` for unknown values.
@@ -110,12 +115,13 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
     let manifest: SopManifest = toml::from_str(&toml_content)?;
 
     let md_path = sop_dir.join("SOP.md");
-    let steps = if md_path.exists() {
+    let mut steps = if md_path.exists() {
         let md_content = std::fs::read_to_string(&md_path)?;
         parse_steps(&md_content)
     } else {
         Vec::new()
     };
+    apply_step_manifests(&mut steps, manifest.steps)?;
 
     let SopMeta {
         name,
@@ -135,7 +141,7 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         execution_mode.unwrap_or(default_execution_mode)
     };
 
-    Ok(Sop {
+    let sop = Sop {
         name,
         description,
         version,
@@ -147,7 +153,163 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         max_concurrent,
         location: Some(sop_dir.to_path_buf()),
         deterministic,
-    })
+    };
+    validate_flow(&sop)?;
+    validate_conditions(&sop)?;
+    Ok(sop)
+}
+
+/// Layer SOP.toml `[[steps]]` entries onto the steps parsed from SOP.md.
+fn apply_step_manifests(steps: &mut [SopStep], manifests: Vec<SopStepManifest>) -> Result<()> {
+    for manifest in manifests {
+        let step = steps
+            .iter_mut()
+            .find(|s| match manifest.step {
+                StepRef::Number(n) => s.number == n,
+                StepRef::Id(ref id) => s.id.as_deref() == Some(id.as_str()),
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!("[[steps]] entry refers to unknown step '{}'", manifest.step)
+            })?;
+        if manifest.id.is_some() {
+            step.id = manifest.id;
+        }
+        if !manifest.next.is_empty() {
+            step.next = manifest.next;
+        }
+        if manifest.on_failure.is_some() {
+            step.on_failure = manifest.on_failure;
+        }
+        if !manifest.parallel.is_empty() {
+            step.kind = SopStepKind::Parallel;
+            step.branches = manifest.parallel;
+        }
+        if let Some(join) = manifest.join {
+            step.join = join;
+        }
+        if let Some(retries) = manifest.retries {
+            step.retries = retries;
+        }
+        if manifest.max_visits.is_some() {
+            step.max_visits = manifest.max_visits;
+        }
+        if manifest.schema.is_some() {
+            step.schema = manifest.schema;
+        }
+    }
+    Ok(())
+}
+
+/// Check step ids, transition targets and parallel groups. Any problem here
+/// would strand a run mid-way, so it fails the load instead of warning.
+pub fn validate_flow(sop: &Sop) -> Result<()> {
+    let mut ids = std::collections::HashSet::new();
+    for step in &sop.steps {
+        if let Some(ref id) = step.id {
+            if id == END_STEP || id.parse::<u32>().is_ok() {
+                bail!("Step {} id '{id}' is reserved", step.number);
+            }
+            if !ids.insert(id.as_str()) {
+                bail!("Duplicate step id '{id}'");
+            }
+        }
+    }
+
+    let resolve = |step: &SopStep, target: &str| -> Result<u32> {
+        sop.resolve_target(target).ok_or_else(|| {
+            anyhow::anyhow!("Step {} refers to unknown step '{target}'", step.number)
+        })
+    };
+
+    let mut branch_owner = std::collections::HashMap::new();
+    for step in &sop.steps {
+        for transition in &step.next {
+            if transition.goto != END_STEP {
+                resolve(step, &transition.goto)?;
+            }
+        }
+        if let Some(ref target) = step.on_failure {
+            resolve(step, target)?;
+        }
+        if step.max_visits == Some(0) {
+            bail!("Step {} has max_visits = 0 and can never run", step.number);
+        }
+        if step.kind != SopStepKind::Parallel {
+            if !step.branches.is_empty() {
+                bail!(
+                    "Step {} lists branches but is not a parallel step",
+                    step.number
+                );
+            }
+            continue;
+        }
+        if step.branches.is_empty() {
+            bail!("Parallel step {} has no branches", step.number);
+        }
+        for branch in &step.branches {
+            let number = resolve(step, branch)?;
+            if number == step.number {
+                bail!("Parallel step {} lists itself as a branch", step.number);
+            }
+            let Some(target) = sop.step(number) else {
+                continue;
+            };
+            if target.kind != SopStepKind::Execute {
+                bail!(
+                    "Branch '{branch}' of step {} must be a plain step, not {}",
+                    step.number,
+                    target.kind
+                );
+            }
+            if !target.next.is_empty() || target.on_failure.is_some() {
+                bail!(
+                    "Branch '{branch}' of step {} cannot declare its own transitions",
+                    step.number
+                );
+            }
+            if let Some(owner) = branch_owner.insert(number, step.number)
+                && owner != step.number
+            {
+                bail!(
+                    "Step {number} is a branch of both step {owner} and step {}",
+                    step.number
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse every trigger and transition condition so syntax errors surface
+/// when the SOP loads instead of silently never matching. The parsed ASTs
+/// stay cached for evaluation.
+pub fn validate_conditions(sop: &Sop) -> Result<()> {
+    for trigger in &sop.triggers {
+        if let SopTrigger::Mqtt {
+            condition: Some(cond),
+            ..
+        }
+        | SopTrigger::Webhook {
+            condition: Some(cond),
+            ..
+        }
+        | SopTrigger::Peripheral {
+            condition: Some(cond),
+            ..
+        } = trigger
+            && !cond.trim().is_empty()
+        {
+            condition::Condition::cached(cond)
+                .map_err(|e| anyhow::anyhow!("Trigger {trigger} condition '{cond}': {e}"))?;
+        }
+    }
+    for step in &sop.steps {
+        for when in step.next.iter().filter_map(|t| t.when.as_deref()) {
+            condition::Condition::cached(when)
+                .map_err(|e| anyhow::anyhow!("Step {} condition '{when}': {e}", step.number))?;
+        }
+    }
+    Ok(())
 }
 
 // ── Markdown step parser ────────────────────────────────────────
@@ -156,16 +318,13 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
 ///
 /// Expects a `## Steps` heading followed by numbered items (`1.`, `2.`, …).
 /// Each item's first bold text (`**...**`) is the step title; the rest is body.
-/// Sub-bullets `- tools:` and `- requires_confirmation: true` are parsed.
+/// Sub-bullets `- tools:`, `- requires_confirmation: true`, `- kind:`, `- id:`,
+/// `- next: <target> [if <condition>]`, `- on_failure:`, `- parallel:`,
+/// `- join:`, `- retries:` and `- max_visits:` are parsed.
 pub fn parse_steps(md: &str) -> Vec<SopStep> {
     let mut steps = Vec::new();
     let mut in_steps_section = false;
-    let mut current_number: Option<u32> = None;
-    let mut current_title = String::new();
-    let mut current_body = String::new();
-    let mut current_tools: Vec<String> = Vec::new();
-    let mut current_requires_confirmation = false;
-    let mut current_kind = SopStepKind::Execute;
+    let mut current: Option<SopStep> = None;
 
     for line in md.lines() {
         let trimmed = line.trim();
@@ -179,16 +338,7 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
             }
             // Any other ## heading ends the steps section
             if in_steps_section {
-                // Flush pending step
-                flush_step(
-                    &mut steps,
-                    &mut current_number,
-                    &mut current_title,
-                    &mut current_body,
-                    &mut current_tools,
-                    &mut current_requires_confirmation,
-                    &mut current_kind,
-                );
+                flush_step(&mut steps, &mut current);
                 in_steps_section = false;
             }
             continue;
@@ -201,112 +351,127 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
         // Check for numbered item: `1.`, `2.`, etc.
         if let Some(rest) = parse_numbered_item(trimmed) {
             // Flush previous step
-            flush_step(
-                &mut steps,
-                &mut current_number,
-                &mut current_title,
-                &mut current_body,
-                &mut current_tools,
-                &mut current_requires_confirmation,
-                &mut current_kind,
-            );
+            flush_step(&mut steps, &mut current);
 
             let step_num = u32::try_from(steps.len())
                 .unwrap_or(u32::MAX)
                 .saturating_add(1);
-            current_number = Some(step_num);
 
             // Extract title from bold text: **title** — body
-            if let Some((title, body)) = extract_bold_title(rest) {
-                current_title = title;
-                current_body = body;
-            } else {
-                current_title = rest.to_string();
-                current_body = String::new();
-            }
-            current_tools = Vec::new();
-            current_requires_confirmation = false;
+            let (title, body) =
+                extract_bold_title(rest).unwrap_or_else(|| (rest.to_string(), String::new()));
+            current = Some(SopStep {
+                number: step_num,
+                title,
+                body,
+                suggested_tools: Vec::new(),
+                requires_confirmation: false,
+                kind: SopStepKind::Execute,
+                schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
+            });
             continue;
         }
 
+        let Some(step) = current.as_mut() else {
+            continue;
+        };
+
         // Sub-bullet parsing (only when inside a step)
-        if current_number.is_some() && trimmed.starts_with("- ") {
-            let bullet = trimmed.trim_start_matches("- ").trim();
+        if let Some(bullet) = trimmed.strip_prefix("- ") {
+            let bullet = bullet.trim();
             if let Some(tools_str) = bullet.strip_prefix("tools:") {
-                current_tools = tools_str
-                    .split(',')
-                    .map(|t| t.trim().to_string())
-                    .filter(|t| !t.is_empty())
-                    .collect();
-            } else if bullet.starts_with("requires_confirmation:") {
-                if let Some(val) = bullet.strip_prefix("requires_confirmation:") {
-                    current_requires_confirmation = val.trim().eq_ignore_ascii_case("true");
-                }
-            } else if bullet.starts_with("kind:") {
-                if let Some(val) = bullet.strip_prefix("kind:") {
-                    let val = val.trim();
-                    if val.eq_ignore_ascii_case("checkpoint") {
-                        current_kind = SopStepKind::Checkpoint;
-                    } else {
-                        current_kind = SopStepKind::Execute;
-                    }
+                step.suggested_tools = split_list(tools_str);
+            } else if let Some(val) = bullet.strip_prefix("requires_confirmation:") {
+                step.requires_confirmation = val.trim().eq_ignore_ascii_case("true");
+            } else if let Some(val) = bullet.strip_prefix("kind:") {
+                let val = val.trim();
+                if val.eq_ignore_ascii_case("checkpoint") {
+                    step.kind = SopStepKind::Checkpoint;
+                } else if val.eq_ignore_ascii_case("parallel") {
+                    step.kind = SopStepKind::Parallel;
+                } else {
+                    step.kind = SopStepKind::Execute;
                 }
+            } else if let Some(val) = bullet.strip_prefix("id:") {
+                step.id = Some(val.trim().to_string()).filter(|id| !id.is_empty());
+            } else if let Some(val) = bullet.strip_prefix("next:") {
+                step.next.push(parse_transition(val));
+            } else if let Some(val) = bullet.strip_prefix("on_failure:") {
+                step.on_failure = Some(val.trim().to_string()).filter(|t| !t.is_empty());
+            } else if let Some(val) = bullet.strip_prefix("parallel:") {
+                step.kind = SopStepKind::Parallel;
+                step.branches = split_list(val);
+            } else if let Some(val) = bullet.strip_prefix("join:") {
+                step.join = if val.trim().eq_ignore_ascii_case("any") {
+                    SopJoin::Any
+                } else {
+                    SopJoin::All
+                };
+            } else if let Some(val) = bullet.strip_prefix("retries:") {
+                step.retries = val.trim().parse().unwrap_or(0);
+            } else if let Some(val) = bullet.strip_prefix("max_visits:") {
+                step.max_visits = val.trim().parse().ok();
             } else {
                 // Continuation body line
-                if !current_body.is_empty() {
-                    current_body.push('\n');
+                if !step.body.is_empty() {
+                    step.body.push('\n');
                 }
-                current_body.push_str(trimmed);
+                step.body.push_str(trimmed);
             }
             continue;
         }
 
         // Continuation line for step body
-        if current_number.is_some() && !trimmed.is_empty() {
-            if !current_body.is_empty() {
-                current_body.push('\n');
+        if !trimmed.is_empty() {
+            if !step.body.is_empty() {
+                step.body.push('\n');
             }
-            current_body.push_str(trimmed);
+            step.body.push_str(trimmed);
         }
     }
 
     // Flush final step
-    flush_step(
-        &mut steps,
-        &mut current_number,
-        &mut current_title,
-        &mut current_body,
-        &mut current_tools,
-        &mut current_requires_confirmation,
-        &mut current_kind,
-    );
+    flush_step(&mut steps, &mut current);
 
     steps
 }
 
-/// Flush accumulated step state into the steps vector.
-fn flush_step(
-    steps: &mut Vec<SopStep>,
-    number: &mut Option<u32>,
-    title: &mut String,
-    body: &mut String,
-    tools: &mut Vec<String>,
-    requires_confirmation: &mut bool,
-    kind: &mut SopStepKind,
-) {
-    if let Some(n) = number.take() {
-        steps.push(SopStep {
-            number: n,
-            title: std::mem::take(title),
-            body: body.trim().to_string(),
-            suggested_tools: std::mem::take(tools),
-            requires_confirmation: *requires_confirmation,
-            kind: *kind,
-            schema: None,
-        });
-        *body = String::new();
-        *requires_confirmation = false;
-        *kind = SopStepKind::Execute;
+/// Flush the step being parsed into the steps vector.
+fn flush_step(steps: &mut Vec<SopStep>, current: &mut Option<SopStep>) {
+    if let Some(mut step) = current.take() {
+        step.body = step.body.trim().to_string();
+        steps.push(step);
+    }
+}
+
+/// Split a comma-separated bullet value, dropping empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Parse `<target> [if <condition>]` from a `- next:` bullet.
+fn parse_transition(value: &str) -> StepTransition {
+    let value = value.trim();
+    match value.split_once(" if ") {
+        Some((goto, when)) => StepTransition {
+            when: Some(when.trim().to_string()),
+            goto: goto.trim().to_string(),
+        },
+        None => StepTransition {
+            when: None,
+            goto: value.to_string(),
+        },
     }
 }
 
@@ -0,0 +1,211 @@
+//! The process-wide SOP engine.
+//!
+//! Everything in one process that touches SOP runs — the agent's `sop_*`
+//! tools, the gateway's `/api/sops`, MQTT and cron triggers — shares one
+//! [`SopEngine`] per workspace. A run started by any of them is visible to
+//! all of them, and the run journal has a single writer.
+//!
+//! The journal is opened and its runs resumed once, when the engine is first
+//! requested. Steps that became runnable while the process was down (approval
+//! timeouts of critical runs that lapsed meanwhile) are executed by [`drive`]
+//! instead of being left `Running` with nothing behind them.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use anyhow::Result;
+use tracing::{info, warn};
+use zeroclaw_config::schema::Config;
+
+use super::engine::SopEngine;
+use super::types::SopRunAction;
+use crate::cron::scheduler::authorize_agent_run;
+use crate::security::SecurityPolicy;
+
+/// Tools an agent turn driving an SOP run may always use, on top of the
+/// step's suggested tools.
+const SOP_TOOLS: [&str; 5] = [
+    "sop_list",
+    "sop_execute",
+    "sop_advance",
+    "sop_approve",
+    "sop_status",
+];
+
+type Registry = Mutex<HashMap<PathBuf, Arc<Mutex<SopEngine>>>>;
+
+static ENGINES: OnceLock<Registry> = OnceLock::new();
+
+/// The process-wide engine for `config.workspace_dir`, or `None` when
+/// `[sop].sops_dir` is not configured.
+///
+/// The first call loads the SOPs, opens the run journal and hands the
+/// actions of the runs it resumes to [`drive`].
+pub fn shared_engine(config: &Config) -> Option<Arc<Mutex<SopEngine>>> {
+    config.sop.sops_dir.as_ref()?;
+    let mut engines = ENGINES
+        .get_or_init(Registry::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(engine) = engines.get(&config.workspace_dir) {
+        return Some(Arc::clone(engine));
+    }
+
+    let mut engine = SopEngine::new(config.sop.clone());
+    engine.reload(&config.workspace_dir);
+    let resumed = engine
+        .open_journal(&config.workspace_dir)
+        .unwrap_or_else(|e| {
+            warn!("SOP run journal unavailable: {e:#}");
+            Vec::new()
+        });
+    let engine = Arc::new(Mutex::new(engine));
+    engines.insert(config.workspace_dir.clone(), Arc::clone(&engine));
+    drop(engines);
+
+    for action in resumed {
+        spawn_drive(config, action);
+    }
+    Some(engine)
+}
+
+/// Run [`drive`] for `action` in the background. Without an async runtime
+/// the step is left for an operator to report with `sop_advance`.
+pub fn spawn_drive(config: &Config, action: SopRunAction) {
+    let Some(run_id) = step_run_id(&action) else {
+        return;
+    };
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        warn!(
+            "SOP run {run_id}: step ready but no runtime to execute it; report it with sop_advance"
+        );
+        return;
+    };
+    let config = config.clone();
+    let run_id = run_id.to_string();
+    handle.spawn(async move {
+        if let Err(e) = drive(config, action).await {
+            warn!("SOP run {run_id}: headless step execution failed: {e:#}");
+        }
+    });
+}
+
+/// Execute the step `action` asks for in an agent turn limited to the
+/// `sop_*` tools and the step's suggested tools. The turn reports the step
+/// with `sop_advance` on the shared engine and carries on with the steps
+/// that follow until the run completes, fails or waits for approval.
+///
+/// Actions that need nothing executed (waits, completion, failure) are a
+/// no-op. Subject to the autonomy level and the action budget, like cron
+/// agent jobs.
+pub async fn drive(config: Config, action: SopRunAction) -> Result<()> {
+    let Some(prompt) = step_prompt(&action) else {
+        return Ok(());
+    };
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    authorize_agent_run(&security)?;
+
+    let mut allowed_tools: Vec<String> = SOP_TOOLS.iter().map(|t| (*t).to_string()).collect();
+    for tool in step_tools(&action) {
+        if !allowed_tools.contains(tool) {
+            allowed_tools.push(tool.clone());
+        }
+    }
+    if let Some(run_id) = step_run_id(&action) {
+        info!("SOP run {run_id}: executing step headlessly");
+    }
+
+    let temperature = config
+        .providers
+        .fallback_provider()
+        .and_then(|e| e.temperature)
+        .unwrap_or(0.7);
+    Box::pin(crate::agent::run(
+        config,
+        Some(prompt),
+        None,
+        None,
+        temperature,
+        vec![],
+        false,
+        None,
+        Some(allowed_tools),
+    ))
+    .await?;
+    Ok(())
+}
+
+fn step_run_id(action: &SopRunAction) -> Option<&str> {
+    match action {
+        SopRunAction::ExecuteStep { run_id, .. }
+        | SopRunAction::DeterministicStep { run_id, .. }
+        | SopRunAction::Parallel { run_id, .. } => Some(run_id),
+        _ => None,
+    }
+}
+
+fn step_tools(action: &SopRunAction) -> Vec<&String> {
+    match action {
+        SopRunAction::ExecuteStep { step, .. } | SopRunAction::DeterministicStep { step, .. } => {
+            step.suggested_tools.iter().collect()
+        }
+        SopRunAction::Parallel { branches, .. } => branches.iter().flat_map(step_tools).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn step_prompt(action: &SopRunAction) -> Option<String> {
+    let (run_id, instructions) = match action {
+        SopRunAction::ExecuteStep {
+            run_id, context, ..
+        } => (run_id, context.clone()),
+        SopRunAction::DeterministicStep {
+            run_id,
+            step,
+            input,
+        } => (
+            run_id,
+            format!(
+                "Step {}: {}\n{}\n\nInput: {input}",
+                step.number, step.title, step.body
+            ),
+        ),
+        SopRunAction::Parallel {
+            run_id, branches, ..
+        } => (
+            run_id,
+            crate::tools::sop_execute::describe_branches(branches),
+        ),
+        _ => return None,
+    };
+    Some(format!(
+        "[SOP run {run_id}] {instructions}\n\nCarry out this step and report its result with \
+         sop_advance for run \"{run_id}\". Continue with the steps sop_advance returns until \
+         the run completes, fails or waits for approval."
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_engine_per_workspace() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        assert!(shared_engine(&config).is_none());
+
+        config.sop.sops_dir = Some(tmp.path().join("sops").display().to_string());
+        let first = shared_engine(&config).unwrap();
+        let second = shared_engine(&config).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other = tempfile::TempDir::new().unwrap();
+        config.workspace_dir = other.path().to_path_buf();
+        assert!(!Arc::ptr_eq(&first, &shared_engine(&config).unwrap()));
+    }
+}
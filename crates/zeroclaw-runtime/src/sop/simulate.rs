@@ -0,0 +1,518 @@
+//! Dry-run simulation of SOPs.
+//!
+//! A simulation walks an SOP through a throwaway engine: triggers, conditions,
+//! transitions, retries and joins are evaluated exactly as in a real run, but
+//! no step is executed. Step outputs come from caller-supplied stubs, and the
+//! trace records which tools each step would run and with what input.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::engine::{SopEngine, now_iso8601, trigger_matches};
+use super::types::{
+    Sop, SopEvent, SopRunAction, SopStep, SopStepResult, SopStepStatus, SopTriggerSource,
+};
+
+/// Upper bound on simulated actions; the engine's retry limits end every
+/// real loop well before this.
+const MAX_SIMULATED_ACTIONS: usize = 1_000;
+
+/// A simulation input file: the event to replay plus optional step stubs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulationInput {
+    #[serde(default = "default_source")]
+    pub source: SopTriggerSource,
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Event payload; non-string values are passed on as JSON text.
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    /// Recorded outputs keyed by step id or number.
+    #[serde(default)]
+    pub steps: HashMap<String, StepStub>,
+}
+
+fn default_source() -> SopTriggerSource {
+    SopTriggerSource::Manual
+}
+
+impl SimulationInput {
+    /// The event this input replays.
+    pub fn event(&self) -> SopEvent {
+        SopEvent {
+            source: self.source,
+            topic: self.topic.clone(),
+            payload: self.payload.as_ref().map(|p| match p {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }),
+            timestamp: now_iso8601(),
+        }
+    }
+}
+
+/// The recorded result of a step. Unstubbed deterministic steps pass their
+/// input through; unstubbed agent steps produce an empty output.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StepStub {
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+    /// Fail the step with this reason instead of completing it.
+    #[serde(default)]
+    pub fail: Option<String>,
+}
+
+/// How a trigger fared against the simulated event.
+#[derive(Debug, Clone, Serialize)]
+pub struct TriggerCheck {
+    pub trigger: String,
+    pub matched: bool,
+}
+
+/// One entry of the would-be execution trace.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceStep {
+    pub step: u32,
+    pub title: String,
+    /// `execute`, `deterministic`, `approval`, `checkpoint` or `parallel`.
+    pub action: &'static str,
+    /// Tools the step would call.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Input piped to a deterministic step; agent steps pick their own
+    /// tool arguments at run time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<SopStepStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// How a simulated run ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SimulationOutcome {
+    /// No trigger matched the event, so the SOP would not start.
+    NotTriggered,
+    Completed,
+    Failed {
+        reason: String,
+    },
+    /// An approval or checkpoint was declined.
+    Stopped {
+        step: u32,
+    },
+}
+
+/// The would-be execution trace of a simulated run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationTrace {
+    pub sop_name: String,
+    pub triggers: Vec<TriggerCheck>,
+    pub steps: Vec<TraceStep>,
+    pub outcome: SimulationOutcome,
+}
+
+/// Simulate `sop` reacting to `event`.
+///
+/// Non-manual events must match one of the SOP's triggers, conditions
+/// included; manual events start the SOP regardless, as `sop_execute` does.
+/// `approve` decides each approval gate and checkpoint; declining one stops
+/// the simulation there.
+pub fn simulate(
+    sop: &Sop,
+    event: SopEvent,
+    stubs: &HashMap<String, StepStub>,
+    approve: &mut dyn FnMut(&SopStep) -> bool,
+) -> Result<SimulationTrace> {
+    let triggers: Vec<TriggerCheck> = sop
+        .triggers
+        .iter()
+        .map(|t| TriggerCheck {
+            trigger: describe_trigger(t),
+            matched: trigger_matches(t, &event),
+        })
+        .collect();
+    let mut trace = SimulationTrace {
+        sop_name: sop.name.clone(),
+        triggers,
+        steps: Vec::new(),
+        outcome: SimulationOutcome::NotTriggered,
+    };
+    if event.source != SopTriggerSource::Manual && !trace.triggers.iter().any(|t| t.matched) {
+        return Ok(trace);
+    }
+
+    let mut engine = SopEngine::for_simulation(vec![sop.clone()]);
+    let first = engine.start_run(&sop.name, event)?;
+    let mut queue = VecDeque::from([first]);
+    let mut handled = 0;
+
+    while let Some(action) = queue.pop_front() {
+        handled += 1;
+        if handled > MAX_SIMULATED_ACTIONS {
+            bail!("Simulation of SOP '{}' did not terminate", sop.name);
+        }
+
+        let next = match action {
+            SopRunAction::ExecuteStep { run_id, step, .. } => {
+                let (result, entry) = run_step(&step, "execute", None, stubs);
+                trace.steps.push(entry);
+                engine.advance_step(&run_id, result)?
+            }
+            SopRunAction::DeterministicStep {
+                run_id,
+                step,
+                input,
+            } => {
+                let (result, entry) = run_step(&step, "deterministic", Some(input), stubs);
+                trace.steps.push(entry);
+                engine.advance_step(&run_id, result)?
+            }
+            SopRunAction::WaitApproval { run_id, step, .. } => {
+                if !gate(&mut trace, &step, "approval", approve) {
+                    engine.cancel_run(&run_id)?;
+                    return Ok(trace);
+                }
+                engine.approve(&run_id)?
+            }
+            SopRunAction::CheckpointWait { run_id, step, .. } => {
+                if !gate(&mut trace, &step, "checkpoint", approve) {
+                    engine.cancel_run(&run_id)?;
+                    return Ok(trace);
+                }
+                engine.approve(&run_id)?
+            }
+            SopRunAction::Parallel { step, branches, .. } => {
+                trace.steps.push(TraceStep {
+                    note: Some(format!(
+                        "{} join over {} branch(es)",
+                        step.join,
+                        branches.len()
+                    )),
+                    ..entry(&step, "parallel")
+                });
+                queue.extend(branches);
+                continue;
+            }
+            SopRunAction::AwaitBranches { .. } => continue,
+            SopRunAction::Completed { .. } => {
+                trace.outcome = SimulationOutcome::Completed;
+                break;
+            }
+            SopRunAction::Failed { reason, .. } => {
+                trace.outcome = SimulationOutcome::Failed { reason };
+                break;
+            }
+        };
+
+        // Drop queued branches the join no longer waits for
+        let pending = engine
+            .active_runs()
+            .values()
+            .next()
+            .map(|run| run.pending_branches.clone())
+            .unwrap_or_default();
+        queue.retain(|a| step_of(a).is_some_and(|n| pending.contains(&n)));
+        queue.push_back(next);
+    }
+
+    Ok(trace)
+}
+
+fn entry(step: &SopStep, action: &'static str) -> TraceStep {
+    TraceStep {
+        step: step.number,
+        title: step.title.clone(),
+        action,
+        tools: Vec::new(),
+        input: None,
+        status: None,
+        output: None,
+        note: None,
+    }
+}
+
+/// Stub the execution of `step`, returning the result to report and its
+/// trace entry.
+fn run_step(
+    step: &SopStep,
+    action: &'static str,
+    input: Option<serde_json::Value>,
+    stubs: &HashMap<String, StepStub>,
+) -> (SopStepResult, TraceStep) {
+    let stub = stubs
+        .get(&step.key())
+        .or_else(|| stubs.get(&step.number.to_string()));
+    let (status, output, note) = match stub {
+        Some(StepStub {
+            fail: Some(reason), ..
+        }) => (
+            SopStepStatus::Failed,
+            serde_json::Value::String(reason.clone()),
+            "stubbed failure",
+        ),
+        Some(StepStub { output, .. }) => (
+            SopStepStatus::Completed,
+            output.clone().unwrap_or(serde_json::Value::Null),
+            "stubbed output",
+        ),
+        None => match input {
+            Some(ref input) => (
+                SopStepStatus::Completed,
+                input.clone(),
+                "input passed through",
+            ),
+            None => (
+                SopStepStatus::Completed,
+                serde_json::Value::String(String::new()),
+                "no stub, empty output",
+            ),
+        },
+    };
+
+    let now = now_iso8601();
+    let result = SopStepResult {
+        step_number: step.number,
+        status,
+        output: match output {
+            serde_json::Value::String(ref s) => s.clone(),
+            ref other => other.to_string(),
+        },
+        started_at: now.clone(),
+        completed_at: Some(now),
+    };
+    let entry = TraceStep {
+        tools: step.suggested_tools.clone(),
+        input,
+        status: Some(status),
+        output: Some(output),
+        note: Some(note.to_string()),
+        ..entry(step, action)
+    };
+    (result, entry)
+}
+
+/// Record an approval gate and ask whether to pass it.
+fn gate(
+    trace: &mut SimulationTrace,
+    step: &SopStep,
+    action: &'static str,
+    approve: &mut dyn FnMut(&SopStep) -> bool,
+) -> bool {
+    let approved = approve(step);
+    trace.steps.push(TraceStep {
+        note: Some(if approved { "approved" } else { "declined" }.into()),
+        ..entry(step, action)
+    });
+    if !approved {
+        trace.outcome = SimulationOutcome::Stopped { step: step.number };
+    }
+    approved
+}
+
+fn step_of(action: &SopRunAction) -> Option<u32> {
+    match action {
+        SopRunAction::ExecuteStep { step, .. } | SopRunAction::DeterministicStep { step, .. } => {
+            Some(step.number)
+        }
+        _ => None,
+    }
+}
+
+fn describe_trigger(trigger: &super::types::SopTrigger) -> String {
+    use super::types::SopTrigger;
+    match trigger {
+        SopTrigger::Mqtt {
+            condition: Some(cond),
+            ..
+        }
+        | SopTrigger::Webhook {
+            condition: Some(cond),
+            ..
+        }
+        | SopTrigger::Peripheral {
+            condition: Some(cond),
+            ..
+        } => format!("{trigger} when {cond}"),
+        _ => trigger.to_string(),
+    }
+}
+
+impl fmt::Display for SimulationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Simulation of SOP '{}' (dry run)", self.sop_name)?;
+        writeln!(f)?;
+        writeln!(f, "Triggers:")?;
+        for check in &self.triggers {
+            let mark = if check.matched { "matched" } else { "no match" };
+            writeln!(f, "  - {} — {mark}", check.trigger)?;
+        }
+        if !self.steps.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Trace:")?;
+        }
+        for step in &self.steps {
+            write!(f, "  {}. [{}] {}", step.step, step.action, step.title)?;
+            if let Some(status) = step.status {
+                write!(f, " → {status}")?;
+            }
+            if let Some(ref note) = step.note {
+                write!(f, " ({note})")?;
+            }
+            writeln!(f)?;
+            if !step.tools.is_empty() {
+                writeln!(f, "       tools: {}", step.tools.join(", "))?;
+            }
+            if let Some(ref input) = step.input {
+                writeln!(f, "       input: {input}")?;
+            }
+            if let Some(ref output) = step.output {
+                writeln!(f, "       output: {output}")?;
+            }
+        }
+        writeln!(f)?;
+        match &self.outcome {
+            SimulationOutcome::NotTriggered => {
+                write!(f, "Outcome: not triggered — no trigger matches the event")
+            }
+            SimulationOutcome::Completed => write!(f, "Outcome: completed"),
+            SimulationOutcome::Failed { reason } => write!(f, "Outcome: failed — {reason}"),
+            SimulationOutcome::Stopped { step } => {
+                write!(f, "Outcome: stopped — step {step} was not approved")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sop::StepTransition;
+    use crate::sop::types::{SopExecutionMode, SopJoin, SopPriority, SopStepKind, SopTrigger};
+
+    fn step(number: u32, title: &str, tools: &[&str]) -> SopStep {
+        SopStep {
+            number,
+            title: title.into(),
+            body: String::new(),
+            suggested_tools: tools.iter().map(|t| (*t).to_string()).collect(),
+            requires_confirmation: false,
+            kind: SopStepKind::Execute,
+            schema: None,
+            id: None,
+            next: Vec::new(),
+            on_failure: None,
+            branches: Vec::new(),
+            join: SopJoin::default(),
+            retries: 0,
+            max_visits: None,
+        }
+    }
+
+    fn sop(mode: SopExecutionMode, steps: Vec<SopStep>) -> Sop {
+        Sop {
+            name: "valve".into(),
+            description: "Valve alarm".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: mode,
+            triggers: vec![SopTrigger::Mqtt {
+                topic: "plant/+/alarm".into(),
+                condition: Some("$.pressure > 80".into()),
+            }],
+            steps,
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            location: None,
+            deterministic: mode == SopExecutionMode::Deterministic,
+        }
+    }
+
+    fn alarm(pressure: u32) -> SopEvent {
+        SimulationInput {
+            source: SopTriggerSource::Mqtt,
+            topic: Some("plant/3/alarm".into()),
+            payload: Some(serde_json::json!({ "pressure": pressure })),
+            steps: HashMap::new(),
+        }
+        .event()
+    }
+
+    #[test]
+    fn unmatched_event_does_not_start() {
+        let sop = sop(SopExecutionMode::Auto, vec![step(1, "Check", &[])]);
+        let trace = simulate(&sop, alarm(50), &HashMap::new(), &mut |_| true).unwrap();
+        assert_eq!(trace.outcome, SimulationOutcome::NotTriggered);
+        assert!(!trace.triggers[0].matched);
+        assert!(trace.steps.is_empty());
+    }
+
+    #[test]
+    fn stubbed_outputs_drive_transitions() {
+        let mut check = step(1, "Check level", &["shell"]);
+        check.next = vec![StepTransition {
+            when: Some(r#"$.level == "high""#.into()),
+            goto: "3".into(),
+        }];
+        let sop = sop(
+            SopExecutionMode::Auto,
+            vec![
+                check,
+                step(2, "Log", &[]),
+                step(3, "Close valve", &["gpio_write"]),
+            ],
+        );
+        let stubs = HashMap::from([(
+            "1".to_string(),
+            StepStub {
+                output: Some(serde_json::json!({ "level": "high" })),
+                fail: None,
+            },
+        )]);
+
+        let trace = simulate(&sop, alarm(90), &stubs, &mut |_| true).unwrap();
+        assert_eq!(trace.outcome, SimulationOutcome::Completed);
+        let walked: Vec<_> = trace.steps.iter().map(|s| s.step).collect();
+        assert_eq!(walked, [1, 3]);
+        assert_eq!(trace.steps[1].tools, ["gpio_write"]);
+    }
+
+    #[test]
+    fn declined_approval_stops_the_simulation() {
+        let sop = sop(SopExecutionMode::Supervised, vec![step(1, "Check", &[])]);
+        let trace = simulate(&sop, alarm(90), &HashMap::new(), &mut |_| false).unwrap();
+        assert_eq!(trace.outcome, SimulationOutcome::Stopped { step: 1 });
+        assert_eq!(trace.steps[0].action, "approval");
+    }
+
+    #[test]
+    fn deterministic_checkpoint_writes_no_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut gate = step(2, "Confirm", &[]);
+        gate.kind = SopStepKind::Checkpoint;
+        let mut sop = sop(
+            SopExecutionMode::Deterministic,
+            vec![step(1, "Read", &["sensor_read"]), gate, step(3, "Act", &[])],
+        );
+        sop.location = Some(dir.path().to_path_buf());
+
+        let mut asked = Vec::new();
+        let trace = simulate(&sop, alarm(90), &HashMap::new(), &mut |s| {
+            asked.push(s.number);
+            true
+        })
+        .unwrap();
+        assert_eq!(trace.outcome, SimulationOutcome::Completed);
+        assert_eq!(asked, [2]);
+        let actions: Vec<_> = trace.steps.iter().map(|s| s.action).collect();
+        assert_eq!(actions, ["deterministic", "checkpoint", "deterministic"]);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,454 @@
+//! SQLite journal for SOP runs.
+//!
+//! Every run the engine starts is journaled here together with its
+//! triggering event, step results and approvals, so a daemon restart can
+//! resume runs that were mid-flight or waiting on a human. Mirrors the
+//! `heartbeat/store.rs` pattern: fresh connection per call, schema
+//! auto-created, finished history pruned to a configurable limit.
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::engine::now_iso8601;
+use super::types::{Sop, SopRun, SopStepResult};
+
+/// Statuses of runs that have not finished yet.
+const ACTIVE_STATUSES: &str = "'pending', 'running', 'waiting_approval', 'paused_checkpoint'";
+
+/// A journaled approval of a run paused for a human.
+#[derive(Debug, Clone)]
+pub struct SopApproval {
+    pub run_id: String,
+    pub step_number: u32,
+    /// `operator` for explicit approvals, `timeout` for auto-approvals.
+    pub approved_by: String,
+    pub approved_at: String,
+}
+
+/// Journal the current state of `run`, appending step results not yet
+/// recorded. Once the run has finished, finished runs beyond the newest
+/// `keep_finished` are pruned (0 = keep all).
+pub fn record_run(workspace_dir: &Path, run: &SopRun, keep_finished: usize) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+
+        tx.execute(
+            "INSERT INTO sop_runs (
+                run_id, sop_name, status, current_step, total_steps, trigger_event,
+                started_at, completed_at, waiting_since, llm_calls_saved, step_visits,
+                pending_branches, updated_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(run_id) DO UPDATE SET
+                status = excluded.status,
+                current_step = excluded.current_step,
+                completed_at = excluded.completed_at,
+                waiting_since = excluded.waiting_since,
+                llm_calls_saved = excluded.llm_calls_saved,
+                step_visits = excluded.step_visits,
+                pending_branches = excluded.pending_branches,
+                updated_at = excluded.updated_at",
+            params![
+                run.run_id,
+                run.sop_name,
+                run.status.to_string(),
+                run.current_step,
+                run.total_steps,
+                serde_json::to_string(&run.trigger_event)?,
+                run.started_at,
+                run.completed_at,
+                run.waiting_since,
+                i64::try_from(run.llm_calls_saved).unwrap_or(i64::MAX),
+                serde_json::to_string(&run.step_visits)?,
+                serde_json::to_string(&run.pending_branches)?,
+                now_iso8601(),
+            ],
+        )
+        .context("Failed to journal SOP run")?;
+
+        let recorded: i64 = tx.query_row(
+            "SELECT COUNT(*) FROM sop_step_results WHERE run_id = ?1",
+            params![run.run_id],
+            |row| row.get(0),
+        )?;
+        let recorded = usize::try_from(recorded).unwrap_or(0);
+        for (seq, result) in run.step_results.iter().enumerate().skip(recorded) {
+            tx.execute(
+                "INSERT INTO sop_step_results
+                    (run_id, seq, step_number, status, output, started_at, completed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    run.run_id,
+                    i64::try_from(seq).unwrap_or(i64::MAX),
+                    result.step_number,
+                    result.status.to_string(),
+                    result.output,
+                    result.started_at,
+                    result.completed_at,
+                ],
+            )
+            .context("Failed to journal SOP step result")?;
+        }
+
+        if run.completed_at.is_some() && keep_finished > 0 {
+            prune_finished(&tx, keep_finished)?;
+        }
+
+        tx.commit().context("Failed to commit SOP run journal")?;
+        Ok(())
+    })
+}
+
+/// Journal an approval of the step `run_id` is paused on.
+pub fn record_approval(
+    workspace_dir: &Path,
+    run_id: &str,
+    step_number: u32,
+    approved_by: &str,
+) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO sop_approvals (run_id, step_number, approved_by, approved_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![run_id, step_number, approved_by, now_iso8601()],
+        )
+        .context("Failed to journal SOP approval")?;
+        Ok(())
+    })
+}
+
+/// List the approvals journaled for a run, oldest first.
+pub fn list_approvals(workspace_dir: &Path, run_id: &str) -> Result<Vec<SopApproval>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT run_id, step_number, approved_by, approved_at
+             FROM sop_approvals WHERE run_id = ?1
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(SopApproval {
+                run_id: row.get(0)?,
+                step_number: row.get(1)?,
+                approved_by: row.get(2)?,
+                approved_at: row.get(3)?,
+            })
+        })?;
+        let mut approvals = Vec::new();
+        for row in rows {
+            approvals.push(row?);
+        }
+        Ok(approvals)
+    })
+}
+
+/// Load every journaled run that has not finished, oldest first.
+pub fn load_active_runs(workspace_dir: &Path) -> Result<Vec<SopRun>> {
+    with_connection(workspace_dir, |conn| {
+        load_runs(
+            conn,
+            &format!(
+                "SELECT {RUN_COLUMNS} FROM sop_runs
+                 WHERE status IN ({ACTIVE_STATUSES})
+                 ORDER BY started_at ASC, run_id ASC"
+            ),
+            params![],
+        )
+    })
+}
+
+/// Load the newest `limit` finished runs (0 = all), oldest first.
+pub fn load_finished_runs(workspace_dir: &Path, limit: usize) -> Result<Vec<SopRun>> {
+    let limit = if limit == 0 {
+        -1
+    } else {
+        i64::try_from(limit).unwrap_or(i64::MAX)
+    };
+    with_connection(workspace_dir, |conn| {
+        let mut runs = load_runs(
+            conn,
+            &format!(
+                "SELECT {RUN_COLUMNS} FROM sop_runs
+                 WHERE status NOT IN ({ACTIVE_STATUSES})
+                 ORDER BY completed_at DESC, run_id DESC
+                 LIMIT ?1"
+            ),
+            params![limit],
+        )?;
+        runs.reverse();
+        Ok(runs)
+    })
+}
+
+/// Explain why a journaled run can no longer be resumed against `sops`,
+/// or `None` when it can.
+pub fn orphan_reason(sops: &[Sop], run: &SopRun) -> Option<String> {
+    let Some(sop) = sops.iter().find(|s| s.name == run.sop_name) else {
+        return Some(format!("SOP '{}' is no longer loaded", run.sop_name));
+    };
+    std::iter::once(&run.current_step)
+        .chain(&run.pending_branches)
+        .find(|&&n| sop.step(n).is_none())
+        .map(|n| format!("SOP '{}' no longer has step {n}", sop.name))
+}
+
+const RUN_COLUMNS: &str = "run_id, sop_name, status, current_step, total_steps, trigger_event,
+    started_at, completed_at, waiting_since, llm_calls_saved, step_visits, pending_branches";
+
+fn load_runs(conn: &Connection, sql: &str, params: impl rusqlite::Params) -> Result<Vec<SopRun>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params, |row| {
+        Ok(SopRun {
+            run_id: row.get(0)?,
+            sop_name: row.get(1)?,
+            status: parse_json_str(&row.get::<_, String>(2)?).map_err(sql_err)?,
+            current_step: row.get(3)?,
+            total_steps: row.get(4)?,
+            trigger_event: parse_json(&row.get::<_, String>(5)?).map_err(sql_err)?,
+            started_at: row.get(6)?,
+            completed_at: row.get(7)?,
+            waiting_since: row.get(8)?,
+            llm_calls_saved: u64::try_from(row.get::<_, i64>(9)?).unwrap_or(0),
+            step_visits: parse_json::<HashMap<u32, u32>>(&row.get::<_, String>(10)?)
+                .map_err(sql_err)?,
+            pending_branches: parse_json(&row.get::<_, String>(11)?).map_err(sql_err)?,
+            step_results: Vec::new(),
+        })
+    })?;
+
+    let mut runs = Vec::new();
+    for row in rows {
+        runs.push(row?);
+    }
+    for run in &mut runs {
+        run.step_results = load_step_results(conn, &run.run_id)?;
+    }
+    Ok(runs)
+}
+
+fn load_step_results(conn: &Connection, run_id: &str) -> Result<Vec<SopStepResult>> {
+    let mut stmt = conn.prepare(
+        "SELECT step_number, status, output, started_at, completed_at
+         FROM sop_step_results WHERE run_id = ?1
+         ORDER BY seq ASC",
+    )?;
+    let rows = stmt.query_map(params![run_id], |row| {
+        Ok(SopStepResult {
+            step_number: row.get(0)?,
+            status: parse_json_str(&row.get::<_, String>(1)?).map_err(sql_err)?,
+            output: row.get(2)?,
+            started_at: row.get(3)?,
+            completed_at: row.get(4)?,
+        })
+    })?;
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row?);
+    }
+    Ok(results)
+}
+
+fn prune_finished(conn: &Connection, keep: usize) -> Result<()> {
+    let keep = i64::try_from(keep).unwrap_or(i64::MAX);
+    conn.execute(
+        &format!(
+            "DELETE FROM sop_runs
+             WHERE status NOT IN ({ACTIVE_STATUSES})
+               AND run_id NOT IN (
+                   SELECT run_id FROM sop_runs
+                   WHERE status NOT IN ({ACTIVE_STATUSES})
+                   ORDER BY completed_at DESC, run_id DESC
+                   LIMIT ?1
+               )"
+        ),
+        params![keep],
+    )
+    .context("Failed to prune SOP run journal")?;
+    conn.execute_batch(
+        "DELETE FROM sop_step_results WHERE run_id NOT IN (SELECT run_id FROM sop_runs);
+         DELETE FROM sop_approvals WHERE run_id NOT IN (SELECT run_id FROM sop_runs);",
+    )
+    .context("Failed to prune SOP run journal")?;
+    Ok(())
+}
+
+fn parse_json<T: DeserializeOwned>(raw: &str) -> Result<T> {
+    serde_json::from_str(raw).with_context(|| format!("Invalid JSON in SOP run journal: {raw}"))
+}
+
+/// Parse a snake_case enum stored as a bare string.
+fn parse_json_str<T: DeserializeOwned>(raw: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(raw.to_string()))
+        .with_context(|| format!("Invalid status in SOP run journal: {raw}"))
+}
+
+fn sql_err(err: anyhow::Error) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(err.into())
+}
+
+/// Path of the run journal for `workspace_dir`.
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("sop").join("runs.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| {
+            format!(
+                "Failed to create SOP journal directory: {}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open SOP run journal: {}", path.display()))?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+
+         CREATE TABLE IF NOT EXISTS sop_runs (
+            run_id           TEXT PRIMARY KEY,
+            sop_name         TEXT NOT NULL,
+            status           TEXT NOT NULL,
+            current_step     INTEGER NOT NULL,
+            total_steps      INTEGER NOT NULL,
+            trigger_event    TEXT NOT NULL,
+            started_at       TEXT NOT NULL,
+            completed_at     TEXT,
+            waiting_since    TEXT,
+            llm_calls_saved  INTEGER NOT NULL DEFAULT 0,
+            step_visits      TEXT NOT NULL DEFAULT '{}',
+            pending_branches TEXT NOT NULL DEFAULT '[]',
+            updated_at       TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_sop_runs_status ON sop_runs(status);
+
+         CREATE TABLE IF NOT EXISTS sop_step_results (
+            run_id       TEXT NOT NULL,
+            seq          INTEGER NOT NULL,
+            step_number  INTEGER NOT NULL,
+            status       TEXT NOT NULL,
+            output       TEXT NOT NULL,
+            started_at   TEXT NOT NULL,
+            completed_at TEXT,
+            PRIMARY KEY (run_id, seq)
+         );
+
+         CREATE TABLE IF NOT EXISTS sop_approvals (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            run_id       TEXT NOT NULL,
+            step_number  INTEGER NOT NULL,
+            approved_by  TEXT NOT NULL,
+            approved_at  TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_sop_approvals_run ON sop_approvals(run_id);",
+    )
+    .context("Failed to initialize SOP run journal schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sop::types::{SopEvent, SopRunStatus, SopStepStatus, SopTriggerSource};
+    use tempfile::TempDir;
+
+    fn run(run_id: &str, status: SopRunStatus) -> SopRun {
+        SopRun {
+            run_id: run_id.into(),
+            sop_name: "valve".into(),
+            trigger_event: SopEvent {
+                source: SopTriggerSource::Mqtt,
+                topic: Some("plant/valve".into()),
+                payload: Some(r#"{"pressure": 90}"#.into()),
+                timestamp: "2026-01-01T00:00:00Z".into(),
+            },
+            status,
+            current_step: 2,
+            total_steps: 3,
+            started_at: "2026-01-01T00:00:00Z".into(),
+            completed_at: None,
+            step_results: Vec::new(),
+            waiting_since: None,
+            llm_calls_saved: 0,
+            step_visits: HashMap::from([(1, 1), (2, 1)]),
+            pending_branches: Vec::new(),
+        }
+    }
+
+    fn result(step_number: u32, output: &str) -> SopStepResult {
+        SopStepResult {
+            step_number,
+            status: SopStepStatus::Completed,
+            output: output.into(),
+            started_at: "2026-01-01T00:00:01Z".into(),
+            completed_at: Some("2026-01-01T00:00:02Z".into()),
+        }
+    }
+
+    #[test]
+    fn record_and_load_round_trips_active_run() {
+        let tmp = TempDir::new().unwrap();
+        let mut active = run("run-1", SopRunStatus::Running);
+        active.step_results.push(result(1, "checked"));
+        record_run(tmp.path(), &active, 10).unwrap();
+
+        active.status = SopRunStatus::WaitingApproval;
+        active.waiting_since = Some("2026-01-01T00:00:03Z".into());
+        active.step_results.push(result(2, "isolated"));
+        record_run(tmp.path(), &active, 10).unwrap();
+
+        let loaded = load_active_runs(tmp.path()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        let loaded = &loaded[0];
+        assert_eq!(loaded.status, SopRunStatus::WaitingApproval);
+        assert_eq!(
+            loaded.waiting_since.as_deref(),
+            Some("2026-01-01T00:00:03Z")
+        );
+        assert_eq!(loaded.step_visits.get(&2), Some(&1));
+        assert_eq!(loaded.trigger_event.topic.as_deref(), Some("plant/valve"));
+        let outputs: Vec<_> = loaded.step_results.iter().map(|r| &r.output).collect();
+        assert_eq!(outputs, ["checked", "isolated"]);
+    }
+
+    #[test]
+    fn finished_runs_leave_the_active_set_and_are_pruned() {
+        let tmp = TempDir::new().unwrap();
+        for (i, id) in ["run-a", "run-b", "run-c"].iter().enumerate() {
+            let mut done = run(id, SopRunStatus::Completed);
+            done.completed_at = Some(format!("2026-01-01T00:01:0{i}Z"));
+            record_run(tmp.path(), &done, 2).unwrap();
+        }
+
+        assert!(load_active_runs(tmp.path()).unwrap().is_empty());
+        let finished: Vec<_> = load_finished_runs(tmp.path(), 0)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.run_id)
+            .collect();
+        assert_eq!(finished, ["run-b", "run-c"]);
+    }
+
+    #[test]
+    fn approvals_are_journaled_per_run() {
+        let tmp = TempDir::new().unwrap();
+        record_approval(tmp.path(), "run-1", 2, "operator").unwrap();
+        record_approval(tmp.path(), "run-1", 3, "timeout").unwrap();
+        record_approval(tmp.path(), "run-2", 1, "operator").unwrap();
+
+        let approvals = list_approvals(tmp.path(), "run-1").unwrap();
+        let summary: Vec<_> = approvals
+            .iter()
+            .map(|a| (a.step_number, a.approved_by.as_str()))
+            .collect();
+        assert_eq!(summary, [(2, "operator"), (3, "timeout")]);
+    }
+}
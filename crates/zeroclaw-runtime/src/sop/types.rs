@@ -7,6 +7,7 @@ use std::path::PathBuf;
 
 /// SOP priority level, used for execution mode resolution and scheduling.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SopPriority {
     Low,
@@ -65,6 +66,7 @@ impl fmt::Display for SopExecutionMode {
 
 /// What event can activate an SOP.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum SopTrigger {
     Mqtt {
@@ -74,6 +76,8 @@ pub enum SopTrigger {
     },
     Webhook {
         path: String,
+        #[serde(default)]
+        condition: Option<String>,
     },
     Cron {
         expression: String,
@@ -91,7 +95,7 @@ impl fmt::Display for SopTrigger {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Mqtt { topic, .. } => write!(f, "mqtt:{topic}"),
-            Self::Webhook { path } => write!(f, "webhook:{path}"),
+            Self::Webhook { path, .. } => write!(f, "webhook:{path}"),
             Self::Cron { expression } => write!(f, "cron:{expression}"),
             Self::Peripheral { board, signal, .. } => write!(f, "peripheral:{board}/{signal}"),
             Self::Manual => write!(f, "manual"),
@@ -103,6 +107,7 @@ impl fmt::Display for SopTrigger {
 
 /// The kind of a workflow step.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SopStepKind {
     /// Normal step — executed by the agent (or deterministic handler).
@@ -110,6 +115,8 @@ pub enum SopStepKind {
     Execute,
     /// Checkpoint step — pauses execution and waits for human approval.
     Checkpoint,
+    /// Parallel step — fans out to its `branches` and joins their results.
+    Parallel,
 }
 
 impl fmt::Display for SopStepKind {
@@ -117,6 +124,7 @@ impl fmt::Display for SopStepKind {
         match self {
             Self::Execute => write!(f, "execute"),
             Self::Checkpoint => write!(f, "checkpoint"),
+            Self::Parallel => write!(f, "parallel"),
         }
     }
 }
@@ -128,6 +136,7 @@ impl fmt::Display for SopStepKind {
 /// Stored as a raw `serde_json::Value` so callers can validate without
 /// pulling in a full JSON Schema library.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct StepSchema {
     /// JSON Schema object describing expected input shape.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -137,10 +146,128 @@ pub struct StepSchema {
     pub output: Option<serde_json::Value>,
 }
 
+impl StepSchema {
+    /// Check a step output against the `output` schema, if one is declared.
+    pub fn validate_output(&self, value: &serde_json::Value) -> Result<(), String> {
+        match self.output {
+            Some(ref schema) => check_schema(schema, value, "$"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Validate `value` against the subset of JSON Schema SOPs rely on:
+/// `type`, `enum`, `required`, `properties` and `items`.
+fn check_schema(
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+    path: &str,
+) -> Result<(), String> {
+    use serde_json::Value;
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let matches = |t: &str| match t {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            _ => false,
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches(t)) {
+            return Err(format!("{path}: expected {}", allowed.join(" or ")));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(value)
+    {
+        return Err(format!("{path}: {value} is not one of the allowed values"));
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(name) {
+                    return Err(format!("{path}: missing required field '{name}'"));
+                }
+            }
+        }
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            for (name, sub) in properties {
+                if let Some(field) = fields.get(name) {
+                    check_schema(sub, field, &format!("{path}.{name}"))?;
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(sub)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_schema(sub, item, &format!("{path}[{i}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+// ── Routing ─────────────────────────────────────────────────────
+
+/// Transition target that finishes the run successfully.
+pub const END_STEP: &str = "end";
+
+/// A transition out of a step, taken when `when` matches the step output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+pub struct StepTransition {
+    /// Condition evaluated against the step output; `None` always matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// Target step id or number, or `end`.
+    pub goto: String,
+}
+
+/// How a parallel step combines the results of its branches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SopJoin {
+    /// Every branch must succeed; the first failure fails the group.
+    #[default]
+    All,
+    /// The first successful branch completes the group.
+    Any,
+}
+
+impl fmt::Display for SopJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Any => write!(f, "any"),
+        }
+    }
+}
+
+fn is_default_join(join: &SopJoin) -> bool {
+    *join == SopJoin::All
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 // ── Step ────────────────────────────────────────────────────────
 
 /// A single step in an SOP procedure, parsed from SOP.md.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct SopStep {
     pub number: u32,
     pub title: String,
@@ -155,12 +282,53 @@ pub struct SopStep {
     /// Typed input/output schemas for deterministic data flow validation.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<StepSchema>,
+    /// Stable identifier usable as a transition target instead of the number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Transitions tried in order after the step completes. When none
+    /// matches, the run falls through to the following step.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub next: Vec<StepTransition>,
+    /// Step to continue at once the step has failed and exhausted its retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+    /// Branch step ids or numbers started together by a `parallel` step.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+    /// Join semantics for a `parallel` step.
+    #[serde(default, skip_serializing_if = "is_default_join")]
+    pub join: SopJoin,
+    /// Extra attempts allowed after consecutive failures of the step.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retries: u32,
+    /// Most times the step may be entered per run, counting loops and
+    /// retries. Defaults to [`DEFAULT_MAX_VISITS`], or `1 + retries` when
+    /// that is larger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_visits: Option<u32>,
+}
+
+/// Visit limit of a step that does not set `max_visits`.
+pub const DEFAULT_MAX_VISITS: u32 = 10;
+
+impl SopStep {
+    /// Most times the step may be entered per run.
+    pub fn visit_limit(&self) -> u32 {
+        self.max_visits
+            .unwrap_or_else(|| DEFAULT_MAX_VISITS.max(self.retries.saturating_add(1)))
+    }
+
+    /// The name other steps use to refer to this one: its id, or its number.
+    pub fn key(&self) -> String {
+        self.id.clone().unwrap_or_else(|| self.number.to_string())
+    }
 }
 
 // ── SOP ─────────────────────────────────────────────────────────
 
 /// A complete Standard Operating Procedure definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct Sop {
     pub name: String,
     pub description: String,
@@ -181,6 +349,47 @@ pub struct Sop {
     pub deterministic: bool,
 }
 
+impl Sop {
+    /// Look up a step by number.
+    pub fn step(&self, number: u32) -> Option<&SopStep> {
+        self.steps.iter().find(|s| s.number == number)
+    }
+
+    /// Resolve a transition target (step id or number) to a step number.
+    pub fn resolve_target(&self, target: &str) -> Option<u32> {
+        let target = target.trim();
+        self.steps
+            .iter()
+            .find(|s| s.id.as_deref() == Some(target))
+            .or_else(|| {
+                let n: u32 = target.parse().ok()?;
+                self.step(n)
+            })
+            .map(|s| s.number)
+    }
+
+    /// Whether `number` is a branch of some parallel step. Branches only run
+    /// as part of their group and are skipped by linear fall-through.
+    pub fn is_branch(&self, number: u32) -> bool {
+        self.steps.iter().any(|s| {
+            s.kind == SopStepKind::Parallel
+                && s.branches
+                    .iter()
+                    .any(|b| self.resolve_target(b) == Some(number))
+        })
+    }
+
+    /// The step a completed `number` falls through to when no `next`
+    /// transition matches: the following step that is not a branch.
+    pub fn following_step(&self, number: u32) -> Option<u32> {
+        self.steps
+            .iter()
+            .map(|s| s.number)
+            .filter(|&n| n > number && !self.is_branch(n))
+            .min()
+    }
+}
+
 fn default_cooldown_secs() -> u64 {
     0
 }
@@ -197,6 +406,51 @@ pub struct SopManifest {
     pub sop: SopMeta,
     #[serde(default)]
     pub triggers: Vec<SopTrigger>,
+    /// Routing and schemas layered onto the steps parsed from SOP.md.
+    #[serde(default)]
+    pub steps: Vec<SopStepManifest>,
+}
+
+/// A `[[steps]]` entry in SOP.toml. Fields that are set override what the
+/// matching SOP.md step declares.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SopStepManifest {
+    /// Step number or id this entry applies to.
+    pub step: StepRef,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub next: Vec<StepTransition>,
+    #[serde(default)]
+    pub on_failure: Option<String>,
+    /// Branches of a parallel step; setting this makes the step `parallel`.
+    #[serde(default)]
+    pub parallel: Vec<String>,
+    #[serde(default)]
+    pub join: Option<SopJoin>,
+    #[serde(default)]
+    pub retries: Option<u32>,
+    #[serde(default)]
+    pub max_visits: Option<u32>,
+    #[serde(default)]
+    pub schema: Option<StepSchema>,
+}
+
+/// Reference to a step by number or id.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum StepRef {
+    Number(u32),
+    Id(String),
+}
+
+impl fmt::Display for StepRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Id(id) => write!(f, "{id}"),
+        }
+    }
 }
 
 /// The `[sop]` table in SOP.toml.
@@ -227,6 +481,7 @@ fn default_sop_version() -> String {
 
 /// The source type of an incoming event that may trigger an SOP.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SopTriggerSource {
     Mqtt,
@@ -250,6 +505,7 @@ impl fmt::Display for SopTriggerSource {
 
 /// An incoming event that may trigger one or more SOPs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct SopEvent {
     pub source: SopTriggerSource,
     /// Topic, path, or signal identifier (depends on source type).
@@ -266,6 +522,7 @@ pub struct SopEvent {
 
 /// Status of an SOP execution run.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SopRunStatus {
     Pending,
@@ -294,6 +551,7 @@ impl fmt::Display for SopRunStatus {
 
 /// Result status of a single step execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SopStepStatus {
     Completed,
@@ -313,6 +571,7 @@ impl fmt::Display for SopStepStatus {
 
 /// Result of executing a single SOP step.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct SopStepResult {
     pub step_number: u32,
     pub status: SopStepStatus,
@@ -323,6 +582,7 @@ pub struct SopStepResult {
 
 /// A full SOP execution run (from trigger to completion).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct SopRun {
     pub run_id: String,
     pub sop_name: String,
@@ -339,6 +599,12 @@ pub struct SopRun {
     /// Number of LLM calls saved by deterministic execution in this run.
     #[serde(default)]
     pub llm_calls_saved: u64,
+    /// How many times each step has been entered, bounded by its visit limit.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub step_visits: HashMap<u32, u32>,
+    /// Branches of the current parallel step still awaiting a result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_branches: Vec<u32>,
 }
 
 // ── Deterministic workflow state (persistence + resume) ──────────
@@ -369,6 +635,7 @@ pub struct DeterministicRunState {
 
 /// Tracks how many LLM round-trips were saved by deterministic execution.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DeterministicSavings {
     /// Total LLM calls saved across all deterministic runs.
     pub total_llm_calls_saved: u64,
@@ -405,6 +672,16 @@ pub enum SopRunAction {
         step: SopStep,
         state_file: PathBuf,
     },
+    /// A parallel step fanned out: run every branch action, reporting each
+    /// result against its own step number.
+    Parallel {
+        run_id: String,
+        step: SopStep,
+        branches: Vec<SopRunAction>,
+    },
+    /// A branch result was recorded; the parallel step is still waiting on
+    /// the listed branch steps.
+    AwaitBranches { run_id: String, pending: Vec<u32> },
     /// The SOP run completed successfully.
     Completed { run_id: String, sop_name: String },
     /// The SOP run failed.
@@ -628,6 +905,8 @@ path = "/sop/test"
             }],
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         let json = serde_json::to_string(&run).unwrap();
         let parsed: SopRun = serde_json::from_str(&json).unwrap();
@@ -636,4 +915,91 @@ path = "/sop/test"
         assert_eq!(parsed.step_results.len(), 1);
         assert_eq!(parsed.step_results[0].status, SopStepStatus::Completed);
     }
+
+    #[test]
+    fn step_routing_defaults_and_roundtrip() {
+        let step: SopStep =
+            serde_json::from_str(r#"{"number": 1, "title": "Check", "body": "Verify readings"}"#)
+                .unwrap();
+        assert!(step.id.is_none());
+        assert!(step.next.is_empty());
+        assert_eq!(step.join, SopJoin::All);
+        assert_eq!(step.retries, 0);
+        assert_eq!(step.key(), "1");
+
+        // Unset routing fields stay out of the serialized form
+        let json = serde_json::to_value(&step).unwrap();
+        assert!(json.get("next").is_none());
+        assert!(json.get("retries").is_none());
+    }
+
+    #[test]
+    fn output_schema_validation() {
+        let schema = StepSchema {
+            input: None,
+            output: Some(serde_json::json!({
+                "type": "object",
+                "required": ["level"],
+                "properties": {
+                    "level": { "enum": ["low", "high"] },
+                    "readings": { "type": "array", "items": { "type": "number" } },
+                },
+            })),
+        };
+        assert!(
+            schema
+                .validate_output(&serde_json::json!({"level": "high", "readings": [1, 2.5]}))
+                .is_ok()
+        );
+        let err = schema
+            .validate_output(&serde_json::json!({"level": "medium"}))
+            .unwrap_err();
+        assert!(err.starts_with("$.level"), "{err}");
+        let err = schema
+            .validate_output(&serde_json::json!({"level": "low", "readings": ["x"]}))
+            .unwrap_err();
+        assert_eq!(err, "$.readings[0]: expected number");
+        assert!(schema.validate_output(&serde_json::json!("text")).is_err());
+    }
+
+    #[test]
+    fn sop_resolves_targets_and_skips_branches() {
+        let step = |number: u32, id: Option<&str>| SopStep {
+            number,
+            title: format!("Step {number}"),
+            body: String::new(),
+            suggested_tools: vec![],
+            requires_confirmation: false,
+            kind: SopStepKind::Execute,
+            schema: None,
+            id: id.map(String::from),
+            next: Vec::new(),
+            on_failure: None,
+            branches: Vec::new(),
+            join: SopJoin::default(),
+            retries: 0,
+            max_visits: None,
+        };
+        let mut fan = step(1, Some("fan"));
+        fan.kind = SopStepKind::Parallel;
+        fan.branches = vec!["2".into(), "b".into()];
+        let sop = Sop {
+            name: "s".into(),
+            description: "d".into(),
+            version: "1".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![],
+            steps: vec![fan, step(2, None), step(3, Some("b")), step(4, None)],
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            location: None,
+            deterministic: false,
+        };
+        assert_eq!(sop.resolve_target("b"), Some(3));
+        assert_eq!(sop.resolve_target("2"), Some(2));
+        assert_eq!(sop.resolve_target("missing"), None);
+        assert!(sop.is_branch(3));
+        assert_eq!(sop.following_step(1), Some(4));
+    }
 }
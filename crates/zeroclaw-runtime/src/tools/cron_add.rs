@@ -1,5 +1,7 @@
+use crate::calendar::{Calendar, CalendarRule};
 use crate::cron::{
-    self, DeliveryConfig, JobType, Schedule, SessionTarget, deserialize_maybe_stringified,
+    self, CronJobPatch, DeliveryConfig, JobDependency, JobType, OverlapPolicy, RetryPolicy,
+    Schedule, SessionTarget, deserialize_maybe_stringified,
 };
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -163,6 +165,50 @@ impl Tool for CronAddTool {
                     "type": "boolean",
                     "description": "If true, the job is automatically deleted after its first successful run. Defaults to true for 'at' schedules."
                 },
+                "depends_on": {
+                    "type": "array",
+                    "description": "Optional upstream jobs. The job runs as soon as every upstream job has finished with the given outcome since this job last ran; on its own schedule it only runs if that already holds.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "job_id": { "type": "string", "description": "ID of an existing cron job" },
+                            "on": {
+                                "type": "string",
+                                "enum": ["success", "failure", "completion"],
+                                "description": "Upstream outcome that releases this job. Defaults to 'success'."
+                            }
+                        },
+                        "required": ["job_id"]
+                    }
+                },
+                "retry": {
+                    "type": "object",
+                    "description": "Optional retry policy for failed runs; overrides the global scheduler retries. Attempts are recorded in the run history.",
+                    "properties": {
+                        "max_attempts": { "type": "integer", "minimum": 1, "description": "Total attempts including the first run" },
+                        "backoff_ms": { "type": "integer", "description": "Delay before the first retry, doubled on each further attempt. Defaults to 1000." },
+                        "max_backoff_ms": { "type": "integer", "description": "Upper bound for the retry delay. Defaults to 60000." }
+                    },
+                    "required": ["max_attempts"]
+                },
+                "overlap": {
+                    "type": "string",
+                    "enum": ["skip", "queue", "kill_previous"],
+                    "description": "What to do when the job comes due while its previous run is still going: 'skip' the new run (default), 'queue' it until the previous run finishes, or 'kill_previous' and start fresh"
+                },
+                "calendar": {
+                    "type": "object",
+                    "description": "Optional named calendar from [calendars] that filters run dates, e.g. to skip holidays or move runs to the next business day",
+                    "properties": {
+                        "name": { "type": "string", "description": "Calendar name" },
+                        "mode": {
+                            "type": "string",
+                            "enum": ["exclude", "include", "business_days", "next_business_day"],
+                            "description": "'exclude' skips dates in the calendar (default), 'include' runs only on them, 'business_days' skips weekends and calendar dates, 'next_business_day' moves such runs to the next business day"
+                        }
+                    },
+                    "required": ["name"]
+                },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -248,6 +294,81 @@ impl Tool for CronAddTool {
             },
             None => None,
         };
+        let depends_on = match args.get("depends_on") {
+            Some(v) => match deserialize_maybe_stringified::<Vec<JobDependency>>(v) {
+                Ok(deps) => deps,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid depends_on: {e}")),
+                    });
+                }
+            },
+            None => Vec::new(),
+        };
+        let retry = match args.get("retry") {
+            Some(v) => match deserialize_maybe_stringified::<RetryPolicy>(v) {
+                Ok(policy) if policy.max_attempts >= 1 => Some(policy),
+                Ok(_) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("Invalid retry: max_attempts must be at least 1".to_string()),
+                    });
+                }
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid retry: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+        let overlap = match args.get("overlap") {
+            Some(v) => match serde_json::from_value::<OverlapPolicy>(v.clone()) {
+                Ok(policy) => Some(policy),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid overlap: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+        let calendar = match args.get("calendar") {
+            Some(v) => match deserialize_maybe_stringified::<CalendarRule>(v) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid calendar: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+        if let Some(rule) = &calendar
+            && let Err(e) = Calendar::load(&self.config, &rule.name)
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            });
+        }
+        if let Err(e) = cron::validate_dependencies(&self.config, None, &depends_on) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            });
+        }
 
         let result = match job_type {
             JobType::Shell => {
@@ -351,6 +472,30 @@ impl Tool for CronAddTool {
             }
         };
 
+        // Dependencies and policies are stored with a follow-up patch so the
+        // job constructors keep their existing signatures.
+        let result = match result {
+            Ok(job)
+                if !depends_on.is_empty()
+                    || retry.is_some()
+                    || overlap.is_some()
+                    || calendar.is_some() =>
+            {
+                cron::update_job(
+                    &self.config,
+                    &job.id,
+                    CronJobPatch {
+                        depends_on: Some(depends_on),
+                        retry,
+                        overlap,
+                        calendar,
+                        ..CronJobPatch::default()
+                    },
+                )
+            }
+            other => other,
+        };
+
         match result {
             Ok(job) => Ok(ToolResult {
                 success: true,
@@ -361,7 +506,11 @@ impl Tool for CronAddTool {
                     "schedule": job.schedule,
                     "next_run": job.next_run,
                     "enabled": job.enabled,
-                    "allowed_tools": job.allowed_tools
+                    "allowed_tools": job.allowed_tools,
+                    "depends_on": job.depends_on,
+                    "retry": job.retry,
+                    "overlap": job.overlap,
+                    "calendar": job.calendar
                 }))?,
                 error: None,
             }),
@@ -734,6 +883,94 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn persists_dependencies_retry_and_overlap() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let upstream = cron::add_job(&cfg, "0 2 * * *", "echo backup").unwrap();
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 3 * * *" },
+                "command": "echo verify",
+                "depends_on": [{ "job_id": upstream.id, "on": "completion" }],
+                "retry": { "max_attempts": 3, "backoff_ms": 500 },
+                "overlap": "queue"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let id = serde_json::from_str::<serde_json::Value>(&result.output).unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let job = cron::get_job(&cfg, &id).unwrap();
+        assert_eq!(job.depends_on[0].job_id, upstream.id);
+        assert_eq!(job.depends_on[0].on, cron::DependencyCondition::Completion);
+        assert_eq!(job.retry.unwrap().backoff_ms, 500);
+        assert_eq!(job.overlap, OverlapPolicy::Queue);
+    }
+
+    #[tokio::test]
+    async fn applies_calendar_and_rejects_unknown_ones() {
+        let tmp = TempDir::new().unwrap();
+        let mut cfg = (*test_config(&tmp).await).clone();
+        cfg.calendars.insert(
+            "holidays".into(),
+            zeroclaw_config::schema::CalendarConfig {
+                dates: vec!["2099-12-25".into()],
+                ..Default::default()
+            },
+        );
+        let cfg = Arc::new(cfg);
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 9 * * *" },
+                "command": "echo report",
+                "calendar": { "name": "holidays", "mode": "next_business_day" }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["calendar"]["mode"], "next_business_day");
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 9 * * *" },
+                "command": "echo report",
+                "calendar": { "name": "missing" }
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown calendar"));
+        assert_eq!(cron::list_jobs(&cfg).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_dependency() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 3 * * *" },
+                "command": "echo verify",
+                "depends_on": [{ "job_id": "missing" }]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not an existing cron job"));
+        assert!(cron::list_jobs(&cfg).unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn delivery_schema_includes_matrix_channel() {
         let tmp = TempDir::new().unwrap();
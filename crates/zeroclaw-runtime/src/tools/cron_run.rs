@@ -115,7 +115,7 @@ impl Tool for CronRunTool {
         }
 
         let started_at = Utc::now();
-        let (mut success, output) =
+        let (mut success, output, attempts) =
             Box::pin(cron::scheduler::execute_job_now(&self.config, &job)).await;
         let finished_at = Utc::now();
         let duration_ms = (finished_at - started_at).num_milliseconds();
@@ -148,8 +148,10 @@ impl Tool for CronRunTool {
             status,
             Some(&output),
             duration_ms,
+            attempts,
         );
         let _ = cron::record_last_run(&self.config, &job.id, finished_at, success, &output);
+        let _ = cron::trigger_dependents(&self.config, &job.id);
 
         Ok(ToolResult {
             success,
@@ -157,6 +159,7 @@ impl Tool for CronRunTool {
                 "job_id": job.id,
                 "status": status,
                 "duration_ms": duration_ms,
+                "attempts": attempts,
                 "output": output
             }))?,
             error: if success {
@@ -27,6 +27,7 @@ struct RunView {
     status: String,
     output: Option<String>,
     duration_ms: Option<i64>,
+    attempts: u32,
 }
 
 #[async_trait]
@@ -87,6 +88,7 @@ impl Tool for CronRunsTool {
                         status: run.status,
                         output: run.output.map(|out| truncate(&out, MAX_RUN_OUTPUT_CHARS)),
                         duration_ms: run.duration_ms,
+                        attempts: run.attempts,
                     })
                     .collect();
 
@@ -149,6 +151,7 @@ mod tests {
             "ok",
             Some(&long_output),
             1,
+            1,
         )
         .unwrap();
 
@@ -163,6 +163,50 @@ impl Tool for CronUpdateTool {
                                     "description": "If true, a delivery failure does not fail the job itself. Defaults to true."
                                 }
                             }
+                        },
+                        "depends_on": {
+                            "type": "array",
+                            "description": "Replaces the job's upstream dependencies. An empty list removes them.",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "job_id": { "type": "string", "description": "ID of an existing cron job" },
+                                    "on": {
+                                        "type": "string",
+                                        "enum": ["success", "failure", "completion"],
+                                        "description": "Upstream outcome that releases this job. Defaults to 'success'."
+                                    }
+                                },
+                                "required": ["job_id"]
+                            }
+                        },
+                        "retry": {
+                            "type": "object",
+                            "description": "Replaces the retry policy. Set max_attempts to 0 to fall back to the global scheduler retries.",
+                            "properties": {
+                                "max_attempts": { "type": "integer", "minimum": 0, "description": "Total attempts including the first run" },
+                                "backoff_ms": { "type": "integer", "description": "Delay before the first retry, doubled on each further attempt. Defaults to 1000." },
+                                "max_backoff_ms": { "type": "integer", "description": "Upper bound for the retry delay. Defaults to 60000." }
+                            },
+                            "required": ["max_attempts"]
+                        },
+                        "overlap": {
+                            "type": "string",
+                            "enum": ["skip", "queue", "kill_previous"],
+                            "description": "What to do when the job comes due while its previous run is still going"
+                        },
+                        "calendar": {
+                            "type": "object",
+                            "description": "Named calendar from [calendars] that filters run dates. An empty name removes the calendar.",
+                            "properties": {
+                                "name": { "type": "string", "description": "Calendar name" },
+                                "mode": {
+                                    "type": "string",
+                                    "enum": ["exclude", "include", "business_days", "next_business_day"],
+                                    "description": "How the calendar applies. Defaults to 'exclude'."
+                                }
+                            },
+                            "required": ["name"]
                         }
                     }
                 },
@@ -415,6 +459,10 @@ mod tests {
             "delete_after_run",
             "schedule",
             "delivery",
+            "depends_on",
+            "retry",
+            "overlap",
+            "calendar",
         ] {
             assert!(
                 patch_props.contains_key(*field),
@@ -584,4 +632,39 @@ mod tests {
             Some(vec!["file_read".into(), "web_search".into()])
         );
     }
+
+    #[tokio::test]
+    async fn patch_sets_dependencies_and_overlap() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let upstream = cron::add_job(&cfg, "0 2 * * *", "echo backup").unwrap();
+        let job = cron::add_job(&cfg, "0 3 * * *", "echo verify").unwrap();
+        let tool = CronUpdateTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "job_id": job.id,
+                "patch": {
+                    "depends_on": [{ "job_id": upstream.id, "on": "failure" }],
+                    "overlap": "kill_previous"
+                }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let stored = cron::get_job(&cfg, &job.id).unwrap();
+        assert_eq!(stored.depends_on[0].on, cron::DependencyCondition::Failure);
+        assert_eq!(stored.overlap, cron::OverlapPolicy::KillPrevious);
+
+        let result = tool
+            .execute(json!({
+                "job_id": upstream.id,
+                "patch": { "depends_on": [{ "job_id": job.id }] }
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("cycle"));
+    }
 }
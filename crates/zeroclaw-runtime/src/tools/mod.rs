@@ -721,9 +721,15 @@ pub fn all_tools_with_runtime(
     // created via /ws/chat was invisible to `sessions_list` /
     // `sessions_history`. Routing both call sites through the factory
     // closes that gap and honors the operator's configured backend.
-    if let Ok(backend) =
-        zeroclaw_infra::make_session_backend(workspace_dir, &config.channels.session_backend)
-    {
+    if let Ok(backend) = zeroclaw_infra::make_session_backend(
+        workspace_dir,
+        &config.channels.session_backend,
+        zeroclaw_infra::PostgresTarget {
+            db_url: config.storage.provider.config.db_url.as_deref(),
+            schema: &config.storage.provider.config.schema,
+            connect_timeout_secs: config.storage.provider.config.connect_timeout_secs,
+        },
+    ) {
         tool_arcs.push(Arc::new(SessionsCurrentTool::new(backend.clone())));
         tool_arcs.push(Arc::new(SessionsListTool::new(backend.clone())));
         tool_arcs.push(Arc::new(SessionsHistoryTool::new(
@@ -769,10 +775,7 @@ pub fn all_tools_with_runtime(
     )));
 
     // SOP tools (registered when sops_dir is configured)
-    if root_config.sop.sops_dir.is_some() {
-        let mut engine = crate::sop::SopEngine::new(root_config.sop.clone());
-        engine.reload(workspace_dir);
-        let sop_engine = Arc::new(std::sync::Mutex::new(engine));
+    if let Some(sop_engine) = crate::sop::shared_engine(root_config) {
         tool_arcs.push(Arc::new(SopListTool::new(Arc::clone(&sop_engine))));
         tool_arcs.push(Arc::new(SopExecuteTool::new(Arc::clone(&sop_engine))));
         tool_arcs.push(Arc::new(SopAdvanceTool::new(Arc::clone(&sop_engine))));
@@ -61,6 +61,10 @@ impl Tool for SopAdvanceTool {
                 "output": {
                     "type": "string",
                     "description": "Brief summary of what happened in this step"
+                },
+                "step": {
+                    "type": "integer",
+                    "description": "Step number being reported. Required for parallel branches; defaults to the current step"
                 }
             },
             "required": ["run_id", "status", "output"]
@@ -109,10 +113,15 @@ impl Tool for SopAdvanceTool {
                 .get_run(run_id)
                 .map(|r| r.current_step)
                 .ok_or_else(|| anyhow::anyhow!("Run not found: {run_id}"))?;
+            let step_number = args
+                .get("step")
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u32::try_from(n).ok())
+                .unwrap_or(current_step);
 
             let now = now_iso8601();
             let step_result = SopStepResult {
-                step_number: current_step,
+                step_number,
                 status: step_status,
                 output: output.to_string(),
                 started_at: now.clone(),
@@ -193,6 +202,19 @@ impl Tool for SopAdvanceTool {
                             step.title
                         )
                     }
+                    SopRunAction::Parallel {
+                        run_id, branches, ..
+                    } => {
+                        format!(
+                            "Step recorded. Run {run_id} fanned out:\n\n{}",
+                            super::sop_execute::describe_branches(&branches)
+                        )
+                    }
+                    SopRunAction::AwaitBranches { run_id, pending } => {
+                        format!(
+                            "Branch recorded. Run {run_id} is still waiting on steps {pending:?}."
+                        )
+                    }
                 };
                 Ok(ToolResult {
                     success: true,
@@ -236,6 +258,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
                 SopStep {
                     number: 2,
@@ -245,6 +274,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
             ],
             cooldown_secs: 0,
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use serde_json::json;
 use tracing::warn;
 
+use crate::sop::simulate::{StepStub, simulate};
 use crate::sop::types::{SopEvent, SopRunAction, SopTriggerSource};
 use crate::sop::{SopAuditLogger, SopEngine};
 use zeroclaw_api::tool::{Tool, ToolResult};
@@ -26,6 +28,47 @@ impl SopExecuteTool {
         self.audit = Some(audit);
         self
     }
+
+    /// Simulate a run, approving every gate, and return its trace.
+    fn dry_run(
+        &self,
+        sop_name: &str,
+        event: SopEvent,
+        stubs: Option<&serde_json::Value>,
+    ) -> anyhow::Result<ToolResult> {
+        let stubs: HashMap<String, StepStub> = match stubs {
+            Some(v) => serde_json::from_value(v.clone())
+                .map_err(|e| anyhow::anyhow!("Invalid 'stubs' parameter: {e}"))?,
+            None => HashMap::new(),
+        };
+        let sop = {
+            let engine = self
+                .engine
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Engine lock poisoned: {e}"))?;
+            engine.get_sop(sop_name).cloned()
+        };
+        let Some(sop) = sop else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("SOP not found: {sop_name}")),
+            });
+        };
+
+        match simulate(&sop, event, &stubs, &mut |_| true) {
+            Ok(trace) => Ok(ToolResult {
+                success: true,
+                output: trace.to_string(),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to simulate SOP: {e}")),
+            }),
+        }
+    }
 }
 
 #[async_trait]
@@ -35,7 +78,7 @@ impl Tool for SopExecuteTool {
     }
 
     fn description(&self) -> &str {
-        "Manually trigger a Standard Operating Procedure (SOP) by name. Returns the run ID and first step instruction. Use sop_list to see available SOPs."
+        "Manually trigger a Standard Operating Procedure (SOP) by name. Returns the run ID and first step instruction. Set dry_run to simulate the SOP instead and get its would-be execution trace. Use sop_list to see available SOPs."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -49,6 +92,14 @@ impl Tool for SopExecuteTool {
                 "payload": {
                     "type": "string",
                     "description": "Optional trigger payload (JSON string)"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Simulate the run without executing anything and return the would-be execution trace"
+                },
+                "stubs": {
+                    "type": "object",
+                    "description": "Dry run only: recorded step results keyed by step id or number, each {\"output\": <json>} or {\"fail\": \"reason\"}"
                 }
             },
             "required": ["name"]
@@ -73,6 +124,14 @@ impl Tool for SopExecuteTool {
             timestamp: now_iso8601(),
         };
 
+        if args
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return self.dry_run(sop_name, event, args.get("stubs"));
+        }
+
         // Lock engine, start run, snapshot run for audit, then drop lock
         let (action, run_snapshot) = {
             let mut engine = self
@@ -129,6 +188,17 @@ impl Tool for SopExecuteTool {
                             step.title
                         )
                     }
+                    SopRunAction::Parallel {
+                        run_id, branches, ..
+                    } => {
+                        format!(
+                            "SOP run started: {run_id}\n\n{}",
+                            describe_branches(&branches)
+                        )
+                    }
+                    SopRunAction::AwaitBranches { run_id, pending } => {
+                        format!("SOP run started: {run_id} (waiting on branches {pending:?})")
+                    }
                 };
                 Ok(ToolResult {
                     success: true,
@@ -153,8 +223,30 @@ fn action_run_id(action: &SopRunAction) -> Option<&str> {
         | SopRunAction::Completed { run_id, .. }
         | SopRunAction::Failed { run_id, .. }
         | SopRunAction::DeterministicStep { run_id, .. }
-        | SopRunAction::CheckpointWait { run_id, .. } => Some(run_id),
+        | SopRunAction::CheckpointWait { run_id, .. }
+        | SopRunAction::Parallel { run_id, .. }
+        | SopRunAction::AwaitBranches { run_id, .. } => Some(run_id),
+    }
+}
+
+/// List the branches a parallel step started, with each branch's context.
+pub(crate) fn describe_branches(branches: &[SopRunAction]) -> String {
+    let mut out = format!(
+        "{} parallel branches started. Report each result with sop_advance and its step number.",
+        branches.len()
+    );
+    for branch in branches {
+        match branch {
+            SopRunAction::ExecuteStep { step, context, .. } => {
+                out.push_str(&format!("\n\n--- Step {} ---\n{context}", step.number));
+            }
+            SopRunAction::DeterministicStep { step, .. } => {
+                out.push_str(&format!("\n\n--- Step {}: {} ---", step.number, step.title));
+            }
+            _ => {}
+        }
     }
+    out
 }
 
 use crate::sop::engine::now_iso8601;
@@ -183,6 +275,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
                 SopStep {
                     number: 2,
@@ -192,6 +291,13 @@ mod tests {
                     requires_confirmation: false,
                     kind: SopStepKind::default(),
                     schema: None,
+                    id: None,
+                    next: Vec::new(),
+                    on_failure: None,
+                    branches: Vec::new(),
+                    join: SopJoin::default(),
+                    retries: 0,
+                    max_visits: None,
                 },
             ],
             cooldown_secs: 0,
@@ -226,6 +332,25 @@ mod tests {
         assert!(result.output.contains("waiting for approval"));
     }
 
+    #[tokio::test]
+    async fn dry_run_returns_trace_without_starting_a_run() {
+        let engine = engine_with_sops(vec![test_sop("test-sop", SopExecutionMode::Supervised)]);
+        let tool = SopExecuteTool::new(Arc::clone(&engine));
+        let result = tool
+            .execute(json!({
+                "name": "test-sop",
+                "dry_run": true,
+                "stubs": { "1": { "fail": "disk full" } }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("[approval] Step one (approved)"));
+        assert!(result.output.contains("tools: shell"));
+        assert!(result.output.contains("Outcome: failed"));
+        assert!(engine.lock().unwrap().active_runs().is_empty());
+    }
+
     #[tokio::test]
     async fn execute_unknown_sop() {
         let engine = engine_with_sops(vec![]);
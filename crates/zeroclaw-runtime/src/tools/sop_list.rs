@@ -139,6 +139,13 @@ mod tests {
                 requires_confirmation: false,
                 kind: SopStepKind::default(),
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
@@ -239,6 +239,7 @@ mod tests {
     use super::*;
     use crate::sop::engine::SopEngine;
     use crate::sop::types::*;
+    use std::collections::HashMap;
     use zeroclaw_config::schema::SopConfig;
 
     fn test_sop(name: &str) -> Sop {
@@ -257,6 +258,13 @@ mod tests {
                 requires_confirmation: false,
                 kind: SopStepKind::default(),
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
@@ -381,6 +389,8 @@ mod tests {
             }],
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         collector.record_run_complete(&run);
 
@@ -417,6 +427,8 @@ mod tests {
             }],
             waiting_since: None,
             llm_calls_saved: 0,
+            step_visits: HashMap::new(),
+            pending_branches: Vec::new(),
         };
         collector.record_run_complete(&run);
 
@@ -63,6 +63,46 @@ pub fn truncate_with_ellipsis(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Fill `{{key}}` placeholders in a prompt template.
+///
+/// `resolve` receives each trimmed key and returns its text; `None` renders
+/// empty. An unterminated `{{` is kept verbatim.
+pub fn render_template(template: &str, mut resolve: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let Some(close) = after.find("}}") else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        if let Some(value) = resolve(after[..close].trim()) {
+            out.push_str(&value);
+        }
+        rest = &after[close + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Text of the field at a dotted path (`issue.labels.0.name`) in a JSON
+/// value, for prompt templates. Numeric segments index arrays; strings
+/// render unquoted, and `null` or a missing field renders as `None`.
+pub fn json_path_text(json: &serde_json::Value, path: &str) -> Option<String> {
+    let value =
+        path.split('.')
+            .try_fold(json, |value, segment| match segment.parse::<usize>() {
+                Ok(index) if value.is_array() => value.get(index),
+                _ => value.get(segment),
+            })?;
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
 /// Utility enum for handling optional values.
 pub enum MaybeSet<T> {
     Set(T),
@@ -74,6 +114,19 @@ pub enum MaybeSet<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn render_template_resolves_keys_and_keeps_unterminated_braces() {
+        let json = serde_json::json!({"a": {"b": [1, "two", null]}});
+        let rendered = render_template("{{ x }}-{{a.b.0}}-{{a.b.1}}-{{a.b.2}}-{{left", |key| {
+            if key == "x" {
+                Some("X".into())
+            } else {
+                json_path_text(&json, key)
+            }
+        });
+        assert_eq!(rendered, "X-1-two--{{left");
+    }
+
     #[test]
     fn test_truncate_ascii_no_truncation() {
         // ASCII string shorter than limit - no change
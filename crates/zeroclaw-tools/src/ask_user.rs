@@ -12,6 +12,7 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
 use zeroclaw_api::channel::{Channel, ChannelMessage, SendMessage};
+use zeroclaw_api::rich::{self, ReplyResolution, RichButton, RichMessage};
 use zeroclaw_api::tool::{Tool, ToolResult};
 use zeroclaw_config::policy::SecurityPolicy;
 use zeroclaw_config::policy::ToolOperation;
@@ -90,7 +91,7 @@ impl Tool for AskUserTool {
                 "choices": {
                     "type": "array",
                     "items": { "type": "string" },
-                    "description": "Optional list of choices (renders as buttons on Telegram, numbered list on CLI)"
+                    "description": "Optional list of choices (renders as buttons on Slack, Telegram and Discord, numbered list elsewhere)"
                 },
                 "timeout_secs": {
                     "type": "integer",
@@ -222,9 +223,25 @@ impl Tool for AskUserTool {
             });
         }
 
-        // Format and send the question
-        let text = format_question(&question, choices.as_deref());
-        let msg = SendMessage::new(&text, "");
+        // Choices go out as a rich message so interactive channels render
+        // buttons; its plain-text fallback is the numbered list. Subscribe
+        // before sending so an immediate click is not missed.
+        let rich_prompt = choices.as_ref().filter(|c| !c.is_empty()).map(|choices| {
+            RichMessage::new()
+                .section(format_question(&question, None))
+                .buttons(
+                    choices
+                        .iter()
+                        .enumerate()
+                        .map(|(i, choice)| RichButton::new(format!("choice_{i}"), choice))
+                        .collect(),
+                )
+        });
+        let mut subscription = rich_prompt.as_ref().map(rich::subscribe);
+        let msg = match rich_prompt.clone() {
+            Some(prompt) => SendMessage::rich(prompt, ""),
+            None => SendMessage::new(format_question(&question, None), ""),
+        };
         if let Err(e) = channel.send(&msg).await {
             return Ok(ToolResult {
                 success: false,
@@ -242,15 +259,33 @@ impl Tool for AskUserTool {
         let listen_channel = Arc::clone(&channel);
         let listen_handle = tokio::spawn(async move { listen_channel.listen(tx).await });
 
-        let response = tokio::time::timeout(timeout, rx.recv()).await;
+        // Whichever arrives first: a button click or a typed reply. A typed
+        // number or label resolves to the choice label, like a click does.
+        let typed = |content: String| match rich_prompt
+            .as_ref()
+            .map(|prompt| prompt.resolve_reply(&content))
+        {
+            Some(ReplyResolution::Choice(choice)) => choice.label,
+            _ => content,
+        };
+        let response = tokio::time::timeout(timeout, async {
+            match subscription.as_mut() {
+                Some(subscription) => tokio::select! {
+                    Some(interaction) = subscription.recv() => Some(interaction.value),
+                    msg = rx.recv() => msg.map(|m| typed(m.content)),
+                },
+                None => rx.recv().await.map(|m| typed(m.content)),
+            }
+        })
+        .await;
 
         // Abort the listener once we have a response or timeout
         listen_handle.abort();
 
         match response {
-            Ok(Some(msg)) => Ok(ToolResult {
+            Ok(Some(answer)) => Ok(ToolResult {
                 success: true,
-                output: msg.content,
+                output: answer,
                 error: None,
             }),
             Ok(None) => Ok(ToolResult {
@@ -357,6 +392,34 @@ mod tests {
         }
     }
 
+    /// A stub channel that renders rich prompts natively and "clicks" the
+    /// second button as soon as the prompt is sent.
+    struct ClickingChannel;
+
+    #[async_trait]
+    impl Channel for ClickingChannel {
+        fn name(&self) -> &str {
+            "slack"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            let prompt = message
+                .rich
+                .as_ref()
+                .expect("choices should be sent as rich");
+            rich::dispatch(&prompt.id, rich::ActionRef::Choice(1), "U1", "slack");
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            tokio::time::sleep(std::time::Duration::from_secs(600)).await;
+            Ok(())
+        }
+    }
+
     fn make_tool_with_channels(channels: Vec<(&str, Arc<dyn Channel>)>) -> AskUserTool {
         let tool = AskUserTool::new(Arc::new(SecurityPolicy::default()));
         let map: HashMap<String, Arc<dyn Channel>> = channels
@@ -517,7 +580,25 @@ mod tests {
             .await
             .unwrap();
         assert!(result.success, "error: {:?}", result.error);
-        assert_eq!(result.output, "2");
+        assert_eq!(result.output, "Option B");
+    }
+
+    #[tokio::test]
+    async fn button_click_resolves_to_choice_label() {
+        let tool = make_tool_with_channels(vec![(
+            "slack",
+            Arc::new(ClickingChannel) as Arc<dyn Channel>,
+        )]);
+        let result = tool
+            .execute(json!({
+                "question": "Ship it?",
+                "choices": ["Yes", "No"],
+                "timeout_secs": 5
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "error: {:?}", result.error);
+        assert_eq!(result.output, "No");
     }
 
     #[tokio::test]
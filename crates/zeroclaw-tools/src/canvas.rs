@@ -31,6 +31,7 @@ pub const ALLOWED_CONTENT_TYPES: &[&str] = &["html", "svg", "markdown", "text"];
 
 /// A single canvas frame (one render).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CanvasFrame {
     /// Unique frame identifier.
     pub frame_id: String,
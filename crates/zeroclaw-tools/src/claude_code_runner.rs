@@ -15,6 +15,7 @@ const SAFE_ENV_VARS: &[&str] = &[
 
 /// Event payload received from Claude Code HTTP hooks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ClaudeCodeHookEvent {
     /// The session identifier (matches the tmux session name suffix).
     pub session_id: String,
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 /// Category of a discovered CLI tool.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub enum CliCategory {
     VersionControl,
     Language,
@@ -33,8 +34,10 @@ impl std::fmt::Display for CliCategory {
 
 /// A discovered CLI tool with metadata.
 #[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DiscoveredCli {
     pub name: String,
+    #[cfg_attr(feature = "schema-export", schemars(with = "String"))]
     pub path: PathBuf,
     pub version: Option<String>,
     pub category: CliCategory,
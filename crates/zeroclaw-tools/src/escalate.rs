@@ -42,7 +42,7 @@ impl EscalateToHumanTool {
     }
 
     /// Format the escalation message with urgency prefix.
-    fn format_message(urgency: &str, summary: &str, context: Option<&str>) -> String {
+    pub fn format_message(urgency: &str, summary: &str, context: Option<&str>) -> String {
         let prefix = match urgency {
             "low" => "\u{2139}\u{fe0f} [LOW]",
             "high" => "\u{1f534} [HIGH]",
@@ -66,6 +66,63 @@ impl EscalateToHumanTool {
         lines.join("\n")
     }
 
+    /// Escalate outside an agent turn (e.g. from a heartbeat escalation
+    /// ladder) through the same path as `execute`: the security gate, the
+    /// formatted message to `recipient` on `channel`, then the alert
+    /// channels for high/critical urgency. `channel` must be in the channel
+    /// map. Never waits for a reply.
+    pub async fn escalate(
+        &self,
+        channel: &str,
+        recipient: &str,
+        urgency: &str,
+        summary: &str,
+        context: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "escalate_to_human")
+            .map_err(|e| anyhow::anyhow!("Action blocked: {e}"))?;
+        if !VALID_URGENCY_LEVELS.contains(&urgency) {
+            anyhow::bail!(
+                "Invalid urgency '{urgency}'. Must be one of: {}",
+                VALID_URGENCY_LEVELS.join(", ")
+            );
+        }
+        let target = self
+            .channel_map
+            .read()
+            .get(channel)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Channel '{channel}' not found in channel map"))?;
+        let text = Self::format_message(urgency, summary, context);
+        self.deliver(channel, target.as_ref(), recipient, urgency, &text)
+            .await
+    }
+
+    /// Send `text` to `recipient` on `channel`, then alert the configured
+    /// alert channels for high/critical urgency.
+    async fn deliver(
+        &self,
+        channel_name: &str,
+        channel: &dyn Channel,
+        recipient: &str,
+        urgency: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        channel
+            .send(&SendMessage::new(text, recipient))
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to send escalation to channel '{channel_name}': {e}")
+            })?;
+
+        // Notify alert channels for high/critical urgency (best-effort)
+        if (urgency == "high" || urgency == "critical") && !self.alert_channels.is_empty() {
+            self.send_alerts(text).await;
+        }
+        Ok(())
+    }
+
     /// Send best-effort alerts to configured alert channels for high/critical urgency.
     async fn send_alerts(&self, text: &str) {
         // Collect Arc clones while holding the lock, then drop the guard before awaiting.
@@ -229,23 +286,18 @@ impl Tool for EscalateToHumanTool {
             });
         }
 
-        // Send the escalation message
-        let msg = SendMessage::new(&text, "");
-        if let Err(e) = channel.send(&msg).await {
+        // Send the escalation message and any alerts
+        if let Err(e) = self
+            .deliver(&channel_name, channel.as_ref(), "", urgency, &text)
+            .await
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(format!(
-                    "Failed to send escalation to channel '{channel_name}': {e}"
-                )),
+                error: Some(e.to_string()),
             });
         }
 
-        // Notify alert channels for high/critical urgency (non-blocking, best-effort)
-        if (urgency == "high" || urgency == "critical") && !self.alert_channels.is_empty() {
-            self.send_alerts(&text).await;
-        }
-
         if wait_for_response {
             // Block and wait for human response (same pattern as ask_user)
             let (tx, mut rx) = tokio::sync::mpsc::channel::<ChannelMessage>(1);
@@ -671,4 +723,37 @@ mod tests {
         assert_eq!(parsed["status"], "escalated");
         assert_eq!(parsed["urgency"], "high");
     }
+
+    // ── 11. escalate_targets_named_channel_and_alerts ──
+
+    #[tokio::test]
+    async fn escalate_targets_named_channel_and_alerts() {
+        let ops = Arc::new(SilentChannel::new("ops"));
+        let other = Arc::new(SilentChannel::new("other"));
+        let pager = Arc::new(SilentChannel::new("pager"));
+        let tool = EscalateToHumanTool::new(
+            Arc::new(SecurityPolicy::default()),
+            vec!["pager".to_string()],
+        );
+        *tool.channel_map.write() = HashMap::from([
+            ("ops".to_string(), Arc::clone(&ops) as Arc<dyn Channel>),
+            ("other".to_string(), Arc::clone(&other) as Arc<dyn Channel>),
+            ("pager".to_string(), Arc::clone(&pager) as Arc<dyn Channel>),
+        ]);
+
+        tool.escalate("ops", "#alerts", "critical", "Disk full", Some("db-1"))
+            .await
+            .unwrap();
+        assert_eq!(ops.sent.read().len(), 1);
+        assert!(ops.sent.read()[0].contains("[CRITICAL]"));
+        assert!(other.sent.read().is_empty());
+        assert_eq!(pager.sent.read().len(), 1);
+
+        assert!(
+            tool.escalate("missing", "", "low", "x", None)
+                .await
+                .is_err()
+        );
+        assert!(tool.escalate("ops", "", "urgent", "x", None).await.is_err());
+    }
 }
@@ -1,9 +1,10 @@
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use zeroclaw_api::channel::{Channel, SendMessage};
+use zeroclaw_api::rich::{self, RichButton, RichMessage};
 use zeroclaw_api::tool::{Tool, ToolResult};
 use zeroclaw_config::policy::SecurityPolicy;
 use zeroclaw_config::policy::ToolOperation;
@@ -66,6 +67,47 @@ pub fn format_text_poll(
     lines.join("\n")
 }
 
+/// Build the interactive form of a poll: the question plus one button per
+/// option. Button values are the option indices so duplicate labels stay
+/// distinguishable.
+fn build_rich_poll(question: &str, options: &[String]) -> RichMessage {
+    RichMessage::new()
+        .section(format!("\u{1F4CA} **Poll: {question}**"))
+        .buttons(
+            options
+                .iter()
+                .enumerate()
+                .map(|(i, option)| {
+                    RichButton::new(format!("poll_option_{i}"), option).with_value(i.to_string())
+                })
+                .collect(),
+        )
+}
+
+/// Tally button votes. In single-choice polls a voter's latest click replaces
+/// their earlier vote; in multi-select polls clicks toggle the option.
+fn tally_votes(votes: &[(String, usize)], option_count: usize, multi_select: bool) -> Vec<usize> {
+    let mut by_voter: HashMap<&str, BTreeSet<usize>> = HashMap::new();
+    for (voter, option) in votes {
+        let selected = by_voter.entry(voter.as_str()).or_default();
+        if multi_select {
+            if !selected.remove(option) {
+                selected.insert(*option);
+            }
+        } else {
+            selected.clear();
+            selected.insert(*option);
+        }
+    }
+    let mut counts = vec![0; option_count];
+    for option in by_voter.values().flatten() {
+        if let Some(count) = counts.get_mut(*option) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
 /// Validate the options array: 2-10 non-empty strings.
 fn validate_options(args: &serde_json::Value) -> Result<Vec<String>, String> {
     let arr = args
@@ -111,7 +153,7 @@ impl Tool for PollTool {
     }
 
     fn description(&self) -> &str {
-        "Create a poll in a messaging channel. For Telegram/Discord uses native polls; for other channels formats as a numbered text message with emoji reactions for voting."
+        "Create a poll in a messaging channel. Options render as buttons on channels with interactive UI (Slack, Telegram, Discord); other channels get a numbered text message with emoji reactions for voting. Set wait_for_votes to block until the poll closes and return the button tallies."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -144,6 +186,10 @@ impl Tool for PollTool {
                 "multi_select": {
                     "type": "boolean",
                     "description": "Allow multiple selections (default: false)"
+                },
+                "wait_for_votes": {
+                    "type": "boolean",
+                    "description": "Wait until the poll closes and return button vote tallies (default: false)"
                 }
             },
             "required": ["question", "options"]
@@ -193,6 +239,11 @@ impl Tool for PollTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        let wait_for_votes = args
+            .get("wait_for_votes")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         let requested_channel = args
             .get("channel")
             .and_then(|v| v.as_str())
@@ -235,7 +286,17 @@ impl Tool for PollTool {
 
         let poll_text = format_text_poll(&question, &options, duration_minutes, multi_select);
 
-        let msg = SendMessage::new(&poll_text, &recipient_id);
+        // When waiting for votes, channels with interactive UI render the
+        // options as buttons; the reaction-vote text stays as the fallback
+        // content for the rest. Without a waiter nobody would count a click,
+        // so the poll goes out as text only.
+        let mut msg = SendMessage::new(&poll_text, &recipient_id);
+        let mut subscription = None;
+        if wait_for_votes {
+            let rich_poll = build_rich_poll(&question, &options);
+            subscription = Some(rich::subscribe(&rich_poll));
+            msg.rich = Some(rich_poll);
+        }
         if let Err(e) = channel.send(&msg).await {
             return Ok(ToolResult {
                 success: false,
@@ -246,6 +307,43 @@ impl Tool for PollTool {
             });
         }
 
+        if let Some(subscription) = subscription.as_mut() {
+            let mut votes: Vec<(String, usize)> = Vec::new();
+            let deadline = tokio::time::sleep(std::time::Duration::from_secs(
+                duration_minutes.saturating_mul(60),
+            ));
+            tokio::pin!(deadline);
+            loop {
+                tokio::select! {
+                    () = &mut deadline => break,
+                    interaction = subscription.recv() => match interaction {
+                        Some(interaction) => {
+                            if let Ok(option) = interaction.value.parse::<usize>() {
+                                votes.push((interaction.sender, option));
+                            }
+                        }
+                        None => break,
+                    },
+                }
+            }
+            let counts = tally_votes(&votes, options.len(), multi_select);
+            let results: Vec<String> = options
+                .iter()
+                .zip(&counts)
+                .map(|(option, count)| format!("{option}: {count}"))
+                .collect();
+            return Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Poll closed on '{channel_name}':\n\
+                     Question: {question}\n\
+                     Results: {}",
+                    results.join(", ")
+                ),
+                error: None,
+            });
+        }
+
         let native_note = if is_native {
             " (native poll API available — text fallback used; trait extension needed for native support)"
         } else {
@@ -274,6 +372,8 @@ mod tests {
     struct StubChannel {
         name: String,
         sent: Arc<RwLock<Vec<String>>>,
+        /// Whether each sent message carried interactive buttons.
+        rich: Arc<RwLock<Vec<bool>>>,
     }
 
     impl StubChannel {
@@ -281,6 +381,7 @@ mod tests {
             Self {
                 name: name.to_string(),
                 sent: Arc::new(RwLock::new(Vec::new())),
+                rich: Arc::new(RwLock::new(Vec::new())),
             }
         }
     }
@@ -293,6 +394,7 @@ mod tests {
 
         async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
             self.sent.write().push(message.content.clone());
+            self.rich.write().push(message.rich.is_some());
             Ok(())
         }
 
@@ -380,6 +482,25 @@ mod tests {
         assert!(text.contains("single choice"));
     }
 
+    #[test]
+    fn rich_poll_buttons_carry_option_indices() {
+        let poll = build_rich_poll("Lunch?", &["Pizza".into(), "Sushi".into()]);
+        let values: Vec<String> = poll.choices().into_iter().map(|c| c.value).collect();
+        assert_eq!(values, ["0", "1"]);
+        assert!(poll.to_plain_text().contains("2. Sushi"));
+    }
+
+    #[test]
+    fn tally_votes_respects_single_and_multi_select() {
+        let votes = vec![
+            ("alice".to_string(), 0),
+            ("alice".to_string(), 1),
+            ("bob".to_string(), 1),
+        ];
+        assert_eq!(tally_votes(&votes, 2, false), vec![0, 2]);
+        assert_eq!(tally_votes(&votes, 2, true), vec![1, 2]);
+    }
+
     #[test]
     fn format_text_poll_multi_select_label() {
         let text = format_text_poll("Pick any", &["A".into(), "B".into()], 60, true);
@@ -446,6 +567,25 @@ mod tests {
         assert!(result.output.contains("Pizza"));
     }
 
+    #[tokio::test]
+    async fn buttons_are_attached_only_when_waiting_for_votes() {
+        let stub = Arc::new(StubChannel::new("slack"));
+        let rich = Arc::clone(&stub.rich);
+        let tool = PollTool::new(
+            Arc::new(SecurityPolicy::default()),
+            make_channel_map(vec![stub]),
+        );
+        let args = json!({ "question": "Lunch?", "options": ["Pizza", "Sushi"] });
+        tool.execute(args.clone()).await.unwrap();
+
+        let mut waiting = args;
+        waiting["wait_for_votes"] = json!(true);
+        waiting["duration_minutes"] = json!(0);
+        tool.execute(waiting).await.unwrap();
+
+        assert_eq!(*rich.read(), vec![false, true]);
+    }
+
     #[tokio::test]
     async fn execute_reports_unknown_channel() {
         let tool = default_tool();
@@ -0,0 +1,120 @@
+//! [`Channel`] registered on the chat agent so supervised-mode tool calls
+//! surface as inline prompts in the transcript instead of reading stdin
+//! (which raw mode owns).
+//!
+//! `request_approval` hands the UI loop a oneshot and parks until the
+//! operator presses a key. Dropping the prompt unanswered — the turn was
+//! cancelled or the UI exited — resolves to `Deny`. Free-form `send()`
+//! messages from tools are shown as transcript notices.
+
+use async_trait::async_trait;
+use tokio::sync::{mpsc, oneshot};
+use zeroclaw_api::channel::{
+    Channel, ChannelApprovalRequest, ChannelApprovalResponse, ChannelMessage, SendMessage,
+};
+
+/// Name the channel is registered under in the agent's channel handles.
+pub const CHANNEL_NAME: &str = "tui";
+
+/// Something the agent needs the UI loop to show.
+pub enum UiRequest {
+    Approval(ApprovalPrompt),
+    Message(String),
+}
+
+/// A pending tool approval. Answering consumes the reply sender.
+pub struct ApprovalPrompt {
+    pub tool_name: String,
+    pub arguments_summary: String,
+    pub reply: oneshot::Sender<ChannelApprovalResponse>,
+}
+
+pub struct TuiChannel {
+    tx: mpsc::Sender<UiRequest>,
+}
+
+impl TuiChannel {
+    pub fn new(tx: mpsc::Sender<UiRequest>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl Channel for TuiChannel {
+    fn name(&self) -> &str {
+        CHANNEL_NAME
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let _ = self
+            .tx
+            .send(UiRequest::Message(message.content.clone()))
+            .await;
+        Ok(())
+    }
+
+    async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        // Turns are driven by the UI loop, not by inbound channel messages.
+        Ok(())
+    }
+
+    async fn request_approval(
+        &self,
+        _recipient: &str,
+        request: &ChannelApprovalRequest,
+    ) -> anyhow::Result<Option<ChannelApprovalResponse>> {
+        let (reply, rx) = oneshot::channel();
+        let prompt = ApprovalPrompt {
+            tool_name: request.tool_name.clone(),
+            arguments_summary: request.arguments_summary.clone(),
+            reply,
+        };
+        if self.tx.send(UiRequest::Approval(prompt)).await.is_err() {
+            // UI loop is gone; fall through to the manager's auto-deny.
+            return Ok(None);
+        }
+        Ok(Some(rx.await.unwrap_or(ChannelApprovalResponse::Deny)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ChannelApprovalRequest {
+        ChannelApprovalRequest {
+            tool_name: "shell".into(),
+            arguments_summary: "command: ls".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn approval_round_trips_through_the_ui_queue() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let channel = TuiChannel::new(tx);
+        let ui = tokio::spawn(async move {
+            let Some(UiRequest::Approval(prompt)) = rx.recv().await else {
+                panic!("expected an approval prompt");
+            };
+            assert_eq!(prompt.tool_name, "shell");
+            prompt
+                .reply
+                .send(ChannelApprovalResponse::AlwaysApprove)
+                .unwrap();
+        });
+        let decision = channel.request_approval("", &request()).await.unwrap();
+        assert_eq!(decision, Some(ChannelApprovalResponse::AlwaysApprove));
+        ui.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn dropped_prompt_denies() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let channel = TuiChannel::new(tx);
+        tokio::spawn(async move {
+            drop(rx.recv().await);
+        });
+        let decision = channel.request_approval("", &request()).await.unwrap();
+        assert_eq!(decision, Some(ChannelApprovalResponse::Deny));
+    }
+}
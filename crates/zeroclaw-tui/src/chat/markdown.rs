@@ -0,0 +1,176 @@
+//! Minimal Markdown → ratatui lines for assistant replies.
+//!
+//! Covers what models actually emit in chat: headings, bullet and numbered
+//! lists, block quotes, fenced code, and inline `code` / **bold** / *italic*.
+//! Anything else falls through as plain body text, so a malformed reply is
+//! still readable rather than dropped.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::theme;
+
+/// Render `text` into owned lines ready for a wrapping `Paragraph`.
+pub fn render(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            if in_code {
+                let lang = trimmed.trim_start_matches('`').trim();
+                if !lang.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {lang}"),
+                        theme::dim_style().add_modifier(Modifier::ITALIC),
+                    )));
+                }
+            }
+            continue;
+        }
+        if in_code {
+            lines.push(Line::from(Span::styled(
+                format!("  {raw}"),
+                theme::code_style(),
+            )));
+            continue;
+        }
+
+        let heading_level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+            lines.push(Line::from(Span::styled(
+                trimmed[heading_level..].trim().to_string(),
+                theme::heading_style(),
+            )));
+            continue;
+        }
+
+        let indent = " ".repeat(raw.len() - trimmed.len());
+        if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            let mut spans = vec![Span::styled(format!("{indent}• "), theme::accent_style())];
+            spans.extend(inline(item, theme::body_style()));
+            lines.push(Line::from(spans));
+            continue;
+        }
+        if let Some((number, item)) = numbered_item(trimmed) {
+            let mut spans = vec![Span::styled(
+                format!("{indent}{number}. "),
+                theme::accent_style(),
+            )];
+            spans.extend(inline(item, theme::body_style()));
+            lines.push(Line::from(spans));
+            continue;
+        }
+        if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::styled("│ ", theme::dim_style())];
+            spans.extend(inline(quote.trim_start(), theme::dim_style()));
+            lines.push(Line::from(spans));
+            continue;
+        }
+
+        lines.push(Line::from(inline(raw, theme::body_style())));
+    }
+    lines
+}
+
+fn numbered_item(line: &str) -> Option<(&str, &str)> {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let rest = line[digits..].strip_prefix(". ")?;
+    Some((&line[..digits], rest))
+}
+
+/// Split a line into styled spans for inline `code`, **bold** and *italic*.
+/// Unclosed markers are kept literally.
+fn inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let marker = match c {
+            '`' => Some(("`", theme::code_style())),
+            '*' if rest.starts_with("**") => Some(("**", base.add_modifier(Modifier::BOLD))),
+            '*' => Some(("*", base.add_modifier(Modifier::ITALIC))),
+            // `_` only opens emphasis at a word boundary, so snake_case
+            // identifiers survive.
+            '_' if plain.chars().last().is_none_or(|p| !p.is_alphanumeric()) => {
+                Some(("_", base.add_modifier(Modifier::ITALIC)))
+            }
+            _ => None,
+        };
+        if let Some((delim, style)) = marker
+            && let Some(end) = rest[delim.len()..].find(delim)
+            && end > 0
+        {
+            if !plain.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut plain), base));
+            }
+            let inner = &rest[delim.len()..delim.len() + end];
+            spans.push(Span::styled(inner.to_string(), style));
+            rest = &rest[delim.len() * 2 + end..];
+            continue;
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if !plain.is_empty() || spans.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(line: &Line<'_>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn renders_headings_lists_and_code_blocks() {
+        let lines = render("# Title\n- one\n2. two\n```rust\nlet x = 1;\n```\n> quoted");
+        let texts: Vec<String> = lines.iter().map(text_of).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Title",
+                "• one",
+                "2. two",
+                "  rust",
+                "  let x = 1;",
+                "│ quoted"
+            ]
+        );
+        assert_eq!(lines[4].spans[0].style, theme::code_style());
+    }
+
+    #[test]
+    fn inline_markers_become_styled_spans() {
+        let spans = inline("run `ls` **now** or *later*", theme::body_style());
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["run ", "ls", " ", "now", " or ", "later"]);
+        assert_eq!(spans[1].style, theme::code_style());
+        assert!(spans[3].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[5].style.add_modifier.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn unclosed_markers_stay_literal() {
+        let spans = inline("2 * 3 = 6 and a_snake_case name", theme::body_style());
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "2 * 3 = 6 and a_snake_case name");
+        assert_eq!(spans.len(), 1);
+    }
+}
@@ -0,0 +1,374 @@
+//! Full-screen chat front-end for `zeroclaw agent`.
+//!
+//! Drives a runtime [`Agent`] turn by turn through `turn_streamed`, so the
+//! screen keeps repainting (and answering approvals) while the agent works.
+//! The agent moves into a spawned task for each turn and comes back when the
+//! turn ends; between turns it lives here. Conversations persist through the
+//! configured session backend under `tui_<timestamp>` keys, and the switcher
+//! can open any stored session, including gateway and channel ones.
+
+mod approval;
+mod markdown;
+mod state;
+mod view;
+
+use std::collections::HashMap;
+use std::io::{self, Stdout};
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use crossterm::{
+    event::{Event, EventStream, KeyEventKind},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use futures_util::StreamExt;
+use ratatui::{Terminal, backend::CrosstermBackend};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use zeroclaw_api::agent::TurnEvent;
+use zeroclaw_api::provider::ChatMessage;
+use zeroclaw_config::cost::{CostTracker, TokenUsage};
+use zeroclaw_config::schema::{Config, ModelPricing};
+use zeroclaw_infra::session_backend::SessionBackend;
+use zeroclaw_runtime::agent::Agent;
+
+use approval::{TuiChannel, UiRequest};
+use state::{Action, ChatState};
+
+type Term = Terminal<CrosstermBackend<Stdout>>;
+
+/// Run the chat UI until the user quits.
+pub async fn run_chat(config: Config) -> Result<()> {
+    let backend = match zeroclaw_infra::make_session_backend(
+        &config.workspace_dir,
+        &config.channels.session_backend,
+        zeroclaw_infra::PostgresTarget {
+            db_url: config.storage.provider.config.db_url.as_deref(),
+            schema: &config.storage.provider.config.schema,
+            connect_timeout_secs: config.storage.provider.config.connect_timeout_secs,
+        },
+    ) {
+        Ok(backend) => Some(backend),
+        Err(e) => {
+            tracing::warn!("Chat session persistence disabled: {e}");
+            None
+        }
+    };
+
+    let mut agent =
+        Agent::from_config_with_session_cwd_and_mcp_backchannel(&config, None, true).await?;
+    let (ui_tx, mut ui_rx) = mpsc::channel::<UiRequest>(8);
+    agent
+        .channel_handles()
+        .register_channel(approval::CHANNEL_NAME, Arc::new(TuiChannel::new(ui_tx)));
+
+    let meter = CostMeter::new(&config);
+    let session_key = new_session_key();
+    agent.set_memory_session_id(Some(session_key.clone()));
+    let mut state = ChatState::new(meter.label(), session_key);
+    if backend.is_none() {
+        state.notice(
+            "Session persistence is unavailable; this conversation won't be saved.",
+            true,
+        );
+    }
+
+    let mut terminal = ChatTerminal::enter()?;
+    let mut keys = EventStream::new();
+    let mut agent = Some(agent);
+    let mut turn: Option<RunningTurn> = None;
+
+    loop {
+        terminal.0.draw(|frame| view::draw(frame, &mut state))?;
+
+        tokio::select! {
+            key_event = keys.next() => {
+                let Some(event) = key_event else { break };
+                let Event::Key(key) = event? else { continue };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match state.handle_key(key) {
+                    Action::None => {}
+                    Action::Quit => {
+                        if let Some(running) = &turn {
+                            running.cancel.cancel();
+                        }
+                        break;
+                    }
+                    Action::Cancel => {
+                        if let Some(running) = &turn {
+                            running.cancel.cancel();
+                        }
+                    }
+                    Action::Send(text) => {
+                        let idle = agent
+                            .take()
+                            .ok_or_else(|| anyhow!("agent is busy with another turn"))?;
+                        if let Some(backend) = &backend {
+                            let _ = backend.append(&state.session_key, &ChatMessage::user(&text));
+                        }
+                        turn = Some(RunningTurn::start(idle, text));
+                    }
+                    Action::ShowSessions => match &backend {
+                        Some(backend) => state.open_switcher(backend.list_sessions_with_metadata()),
+                        None => state.notice("Session persistence is unavailable.", true),
+                    },
+                    Action::OpenSession(key) => match agent.as_mut() {
+                        Some(idle) => open_session(idle, &mut state, backend.as_deref(), key),
+                        None => state.notice(
+                            "Finish or cancel (Ctrl+C) the current turn before switching sessions.",
+                            true,
+                        ),
+                    },
+                }
+            }
+            update = next_turn_update(&mut turn) => match update {
+                TurnUpdate::Event(event) => {
+                    if let TurnEvent::Usage { input_tokens, output_tokens, .. } = &event {
+                        state.usage.cost_usd += meter.record(*input_tokens, *output_tokens);
+                    }
+                    state.apply(event);
+                }
+                TurnUpdate::Done { agent: returned, result, cancelled } => {
+                    turn = None;
+                    agent = Some(*returned.ok_or_else(|| anyhow!("agent task panicked"))?);
+                    if let (Some(backend), Ok(reply)) = (&backend, &result)
+                        && !reply.trim().is_empty()
+                    {
+                        let _ = backend.append(&state.session_key, &ChatMessage::assistant(reply));
+                    }
+                    let result = result.map_err(|e| {
+                        if cancelled { "(cancelled)".to_string() } else { format!("Error: {e}") }
+                    });
+                    let finished_ok = result.is_ok();
+                    state.finish_turn(result);
+                    if cancelled && finished_ok {
+                        state.notice("(cancelled)", false);
+                    }
+                }
+            },
+            Some(request) = ui_rx.recv() => match request {
+                UiRequest::Approval(prompt) => state.approval = Some(prompt),
+                UiRequest::Message(text) => state.notice(text, false),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Swap the conversation for a stored session, or start a fresh one.
+fn open_session(
+    agent: &mut Agent,
+    state: &mut ChatState,
+    backend: Option<&dyn SessionBackend>,
+    key: Option<String>,
+) {
+    let (key, name, messages) = match (key, backend) {
+        (Some(key), Some(backend)) => {
+            let name = backend.get_session_name(&key).unwrap_or(None);
+            let messages = backend.load(&key);
+            (key, name, messages)
+        }
+        _ => (new_session_key(), None, Vec::new()),
+    };
+    agent.clear_history();
+    if !messages.is_empty() {
+        agent.seed_history(&messages);
+    }
+    agent.set_memory_session_id(Some(key.clone()));
+    state.load_session(&key, name, &messages);
+}
+
+fn new_session_key() -> String {
+    format!("tui_{}", chrono::Local::now().format("%Y%m%d_%H%M%S"))
+}
+
+/// An agent turn running on its own task.
+struct RunningTurn {
+    events: mpsc::Receiver<TurnEvent>,
+    handle: JoinHandle<(Agent, Result<String>)>,
+    cancel: CancellationToken,
+}
+
+enum TurnUpdate {
+    Event(TurnEvent),
+    Done {
+        /// `None` only if the turn task panicked.
+        agent: Option<Box<Agent>>,
+        result: Result<String>,
+        cancelled: bool,
+    },
+}
+
+impl RunningTurn {
+    fn start(mut agent: Agent, message: String) -> Self {
+        let (event_tx, events) = mpsc::channel(64);
+        let cancel = CancellationToken::new();
+        let token = cancel.clone();
+        let handle = tokio::spawn(async move {
+            let result = agent.turn_streamed(&message, event_tx, Some(token)).await;
+            (agent, result)
+        });
+        Self {
+            events,
+            handle,
+            cancel,
+        }
+    }
+
+    /// Next streamed event; once the agent drops its sender, the outcome.
+    /// Both awaits are cancel-safe, so this can sit in a `select!`.
+    async fn next(&mut self) -> TurnUpdate {
+        if let Some(event) = self.events.recv().await {
+            return TurnUpdate::Event(event);
+        }
+        let cancelled = self.cancel.is_cancelled();
+        match (&mut self.handle).await {
+            Ok((agent, result)) => TurnUpdate::Done {
+                agent: Some(Box::new(agent)),
+                result,
+                cancelled,
+            },
+            Err(e) => TurnUpdate::Done {
+                agent: None,
+                result: Err(anyhow!("agent task failed: {e}")),
+                cancelled,
+            },
+        }
+    }
+}
+
+async fn next_turn_update(turn: &mut Option<RunningTurn>) -> TurnUpdate {
+    match turn {
+        Some(running) => running.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Prices per-call usage for the status bar and records it with the global
+/// cost tracker, using the same lookup order as the agent loop:
+/// `<provider>/<model>`, then `<model>`, then the suffix after the last `/`.
+struct CostMeter {
+    provider: String,
+    model: String,
+    prices: HashMap<String, ModelPricing>,
+    tracker: Option<Arc<CostTracker>>,
+}
+
+impl CostMeter {
+    fn new(config: &Config) -> Self {
+        let provider = config
+            .providers
+            .fallback
+            .clone()
+            .unwrap_or_else(|| "openrouter".into());
+        let model = config
+            .providers
+            .fallback_provider()
+            .and_then(|e| e.model.clone())
+            .filter(|m| !m.trim().is_empty())
+            .or_else(|| config.providers.resolve_default_model())
+            .unwrap_or_else(|| "default".into());
+        Self {
+            provider,
+            model,
+            prices: config.combined_pricing(),
+            tracker: CostTracker::get_or_init_global(config.cost.clone(), &config.workspace_dir),
+        }
+    }
+
+    fn label(&self) -> String {
+        format!("{}/{}", self.provider, self.model)
+    }
+
+    fn pricing(&self) -> Option<&ModelPricing> {
+        self.prices
+            .get(&self.label())
+            .or_else(|| self.prices.get(&self.model))
+            .or_else(|| {
+                self.model
+                    .rsplit_once('/')
+                    .and_then(|(_, suffix)| self.prices.get(suffix))
+            })
+    }
+
+    fn record(&self, input_tokens: Option<u64>, output_tokens: Option<u64>) -> f64 {
+        let (input, output) = (input_tokens.unwrap_or(0), output_tokens.unwrap_or(0));
+        if input == 0 && output == 0 {
+            return 0.0;
+        }
+        let pricing = self.pricing();
+        let usage = TokenUsage::new(
+            &self.model,
+            input,
+            output,
+            pricing.map_or(0.0, |p| p.input),
+            pricing.map_or(0.0, |p| p.output),
+        );
+        let cost = usage.cost_usd;
+        if let Some(tracker) = &self.tracker
+            && let Err(e) = tracker.record_usage(usage)
+        {
+            tracing::warn!("Failed to record chat cost: {e}");
+        }
+        cost
+    }
+}
+
+/// Alternate screen + raw mode for the lifetime of the chat; restored on
+/// drop so an error or panic doesn't leave the terminal unusable.
+struct ChatTerminal(Term);
+
+impl ChatTerminal {
+    fn enter() -> Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        Ok(Self(Terminal::new(CrosstermBackend::new(stdout))?))
+    }
+}
+
+impl Drop for ChatTerminal {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(self.0.backend_mut(), LeaveAlternateScreen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_meter_prices_by_provider_then_model() {
+        let mut meter = CostMeter {
+            provider: "openrouter".into(),
+            model: "anthropic/claude-sonnet".into(),
+            prices: HashMap::new(),
+            tracker: None,
+        };
+        meter.prices.insert(
+            "claude-sonnet".into(),
+            ModelPricing {
+                input: 3.0,
+                output: 15.0,
+            },
+        );
+        let cost = meter.record(Some(1_000_000), Some(100_000));
+        assert!((cost - 4.5).abs() < 1e-9);
+
+        meter.prices.insert(
+            "openrouter/anthropic/claude-sonnet".into(),
+            ModelPricing {
+                input: 1.0,
+                output: 1.0,
+            },
+        );
+        let cost = meter.record(Some(1_000_000), Some(0));
+        assert!((cost - 1.0).abs() < 1e-9);
+        assert_eq!(meter.record(None, None), 0.0);
+    }
+}
@@ -0,0 +1,698 @@
+//! Chat screen state and key handling, kept free of terminal and agent I/O
+//! so the transitions can be unit-tested. The run loop in `mod.rs` feeds it
+//! key presses and `TurnEvent`s and acts on the returned [`Action`]s.
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use zeroclaw_api::agent::TurnEvent;
+use zeroclaw_api::channel::ChannelApprovalResponse;
+use zeroclaw_api::provider::ChatMessage;
+use zeroclaw_infra::session_backend::SessionMetadata;
+
+use super::approval::ApprovalPrompt;
+
+/// Rows moved per PageUp / PageDown.
+const SCROLL_PAGE: u16 = 10;
+
+/// One block in the scrolling transcript.
+pub enum Entry {
+    User(String),
+    Assistant(String),
+    Tool(ToolBlock),
+    Notice { text: String, warn: bool },
+}
+
+/// A tool call and (once it returns) its result. Collapsed blocks render as
+/// a single summary row.
+pub struct ToolBlock {
+    pub id: String,
+    pub name: String,
+    pub args: String,
+    pub output: Option<String>,
+    pub expanded: bool,
+}
+
+/// Running token and cost totals for the status bar.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Session switcher overlay. Row 0 is always "new session".
+pub struct Switcher {
+    pub sessions: Vec<SessionMetadata>,
+    pub filter: String,
+    pub cursor: usize,
+}
+
+impl Switcher {
+    /// Sessions matching the filter, most recently active first.
+    pub fn matches(&self) -> Vec<&SessionMetadata> {
+        let needle = self.filter.to_ascii_lowercase();
+        self.sessions
+            .iter()
+            .filter(|s| {
+                needle.is_empty()
+                    || s.key.to_ascii_lowercase().contains(&needle)
+                    || s.name
+                        .as_deref()
+                        .is_some_and(|n| n.to_ascii_lowercase().contains(&needle))
+            })
+            .collect()
+    }
+}
+
+/// What the run loop should do after a key press.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Send(String),
+    Cancel,
+    ShowSessions,
+    /// Switch to an existing session, or start a new one on `None`.
+    OpenSession(Option<String>),
+}
+
+pub struct ChatState {
+    pub model: String,
+    pub session_key: String,
+    pub session_name: Option<String>,
+    pub entries: Vec<Entry>,
+    /// Reply text streaming in for the current turn.
+    pub streaming: String,
+    pub thinking: String,
+    pub busy: bool,
+    pub input: String,
+    /// Rows scrolled up from the bottom of the transcript.
+    pub scroll: u16,
+    /// Index into `entries` of the tool block Tab has selected.
+    pub focused_tool: Option<usize>,
+    pub usage: Usage,
+    pub approval: Option<ApprovalPrompt>,
+    pub switcher: Option<Switcher>,
+}
+
+impl ChatState {
+    pub fn new(model: impl Into<String>, session_key: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            session_key: session_key.into(),
+            session_name: None,
+            entries: Vec::new(),
+            streaming: String::new(),
+            thinking: String::new(),
+            busy: false,
+            input: String::new(),
+            scroll: 0,
+            focused_tool: None,
+            usage: Usage::default(),
+            approval: None,
+            switcher: None,
+        }
+    }
+
+    pub fn notice(&mut self, text: impl Into<String>, warn: bool) {
+        self.entries.push(Entry::Notice {
+            text: text.into(),
+            warn,
+        });
+        self.scroll = 0;
+    }
+
+    /// Replace the transcript with a stored session. Native tool-call
+    /// payloads (assistant JSON with `tool_calls`, `tool` role results) are
+    /// rebuilt into collapsed tool blocks.
+    pub fn load_session(&mut self, key: &str, name: Option<String>, messages: &[ChatMessage]) {
+        self.session_key = key.to_string();
+        self.session_name = name;
+        self.entries.clear();
+        self.streaming.clear();
+        self.thinking.clear();
+        self.scroll = 0;
+        self.focused_tool = None;
+
+        for msg in messages {
+            match msg.role.as_str() {
+                "user" => self.entries.push(Entry::User(msg.content.clone())),
+                "assistant" => self.push_stored_assistant(&msg.content),
+                "tool" => self.push_stored_tool_result(&msg.content),
+                _ => {}
+            }
+        }
+    }
+
+    fn push_stored_assistant(&mut self, content: &str) {
+        let payload = serde_json::from_str::<serde_json::Value>(content).ok();
+        let Some(calls) = payload
+            .as_ref()
+            .and_then(|p| p.get("tool_calls"))
+            .and_then(|c| c.as_array())
+        else {
+            self.entries.push(Entry::Assistant(content.to_string()));
+            return;
+        };
+        if let Some(text) = payload
+            .as_ref()
+            .and_then(|p| p.get("content"))
+            .and_then(|c| c.as_str())
+            .filter(|t| !t.trim().is_empty())
+        {
+            self.entries.push(Entry::Assistant(text.to_string()));
+        }
+        for call in calls {
+            let field = |key: &str| call.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+            self.entries.push(Entry::Tool(ToolBlock {
+                id: field("id").to_string(),
+                name: field("name").to_string(),
+                args: field("arguments").to_string(),
+                output: None,
+                expanded: false,
+            }));
+        }
+    }
+
+    fn push_stored_tool_result(&mut self, content: &str) {
+        let payload = serde_json::from_str::<serde_json::Value>(content).ok();
+        let id = payload
+            .as_ref()
+            .and_then(|p| p.get("tool_call_id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let output = payload
+            .as_ref()
+            .and_then(|p| p.get("content"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(content)
+            .to_string();
+        if let Some(block) = self.tool_block_mut(id) {
+            block.output = Some(output);
+        } else {
+            self.entries.push(Entry::Tool(ToolBlock {
+                id: id.to_string(),
+                name: "tool".into(),
+                args: String::new(),
+                output: Some(output),
+                expanded: false,
+            }));
+        }
+    }
+
+    fn tool_block_mut(&mut self, id: &str) -> Option<&mut ToolBlock> {
+        if id.is_empty() {
+            return None;
+        }
+        self.entries.iter_mut().rev().find_map(|entry| match entry {
+            Entry::Tool(block) if block.id == id && block.output.is_none() => Some(block),
+            _ => None,
+        })
+    }
+
+    /// Fold one streamed agent event into the live view.
+    pub fn apply(&mut self, event: TurnEvent) {
+        match event {
+            TurnEvent::Chunk { delta } => self.streaming.push_str(&delta),
+            TurnEvent::Thinking { delta } => self.thinking.push_str(&delta),
+            TurnEvent::ToolCall { id, name, args } => {
+                // Text streamed before a tool call is its own reply segment;
+                // commit it so the transcript keeps the agent's ordering.
+                self.commit_streaming();
+                self.thinking.clear();
+                self.entries.push(Entry::Tool(ToolBlock {
+                    id,
+                    name,
+                    args: args.to_string(),
+                    output: None,
+                    expanded: false,
+                }));
+            }
+            TurnEvent::ToolResult { id, name, output } => {
+                if let Some(block) = self.tool_block_mut(&id) {
+                    block.output = Some(output);
+                } else {
+                    self.entries.push(Entry::Tool(ToolBlock {
+                        id,
+                        name,
+                        args: String::new(),
+                        output: Some(output),
+                        expanded: false,
+                    }));
+                }
+            }
+            TurnEvent::Usage {
+                input_tokens,
+                output_tokens,
+                ..
+            } => {
+                self.usage.input_tokens += input_tokens.unwrap_or(0);
+                self.usage.output_tokens += output_tokens.unwrap_or(0);
+            }
+            // Approvals arrive through the `tui` channel, which carries the
+            // reply handle; the bare event has nothing to answer with.
+            TurnEvent::ApprovalRequest { .. } => {}
+        }
+    }
+
+    fn commit_streaming(&mut self) {
+        let text = std::mem::take(&mut self.streaming);
+        if !text.trim().is_empty() {
+            self.entries.push(Entry::Assistant(text));
+        }
+    }
+
+    /// Close out the running turn. `Ok` carries the final reply; `Err` the
+    /// failure to show (partial streamed text is kept either way).
+    pub fn finish_turn(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(reply) => {
+                self.streaming.clear();
+                if !reply.trim().is_empty() {
+                    self.entries.push(Entry::Assistant(reply));
+                }
+            }
+            Err(error) => {
+                self.commit_streaming();
+                self.notice(error, true);
+            }
+        }
+        self.thinking.clear();
+        self.busy = false;
+        // An approval still open belongs to the finished turn; dropping the
+        // reply sender denies it.
+        self.approval = None;
+    }
+
+    pub fn open_switcher(&mut self, mut sessions: Vec<SessionMetadata>) {
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+        self.switcher = Some(Switcher {
+            sessions,
+            filter: String::new(),
+            cursor: 0,
+        });
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if let Some(prompt) = self.approval.take() {
+            let decision = match key.code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => ChannelApprovalResponse::Approve,
+                KeyCode::Char('a' | 'A') => ChannelApprovalResponse::AlwaysApprove,
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => ChannelApprovalResponse::Deny,
+                KeyCode::Char('c') if ctrl => ChannelApprovalResponse::Deny,
+                _ => {
+                    self.approval = Some(prompt);
+                    return Action::None;
+                }
+            };
+            let verdict = match decision {
+                ChannelApprovalResponse::Approve => "approved",
+                ChannelApprovalResponse::AlwaysApprove => "always approved",
+                ChannelApprovalResponse::Deny => "denied",
+            };
+            self.notice(format!("{}: {verdict}", prompt.tool_name), false);
+            let _ = prompt.reply.send(decision);
+            return Action::None;
+        }
+
+        if let Some(switcher) = self.switcher.as_mut() {
+            let rows = switcher.matches().len() + 1;
+            match key.code {
+                KeyCode::Esc => self.switcher = None,
+                KeyCode::Up => switcher.cursor = switcher.cursor.saturating_sub(1),
+                KeyCode::Down if switcher.cursor + 1 < rows => switcher.cursor += 1,
+                KeyCode::Backspace => {
+                    switcher.filter.pop();
+                    switcher.cursor = 0;
+                }
+                KeyCode::Char(c) if !ctrl => {
+                    switcher.filter.push(c);
+                    switcher.cursor = 0;
+                }
+                KeyCode::Enter => {
+                    let choice = match switcher.cursor {
+                        0 => None,
+                        i => switcher.matches().get(i - 1).map(|s| s.key.clone()),
+                    };
+                    self.switcher = None;
+                    return Action::OpenSession(choice);
+                }
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        match key.code {
+            KeyCode::Char('c') if ctrl => {
+                if self.busy {
+                    Action::Cancel
+                } else {
+                    Action::Quit
+                }
+            }
+            KeyCode::Char('d') if ctrl && self.input.is_empty() && !self.busy => Action::Quit,
+            KeyCode::Char('s') if ctrl => Action::ShowSessions,
+            KeyCode::Char('o') if ctrl => {
+                self.toggle_tool();
+                Action::None
+            }
+            KeyCode::Tab => {
+                self.cycle_tool(true);
+                Action::None
+            }
+            KeyCode::BackTab => {
+                self.cycle_tool(false);
+                Action::None
+            }
+            KeyCode::PageUp => {
+                self.scroll = self.scroll.saturating_add(SCROLL_PAGE);
+                Action::None
+            }
+            KeyCode::PageDown => {
+                self.scroll = self.scroll.saturating_sub(SCROLL_PAGE);
+                Action::None
+            }
+            KeyCode::End if ctrl => {
+                self.scroll = 0;
+                Action::None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                Action::None
+            }
+            KeyCode::Esc => {
+                self.focused_tool = None;
+                Action::None
+            }
+            KeyCode::Enter => self.submit(),
+            KeyCode::Char(c) if !ctrl => {
+                self.input.push(c);
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn submit(&mut self) -> Action {
+        let text = self.input.trim().to_string();
+        if text.is_empty() {
+            return Action::None;
+        }
+        match text.as_str() {
+            "/quit" | "/exit" => return Action::Quit,
+            "/sessions" => {
+                self.input.clear();
+                return Action::ShowSessions;
+            }
+            "/new" | "/clear" => {
+                self.input.clear();
+                return Action::OpenSession(None);
+            }
+            "/help" => {
+                self.input.clear();
+                self.notice(HELP, false);
+                return Action::None;
+            }
+            _ => {}
+        }
+        if self.busy {
+            // Keep the draft; it can be sent once the turn finishes.
+            return Action::None;
+        }
+        self.input.clear();
+        self.entries.push(Entry::User(text.clone()));
+        self.busy = true;
+        self.scroll = 0;
+        self.streaming.clear();
+        self.thinking.clear();
+        Action::Send(text)
+    }
+
+    fn tool_indices(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| matches!(e, Entry::Tool(_)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn cycle_tool(&mut self, forward: bool) {
+        let tools = self.tool_indices();
+        if tools.is_empty() {
+            return;
+        }
+        let pos = self
+            .focused_tool
+            .and_then(|f| tools.iter().position(|&i| i == f));
+        let next = match (pos, forward) {
+            (None, _) => tools.len() - 1,
+            (Some(p), true) => (p + 1) % tools.len(),
+            (Some(p), false) => (p + tools.len() - 1) % tools.len(),
+        };
+        self.focused_tool = Some(tools[next]);
+    }
+
+    /// Expand or collapse the focused tool block, or the latest one when
+    /// nothing is focused.
+    fn toggle_tool(&mut self) {
+        let target = self
+            .focused_tool
+            .or_else(|| self.tool_indices().last().copied());
+        if let Some(Entry::Tool(block)) = target.and_then(|i| self.entries.get_mut(i)) {
+            block.expanded = !block.expanded;
+        }
+    }
+}
+
+const HELP: &str = "Enter send · Ctrl+C cancel turn / quit · Ctrl+S or /sessions switch session · \
+/new start over · Tab/Shift+Tab select tool block · Ctrl+O expand/collapse · \
+PgUp/PgDn scroll · Ctrl+End jump to latest · /quit exit";
+
+/// "3m ago"-style age for the session switcher.
+pub fn relative_age(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - then).num_seconds().max(0);
+    match secs {
+        0..60 => "just now".into(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86_400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn type_text(state: &mut ChatState, text: &str) {
+        for c in text.chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn meta(key: &str, age_secs: i64) -> SessionMetadata {
+        let at = Utc::now() - chrono::Duration::seconds(age_secs);
+        SessionMetadata {
+            key: key.into(),
+            name: None,
+            created_at: at,
+            last_activity: at,
+            message_count: 2,
+            parent_key: None,
+            fork_point: None,
+        }
+    }
+
+    #[test]
+    fn enter_sends_and_streams_into_transcript() {
+        let mut state = ChatState::new("openai/gpt", "tui_1");
+        type_text(&mut state, "hello");
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            Action::Send("hello".into())
+        );
+        assert!(state.busy && state.input.is_empty());
+
+        state.apply(TurnEvent::Chunk {
+            delta: "Let me check.".into(),
+        });
+        state.apply(TurnEvent::ToolCall {
+            id: "c1".into(),
+            name: "shell".into(),
+            args: serde_json::json!({ "command": "ls" }),
+        });
+        state.apply(TurnEvent::ToolResult {
+            id: "c1".into(),
+            name: "shell".into(),
+            output: "a.txt".into(),
+        });
+        state.apply(TurnEvent::Usage {
+            input_tokens: Some(10),
+            output_tokens: Some(4),
+            cost_usd: None,
+        });
+        state.apply(TurnEvent::Chunk {
+            delta: "Found a.txt".into(),
+        });
+        state.finish_turn(Ok("Found a.txt".into()));
+
+        assert!(!state.busy && state.streaming.is_empty());
+        assert!(matches!(&state.entries[0], Entry::User(t) if t == "hello"));
+        assert!(matches!(&state.entries[1], Entry::Assistant(t) if t == "Let me check."));
+        assert!(
+            matches!(&state.entries[2], Entry::Tool(b) if b.output.as_deref() == Some("a.txt"))
+        );
+        assert!(matches!(&state.entries[3], Entry::Assistant(t) if t == "Found a.txt"));
+        assert_eq!(state.usage.input_tokens, 10);
+        assert_eq!(state.usage.output_tokens, 4);
+    }
+
+    #[test]
+    fn failed_turn_keeps_partial_text_and_warns() {
+        let mut state = ChatState::new("m", "k");
+        type_text(&mut state, "go");
+        state.handle_key(key(KeyCode::Enter));
+        assert_eq!(state.handle_key(ctrl('c')), Action::Cancel);
+        state.apply(TurnEvent::Chunk {
+            delta: "partial".into(),
+        });
+        state.finish_turn(Err("cancelled".into()));
+        assert!(matches!(&state.entries[1], Entry::Assistant(t) if t == "partial"));
+        assert!(matches!(
+            &state.entries[2],
+            Entry::Notice { warn: true, .. }
+        ));
+        assert_eq!(state.handle_key(ctrl('c')), Action::Quit);
+    }
+
+    #[test]
+    fn approval_keys_answer_the_pending_prompt() {
+        let mut state = ChatState::new("m", "k");
+        let (reply, mut rx) = oneshot::channel();
+        state.approval = Some(ApprovalPrompt {
+            tool_name: "shell".into(),
+            arguments_summary: "command: rm -rf build".into(),
+            reply,
+        });
+        // Unrelated keys leave the prompt open and don't reach the input.
+        state.handle_key(key(KeyCode::Char('x')));
+        assert!(state.approval.is_some() && state.input.is_empty());
+
+        state.handle_key(key(KeyCode::Char('a')));
+        assert!(state.approval.is_none());
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            ChannelApprovalResponse::AlwaysApprove
+        );
+    }
+
+    #[test]
+    fn tab_and_ctrl_o_toggle_tool_blocks() {
+        let mut state = ChatState::new("m", "k");
+        for id in ["a", "b"] {
+            state.apply(TurnEvent::ToolCall {
+                id: id.into(),
+                name: "file_read".into(),
+                args: serde_json::json!({}),
+            });
+        }
+        state.handle_key(key(KeyCode::Tab));
+        assert_eq!(state.focused_tool, Some(1));
+        state.handle_key(key(KeyCode::Tab));
+        assert_eq!(state.focused_tool, Some(0));
+        state.handle_key(ctrl('o'));
+        assert!(matches!(&state.entries[0], Entry::Tool(b) if b.expanded));
+        assert!(matches!(&state.entries[1], Entry::Tool(b) if !b.expanded));
+    }
+
+    #[test]
+    fn switcher_filters_and_opens_sessions() {
+        let mut state = ChatState::new("m", "k");
+        state.open_switcher(vec![meta("tui_old", 7200), meta("gw_recent", 30)]);
+        assert_eq!(
+            state.switcher.as_ref().unwrap().sessions[0].key,
+            "gw_recent"
+        );
+
+        type_text(&mut state, "old");
+        state.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            Action::OpenSession(Some("tui_old".into()))
+        );
+        assert!(state.switcher.is_none());
+
+        state.open_switcher(Vec::new());
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            Action::OpenSession(None)
+        );
+    }
+
+    #[test]
+    fn load_session_rebuilds_native_tool_calls() {
+        let mut state = ChatState::new("m", "k");
+        let messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("list files"),
+            ChatMessage::assistant(
+                serde_json::json!({
+                    "content": "Checking",
+                    "tool_calls": [{ "id": "t1", "name": "shell", "arguments": "{\"command\":\"ls\"}" }],
+                })
+                .to_string(),
+            ),
+            ChatMessage::tool(
+                serde_json::json!({ "tool_call_id": "t1", "content": "a.txt" }).to_string(),
+            ),
+            ChatMessage::assistant("There is a.txt"),
+        ];
+        state.load_session("gw_1", Some("files".into()), &messages);
+        assert_eq!(state.session_key, "gw_1");
+        assert_eq!(state.entries.len(), 4);
+        assert!(matches!(
+            &state.entries[2],
+            Entry::Tool(b) if b.name == "shell" && b.output.as_deref() == Some("a.txt")
+        ));
+    }
+
+    #[test]
+    fn slash_commands_map_to_actions() {
+        let mut state = ChatState::new("m", "k");
+        type_text(&mut state, "/new");
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            Action::OpenSession(None)
+        );
+        type_text(&mut state, "/sessions");
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), Action::ShowSessions);
+        type_text(&mut state, "/quit");
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), Action::Quit);
+    }
+
+    #[test]
+    fn relative_age_buckets() {
+        let now = Utc::now();
+        assert_eq!(relative_age(now, now), "just now");
+        assert_eq!(
+            relative_age(now - chrono::Duration::minutes(5), now),
+            "5m ago"
+        );
+        assert_eq!(
+            relative_age(now - chrono::Duration::hours(3), now),
+            "3h ago"
+        );
+        assert_eq!(relative_age(now - chrono::Duration::days(2), now), "2d ago");
+    }
+}
@@ -0,0 +1,419 @@
+//! Drawing for the chat screen.
+//!
+//! Top to bottom: scrolling transcript, live pane for the in-flight reply,
+//! inline approval prompt, input, status bar, key hints. The live pane and
+//! approval prompt take no rows when there is nothing to show. The session
+//! switcher draws as a centered overlay on top.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use super::markdown;
+use super::state::{ChatState, Entry, ToolBlock};
+use crate::theme;
+use crate::widgets::{BANNER_HEIGHT, Banner, InputPrompt};
+
+const HINTS: &str = "Enter=send  Ctrl+S=sessions  Tab=select tool  Ctrl+O=expand  PgUp/PgDn=scroll  Ctrl+C=cancel/quit  /help";
+const HINTS_APPROVAL: &str = "y=approve  a=always allow this tool  n=deny";
+const HINTS_SWITCHER: &str = "↑↓=navigate  type=filter  Enter=open  Esc=close";
+
+/// Tallest the live pane grows before it starts following the tail.
+const LIVE_MAX_ROWS: u16 = 10;
+/// Output lines shown for an expanded tool block before eliding.
+const TOOL_OUTPUT_MAX_LINES: usize = 40;
+
+pub fn draw(frame: &mut Frame, state: &mut ChatState) {
+    let area = frame.area();
+    let live = live_lines(state);
+    let live_rows = if state.busy {
+        let inner_width = area.width.saturating_sub(2).max(1);
+        let rows = Paragraph::new(live.clone())
+            .wrap(Wrap { trim: false })
+            .line_count(inner_width) as u16;
+        rows.clamp(1, LIVE_MAX_ROWS) + 2
+    } else {
+        0
+    };
+    let approval_rows = if state.approval.is_some() { 4 } else { 0 };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),                // transcript (flex)
+            Constraint::Length(live_rows),     // streaming reply
+            Constraint::Length(approval_rows), // approval prompt
+            Constraint::Length(3),             // input
+            Constraint::Length(1),             // status bar
+            Constraint::Length(1),             // hints
+        ])
+        .split(area);
+
+    render_transcript(frame, chunks[0], state);
+    if live_rows > 0 {
+        render_live(frame, chunks[1], live);
+    }
+    render_approval(frame, chunks[2], state);
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::dim_style());
+    let input_area = input_block.inner(chunks[3]);
+    frame.render_widget(input_block, chunks[3]);
+    frame.render_widget(
+        InputPrompt {
+            label: "you",
+            input: &state.input,
+            masked: false,
+        },
+        input_area,
+    );
+    render_status(frame, chunks[4], state);
+
+    let hints = if state.approval.is_some() {
+        HINTS_APPROVAL
+    } else if state.switcher.is_some() {
+        HINTS_SWITCHER
+    } else {
+        HINTS
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(hints, theme::dim_style())),
+        chunks[5],
+    );
+
+    if state.switcher.is_some() {
+        render_switcher(frame, area, state);
+    }
+}
+
+fn render_transcript(frame: &mut Frame, area: Rect, state: &mut ChatState) {
+    if state.entries.is_empty() {
+        let banner_rows = BANNER_HEIGHT + 1;
+        if area.height > banner_rows + 2 {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1),
+                    Constraint::Length(banner_rows),
+                    Constraint::Min(1),
+                ])
+                .split(area);
+            frame.render_widget(Banner, rows[1]);
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    "Ask anything. /help lists the keys.",
+                    theme::dim_style(),
+                ))
+                .alignment(ratatui::layout::Alignment::Center),
+                rows[2],
+            );
+        }
+        return;
+    }
+
+    let lines = transcript_lines(state);
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let total = paragraph.line_count(area.width.max(1)) as u16;
+    let max_scroll = total.saturating_sub(area.height);
+    state.scroll = state.scroll.min(max_scroll);
+    let offset = max_scroll - state.scroll;
+    frame.render_widget(paragraph.scroll((offset, 0)), area);
+}
+
+fn transcript_lines(state: &ChatState) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (index, entry) in state.entries.iter().enumerate() {
+        match entry {
+            Entry::User(text) => {
+                lines.push(Line::from(Span::styled("› you", theme::accent_style())));
+                lines.extend(
+                    text.lines()
+                        .map(|l| Line::from(Span::styled(l.to_string(), theme::input_style()))),
+                );
+            }
+            Entry::Assistant(text) => {
+                lines.push(Line::from(Span::styled("◆ zeroclaw", theme::title_style())));
+                lines.extend(markdown::render(text));
+            }
+            Entry::Tool(block) => {
+                lines.extend(tool_lines(block, state.focused_tool == Some(index)));
+            }
+            Entry::Notice { text, warn } => {
+                let style = if *warn {
+                    theme::warn_style()
+                } else {
+                    theme::dim_style()
+                };
+                lines.push(Line::from(Span::styled(text.clone(), style)));
+            }
+        }
+        lines.push(Line::default());
+    }
+    lines
+}
+
+fn tool_lines(block: &ToolBlock, focused: bool) -> Vec<Line<'static>> {
+    let marker = if block.expanded { "▾" } else { "▸" };
+    let status = match &block.output {
+        Some(_) => "✓",
+        None => "…",
+    };
+    let header_style = if focused {
+        theme::selected_style()
+    } else {
+        theme::heading_style()
+    };
+    let mut header = vec![Span::styled(
+        format!("{marker} 🔧 {} {status}", block.name),
+        header_style,
+    )];
+    if !block.expanded {
+        let summary: String = block.args.chars().take(60).collect();
+        header.push(Span::styled(format!("  {summary}"), theme::dim_style()));
+        return vec![Line::from(header)];
+    }
+
+    let mut lines = vec![Line::from(header)];
+    let args = serde_json::from_str::<serde_json::Value>(&block.args)
+        .ok()
+        .and_then(|v| serde_json::to_string_pretty(&v).ok())
+        .unwrap_or_else(|| block.args.clone());
+    lines.extend(
+        args.lines()
+            .map(|l| Line::from(Span::styled(format!("  {l}"), theme::dim_style()))),
+    );
+    if let Some(output) = &block.output {
+        lines.push(Line::from(Span::styled("  → result", theme::dim_style())));
+        let total = output.lines().count();
+        lines.extend(
+            output
+                .lines()
+                .take(TOOL_OUTPUT_MAX_LINES)
+                .map(|l| Line::from(Span::styled(format!("  {l}"), theme::code_style()))),
+        );
+        if total > TOOL_OUTPUT_MAX_LINES {
+            lines.push(Line::from(Span::styled(
+                format!("  … {} more lines", total - TOOL_OUTPUT_MAX_LINES),
+                theme::dim_style().add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
+    lines
+}
+
+fn live_lines(state: &ChatState) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = state
+        .thinking
+        .lines()
+        .map(|l| {
+            Line::from(Span::styled(
+                l.to_string(),
+                theme::dim_style().add_modifier(Modifier::ITALIC),
+            ))
+        })
+        .collect();
+    lines.extend(markdown::render(&state.streaming));
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled("thinking…", theme::dim_style())));
+    }
+    lines
+}
+
+fn render_live(frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::dim_style())
+        .title(Span::styled(" ◆ zeroclaw ", theme::title_style()));
+    let inner = block.inner(area);
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    // Follow the tail: the newest streamed text stays in view.
+    let total = paragraph.line_count(inner.width.max(1)) as u16;
+    let offset = total.saturating_sub(inner.height);
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph.scroll((offset, 0)), inner);
+}
+
+fn render_approval(frame: &mut Frame, area: Rect, state: &ChatState) {
+    let Some(prompt) = &state.approval else {
+        return;
+    };
+    let text = vec![
+        Line::from(vec![
+            Span::styled("🔧 Agent wants to run ", theme::body_style()),
+            Span::styled(prompt.tool_name.clone(), theme::accent_style()),
+        ]),
+        Line::from(Span::styled(
+            prompt.arguments_summary.clone(),
+            theme::dim_style(),
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(text).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme::warn_style())
+                .title(Span::styled(" approval required ", theme::warn_style())),
+        ),
+        area,
+    );
+}
+
+fn render_status(frame: &mut Frame, area: Rect, state: &ChatState) {
+    let session = state
+        .session_name
+        .clone()
+        .unwrap_or_else(|| state.session_key.clone());
+    let activity = if state.busy {
+        Span::styled("● working", theme::accent_style())
+    } else {
+        Span::styled("○ idle", theme::dim_style())
+    };
+    let usage = state.usage;
+    let sep = || Span::styled("  │  ", theme::dim_style());
+    let line = Line::from(vec![
+        activity,
+        sep(),
+        Span::styled(state.model.clone(), theme::heading_style()),
+        sep(),
+        Span::styled(session, theme::body_style()),
+        sep(),
+        Span::styled(
+            format!("↑{} ↓{} tokens", usage.input_tokens, usage.output_tokens),
+            theme::body_style(),
+        ),
+        sep(),
+        Span::styled(format!("${:.4}", usage.cost_usd), theme::body_style()),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn render_switcher(frame: &mut Frame, area: Rect, state: &ChatState) {
+    let Some(switcher) = &state.switcher else {
+        return;
+    };
+    let popup = centered(area, 70, 60);
+    let now = chrono::Utc::now();
+    let mut items = vec![ListItem::new(Line::from(Span::styled(
+        "＋ New session",
+        theme::accent_style(),
+    )))];
+    items.extend(switcher.matches().into_iter().map(|s| {
+        let label = s.name.clone().unwrap_or_else(|| s.key.clone());
+        let mut spans = vec![Span::styled(label, theme::body_style())];
+        if s.key == state.session_key {
+            spans.push(Span::styled(" (current)", theme::accent_style()));
+        }
+        spans.push(Span::styled(
+            format!(
+                "  {} msgs · {}",
+                s.message_count,
+                super::state::relative_age(s.last_activity, now)
+            ),
+            theme::dim_style().add_modifier(Modifier::ITALIC),
+        ));
+        ListItem::new(Line::from(spans))
+    }));
+    let mut list_state = ListState::default();
+    list_state.select(Some(switcher.cursor));
+
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(Span::styled(
+                format!(" Sessions  filter: {} ", switcher.filter),
+                theme::heading_style(),
+            )))
+            .highlight_style(theme::selected_style())
+            .highlight_symbol("› "),
+        popup,
+        &mut list_state,
+    );
+}
+
+fn centered(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn screen(state: &mut ChatState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, state)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn collapsed_tool_block_is_one_row_and_expands() {
+        let mut block = ToolBlock {
+            id: "1".into(),
+            name: "shell".into(),
+            args: r#"{"command":"ls"}"#.into(),
+            output: Some("a\nb".into()),
+            expanded: false,
+        };
+        assert_eq!(tool_lines(&block, false).len(), 1);
+        block.expanded = true;
+        // header + 3 pretty-printed arg lines + result marker + 2 output lines
+        assert_eq!(tool_lines(&block, false).len(), 7);
+    }
+
+    #[test]
+    fn status_bar_shows_model_session_and_usage() {
+        let mut state = ChatState::new("anthropic/claude", "tui_20260101");
+        state.usage.input_tokens = 1200;
+        state.usage.output_tokens = 300;
+        state.usage.cost_usd = 0.0123;
+        state.entries.push(Entry::User("hi".into()));
+        let text = screen(&mut state);
+        assert!(text.contains("anthropic/claude"));
+        assert!(text.contains("tui_20260101"));
+        assert!(text.contains("↑1200 ↓300 tokens"));
+        assert!(text.contains("$0.0123"));
+    }
+
+    #[test]
+    fn transcript_scrolls_from_the_bottom() {
+        let mut state = ChatState::new("m", "k");
+        for i in 0..60 {
+            state.notice(format!("line {i}"), false);
+        }
+        let bottom = screen(&mut state);
+        assert!(bottom.contains("line 59") && !bottom.contains("line 0 "));
+
+        state.scroll = u16::MAX;
+        let top = screen(&mut state);
+        assert!(top.contains("line 0") && !top.contains("line 59"));
+        // Over-scrolling clamps to the top of the transcript.
+        assert!(state.scroll < u16::MAX);
+    }
+}
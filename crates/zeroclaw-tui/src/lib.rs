@@ -1,10 +1,16 @@
-//! Ratatui-backed `OnboardUi` implementation.
+//! Ratatui front-ends.
 //!
-//! The onboard orchestrator lives in `zeroclaw-runtime`; this crate only
-//! provides the TUI drawing/input layer. `RatatuiUi` is the one export.
+//! `RatatuiUi` is the `OnboardUi` implementation; the onboard orchestrator
+//! lives in `zeroclaw-runtime` and this crate only provides the drawing and
+//! input layer. With the `chat` feature, `run_chat` adds the full-screen
+//! agent chat used by `zeroclaw agent`.
 
+#[cfg(feature = "chat")]
+mod chat;
 mod onboarding;
 mod theme;
 mod widgets;
 
+#[cfg(feature = "chat")]
+pub use chat::run_chat;
 pub use onboarding::RatatuiUi;
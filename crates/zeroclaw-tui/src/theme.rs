@@ -9,6 +9,8 @@ const FROST_DIM: Color = Color::Rgb(80, 130, 170);
 const CRAB_ACCENT: Color = Color::Rgb(255, 100, 80);
 const WARN_YELLOW: Color = Color::Rgb(255, 220, 80);
 const SELECTION_BG: Color = Color::Rgb(30, 60, 100);
+#[cfg(feature = "chat")]
+const CODE_BG: Color = Color::Rgb(20, 32, 48);
 
 pub fn title_style() -> Style {
     Style::default().fg(ICY_BLUE).add_modifier(Modifier::BOLD)
@@ -46,3 +48,8 @@ pub fn selected_style() -> Style {
 pub fn input_style() -> Style {
     Style::default().fg(ICY_WHITE)
 }
+
+#[cfg(feature = "chat")]
+pub fn code_style() -> Style {
+    Style::default().fg(ICY_CYAN).bg(CODE_BG)
+}
@@ -77,6 +77,8 @@ pub mod nodes;
 #[cfg(feature = "agent-runtime")]
 pub mod observability;
 #[cfg(feature = "agent-runtime")]
+pub mod outbox;
+#[cfg(feature = "agent-runtime")]
 pub mod peripherals;
 #[cfg(feature = "agent-runtime")]
 pub mod platform;
@@ -316,6 +318,12 @@ pub enum MigrateCommands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Copy channel session history from the local SQLite store into PostgreSQL
+    Sessions {
+        /// Preview the sessions that would be copied without writing any data
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Cron subcommands
@@ -461,6 +469,77 @@ Examples:
     },
 }
 
+/// Outbox subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutboxCommands {
+    /// List outbound messages, newest first
+    List {
+        /// Filter by status (pending, in_flight, delivered, dead)
+        #[arg(long)]
+        status: Option<String>,
+        /// Maximum number of entries to display
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Show a single outbound message, including its last error
+    Show {
+        /// Outbox entry ID
+        id: String,
+    },
+    /// Requeue a failed message for immediate delivery
+    #[command(long_about = "\
+Requeue failed outbound messages for immediate delivery.
+
+Replayed messages get a fresh attempt budget and are picked up by the \
+outbox dispatcher of the running daemon or channel server.
+
+Examples:
+  zeroclaw outbox replay ENTRY_ID
+  zeroclaw outbox replay --all-dead")]
+    Replay {
+        /// Outbox entry ID
+        #[arg(required_unless_present = "all_dead", conflicts_with = "all_dead")]
+        id: Option<String>,
+        /// Requeue every dead-lettered message
+        #[arg(long)]
+        all_dead: bool,
+    },
+    /// Delete an outbound message without delivering it
+    Remove {
+        /// Outbox entry ID
+        id: String,
+    },
+}
+
+/// Hands subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HandsCommands {
+    /// List hands with their schedule and next run
+    List,
+    /// Run a hand now, outside its schedule
+    #[command(long_about = "\
+Run a hand now, outside its schedule.
+
+The run uses the hand's tool allowlist and model override, is recorded \
+in its rolling context like a scheduled run, and delivers its report \
+when the hand has a delivery target.
+
+Examples:
+  zeroclaw hands run market-scanner")]
+    Run {
+        /// Hand name
+        name: String,
+    },
+    /// Show a hand's recent runs and what it has learned
+    History {
+        /// Hand name
+        name: String,
+        /// Maximum number of runs to display
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+}
+
 /// Memory management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryCommands {
@@ -627,4 +706,19 @@ pub enum SopCommands {
         /// Name of the SOP to show
         name: String,
     },
+    /// Dry-run an SOP against an event and print the would-be execution trace
+    Simulate {
+        /// Name of the SOP to simulate
+        name: String,
+        /// JSON file with the event (`source`, `topic`, `payload`) and optional
+        /// step stubs; a manual event without payload if omitted
+        #[arg(long)]
+        event: Option<std::path::PathBuf>,
+        /// Pass every approval and checkpoint without prompting
+        #[arg(long)]
+        yes: bool,
+        /// Print the trace as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
@@ -155,6 +155,8 @@ mod doctor;
 #[cfg(feature = "gateway")]
 mod gateway;
 #[cfg(feature = "agent-runtime")]
+mod hands;
+#[cfg(feature = "agent-runtime")]
 mod hardware;
 #[cfg(feature = "agent-runtime")]
 mod health;
@@ -176,6 +178,8 @@ mod multimodal;
 #[cfg(feature = "agent-runtime")]
 mod observability;
 #[cfg(feature = "agent-runtime")]
+mod outbox;
+#[cfg(feature = "agent-runtime")]
 mod peripherals;
 #[cfg(feature = "agent-runtime")]
 mod platform;
@@ -189,6 +193,8 @@ mod security;
 #[cfg(feature = "agent-runtime")]
 mod service;
 #[cfg(feature = "agent-runtime")]
+mod sessions;
+#[cfg(feature = "agent-runtime")]
 mod skillforge;
 #[cfg(feature = "agent-runtime")]
 mod skills;
@@ -209,8 +215,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, GatewayCommands, HardwareCommands, IntegrationCommands,
-    MigrateCommands, PeripheralCommands, ServiceCommands, SkillCommands, SopCommands,
+    ChannelCommands, CronCommands, GatewayCommands, HandsCommands, HardwareCommands,
+    IntegrationCommands, MigrateCommands, OutboxCommands, PeripheralCommands, ServiceCommands,
+    SkillCommands, SopCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -318,10 +325,13 @@ enum Commands {
 Start the AI agent loop.
 
 Launches an interactive chat session with the configured AI provider. \
-Use --message for single-shot queries without entering interactive mode.
+On a terminal this opens the full-screen chat UI; --cli keeps the \
+line-based REPL. Use --message for single-shot queries without entering \
+interactive mode.
 
 Examples:
   zeroclaw agent                              # interactive session
+  zeroclaw agent --cli                        # line-based REPL
   zeroclaw agent -m \"Summarize today's logs\"  # single message
   zeroclaw agent -p anthropic --model claude-sonnet-4-20250514
   zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0")]
@@ -349,6 +359,10 @@ Examples:
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Use the line-based REPL instead of the full-screen chat UI
+        #[arg(long)]
+        cli: bool,
     },
 
     /// Start/manage the gateway server (webhooks, websockets)
@@ -375,7 +389,7 @@ Launches a JSON-RPC 2.0 server on stdin/stdout for IDE and tool \
 integration. Supports session management and streaming agent \
 responses as notifications.
 
-Methods: initialize, session/new, session/prompt, session/stop.
+Methods: initialize, session/new, session/prompt, session/fork, session/stop.
 
 Examples:
   zeroclaw acp                        # start ACP server
@@ -493,6 +507,42 @@ Examples:
         cron_command: CronCommands,
     },
 
+    /// Inspect and replay outbound message deliveries
+    #[command(long_about = "\
+Inspect and replay outbound message deliveries.
+
+Every outbound channel message is recorded in a durable outbox before it \
+is sent. Failed sends are retried with exponential backoff; messages that \
+exhaust their attempts land in the dead-letter queue.
+
+Examples:
+  zeroclaw outbox list --status dead
+  zeroclaw outbox show ENTRY_ID
+  zeroclaw outbox replay ENTRY_ID
+  zeroclaw outbox replay --all-dead")]
+    Outbox {
+        #[command(subcommand)]
+        outbox_command: OutboxCommands,
+    },
+
+    /// Run and inspect scheduled autonomous agents
+    #[command(long_about = "\
+Run and inspect hands: scheduled autonomous agents.
+
+Each hand is a TOML file in the hands directory (default: hands/ next to \
+config.toml) with a prompt, a cron schedule, an optional tool allowlist \
+and model override, and a delivery target. The daemon runs active hands \
+on their schedule and keeps a rolling context of findings per hand.
+
+Examples:
+  zeroclaw hands list
+  zeroclaw hands run market-scanner
+  zeroclaw hands history market-scanner --limit 5")]
+    Hands {
+        #[command(subcommand)]
+        hands_command: HandsCommands,
+    },
+
     /// Manage provider model catalogs
     Models {
         #[command(subcommand)]
@@ -607,6 +657,25 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// Export, import, and list conversation sessions
+    #[command(long_about = "\
+Export, import, and list conversation sessions.
+
+Sessions live in the backend selected by [channels].session_backend. \
+Markdown and HTML exports are readable transcripts with collapsible \
+tool blocks, timestamps, and model/cost annotations; secrets are \
+always scrubbed. JSON exports are lossless and can be re-imported.
+
+Examples:
+  zeroclaw session list
+  zeroclaw session export telegram_alice --format html -o alice.html
+  zeroclaw session export gw_abc123 --format json > backup.json
+  zeroclaw session import backup.json --key restored")]
+    Session {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -1151,6 +1220,38 @@ enum MemoryCommands {
     Reindex,
 }
 
+#[derive(Subcommand, Debug)]
+enum SessionCommands {
+    /// List stored sessions, most recently active first
+    List {
+        /// Maximum number of sessions to display
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Export a session transcript as Markdown, HTML, or JSON
+    Export {
+        /// Session key to export
+        key: String,
+        /// Output format: md, html, or json
+        #[arg(long, short, default_value = "md")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Scrub secrets from JSON exports (Markdown and HTML are always scrubbed)
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Import a JSON transcript as a new session
+    Import {
+        /// Path to a JSON transcript produced by `session export --format json`
+        file: PathBuf,
+        /// Store under this key instead of the one recorded in the transcript
+        #[arg(long)]
+        key: Option<String>,
+    },
+}
+
 fn apply_i18n_to_command(cmd: clap::Command) -> clap::Command {
     #[cfg(feature = "agent-runtime")]
     {
@@ -1530,6 +1631,7 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
+            cli,
         } => {
             let final_temperature = temperature.unwrap_or_else(|| {
                 config
@@ -1539,6 +1641,33 @@ async fn main() -> Result<()> {
                     .unwrap_or(0.7)
             });
 
+            // Interactive sessions on a terminal get the full-screen chat;
+            // single-shot, session-file, peripheral and piped runs keep the
+            // REPL path.
+            #[cfg(feature = "tui-chat")]
+            {
+                use std::io::IsTerminal;
+                if !cli
+                    && message.is_none()
+                    && session_state_file.is_none()
+                    && peripheral.is_empty()
+                    && std::io::stdin().is_terminal()
+                    && std::io::stdout().is_terminal()
+                {
+                    let mut config = config;
+                    if let Some(p) = &provider {
+                        config.providers.fallback = Some(p.clone());
+                    }
+                    if let Some(m) = &model {
+                        config.ensure_fallback_provider().model = Some(m.clone());
+                    }
+                    config.ensure_fallback_provider().temperature = Some(final_temperature);
+                    return Box::pin(zeroclaw_tui::run_chat(config)).await;
+                }
+            }
+            #[cfg(not(feature = "tui-chat"))]
+            let _ = cli;
+
             // Wire CLI channel for interactive mode
             zeroclaw_runtime::agent::loop_::register_cli_channel_fn(Box::new(|| {
                 Box::new(zeroclaw_channels::cli::CliChannel::new())
@@ -1806,18 +1935,12 @@ async fn main() -> Result<()> {
                         use std::sync::{Arc, Mutex};
                         use zeroclaw_config::schema::SopConfig;
                         use zeroclaw_memory::NoneMemory;
-                        use zeroclaw_runtime::sop::{SopAuditLogger, SopEngine};
-                        let sop_config = current_config.sop.clone();
-                        let workspace_dir = current_config.workspace_dir.clone();
+                        use zeroclaw_runtime::sop::{SopAuditLogger, SopEngine, shared_engine};
+                        let sop_config = current_config.clone();
                         move |mqtt_config| {
-                            let engine = if sop_config.sops_dir.is_some() {
-                                let mut e = SopEngine::new(sop_config.clone());
-                                e.reload(&workspace_dir);
-                                e
-                            } else {
-                                SopEngine::new(SopConfig::default())
-                            };
-                            let engine = Arc::new(Mutex::new(engine));
+                            let engine = shared_engine(&sop_config).unwrap_or_else(|| {
+                                Arc::new(Mutex::new(SopEngine::new(SopConfig::default())))
+                            });
                             let audit = Arc::new(SopAuditLogger::new(Arc::new(NoneMemory)));
                             Box::pin(async move {
                                 zeroclaw_channels::orchestrator::mqtt::run_mqtt_sop_listener(
@@ -2013,6 +2136,12 @@ async fn main() -> Result<()> {
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
+        Commands::Outbox { outbox_command } => outbox::handle_command(outbox_command, &config),
+
+        Commands::Hands { hands_command } => {
+            Box::pin(hands::handle_command(hands_command, &config)).await
+        }
+
         Commands::Models { model_command } => {
             let provider = match &model_command {
                 ModelCommands::Refresh { provider, .. } | ModelCommands::List { provider } => {
@@ -2107,6 +2236,8 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Session { session_command } => sessions::handle_command(session_command, &config),
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -4244,6 +4375,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn agent_command_parses_cli_flag() {
+        let cli = Cli::try_parse_from(["zeroclaw", "agent", "--cli"])
+            .expect("agent command with --cli should parse");
+
+        match cli.command {
+            Commands::Agent { cli, .. } => assert!(cli),
+            other => panic!("expected agent command, got {other:?}"),
+        }
+    }
+
     #[test]
     #[cfg(feature = "agent-runtime")]
     fn agent_fallback_uses_config_default_temperature() {
@@ -14,5 +14,6 @@ pub async fn handle_command(command: crate::MigrateCommands, config: &Config) ->
         crate::MigrateCommands::Openclaw { source, dry_run } => {
             migrate_openclaw_memory(config, source, dry_run).await
         }
+        crate::MigrateCommands::Sessions { dry_run } => migrate_sessions(config, dry_run).await,
     }
 }
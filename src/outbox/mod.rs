@@ -0,0 +1,80 @@
+pub use zeroclaw_runtime::outbox::*;
+
+use crate::config::Config;
+use anyhow::Result;
+
+pub fn handle_command(command: crate::OutboxCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::OutboxCommands::List { status, limit } => {
+            let status = status
+                .as_deref()
+                .map(OutboxStatus::try_from)
+                .transpose()
+                .map_err(anyhow::Error::msg)?;
+            let entries = list_entries(config, status, limit)?;
+            if entries.is_empty() {
+                println!("Outbox is empty.");
+                return Ok(());
+            }
+
+            println!("📮 Outbox entries ({}):", entries.len());
+            for entry in entries {
+                println!(
+                    "- {} | {} | {} -> {} | attempts={} | created={}",
+                    entry.id,
+                    entry.status.as_str(),
+                    entry.channel,
+                    entry.recipient,
+                    entry.attempts,
+                    entry.created_at.to_rfc3339(),
+                );
+                if entry.status == OutboxStatus::Pending {
+                    println!("    next attempt: {}", entry.next_attempt_at.to_rfc3339());
+                }
+                if let Some(error) = &entry.last_error {
+                    println!("    last error: {error}");
+                }
+            }
+            Ok(())
+        }
+        crate::OutboxCommands::Show { id } => {
+            let entry = get_entry(config, &id)?;
+            println!("ID:           {}", entry.id);
+            println!("Status:       {}", entry.status.as_str());
+            println!("Channel:      {}", entry.channel);
+            println!("Recipient:    {}", entry.recipient);
+            if let Some(thread) = &entry.thread_ts {
+                println!("Thread:       {thread}");
+            }
+            if let Some(subject) = &entry.subject {
+                println!("Subject:      {subject}");
+            }
+            println!("Attempts:     {}", entry.attempts);
+            println!("Created:      {}", entry.created_at.to_rfc3339());
+            println!("Next attempt: {}", entry.next_attempt_at.to_rfc3339());
+            if let Some(delivered) = entry.delivered_at {
+                println!("Delivered:    {}", delivered.to_rfc3339());
+            }
+            if let Some(error) = &entry.last_error {
+                println!("Last error:   {error}");
+            }
+            println!("\n{}", entry.content);
+            Ok(())
+        }
+        crate::OutboxCommands::Replay { id, all_dead } => {
+            if all_dead {
+                let count = replay_dead_letters(config)?;
+                println!("✅ Requeued {count} dead-lettered message(s)");
+            } else if let Some(id) = id {
+                let entry = replay_entry(config, &id)?;
+                println!("✅ Requeued {} for {}", entry.id, entry.channel);
+            }
+            Ok(())
+        }
+        crate::OutboxCommands::Remove { id } => {
+            remove_entry(config, &id)?;
+            println!("✅ Removed outbox entry {id}");
+            Ok(())
+        }
+    }
+}
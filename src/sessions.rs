@@ -0,0 +1,123 @@
+use crate::SessionCommands;
+use crate::config::Config;
+use anyhow::{Context, Result, bail};
+use console::style;
+use std::sync::Arc;
+use zeroclaw_config::cost::CostTracker;
+use zeroclaw_infra::session_backend::SessionBackend;
+use zeroclaw_runtime::session_export::{self, ExportFormat, Transcript};
+
+/// Handle `zeroclaw session <subcommand>` CLI commands.
+pub fn handle_command(command: SessionCommands, config: &Config) -> Result<()> {
+    let backend = open_backend(config)?;
+    match command {
+        SessionCommands::List { limit } => handle_list(backend.as_ref(), limit),
+        SessionCommands::Export {
+            key,
+            format,
+            output,
+            redact,
+        } => handle_export(config, backend.as_ref(), &key, &format, output, redact),
+        SessionCommands::Import { file, key } => {
+            handle_import(backend.as_ref(), &file, key.as_deref())
+        }
+    }
+}
+
+fn open_backend(config: &Config) -> Result<Arc<dyn SessionBackend>> {
+    zeroclaw_infra::make_session_backend(
+        &config.workspace_dir,
+        &config.channels.session_backend,
+        zeroclaw_infra::PostgresTarget {
+            db_url: config.storage.provider.config.db_url.as_deref(),
+            schema: &config.storage.provider.config.schema,
+            connect_timeout_secs: config.storage.provider.config.connect_timeout_secs,
+        },
+    )
+    .with_context(|| {
+        format!(
+            "Failed to open '{}' session backend",
+            config.channels.session_backend
+        )
+    })
+}
+
+fn handle_list(backend: &dyn SessionBackend, limit: usize) -> Result<()> {
+    let mut sessions = backend.list_sessions_with_metadata();
+    if sessions.is_empty() {
+        println!("No sessions stored.");
+        return Ok(());
+    }
+    sessions.sort_by_key(|m| std::cmp::Reverse(m.last_activity));
+    for meta in sessions.iter().take(limit) {
+        let name = meta
+            .name
+            .as_deref()
+            .map(|n| format!(" ({n})"))
+            .unwrap_or_default();
+        println!(
+            "{}{}  {} messages  last active {}",
+            style(&meta.key).cyan(),
+            name,
+            meta.message_count,
+            meta.last_activity.format("%Y-%m-%d %H:%M UTC"),
+        );
+    }
+    if sessions.len() > limit {
+        println!("… {} more", sessions.len() - limit);
+    }
+    Ok(())
+}
+
+fn handle_export(
+    config: &Config,
+    backend: &dyn SessionBackend,
+    key: &str,
+    format: &str,
+    output: Option<std::path::PathBuf>,
+    redact: bool,
+) -> Result<()> {
+    let format: ExportFormat = format.parse()?;
+    let tracker = CostTracker::get_or_init_global(config.cost.clone(), &config.workspace_dir);
+    let Some(mut transcript) = session_export::export_session(backend, key, tracker.as_deref())
+    else {
+        bail!("Session '{key}' not found");
+    };
+    if redact {
+        transcript = transcript.redacted();
+    }
+    let rendered = session_export::render(&transcript, format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "{} Exported {} messages from '{key}' to {}",
+                style("✓").green().bold(),
+                transcript.messages.len(),
+                path.display()
+            );
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn handle_import(
+    backend: &dyn SessionBackend,
+    file: &std::path::Path,
+    key: Option<&str>,
+) -> Result<()> {
+    let raw = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let transcript: Transcript = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not a JSON session transcript", file.display()))?;
+    let stored_key = session_export::import_transcript(backend, &transcript, key)?;
+    println!(
+        "{} Imported {} messages as session '{stored_key}'",
+        style("✓").green().bold(),
+        transcript.messages.len()
+    );
+    Ok(())
+}
@@ -1,7 +1,7 @@
 #[allow(unused_imports)]
 pub use zeroclaw_runtime::sop::*;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 pub fn handle_command(command: crate::SopCommands, config: &crate::config::Config) -> Result<()> {
     let workspace_dir = &config.workspace_dir;
@@ -122,11 +122,68 @@ pub fn handle_command(command: crate::SopCommands, config: &crate::config::Confi
                     if !step.suggested_tools.is_empty() {
                         println!("       Tools: {}", step.suggested_tools.join(", "));
                     }
+                    if let Some(id) = &step.id {
+                        println!("       Id: {id}");
+                    }
+                    if step.kind == SopStepKind::Parallel {
+                        println!(
+                            "       Parallel ({}): {}",
+                            step.join,
+                            step.branches.join(", ")
+                        );
+                    }
+                    for transition in &step.next {
+                        match &transition.when {
+                            Some(when) => println!("       Next: {} if {when}", transition.goto),
+                            None => println!("       Next: {}", transition.goto),
+                        }
+                    }
+                    if let Some(target) = &step.on_failure {
+                        println!("       On failure: {target}");
+                    }
+                    if step.retries > 0 {
+                        println!("       Retries: {}", step.retries);
+                    }
                 }
             }
             println!();
             Ok(())
         }
+        crate::SopCommands::Simulate {
+            name,
+            event,
+            yes,
+            json,
+        } => {
+            let sop = sops
+                .iter()
+                .find(|s| s.name == name)
+                .ok_or_else(|| anyhow::anyhow!("SOP not found: {name}"))?;
+            let input: simulate::SimulationInput = match &event {
+                Some(path) => {
+                    let raw = std::fs::read_to_string(path)
+                        .with_context(|| format!("Failed to read event file {}", path.display()))?;
+                    serde_json::from_str(&raw)
+                        .with_context(|| format!("Invalid event file {}", path.display()))?
+                }
+                None => serde_json::from_str("{}")?,
+            };
+
+            let trace = simulate::simulate(sop, input.event(), &input.steps, &mut |step| {
+                yes || dialoguer::Confirm::new()
+                    .with_prompt(format!("  Approve step {} '{}'?", step.number, step.title))
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false)
+            })?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&trace)?);
+            } else {
+                println!("{trace}");
+            }
+            Ok(())
+        }
     }
 }
 
@@ -363,6 +420,13 @@ type = "manual"
                 requires_confirmation: false,
                 kind: SopStepKind::default(),
                 schema: None,
+                id: None,
+                next: Vec::new(),
+                on_failure: None,
+                branches: Vec::new(),
+                join: SopJoin::default(),
+                retries: 0,
+                max_visits: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
@@ -519,4 +583,206 @@ type = "manual"
         // Default kind should be Execute
         assert_eq!(steps[2].kind, SopStepKind::Execute);
     }
+
+    #[test]
+    fn parse_steps_with_routing() {
+        let md = r#"## Steps
+
+1. **Triage** — Classify the alert.
+   - id: triage
+   - next: page if $.severity == "high"
+   - next: end
+
+2. **Gather** — Collect diagnostics.
+   - parallel: logs, metrics
+   - join: any
+   - retries: 2
+   - max_visits: 3
+   - on_failure: page
+
+3. **Logs** — Pull logs.
+   - id: logs
+
+4. **Metrics** — Pull metrics.
+   - id: metrics
+
+5. **Page** — Page on-call.
+   - id: page
+"#;
+        let steps = parse_steps(md);
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[0].id.as_deref(), Some("triage"));
+        assert_eq!(
+            steps[0].next,
+            vec![
+                StepTransition {
+                    when: Some(r#"$.severity == "high""#.into()),
+                    goto: "page".into(),
+                },
+                StepTransition {
+                    when: None,
+                    goto: "end".into(),
+                },
+            ]
+        );
+        assert_eq!(steps[1].kind, SopStepKind::Parallel);
+        assert_eq!(steps[1].branches, vec!["logs", "metrics"]);
+        assert_eq!(steps[1].join, SopJoin::Any);
+        assert_eq!(steps[1].retries, 2);
+        assert_eq!(steps[1].max_visits, Some(3));
+        assert_eq!(steps[0].max_visits, None);
+        assert_eq!(steps[1].on_failure.as_deref(), Some("page"));
+        assert_eq!(steps[1].body, "Collect diagnostics.");
+    }
+
+    fn write_sop(root: &Path, toml: &str, md: &str) -> PathBuf {
+        let sop_dir = root.join("flow-sop");
+        fs::create_dir_all(&sop_dir).unwrap();
+        fs::write(sop_dir.join("SOP.toml"), toml).unwrap();
+        fs::write(sop_dir.join("SOP.md"), md).unwrap();
+        sop_dir
+    }
+
+    const FLOW_MD: &str = r#"## Steps
+
+1. **Check** — Check the service.
+   - id: check
+
+2. **Restart** — Restart it.
+   - id: restart
+"#;
+
+    #[test]
+    fn load_sop_applies_toml_step_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        write_sop(
+            dir.path(),
+            r#"
+[sop]
+name = "flow-sop"
+description = "Routing from SOP.toml"
+
+[[steps]]
+step = "check"
+retries = 1
+next = [{ when = "$.healthy == true", goto = "end" }]
+
+[steps.schema.output]
+type = "object"
+required = ["healthy"]
+
+[[steps]]
+step = 2
+on_failure = "check"
+"#,
+            FLOW_MD,
+        );
+
+        let sops = load_sops_from_directory(dir.path(), SopExecutionMode::Auto);
+        assert_eq!(sops.len(), 1);
+        let steps = &sops[0].steps;
+        assert_eq!(steps[0].retries, 1);
+        assert_eq!(steps[0].next[0].goto, "end");
+        let schema = steps[0].schema.as_ref().unwrap();
+        assert!(
+            schema
+                .validate_output(&serde_json::json!({"healthy": true}))
+                .is_ok()
+        );
+        assert!(schema.validate_output(&serde_json::json!({})).is_err());
+        assert_eq!(steps[1].on_failure.as_deref(), Some("check"));
+    }
+
+    #[test]
+    fn load_sop_rejects_unknown_transition_target() {
+        let dir = tempfile::tempdir().unwrap();
+        write_sop(
+            dir.path(),
+            r#"
+[sop]
+name = "flow-sop"
+description = "Broken routing"
+"#,
+            &format!("{FLOW_MD}   - next: nowhere\n"),
+        );
+        assert!(load_sops_from_directory(dir.path(), SopExecutionMode::Auto).is_empty());
+    }
+
+    #[test]
+    fn validate_flow_rejects_bad_parallel_groups() {
+        let md = r#"## Steps
+
+1. **Fan** — Fan out.
+   - parallel: 1, two
+
+2. **Two** — Second.
+   - id: two
+"#;
+        let mut sop = Sop {
+            name: "fan".into(),
+            description: "fan".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![SopTrigger::Manual],
+            steps: parse_steps(md),
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            location: None,
+            deterministic: false,
+        };
+        let err = validate_flow(&sop).unwrap_err().to_string();
+        assert!(err.contains("itself"), "{err}");
+
+        sop.steps[0].branches = vec!["two".into()];
+        assert!(validate_flow(&sop).is_ok());
+
+        sop.steps[1].kind = SopStepKind::Checkpoint;
+        assert!(validate_flow(&sop).is_err());
+    }
+
+    #[test]
+    fn load_sop_rejects_malformed_conditions() {
+        let dir = tempfile::tempdir().unwrap();
+        write_sop(
+            dir.path(),
+            r#"
+[sop]
+name = "flow-sop"
+description = "Broken trigger condition"
+
+[[triggers]]
+type = "mqtt"
+topic = "plant/temp"
+condition = "$.temp > 80 &&"
+"#,
+            FLOW_MD,
+        );
+        assert!(load_sops_from_directory(dir.path(), SopExecutionMode::Auto).is_empty());
+
+        let mut sop = Sop {
+            name: "cond".into(),
+            description: "cond".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![SopTrigger::Mqtt {
+                topic: "plant/temp".into(),
+                condition: Some(r#"$.temp > 80 && $.sensor in ["a", "b"]"#.into()),
+            }],
+            steps: parse_steps(FLOW_MD),
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            location: None,
+            deterministic: false,
+        };
+        assert!(validate_conditions(&sop).is_ok());
+
+        sop.steps[0].next.push(StepTransition {
+            when: Some("matches($.msg, \"[\")".into()),
+            goto: "end".into(),
+        });
+        let err = validate_conditions(&sop).unwrap_err().to_string();
+        assert!(err.starts_with("Step 1 condition"), "{err}");
+    }
 }